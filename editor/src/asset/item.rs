@@ -176,6 +176,8 @@ impl AssetItem {
             }
         } else if self.path.extension().is_some_and(|ext| ext == "blueprint") {
             sender.send(Message::OpenBlueprintEditor(self.path.clone()));
+        } else if self.path.extension().is_some_and(|ext| ext == "behavior") {
+            sender.send(Message::OpenBehaviorEditor(self.path.clone()));
         } else if self.path.is_dir() {
             sender.send(Message::SetAssetBrowserCurrentDir(self.path.clone()));
         } else {
@@ -20,7 +20,7 @@
 
 use crate::message::MessageSender;
 use crate::{
-    asset::{self, item::AssetItem},
+    asset::{self, item::AssetItem, reference::ReferenceIndexCache},
     fyrox::{
         asset::manager::ResourceManager,
         core::{
@@ -276,6 +276,7 @@ pub struct AssetItemContextMenu {
     pub rename: Handle<UiNode>,
     pub placement_target: Handle<UiNode>,
     pub dependencies: Handle<UiNode>,
+    pub find_references: Handle<UiNode>,
     pub delete_confirmation_dialog: Handle<UiNode>,
     pub path_to_delete: PathBuf,
     pub reload: Handle<UiNode>,
@@ -296,6 +297,7 @@ impl AssetItemContextMenu {
         let copy_path = item("Copy Full Path", ctx);
         let copy_file_name = item("Copy File Name", ctx);
         let dependencies = item("Dependencies", ctx);
+        let find_references = item("Find References", ctx);
         let rename = item("Rename", ctx);
         let reload = item("Reload", ctx);
 
@@ -310,6 +312,7 @@ impl AssetItemContextMenu {
                         delete,
                         show_in_explorer,
                         dependencies,
+                        find_references,
                         rename,
                         reload,
                     ]))
@@ -330,6 +333,7 @@ impl AssetItemContextMenu {
             placement_target: Default::default(),
             copy_file_name,
             dependencies,
+            find_references,
             delete_confirmation_dialog: Default::default(),
             path_to_delete: Default::default(),
             rename,
@@ -359,6 +363,7 @@ impl AssetItemContextMenu {
             item.path.canonicalize().ok() == Some(resource_manager.registry_folder());
         let can_be_deleted = !is_built_in && !is_registry_folder;
         ui.send(self.delete, WidgetMessage::Enabled(can_be_deleted));
+        ui.send(self.find_references, WidgetMessage::Enabled(!is_built_in));
         ui.send(self.reload, WidgetMessage::Enabled(is_file));
     }
 
@@ -367,6 +372,7 @@ impl AssetItemContextMenu {
         message: &UiMessage,
         sender: &MessageSender,
         engine: &mut Engine,
+        reference_index: &mut ReferenceIndexCache,
     ) -> bool {
         let ui = engine.user_interfaces.first_mut();
         if let Some(PopupMessage::Placement(Placement::Cursor(target))) = message.data() {
@@ -376,17 +382,38 @@ impl AssetItemContextMenu {
         } else if let Some(MenuItemMessage::Click) = message.data() {
             if let Some(item) = ui.try_get_mut_of_type::<AssetItem>(self.placement_target) {
                 if message.destination() == self.delete {
-                    let text = format!(
+                    let mut text = format!(
                         "Do you really want to delete {} asset? This \
                     is irreversible operation!",
                         item.path.display()
                     );
 
+                    let referencers =
+                        reference_index.referencers_of(&item.path, &engine.resource_manager);
+                    if !referencers.is_empty() {
+                        text += &format!(
+                            "\n\nWARNING: it is used by {} other asset(s), they will \
+                            have broken references:",
+                            referencers.len()
+                        );
+                        for path in referencers.iter().take(5) {
+                            text += &format!("\n{}", path.display());
+                        }
+                        if referencers.len() > 5 {
+                            text += &format!("\n...and {} more", referencers.len() - 5);
+                        }
+                    }
+
                     self.path_to_delete = item.path.clone();
 
+                    let (width, height) = if referencers.is_empty() {
+                        (250.0, 100.0)
+                    } else {
+                        (350.0, 220.0)
+                    };
                     self.delete_confirmation_dialog = MessageBoxBuilder::new(
                         WindowBuilder::new(
-                            WidgetBuilder::new().with_width(250.0).with_height(100.0),
+                            WidgetBuilder::new().with_width(width).with_height(height),
                         )
                         .open(false)
                         .with_remove_on_close(true)
@@ -28,6 +28,7 @@ use crate::{
             cache::{AssetPreviewCache, IconRequest},
             AssetPreviewGeneratorsCollection,
         },
+        reference::{ReferenceIndexCache, ReferenceViewer},
         selection::AssetSelection,
     },
     fyrox::{
@@ -90,10 +91,11 @@ mod dependency;
 pub mod item;
 pub mod menu;
 pub mod preview;
+mod reference;
 mod selection;
 pub mod selector;
 
-fn show_in_explorer<P: AsRef<Path>>(path: P) {
+pub(crate) fn show_in_explorer<P: AsRef<Path>>(path: P) {
     // opener crate is bugged on Windows, so using explorer's command directly.
     #[cfg(target_os = "windows")]
     {
@@ -186,6 +188,8 @@ pub struct AssetBrowser {
     current_path: PathBuf,
     watcher: Option<RecommendedWatcher>,
     dependency_viewer: DependencyViewer,
+    reference_viewer: ReferenceViewer,
+    reference_index: ReferenceIndexCache,
     resource_creator: Option<ResourceCreator>,
     preview_cache: AssetPreviewCache,
     pub preview_sender: Sender<IconRequest>,
@@ -468,6 +472,7 @@ impl AssetBrowser {
         let context_menu = AssetItemContextMenu::new(ctx);
 
         let dependency_viewer = DependencyViewer::new(ctx);
+        let reference_viewer = ReferenceViewer::new(ctx);
 
         let (preview_sender, preview_receiver) = mpsc::channel();
 
@@ -477,6 +482,8 @@ impl AssetBrowser {
 
         Self {
             dependency_viewer,
+            reference_viewer,
+            reference_index: Default::default(),
             window,
             docking_manager,
             content_panel,
@@ -751,12 +758,17 @@ impl AssetBrowser {
         }
         if self
             .context_menu
-            .handle_ui_message(message, &sender, engine)
+            .handle_ui_message(message, &sender, engine, &mut self.reference_index)
         {
             self.schedule_refresh();
         }
         self.dependency_viewer
             .handle_ui_message(message, engine.user_interfaces.first_mut());
+        self.reference_viewer.handle_ui_message(
+            message,
+            engine.user_interfaces.first_mut(),
+            &sender,
+        );
         if let Some(resource_creator) = self.resource_creator.as_mut() {
             let asset_added = resource_creator.handle_ui_message(
                 message,
@@ -892,6 +904,21 @@ impl AssetBrowser {
                         );
                     }
                 }
+            } else if message.destination() == self.context_menu.find_references {
+                if let Some(path) = engine
+                    .user_interfaces
+                    .first_mut()
+                    .try_get_node(self.context_menu.placement_target)
+                    .and_then(|n| n.cast::<AssetItem>())
+                    .map(|item| item.path.clone())
+                {
+                    self.reference_viewer.show_references(
+                        &path,
+                        &mut self.reference_index,
+                        &engine.resource_manager,
+                        engine.user_interfaces.first_mut(),
+                    );
+                }
             }
         } else if let Some(WindowMessage::Close) = message.data() {
             if let Some(resource_creator) = self.resource_creator.as_ref() {
@@ -1055,6 +1082,8 @@ impl AssetBrowser {
             inspector_addon.preview.update(engine);
         }
         if self.need_refresh.load(Ordering::Relaxed) {
+            // Project files have changed, so the references could be different now.
+            self.reference_index.invalidate();
             self.refresh(
                 engine.user_interfaces.first_mut(),
                 &engine.resource_manager,
@@ -1064,6 +1093,14 @@ impl AssetBrowser {
         }
     }
 
+    pub fn show_unused_assets(&mut self, engine: &mut Engine) {
+        self.reference_viewer.show_unused_assets(
+            &mut self.reference_index,
+            &engine.resource_manager,
+            engine.user_interfaces.first_mut(),
+        );
+    }
+
     pub fn on_mode_changed(&mut self, ui: &UserInterface, mode: &Mode) {
         ui.send(
             window_content(self.window, ui),
@@ -0,0 +1,298 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Reference search and unused-asset report, both backed by a project-wide
+//! [`ResourceReferenceIndex`].
+
+use crate::{
+    fyrox::{
+        asset::{graph::ResourceReferenceIndex, io::normalize_path, manager::ResourceManager},
+        core::{futures::executor::block_on, log::Log, pool::Handle},
+        gui::{
+            button::{ButtonBuilder, ButtonMessage},
+            copypasta::ClipboardProvider,
+            formatted_text::WrapMode,
+            grid::{Column, GridBuilder, Row},
+            list_view::{ListViewBuilder, ListViewMessage},
+            message::UiMessage,
+            scroll_viewer::ScrollViewerBuilder,
+            stack_panel::StackPanelBuilder,
+            text::{TextBuilder, TextMessage},
+            widget::WidgetBuilder,
+            window::{WindowAlignment, WindowBuilder, WindowMessage, WindowTitle},
+            BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+            VerticalAlignment,
+        },
+    },
+    message::MessageSender,
+    Message,
+};
+use std::path::{Path, PathBuf};
+
+/// Lazily built reference index. Building the index is expensive, so it is kept until the
+/// project files change.
+#[derive(Default)]
+pub struct ReferenceIndexCache {
+    index: Option<ResourceReferenceIndex>,
+}
+
+impl ReferenceIndexCache {
+    pub fn get(&mut self, resource_manager: &ResourceManager) -> &ResourceReferenceIndex {
+        self.index.get_or_insert_with(|| {
+            Log::info("Building the index of asset references...");
+            let index = block_on(ResourceReferenceIndex::build(resource_manager));
+            Log::info(format!(
+                "The index of asset references is built, {} assets were indexed.",
+                index.paths().len()
+            ));
+            index
+        })
+    }
+
+    pub fn invalidate(&mut self) {
+        self.index = None;
+    }
+
+    /// Returns the paths of the assets that refer to the asset at the given path. If the path is a
+    /// folder, then the references to every asset in it from the assets outside it are returned.
+    pub fn referencers_of(
+        &mut self,
+        path: &Path,
+        resource_manager: &ResourceManager,
+    ) -> Vec<PathBuf> {
+        let Ok(path) = normalize_path(path) else {
+            return Vec::new();
+        };
+        let index = self.get(resource_manager);
+        let targets = index
+            .paths()
+            .iter()
+            .filter(|(_, asset_path)| {
+                normalize_path(asset_path).is_ok_and(|asset_path| asset_path.starts_with(&path))
+            })
+            .map(|(uuid, _)| *uuid)
+            .collect::<Vec<_>>();
+        let mut referencers = targets
+            .iter()
+            .flat_map(|target| index.referencers(*target))
+            .filter(|referencer| !targets.contains(referencer))
+            .filter_map(|referencer| index.path(referencer).map(|path| path.to_path_buf()))
+            .collect::<Vec<_>>();
+        referencers.sort();
+        referencers.dedup();
+        referencers
+    }
+}
+
+pub struct ReferenceViewer {
+    pub window: Handle<UiNode>,
+    description: Handle<UiNode>,
+    list: Handle<UiNode>,
+    copy_to_clipboard: Handle<UiNode>,
+    close: Handle<UiNode>,
+    paths: Vec<PathBuf>,
+}
+
+impl ReferenceViewer {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let description;
+        let list;
+        let copy_to_clipboard;
+        let close;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(400.0).with_height(400.0))
+            .open(false)
+            .with_title(WindowTitle::text("References"))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child({
+                            description = TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(0)
+                                    .with_margin(Thickness::uniform(2.0)),
+                            )
+                            .with_wrap(WrapMode::Word)
+                            .build(ctx);
+                            description
+                        })
+                        .with_child(
+                            ScrollViewerBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_content({
+                                list = ListViewBuilder::new(WidgetBuilder::new()).build(ctx);
+                                list
+                            })
+                            .build(ctx),
+                        )
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_margin(Thickness::uniform(2.0))
+                                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .on_row(2)
+                                    .with_child({
+                                        copy_to_clipboard = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(130.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Copy To Clipboard")
+                                        .build(ctx);
+                                        copy_to_clipboard
+                                    })
+                                    .with_child({
+                                        close = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(130.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Close")
+                                        .build(ctx);
+                                        close
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        ),
+                )
+                .add_row(Row::auto())
+                .add_row(Row::stretch())
+                .add_row(Row::strict(24.0))
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            description,
+            list,
+            copy_to_clipboard,
+            close,
+            paths: Default::default(),
+        }
+    }
+
+    /// Shows every asset that refers to the asset at the given path.
+    pub fn show_references(
+        &mut self,
+        path: &Path,
+        cache: &mut ReferenceIndexCache,
+        resource_manager: &ResourceManager,
+        ui: &mut UserInterface,
+    ) {
+        let referencers = cache.referencers_of(path, resource_manager);
+        let description = if referencers.is_empty() {
+            format!("{} is not referenced by any asset.", path.display())
+        } else {
+            format!(
+                "{} is referenced by {} asset(s). Click an asset to show it in the asset browser.",
+                path.display(),
+                referencers.len()
+            )
+        };
+        self.open("Find References", description, referencers, ui);
+    }
+
+    /// Shows every asset that is not referenced by any other asset.
+    pub fn show_unused_assets(
+        &mut self,
+        cache: &mut ReferenceIndexCache,
+        resource_manager: &ResourceManager,
+        ui: &mut UserInterface,
+    ) {
+        let index = cache.get(resource_manager);
+        let unused = index
+            .unreferenced()
+            .into_iter()
+            .filter_map(|uuid| index.path(uuid).map(|path| path.to_path_buf()))
+            .collect::<Vec<_>>();
+        let description = format!(
+            "{} asset(s) are not referenced by any other asset. Some of them could still be used \
+            directly by the game code, for example the scenes it loads.",
+            unused.len()
+        );
+        self.open("Unused Assets", description, unused, ui);
+    }
+
+    fn open(
+        &mut self,
+        title: &str,
+        description: String,
+        paths: Vec<PathBuf>,
+        ui: &mut UserInterface,
+    ) {
+        let ctx = &mut ui.build_ctx();
+        let items = paths
+            .iter()
+            .map(|path| {
+                TextBuilder::new(
+                    WidgetBuilder::new()
+                        .with_margin(Thickness::uniform(1.0))
+                        .with_vertical_alignment(VerticalAlignment::Center),
+                )
+                .with_text(path.to_string_lossy())
+                .build(ctx)
+            })
+            .collect();
+        ui.send(self.list, ListViewMessage::Items(items));
+        ui.send(self.description, TextMessage::Text(description));
+        ui.send(self.window, WindowMessage::Title(WindowTitle::text(title)));
+        ui.send(
+            self.window,
+            WindowMessage::Open {
+                alignment: WindowAlignment::Center,
+                modal: false,
+                focus_content: true,
+            },
+        );
+        self.paths = paths;
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        ui: &mut UserInterface,
+        sender: &MessageSender,
+    ) {
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.close {
+                ui.send(self.window, WindowMessage::Close);
+            } else if message.destination() == self.copy_to_clipboard {
+                if let Some(mut clipboard) = ui.clipboard_mut() {
+                    let text = self
+                        .paths
+                        .iter()
+                        .map(|path| path.to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    Log::verify(clipboard.set_contents(text));
+                }
+            }
+        } else if let Some(ListViewMessage::Selection(selection)) = message.data_from(self.list) {
+            if let Some(path) = selection.first().and_then(|i| self.paths.get(*i)) {
+                sender.send(Message::ShowInAssetBrowser(path.clone()));
+            }
+        }
+    }
+}
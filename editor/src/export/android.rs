@@ -133,6 +133,7 @@ pub fn copy_assets(
     temp_folders: &mut Vec<PathBuf>,
     resource_manager: &ResourceManager,
     convert: bool,
+    filter: &dyn Fn(&Path) -> bool,
 ) -> Result<(), String> {
     // Asset management on Android is quite annoying, because all other target platforms
     // uses the workspace manifest path as a root directory and all paths in code/assets
@@ -180,12 +181,19 @@ pub fn copy_assets(
                 folder,
                 temp_assets_storage.join(folder),
                 TargetPlatform::Android,
-                &|_| true,
+                filter,
                 resource_manager,
                 convert,
             ));
         }
 
+        asset::build_derived_data(
+            &temp_assets_storage,
+            &export_options.assets_folders,
+            TargetPlatform::Android,
+            resource_manager,
+        );
+
         Ok(())
     } else {
         Err("Android executor must specify assets folder in \
@@ -22,12 +22,28 @@
 
 use crate::export::utils;
 use fyrox::{
-    asset::manager::ResourceManager,
+    asset::{
+        derived::DerivedDataCache,
+        graph::ResourceReferenceIndex,
+        io::{
+            normalize_path, FsResourceIo, PackedArchiveWriter, PackedCompression, PackedResourceIo,
+            ResourceIo,
+        },
+        manager::ResourceManager,
+        metadata::ResourceMetadata,
+        options::OPTIONS_EXTENSION,
+    },
     core::{
         futures::executor, futures::future::join_all, log::Log, platform::TargetPlatform, SafeLock,
     },
+    walkdir::WalkDir,
+};
+use std::{
+    fs,
+    io::{self, BufWriter},
+    path::{Path, PathBuf},
+    sync::Arc,
 };
-use std::{fs, io, path::Path};
 
 pub fn copy_and_convert_assets(
     src_folder: impl AsRef<Path>,
@@ -74,3 +90,157 @@ pub fn copy_and_convert_assets(
         utils::copy_dir(src_folder, dst_folder, &filter)
     }
 }
+
+/// Imports the exported assets that need conversion (textures, sounds, models, etc.) into the
+/// derived data cache of the target platform. The cache is placed next to the asset folders in
+/// [`DerivedDataCache::DEFAULT_PATH`], where the executor of the exported game looks for it.
+pub fn build_derived_data(
+    destination_folder: &Path,
+    assets_folders: &[PathBuf],
+    target_platform: TargetPlatform,
+    resource_manager: &ResourceManager,
+) {
+    Log::info(format!(
+        "Building the derived data for {target_platform}..."
+    ));
+
+    let cache = DerivedDataCache::new(
+        destination_folder.join(DerivedDataCache::DEFAULT_PATH),
+        target_platform,
+    );
+    // The exported files are imported, because the conversion could change them.
+    let io: Arc<dyn ResourceIo> = Arc::new(FsResourceIo);
+    let rm = resource_manager.state();
+    let loaders = rm.loaders.safe_lock();
+    let mut tasks = Vec::new();
+    for folder in assets_folders {
+        for entry in WalkDir::new(destination_folder.join(folder))
+            .into_iter()
+            .flatten()
+            .filter(|entry| entry.file_type().is_file())
+        {
+            let path = entry.into_path();
+            let Some(loader) = loaders.loader_for(&path) else {
+                continue;
+            };
+            if let Some(importer) = loader.importer_for(&path) {
+                tasks.push(cache.prepare(path, loader.data_type_uuid(), importer, io.clone()));
+            }
+        }
+    }
+
+    let count = tasks.len();
+    for result in executor::block_on(join_all(tasks)) {
+        Log::verify(result);
+    }
+    Log::info(format!(
+        "{count} assets were imported into the derived data."
+    ));
+}
+
+/// Creates a filter of the exported files that skips the assets which are not reachable from the
+/// given root assets (files or folders). When there are no root assets, the scenes (`rgs` and `ui`
+/// files) that are not referenced by other assets are used as the roots. Files that are not
+/// registered assets are always exported.
+pub fn used_assets_filter(
+    resource_manager: &ResourceManager,
+    root_assets: &[PathBuf],
+) -> impl Fn(&Path) -> bool {
+    Log::info("Looking for the assets used by the game...");
+
+    let index = executor::block_on(ResourceReferenceIndex::build(resource_manager));
+    let roots = if root_assets.is_empty() {
+        index
+            .unreferenced()
+            .into_iter()
+            .filter(|uuid| {
+                index
+                    .path(*uuid)
+                    .and_then(|path| path.extension())
+                    .is_some_and(|ext| ext == "rgs" || ext == "ui")
+            })
+            .collect::<Vec<_>>()
+    } else {
+        let root_assets = root_assets
+            .iter()
+            .filter_map(|root| normalize_path(root).ok())
+            .collect::<Vec<_>>();
+        index
+            .paths()
+            .iter()
+            .filter(|(_, path)| {
+                normalize_path(path)
+                    .is_ok_and(|path| root_assets.iter().any(|root| path.starts_with(root)))
+            })
+            .map(|(uuid, _)| *uuid)
+            .collect()
+    };
+
+    let reachable = index.reachable_from(roots);
+    Log::info(format!(
+        "{} of {} assets are used and will be exported.",
+        reachable.len(),
+        index.paths().len()
+    ));
+
+    move |path: &Path| {
+        // Import options and metadata go along with their assets.
+        let is_companion = path
+            .extension()
+            .is_some_and(|ext| ext == OPTIONS_EXTENSION || ext == ResourceMetadata::EXTENSION);
+        let asset_path = if is_companion {
+            path.with_extension("")
+        } else {
+            path.to_path_buf()
+        };
+        index
+            .find_uuid(&asset_path)
+            .is_none_or(|uuid| reachable.contains(&uuid))
+    }
+}
+
+/// Moves the asset folders copied to the destination folder into a single packed archive, which
+/// the executor picks up instead of loose files. The derived data cache, if any, is packed too.
+pub fn pack_assets(destination_folder: &Path, assets_folders: &[PathBuf]) -> Result<(), String> {
+    let archive_path = destination_folder.join(PackedResourceIo::DEFAULT_PATH);
+    Log::info(format!(
+        "Packing the assets into {}...",
+        archive_path.display()
+    ));
+
+    let mut assets_folders = assets_folders.to_vec();
+    if destination_folder
+        .join(DerivedDataCache::DEFAULT_PATH)
+        .exists()
+    {
+        assets_folders.push(DerivedDataCache::DEFAULT_PATH.into());
+    }
+
+    let pack = || {
+        let file = fs::File::create(&archive_path)?;
+        let mut writer = PackedArchiveWriter::new(BufWriter::new(file))?;
+        for folder in &assets_folders {
+            let folder = destination_folder.join(folder);
+            let count = writer.add_directory(
+                destination_folder,
+                &folder,
+                &|_| true,
+                PackedCompression::Deflate,
+            )?;
+            Log::info(format!(
+                "{count} files of {} were packed.",
+                folder.display()
+            ));
+        }
+        writer.finish()?;
+
+        for folder in &assets_folders {
+            fs::remove_dir_all(destination_folder.join(folder))?;
+        }
+        Ok(())
+    };
+
+    pack().map_err(|err: fyrox::core::io::FileError| {
+        format!("Failed to pack the assets. Reason: {err}")
+    })
+}
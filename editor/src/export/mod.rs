@@ -67,7 +67,7 @@ use cargo_metadata::camino::Utf8Path;
 use fyrox::gui::window::WindowAlignment;
 use std::{
     io::{BufRead, BufReader},
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{
         atomic::{AtomicBool, Ordering},
         mpsc::{self, Receiver},
@@ -83,6 +83,7 @@ struct ExportOptions {
     target_platform: TargetPlatform,
     destination_folder: PathBuf,
     include_used_assets: bool,
+    root_assets: Vec<PathBuf>,
     assets_folders: Vec<PathBuf>,
     ignored_extensions: Vec<String>,
     #[reflect(hidden)]
@@ -92,6 +93,7 @@ struct ExportOptions {
     run_after_build: bool,
     open_destination_folder: bool,
     convert_assets: bool,
+    pack_assets: bool,
     enable_optimization: bool,
 }
 
@@ -102,12 +104,14 @@ impl Default for ExportOptions {
             destination_folder: "./build/".into(),
             assets_folders: vec!["./data/".into()],
             include_used_assets: false,
+            root_assets: Default::default(),
             ignored_extensions: vec!["log".to_string()],
             build_targets: vec!["default".to_string()],
             selected_build_target: 0,
             run_after_build: false,
             open_destination_folder: true,
             convert_assets: true,
+            pack_assets: false,
             enable_optimization: true,
         }
     }
@@ -227,12 +231,22 @@ fn export(
 
     let mut temp_folders = Vec::new();
 
+    // Skip the assets that are not used by the game, if asked.
+    let used_assets_filter = export_options
+        .include_used_assets
+        .then(|| asset::used_assets_filter(&resource_manager, &export_options.root_assets));
+    let filter = |path: &Path| {
+        used_assets_filter
+            .as_ref()
+            .is_none_or(|filter| filter(path))
+    };
+
     // Copy assets
     match export_options.target_platform {
         TargetPlatform::PC | TargetPlatform::WebAssembly => {
             Log::info("Trying to copy the assets...");
 
-            for folder in export_options.assets_folders {
+            for folder in &export_options.assets_folders {
                 Log::info(format!(
                     "Trying to copy assets from {} to {}...",
                     folder.display(),
@@ -240,14 +254,32 @@ fn export(
                 ));
 
                 Log::verify(asset::copy_and_convert_assets(
-                    &folder,
-                    export_options.destination_folder.join(&folder),
+                    folder,
+                    export_options.destination_folder.join(folder),
                     export_options.target_platform,
-                    &|_| true,
+                    &filter,
                     &resource_manager,
                     export_options.convert_assets,
                 ));
             }
+
+            asset::build_derived_data(
+                &export_options.destination_folder,
+                &export_options.assets_folders,
+                export_options.target_platform,
+                &resource_manager,
+            );
+
+            if export_options.pack_assets {
+                if export_options.target_platform == TargetPlatform::PC {
+                    asset::pack_assets(
+                        &export_options.destination_folder,
+                        &export_options.assets_folders,
+                    )?;
+                } else {
+                    Log::warn("Packing assets is supported only for PC builds.");
+                }
+            }
         }
         TargetPlatform::Android => android::copy_assets(
             &export_options,
@@ -256,6 +288,7 @@ fn export(
             &mut temp_folders,
             &resource_manager,
             export_options.convert_assets,
+            &filter,
         )?,
     }
 
@@ -70,13 +70,17 @@ use crate::{
     configurator::Configurator,
     export::ExportWindow,
     fyrox::{
-        asset::{io::FsResourceIo, manager::ResourceManager, untyped::ResourceKind},
+        asset::{
+            derived::DerivedDataCache, io::FsResourceIo, manager::ResourceManager,
+            untyped::ResourceKind,
+        },
         core::{
             algebra::{Matrix3, Vector2},
             color::Color,
             log::{Log, MessageKind},
             make_relative_path,
             parking_lot::Mutex,
+            platform::TargetPlatform,
             pool::Handle,
             task::TaskPool,
             uuid::Uuid,
@@ -149,8 +153,10 @@ use crate::{
     plugins::{
         absm::{AbsmEditor, AbsmEditorPlugin},
         animation::AnimationEditorPlugin,
+        behavior::BehaviorEditorPlugin,
         blueprint::BlueprintEditorPlugin,
         collider::ColliderPlugin,
+        crash::CrashReportPlugin,
         curve_editor::CurveEditorPlugin,
         material::MaterialPlugin,
         probe::ReflectionProbePlugin,
@@ -815,6 +821,16 @@ impl Editor {
         })
         .unwrap();
 
+        // Imported assets (decoded and compressed textures, etc.) are cached in the folder of the
+        // project, so they are imported only once instead of on every load.
+        engine
+            .resource_manager
+            .state()
+            .set_derived_data_cache(Some(DerivedDataCache::new(
+                DerivedDataCache::DEFAULT_PATH,
+                TargetPlatform::current(),
+            )));
+
         // Register Blueprint (.blueprint) resources + BlueprintScript in the editor runtime.
         // This keeps the editor self-contained (no game plugin required).
         fyrox_blueprint::register_resources(&engine.resource_manager);
@@ -1088,6 +1104,7 @@ impl Editor {
                 .with(TileMapEditorPlugin::default())
                 .with(MaterialPlugin::default())
                 .with(BlueprintEditorPlugin::default())
+                .with(BehaviorEditorPlugin::default())
                 .with(RagdollPlugin::default())
                 .with(SettingsPlugin::default())
                 .with(AnimationEditorPlugin::default())
@@ -1095,6 +1112,7 @@ impl Editor {
                 .with(UiStatisticsPlugin::default())
                 .with(CurveEditorPlugin::default())
                 .with(ReflectionProbePlugin::default())
+                .with(CrashReportPlugin::default())
                 .with(inspector_plugin),
             // Apparently, some window managers (like Wayland), does not send `Focused` event after the window
             // was created. So we must assume that the editor is focused by default, otherwise editor's thread
@@ -1667,6 +1685,9 @@ impl Editor {
             command.arg("--");
         }
         command.arg("--override-scene").arg(path);
+        if self.settings.build.profile_blueprints {
+            command.env(fyrox_blueprint::BlueprintProfilerPlugin::ENABLE_VAR, "1");
+        }
 
         match command.spawn() {
             Ok(mut process) => {
@@ -2676,6 +2697,9 @@ impl Editor {
                         self.asset_browser
                             .locate_path(self.engine.user_interfaces.first(), path);
                     }
+                    Message::ShowUnusedAssets => {
+                        self.asset_browser.show_unused_assets(&mut self.engine);
+                    }
                     Message::LocateObject { handle } => self
                         .world_viewer
                         .try_locate_object(handle, self.engine.user_interfaces.first()),
@@ -246,6 +246,8 @@ impl Menu {
             message,
             &mut ctx.panels,
             ctx.engine.user_interfaces.first_mut(),
+            &ctx.engine.resource_manager,
+            &self.message_sender,
         );
         self.file_menu.handle_ui_message(
             message,
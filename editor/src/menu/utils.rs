@@ -20,43 +20,70 @@
 
 use crate::{
     fyrox::{
-        asset::core::pool::Handle,
+        asset::{core::pool::Handle, manager::ResourceManager},
+        core::log::Log,
         gui::{menu::MenuItemMessage, message::UiMessage, BuildContext, UiNode, UserInterface},
     },
     menu::{create_menu_item, create_root_menu_item, Panels},
+    message::MessageSender,
     stats::StatisticsWindow,
+    Message,
 };
 use fyrox::core::{uuid, Uuid};
 
 pub struct UtilsMenu {
     pub menu: Handle<UiNode>,
     pub rendering_statistics: Handle<UiNode>,
+    pub reimport_all_assets: Handle<UiNode>,
+    pub unused_assets: Handle<UiNode>,
 }
 
 impl UtilsMenu {
     pub const UTILS: Uuid = uuid!("f6a9a297-6efc-4b62-83b6-3955c0c43a00");
     pub const RENDERING_STATISTICS: Uuid = uuid!("ecf0bdb9-f97f-4df0-b17f-7ec07bdebd4d");
+    pub const REIMPORT_ALL_ASSETS: Uuid = uuid!("3b0f6c1e-8d2a-4a57-9e61-5c7d2f4b9a08");
+    pub const UNUSED_ASSETS: Uuid = uuid!("c4e2a9d7-51b3-4f0e-8a6c-2d9f7b3e1a54");
 
     pub fn new(ctx: &mut BuildContext) -> Self {
         let rendering_statistics;
+        let reimport_all_assets;
+        let unused_assets;
         let menu = create_root_menu_item(
             "Utils",
             Self::UTILS,
-            vec![{
-                rendering_statistics = create_menu_item(
-                    "Rendering Statistics",
-                    Self::RENDERING_STATISTICS,
-                    vec![],
-                    ctx,
-                );
-                rendering_statistics
-            }],
+            vec![
+                {
+                    rendering_statistics = create_menu_item(
+                        "Rendering Statistics",
+                        Self::RENDERING_STATISTICS,
+                        vec![],
+                        ctx,
+                    );
+                    rendering_statistics
+                },
+                {
+                    reimport_all_assets = create_menu_item(
+                        "Reimport All Assets",
+                        Self::REIMPORT_ALL_ASSETS,
+                        vec![],
+                        ctx,
+                    );
+                    reimport_all_assets
+                },
+                {
+                    unused_assets =
+                        create_menu_item("Unused Assets", Self::UNUSED_ASSETS, vec![], ctx);
+                    unused_assets
+                },
+            ],
             ctx,
         );
 
         Self {
             menu,
             rendering_statistics,
+            reimport_all_assets,
+            unused_assets,
         }
     }
 
@@ -65,6 +92,8 @@ impl UtilsMenu {
         message: &UiMessage,
         panels: &mut Panels,
         ui: &mut UserInterface,
+        resource_manager: &ResourceManager,
+        sender: &MessageSender,
     ) {
         if let Some(MenuItemMessage::Click) = message.data::<MenuItemMessage>() {
             if message.destination() == self.rendering_statistics {
@@ -72,6 +101,12 @@ impl UtilsMenu {
                     &mut ui.build_ctx(),
                     panels.scene_frame,
                 ))
+            } else if message.destination() == self.reimport_all_assets {
+                // Drops the cached derived data and loads every resource from its source again.
+                let resources = resource_manager.state().reimport_resources();
+                Log::info(format!("Reimporting {} resources...", resources.len()));
+            } else if message.destination() == self.unused_assets {
+                sender.send(Message::ShowUnusedAssets);
             }
         }
     }
@@ -82,8 +82,10 @@ pub enum Message {
     OpenTileSetEditor(TileSetResource),
     OpenTileMapBrushEditor(TileMapBrushResource),
     OpenBlueprintEditor(PathBuf),
+    OpenBehaviorEditor(PathBuf),
     OpenNodeRemovalDialog,
     ShowInAssetBrowser(PathBuf),
+    ShowUnusedAssets,
     LocateObject {
         handle: ErasedHandle,
     },
@@ -0,0 +1,789 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Editor of behavior tree assets (`.behavior`). Nodes are laid out on a canvas, children are
+//! attached by dragging a connection from the output socket of the parent to the input socket
+//! of the child. Children run from left to right.
+
+use crate::{
+    fyrox::{
+        asset::ResourceData,
+        core::{
+            algebra::Vector2,
+            color::Color,
+            futures::executor::block_on,
+            log::Log,
+            make_relative_path,
+            pool::{ErasedHandle, Handle, Pool},
+            reflect::prelude::*,
+        },
+        engine::Engine,
+        graph::BaseSceneGraph,
+        gui::{
+            brush::Brush,
+            button::{ButtonBuilder, ButtonMessage},
+            dock::DockingManagerMessage,
+            dropdown_list::{DropdownListBuilder, DropdownListMessage},
+            grid::{Column, GridBuilder, Row},
+            message::UiMessage,
+            numeric::{NumericUpDownBuilder, NumericUpDownMessage},
+            scroll_viewer::ScrollViewerBuilder,
+            stack_panel::StackPanelBuilder,
+            text::{TextBuilder, TextMessage},
+            text_box::TextBoxBuilder,
+            utils::make_dropdown_list_option,
+            widget::{WidgetBuilder, WidgetMessage},
+            window::{WindowAlignment, WindowBuilder, WindowMessage, WindowTitle},
+            BuildContext, Orientation, Thickness, UiNode, UserInterface,
+        },
+    },
+    plugin::EditorPlugin,
+    plugins::absm::{
+        canvas::{AbsmCanvasBuilder, AbsmCanvasMessage},
+        connection::ConnectionBuilder,
+        node::{AbsmNodeBuilder, AbsmNodeLayout},
+        socket::{Socket, SocketBuilder, SocketDirection},
+    },
+    Editor, Message,
+};
+use fyrox_blueprint::BehaviorAsset;
+use fyrox_visual_scripting::{
+    behavior::BehaviorNodeId, BehaviorGraph, BehaviorNodeKind, BlackboardCondition, ConditionOp,
+};
+use std::{collections::HashMap, path::PathBuf};
+
+#[derive(Clone, Debug, Reflect)]
+struct BehaviorNodeModel {
+    node_id: u32,
+}
+
+/// Property of the selected node edited by a widget of the details panel.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum DetailsField {
+    Task,
+    Duration,
+    Count,
+    Variable,
+    Op,
+    Value,
+}
+
+struct BehaviorEditor {
+    window: Handle<UiNode>,
+    save: Handle<UiNode>,
+    add_buttons: Vec<(Handle<UiNode>, BehaviorNodeKind)>,
+    set_root: Handle<UiNode>,
+    detach: Handle<UiNode>,
+    delete: Handle<UiNode>,
+    canvas: Handle<UiNode>,
+    details_panel: Handle<UiNode>,
+    details_widgets: Vec<Handle<UiNode>>,
+    details_bindings: HashMap<Handle<UiNode>, DetailsField>,
+
+    models: Pool<BehaviorNodeModel>,
+    node_views: HashMap<BehaviorNodeId, Handle<UiNode>>,
+    view_to_node: HashMap<Handle<UiNode>, BehaviorNodeId>,
+    socket_to_node: HashMap<Handle<UiNode>, BehaviorNodeId>,
+    connection_views: Vec<Handle<UiNode>>,
+
+    path: Option<PathBuf>,
+    version: u32,
+    graph: BehaviorGraph,
+    selected: Option<BehaviorNodeId>,
+}
+
+fn make_toolbar_button(text: &str, ctx: &mut BuildContext) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_height(24.0)
+            .with_margin(Thickness::right(2.0)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+fn kind_name(kind: &BehaviorNodeKind) -> &'static str {
+    match kind {
+        BehaviorNodeKind::Sequence => "Sequence",
+        BehaviorNodeKind::Selector => "Selector",
+        BehaviorNodeKind::Inverter => "Inverter",
+        BehaviorNodeKind::Cooldown { .. } => "Cooldown",
+        BehaviorNodeKind::Repeat { .. } => "Repeat",
+        BehaviorNodeKind::Timeout { .. } => "Timeout",
+        BehaviorNodeKind::Condition(_) => "Condition",
+        BehaviorNodeKind::Task { .. } => "Task",
+    }
+}
+
+fn node_title(kind: &BehaviorNodeKind) -> String {
+    match kind {
+        BehaviorNodeKind::Cooldown { duration } => format!("Cooldown {duration}s"),
+        BehaviorNodeKind::Repeat { count: 0 } => "Repeat forever".to_string(),
+        BehaviorNodeKind::Repeat { count } => format!("Repeat x{count}"),
+        BehaviorNodeKind::Timeout { duration } => format!("Timeout {duration}s"),
+        BehaviorNodeKind::Condition(condition) => format!(
+            "If {} {} {}",
+            condition.variable,
+            condition.op.symbol(),
+            condition.value
+        ),
+        BehaviorNodeKind::Task { task } => format!("Task {task}"),
+        kind => kind_name(kind).to_string(),
+    }
+}
+
+fn header_color(kind: &BehaviorNodeKind) -> Color {
+    match kind {
+        BehaviorNodeKind::Sequence | BehaviorNodeKind::Selector => Color::opaque(90, 90, 90),
+        BehaviorNodeKind::Condition(_) => Color::opaque(40, 130, 60),
+        BehaviorNodeKind::Task { .. } => Color::opaque(200, 120, 40),
+        _ => Color::opaque(50, 100, 190),
+    }
+}
+
+impl BehaviorEditor {
+    fn new(ctx: &mut BuildContext) -> Self {
+        let kinds = [
+            BehaviorNodeKind::Sequence,
+            BehaviorNodeKind::Selector,
+            BehaviorNodeKind::Inverter,
+            BehaviorNodeKind::Cooldown { duration: 1.0 },
+            BehaviorNodeKind::Repeat { count: 0 },
+            BehaviorNodeKind::Timeout { duration: 5.0 },
+            BehaviorNodeKind::Condition(BlackboardCondition::default()),
+            BehaviorNodeKind::Task {
+                task: "Task".to_string(),
+            },
+        ];
+        let add_buttons = kinds
+            .into_iter()
+            .map(|kind| (make_toolbar_button(kind_name(&kind), ctx), kind))
+            .collect::<Vec<_>>();
+
+        let save = make_toolbar_button("Save", ctx);
+        let set_root = make_toolbar_button("Set Root", ctx);
+        let detach = make_toolbar_button("Detach", ctx);
+        let delete = make_toolbar_button("Delete", ctx);
+
+        let toolbar = StackPanelBuilder::new(
+            WidgetBuilder::new()
+                .on_row(0)
+                .with_margin(Thickness::uniform(2.0))
+                .with_child(save)
+                .with_children(add_buttons.iter().map(|(button, _)| *button))
+                .with_child(set_root)
+                .with_child(detach)
+                .with_child(delete),
+        )
+        .with_orientation(Orientation::Horizontal)
+        .build(ctx);
+
+        let canvas = AbsmCanvasBuilder::new(
+            WidgetBuilder::new()
+                .on_row(1)
+                .on_column(0)
+                .with_allow_drop(true),
+        )
+        .build(ctx);
+
+        let details_panel =
+            StackPanelBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(4.0)))
+                .build(ctx);
+
+        let content = GridBuilder::new(
+            WidgetBuilder::new()
+                .with_child(toolbar)
+                .with_child(canvas)
+                .with_child(
+                    ScrollViewerBuilder::new(WidgetBuilder::new().on_row(1).on_column(1))
+                        .with_content(details_panel)
+                        .build(ctx),
+                ),
+        )
+        .add_row(Row::auto())
+        .add_row(Row::stretch())
+        .add_column(Column::stretch())
+        .add_column(Column::strict(260.0))
+        .build(ctx);
+
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(900.0).with_height(600.0))
+            .with_title(WindowTitle::text("Behavior Tree"))
+            .open(false)
+            .with_content(content)
+            .build(ctx);
+
+        Self {
+            window,
+            save,
+            add_buttons,
+            set_root,
+            detach,
+            delete,
+            canvas,
+            details_panel,
+            details_widgets: Vec::new(),
+            details_bindings: HashMap::new(),
+            models: Pool::new(),
+            node_views: HashMap::new(),
+            view_to_node: HashMap::new(),
+            socket_to_node: HashMap::new(),
+            connection_views: Vec::new(),
+            path: None,
+            version: 1,
+            graph: BehaviorGraph::default(),
+            selected: None,
+        }
+    }
+
+    fn open(&mut self, editor: &mut Editor, path: PathBuf) {
+        let title = path
+            .file_name()
+            .map(|n| format!("Behavior Tree - {}", n.to_string_lossy()))
+            .unwrap_or_else(|| "Behavior Tree".to_string());
+        self.path = Some(path);
+        self.reload_from_resource(&editor.engine);
+
+        let ui = editor.engine.user_interfaces.first_mut();
+        ui.send(self.window, WindowMessage::Title(WindowTitle::text(title)));
+        self.selected = None;
+        self.rebuild_view(ui);
+        self.rebuild_details(ui);
+        ui.send(
+            self.window,
+            WindowMessage::Open {
+                alignment: WindowAlignment::Center,
+                modal: false,
+                focus_content: true,
+            },
+        );
+        ui.send(
+            editor.docking_manager,
+            DockingManagerMessage::AddFloatingWindow(self.window),
+        );
+    }
+
+    fn reload_from_resource(&mut self, engine: &Engine) {
+        self.version = 1;
+        self.graph = BehaviorGraph::default();
+
+        let Some(path) = self.path.as_ref() else {
+            return;
+        };
+        let Ok(relative) = make_relative_path(path) else {
+            return;
+        };
+        let resource = match block_on(engine.resource_manager.request::<BehaviorAsset>(relative)) {
+            Ok(resource) => resource,
+            Err(err) => {
+                Log::err(format!("BehaviorEditor: failed to load: {err:?}"));
+                return;
+            }
+        };
+        let data = resource.data_ref();
+        let Some(asset) = data.as_loaded_ref() else {
+            return;
+        };
+        self.version = asset.version;
+        match asset.graph() {
+            Ok(graph) => self.graph = graph,
+            Err(err) => Log::err(format!("BehaviorEditor: failed to parse tree: {err}")),
+        }
+    }
+
+    fn save_to_disk(&self, engine: &Engine) {
+        let Some(path) = self.path.as_ref() else {
+            return;
+        };
+
+        let tree_json = match serde_json::to_string_pretty(&self.graph) {
+            Ok(s) => s,
+            Err(err) => {
+                Log::err(format!("BehaviorEditor: failed to serialize tree: {err}"));
+                return;
+            }
+        };
+        let mut asset = BehaviorAsset {
+            version: self.version,
+            tree_json,
+        };
+        if let Err(err) = asset.save(path) {
+            Log::err(format!("BehaviorEditor: save failed: {err:?}"));
+            return;
+        }
+
+        if let Ok(relative) = make_relative_path(path) {
+            if let Ok(resource) = block_on(engine.resource_manager.request_untyped(&relative)) {
+                engine.resource_manager.state().reload_resource(resource);
+            }
+        }
+
+        Log::info(format!("Saved behavior tree: {}", path.display()));
+    }
+
+    fn clear_view(&mut self, ui: &UserInterface) {
+        for view in self
+            .connection_views
+            .drain(..)
+            .chain(self.node_views.drain().map(|(_, view)| view))
+        {
+            ui.send(view, WidgetMessage::Remove);
+        }
+        self.models.clear();
+        self.view_to_node.clear();
+        self.socket_to_node.clear();
+    }
+
+    fn rebuild_view(&mut self, ui: &mut UserInterface) {
+        self.clear_view(ui);
+
+        let mut input_sockets = HashMap::new();
+        let mut output_sockets = HashMap::new();
+
+        for (id, node) in self.graph.nodes.iter() {
+            let model_handle = self.models.spawn(BehaviorNodeModel { node_id: id.0 });
+
+            let mut make_socket = |direction, ui: &mut UserInterface| {
+                let label = TextBuilder::new(
+                    WidgetBuilder::new()
+                        .with_margin(Thickness::left(4.0))
+                        .with_height(18.0),
+                )
+                .with_text(match direction {
+                    SocketDirection::Input => "parent",
+                    SocketDirection::Output => "children",
+                })
+                .build(&mut ui.build_ctx());
+                let socket =
+                    SocketBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(2.0)))
+                        .with_direction(direction)
+                        .with_parent_node(ErasedHandle::from(model_handle))
+                        .with_editor(label)
+                        .with_index(0)
+                        .with_show_index(false)
+                        .with_pin_color(Color::WHITE)
+                        .with_canvas(self.canvas)
+                        .build(&mut ui.build_ctx());
+                self.socket_to_node.insert(socket, *id);
+                socket
+            };
+
+            let input = make_socket(SocketDirection::Input, ui);
+            input_sockets.insert(*id, input);
+            let mut outputs = Vec::new();
+            if node.kind.has_children() {
+                let output = make_socket(SocketDirection::Output, ui);
+                output_sockets.insert(*id, output);
+                outputs.push(output);
+            }
+
+            let mut title = node_title(&node.kind);
+            if self.graph.root == Some(*id) {
+                title.push_str(" (root)");
+            }
+            let color = header_color(&node.kind);
+            let selected_color = Color::opaque(
+                color.r.saturating_add(50),
+                color.g.saturating_add(50),
+                color.b.saturating_add(50),
+            );
+
+            let view = AbsmNodeBuilder::new(
+                WidgetBuilder::new()
+                    .with_desired_position(Vector2::new(node.position.0, node.position.1)),
+            )
+            .with_model_handle(model_handle)
+            .with_name(title)
+            .with_show_model_handle(false)
+            .with_layout(AbsmNodeLayout::BlueprintCompact)
+            .with_normal_brush(Brush::Solid(color).into())
+            .with_selected_brush(Brush::Solid(selected_color).into())
+            .with_input_sockets(vec![input])
+            .with_output_sockets(outputs)
+            .build(&mut ui.build_ctx());
+
+            ui.send_sync(view, WidgetMessage::LinkWith(self.canvas));
+            self.node_views.insert(*id, view);
+            self.view_to_node.insert(view, *id);
+        }
+
+        for (id, node) in self.graph.nodes.iter() {
+            for child in node.children.iter() {
+                let (Some(source_socket), Some(dest_socket)) =
+                    (output_sockets.get(id), input_sockets.get(child))
+                else {
+                    continue;
+                };
+                let connection = ConnectionBuilder::new(WidgetBuilder::new())
+                    .with_source_socket(*source_socket)
+                    .with_dest_socket(*dest_socket)
+                    .with_source_node(self.node_views[id])
+                    .with_dest_node(self.node_views[child])
+                    .with_brushes(
+                        Brush::Solid(Color::opaque(200, 200, 200)),
+                        Brush::Solid(Color::WHITE),
+                    )
+                    .with_thickness(6.0)
+                    .build(self.canvas, &mut ui.build_ctx());
+                ui.send_sync(connection, WidgetMessage::LinkWith(self.canvas));
+                self.connection_views.push(connection);
+            }
+        }
+    }
+
+    /// Orders children of every node by their horizontal position, so the tree runs in the order
+    /// it is drawn.
+    fn sort_children(&mut self) {
+        let positions = self
+            .graph
+            .nodes
+            .iter()
+            .map(|(id, node)| (*id, node.position.0))
+            .collect::<HashMap<_, _>>();
+        for node in self.graph.nodes.values_mut() {
+            node.children
+                .sort_by(|a, b| positions[a].total_cmp(&positions[b]));
+        }
+    }
+
+    fn add_node(&mut self, ui: &mut UserInterface, kind: BehaviorNodeKind) {
+        let parent = self.selected;
+        let position = parent
+            .and_then(|parent| self.graph.nodes.get(&parent))
+            .map(|parent| {
+                let x = parent.position.0 + 220.0 * parent.children.len() as f32;
+                (x, parent.position.1 + 120.0)
+            })
+            .unwrap_or((0.0, 0.0));
+
+        let id = self.graph.add_node(kind);
+        if let Some(node) = self.graph.nodes.get_mut(&id) {
+            node.position = position;
+        }
+        if self.graph.root.is_none() {
+            self.graph.root = Some(id);
+        } else if let Some(parent) = parent {
+            self.graph.link(parent, id);
+        }
+
+        self.selected = Some(id);
+        self.rebuild_view(ui);
+        self.rebuild_details(ui);
+    }
+
+    fn add_details_label(&mut self, ui: &mut UserInterface, text: &str) {
+        let label = TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(2.0)))
+            .with_text(text)
+            .build(&mut ui.build_ctx());
+        ui.send(label, WidgetMessage::LinkWith(self.details_panel));
+        self.details_widgets.push(label);
+    }
+
+    fn add_details_field(
+        &mut self,
+        ui: &mut UserInterface,
+        widget: Handle<UiNode>,
+        field: DetailsField,
+    ) {
+        ui.send(widget, WidgetMessage::LinkWith(self.details_panel));
+        self.details_widgets.push(widget);
+        self.details_bindings.insert(widget, field);
+    }
+
+    fn add_text_field(
+        &mut self,
+        ui: &mut UserInterface,
+        label: &str,
+        text: &str,
+        field: DetailsField,
+    ) {
+        self.add_details_label(ui, label);
+        let text_box = TextBoxBuilder::new(
+            WidgetBuilder::new()
+                .with_margin(Thickness::uniform(2.0))
+                .with_height(24.0),
+        )
+        .with_text(text)
+        .build(&mut ui.build_ctx());
+        self.add_details_field(ui, text_box, field);
+    }
+
+    fn rebuild_details(&mut self, ui: &mut UserInterface) {
+        for widget in self.details_widgets.drain(..) {
+            ui.send(widget, WidgetMessage::Remove);
+        }
+        self.details_bindings.clear();
+
+        let Some(kind) = self
+            .selected
+            .and_then(|id| self.graph.nodes.get(&id))
+            .map(|node| node.kind.clone())
+        else {
+            self.add_details_label(ui, "Select a node to edit its properties");
+            return;
+        };
+
+        self.add_details_label(ui, &format!("Selected: {}", kind_name(&kind)));
+
+        match kind {
+            BehaviorNodeKind::Cooldown { duration } | BehaviorNodeKind::Timeout { duration } => {
+                self.add_details_label(ui, "Duration (s)");
+                let widget = NumericUpDownBuilder::<f32>::new(
+                    WidgetBuilder::new()
+                        .with_margin(Thickness::uniform(2.0))
+                        .with_height(24.0),
+                )
+                .with_min_value(0.0)
+                .with_value(duration)
+                .build(&mut ui.build_ctx());
+                self.add_details_field(ui, widget, DetailsField::Duration);
+            }
+            BehaviorNodeKind::Repeat { count } => {
+                self.add_details_label(ui, "Count (0 repeats forever)");
+                let widget = NumericUpDownBuilder::<u32>::new(
+                    WidgetBuilder::new()
+                        .with_margin(Thickness::uniform(2.0))
+                        .with_height(24.0),
+                )
+                .with_value(count)
+                .build(&mut ui.build_ctx());
+                self.add_details_field(ui, widget, DetailsField::Count);
+            }
+            BehaviorNodeKind::Condition(condition) => {
+                self.add_text_field(ui, "Variable", &condition.variable, DetailsField::Variable);
+
+                self.add_details_label(ui, "Comparison");
+                let items = ConditionOp::ALL
+                    .iter()
+                    .map(|op| make_dropdown_list_option(&mut ui.build_ctx(), op.symbol()))
+                    .collect::<Vec<_>>();
+                let selected = ConditionOp::ALL
+                    .iter()
+                    .position(|op| *op == condition.op)
+                    .unwrap_or_default();
+                let widget = DropdownListBuilder::new(
+                    WidgetBuilder::new()
+                        .with_margin(Thickness::uniform(2.0))
+                        .with_height(24.0),
+                )
+                .with_items(items)
+                .with_selected(selected)
+                .build(&mut ui.build_ctx());
+                self.add_details_field(ui, widget, DetailsField::Op);
+
+                self.add_text_field(ui, "Value", &condition.value, DetailsField::Value);
+            }
+            BehaviorNodeKind::Task { task } => {
+                self.add_text_field(ui, "Task", &task, DetailsField::Task);
+            }
+            BehaviorNodeKind::Sequence
+            | BehaviorNodeKind::Selector
+            | BehaviorNodeKind::Inverter => (),
+        }
+    }
+
+    /// Applies an edit of the details panel to the selected node. Returns `true` if the node
+    /// changed.
+    fn apply_details(&mut self, message: &UiMessage) -> bool {
+        let Some(field) = self.details_bindings.get(&message.destination()).copied() else {
+            return false;
+        };
+        let Some(node) = self.selected.and_then(|id| self.graph.nodes.get_mut(&id)) else {
+            return false;
+        };
+
+        match (&mut node.kind, field) {
+            (BehaviorNodeKind::Task { task }, DetailsField::Task) => {
+                let Some(TextMessage::Text(text)) = message.data() else {
+                    return false;
+                };
+                *task = text.clone();
+            }
+            (
+                BehaviorNodeKind::Cooldown { duration } | BehaviorNodeKind::Timeout { duration },
+                DetailsField::Duration,
+            ) => {
+                let Some(NumericUpDownMessage::Value(value)) =
+                    message.data::<NumericUpDownMessage<f32>>()
+                else {
+                    return false;
+                };
+                *duration = *value;
+            }
+            (BehaviorNodeKind::Repeat { count }, DetailsField::Count) => {
+                let Some(NumericUpDownMessage::Value(value)) =
+                    message.data::<NumericUpDownMessage<u32>>()
+                else {
+                    return false;
+                };
+                *count = *value;
+            }
+            (BehaviorNodeKind::Condition(condition), DetailsField::Variable) => {
+                let Some(TextMessage::Text(text)) = message.data() else {
+                    return false;
+                };
+                condition.variable = text.clone();
+            }
+            (BehaviorNodeKind::Condition(condition), DetailsField::Value) => {
+                let Some(TextMessage::Text(text)) = message.data() else {
+                    return false;
+                };
+                condition.value = text.clone();
+            }
+            (BehaviorNodeKind::Condition(condition), DetailsField::Op) => {
+                let Some(DropdownListMessage::Selection(Some(index))) = message.data() else {
+                    return false;
+                };
+                let Some(op) = ConditionOp::ALL.get(*index) else {
+                    return false;
+                };
+                condition.op = *op;
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn handle_canvas_message(&mut self, message: &UiMessage, ui: &mut UserInterface) {
+        if let Some(AbsmCanvasMessage::SelectionChanged(selection)) = message.data_from(self.canvas)
+        {
+            self.selected = selection
+                .iter()
+                .find_map(|view| self.view_to_node.get(view).copied());
+            self.rebuild_details(ui);
+        } else if let Some(AbsmCanvasMessage::CommitDrag { .. }) = message.data_from(self.canvas) {
+            for (id, view) in self.node_views.iter() {
+                let position = ui.node(*view).desired_local_position();
+                if let Some(node) = self.graph.nodes.get_mut(id) {
+                    node.position = (position.x, position.y);
+                }
+            }
+            self.sort_children();
+        } else if let Some(AbsmCanvasMessage::CommitConnection {
+            source_socket,
+            dest_socket,
+        }) = message.data_from(self.canvas)
+        {
+            let (Some(a), Some(b)) = (
+                self.socket_to_node.get(source_socket).copied(),
+                self.socket_to_node.get(dest_socket).copied(),
+            ) else {
+                return;
+            };
+            let (parent, child) = match socket_direction(ui, *source_socket) {
+                Some(SocketDirection::Output) => (a, b),
+                _ => (b, a),
+            };
+            if self.graph.link(parent, child) {
+                self.sort_children();
+            } else {
+                Log::warn("BehaviorEditor: the link would not form a tree");
+            }
+            self.rebuild_view(ui);
+        }
+    }
+
+    fn handle_ui_message(&mut self, message: &UiMessage, engine: &mut Engine) {
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.save {
+                self.save_to_disk(engine);
+                return;
+            }
+        }
+
+        let ui = engine.user_interfaces.first_mut();
+
+        if let Some(ButtonMessage::Click) = message.data() {
+            let destination = message.destination();
+            if let Some((_, kind)) = self
+                .add_buttons
+                .iter()
+                .find(|(button, _)| *button == destination)
+            {
+                let kind = kind.clone();
+                self.add_node(ui, kind);
+            } else if let Some(selected) = self.selected {
+                if destination == self.set_root {
+                    self.graph.unlink(selected);
+                    self.graph.root = Some(selected);
+                } else if destination == self.detach {
+                    self.graph.unlink(selected);
+                } else if destination == self.delete {
+                    self.graph.remove_node(selected);
+                    self.selected = None;
+                    self.rebuild_details(ui);
+                } else {
+                    return;
+                }
+                self.rebuild_view(ui);
+            }
+            return;
+        }
+
+        if self.apply_details(message) {
+            self.rebuild_view(ui);
+            return;
+        }
+
+        self.handle_canvas_message(message, ui);
+    }
+}
+
+fn socket_direction(ui: &UserInterface, socket: Handle<UiNode>) -> Option<SocketDirection> {
+    ui.node(socket)
+        .query_component::<Socket>()
+        .map(|s| s.direction)
+}
+
+#[derive(Default)]
+pub struct BehaviorEditorPlugin {
+    editor: Option<BehaviorEditor>,
+}
+
+impl EditorPlugin for BehaviorEditorPlugin {
+    fn on_ui_message(&mut self, message: &mut UiMessage, editor: &mut Editor) {
+        let Some(behavior_editor) = self.editor.as_mut() else {
+            return;
+        };
+
+        behavior_editor.handle_ui_message(message, &mut editor.engine);
+
+        if let Some(WindowMessage::Close) = message.data() {
+            if message.destination() == behavior_editor.window {
+                editor
+                    .engine
+                    .user_interfaces
+                    .first()
+                    .send(behavior_editor.window, WidgetMessage::Remove);
+                self.editor = None;
+            }
+        }
+    }
+
+    fn on_message(&mut self, message: &Message, editor: &mut Editor) {
+        let Message::OpenBehaviorEditor(path) = message else {
+            return;
+        };
+
+        let behavior_editor = self.editor.get_or_insert_with(|| {
+            BehaviorEditor::new(&mut editor.engine.user_interfaces.first_mut().build_ctx())
+        });
+        behavior_editor.open(editor, path.clone());
+    }
+}
@@ -1,6 +1,9 @@
+mod profiler;
+
 use crate::{
     asset::preview::cache::IconRequest,
     fyrox::{
+        asset::event::ResourceEvent,
         asset::io::FsResourceIo,
         asset::ResourceData,
         core::{
@@ -11,10 +14,11 @@ use crate::{
             algebra::{UnitQuaternion, Vector2, Vector3},
             pool::{ErasedHandle, Handle, Pool},
             reflect::prelude::*,
-            visitor::Visitor,
+            visitor::{Format, Visitor},
             uuid::Uuid,
+            SafeLock,
         },
-        engine::Engine,
+        engine::{ApplicationLoopController, Engine},
         graph::BaseSceneGraph,
         gui::{
             border::BorderBuilder,
@@ -58,19 +62,25 @@ use crate::{
 };
 use fyrox::gui::window::WindowAlignment;
 use fyrox::gui::style::{resource::StyleResourceExt, Style};
-use fyrox_blueprint::BlueprintAsset;
+use fyrox_blueprint::{
+    collect_types, define_from_resource, BlueprintAsset, BlueprintInterfaceAsset,
+    BlueprintInterfaceLoader, BlueprintLoader,
+};
 use fyrox_visual_scripting::{
-    compile, BlueprintGraph, BuiltinNodeKind, DataType, GraphKind, Link, Node, NodeId, PinDirection,
-    PinId, Value,
+    codegen::GENERATED_HEADER, compile, generated_type_uuid, nativize, AbsmParameterKind,
+    BlueprintGraph, BlueprintInterface, BlueprintProfile, BuiltinNodeKind, DataType, ElementType,
+    GraphKind, Link, Node, NodeId, PinDirection, PinId, SoundProperty, UserTypes, Value,
+    WidgetEvent, WidgetProperty,
 };
 use fyrox_visual_scripting::model::VariableDef;
 use std::{
     collections::{HashMap, HashSet},
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::Arc,
-    sync::mpsc::Sender,
+    sync::mpsc::{self, Receiver, Sender},
 };
 
+use self::profiler::{heat_color, ProfilerPanel};
 use crate::plugins::absm::{
     canvas::{AbsmCanvas, AbsmCanvasBuilder, AbsmCanvasMessage},
     connection::ConnectionBuilder,
@@ -180,6 +190,9 @@ enum DetailsBinding {
     NodeProp { node: NodeId, key: &'static str },
     VariableName { index: usize },
     VariableType { index: usize },
+    VariableContainer { index: usize },
+    VariableValueType { index: usize },
+    VariableDefault { index: usize },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -187,6 +200,21 @@ enum ActionMenuAction {
     SpawnBuiltin(BuiltinNodeKind),
     SpawnGetVariable(usize),
     SpawnSetVariable(usize),
+    // Indices into `BlueprintEditor::known_interfaces` and their functions.
+    SpawnCallInterface { interface: usize, function: usize },
+    SpawnDoesImplementInterface(usize),
+    ImplementInterface(usize),
+    SpawnExpectEqual(DataType),
+    SpawnGlobal(BuiltinNodeKind, DataType),
+    // Indices into the structs and enums of `BlueprintEditor::known_types`.
+    SpawnMakeStruct(usize),
+    SpawnBreakStruct(usize),
+    SpawnSwitchOnEnum(usize),
+    SpawnDataTableNode(BuiltinNodeKind, usize),
+    SpawnWidgetEvent(WidgetEvent),
+    SpawnSetWidgetProperty(WidgetProperty),
+    SpawnSetAbsmParameter(AbsmParameterKind),
+    SpawnSetSoundProperty(SoundProperty),
 }
 
 #[derive(Debug, Clone)]
@@ -200,7 +228,9 @@ struct PendingConnection {
 struct BlueprintEditor {
     window: fyrox::core::pool::Handle<UiNode>,
     save: fyrox::core::pool::Handle<UiNode>,
+    nativize: fyrox::core::pool::Handle<UiNode>,
     tab_control: fyrox::core::pool::Handle<UiNode>,
+    profiler: ProfilerPanel,
 
     // Viewport/Components (Actor Blueprint authoring).
     viewport_image: Handle<UiNode>,
@@ -208,6 +238,8 @@ struct BlueprintEditor {
     preview_scene: Handle<Scene>,
     preview_actor_root: Handle<SceneNode>,
     prefab_path: Option<String>,
+    // User interface scene scripted by the blueprint, kept as loaded.
+    ui_path: Option<String>,
 
     // Preview viewport camera controls.
     preview_camera_pivot: Handle<SceneNode>,
@@ -270,6 +302,13 @@ struct BlueprintEditor {
 
     pending_connection: Option<PendingConnection>,
 
+    /// Interfaces found in the resource registry (`.blueprint_interface` assets).
+    known_interfaces: Vec<BlueprintInterface>,
+
+    /// Struct and enum types from the resource registry (`.blueprint_struct` and `.blueprint_enum`
+    /// assets) and the ones the opened blueprint defines.
+    known_types: UserTypes,
+
     active_tab: BlueprintGraphTab,
     active_extra_tab: Option<usize>,
 
@@ -342,6 +381,7 @@ impl BlueprintEditor {
         let mut node_palette_buttons = HashMap::new();
 
         let save;
+        let nativize;
         let my_blueprint_graphs_event;
         let my_blueprint_graphs_construction;
         let my_blueprint_new_graph;
@@ -376,6 +416,17 @@ impl BlueprintEditor {
                 .on_row(0)
                 .with_margin(Thickness::uniform(2.0))
                 .with_horizontal_alignment(HorizontalAlignment::Right)
+                .with_child({
+                    nativize = ButtonBuilder::new(
+                        WidgetBuilder::new()
+                            .with_width(120.0)
+                            .with_height(24.0)
+                            .with_margin(Thickness::right(2.0)),
+                    )
+                    .with_text("Nativize")
+                    .build(ctx);
+                    nativize
+                })
                 .with_child({
                     save = ButtonBuilder::new(WidgetBuilder::new().with_width(120.0).with_height(24.0))
                         .with_text("Save")
@@ -617,10 +668,13 @@ impl BlueprintEditor {
             .with_texture(preview_render_target.clone())
             .build(ctx);
 
+        let profiler = ProfilerPanel::new(ctx);
+
         tab_control = TabControlBuilder::new(WidgetBuilder::new())
             .with_tab(make_tab("Viewport", viewport_image, ctx))
             .with_tab(make_tab("Event Graph", event_canvas, ctx))
             .with_tab(make_tab("Construction Script", construction_canvas, ctx))
+            .with_tab(make_tab("Profiler", profiler.root, ctx))
             .build(ctx);
 
         let graph_window = WindowBuilder::new(WidgetBuilder::new())
@@ -795,13 +849,16 @@ impl BlueprintEditor {
         Self {
             window,
             save,
+            nativize,
             tab_control,
+            profiler,
 
             viewport_image,
             preview_render_target,
             preview_scene,
             preview_actor_root,
             prefab_path: None,
+            ui_path: None,
 
             preview_camera_pivot,
             preview_camera,
@@ -863,6 +920,9 @@ impl BlueprintEditor {
 
             pending_connection: None,
 
+            known_interfaces: Vec::new(),
+            known_types: UserTypes::default(),
+
             active_tab: BlueprintGraphTab::EventGraph,
             active_extra_tab: None,
 
@@ -989,7 +1049,12 @@ impl BlueprintEditor {
         preview_scene.graph.update_hierarchical_data();
     }
 
-    fn save_preview_prefab(&self, prefab_path: &PathBuf, engine: &Engine) -> Result<(), String> {
+    fn save_preview_prefab(
+        &self,
+        prefab_path: &PathBuf,
+        engine: &Engine,
+        format: Format,
+    ) -> Result<(), String> {
         let source_scene = &engine.scenes[self.preview_scene];
         if !source_scene.graph.is_valid_handle(self.preview_actor_root) {
             return Ok(());
@@ -1020,7 +1085,7 @@ impl BlueprintEditor {
             .save("Scene", &mut visitor)
             .map_err(|e| format!("Failed to serialize prefab scene: {e:?}"))?;
         visitor
-            .save_ascii_to_file(prefab_path)
+            .save_to_file(prefab_path, format)
             .map_err(|e| format!("Failed to write prefab file: {e:?}"))?;
 
         Ok(())
@@ -1145,10 +1210,146 @@ impl BlueprintEditor {
             (BuiltinNodeKind::SpawnActor, "Spawn Actor"),
             (BuiltinNodeKind::GetActorByName, "Get Actor By Name"),
             (BuiltinNodeKind::GetActorName, "Get Actor Name"),
+            (BuiltinNodeKind::Assert, "Assert"),
+            (BuiltinNodeKind::ForEach, "For Each"),
+            (BuiltinNodeKind::ContainerAdd, "Add (Container)"),
+            (BuiltinNodeKind::ContainerRemove, "Remove (Container)"),
+            (BuiltinNodeKind::ContainerGet, "Get Element (Container)"),
+            (BuiltinNodeKind::ContainerLength, "Length (Container)"),
+            (BuiltinNodeKind::ContainerContains, "Contains (Container)"),
+            (BuiltinNodeKind::ContainerFind, "Find (Container)"),
+            (BuiltinNodeKind::ContainerClear, "Clear (Container)"),
+            (BuiltinNodeKind::OnAnimationSignal, "On Animation Signal"),
+            (BuiltinNodeKind::GetActiveState, "Get Active State"),
+            (BuiltinNodeKind::PlayAnimation, "Play Animation"),
+            (BuiltinNodeKind::StopAnimation, "Stop Animation"),
+            (BuiltinNodeKind::OnSoundFinished, "On Sound Finished"),
+            (BuiltinNodeKind::PlaySound, "Play Sound"),
+            (BuiltinNodeKind::StopSound, "Stop Sound"),
+            (BuiltinNodeKind::SpawnSound, "Spawn Sound At Location"),
+            (BuiltinNodeKind::FadeInSound, "Fade In Sound"),
+            (BuiltinNodeKind::FadeOutSound, "Fade Out Sound"),
+            (BuiltinNodeKind::SetSoundBus, "Set Sound Bus"),
+            (BuiltinNodeKind::OnSceneLoaded, "On Scene Loaded"),
+            (BuiltinNodeKind::OnSceneUnloaded, "On Scene Unloaded"),
+            (BuiltinNodeKind::LoadScene, "Load Scene"),
+            (BuiltinNodeKind::GetActorsWithBlueprint, "Get Actors With Blueprint"),
+            (BuiltinNodeKind::GetActorsWithTag, "Get Actors With Tag"),
+            (BuiltinNodeKind::HasTag, "Has Tag"),
+            (BuiltinNodeKind::AddTag, "Add Tag"),
+            (BuiltinNodeKind::RemoveTag, "Remove Tag"),
+            (BuiltinNodeKind::CustomEvent, "Custom Event"),
+            (BuiltinNodeKind::SetTimerByEvent, "Set Timer By Event"),
+            (BuiltinNodeKind::ClearTimer, "Clear Timer"),
+            (BuiltinNodeKind::GetDataTableRow, "Get Data Table Row"),
+            (BuiltinNodeKind::ForEachRow, "For Each Row"),
+            (BuiltinNodeKind::BehaviorTask, "Behavior Task"),
+            (BuiltinNodeKind::FinishTask, "Finish Task"),
         ];
         for (kind, label) in builtins.iter().copied() {
             entries.push((label.to_string(), ActionMenuAction::SpawnBuiltin(kind)));
         }
+        for data_type in [DataType::Bool, DataType::I32, DataType::F32, DataType::String] {
+            entries.push((
+                format!("Expect Equal ({})", self.graph.types.type_name(data_type)),
+                ActionMenuAction::SpawnExpectEqual(data_type),
+            ));
+        }
+        for kind in [BuiltinNodeKind::GetGlobal, BuiltinNodeKind::SetGlobal] {
+            let verb = if kind == BuiltinNodeKind::GetGlobal {
+                "Get"
+            } else {
+                "Set"
+            };
+            for data_type in [
+                DataType::Bool,
+                DataType::I32,
+                DataType::F32,
+                DataType::String,
+            ] {
+                entries.push((
+                    format!("{verb} Global ({})", self.graph.types.type_name(data_type)),
+                    ActionMenuAction::SpawnGlobal(kind, data_type),
+                ));
+            }
+        }
+
+        for kind in AbsmParameterKind::ALL {
+            entries.push((
+                format!("Set ABSM Parameter ({})", kind.name()),
+                ActionMenuAction::SpawnSetAbsmParameter(kind),
+            ));
+        }
+
+        for property in SoundProperty::ALL {
+            entries.push((
+                format!("Set Sound {}", property.name()),
+                ActionMenuAction::SpawnSetSoundProperty(property),
+            ));
+        }
+
+        // User interface widgets.
+        for event in WidgetEvent::ALL {
+            entries.push((
+                format!("On Widget {}", event.name()),
+                ActionMenuAction::SpawnWidgetEvent(event),
+            ));
+        }
+        for property in WidgetProperty::ALL {
+            entries.push((
+                format!("Set Widget {}", property.name()),
+                ActionMenuAction::SpawnSetWidgetProperty(property),
+            ));
+        }
+
+        // User-defined types.
+        for (i, def) in self.known_types.structs.iter().enumerate() {
+            entries.push((
+                format!("Make {}", def.name),
+                ActionMenuAction::SpawnMakeStruct(i),
+            ));
+            entries.push((
+                format!("Break {}", def.name),
+                ActionMenuAction::SpawnBreakStruct(i),
+            ));
+            entries.push((
+                format!("Get Data Table Row ({})", def.name),
+                ActionMenuAction::SpawnDataTableNode(BuiltinNodeKind::GetDataTableRow, i),
+            ));
+            entries.push((
+                format!("For Each Row ({})", def.name),
+                ActionMenuAction::SpawnDataTableNode(BuiltinNodeKind::ForEachRow, i),
+            ));
+        }
+        for (i, def) in self.known_types.enums.iter().enumerate() {
+            entries.push((
+                format!("Switch on {}", def.name),
+                ActionMenuAction::SpawnSwitchOnEnum(i),
+            ));
+        }
+
+        // Interfaces.
+        for (i, interface) in self.known_interfaces.iter().enumerate() {
+            if !self.graph.implements_interface(&interface.name) {
+                entries.push((
+                    format!("Implement Interface {}", interface.name),
+                    ActionMenuAction::ImplementInterface(i),
+                ));
+            }
+            entries.push((
+                format!("Does Implement {}", interface.name),
+                ActionMenuAction::SpawnDoesImplementInterface(i),
+            ));
+            for (j, function) in interface.functions.iter().enumerate() {
+                entries.push((
+                    format!("Call {}::{}", interface.name, function.name),
+                    ActionMenuAction::SpawnCallInterface {
+                        interface: i,
+                        function: j,
+                    },
+                ));
+            }
+        }
 
         // Variable shortcuts.
         for (i, var) in self.graph.variables.iter().enumerate() {
@@ -1236,6 +1437,15 @@ impl BlueprintEditor {
             return;
         }
 
+        // Container nodes take the type of the container they are spawned from.
+        if pending.from_type.is_container() && pending.from_dir == PinDirection::Output {
+            if let Some(new_node) = self.graph.nodes.get_mut(&new_node_id) {
+                if new_node.kind.is_container_node() {
+                    new_node.set_container_type(pending.from_type);
+                }
+            }
+        }
+
         let Some(new_node) = self.graph.nodes.get(&new_node_id) else {
             return;
         };
@@ -1305,8 +1515,57 @@ impl BlueprintEditor {
         }
     }
 
+    /// Reloads the struct and enum types from the type assets and migrates the graph to their
+    /// definitions. Types whose assets are gone are still usable in this blueprint.
+    fn refresh_known_types(&mut self, resource_manager: &fyrox::asset::manager::ResourceManager) {
+        self.known_types = collect_types(resource_manager);
+
+        let migrated = self.graph.refresh_types(&self.known_types);
+        if !migrated.is_empty() {
+            Log::warn(format!(
+                "BlueprintEditor: {} changed since the blueprint was saved. The \
+                blueprint was migrated, save it to recompile.",
+                migrated.join(", ")
+            ));
+        }
+        for def in self.graph.types.structs.iter() {
+            if self.known_types.struct_def(def.id()).is_none() {
+                self.known_types.define_struct(def.clone());
+            }
+        }
+        for def in self.graph.types.enums.iter() {
+            if self.known_types.enum_def(def.id()).is_none() {
+                self.known_types.define_enum(def.clone());
+            }
+        }
+    }
+
+    /// Called when struct or enum assets were saved. Returns the path of the opened blueprint.
+    fn on_types_changed(&mut self, changed: &UserTypes, engine: &mut Engine) -> Option<PathBuf> {
+        let path = self.path.clone()?;
+        let uses_changed = changed
+            .structs
+            .iter()
+            .any(|def| self.graph.types.struct_def(def.id()).is_some())
+            || changed
+                .enums
+                .iter()
+                .any(|def| self.graph.types.enum_def(def.id()).is_some());
+        if uses_changed {
+            let resource_manager = engine.resource_manager.clone();
+            self.refresh_known_types(&resource_manager);
+            self.sync_variable_node_pin_types();
+            let ui = engine.user_interfaces.first_mut();
+            self.rebuild_all_graph_views(ui);
+            self.rebuild_variables_panel(ui);
+        }
+        Some(path)
+    }
+
     fn open(&mut self, editor: &mut Editor, path: PathBuf) {
         self.path = Some(path);
+        self.profiler
+            .clear(editor.engine.user_interfaces.first_mut());
 
         if let Some(path) = self.path.as_ref() {
             let title = path
@@ -1366,6 +1625,7 @@ impl BlueprintEditor {
                 if let Some(asset) = guard.as_loaded_ref() {
                     self.version = asset.version;
                     self.prefab_path = asset.prefab_path.clone();
+                    self.ui_path = asset.ui_path.clone();
 
                     match serde_json::from_str::<BlueprintGraph>(&asset.graph_json) {
                         Ok(graph) => {
@@ -1382,6 +1642,8 @@ impl BlueprintEditor {
                     }
 
                     self.graph.ensure_builtin_graphs();
+                    self.known_interfaces = collect_known_interfaces(&resource_manager);
+                    self.refresh_known_types(&resource_manager);
 
                     if self.graph.nodes.is_empty() {
                         self.seed_default_graph();
@@ -1512,19 +1774,44 @@ impl BlueprintEditor {
         let from_data_type = self.get_actual_pin_type(from).unwrap_or(from_pin.data_type);
         let to_data_type = self.get_actual_pin_type(to).unwrap_or(to_pin.data_type);
 
-        if from_data_type != to_data_type {
+        if from_data_type != to_data_type && !self.is_container_target(to, from_data_type) {
             return None;
         }
 
         Some((from, to))
     }
 
+    /// Whether `pin` is the `target` input of a container node, which accepts any container and
+    /// retypes the node when connected.
+    fn is_container_target(&self, pin: PinId, from_type: DataType) -> bool {
+        from_type.is_container()
+            && self.graph.pin(pin).is_some_and(|p| p.name == "target")
+            && self
+                .graph
+                .pin_owner(pin)
+                .and_then(|owner| self.graph.nodes.get(&owner))
+                .is_some_and(|node| node.kind.is_container_node())
+    }
+
     fn apply_connection(&mut self, from: PinId, to: PinId) {
+        let Some(from_pin) = self.graph.pin(from) else {
+            return;
+        };
+        let from_data_type = self.get_actual_pin_type(from).unwrap_or(from_pin.data_type);
+        if self.is_container_target(to, from_data_type) {
+            if let Some(node) = self
+                .graph
+                .pin_owner(to)
+                .and_then(|owner| self.graph.nodes.get_mut(&owner))
+            {
+                node.set_container_type(from_data_type);
+            }
+        }
+
         let (Some(from_pin), Some(to_pin)) = (self.graph.pin(from), self.graph.pin(to)) else {
             return;
         };
 
-        let from_data_type = self.get_actual_pin_type(from).unwrap_or(from_pin.data_type);
         let to_data_type = self.get_actual_pin_type(to).unwrap_or(to_pin.data_type);
 
         if from_pin.direction != PinDirection::Output
@@ -1543,7 +1830,12 @@ impl BlueprintEditor {
         }
 
         if !self.graph.links.iter().any(|l| l.from == from && l.to == to) {
-            self.graph.links.push(Link::exec(from, to));
+            let link = if from_data_type == DataType::Exec {
+                Link::exec(from, to)
+            } else {
+                Link::data(from, to)
+            };
+            self.graph.links.push(link);
         }
     }
 
@@ -1569,7 +1861,8 @@ impl BlueprintEditor {
             let name = self.extra_tabs[i].name.clone();
             let visible = self.visible_nodes_by_graph_name(&name);
             let view = &mut self.extra_tabs[i].view;
-            Self::rebuild_graph_view_for_view(ui, &self.graph, &pin_owner, view, &visible);
+            let heat = self.profiler.heat_map();
+            Self::rebuild_graph_view_for_view(ui, &self.graph, &pin_owner, view, &visible, heat);
         }
     }
 
@@ -1603,7 +1896,8 @@ impl BlueprintEditor {
             BlueprintGraphTab::ConstructionScript => &mut self.construction_view,
         };
 
-        Self::rebuild_graph_view_for_view(ui, &self.graph, pin_owner, view, visible_nodes);
+        let heat = self.profiler.heat_map();
+        Self::rebuild_graph_view_for_view(ui, &self.graph, pin_owner, view, visible_nodes, heat);
     }
 
     fn rebuild_graph_view_for_view(
@@ -1612,6 +1906,7 @@ impl BlueprintEditor {
         pin_owner: &HashMap<PinId, NodeId>,
         view: &mut GraphView,
         visible_nodes: &HashSet<NodeId>,
+        heat: Option<&BlueprintProfile>,
     ) {
         view.clear_ui(ui);
 
@@ -1665,27 +1960,12 @@ impl BlueprintEditor {
                 let actual_data_type = actual_pin_type(node, pin);
 
                 // Unreal-like pin colors based on data type.
-                let pin_color = match actual_data_type {
-                    DataType::Exec => fyrox::core::color::Color::WHITE,
-                    DataType::Bool => fyrox::core::color::Color::opaque(200, 70, 70),
-                    DataType::I32 => fyrox::core::color::Color::opaque(60, 200, 220),
-                    DataType::F32 => fyrox::core::color::Color::opaque(90, 200, 90),
-                    DataType::String => fyrox::core::color::Color::opaque(240, 80, 200),
-                    DataType::Unit => fyrox::core::color::Color::opaque(140, 140, 140),
-                };
+                let pin_color = data_type_color(actual_data_type);
 
                 let type_suffix = if pin.direction == PinDirection::Input
                     && actual_data_type != DataType::Exec
                 {
-                    let ty = match actual_data_type {
-                        DataType::Bool => "bool",
-                        DataType::I32 => "int",
-                        DataType::F32 => "float",
-                        DataType::String => "string",
-                        DataType::Unit => "unit",
-                        DataType::Exec => "exec",
-                    };
-                    format!(" ({ty})")
+                    format!(" ({})", graph.types.type_name(actual_data_type).to_lowercase())
                 } else {
                     String::new()
                 };
@@ -1831,6 +2111,42 @@ impl BlueprintEditor {
                 }
             }
 
+            // Interface and user type nodes are named after their interface or type.
+            let property_label = || {
+                let prop = |key: &str| match node.properties.get(key) {
+                    Some(Value::String(s)) => s.clone(),
+                    _ => String::new(),
+                };
+                match node.kind {
+                    BuiltinNodeKind::InterfaceEvent => format!("Event {}", prop("function")),
+                    BuiltinNodeKind::CallInterface => {
+                        format!("Call {}::{}", prop("interface"), prop("function"))
+                    }
+                    BuiltinNodeKind::MakeStruct => format!("Make {}", prop("type")),
+                    BuiltinNodeKind::BreakStruct => format!("Break {}", prop("type")),
+                    BuiltinNodeKind::SwitchOnEnum => format!("Switch on {}", prop("type")),
+                    BuiltinNodeKind::WidgetEvent => {
+                        format!("On {} {}", prop("widget"), prop("event"))
+                    }
+                    BuiltinNodeKind::SetWidgetProperty => {
+                        format!("Set Widget {}", prop("property"))
+                    }
+                    BuiltinNodeKind::SetAbsmParameter => {
+                        format!("Set ABSM {}", prop("parameter"))
+                    }
+                    BuiltinNodeKind::OnAnimationSignal => format!("On Signal {}", prop("signal")),
+                    BuiltinNodeKind::SetSoundProperty => {
+                        format!("Set Sound {}", prop("property"))
+                    }
+                    BuiltinNodeKind::OnSoundFinished => format!("On {} Finished", prop("sound")),
+                    BuiltinNodeKind::CustomEvent => format!("Event {}", prop("event")),
+                    BuiltinNodeKind::BehaviorTask => format!("Task {}", prop("task")),
+                    BuiltinNodeKind::GetGlobal => format!("Get Global {}", prop("name")),
+                    BuiltinNodeKind::SetGlobal => format!("Set Global {}", prop("name")),
+                    _ => format!("Does Implement {}", prop("interface")),
+                }
+            };
+
             let display_name = match node.kind {
                 BuiltinNodeKind::BeginPlay => "BeginPlay",
                 BuiltinNodeKind::Tick => "Tick",
@@ -1846,12 +2162,74 @@ impl BlueprintEditor {
                 BuiltinNodeKind::SpawnActor => "Spawn Actor",
                 BuiltinNodeKind::GetActorByName => "Get Actor By Name",
                 BuiltinNodeKind::GetActorName => "Get Actor Name",
+                BuiltinNodeKind::Assert => "Assert",
+                BuiltinNodeKind::ExpectEqual => "Expect Equal",
+                BuiltinNodeKind::ForEach => "For Each",
+                BuiltinNodeKind::ContainerAdd => "Add",
+                BuiltinNodeKind::ContainerRemove => "Remove",
+                BuiltinNodeKind::ContainerGet => "Get Element",
+                BuiltinNodeKind::ContainerLength => "Length",
+                BuiltinNodeKind::ContainerContains => "Contains",
+                BuiltinNodeKind::ContainerFind => "Find",
+                BuiltinNodeKind::ContainerClear => "Clear",
+                BuiltinNodeKind::GetActiveState => "Get Active State",
+                BuiltinNodeKind::PlayAnimation => "Play Animation",
+                BuiltinNodeKind::StopAnimation => "Stop Animation",
+                BuiltinNodeKind::PlaySound => "Play Sound",
+                BuiltinNodeKind::StopSound => "Stop Sound",
+                BuiltinNodeKind::SpawnSound => "Spawn Sound At Location",
+                BuiltinNodeKind::FadeInSound => "Fade In Sound",
+                BuiltinNodeKind::FadeOutSound => "Fade Out Sound",
+                BuiltinNodeKind::SetSoundBus => "Set Sound Bus",
+                BuiltinNodeKind::OnSceneLoaded => "On Scene Loaded",
+                BuiltinNodeKind::OnSceneUnloaded => "On Scene Unloaded",
+                BuiltinNodeKind::LoadScene => "Load Scene",
+                BuiltinNodeKind::GetActorsWithBlueprint => "Get Actors With Blueprint",
+                BuiltinNodeKind::GetActorsWithTag => "Get Actors With Tag",
+                BuiltinNodeKind::HasTag => "Has Tag",
+                BuiltinNodeKind::AddTag => "Add Tag",
+                BuiltinNodeKind::RemoveTag => "Remove Tag",
+                BuiltinNodeKind::SetTimerByEvent => "Set Timer By Event",
+                BuiltinNodeKind::ClearTimer => "Clear Timer",
+                BuiltinNodeKind::GetDataTableRow => "Get Data Table Row",
+                BuiltinNodeKind::ForEachRow => "For Each Row",
+                BuiltinNodeKind::FinishTask => "Finish Task",
+                BuiltinNodeKind::InterfaceEvent
+                | BuiltinNodeKind::CallInterface
+                | BuiltinNodeKind::DoesImplementInterface
+                | BuiltinNodeKind::MakeStruct
+                | BuiltinNodeKind::BreakStruct
+                | BuiltinNodeKind::SwitchOnEnum
+                | BuiltinNodeKind::WidgetEvent
+                | BuiltinNodeKind::SetWidgetProperty
+                | BuiltinNodeKind::SetAbsmParameter
+                | BuiltinNodeKind::OnAnimationSignal
+                | BuiltinNodeKind::SetSoundProperty
+                | BuiltinNodeKind::OnSoundFinished
+                | BuiltinNodeKind::CustomEvent
+                | BuiltinNodeKind::BehaviorTask
+                | BuiltinNodeKind::GetGlobal
+                | BuiltinNodeKind::SetGlobal => "",
             }
             .to_string();
+            let display_name = if display_name.is_empty() {
+                property_label()
+            } else {
+                display_name
+            };
 
             // Unreal-like header colors based on node type.
             let header_color = match node.kind {
-                BuiltinNodeKind::BeginPlay | BuiltinNodeKind::Tick => {
+                BuiltinNodeKind::BeginPlay
+                | BuiltinNodeKind::Tick
+                | BuiltinNodeKind::InterfaceEvent
+                | BuiltinNodeKind::WidgetEvent
+                | BuiltinNodeKind::OnAnimationSignal
+                | BuiltinNodeKind::OnSoundFinished
+                | BuiltinNodeKind::OnSceneLoaded
+                | BuiltinNodeKind::OnSceneUnloaded
+                | BuiltinNodeKind::CustomEvent
+                | BuiltinNodeKind::BehaviorTask => {
                     // Event nodes = red
                     fyrox::core::color::Color::opaque(180, 40, 40)
                 }
@@ -1859,15 +2237,34 @@ impl BlueprintEditor {
                     // Construction = dark blue
                     fyrox::core::color::Color::opaque(30, 80, 160)
                 }
-                BuiltinNodeKind::Print | BuiltinNodeKind::RhaiScript => {
+                BuiltinNodeKind::Print
+                | BuiltinNodeKind::RhaiScript
+                | BuiltinNodeKind::Assert
+                | BuiltinNodeKind::ExpectEqual => {
                     // Utility/debug = cyan
                     fyrox::core::color::Color::opaque(40, 140, 160)
                 }
-                BuiltinNodeKind::Branch => {
+                BuiltinNodeKind::Branch
+                | BuiltinNodeKind::ForEach
+                | BuiltinNodeKind::SwitchOnEnum
+                | BuiltinNodeKind::ForEachRow
+                | BuiltinNodeKind::FinishTask => {
                     // Flow control = gray
                     fyrox::core::color::Color::opaque(90, 90, 90)
                 }
-                BuiltinNodeKind::GetVariable | BuiltinNodeKind::SetVariable => {
+                BuiltinNodeKind::GetVariable
+                | BuiltinNodeKind::SetVariable
+                | BuiltinNodeKind::GetGlobal
+                | BuiltinNodeKind::SetGlobal
+                | BuiltinNodeKind::ContainerAdd
+                | BuiltinNodeKind::ContainerRemove
+                | BuiltinNodeKind::ContainerGet
+                | BuiltinNodeKind::ContainerLength
+                | BuiltinNodeKind::ContainerContains
+                | BuiltinNodeKind::ContainerFind
+                | BuiltinNodeKind::ContainerClear
+                | BuiltinNodeKind::MakeStruct
+                | BuiltinNodeKind::BreakStruct => {
                     // Variable nodes = green
                     fyrox::core::color::Color::opaque(40, 140, 60)
                 }
@@ -1876,10 +2273,50 @@ impl BlueprintEditor {
                 | BuiltinNodeKind::SetActorTransform
                 | BuiltinNodeKind::SpawnActor
                 | BuiltinNodeKind::GetActorByName
-                | BuiltinNodeKind::GetActorName => {
+                | BuiltinNodeKind::GetActorName
+                | BuiltinNodeKind::SetWidgetProperty
+                | BuiltinNodeKind::SetAbsmParameter
+                | BuiltinNodeKind::GetActiveState
+                | BuiltinNodeKind::PlayAnimation
+                | BuiltinNodeKind::StopAnimation
+                | BuiltinNodeKind::PlaySound
+                | BuiltinNodeKind::StopSound
+                | BuiltinNodeKind::SpawnSound
+                | BuiltinNodeKind::SetSoundProperty
+                | BuiltinNodeKind::FadeInSound
+                | BuiltinNodeKind::FadeOutSound
+                | BuiltinNodeKind::SetSoundBus
+                | BuiltinNodeKind::LoadScene
+                | BuiltinNodeKind::GetActorsWithBlueprint
+                | BuiltinNodeKind::GetActorsWithTag
+                | BuiltinNodeKind::HasTag
+                | BuiltinNodeKind::AddTag
+                | BuiltinNodeKind::RemoveTag
+                | BuiltinNodeKind::SetTimerByEvent
+                | BuiltinNodeKind::ClearTimer
+                | BuiltinNodeKind::GetDataTableRow => {
                     // World nodes = orange
                     fyrox::core::color::Color::opaque(200, 120, 40)
                 }
+                BuiltinNodeKind::CallInterface | BuiltinNodeKind::DoesImplementInterface => {
+                    // Interface messages = blue
+                    fyrox::core::color::Color::opaque(50, 100, 190)
+                }
+            };
+
+            // Profiler heat map: hot nodes turn red and show their total time.
+            let (header_color, display_name) = match heat {
+                Some(profile) => {
+                    let display_name = match profile.nodes.get(node_id) {
+                        Some(sample) => format!(
+                            "{display_name} ({:.2} ms)",
+                            sample.total.as_secs_f64() * 1000.0
+                        ),
+                        None => display_name,
+                    };
+                    (heat_color(header_color, profile.heat(*node_id)), display_name)
+                }
+                None => (header_color, display_name),
             };
 
             let selected_header_color = fyrox::core::color::Color::opaque(
@@ -1976,8 +2413,10 @@ impl BlueprintEditor {
                 .unwrap_or(DataType::Unit);
 
             let is_exec = data_type == DataType::Exec;
+            // Exec wires take the heat of the node they lead to.
+            let wire_heat = heat.filter(|_| is_exec).map(|profile| profile.heat(dst_node));
 
-            spawn_connection_view(ui, view, link.from, link.to, data_type, is_exec);
+            spawn_connection_view(ui, view, link.from, link.to, data_type, is_exec, wire_heat);
         }
     }
 
@@ -2106,7 +2545,8 @@ impl BlueprintEditor {
         let mut view = GraphView::new(canvas);
         let visible = self.visible_nodes_by_graph_name(name);
         let pin_owner = self.pin_owner_map();
-        Self::rebuild_graph_view_for_view(ui, &self.graph, &pin_owner, &mut view, &visible);
+        let heat = self.profiler.heat_map();
+        Self::rebuild_graph_view_for_view(ui, &self.graph, &pin_owner, &mut view, &visible, heat);
 
         self.extra_tabs.push(ExtraTab {
             uuid,
@@ -2180,39 +2620,121 @@ impl BlueprintEditor {
             self.details_bindings
                 .insert(tb, DetailsBinding::VariableName { index: var_index });
 
-            let label = TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(2.0)))
-                .with_text("Type")
+            let data_type = var.data_type;
+            let default_value = var.default_value.clone();
+            let user_types = self.user_type_options();
+            let mut dropdown = |ui: &mut UserInterface,
+                                title: &str,
+                                options: &[&str],
+                                selected: usize,
+                                binding: DetailsBinding| {
+                let label =
+                    TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(2.0)))
+                        .with_text(title)
+                        .build(&mut ui.build_ctx());
+                ui.send(label, WidgetMessage::LinkWith(self.details_panel));
+                self.details_widgets.push(label);
+
+                let items = options
+                    .iter()
+                    .map(|option| make_dropdown_list_option(&mut ui.build_ctx(), option))
+                    .collect::<Vec<_>>();
+                let dd = DropdownListBuilder::new(
+                    WidgetBuilder::new()
+                        .with_margin(Thickness::uniform(2.0))
+                        .with_height(24.0),
+                )
+                .with_items(items)
+                .with_selected(selected)
                 .build(&mut ui.build_ctx());
-            ui.send(label, WidgetMessage::LinkWith(self.details_panel));
-            self.details_widgets.push(label);
 
-            let items = vec![
-                make_dropdown_list_option(&mut ui.build_ctx(), "Bool"),
-                make_dropdown_list_option(&mut ui.build_ctx(), "I32"),
-                make_dropdown_list_option(&mut ui.build_ctx(), "F32"),
-                make_dropdown_list_option(&mut ui.build_ctx(), "String"),
-            ];
+                ui.send(dd, WidgetMessage::LinkWith(self.details_panel));
+                self.details_widgets.push(dd);
+                self.details_bindings.insert(dd, binding);
+            };
 
-            let selected = match var.data_type {
-                DataType::Bool => 0,
-                DataType::I32 => 1,
-                DataType::F32 => 2,
-                _ => 3,
+            const ELEMENT_TYPES: [&str; 4] = ["Bool", "I32", "F32", "String"];
+            let element_index = |element: ElementType| {
+                ElementType::ALL
+                    .iter()
+                    .position(|e| *e == element)
+                    .unwrap_or(3)
             };
 
-            let dd = DropdownListBuilder::new(
-                WidgetBuilder::new()
-                    .with_margin(Thickness::uniform(2.0))
-                    .with_height(24.0),
-            )
-            .with_items(items)
-            .with_selected(selected)
-            .build(&mut ui.build_ctx());
+            dropdown(
+                ui,
+                "Container",
+                &["Single", "Array", "Set", "Map"],
+                container_index(data_type),
+                DetailsBinding::VariableContainer { index: var_index },
+            );
 
-            ui.send(dd, WidgetMessage::LinkWith(self.details_panel));
-            self.details_widgets.push(dd);
-            self.details_bindings
-                .insert(dd, DetailsBinding::VariableType { index: var_index });
+            // Single values may also have a struct or enum type, listed after the element types.
+            let mut single_types = ELEMENT_TYPES.to_vec();
+            single_types.extend(user_types.iter().map(|(name, _)| name.as_str()));
+            let (title, options, selected) = match data_type {
+                DataType::Array(element) | DataType::Set(element) => {
+                    ("Element Type", &ELEMENT_TYPES[..], element_index(element))
+                }
+                DataType::Map(key, _) => ("Key Type", &ELEMENT_TYPES[..], element_index(key)),
+                DataType::Struct(_) | DataType::Enum(_) => (
+                    "Type",
+                    &single_types[..],
+                    user_types
+                        .iter()
+                        .position(|(_, dt)| *dt == data_type)
+                        .map_or(0, |i| ELEMENT_TYPES.len() + i),
+                ),
+                dt => (
+                    "Type",
+                    &single_types[..],
+                    element_index(ElementType::from_data_type(dt).unwrap_or(ElementType::String)),
+                ),
+            };
+            dropdown(
+                ui,
+                title,
+                options,
+                selected,
+                DetailsBinding::VariableType { index: var_index },
+            );
+
+            if let DataType::Map(_, value) = data_type {
+                dropdown(
+                    ui,
+                    "Value Type",
+                    &ELEMENT_TYPES,
+                    element_index(value),
+                    DetailsBinding::VariableValueType { index: var_index },
+                );
+            }
+
+            if data_type.is_container()
+                || matches!(data_type, DataType::Struct(_) | DataType::Enum(_))
+            {
+                let label =
+                    TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(2.0)))
+                        .with_text("Default (JSON)")
+                        .build(&mut ui.build_ctx());
+                ui.send(label, WidgetMessage::LinkWith(self.details_panel));
+                self.details_widgets.push(label);
+
+                let json = default_value
+                    .unwrap_or_else(|| self.graph.types.default_value(data_type))
+                    .to_json()
+                    .to_string();
+                let tb = TextBoxBuilder::new(
+                    WidgetBuilder::new()
+                        .with_margin(Thickness::uniform(2.0))
+                        .with_height(24.0),
+                )
+                .with_text(json)
+                .build(&mut ui.build_ctx());
+                ui.send(tb, WidgetMessage::LinkWith(self.details_panel));
+                self.details_widgets.push(tb);
+                self.details_bindings
+                    .insert(tb, DetailsBinding::VariableDefault { index: var_index });
+            }
             return;
         }
 
@@ -2292,6 +2814,50 @@ impl BlueprintEditor {
                 ui.send(hint, WidgetMessage::LinkWith(self.details_panel));
                 self.details_widgets.push(hint);
             }
+            BuiltinNodeKind::WidgetEvent
+            | BuiltinNodeKind::OnAnimationSignal
+            | BuiltinNodeKind::OnSoundFinished
+            | BuiltinNodeKind::CustomEvent
+            | BuiltinNodeKind::BehaviorTask
+            | BuiltinNodeKind::GetGlobal
+            | BuiltinNodeKind::SetGlobal => {
+                let (title, key) = match node.kind {
+                    BuiltinNodeKind::WidgetEvent => ("Widget", "widget"),
+                    BuiltinNodeKind::OnAnimationSignal => ("Signal", "signal"),
+                    BuiltinNodeKind::CustomEvent => ("Event", "event"),
+                    BuiltinNodeKind::BehaviorTask => ("Task", "task"),
+                    BuiltinNodeKind::GetGlobal | BuiltinNodeKind::SetGlobal => ("Global", "name"),
+                    _ => ("Sound", "sound"),
+                };
+                let label = TextBuilder::new(
+                    WidgetBuilder::new().with_margin(Thickness::uniform(2.0)),
+                )
+                .with_text(title)
+                .build(&mut ui.build_ctx());
+                ui.send(label, WidgetMessage::LinkWith(self.details_panel));
+                self.details_widgets.push(label);
+
+                let initial = node
+                    .properties
+                    .get(key)
+                    .and_then(|v| match v {
+                        Value::String(s) => Some(s.as_str()),
+                        _ => None,
+                    })
+                    .unwrap_or("");
+
+                let tb = TextBoxBuilder::new(
+                    WidgetBuilder::new()
+                        .with_margin(Thickness::uniform(2.0))
+                        .with_height(24.0),
+                )
+                .with_text(initial)
+                .build(&mut ui.build_ctx());
+                ui.send(tb, WidgetMessage::LinkWith(self.details_panel));
+                self.details_widgets.push(tb);
+                self.details_bindings
+                    .insert(tb, DetailsBinding::NodeProp { node: node_id, key });
+            }
             BuiltinNodeKind::GetVariable => {
                 let label = TextBuilder::new(
                     WidgetBuilder::new().with_margin(Thickness::uniform(2.0)),
@@ -2432,7 +2998,7 @@ impl BlueprintEditor {
 
         for (index, var) in self.graph.variables.iter().enumerate() {
             let select = {
-                let label = format!("{} : {}", var.name, data_type_label(var.data_type));
+                let label = format!("{} : {}", var.name, self.graph.types.type_name(var.data_type));
                 let b = ButtonBuilder::new(
                     WidgetBuilder::new()
                         .with_height(24.0)
@@ -2574,9 +3140,34 @@ impl BlueprintEditor {
                 _ => {}
             }
         }
+
+        // Container nodes follow the type of the container variable wired into `target`.
+        let mut retyped = Vec::new();
+        for (node_id, node) in self.graph.nodes.iter() {
+            if !node.kind.is_container_node() {
+                continue;
+            }
+            let Some(target) = node.pin_named("target") else {
+                continue;
+            };
+            let Some(link) = self.graph.links.iter().find(|l| l.to == target) else {
+                continue;
+            };
+            if let Some(dt) = self
+                .get_actual_pin_type(link.from)
+                .filter(|dt| dt.is_container())
+            {
+                retyped.push((*node_id, dt));
+            }
+        }
+        for (node_id, dt) in retyped {
+            if let Some(node) = self.graph.nodes.get_mut(&node_id) {
+                node.set_container_type(dt);
+            }
+        }
     }
 
-    fn save_to_disk(&mut self, engine: &mut Engine) {
+    fn save_to_disk(&mut self, engine: &mut Engine, save_format: Format) {
         let Some(path) = self.path.as_ref() else {
             return;
         };
@@ -2606,7 +3197,7 @@ impl BlueprintEditor {
                 .or_else(|| Some(prefab_abs.to_string_lossy().to_string()));
         }
 
-        if let Err(err) = self.save_preview_prefab(&prefab_abs, engine) {
+        if let Err(err) = self.save_preview_prefab(&prefab_abs, engine, save_format) {
             Log::err(format!("BlueprintEditor: failed to save prefab: {err}"));
             return;
         }
@@ -2615,6 +3206,7 @@ impl BlueprintEditor {
             version: self.version,
             graph_json,
             prefab_path: self.prefab_path.clone(),
+            ui_path: self.ui_path.clone(),
         };
 
         if let Err(err) = asset.save(path) {
@@ -2631,7 +3223,75 @@ impl BlueprintEditor {
         Log::info(format!("Saved blueprint: {}", path.display()));
     }
 
-    fn handle_ui_message(&mut self, message: &UiMessage, engine: &mut Engine) {
+    /// Writes a Rust script generated from the graph into `game/src` of the project (or next to the
+    /// blueprint if there is no such folder). Hand-written files are never overwritten.
+    fn nativize_to_disk(&mut self, engine: &mut Engine, save_format: Format) {
+        let Some(path) = self.path.as_ref() else {
+            return;
+        };
+        let Some(stem) = path.file_stem().map(|s| s.to_string_lossy().to_string()) else {
+            return;
+        };
+
+        let game_src = PathBuf::from("game/src");
+        let dir = if game_src.is_dir() {
+            game_src
+        } else {
+            path.parent().map(PathBuf::from).unwrap_or_default()
+        };
+        let file_name = format!("{}.rs", stem.to_lowercase().replace([' ', '-'], "_"));
+        let out_path = dir.join(file_name);
+
+        let existing = std::fs::read_to_string(&out_path).ok();
+        if let Some(existing) = existing.as_ref() {
+            if !existing.starts_with(GENERATED_HEADER) {
+                Log::err(format!(
+                    "BlueprintEditor: {} exists and was not generated from a blueprint, \
+                    refusing to overwrite it",
+                    out_path.display()
+                ));
+                return;
+            }
+        }
+
+        // Scenes refer to scripts by their type UUID, so it must survive regeneration. It is
+        // generated once and stored in the blueprint. Scripts generated before that keep the UUID
+        // of the previously generated file.
+        let type_uuid = match self.graph.native_type_uuid.clone() {
+            Some(type_uuid) => type_uuid,
+            None => {
+                let type_uuid = existing
+                    .as_deref()
+                    .and_then(generated_type_uuid)
+                    .map(str::to_string)
+                    .unwrap_or_else(|| Uuid::new_v4().to_string());
+                self.graph.native_type_uuid = Some(type_uuid.clone());
+                self.save_to_disk(engine, save_format);
+                type_uuid
+            }
+        };
+        let source = match nativize(&self.graph, &stem, &type_uuid) {
+            Ok(source) => source,
+            Err(err) => {
+                Log::err(format!("BlueprintEditor: unable to nativize {stem}: {err}"));
+                return;
+            }
+        };
+
+        match std::fs::write(&out_path, source) {
+            Ok(()) => Log::info(format!(
+                "Nativized blueprint into {}. Add the module to your game and call its \
+                `register` function in `Plugin::register` to use it.",
+                out_path.display()
+            )),
+            Err(err) => Log::err(format!(
+                "BlueprintEditor: failed to write {}: {err}",
+                out_path.display()
+            )),
+        }
+    }
+
+    fn handle_ui_message(&mut self, message: &UiMessage, engine: &mut Engine, save_format: Format) {
         // Right-click anywhere on a graph canvas (or its children) opens the Unreal-like action menu.
         if let Some(WidgetMessage::MouseDown { button, .. }) = message.data() {
             if *button == MouseButton::Right {
@@ -2767,9 +3427,21 @@ impl BlueprintEditor {
             }
         }
 
+        {
+            let ui = engine.user_interfaces.first_mut();
+            let path = self.path.as_deref();
+            if self.profiler.handle_ui_message(message, ui, &self.graph, path) {
+                self.rebuild_all_graph_views(ui);
+            }
+        }
+
         if let Some(ButtonMessage::Click) = message.data() {
             if message.destination() == self.save {
-                self.save_to_disk(engine);
+                self.save_to_disk(engine, save_format);
+            }
+
+            if message.destination() == self.nativize {
+                self.nativize_to_disk(engine, save_format);
             }
 
             if message.destination() == self.add_component {
@@ -2903,6 +3575,103 @@ impl BlueprintEditor {
                             spawned = Some(self.graph.add_node(n));
                         }
                     }
+                    ActionMenuAction::SpawnCallInterface {
+                        interface,
+                        function,
+                    } => {
+                        if let Some(interface) = self.known_interfaces.get(interface) {
+                            if let Some(function) = interface.functions.get(function) {
+                                let mut n = Node::interface_call(&interface.name, function);
+                                n.graph = graph_name.clone();
+                                n.position = [pos.x, pos.y];
+                                spawned = Some(self.graph.add_node(n));
+                            }
+                        }
+                    }
+                    ActionMenuAction::SpawnDoesImplementInterface(index) => {
+                        if let Some(interface) = self.known_interfaces.get(index) {
+                            let mut n = Node::new(BuiltinNodeKind::DoesImplementInterface);
+                            n.graph = graph_name.clone();
+                            n.position = [pos.x, pos.y];
+                            n.set_property_string("interface", interface.name.clone());
+                            spawned = Some(self.graph.add_node(n));
+                        }
+                    }
+                    ActionMenuAction::SpawnExpectEqual(data_type) => {
+                        let mut n = Node::expect_equal(data_type);
+                        n.graph = graph_name.clone();
+                        n.position = [pos.x, pos.y];
+                        spawned = Some(self.graph.add_node(n));
+                    }
+                    ActionMenuAction::SpawnGlobal(kind, data_type) => {
+                        let mut n = Node::global(kind, "", data_type);
+                        n.graph = graph_name.clone();
+                        n.position = [pos.x, pos.y];
+                        spawned = Some(self.graph.add_node(n));
+                    }
+                    ActionMenuAction::SpawnMakeStruct(index)
+                    | ActionMenuAction::SpawnBreakStruct(index) => {
+                        if let Some(def) = self.known_types.structs.get(index).cloned() {
+                            self.graph.define_struct(def.clone());
+                            let mut n = match action {
+                                ActionMenuAction::SpawnMakeStruct(_) => Node::make_struct(&def),
+                                _ => Node::break_struct(&def),
+                            };
+                            n.graph = graph_name.clone();
+                            n.position = [pos.x, pos.y];
+                            spawned = Some(self.graph.add_node(n));
+                        }
+                    }
+                    ActionMenuAction::SpawnDataTableNode(kind, index) => {
+                        if let Some(def) = self.known_types.structs.get(index).cloned() {
+                            self.graph.define_struct(def.clone());
+                            let mut n = Node::data_table(kind, &def);
+                            n.graph = graph_name.clone();
+                            n.position = [pos.x, pos.y];
+                            spawned = Some(self.graph.add_node(n));
+                        }
+                    }
+                    ActionMenuAction::SpawnSwitchOnEnum(index) => {
+                        if let Some(def) = self.known_types.enums.get(index).cloned() {
+                            self.graph.define_enum(def.clone());
+                            let mut n = Node::switch_on_enum(&def);
+                            n.graph = graph_name.clone();
+                            n.position = [pos.x, pos.y];
+                            spawned = Some(self.graph.add_node(n));
+                        }
+                    }
+                    ActionMenuAction::SpawnWidgetEvent(event) => {
+                        let mut n = Node::widget_event("", event);
+                        n.graph = graph_name.clone();
+                        n.position = [pos.x, pos.y];
+                        spawned = Some(self.graph.add_node(n));
+                    }
+                    ActionMenuAction::SpawnSetWidgetProperty(property) => {
+                        let mut n = Node::set_widget_property(property);
+                        n.graph = graph_name.clone();
+                        n.position = [pos.x, pos.y];
+                        spawned = Some(self.graph.add_node(n));
+                    }
+                    ActionMenuAction::SpawnSetAbsmParameter(kind) => {
+                        let mut n = Node::set_absm_parameter(kind);
+                        n.graph = graph_name.clone();
+                        n.position = [pos.x, pos.y];
+                        spawned = Some(self.graph.add_node(n));
+                    }
+                    ActionMenuAction::SpawnSetSoundProperty(property) => {
+                        let mut n = Node::set_sound_property(property);
+                        n.graph = graph_name.clone();
+                        n.position = [pos.x, pos.y];
+                        spawned = Some(self.graph.add_node(n));
+                    }
+                    ActionMenuAction::ImplementInterface(index) => {
+                        if let Some(interface) = self.known_interfaces.get(index).cloned() {
+                            self.graph.implement_interface(interface);
+                            self.rebuild_all_graph_views(ui);
+                            self.rebuild_graphs_panel(ui);
+                            self.rebuild_functions_panel(ui);
+                        }
+                    }
                 }
 
                 if let Some(node_id) = spawned {
@@ -3134,32 +3903,87 @@ impl BlueprintEditor {
                         self.rebuild_all_graph_views(ui);
                         self.rebuild_details(ui);
                     }
-                    DetailsBinding::VariableType { .. } => {}
+                    DetailsBinding::VariableDefault { index } => {
+                        let Some(var) = self.graph.variables.get_mut(index) else {
+                            return;
+                        };
+                        // Keep the last valid literal while the user is typing.
+                        let types = &self.graph.types;
+                        if let Some(value) = serde_json::from_str(text)
+                            .ok()
+                            .and_then(|json| types.value_from_json(&json, var.data_type))
+                        {
+                            var.default_value = Some(value);
+                        }
+                    }
+                    DetailsBinding::VariableType { .. }
+                    | DetailsBinding::VariableContainer { .. }
+                    | DetailsBinding::VariableValueType { .. } => {}
                 }
             }
         }
 
         if let Some(DropdownListMessage::Selection(Some(selection))) = message.data() {
             if let Some(binding) = self.details_bindings.get(&message.destination()).copied() {
-                if let DetailsBinding::VariableType { index } = binding {
+                let index = match binding {
+                    DetailsBinding::VariableType { index }
+                    | DetailsBinding::VariableContainer { index }
+                    | DetailsBinding::VariableValueType { index } => Some(index),
+                    _ => None,
+                };
+                if let Some(index) = index {
+                    // Struct and enum types follow the element types in the "Type" dropdown.
+                    let user_type = match binding {
+                        DetailsBinding::VariableType { .. } => selection
+                            .checked_sub(ElementType::ALL.len())
+                            .and_then(|i| self.user_type_options().get(i).map(|(_, dt)| *dt)),
+                        _ => None,
+                    };
+                    if let Some(user_type) = user_type {
+                        match user_type {
+                            DataType::Struct(id) => {
+                                if let Some(def) = self.known_types.struct_def(id).cloned() {
+                                    self.graph.define_struct(def);
+                                }
+                            }
+                            DataType::Enum(id) => {
+                                if let Some(def) = self.known_types.enum_def(id).cloned() {
+                                    self.graph.define_enum(def);
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                    let types = &self.graph.types;
                     let Some(var) = self.graph.variables.get_mut(index) else {
                         return;
                     };
 
-                    var.data_type = match *selection {
-                        0 => DataType::Bool,
-                        1 => DataType::I32,
-                        2 => DataType::F32,
-                        _ => DataType::String,
+                    let element_type = |dt: Option<DataType>| {
+                        dt.and_then(ElementType::from_data_type)
+                            .unwrap_or(ElementType::String)
+                    };
+                    let mut container = container_index(var.data_type);
+                    let mut element = match var.data_type {
+                        DataType::Array(element)
+                        | DataType::Set(element)
+                        | DataType::Map(element, _) => element,
+                        dt => element_type(Some(dt)),
                     };
+                    let mut value = element_type(var.data_type.element_type());
+                    let selected = ElementType::ALL
+                        .get(*selection)
+                        .copied()
+                        .unwrap_or(ElementType::String);
+                    match binding {
+                        DetailsBinding::VariableContainer { .. } => container = *selection,
+                        DetailsBinding::VariableValueType { .. } => value = selected,
+                        _ => element = selected,
+                    }
 
-                    var.default_value = Some(match var.data_type {
-                        DataType::Bool => Value::Bool(false),
-                        DataType::I32 => Value::I32(0),
-                        DataType::F32 => Value::F32(0.0),
-                        DataType::String => Value::String(String::new()),
-                        _ => Value::Unit,
-                    });
+                    var.data_type = user_type
+                        .unwrap_or_else(|| variable_data_type(container, element, value));
+                    var.default_value = Some(types.default_value(var.data_type));
 
                     self.sync_variable_node_pin_types();
 
@@ -3171,6 +3995,21 @@ impl BlueprintEditor {
         }
     }
 
+    /// Struct and enum types a variable can have, in the order of the "Type" dropdown.
+    fn user_type_options(&self) -> Vec<(String, DataType)> {
+        let structs = self
+            .known_types
+            .structs
+            .iter()
+            .map(|def| (def.name.clone(), def.data_type()));
+        let enums = self
+            .known_types
+            .enums
+            .iter()
+            .map(|def| (def.name.clone(), def.data_type()));
+        structs.chain(enums).collect()
+    }
+
     fn handle_canvas_message(
         &mut self,
         message: &UiMessage,
@@ -3538,6 +4377,7 @@ fn spawn_connection_view(
     to: PinId,
     data_type: DataType,
     is_exec: bool,
+    heat: Option<f32>,
 ) {
     let Some(source_socket) = view.pin_to_socket.get(&from).copied() else {
         return;
@@ -3561,13 +4401,10 @@ fn spawn_connection_view(
     };
 
     // Unreal-like: exec is white; data wires are type-colored (matching pin colors).
-    let base_color = match data_type {
-        DataType::Exec => fyrox::core::color::Color::WHITE,
-        DataType::Bool => fyrox::core::color::Color::opaque(200, 70, 70),
-        DataType::I32 => fyrox::core::color::Color::opaque(60, 200, 220),
-        DataType::F32 => fyrox::core::color::Color::opaque(90, 200, 90),
-        DataType::String => fyrox::core::color::Color::opaque(240, 80, 200),
-        DataType::Unit => fyrox::core::color::Color::opaque(140, 140, 140),
+    let base_color = data_type_color(data_type);
+    let base_color = match heat {
+        Some(heat) => heat_color(base_color, heat),
+        None => base_color,
     };
 
     let hover_color = fyrox::core::color::Color::opaque(
@@ -3598,14 +4435,146 @@ fn set_pin_data_type_by_name(node: &mut Node, pin_name: &str, data_type: DataTyp
     }
 }
 
-fn data_type_label(dt: DataType) -> &'static str {
+/// Loads every `.blueprint_interface` asset known to the resource registry.
+fn collect_known_interfaces(
+    resource_manager: &fyrox::asset::manager::ResourceManager,
+) -> Vec<BlueprintInterface> {
+    let paths = {
+        let registry = resource_manager.state().resource_registry.clone();
+        let registry = registry.safe_lock();
+        registry
+            .inner()
+            .values()
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|ext| ext == BlueprintInterfaceLoader::EXT)
+            })
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+
+    let mut interfaces = Vec::new();
+    for path in paths {
+        match block_on(resource_manager.request::<BlueprintInterfaceAsset>(&path)) {
+            Ok(resource) => {
+                if let Some(asset) = resource.data_ref().as_loaded_ref() {
+                    interfaces.push(asset.to_interface());
+                }
+            }
+            Err(err) => Log::err(format!(
+                "BlueprintEditor: failed to load interface {}: {err:?}",
+                path.display()
+            )),
+        }
+    }
+    interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+    interfaces
+}
+
+/// Migrates the `.blueprint` assets that use any of the `changed` types to their new definitions
+/// and saves them, so games do not start with stale copies of the types. `skip` is the blueprint
+/// opened in the editor, which is migrated in memory instead.
+fn migrate_dependent_blueprints(
+    resource_manager: &fyrox::asset::manager::ResourceManager,
+    changed: &UserTypes,
+    skip: Option<&Path>,
+) {
+    let skip = skip.and_then(|path| make_relative_path(path).ok());
+    let paths = {
+        let registry = resource_manager.state().resource_registry.clone();
+        let registry = registry.safe_lock();
+        registry
+            .inner()
+            .values()
+            .filter(|path| path.extension().is_some_and(|ext| ext == BlueprintLoader::EXT))
+            .filter(|path| skip.as_deref() != Some(path.as_path()))
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+
+    for path in paths {
+        let resource = match block_on(resource_manager.request::<BlueprintAsset>(&path)) {
+            Ok(resource) => resource,
+            Err(err) => {
+                Log::err(format!(
+                    "BlueprintEditor: failed to load blueprint {}: {err:?}",
+                    path.display()
+                ));
+                continue;
+            }
+        };
+        let Some(mut asset) = resource.data_ref().as_loaded_ref().cloned() else {
+            continue;
+        };
+        let Ok(mut graph) = serde_json::from_str::<BlueprintGraph>(&asset.graph_json) else {
+            continue;
+        };
+
+        let migrated = graph.refresh_types(changed);
+        if migrated.is_empty() {
+            continue;
+        }
+        asset.graph_json = match serde_json::to_string_pretty(&graph) {
+            Ok(json) => json,
+            Err(err) => {
+                Log::err(format!("BlueprintEditor: failed to serialize graph: {err}"));
+                continue;
+            }
+        };
+        if let Err(err) = asset.save(&path) {
+            Log::err(format!(
+                "BlueprintEditor: failed to save blueprint {}: {err:?}",
+                path.display()
+            ));
+            continue;
+        }
+        resource_manager
+            .state()
+            .reload_resource(resource.into_untyped());
+
+        Log::info(format!(
+            "BlueprintEditor: {} migrated to new definitions of {}",
+            path.display(),
+            migrated.join(", ")
+        ));
+    }
+}
+
+/// Unreal-like pin and wire colors: exec is white, data is colored by type. Containers use the
+/// color of their elements (values for maps).
+fn data_type_color(dt: DataType) -> fyrox::core::color::Color {
     match dt {
-        DataType::Bool => "Bool",
-        DataType::I32 => "Int",
-        DataType::F32 => "Float",
-        DataType::String => "String",
-        DataType::Exec => "Exec",
-        DataType::Unit => "Unit",
+        DataType::Exec => fyrox::core::color::Color::WHITE,
+        DataType::Bool => fyrox::core::color::Color::opaque(200, 70, 70),
+        DataType::I32 => fyrox::core::color::Color::opaque(60, 200, 220),
+        DataType::F32 => fyrox::core::color::Color::opaque(90, 200, 90),
+        DataType::String => fyrox::core::color::Color::opaque(240, 80, 200),
+        DataType::Unit => fyrox::core::color::Color::opaque(140, 140, 140),
+        DataType::Array(element) | DataType::Set(element) | DataType::Map(_, element) => {
+            data_type_color(element.data_type())
+        }
+        DataType::Struct(_) => fyrox::core::color::Color::opaque(0, 90, 200),
+        DataType::Enum(_) => fyrox::core::color::Color::opaque(0, 140, 120),
+    }
+}
+
+/// Index of the container kind in the "Container" dropdown of variables.
+fn container_index(dt: DataType) -> usize {
+    match dt {
+        DataType::Array(_) => 1,
+        DataType::Set(_) => 2,
+        DataType::Map(..) => 3,
+        _ => 0,
+    }
+}
+
+/// Variable type from the selections of the "Container", "Type" and "Value Type" dropdowns.
+fn variable_data_type(container: usize, element: ElementType, value: ElementType) -> DataType {
+    match container {
+        1 => DataType::Array(element),
+        2 => DataType::Set(element),
+        3 => DataType::Map(element, value),
+        _ => element.data_type(),
     }
 }
 
@@ -3618,15 +4587,53 @@ fn ui_node_socket_dir(ui: &UserInterface, socket: Handle<UiNode>) -> Option<Sock
 #[derive(Default)]
 pub struct BlueprintEditorPlugin {
     editor: Option<BlueprintEditor>,
+    // Resource manager events, used to migrate the blueprints that use a saved struct or enum asset.
+    resource_events: Option<Receiver<ResourceEvent>>,
 }
 
 impl EditorPlugin for BlueprintEditorPlugin {
+    fn on_start(&mut self, editor: &mut Editor) {
+        let (sender, receiver) = mpsc::channel();
+        editor
+            .engine
+            .resource_manager
+            .state()
+            .event_broadcaster
+            .add(sender);
+        self.resource_events = Some(receiver);
+    }
+
+    fn on_update(&mut self, editor: &mut Editor, _loop_controller: ApplicationLoopController) {
+        let Some(receiver) = self.resource_events.as_ref() else {
+            return;
+        };
+        let mut changed = UserTypes::default();
+        for event in receiver.try_iter() {
+            if let ResourceEvent::Reloaded(resource) = event {
+                define_from_resource(&mut changed, &resource);
+            }
+        }
+        if changed.structs.is_empty() && changed.enums.is_empty() {
+            return;
+        }
+
+        let opened = self
+            .editor
+            .as_mut()
+            .and_then(|bp| bp.on_types_changed(&changed, &mut editor.engine));
+        migrate_dependent_blueprints(&editor.engine.resource_manager, &changed, opened.as_deref());
+    }
+
     fn on_ui_message(&mut self, message: &mut UiMessage, editor: &mut Editor) {
         let Some(bp) = self.editor.as_mut() else {
             return;
         };
 
-        bp.handle_ui_message(message, &mut editor.engine);
+        bp.handle_ui_message(
+            message,
+            &mut editor.engine,
+            editor.settings.general.save_format.into(),
+        );
 
         if let Some(WindowMessage::Close) = message.data() {
             if message.destination() == bp.window {
@@ -0,0 +1,283 @@
+//! Profiler tab of the blueprint editor. Shows the statistics a game collected with
+//! [`BlueprintProfilerPlugin`] for the open blueprint and optionally paints them over the graph.
+
+use crate::fyrox::{
+    core::{color::Color, log::Log, make_relative_path, pool::Handle},
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        check_box::{CheckBoxBuilder, CheckBoxMessage},
+        grid::{Column, GridBuilder, Row},
+        message::{MessageDirection, UiMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        widget::WidgetBuilder,
+        BuildContext, Orientation, Thickness, UiNode, UserInterface, VerticalAlignment,
+    },
+};
+use fyrox_blueprint::BlueprintProfilerPlugin;
+use fyrox_visual_scripting::{
+    profiles_from_csv, profiles_to_csv, BlueprintGraph, BlueprintProfile, ProfileSample,
+};
+use std::{cmp::Reverse, fmt::Write, path::Path, time::Duration};
+
+/// Color of the hottest node and wire of the heat map.
+const HOT: Color = Color::opaque(230, 40, 30);
+
+pub struct ProfilerPanel {
+    pub root: Handle<UiNode>,
+    reload: Handle<UiNode>,
+    export: Handle<UiNode>,
+    heat_map: Handle<UiNode>,
+    summary: Handle<UiNode>,
+    profile: Option<BlueprintProfile>,
+    show_heat_map: bool,
+}
+
+impl ProfilerPanel {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let reload;
+        let export;
+        let heat_map;
+        let summary;
+        let root = GridBuilder::new(
+            WidgetBuilder::new()
+                .with_child(
+                    StackPanelBuilder::new(
+                        WidgetBuilder::new()
+                            .with_margin(Thickness::uniform(2.0))
+                            .with_child({
+                                reload = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_width(120.0)
+                                        .with_height(24.0)
+                                        .with_margin(Thickness::right(2.0)),
+                                )
+                                .with_text("Reload")
+                                .build(ctx);
+                                reload
+                            })
+                            .with_child({
+                                export = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_width(120.0)
+                                        .with_height(24.0)
+                                        .with_margin(Thickness::right(6.0)),
+                                )
+                                .with_text("Export CSV")
+                                .build(ctx);
+                                export
+                            })
+                            .with_child({
+                                heat_map = CheckBoxBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_vertical_alignment(VerticalAlignment::Center),
+                                )
+                                .checked(Some(false))
+                                .build(ctx);
+                                heat_map
+                            })
+                            .with_child(
+                                TextBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::left(4.0))
+                                        .with_vertical_alignment(VerticalAlignment::Center),
+                                )
+                                .with_text("Heat Map")
+                                .build(ctx),
+                            ),
+                    )
+                    .with_orientation(Orientation::Horizontal)
+                    .build(ctx),
+                )
+                .with_child(
+                    ScrollViewerBuilder::new(WidgetBuilder::new().on_row(1))
+                        .with_content({
+                            summary = TextBuilder::new(
+                                WidgetBuilder::new().with_margin(Thickness::uniform(4.0)),
+                            )
+                            .with_text(no_data_text())
+                            .build(ctx);
+                            summary
+                        })
+                        .build(ctx),
+                ),
+        )
+        .add_row(Row::auto())
+        .add_row(Row::stretch())
+        .add_column(Column::stretch())
+        .build(ctx);
+
+        Self {
+            root,
+            reload,
+            export,
+            heat_map,
+            summary,
+            profile: None,
+            show_heat_map: false,
+        }
+    }
+
+    /// Statistics to paint the graph with, if the heat map is enabled.
+    pub fn heat_map(&self) -> Option<&BlueprintProfile> {
+        self.profile.as_ref().filter(|_| self.show_heat_map)
+    }
+
+    /// Drops the statistics of the previously opened blueprint.
+    pub fn clear(&mut self, ui: &UserInterface) {
+        self.profile = None;
+        ui.send(self.summary, TextMessage::Text(no_data_text()));
+    }
+
+    /// Returns `true` if the graph views must be rebuilt to update the heat map.
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        ui: &UserInterface,
+        graph: &BlueprintGraph,
+        blueprint_path: Option<&Path>,
+    ) -> bool {
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.reload {
+                self.load(ui, graph, blueprint_path);
+                return self.show_heat_map;
+            }
+            if message.destination() == self.export {
+                self.export(blueprint_path);
+            }
+        }
+
+        if let Some(CheckBoxMessage::Check(Some(value))) = message.data::<CheckBoxMessage>() {
+            if message.destination() == self.heat_map
+                && message.direction() == MessageDirection::FromWidget
+                && self.show_heat_map != *value
+            {
+                self.show_heat_map = *value;
+                return self.profile.is_some();
+            }
+        }
+
+        false
+    }
+
+    fn load(&mut self, ui: &UserInterface, graph: &BlueprintGraph, blueprint_path: Option<&Path>) {
+        let source = BlueprintProfilerPlugin::DEFAULT_OUTPUT;
+        let profiles = match std::fs::read_to_string(source) {
+            Ok(csv) => profiles_from_csv(&csv),
+            Err(err) => {
+                self.profile = None;
+                ui.send(
+                    self.summary,
+                    TextMessage::Text(format!(
+                        "Unable to read {source}: {err}\n\n{}",
+                        no_data_text()
+                    )),
+                );
+                return;
+            }
+        };
+
+        self.profile = blueprint_path.and_then(|path| {
+            let key = profile_key(path);
+            profiles.get(&key).cloned().or_else(|| {
+                // The game may run from another directory; fall back to the file name.
+                let file_name = path.file_name()?;
+                profiles
+                    .iter()
+                    .find(|(blueprint, _)| {
+                        Path::new(blueprint.as_str()).file_name() == Some(file_name)
+                    })
+                    .map(|(_, profile)| profile.clone())
+            })
+        });
+
+        let text = match self.profile.as_ref() {
+            Some(profile) => summary_text(profile, graph),
+            None => format!("{source} has no statistics for this blueprint."),
+        };
+        ui.send(self.summary, TextMessage::Text(text));
+    }
+
+    fn export(&self, blueprint_path: Option<&Path>) {
+        let (Some(profile), Some(path)) = (self.profile.as_ref(), blueprint_path) else {
+            Log::warn("BlueprintEditor: there are no statistics to export");
+            return;
+        };
+        let key = profile_key(path);
+        let output = path.with_extension("profile.csv");
+        match std::fs::write(&output, profiles_to_csv([(key.as_str(), profile)])) {
+            Ok(()) => Log::info(format!(
+                "Exported blueprint profile to {}",
+                output.display()
+            )),
+            Err(err) => Log::err(format!(
+                "BlueprintEditor: failed to write {}: {err}",
+                output.display()
+            )),
+        }
+    }
+}
+
+/// Header color of a node with the given heat (see [`BlueprintProfile::heat`]).
+pub fn heat_color(base: Color, heat: f32) -> Color {
+    base.lerp(HOT, heat.clamp(0.0, 1.0))
+}
+
+/// Statistics are keyed by the path of the asset the game loaded, relative to the project.
+fn profile_key(path: &Path) -> String {
+    make_relative_path(path)
+        .unwrap_or_else(|_| path.to_path_buf())
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+fn no_data_text() -> String {
+    format!(
+        "Enable Profile Blueprints in the build settings and run the game (or add \
+        BlueprintProfilerPlugin to the game). Statistics are written to {} in the working \
+        directory; press Reload to show them.",
+        BlueprintProfilerPlugin::DEFAULT_OUTPUT
+    )
+}
+
+fn summary_text(profile: &BlueprintProfile, graph: &BlueprintGraph) -> String {
+    fn row(text: &mut String, name: &str, sample: &ProfileSample) {
+        let _ = writeln!(
+            text,
+            "{name}: {} runs, {} total, {} avg",
+            sample.count,
+            format_duration(sample.total),
+            format_duration(sample.average())
+        );
+    }
+
+    let mut text = String::new();
+    let _ = writeln!(text, "EVENTS");
+    for (event, sample) in profile.events.iter() {
+        row(&mut text, event, sample);
+    }
+    if profile.rhai.count > 0 {
+        row(&mut text, "Rhai", &profile.rhai);
+    }
+
+    let _ = writeln!(text, "\nNODES (slowest first)");
+    let mut nodes = profile.nodes.iter().collect::<Vec<_>>();
+    nodes.sort_by_key(|(_, sample)| Reverse(sample.total));
+    for (node_id, sample) in nodes {
+        let name = match graph.nodes.get(node_id) {
+            Some(node) => format!("{:?} #{}", node.kind, node_id.0),
+            None => format!("Removed node #{}", node_id.0),
+        };
+        row(&mut text, &name, sample);
+    }
+    text
+}
+
+fn format_duration(duration: Duration) -> String {
+    if duration >= Duration::from_millis(1) {
+        format!("{:.2} ms", duration.as_secs_f64() * 1000.0)
+    } else {
+        format!("{} us", duration.as_micros())
+    }
+}
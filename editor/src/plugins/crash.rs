@@ -0,0 +1,230 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Offers to open crash reports of the game, that were written since the last start of the editor.
+
+use crate::{
+    asset::show_in_explorer,
+    fyrox::{
+        core::{log::Log, pool::Handle},
+        engine::crash::{CrashReporter, CrashReporterOptions},
+        gui::{
+            button::{ButtonBuilder, ButtonMessage},
+            grid::{Column, GridBuilder, Row},
+            message::UiMessage,
+            messagebox::{
+                MessageBoxBuilder, MessageBoxButtons, MessageBoxMessage, MessageBoxResult,
+            },
+            scroll_viewer::ScrollViewerBuilder,
+            stack_panel::StackPanelBuilder,
+            text::TextBuilder,
+            widget::{WidgetBuilder, WidgetMessage},
+            window::{WindowAlignment, WindowBuilder, WindowMessage, WindowTitle},
+            HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+        },
+    },
+    message::Message,
+    plugin::EditorPlugin,
+    Editor,
+};
+use std::path::{Path, PathBuf};
+
+struct CrashReportViewer {
+    window: Handle<UiNode>,
+    show_in_folder: Handle<UiNode>,
+    bundle: PathBuf,
+}
+
+impl CrashReportViewer {
+    fn new(bundle: PathBuf, ui: &mut UserInterface) -> Self {
+        let text = match CrashReporter::read_bundle(&bundle) {
+            Ok(files) => files
+                .into_iter()
+                .map(|(path, contents)| format!("==== {} ====\n{contents}\n", path.display()))
+                .collect::<String>(),
+            Err(err) => format!("Unable to read the crash report. Reason: {err:?}"),
+        };
+
+        let ctx = &mut ui.build_ctx();
+        let show_in_folder = ButtonBuilder::new(
+            WidgetBuilder::new()
+                .with_width(120.0)
+                .with_margin(Thickness::uniform(1.0)),
+        )
+        .with_text("Show In Folder")
+        .build(ctx);
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(700.0).with_height(600.0))
+            .open(false)
+            .with_title(WindowTitle::text(format!(
+                "Crash Report - {}",
+                bundle.display()
+            )))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            ScrollViewerBuilder::new(
+                                WidgetBuilder::new().with_margin(Thickness::uniform(2.0)),
+                            )
+                            .with_content(
+                                TextBuilder::new(
+                                    WidgetBuilder::new().with_margin(Thickness::uniform(2.0)),
+                                )
+                                .with_text(text)
+                                .build(ctx),
+                            )
+                            .build(ctx),
+                        )
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .with_child(show_in_folder),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        ),
+                )
+                .add_row(Row::stretch())
+                .add_row(Row::strict(25.0))
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        ui.send(
+            window,
+            WindowMessage::Open {
+                alignment: WindowAlignment::Center,
+                modal: false,
+                focus_content: true,
+            },
+        );
+
+        Self {
+            window,
+            show_in_folder,
+            bundle,
+        }
+    }
+}
+
+/// Checks the crash report folder of the game when a project is opened and offers to open the
+/// latest report written by [`CrashReporter`].
+#[derive(Default)]
+pub struct CrashReportPlugin {
+    message_box: Handle<UiNode>,
+    pending: Vec<PathBuf>,
+    viewer: Option<CrashReportViewer>,
+}
+
+impl CrashReportPlugin {
+    fn check_pending_reports(&mut self, working_directory: &Path, ui: &mut UserInterface) {
+        self.pending = CrashReporter::pending_bundles(
+            &working_directory.join(CrashReporterOptions::DEFAULT_DIRECTORY),
+        );
+        if self.pending.is_empty() || self.message_box.is_some() {
+            return;
+        }
+
+        let text = format!(
+            "The game has crashed {} time(s) since the last start of the editor. \
+            Do you want to open the latest crash report?",
+            self.pending.len()
+        );
+        self.message_box = MessageBoxBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(350.0).with_height(110.0))
+                .can_close(false)
+                .can_minimize(false)
+                .open(false)
+                .with_title(WindowTitle::text("Crash Report")),
+        )
+        .with_text(&text)
+        .with_buttons(MessageBoxButtons::YesNo)
+        .build(&mut ui.build_ctx());
+        ui.send(
+            self.message_box,
+            MessageBoxMessage::Open {
+                title: None,
+                text: None,
+            },
+        );
+    }
+
+    /// Moves all the pending reports out of the way, so they won't be offered on the next start.
+    /// Returns the new path of the latest report.
+    fn acknowledge_pending(&mut self) -> Option<PathBuf> {
+        let mut latest = None;
+        for bundle in self.pending.drain(..) {
+            match CrashReporter::acknowledge(&bundle) {
+                Ok(path) => latest = Some(path),
+                Err(err) => Log::err(format!(
+                    "Unable to acknowledge {} crash report. Reason: {err:?}",
+                    bundle.display()
+                )),
+            }
+        }
+        latest
+    }
+}
+
+impl EditorPlugin for CrashReportPlugin {
+    fn on_ui_message(&mut self, message: &mut UiMessage, editor: &mut Editor) {
+        let ui = editor.engine.user_interfaces.first_mut();
+
+        if let Some(MessageBoxMessage::Close(result)) = message.data() {
+            if message.destination() == self.message_box {
+                ui.send(self.message_box, WidgetMessage::Remove);
+                self.message_box = Handle::NONE;
+
+                let latest = self.acknowledge_pending();
+                if let (MessageBoxResult::Yes, Some(latest)) = (result, latest) {
+                    if let Some(viewer) = self.viewer.take() {
+                        ui.send(viewer.window, WidgetMessage::Remove);
+                    }
+                    self.viewer = Some(CrashReportViewer::new(latest, ui));
+                }
+            }
+        }
+
+        if let Some(viewer) = self.viewer.as_ref() {
+            if let Some(ButtonMessage::Click) = message.data() {
+                if message.destination() == viewer.show_in_folder {
+                    show_in_explorer(&viewer.bundle);
+                }
+            } else if let Some(WindowMessage::Close) = message.data() {
+                if message.destination() == viewer.window {
+                    ui.send(viewer.window, WidgetMessage::Remove);
+                    self.viewer = None;
+                }
+            }
+        }
+    }
+
+    fn on_message(&mut self, message: &Message, editor: &mut Editor) {
+        if let Message::Configure { working_directory } = message {
+            self.check_pending_reports(
+                working_directory,
+                editor.engine.user_interfaces.first_mut(),
+            );
+        }
+    }
+}
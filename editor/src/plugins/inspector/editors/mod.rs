@@ -25,6 +25,7 @@ use crate::{
             pool::{ErasedHandle, Handle},
             reflect::Reflect,
         },
+        engine::input::{GamepadAxis, GamepadButton},
         graphics::PolygonFillMode,
         gui::{
             self,
@@ -51,6 +52,7 @@ use crate::{
             key::KeyBindingEditor,
             list_view::{ListView, ListViewItem},
             menu::{ContextMenu, Menu, MenuItem},
+            message::{KeyCode, KeyboardModifiers},
             messagebox::MessageBox,
             navigation::NavigationLayer,
             nine_patch::NinePatch,
@@ -81,6 +83,13 @@ use crate::{
         renderer::{HdrSettings, LuminanceCalculationMethod},
         resource::{
             curve::{CurveResource, CurveResourceState},
+            data_table::{
+                DataCell, DataColumn, DataColumnType, DataRow, DataTable, DataTableResource,
+            },
+            input_map::{
+                ActionBinding, AxisBinding, AxisDirection, InputAction, InputAxis, InputMap,
+                InputMapResource, InputSource, MouseAxis,
+            },
             model::{MaterialSearchOptions, Model, ModelResource},
             texture::{
                 CompressionOptions, MipFilter, TextureMagnificationFilter,
@@ -173,6 +182,10 @@ use crate::{
         },
     },
 };
+use fyrox_blueprint::{
+    BehaviorAsset, BehaviorResource, InterfaceFunctionDef, InterfaceParamDef, InterfaceParamType,
+    StructFieldDef, StructFieldElement, StructFieldType,
+};
 
 pub mod animation;
 pub mod font;
@@ -368,6 +381,52 @@ pub fn make_property_editors_container(
     container.insert(InheritablePropertyEditorDefinition::<Option<CurveResource>>::new());
     container.register_inheritable_vec_collection::<Option<CurveResource>>();
 
+    container.insert(ResourceFieldPropertyEditorDefinition::<DataTable>::new(
+        sender.clone(),
+    ));
+    container.insert(InheritablePropertyEditorDefinition::<
+        Option<DataTableResource>,
+    >::new());
+    container.register_inheritable_vec_collection::<Option<DataTableResource>>();
+    container.insert(ResourceFieldPropertyEditorDefinition::<BehaviorAsset>::new(
+        sender.clone(),
+    ));
+    container.insert(InheritablePropertyEditorDefinition::<
+        Option<BehaviorResource>,
+    >::new());
+    container.register_inheritable_vec_collection::<Option<BehaviorResource>>();
+    container.register_inheritable_enum::<DataColumnType, _>();
+    container.register_inheritable_inspectable::<DataColumn>();
+    container.register_inheritable_vec_collection::<DataColumn>();
+    container.register_inheritable_enum::<DataCell, _>();
+    container.register_inheritable_vec_collection::<DataCell>();
+    container.register_inheritable_inspectable::<DataRow>();
+    container.register_inheritable_vec_collection::<DataRow>();
+
+    container.insert(ResourceFieldPropertyEditorDefinition::<InputMap>::new(
+        sender.clone(),
+    ));
+    container.insert(InheritablePropertyEditorDefinition::<
+        Option<InputMapResource>,
+    >::new());
+    container.register_inheritable_vec_collection::<Option<InputMapResource>>();
+    container.register_inheritable_enum::<KeyCode, _>();
+    container.register_inheritable_inspectable::<KeyboardModifiers>();
+    container.register_inheritable_enum::<GamepadButton, _>();
+    container.register_inheritable_enum::<GamepadAxis, _>();
+    container.register_inheritable_enum::<MouseAxis, _>();
+    container.register_inheritable_enum::<AxisDirection, _>();
+    container.register_inheritable_enum::<InputSource, _>();
+    container.register_inheritable_vec_collection::<InputSource>();
+    container.register_inheritable_inspectable::<ActionBinding>();
+    container.register_inheritable_vec_collection::<ActionBinding>();
+    container.register_inheritable_inspectable::<InputAction>();
+    container.register_inheritable_vec_collection::<InputAction>();
+    container.register_inheritable_enum::<AxisBinding, _>();
+    container.register_inheritable_vec_collection::<AxisBinding>();
+    container.register_inheritable_inspectable::<InputAxis>();
+    container.register_inheritable_vec_collection::<InputAxis>();
+
     container.insert(ResourceFieldPropertyEditorDefinition::<UserInterface>::new(
         sender.clone(),
     ));
@@ -558,6 +617,16 @@ pub fn make_property_editors_container(
     container.register_inheritable_enum::<TileCollider, _>();
     container.register_inheritable_enum::<RigidBodyMassPropertiesType, _>();
 
+    container.register_inheritable_vec_collection::<InterfaceFunctionDef>();
+    container.register_inheritable_inspectable::<InterfaceFunctionDef>();
+    container.register_inheritable_vec_collection::<InterfaceParamDef>();
+    container.register_inheritable_inspectable::<InterfaceParamDef>();
+    container.register_inheritable_enum::<InterfaceParamType, _>();
+    container.register_inheritable_vec_collection::<StructFieldDef>();
+    container.register_inheritable_inspectable::<StructFieldDef>();
+    container.register_inheritable_enum::<StructFieldType, _>();
+    container.register_inheritable_enum::<StructFieldElement, _>();
+
     reg_node_handle_editors!(
         container,
         sender,
@@ -20,8 +20,10 @@
 
 pub mod absm;
 pub mod animation;
+pub mod behavior;
 pub mod blueprint;
 pub mod collider;
+pub mod crash;
 pub mod curve_editor;
 pub mod inspector;
 pub mod material;
@@ -62,7 +62,7 @@ use crate::{
         build::BuildSettings,
         camera::CameraSettings,
         debugging::DebuggingSettings,
-        general::{EditorStyle, GeneralSettings, ScriptEditor},
+        general::{EditorStyle, GeneralSettings, SaveFormat, ScriptEditor},
         graphics::GraphicsSettings,
         keys::{KeyBindings, TerrainKeyBindings},
         model::ModelSettings,
@@ -97,6 +97,7 @@ fn make_property_editors_container(
     container.insert(EnumPropertyEditorDefinition::<ShadowMapPrecision>::new());
     container.insert(EnumPropertyEditorDefinition::<ScriptEditor>::new());
     container.insert(EnumPropertyEditorDefinition::<EditorStyle>::new());
+    container.insert(EnumPropertyEditorDefinition::<SaveFormat>::new());
     container.insert(InspectablePropertyEditorDefinition::<DebuggingSettings>::new());
     container.insert(InspectablePropertyEditorDefinition::<CsmSettings>::new());
     container.insert(InspectablePropertyEditorDefinition::<QualitySettings>::new());
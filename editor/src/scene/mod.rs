@@ -34,7 +34,7 @@ use crate::{
             math::{aabb::AxisAlignedBoundingBox, plane::Plane, Rect},
             pool::{ErasedHandle, Handle},
             reflect::Reflect,
-            visitor::Visitor,
+            visitor::{Format, Visitor},
             Uuid,
         },
         engine::{Engine, SerializationContext},
@@ -151,6 +151,7 @@ pub struct GameScene {
     pub grid: Handle<Node>,
     pub grid_material: MaterialResource,
     pub settings_receiver: Receiver<SettingsMessage>,
+    pub save_format: Format,
 }
 
 lazy_static! {
@@ -253,6 +254,7 @@ impl GameScene {
             grid,
             grid_material,
             settings_receiver,
+            save_format: settings.general.save_format.into(),
         }
     }
 
@@ -281,7 +283,7 @@ impl GameScene {
         let mut visitor = Visitor::new();
         pure_scene.save("Scene", &mut visitor).unwrap();
 
-        if let Err(e) = visitor.save_ascii_to_file(path) {
+        if let Err(e) = visitor.save_to_file(path, settings.general.save_format.into()) {
             Err(format!(
                 "Failed to save scene {}! Reason: {e}",
                 path.display()
@@ -463,7 +465,7 @@ impl GameScene {
             match dest_scene.save("Scene", &mut visitor) {
                 Err(e) => Log::err(format!("Failed to save selection as prefab! Reason: {e:?}")),
                 Ok(_) => {
-                    if let Err(e) = visitor.save_ascii_to_file(path) {
+                    if let Err(e) = visitor.save_to_file(path, self.save_format) {
                         Log::err(format!("Failed to save selection as prefab! Reason: {e:?}"));
                     } else {
                         Log::info(format!(
@@ -965,6 +967,7 @@ impl SceneController for GameScene {
             match message {
                 SettingsMessage::Changed => {
                     scene.graph[self.grid].set_visibility(settings.graphics.draw_grid);
+                    self.save_format = settings.general.save_format.into();
                 }
             }
         }
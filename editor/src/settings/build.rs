@@ -27,6 +27,10 @@ pub struct BuildSettings {
     #[reflect(hidden)]
     pub selected_profile: usize,
     pub profiles: Vec<BuildProfile>,
+    /// Collect execution statistics of blueprints when the game runs in play mode. The statistics
+    /// are shown in the Profiler tab of the blueprint editor.
+    #[serde(default)]
+    pub profile_blueprints: bool,
 }
 
 impl Default for BuildSettings {
@@ -38,6 +42,7 @@ impl Default for BuildSettings {
         Self {
             selected_profile: 0,
             profiles: vec![debug, debug_hot_reloading, release, release_hot_reloading],
+            profile_blueprints: false,
         }
     }
 }
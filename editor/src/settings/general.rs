@@ -18,7 +18,9 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use crate::fyrox::core::{reflect::prelude::*, type_traits::prelude::*, uuid_provider};
+use crate::fyrox::core::{
+    reflect::prelude::*, type_traits::prelude::*, uuid_provider, visitor::Format,
+};
 use serde::{Deserialize, Serialize};
 use strum_macros::{AsRefStr, EnumString, VariantNames};
 
@@ -81,6 +83,11 @@ pub struct GeneralSettings {
 
     #[serde(default = "default_style")]
     pub style: EditorStyle,
+
+    /// Format of saved scenes, user interfaces and prefabs. Text formats can be merged by a version
+    /// control system, binary one is the fastest and smallest.
+    #[serde(default)]
+    pub save_format: SaveFormat,
 }
 
 fn default_style() -> EditorStyle {
@@ -133,6 +140,42 @@ pub enum ScriptEditor {
 
 uuid_provider!(ScriptEditor = "d0c942e8-24e4-40f2-ad2e-1b9f189d3ca2");
 
+#[derive(
+    Copy,
+    Clone,
+    Hash,
+    PartialOrd,
+    PartialEq,
+    Eq,
+    Ord,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+)]
+pub enum SaveFormat {
+    #[default]
+    Ascii,
+    Binary,
+    Json,
+}
+
+uuid_provider!(SaveFormat = "6f3a2b8e-1d4c-4e7a-9b05-8c2e7f4d1a63");
+
+impl From<SaveFormat> for Format {
+    fn from(format: SaveFormat) -> Self {
+        match format {
+            SaveFormat::Ascii => Format::Ascii,
+            SaveFormat::Binary => Format::Binary,
+            SaveFormat::Json => Format::Json,
+        }
+    }
+}
+
 impl Default for GeneralSettings {
     fn default() -> Self {
         Self {
@@ -144,6 +187,7 @@ impl Default for GeneralSettings {
             generate_previews: default_generate_previews(),
             max_log_entries: default_max_log_entries(),
             style: EditorStyle::Dark,
+            save_format: Default::default(),
         }
     }
 }
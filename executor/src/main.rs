@@ -1,6 +1,6 @@
 use fyrox::{
     core::{
-        log::Log,
+        log::{sink::RotatingFileSink, Log},
         pool::Handle,
         reflect::prelude::*,
         visitor::prelude::*,
@@ -9,9 +9,12 @@ use fyrox::{
     event_loop::EventLoop,
     plugin::{Plugin, PluginContext, PluginRegistrationContext},
     scene::Scene,
+    utils::validation::AssetValidator,
 };
 
-use std::path::Path;
+use fyrox_blueprint::{BlueprintProfilerPlugin, BlueprintTestRunnerPlugin};
+
+use std::path::{Path, PathBuf};
 
 fn set_working_directory_from_override_scene_arg() {
     // The editor runs `cargo run -p executor -- --override-scene <path>`.
@@ -43,6 +46,193 @@ fn set_working_directory_from_override_scene_arg() {
     }
 }
 
+/// Arguments of the headless blueprint test mode:
+///
+/// `executor --test-blueprints [--scene <path>] [--blueprint <path>]... [--frames <n>] [--dt <seconds>] [--junit <path>]`
+struct BlueprintTestArgs {
+    scene: Option<PathBuf>,
+    blueprints: Vec<PathBuf>,
+    frames: usize,
+    dt: f32,
+    junit: Option<PathBuf>,
+}
+
+impl BlueprintTestArgs {
+    fn parse() -> Result<Option<Self>, String> {
+        let mut args = std::env::args().skip(1).peekable();
+        if args.peek().map(String::as_str) != Some("--test-blueprints") {
+            return Ok(None);
+        }
+        args.next();
+
+        let mut test_args = Self {
+            scene: None,
+            blueprints: Vec::new(),
+            frames: 60,
+            dt: 1.0 / 60.0,
+            junit: None,
+        };
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or_else(|| format!("{arg} expects a value"));
+            match arg.as_str() {
+                "--scene" => test_args.scene = Some(value()?.into()),
+                "--blueprint" => test_args.blueprints.push(value()?.into()),
+                "--frames" => {
+                    test_args.frames = value()?
+                        .parse()
+                        .map_err(|err| format!("invalid --frames: {err}"))?
+                }
+                "--dt" => {
+                    test_args.dt = value()?
+                        .parse()
+                        .map_err(|err| format!("invalid --dt: {err}"))?
+                }
+                "--junit" => test_args.junit = Some(value()?.into()),
+                _ => return Err(format!("unknown argument {arg}")),
+            }
+        }
+
+        if test_args.scene.is_none() && test_args.blueprints.is_empty() {
+            return Err(
+                "--test-blueprints needs a --scene or at least one --blueprint".to_string(),
+            );
+        }
+        if test_args.dt <= 0.0 {
+            return Err("--dt must be positive".to_string());
+        }
+        Ok(Some(test_args))
+    }
+}
+
+/// Runs the blueprints headless and returns the process exit code.
+fn run_blueprint_tests(args: BlueprintTestArgs) -> i32 {
+    let runner = BlueprintTestRunnerPlugin::new(args.scene, args.blueprints, args.frames);
+    let report = runner.report();
+
+    let mut executor = Executor::new(None);
+    executor.set_desired_update_rate(1.0 / args.dt);
+    executor.set_lockstep(true);
+    executor.set_resource_hot_reloading_enabled(false);
+    executor.add_plugin(runner);
+    executor.run();
+
+    let report = report
+        .lock()
+        .map(|report| report.clone())
+        .unwrap_or_default();
+    let xml = report.to_junit_xml();
+    match args.junit {
+        Some(path) => {
+            if let Err(err) = std::fs::write(&path, &xml) {
+                eprintln!("Unable to write {}: {err}", path.display());
+                return 2;
+            }
+        }
+        None => print!("{xml}"),
+    }
+
+    let passed = report.cases().filter(|case| case.failure.is_none()).count();
+    eprintln!(
+        "Blueprint tests: {passed} passed, {} failed",
+        report.failures()
+    );
+    if report.is_success() {
+        0
+    } else {
+        1
+    }
+}
+
+/// Arguments of the asset validation mode:
+///
+/// `executor --validate <path>... [--fix]`
+///
+/// Paths could be `.rgs`, `.ui` or `.material` files or folders, that are searched for such
+/// files recursively.
+struct ValidateArgs {
+    paths: Vec<PathBuf>,
+    fix: bool,
+}
+
+impl ValidateArgs {
+    fn parse() -> Result<Option<Self>, String> {
+        let mut args = std::env::args().skip(1).peekable();
+        if args.peek().map(String::as_str) != Some("--validate") {
+            return Ok(None);
+        }
+        args.next();
+
+        let mut validate_args = Self {
+            paths: Vec::new(),
+            fix: false,
+        };
+        for arg in args {
+            match arg.as_str() {
+                "--fix" => validate_args.fix = true,
+                _ if arg.starts_with("--") => return Err(format!("unknown argument {arg}")),
+                _ => validate_args.paths.push(arg.into()),
+            }
+        }
+
+        if validate_args.paths.is_empty() {
+            return Err("--validate needs at least one path".to_string());
+        }
+        Ok(Some(validate_args))
+    }
+}
+
+/// Validates (and optionally repairs) the assets and returns the process exit code.
+fn run_validation(args: ValidateArgs) -> i32 {
+    let validator = AssetValidator::new();
+    // Register the same scripts and resources as the game does, so they're not reported as unknown.
+    fyrox_blueprint::register_resources(&validator.resource_manager);
+    fyrox_blueprint::register(&validator.serialization_context.script_constructors);
+    validator.resource_manager.update_or_load_registry();
+
+    let mut files = Vec::new();
+    for path in args.paths {
+        if path.is_dir() {
+            files.extend(
+                fyrox::walkdir::WalkDir::new(&path)
+                    .into_iter()
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.into_path())
+                    .filter(|path| {
+                        path.extension()
+                            .is_some_and(|ext| ext == "rgs" || ext == "ui" || ext == "material")
+                    }),
+            );
+        } else {
+            files.push(path);
+        }
+    }
+
+    let mut failed = 0;
+    for file in files {
+        match validator.validate_blocking(&file, args.fix) {
+            Ok(report) => {
+                for issue in report.issues.iter() {
+                    println!("{}: {issue}", file.display());
+                }
+                if !report.is_valid() {
+                    failed += 1;
+                }
+            }
+            Err(err) => {
+                println!("{}: unable to load: {err}", file.display());
+                failed += 1;
+            }
+        }
+    }
+
+    if failed == 0 {
+        0
+    } else {
+        eprintln!("{failed} file(s) have unresolved issues");
+        1
+    }
+}
+
 #[derive(Default, Visit, Reflect, Debug)]
 #[reflect(non_cloneable)]
 struct Game {
@@ -81,13 +271,42 @@ impl Plugin for Game {
 }
 
 fn main() {
-    Log::set_file_name("executor.log");
+    // Keep the logs of a few previous runs, they're often needed to diagnose issues in the field.
+    match RotatingFileSink::new("executor.log", 16 * 1024 * 1024, 3) {
+        Ok(sink) => {
+            Log::add_sink(sink);
+        }
+        Err(_) => Log::set_file_name("executor.log"),
+    }
+
+    match BlueprintTestArgs::parse() {
+        Ok(Some(args)) => std::process::exit(run_blueprint_tests(args)),
+        Ok(None) => {}
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(2);
+        }
+    }
+
+    match ValidateArgs::parse() {
+        Ok(Some(args)) => std::process::exit(run_validation(args)),
+        Ok(None) => {}
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(2);
+        }
+    }
 
     set_working_directory_from_override_scene_arg();
 
     let event_loop = EventLoop::new().unwrap();
     let mut executor = Executor::new(Some(event_loop));
+    executor.set_crash_reporter(Some(Default::default()));
     executor.add_plugin(Game::default());
     executor.add_plugin(fyrox_blueprint::BlueprintScreenLogPlugin::default());
+    // The editor enables profiling of blueprints in play mode.
+    if let Some(profiler) = BlueprintProfilerPlugin::from_env() {
+        executor.add_plugin(profiler);
+    }
     executor.run()
 }
@@ -87,4 +87,13 @@ impl<T: EntityId> FixedEventQueue<T> {
     pub fn pop(&mut self) -> Option<Event<T>> {
         self.queue.pop_front()
     }
+
+    /// Removes every event for which the given closure returns `false`, the order of the other
+    /// events is preserved.
+    pub fn retain<P>(&mut self, pred: P)
+    where
+        P: FnMut(&Event<T>) -> bool,
+    {
+        self.queue.retain(pred)
+    }
 }
@@ -284,6 +284,17 @@ impl<T: EntityId> MachineLayer<T> {
         self.events.pop()
     }
 
+    /// Removes every event for which the given closure returns `false` from the inner event queue. Unlike
+    /// [`Self::pop_event`], it allows to take only the events you are interested in and leave the rest to other
+    /// consumers.
+    #[inline]
+    pub fn retain_events<P>(&mut self, pred: P)
+    where
+        P: FnMut(&Event<T>) -> bool,
+    {
+        self.events.retain(pred)
+    }
+
     /// Resets layer state; deactivates all active transitions and sets active state to entry state.
     #[inline]
     pub fn reset(&mut self) {
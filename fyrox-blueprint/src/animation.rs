@@ -0,0 +1,221 @@
+//! Scene side of the animation nodes: finds state machines and animation players of actors, applies
+//! animation commands and collects animation signals.
+
+use fyrox::{
+    core::pool::Handle,
+    graph::SceneGraph,
+    scene::{
+        animation::{
+            absm::{prelude::Parameter, AnimationBlendingStateMachine, Event},
+            AnimationPlayer,
+        },
+        graph::Graph,
+        node::Node,
+    },
+};
+use fyrox_visual_scripting::{
+    animation::{state_enter_signal, state_leave_signal},
+    AnimationCommand, Value,
+};
+
+use crate::{find_components, resolve_actor};
+
+/// Name of the active state of a layer of the first state machine of the actor.
+pub fn active_state(graph: &Graph, actor: Handle<Node>, layer: usize) -> Option<String> {
+    let absm = *find_components::<AnimationBlendingStateMachine>(graph, actor).first()?;
+    let layer = graph
+        .try_get_of_type::<AnimationBlendingStateMachine>(absm)?
+        .machine()
+        .layers()
+        .get(layer)?;
+    let state = layer.states().try_borrow(layer.active_state())?;
+    Some(state.name.clone())
+}
+
+/// Applies an animation command of the blueprint running on `self_handle`. Parameters are set on
+/// every state machine of the target, animations are played or stopped in every animation player
+/// that has them. Returns `false` if nothing was changed.
+pub fn apply_animation_command(
+    graph: &mut Graph,
+    self_handle: Handle<Node>,
+    command: &AnimationCommand,
+) -> bool {
+    let mut applied = false;
+    match command {
+        AnimationCommand::SetParameter {
+            target,
+            name,
+            value,
+        } => {
+            let parameter = match value {
+                Value::Bool(rule) => Parameter::Rule(*rule),
+                Value::F32(weight) => Parameter::Weight(*weight),
+                Value::I32(index) => Parameter::Index(u32::try_from(*index).unwrap_or_default()),
+                _ => return false,
+            };
+            let actor = resolve_actor(graph, self_handle, target);
+            for absm in find_components::<AnimationBlendingStateMachine>(graph, actor) {
+                if let Some(absm) = graph.try_get_mut_of_type::<AnimationBlendingStateMachine>(absm)
+                {
+                    absm.machine_mut()
+                        .get_value_mut_silent()
+                        .set_parameter(name, parameter);
+                    applied = true;
+                }
+            }
+        }
+        AnimationCommand::Play { target, animation }
+        | AnimationCommand::Stop { target, animation } => {
+            let play = matches!(command, AnimationCommand::Play { .. });
+            let actor = resolve_actor(graph, self_handle, target);
+            for player in find_components::<AnimationPlayer>(graph, actor) {
+                let Some(player) = graph.try_get_mut_of_type::<AnimationPlayer>(player) else {
+                    continue;
+                };
+                let animations = player.animations_mut().get_value_mut_silent();
+                if let Some((_, animation)) = animations.find_by_name_mut(animation) {
+                    if play {
+                        animation.set_enabled(true).rewind();
+                    } else {
+                        animation.set_enabled(false);
+                    }
+                    applied = true;
+                }
+            }
+        }
+    }
+    applied
+}
+
+/// Takes the pending events of every animation player and state machine of the actor, for which
+/// `handles` returns `true`, as `(signal, source)` pairs. Animation signals are named by the signal
+/// and come from the animation; state machine events are named by [`state_enter_signal`] and
+/// [`state_leave_signal`] and come from the state.
+///
+/// Taken events are removed from their queues, the other events are left to other consumers.
+pub fn take_animation_signals(
+    graph: &mut Graph,
+    actor: Handle<Node>,
+    handles: impl Fn(&str) -> bool,
+) -> Vec<(String, String)> {
+    let mut signals = Vec::new();
+
+    for player in find_components::<AnimationPlayer>(graph, actor) {
+        let Some(player) = graph.try_get_mut_of_type::<AnimationPlayer>(player) else {
+            continue;
+        };
+        for animation in player.animations_mut().get_value_mut_silent().iter_mut() {
+            let source = animation.name().to_string();
+            animation.events_mut().retain(|event| {
+                if !handles(&event.name) {
+                    return true;
+                }
+                signals.push((event.name.clone(), source.clone()));
+                false
+            });
+        }
+    }
+
+    for absm in find_components::<AnimationBlendingStateMachine>(graph, actor) {
+        let Some(absm) = graph.try_get_mut_of_type::<AnimationBlendingStateMachine>(absm) else {
+            continue;
+        };
+        for layer in absm.machine_mut().get_value_mut_silent().layers_mut() {
+            // Signals of the states are resolved up front, the queue can't be modified while the
+            // states are borrowed.
+            let mut handled = Vec::new();
+            for (handle, state) in layer.states().pair_iter() {
+                for (signal, enter) in [
+                    (state_enter_signal(&state.name), true),
+                    (state_leave_signal(&state.name), false),
+                ] {
+                    if handles(&signal) {
+                        handled.push((handle, enter, signal, state.name.clone()));
+                    }
+                }
+            }
+            if handled.is_empty() {
+                continue;
+            }
+            layer.retain_events(|event| {
+                let (state, enter) = match event {
+                    Event::StateEnter(state) => (*state, true),
+                    Event::StateLeave(state) => (*state, false),
+                    Event::ActiveStateChanged { .. } | Event::ActiveTransitionChanged(_) => {
+                        return true
+                    }
+                };
+                let Some((.., signal, source)) = handled
+                    .iter()
+                    .find(|(handle, is_enter, ..)| *handle == state && *is_enter == enter)
+                else {
+                    return true;
+                };
+                signals.push((signal.clone(), source.clone()));
+                false
+            });
+        }
+    }
+
+    signals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fyrox::scene::{
+        animation::{
+            prelude::AnimationEvent, Animation, AnimationContainer, AnimationPlayerBuilder,
+        },
+        base::BaseBuilder,
+    };
+
+    #[test]
+    fn animation_player_commands_and_signals() {
+        let mut graph = Graph::new();
+        let mut walk = Animation::default();
+        walk.set_name("Walk");
+        walk.set_enabled(false);
+        walk.events_mut().push_back(AnimationEvent {
+            signal_id: Default::default(),
+            name: "Footstep".to_string(),
+        });
+        let mut animations = AnimationContainer::new();
+        animations.add(walk);
+        let player = AnimationPlayerBuilder::new(BaseBuilder::new())
+            .with_animations(animations)
+            .build(&mut graph);
+        let root = graph.get_root();
+
+        let play = AnimationCommand::Play {
+            target: String::new(),
+            animation: "Walk".to_string(),
+        };
+        assert!(apply_animation_command(&mut graph, root, &play));
+        let enabled = |graph: &Graph| {
+            graph
+                .try_get_of_type::<AnimationPlayer>(player)
+                .unwrap()
+                .animations()
+                .find_by_name_ref("Walk")
+                .unwrap()
+                .1
+                .is_enabled()
+        };
+        assert!(enabled(&graph));
+
+        let missing = AnimationCommand::Stop {
+            target: String::new(),
+            animation: "Run".to_string(),
+        };
+        assert!(!apply_animation_command(&mut graph, root, &missing));
+
+        assert!(take_animation_signals(&mut graph, root, |signal| signal == "Jump").is_empty());
+        assert_eq!(
+            take_animation_signals(&mut graph, root, |signal| signal == "Footstep"),
+            vec![("Footstep".to_string(), "Walk".to_string())]
+        );
+        assert!(take_animation_signals(&mut graph, root, |_| true).is_empty());
+        assert_eq!(active_state(&graph, root, 0), None);
+    }
+}
@@ -0,0 +1,347 @@
+//! Behavior tree assets (`.behavior`) and the runner that ticks them for blueprint scripts.
+//!
+//! The asset stores a [`BehaviorGraph`]; [`BehaviorRunner`] turns it into an engine
+//! [`BehaviorTree`] whose leaves run the task graphs of the blueprint and read its variables, see
+//! [`fyrox_visual_scripting::behavior`] for details.
+
+use fyrox::{
+    asset::{
+        io::ResourceIo,
+        loader::{BoxedLoaderFuture, LoaderPayload, ResourceLoader},
+        state::LoadError,
+        Resource, ResourceData,
+    },
+    core::{
+        pool::Handle, reflect::prelude::*, type_traits::prelude::*, uuid::Uuid,
+        visitor::prelude::*, TypeUuidProvider,
+    },
+    utils::behavior::{
+        composite::{CompositeNode, CompositeNodeKind},
+        decorator::{ConditionalNode, Decorator, DecoratorKind},
+        inverter::Inverter,
+        leaf::LeafNode,
+        Behavior, BehaviorNode, BehaviorTree, Status,
+    },
+};
+use fyrox_visual_scripting::{
+    behavior::BehaviorNodeId, BehaviorGraph, BehaviorNodeKind, BlueprintHost, ExecutionEvent,
+    Interpreter, TaskStatus,
+};
+use std::{
+    collections::BTreeSet,
+    error::Error,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+#[derive(TypeUuidProvider, Debug, Clone, Visit, Reflect)]
+#[type_uuid(id = "2f4e7d7a-8f0b-4c57-b6a4-51f0d3c9e2a8")]
+pub struct BehaviorAsset {
+    /// Asset format version. Useful for future migrations.
+    #[visit(optional)]
+    pub version: u32,
+
+    /// JSON of `fyrox_visual_scripting::BehaviorGraph`.
+    #[visit(optional)]
+    pub tree_json: String,
+}
+
+impl Default for BehaviorAsset {
+    fn default() -> Self {
+        Self {
+            version: 1,
+            tree_json: serde_json::to_string(&BehaviorGraph::default()).unwrap_or_default(),
+        }
+    }
+}
+
+impl BehaviorAsset {
+    pub async fn from_file(path: &Path, io: &dyn ResourceIo) -> Result<Self, VisitError> {
+        let bytes = io.load_file(path).await?;
+        let mut visitor = Visitor::load_from_memory(&bytes)?;
+        let mut asset = BehaviorAsset::default();
+        asset.visit("Behavior", &mut visitor)?;
+        Ok(asset)
+    }
+
+    /// Parses the stored tree.
+    pub fn graph(&self) -> Result<BehaviorGraph, serde_json::Error> {
+        serde_json::from_str(&self.tree_json)
+    }
+}
+
+pub type BehaviorResource = Resource<BehaviorAsset>;
+
+impl ResourceData for BehaviorAsset {
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut visitor = Visitor::new();
+        self.visit("Behavior", &mut visitor)?;
+        visitor.save_ascii_to_file(path)?;
+        Ok(())
+    }
+
+    fn can_be_saved(&self) -> bool {
+        true
+    }
+
+    fn try_clone_box(&self) -> Option<Box<dyn ResourceData>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+#[derive(Default)]
+pub struct BehaviorLoader;
+
+impl BehaviorLoader {
+    pub const EXT: &'static str = "behavior";
+}
+
+impl ResourceLoader for BehaviorLoader {
+    fn extensions(&self) -> &[&str] {
+        &[Self::EXT]
+    }
+
+    fn is_native_extension(&self, ext: &str) -> bool {
+        fyrox::core::cmp_strings_case_insensitive(ext, Self::EXT)
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        <BehaviorAsset as TypeUuidProvider>::type_uuid()
+    }
+
+    fn load(&self, path: PathBuf, io: Arc<dyn ResourceIo>) -> BoxedLoaderFuture {
+        Box::pin(async move {
+            let asset = BehaviorAsset::from_file(&path, io.as_ref())
+                .await
+                .map_err(LoadError::new)?;
+            Ok(LoaderPayload::new(asset))
+        })
+    }
+}
+
+/// Leaf of the engine tree: a task or a condition, referring to its node in the behavior graph.
+#[derive(Debug, Default, Clone, PartialEq, Visit)]
+struct BehaviorLeaf {
+    node: u32,
+}
+
+struct BehaviorContext<'a> {
+    graph: &'a BehaviorGraph,
+    interpreter: &'a mut Interpreter,
+    host: &'a dyn BlueprintHost,
+    events: Vec<ExecutionEvent>,
+}
+
+impl<'a> Behavior<'a> for BehaviorLeaf {
+    type Context = BehaviorContext<'a>;
+
+    fn tick(&mut self, context: &mut Self::Context) -> Status {
+        let Some(node) = context.graph.nodes.get(&BehaviorNodeId(self.node)) else {
+            return Status::Failure;
+        };
+        match &node.kind {
+            BehaviorNodeKind::Task { task } => {
+                let (out, status) = context.interpreter.run_behavior_task(context.host, task);
+                context.events.extend(out.events);
+                match status {
+                    TaskStatus::Success => Status::Success,
+                    TaskStatus::Failure => Status::Failure,
+                    TaskStatus::Running => Status::Running,
+                }
+            }
+            BehaviorNodeKind::Condition(condition) => {
+                if condition.evaluate(context.interpreter.variables()) {
+                    Status::Success
+                } else {
+                    Status::Failure
+                }
+            }
+            _ => Status::Failure,
+        }
+    }
+}
+
+/// Ticks a behavior tree for the interpreter of a blueprint script.
+pub struct BehaviorRunner {
+    graph: BehaviorGraph,
+    tree: BehaviorTree<BehaviorLeaf>,
+}
+
+impl BehaviorRunner {
+    /// Builds the tree. Decorators without a child and tasks without a name are left out; a
+    /// condition without a child just checks the blackboard.
+    pub fn new(graph: BehaviorGraph) -> Self {
+        let mut tree = BehaviorTree::new();
+        let mut visited = BTreeSet::new();
+        if let Some(entry) = graph
+            .root
+            .and_then(|root| build(&graph, root, &mut tree, &mut visited))
+        {
+            tree.set_entry_node(entry);
+        }
+        Self { graph, tree }
+    }
+
+    /// Advances the tree by `dt` seconds and ticks it once. Returns the events of the task graphs
+    /// that ran.
+    pub fn tick(
+        &self,
+        interpreter: &mut Interpreter,
+        host: &dyn BlueprintHost,
+        dt: f32,
+    ) -> Vec<ExecutionEvent> {
+        let mut context = BehaviorContext {
+            graph: &self.graph,
+            interpreter,
+            host,
+            events: Vec::new(),
+        };
+        self.tree.tick_with_dt(dt, &mut context);
+        context.events
+    }
+}
+
+fn build(
+    graph: &BehaviorGraph,
+    id: BehaviorNodeId,
+    tree: &mut BehaviorTree<BehaviorLeaf>,
+    visited: &mut BTreeSet<BehaviorNodeId>,
+) -> Option<Handle<BehaviorNode<BehaviorLeaf>>> {
+    if !visited.insert(id) {
+        return None;
+    }
+    let node = graph.nodes.get(&id)?;
+    let leaf = || BehaviorLeaf { node: id.0 };
+    let mut child = |tree: &mut BehaviorTree<BehaviorLeaf>| {
+        node.children
+            .first()
+            .and_then(|child| build(graph, *child, tree, visited))
+    };
+
+    let handle = match &node.kind {
+        BehaviorNodeKind::Sequence | BehaviorNodeKind::Selector => {
+            let kind = if node.kind == BehaviorNodeKind::Sequence {
+                CompositeNodeKind::Sequence
+            } else {
+                CompositeNodeKind::Selector
+            };
+            let children = node
+                .children
+                .iter()
+                .filter_map(|child| build(graph, *child, tree, visited))
+                .collect();
+            CompositeNode::new(kind, children).add_to(tree)
+        }
+        BehaviorNodeKind::Inverter => Inverter::new(child(tree)?).add_to(tree),
+        BehaviorNodeKind::Cooldown { duration } => {
+            let kind = DecoratorKind::Cooldown {
+                duration: seconds(*duration),
+            };
+            Decorator::new(kind, child(tree)?).add_to(tree)
+        }
+        BehaviorNodeKind::Repeat { count } => {
+            let kind = DecoratorKind::Repeat { count: *count };
+            Decorator::new(kind, child(tree)?).add_to(tree)
+        }
+        BehaviorNodeKind::Timeout { duration } => {
+            let kind = DecoratorKind::Timeout {
+                duration: seconds(*duration),
+            };
+            Decorator::new(kind, child(tree)?).add_to(tree)
+        }
+        BehaviorNodeKind::Condition(_) => match child(tree) {
+            Some(child) => ConditionalNode::new(leaf(), child).add_to(tree),
+            None => LeafNode::new(leaf()).add_to(tree),
+        },
+        BehaviorNodeKind::Task { task } if task.is_empty() => return None,
+        BehaviorNodeKind::Task { .. } => LeafNode::new(leaf()).add_to(tree),
+    };
+    Some(handle)
+}
+
+// Durations of the graph are in seconds, negative ones are treated as zero.
+fn seconds(duration: f32) -> Duration {
+    Duration::try_from_secs_f32(duration).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fyrox_visual_scripting::{
+        compile, model::VariableDef, BlackboardCondition, BlueprintGraph, BuiltinNodeKind,
+        ConditionOp, DataType, GraphId, GraphKind, Link, Node, NullHost, Value,
+    };
+
+    fn blueprint() -> Interpreter {
+        let mut graph = BlueprintGraph::new(GraphId("guard".to_string()));
+        graph.variables.push(VariableDef {
+            name: "alerted".to_string(),
+            data_type: DataType::Bool,
+            default_value: Some(Value::Bool(true)),
+        });
+        graph.add_graph("Attack".to_string(), GraphKind::Function);
+        let mut entry = Node::behavior_task("Attack");
+        entry.graph = "Attack".to_string();
+        let entry = graph.add_node(entry);
+        let mut finish = Node::new(BuiltinNodeKind::FinishTask);
+        finish.set_property_bool("success", true);
+        finish.graph = "Attack".to_string();
+        let finish = graph.add_node(finish);
+        let then = graph.nodes[&entry].pin_named("then").unwrap();
+        let exec = graph.nodes[&finish].pin_named("exec").unwrap();
+        graph.add_link(Link::exec(then, exec));
+        Interpreter::new(compile(&graph).unwrap())
+    }
+
+    #[test]
+    fn runner_ticks_tasks_behind_conditions_and_cooldowns() {
+        let mut graph = BehaviorGraph::default();
+        let sequence = graph.add_node(BehaviorNodeKind::Sequence);
+        let condition = graph.add_node(BehaviorNodeKind::Condition(BlackboardCondition {
+            variable: "alerted".to_string(),
+            op: ConditionOp::Equal,
+            value: "true".to_string(),
+        }));
+        let cooldown = graph.add_node(BehaviorNodeKind::Cooldown { duration: 1.0 });
+        let attack = graph.add_node(BehaviorNodeKind::Task {
+            task: "Attack".to_string(),
+        });
+        graph.root = Some(sequence);
+        graph.link(sequence, condition);
+        graph.link(sequence, cooldown);
+        graph.link(cooldown, attack);
+
+        let runner = BehaviorRunner::new(graph);
+        let mut interpreter = blueprint();
+        let mut attacks = |dt| {
+            runner
+                .tick(&mut interpreter, &NullHost, dt)
+                .iter()
+                .filter(|event| matches!(event, ExecutionEvent::EnterNode(_)))
+                .count()
+        };
+        assert_eq!(attacks(0.1), 1);
+        assert_eq!(attacks(0.5), 0);
+        assert_eq!(attacks(0.6), 1);
+    }
+
+    #[test]
+    fn behavior_asset_round_trips_the_graph() {
+        let mut graph = BehaviorGraph::default();
+        graph.root = Some(graph.add_node(BehaviorNodeKind::Repeat { count: 3 }));
+        let asset = BehaviorAsset {
+            version: 1,
+            tree_json: serde_json::to_string(&graph).unwrap(),
+        };
+        assert_eq!(asset.graph().unwrap(), graph);
+        assert_eq!(
+            BehaviorAsset::default().graph().unwrap(),
+            BehaviorGraph::default()
+        );
+    }
+}
@@ -0,0 +1,81 @@
+//! Engine side of the data table nodes: reads rows of data table assets.
+
+use fyrox::{
+    asset::manager::ResourceManager,
+    core::futures::executor::block_on,
+    resource::data_table::{DataCell, DataTable},
+};
+use fyrox_visual_scripting::Value;
+
+fn cell_value(cell: &DataCell) -> Value {
+    match cell {
+        DataCell::Bool(v) => Value::Bool(*v),
+        // Blueprints only have 32-bit integers.
+        DataCell::Integer(v) => Value::I32((*v).clamp(i32::MIN as i64, i32::MAX as i64) as i32),
+        DataCell::Float(v) => Value::F32(*v),
+        DataCell::String(v) => Value::String(v.clone()),
+    }
+}
+
+/// Cells of the row with the given key by column name, starting with the key column.
+pub fn row_cells(table: &DataTable, key: &str) -> Option<Vec<(String, Value)>> {
+    let row = table.row(key)?;
+    let key = (
+        table.key_column().to_string(),
+        Value::String(row.key.clone()),
+    );
+    let cells = table
+        .columns()
+        .iter()
+        .zip(row.cells.iter())
+        .map(|(column, cell)| (column.name.clone(), cell_value(cell)));
+    Some(std::iter::once(key).chain(cells).collect())
+}
+
+/// Loads the data table at the given path, or returns the already loaded one. Load errors are
+/// reported by the resource manager.
+fn load_table<T>(
+    resource_manager: &ResourceManager,
+    path: &str,
+    func: impl FnOnce(&DataTable) -> T,
+) -> Option<T> {
+    let table = block_on(resource_manager.request::<DataTable>(path)).ok()?;
+    let guard = table.data_ref();
+    Some(func(&guard))
+}
+
+/// Cells of a row of the data table at the given path, see [`row_cells`].
+pub fn data_table_row(
+    resource_manager: &ResourceManager,
+    table: &str,
+    key: &str,
+) -> Option<Vec<(String, Value)>> {
+    load_table(resource_manager, table, |table| row_cells(table, key)).flatten()
+}
+
+/// Keys of the rows of the data table at the given path.
+pub fn data_table_keys(resource_manager: &ResourceManager, table: &str) -> Vec<String> {
+    load_table(resource_manager, table, |table| {
+        table.rows.iter().map(|row| row.key.clone()).collect()
+    })
+    .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_become_blueprint_values() {
+        let table = DataTable::from_csv("name,damage:float,ammo:int\nrifle,12.5,30\n").unwrap();
+        assert_eq!(
+            row_cells(&table, "rifle").unwrap(),
+            vec![
+                ("name".to_string(), Value::String("rifle".to_string())),
+                ("damage".to_string(), Value::F32(12.5)),
+                ("ammo".to_string(), Value::I32(30)),
+            ]
+        );
+        assert!(row_cells(&table, "shotgun").is_none());
+    }
+}
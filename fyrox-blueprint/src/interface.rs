@@ -0,0 +1,209 @@
+use fyrox::{
+    asset::{
+        io::ResourceIo,
+        loader::{BoxedLoaderFuture, LoaderPayload, ResourceLoader},
+        state::LoadError,
+        Resource, ResourceData,
+    },
+    core::{
+        reflect::prelude::*, type_traits::prelude::*, uuid::Uuid, visitor::prelude::*,
+        TypeUuidProvider,
+    },
+};
+use fyrox_visual_scripting::{BlueprintInterface, DataType, InterfaceFunction, ParamDef};
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+/// Type of an interface function parameter.
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+    TypeUuidProvider,
+)]
+#[type_uuid(id = "5d2b7c61-93f0-4a8e-b3c4-2e7f1a9d6b05")]
+pub enum InterfaceParamType {
+    Bool,
+    I32,
+    F32,
+    #[default]
+    String,
+}
+
+impl From<InterfaceParamType> for DataType {
+    fn from(value: InterfaceParamType) -> Self {
+        match value {
+            InterfaceParamType::Bool => DataType::Bool,
+            InterfaceParamType::I32 => DataType::I32,
+            InterfaceParamType::F32 => DataType::F32,
+            InterfaceParamType::String => DataType::String,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Visit, Reflect, TypeUuidProvider)]
+#[type_uuid(id = "8e41a2d9-0c7b-4f36-a5e1-6b9d3f2c7a48")]
+pub struct InterfaceParamDef {
+    #[visit(optional)]
+    pub name: String,
+    #[visit(optional)]
+    pub data_type: InterfaceParamType,
+}
+
+/// Function signature. Interfaces declare functions without implementations.
+#[derive(Debug, Default, Clone, PartialEq, Visit, Reflect, TypeUuidProvider)]
+#[type_uuid(id = "f17c3b52-8d4e-4a09-9c6f-1e5a7b2d8c93")]
+pub struct InterfaceFunctionDef {
+    #[visit(optional)]
+    pub name: String,
+    #[visit(optional)]
+    pub inputs: Vec<InterfaceParamDef>,
+}
+
+/// `.blueprint_interface` asset: a named set of function signatures that blueprints can implement.
+#[derive(TypeUuidProvider, Debug, Default, Clone, Visit, Reflect)]
+#[type_uuid(id = "c3a8d0f4-6a52-4f5e-9b8e-0d2f1c7b5e31")]
+pub struct BlueprintInterfaceAsset {
+    /// Asset format version. Useful for future migrations.
+    #[visit(optional)]
+    pub version: u32,
+
+    /// Interface name. Blueprints refer to the interface by this name.
+    #[visit(optional)]
+    pub name: String,
+
+    #[visit(optional)]
+    pub functions: Vec<InterfaceFunctionDef>,
+}
+
+impl BlueprintInterfaceAsset {
+    pub async fn from_file(path: &Path, io: &dyn ResourceIo) -> Result<Self, VisitError> {
+        let bytes = io.load_file(path).await?;
+        let mut visitor = Visitor::load_from_memory(&bytes)?;
+        let mut asset = BlueprintInterfaceAsset::default();
+        asset.visit("BlueprintInterface", &mut visitor)?;
+        Ok(asset)
+    }
+
+    /// Engine-agnostic form of the interface, as stored by implementing graphs.
+    pub fn to_interface(&self) -> BlueprintInterface {
+        BlueprintInterface {
+            name: self.name.clone(),
+            functions: self
+                .functions
+                .iter()
+                .map(|f| InterfaceFunction {
+                    name: f.name.clone(),
+                    inputs: f
+                        .inputs
+                        .iter()
+                        .map(|p| ParamDef {
+                            name: p.name.clone(),
+                            data_type: p.data_type.into(),
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+pub type BlueprintInterfaceResource = Resource<BlueprintInterfaceAsset>;
+
+impl ResourceData for BlueprintInterfaceAsset {
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut visitor = Visitor::new();
+        self.visit("BlueprintInterface", &mut visitor)?;
+        visitor.save_ascii_to_file(path)?;
+        Ok(())
+    }
+
+    fn can_be_saved(&self) -> bool {
+        true
+    }
+
+    fn try_clone_box(&self) -> Option<Box<dyn ResourceData>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+#[derive(Default)]
+pub struct BlueprintInterfaceLoader;
+
+impl BlueprintInterfaceLoader {
+    pub const EXT: &'static str = "blueprint_interface";
+}
+
+impl ResourceLoader for BlueprintInterfaceLoader {
+    fn extensions(&self) -> &[&str] {
+        &[Self::EXT]
+    }
+
+    fn is_native_extension(&self, ext: &str) -> bool {
+        fyrox::core::cmp_strings_case_insensitive(ext, Self::EXT)
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        <BlueprintInterfaceAsset as TypeUuidProvider>::type_uuid()
+    }
+
+    fn load(&self, path: PathBuf, io: Arc<dyn ResourceIo>) -> BoxedLoaderFuture {
+        Box::pin(async move {
+            let asset = BlueprintInterfaceAsset::from_file(&path, io.as_ref())
+                .await
+                .map_err(LoadError::new)?;
+            Ok(LoaderPayload::new(asset))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fyrox::asset::io::FsResourceIo;
+    use fyrox::core::futures::executor::block_on;
+
+    #[test]
+    fn interface_asset_save_load_roundtrip() {
+        let dir = std::env::temp_dir().join("fyrox_blueprint_tests");
+        let _ = std::fs::create_dir_all(&dir);
+        let path = dir.join(format!("roundtrip.{}", BlueprintInterfaceLoader::EXT));
+
+        let mut asset = BlueprintInterfaceAsset {
+            version: 1,
+            name: "Damageable".to_string(),
+            functions: vec![InterfaceFunctionDef {
+                name: "TakeDamage".to_string(),
+                inputs: vec![InterfaceParamDef {
+                    name: "amount".to_string(),
+                    data_type: InterfaceParamType::F32,
+                }],
+            }],
+        };
+        asset.save(&path).unwrap();
+
+        let loaded = block_on(BlueprintInterfaceAsset::from_file(&path, &FsResourceIo)).unwrap();
+        assert_eq!(loaded.name, asset.name);
+        assert_eq!(loaded.functions, asset.functions);
+
+        let interface = loaded.to_interface();
+        let function = interface.function("TakeDamage").unwrap();
+        assert_eq!(function.inputs[0].data_type, DataType::F32);
+    }
+}
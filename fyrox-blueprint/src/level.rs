@@ -0,0 +1,473 @@
+//! Level blueprints: graphs that run for the whole game instead of on an actor, like a game mode.
+//! They receive `OnSceneLoaded` and `OnSceneUnloaded` events, load scenes with `LoadScene` nodes and
+//! find actors with `GetActorsWithBlueprint`. Their variables live as long as the plugin, so they
+//! keep their values across scene switches and serve as the global variables of the game, which
+//! actor blueprints read and change with `GetGlobal` and `SetGlobal`.
+
+use fyrox::{
+    asset::manager::ResourceManager,
+    core::{
+        futures::executor::block_on, log::Log, pool::Handle, reflect::prelude::*,
+        visitor::prelude::*,
+    },
+    graph::SceneGraph,
+    plugin::{Plugin, PluginContext, PluginRegistrationContext},
+    scene::{graph::Graph, node::Node, Scene, SceneContainer},
+};
+use fyrox_visual_scripting::{
+    compile, host::is_self_target, BlueprintGraph, BlueprintHost, CompiledGraph, ExecutionEvent,
+    Interpreter, Value,
+};
+use std::path::{Path, PathBuf};
+
+use crate::{
+    active_state, actors_with_tag, apply_animation_command, apply_sound_command, apply_tag_command,
+    apply_timer_command, apply_widget_command, data_table_keys, data_table_row, fired_event,
+    has_tag, log_print, register, register_resources, sound_gain, BlueprintAsset,
+    BlueprintResource, BlueprintScript, EventTimers,
+};
+
+fn normalized_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// Names of the actors of the graph whose blueprint script runs the blueprint asset at the given
+/// path.
+pub fn actors_with_blueprint(
+    graph: &Graph,
+    resource_manager: &ResourceManager,
+    blueprint: &str,
+) -> Vec<String> {
+    let blueprint = normalized_path(Path::new(blueprint));
+    graph
+        .pair_iter()
+        .filter(|(_, node)| {
+            node.try_get_script::<BlueprintScript>()
+                .and_then(|script| script.blueprint.as_ref())
+                .and_then(|resource| resource_manager.resource_path(resource))
+                .is_some_and(|path| normalized_path(&path) == blueprint)
+        })
+        .map(|(_, node)| node.name_owned())
+        .collect()
+}
+
+/// Gives level blueprints read access to every loaded scene. Level blueprints have no actor of
+/// their own, so `self` references nothing.
+struct LevelHost<'a> {
+    scenes: &'a SceneContainer,
+    resource_manager: &'a ResourceManager,
+}
+
+impl<'a> LevelHost<'a> {
+    fn new(context: &'a PluginContext) -> Self {
+        Self {
+            scenes: context.scenes,
+            resource_manager: context.resource_manager,
+        }
+    }
+
+    fn find_actor(&self, target: &str) -> Option<(&'a Graph, Handle<Node>)> {
+        if is_self_target(target) {
+            return None;
+        }
+        self.scenes.iter().find_map(|scene| {
+            let (actor, _) = scene.graph.find_by_name_from_root(target)?;
+            Some((&scene.graph, actor))
+        })
+    }
+}
+
+impl BlueprintHost for LevelHost<'_> {
+    fn does_implement_interface(&self, target: &str, interface: &str) -> bool {
+        self.find_actor(target).is_some_and(|(graph, actor)| {
+            graph
+                .try_get_script_of::<BlueprintScript>(actor)
+                .is_some_and(|script| script.implements_interface(interface))
+        })
+    }
+
+    fn active_state(&self, target: &str, layer: usize) -> Option<String> {
+        let (graph, actor) = self.find_actor(target)?;
+        active_state(graph, actor, layer)
+    }
+
+    fn sound_gain(&self, target: &str) -> Option<f32> {
+        let (graph, actor) = self.find_actor(target)?;
+        sound_gain(graph, actor)
+    }
+
+    fn actors_with_blueprint(&self, blueprint: &str) -> Vec<String> {
+        self.scenes
+            .iter()
+            .flat_map(|scene| actors_with_blueprint(&scene.graph, self.resource_manager, blueprint))
+            .collect()
+    }
+
+    fn actors_with_tag(&self, tag: &str) -> Vec<String> {
+        self.scenes
+            .iter()
+            .flat_map(|scene| actors_with_tag(&scene.graph, tag))
+            .collect()
+    }
+
+    fn has_tag(&self, target: &str, tag: &str) -> bool {
+        self.find_actor(target)
+            .is_some_and(|(graph, actor)| has_tag(graph, actor, tag))
+    }
+
+    fn data_table_row(&self, table: &str, key: &str) -> Option<Vec<(String, Value)>> {
+        data_table_row(self.resource_manager, table, key)
+    }
+
+    fn data_table_keys(&self, table: &str) -> Vec<String> {
+        data_table_keys(self.resource_manager, table)
+    }
+}
+
+/// A running level blueprint.
+struct LevelBlueprint {
+    path: PathBuf,
+    interpreter: Interpreter,
+    // Timers of the current level started by `SetTimerByEvent` nodes.
+    timers: EventTimers,
+}
+
+impl std::fmt::Debug for LevelBlueprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LevelBlueprint")
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+/// Runs level blueprints. The plugin runs Construction Script and BeginPlay of every blueprint when
+/// it is initialized, Tick every frame and the scene events whenever a scene is loaded or removed.
+///
+/// Like the game plugin of the executor, the plugin treats the last loaded scene as the current
+/// level: a scene that finishes loading replaces the previous one.
+#[derive(Debug, Default, Visit, Reflect)]
+#[reflect(non_cloneable)]
+pub struct BlueprintLevelPlugin {
+    #[reflect(hidden)]
+    #[visit(skip)]
+    blueprints: Vec<PathBuf>,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    running: Vec<LevelBlueprint>,
+
+    // Loaded scenes and their paths, used to notice scenes removed by other plugins.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    scenes: Vec<(Handle<Scene>, String)>,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    level: Handle<Scene>,
+
+    // Scenes requested by actor blueprints, which cannot reach the scene loader themselves.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    requests: Vec<String>,
+}
+
+impl BlueprintLevelPlugin {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a `.blueprint` asset to run when the plugin is initialized.
+    pub fn with_blueprint(mut self, path: impl Into<PathBuf>) -> Self {
+        self.blueprints.push(path.into());
+        self
+    }
+
+    /// Requests loading of the scene at the given path on the next update. Used by `LoadScene`
+    /// nodes of actor blueprints.
+    pub fn load_scene(&mut self, path: impl Into<String>) {
+        self.requests.push(path.into());
+    }
+
+    /// Value of a variable of a running level blueprint.
+    pub fn global(&self, name: &str) -> Option<&Value> {
+        self.running
+            .iter()
+            .find_map(|running| running.interpreter.variables().get(name))
+    }
+
+    /// Changes the variable of the first running level blueprint that has it. Returns `false` if
+    /// there is no such variable or it has another type. Used by `SetGlobal` nodes of actor
+    /// blueprints.
+    pub fn set_global(&mut self, name: &str, value: Value) -> bool {
+        self.running
+            .iter_mut()
+            .find(|running| running.interpreter.variables().contains_key(name))
+            .is_some_and(|running| running.interpreter.set_variable_value(name, value))
+    }
+
+    fn start(&mut self, context: &mut PluginContext, path: PathBuf) {
+        let blueprint = match block_on(context.resource_manager.request::<BlueprintAsset>(&path)) {
+            Ok(blueprint) => blueprint,
+            Err(err) => {
+                Log::err(format!(
+                    "BlueprintLevelPlugin: failed to load {}: {err:?}",
+                    path.display()
+                ));
+                return;
+            }
+        };
+        let Some(compiled) = compile_level_blueprint(&blueprint, &path) else {
+            return;
+        };
+
+        let mut running = LevelBlueprint {
+            path,
+            interpreter: Interpreter::new(compiled),
+            timers: Default::default(),
+        };
+        let construction = running
+            .interpreter
+            .run_construction_script_with_host(&LevelHost::new(context));
+        running.apply(context, self.level, construction.events);
+        let begin_play = running
+            .interpreter
+            .run_begin_play_with_host(&LevelHost::new(context));
+        running.apply(context, self.level, begin_play.events);
+        self.running.push(running);
+    }
+
+    fn dispatch_scene_event(&mut self, context: &mut PluginContext, scene: &str, loaded: bool) {
+        for running in self.running.iter_mut() {
+            let host = LevelHost::new(context);
+            let out = if loaded {
+                running.interpreter.run_scene_loaded(&host, scene)
+            } else {
+                running.interpreter.run_scene_unloaded(&host, scene)
+            };
+            running.apply(context, self.level, out.events);
+        }
+    }
+
+    /// Runs the custom events of the level timers that fired during the last update.
+    fn run_custom_events(&mut self, context: &mut PluginContext) {
+        let Some(scene) = context.scenes.try_get(self.level) else {
+            return;
+        };
+        let fired = scene
+            .timers
+            .fired()
+            .iter()
+            .filter(|fired| fired.owner.is_none())
+            .map(|fired| fired.timer)
+            .collect::<Vec<_>>();
+        for running in self.running.iter_mut() {
+            for &timer in fired.iter() {
+                let Some(scene) = context.scenes.try_get(self.level) else {
+                    return;
+                };
+                let Some(event) = fired_event(&mut running.timers, &scene.timers, timer) else {
+                    continue;
+                };
+                let out = running
+                    .interpreter
+                    .dispatch_custom_event(&LevelHost::new(context), &event);
+                running.apply(context, self.level, out.events);
+            }
+        }
+    }
+}
+
+fn compile_level_blueprint(blueprint: &BlueprintResource, path: &Path) -> Option<CompiledGraph> {
+    let guard = blueprint.data_ref();
+    let asset = guard.as_loaded_ref()?;
+    let compiled = serde_json::from_str::<BlueprintGraph>(&asset.graph_json)
+        .map_err(|err| err.to_string())
+        .and_then(|graph| compile(&graph).map_err(|err| err.to_string()));
+    match compiled {
+        Ok(compiled) => Some(compiled),
+        Err(err) => {
+            Log::err(format!(
+                "BlueprintLevelPlugin: {} does not compile: {err}",
+                path.display()
+            ));
+            None
+        }
+    }
+}
+
+/// Calls `apply` with the graph of the current level first and then with the other scenes, until
+/// it reports success.
+fn apply_to_scenes(
+    context: &mut PluginContext,
+    level: Handle<Scene>,
+    mut apply: impl FnMut(&mut Graph, &ResourceManager) -> bool,
+) -> bool {
+    let resource_manager = context.resource_manager;
+    if let Some(scene) = context.scenes.try_get_mut(level) {
+        if apply(&mut scene.graph, resource_manager) {
+            return true;
+        }
+    }
+    let others = context
+        .scenes
+        .pair_iter_mut()
+        .filter(|(handle, _)| *handle != level);
+    for (_, scene) in others {
+        if apply(&mut scene.graph, resource_manager) {
+            return true;
+        }
+    }
+    false
+}
+
+impl LevelBlueprint {
+    fn apply(
+        &mut self,
+        context: &mut PluginContext,
+        level: Handle<Scene>,
+        events: Vec<ExecutionEvent>,
+    ) {
+        for event in events {
+            match event {
+                ExecutionEvent::EnterNode(_) => {}
+                ExecutionEvent::Print(text) => log_print(None, text),
+                ExecutionEvent::Widget(command) => {
+                    for ui in context.user_interfaces.iter() {
+                        apply_widget_command(ui, &command);
+                    }
+                }
+                ExecutionEvent::Assertion(result) => {
+                    if let Some(failure) = result.failure {
+                        Log::err(format!(
+                            "[Blueprint] {}: {} failed: {failure}",
+                            self.path.display(),
+                            result.name
+                        ));
+                    }
+                }
+                ExecutionEvent::Animation(command) => {
+                    apply_to_scenes(context, level, |graph, _| {
+                        apply_animation_command(graph, Handle::NONE, &command)
+                    });
+                }
+                ExecutionEvent::Sound(command) => {
+                    apply_to_scenes(context, level, |graph, resource_manager| {
+                        apply_sound_command(graph, resource_manager, Handle::NONE, &command)
+                    });
+                }
+                // The target runs the call on its next update, where it has a script context.
+                ExecutionEvent::InterfaceMessage(call) => {
+                    let delivered = apply_to_scenes(context, level, |graph, _| {
+                        let Some((actor, _)) = graph.find_by_name_from_root(&call.target) else {
+                            return false;
+                        };
+                        let Some(script) = graph.try_get_script_of_mut::<BlueprintScript>(actor)
+                        else {
+                            return false;
+                        };
+                        script.post_interface_call(call.clone());
+                        true
+                    });
+                    if !delivered {
+                        Log::warn(format!(
+                            "BlueprintLevelPlugin: {}: no blueprint actor {} for {}::{}",
+                            self.path.display(),
+                            call.target,
+                            call.interface,
+                            call.function
+                        ));
+                    }
+                }
+                ExecutionEvent::LoadScene(path) => context.async_scene_loader.request(path),
+                // Level blueprints change their own variables, like `GetGlobal` reads them.
+                ExecutionEvent::SetGlobal(name, value) => {
+                    if !self.interpreter.set_variable_value(&name, value) {
+                        Log::warn(format!(
+                            "BlueprintLevelPlugin: {}: no variable {name} of this type",
+                            self.path.display()
+                        ));
+                    }
+                }
+                ExecutionEvent::Tag(command) => {
+                    apply_to_scenes(context, level, |graph, _| {
+                        apply_tag_command(graph, Handle::NONE, &command)
+                    });
+                }
+                // Timers have no owner and live in the current level, so they stop with it.
+                ExecutionEvent::Timer(command) => match context.scenes.try_get_mut(level) {
+                    Some(scene) => {
+                        let manager = &mut scene.timers;
+                        apply_timer_command(&mut self.timers, manager, Handle::NONE, &command);
+                    }
+                    None => Log::warn(format!(
+                        "BlueprintLevelPlugin: {}: no level to run timer command {command:?}",
+                        self.path.display()
+                    )),
+                },
+            }
+        }
+    }
+}
+
+impl Plugin for BlueprintLevelPlugin {
+    fn register(&self, context: PluginRegistrationContext) {
+        register_resources(context.resource_manager);
+        register(&context.serialization_context.script_constructors);
+    }
+
+    fn init(&mut self, _scene_path: Option<&str>, mut context: PluginContext) {
+        for path in std::mem::take(&mut self.blueprints) {
+            self.start(&mut context, path);
+        }
+    }
+
+    fn update(&mut self, context: &mut PluginContext) {
+        for path in std::mem::take(&mut self.requests) {
+            context.async_scene_loader.request(path);
+        }
+
+        let (alive, removed) = std::mem::take(&mut self.scenes)
+            .into_iter()
+            .partition(|(scene, _)| context.scenes.is_valid_handle(*scene));
+        self.scenes = alive;
+        for (_, path) in removed {
+            self.dispatch_scene_event(context, &path, false);
+        }
+
+        for running in self.running.iter_mut() {
+            let out = running
+                .interpreter
+                .tick_with_host(&LevelHost::new(context), context.dt);
+            running.apply(context, self.level, out.events);
+        }
+
+        self.run_custom_events(context);
+    }
+
+    fn on_scene_loaded(
+        &mut self,
+        path: &Path,
+        scene: Handle<Scene>,
+        _data: &[u8],
+        context: &mut PluginContext,
+    ) {
+        let previous = std::mem::replace(&mut self.level, scene);
+        if previous != scene && context.scenes.is_valid_handle(previous) {
+            context.scenes.remove(previous);
+            if let Some(index) = self.scenes.iter().position(|(s, _)| *s == previous) {
+                let (_, previous_path) = self.scenes.remove(index);
+                self.dispatch_scene_event(context, &previous_path, false);
+            }
+        }
+
+        if previous != scene {
+            for running in self.running.iter_mut() {
+                running.timers.clear();
+            }
+        }
+
+        let path = normalized_path(path);
+        self.scenes.push((scene, path.clone()));
+        self.dispatch_scene_event(context, &path, true);
+    }
+}
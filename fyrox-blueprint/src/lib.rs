@@ -1,28 +1,223 @@
+mod animation;
+mod behavior;
+mod data_tables;
+mod interface;
+mod level;
+mod profiler;
 mod resource;
 mod screen_log;
+mod sound;
+mod tags;
+mod timers;
+mod test_runner;
+mod types;
+mod ui;
 
 use fyrox::{
+    asset::{
+        event::{ResourceEvent, ResourceEventSender},
+        manager::ResourceManager,
+    },
     core::{
         impl_component_provider,
         log::Log,
+        pool::Handle,
         reflect::prelude::*,
         uuid_provider,
         variable::InheritableVariable,
         visitor::prelude::*,
     },
-    script::{constructor::ScriptConstructorContainer, ScriptContext, ScriptTrait},
+    graph::{BaseSceneGraph, SceneGraph},
+    scene::{graph::Graph, node::Node, timer::TimerFired},
+    script::{
+        constructor::ScriptConstructorContainer, ScriptContext, ScriptDeinitContext,
+        ScriptMessageContext, ScriptMessagePayload, ScriptTrait,
+    },
 };
 use fyrox_visual_scripting::{
     compile,
     compile::CompiledGraph,
-    interpret::{ExecutionEvent, Interpreter},
-    BlueprintGraph,
+    host::{is_self_target, SELF_TARGET},
+    interpret::{AssertionResult, ExecutionEvent, InterfaceCall, Interpreter},
+    BlueprintGraph, BlueprintHost, UserTypes, Value, WidgetCommand,
 };
 
-use std::sync::Once;
+use std::{
+    collections::{BTreeSet, VecDeque},
+    sync::{
+        mpsc::{self, Receiver},
+        Once,
+    },
+};
 
+pub use crate::animation::{active_state, apply_animation_command, take_animation_signals};
+pub use crate::behavior::{BehaviorAsset, BehaviorLoader, BehaviorResource, BehaviorRunner};
+pub use crate::data_tables::{data_table_keys, data_table_row, row_cells};
+pub use crate::interface::{
+    BlueprintInterfaceAsset, BlueprintInterfaceLoader, BlueprintInterfaceResource,
+    InterfaceFunctionDef, InterfaceParamDef, InterfaceParamType,
+};
+pub use crate::level::{actors_with_blueprint, BlueprintLevelPlugin};
+pub use crate::profiler::BlueprintProfilerPlugin;
 pub use crate::resource::{register_resources, BlueprintAsset, BlueprintLoader, BlueprintResource};
 pub use crate::screen_log::BlueprintScreenLogPlugin;
+pub use crate::sound::{apply_sound_command, is_sound_playing, sound_gain};
+pub use crate::tags::{actors_with_tag, apply_tag_command, has_tag};
+pub use crate::test_runner::{BlueprintTestCase, BlueprintTestReport, BlueprintTestRunnerPlugin};
+pub use crate::timers::{apply_timer_command, fired_event, EventTimers};
+pub use crate::types::{
+    collect_types, define_from_resource, BlueprintEnumAsset, BlueprintEnumLoader,
+    BlueprintEnumResource, BlueprintStructAsset, BlueprintStructLoader, BlueprintStructResource,
+    StructFieldDef, StructFieldElement, StructFieldType,
+};
+pub use crate::ui::{apply_widget_command, widget_event, BlueprintUiPlugin};
+
+/// Resolves an actor reference used by blueprint nodes: empty or `self` is the calling node,
+/// anything else is a node name.
+pub fn resolve_actor(graph: &Graph, self_handle: Handle<Node>, target: &str) -> Handle<Node> {
+    if is_self_target(target) {
+        return self_handle;
+    }
+    graph
+        .find_by_name_from_root(target)
+        .map(|(handle, _)| handle)
+        .unwrap_or_default()
+}
+
+/// The actor itself and every descendant of it that has a component of type `T`. Characters usually
+/// keep their state machines, animation players and sounds in child nodes.
+pub(crate) fn find_components<T: 'static>(
+    graph: &Graph,
+    actor: Handle<Node>,
+) -> Vec<Handle<Node>> {
+    if graph.try_get_node(actor).is_none() {
+        return Vec::new();
+    }
+    graph
+        .traverse_handle_iter(actor)
+        .filter(|handle| graph.try_get_of_type::<T>(*handle).is_some())
+        .collect()
+}
+
+/// Gives running graphs read access to the scene of the node they run on and to the globals of
+/// the level plugin.
+struct SceneHost<'a> {
+    graph: &'a Graph,
+    handle: Handle<Node>,
+    resource_manager: &'a ResourceManager,
+    level: Option<&'a BlueprintLevelPlugin>,
+}
+
+impl BlueprintHost for SceneHost<'_> {
+    fn is_self(&self, target: &str) -> bool {
+        resolve_actor(self.graph, self.handle, target) == self.handle
+    }
+
+    fn does_implement_interface(&self, target: &str, interface: &str) -> bool {
+        let target = resolve_actor(self.graph, self.handle, target);
+        self.graph
+            .try_get_script_of::<BlueprintScript>(target)
+            .is_some_and(|script| script.implements_interface(interface))
+    }
+
+    fn active_state(&self, target: &str, layer: usize) -> Option<String> {
+        active_state(self.graph, resolve_actor(self.graph, self.handle, target), layer)
+    }
+
+    fn sound_gain(&self, target: &str) -> Option<f32> {
+        sound_gain(self.graph, resolve_actor(self.graph, self.handle, target))
+    }
+
+    fn actors_with_blueprint(&self, blueprint: &str) -> Vec<String> {
+        actors_with_blueprint(self.graph, self.resource_manager, blueprint)
+    }
+
+    fn actors_with_tag(&self, tag: &str) -> Vec<String> {
+        actors_with_tag(self.graph, tag)
+    }
+
+    fn has_tag(&self, target: &str, tag: &str) -> bool {
+        has_tag(self.graph, resolve_actor(self.graph, self.handle, target), tag)
+    }
+
+    fn data_table_row(&self, table: &str, key: &str) -> Option<Vec<(String, Value)>> {
+        data_table_row(self.resource_manager, table, key)
+    }
+
+    fn data_table_keys(&self, table: &str) -> Vec<String> {
+        data_table_keys(self.resource_manager, table)
+    }
+
+    fn global(&self, name: &str) -> Option<Value> {
+        self.level?.global(name).cloned()
+    }
+}
+
+fn log_print(ctx: Option<&mut ScriptContext>, text: String) {
+    Log::info(format!("[Blueprint] {text}"));
+
+    if let Some(ctx) = ctx {
+        if let Some(screen_log) = ctx.plugins.of_type_mut::<BlueprintScreenLogPlugin>() {
+            screen_log.push(text);
+        }
+    }
+}
+
+/// Actor blueprints do not own a user interface, so their widget commands go to every user
+/// interface that has a widget with the commanded name.
+fn broadcast_widget_command(ctx: Option<&ScriptContext>, command: &WidgetCommand) {
+    let Some(ctx) = ctx else {
+        return;
+    };
+    for ui in ctx.user_interfaces.iter() {
+        apply_widget_command(ui, command);
+    }
+}
+
+/// Actor blueprints cannot reach the scene loader, so their scene loads go through the level
+/// plugin.
+fn request_scene_load(ctx: Option<&mut ScriptContext>, path: String) {
+    match ctx.and_then(|ctx| ctx.plugins.of_type_mut::<BlueprintLevelPlugin>()) {
+        Some(level) => level.load_scene(path),
+        None => Log::warn(format!(
+            "BlueprintScript: loading {path} needs a BlueprintLevelPlugin"
+        )),
+    }
+}
+
+/// Globals are the variables of level blueprints, so actor blueprints change them through the
+/// level plugin.
+fn set_global(ctx: Option<&mut ScriptContext>, name: String, value: Value) {
+    let Some(level) = ctx.and_then(|ctx| ctx.plugins.of_type_mut::<BlueprintLevelPlugin>()) else {
+        Log::warn(format!(
+            "BlueprintScript: setting global {name} needs a BlueprintLevelPlugin"
+        ));
+        return;
+    };
+    if !level.set_global(&name, value) {
+        Log::warn(format!(
+            "BlueprintScript: no level blueprint has a global {name} of this type"
+        ));
+    }
+}
+
+fn log_assertion(ctx: Option<&mut ScriptContext>, actor: Handle<Node>, result: AssertionResult) {
+    let Some(ctx) = ctx else {
+        return;
+    };
+    let suite = ctx
+        .scene
+        .graph
+        .try_get(actor)
+        .map(|node| node.name_owned())
+        .unwrap_or_else(|| actor.to_string());
+    if let Some(failure) = result.failure.as_ref() {
+        Log::err(format!("[Blueprint] {suite}: {} failed: {failure}", result.name));
+    }
+    if let Some(runner) = ctx.plugins.of_type_mut::<BlueprintTestRunnerPlugin>() {
+        runner.record(&suite, result);
+    }
+}
 
 #[derive(Visit, Reflect)]
 #[reflect(non_cloneable)]
@@ -31,6 +226,11 @@ pub struct BlueprintScript {
     #[visit(optional)]
     pub blueprint: InheritableVariable<Option<BlueprintResource>>,
 
+    /// Behavior tree ticked after the `Tick` event. Its tasks are function graphs of the blueprint
+    /// and its blackboard conditions read the variables of the blueprint.
+    #[visit(optional)]
+    pub behavior: InheritableVariable<Option<BehaviorResource>>,
+
     #[reflect(hidden)]
     #[visit(optional)]
     pub construction_ran: InheritableVariable<bool>,
@@ -46,6 +246,36 @@ pub struct BlueprintScript {
     #[reflect(hidden)]
     #[visit(skip)]
     interpreter: Option<Interpreter>,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    behavior_runner: Option<BehaviorRunner>,
+
+    // Resource manager events, used to pick up edits of the blueprint asset while the game runs.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    resource_events: Option<(Receiver<ResourceEvent>, Handle<ResourceEventSender>)>,
+
+    // Names of the sounds with `OnSoundFinished` nodes that were playing at the last update.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    playing_sounds: BTreeSet<String>,
+
+    // Interface calls posted by level blueprints, run on the next update.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    inbox: Vec<InterfaceCall>,
+
+    // Engine timers started by `SetTimerByEvent` nodes. Saved with the script, since the timers
+    // themselves are saved with the scene.
+    #[reflect(hidden)]
+    #[visit(optional)]
+    timers: EventTimers,
+
+    // Custom events of the timers that fired, run on the next update.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    fired_events: Vec<String>,
 }
 
 impl std::fmt::Debug for BlueprintScript {
@@ -62,10 +292,17 @@ impl Clone for BlueprintScript {
     fn clone(&self) -> Self {
         Self {
             blueprint: self.blueprint.clone(),
+            behavior: self.behavior.clone(),
             construction_ran: self.construction_ran.clone(),
             begin_play_ran: self.begin_play_ran.clone(),
             compiled: None,
             interpreter: None,
+            behavior_runner: None,
+            resource_events: None,
+            playing_sounds: Default::default(),
+            inbox: Default::default(),
+            timers: Default::default(),
+            fired_events: Default::default(),
         }
     }
 }
@@ -74,10 +311,17 @@ impl Default for BlueprintScript {
     fn default() -> Self {
         Self {
             blueprint: Default::default(),
+            behavior: Default::default(),
             construction_ran: false.into(),
             begin_play_ran: false.into(),
             compiled: None,
             interpreter: None,
+            behavior_runner: None,
+            resource_events: None,
+            playing_sounds: Default::default(),
+            inbox: Default::default(),
+            timers: Default::default(),
+            fired_events: Default::default(),
         }
     }
 }
@@ -86,7 +330,37 @@ impl_component_provider!(BlueprintScript);
 uuid_provider!(BlueprintScript = "a4c9f660-2a5b-4e8a-b171-5213384e011b");
 
 impl BlueprintScript {
-    fn ensure_compiled(&mut self) {
+    /// Interface messages delivered per flush, including messages sent by the receivers.
+    pub const MAX_INTERFACE_MESSAGES: usize = 64;
+
+    /// Whether the assigned blueprint implements the given interface. Works before the script has
+    /// run, in which case the graph is read from the asset.
+    pub fn implements_interface(&self, interface: &str) -> bool {
+        if let Some(compiled) = self.compiled.as_ref() {
+            return compiled.interfaces.contains(interface);
+        }
+
+        let Some(blueprint) = self.blueprint.clone_inner() else {
+            return false;
+        };
+        let asset_guard = blueprint.data_ref();
+        let Some(asset) = asset_guard.as_loaded_ref() else {
+            return false;
+        };
+        serde_json::from_str::<BlueprintGraph>(&asset.graph_json)
+            .is_ok_and(|graph| graph.implements_interface(interface))
+    }
+
+    /// Queues an interface call to run on the next update of the script. Used to deliver messages
+    /// sent from outside of a scene.
+    pub fn post_interface_call(&mut self, call: InterfaceCall) {
+        self.inbox.push(call);
+    }
+
+    /// Compiles the graph unless it is already running. Definitions of struct and enum types stored
+    /// in the graph are replaced by the ones of the type assets, which could change after the
+    /// blueprint was saved.
+    fn ensure_compiled(&mut self, resource_manager: Option<&ResourceManager>) {
         if self.interpreter.is_some() {
             return;
         }
@@ -106,7 +380,7 @@ impl BlueprintScript {
             return;
         }
 
-        let graph: BlueprintGraph = match serde_json::from_str(&graph_json) {
+        let mut graph: BlueprintGraph = match serde_json::from_str(&graph_json) {
             Ok(graph) => graph,
             Err(err) => {
                 Log::err(format!("BlueprintScript: invalid graph JSON: {err}"));
@@ -114,6 +388,20 @@ impl BlueprintScript {
             }
         };
 
+        let uses_types = !graph.types.structs.is_empty() || !graph.types.enums.is_empty();
+        let migrated = match resource_manager {
+            Some(resource_manager) if uses_types => {
+                graph.refresh_types(&collect_types(resource_manager))
+            }
+            _ => Vec::new(),
+        };
+        if !migrated.is_empty() {
+            Log::info(format!(
+                "BlueprintScript: graph migrated to new definitions of {}",
+                migrated.join(", ")
+            ));
+        }
+
         let compiled = match compile(&graph) {
             Ok(compiled) => compiled,
             Err(err) => {
@@ -126,18 +414,227 @@ impl BlueprintScript {
         self.compiled = Some(compiled);
     }
 
-    fn flush_events(&self, mut ctx: Option<&mut ScriptContext>, events: Vec<ExecutionEvent>) {
+    fn subscribe_to_resource_events(&mut self, resource_manager: &ResourceManager) {
+        if self.resource_events.is_some() {
+            return;
+        }
+        let (sender, receiver) = mpsc::channel();
+        let handle = resource_manager.state().event_broadcaster.add(sender);
+        self.resource_events = Some((receiver, handle));
+    }
+
+    /// Whether the blueprint asset or the definition of a struct or enum type used by the graph
+    /// was reloaded.
+    fn blueprint_was_reloaded(&mut self) -> bool {
+        let Some((receiver, _)) = self.resource_events.as_ref() else {
+            return false;
+        };
+        let key = self.blueprint.as_ref().map(|blueprint| blueprint.key());
+        let behavior_key = self.behavior.as_ref().map(|behavior| behavior.key());
+        let mut reloaded = false;
+        for event in receiver.try_iter() {
+            if let ResourceEvent::Reloaded(resource) = event {
+                reloaded |= Some(resource.key()) == key;
+                if Some(resource.key()) == behavior_key {
+                    // Rebuilt on the next update.
+                    self.behavior_runner = None;
+                }
+                if let Some(name) = define_from_resource(&mut UserTypes::default(), &resource) {
+                    reloaded |= self.compiled.as_ref().is_some_and(|compiled| {
+                        compiled.types.structs.iter().any(|s| s.name == name)
+                            || compiled.types.enums.iter().any(|e| e.name == name)
+                    });
+                }
+            }
+        }
+        reloaded
+    }
+
+    /// Recompiles the graph of a reloaded asset and carries live variable values over to the new
+    /// interpreter. The old graph keeps running if the new one does not compile.
+    fn hot_reload(&mut self, resource_manager: &ResourceManager) {
+        let previous_compiled = self.compiled.take();
+        let Some(previous) = self.interpreter.take() else {
+            // Nothing was running yet, the regular path compiles the new graph.
+            return;
+        };
+
+        self.ensure_compiled(Some(resource_manager));
+        match self.interpreter.as_mut() {
+            Some(interpreter) => {
+                let migrated = interpreter.migrate_variables(&previous);
+                Log::info(format!(
+                    "BlueprintScript: graph reloaded, {migrated} variable(s) migrated"
+                ));
+            }
+            None => {
+                Log::warn("BlueprintScript: reloaded graph is invalid, keeping the old one");
+                self.interpreter = Some(previous);
+                self.compiled = previous_compiled;
+            }
+        }
+    }
+
+    fn flush_events(&mut self, mut ctx: Option<&mut ScriptContext>, events: Vec<ExecutionEvent>) {
+        let mut messages = VecDeque::new();
+        let self_handle = ctx.as_ref().map(|ctx| ctx.handle).unwrap_or_default();
+
         for event in events {
             match event {
                 ExecutionEvent::EnterNode(_) => {}
-                ExecutionEvent::Print(text) => {
-                    Log::info(format!("[Blueprint] {text}"));
-
+                ExecutionEvent::Print(text) => log_print(ctx.as_deref_mut(), text),
+                ExecutionEvent::InterfaceMessage(call) => messages.push_back((self_handle, call)),
+                ExecutionEvent::Assertion(result) => {
+                    log_assertion(ctx.as_deref_mut(), self_handle, result)
+                }
+                ExecutionEvent::Widget(command) => {
+                    broadcast_widget_command(ctx.as_deref(), &command)
+                }
+                ExecutionEvent::Animation(command) => {
+                    if let Some(ctx) = ctx.as_deref_mut() {
+                        apply_animation_command(&mut ctx.scene.graph, self_handle, &command);
+                    }
+                }
+                ExecutionEvent::Sound(command) => {
+                    if let Some(ctx) = ctx.as_deref_mut() {
+                        let graph = &mut ctx.scene.graph;
+                        apply_sound_command(graph, ctx.resource_manager, self_handle, &command);
+                    }
+                }
+                ExecutionEvent::LoadScene(path) => request_scene_load(ctx.as_deref_mut(), path),
+                ExecutionEvent::SetGlobal(name, value) => {
+                    set_global(ctx.as_deref_mut(), name, value)
+                }
+                ExecutionEvent::Tag(command) => {
                     if let Some(ctx) = ctx.as_deref_mut() {
-                        if let Some(screen_log) =
-                            ctx.plugins.of_type_mut::<BlueprintScreenLogPlugin>()
-                        {
-                            screen_log.push(text);
+                        apply_tag_command(&mut ctx.scene.graph, self_handle, &command);
+                    }
+                }
+                ExecutionEvent::Timer(command) => {
+                    if let Some(ctx) = ctx.as_deref_mut() {
+                        let manager = &mut ctx.scene.timers;
+                        apply_timer_command(&mut self.timers, manager, self_handle, &command);
+                    }
+                }
+            }
+        }
+
+        if let Some(ctx) = ctx {
+            self.dispatch_interface_messages(ctx, messages);
+        }
+    }
+
+    /// Delivers interface messages to the blueprints of their targets. Messages sent by the
+    /// receivers are delivered in the same pass, up to [`Self::MAX_INTERFACE_MESSAGES`].
+    fn dispatch_interface_messages(
+        &mut self,
+        ctx: &mut ScriptContext,
+        mut queue: VecDeque<(Handle<Node>, InterfaceCall)>,
+    ) {
+        let mut budget = Self::MAX_INTERFACE_MESSAGES;
+        while let Some((sender, call)) = queue.pop_front() {
+            if budget == 0 {
+                Log::warn(format!(
+                    "BlueprintScript: dropped interface messages after {} deliveries",
+                    Self::MAX_INTERFACE_MESSAGES
+                ));
+                break;
+            }
+            budget -= 1;
+
+            let target = resolve_actor(&ctx.scene.graph, sender, &call.target);
+
+            let events = if target == ctx.handle {
+                // This script is detached from its node while it runs, so it is not in the graph.
+                let host = SceneHost {
+                    graph: &ctx.scene.graph,
+                    handle: target,
+                    resource_manager: ctx.resource_manager,
+                    level: ctx.plugins.of_type_ref::<BlueprintLevelPlugin>(),
+                };
+                match self.interpreter.as_mut() {
+                    Some(interpreter) => {
+                        interpreter
+                            .call_interface_with_host(
+                                &host,
+                                &call.interface,
+                                &call.function,
+                                &call.args,
+                            )
+                            .events
+                    }
+                    None => continue,
+                }
+            } else {
+                let Some(script) = ctx
+                    .scene
+                    .graph
+                    .try_get_script_of_mut::<BlueprintScript>(target)
+                else {
+                    // Targets without a blueprint ignore interface messages.
+                    continue;
+                };
+                script.ensure_compiled(Some(ctx.resource_manager));
+                let Some(mut interpreter) = script.interpreter.take() else {
+                    continue;
+                };
+
+                let host = SceneHost {
+                    graph: &ctx.scene.graph,
+                    handle: target,
+                    resource_manager: ctx.resource_manager,
+                    level: ctx.plugins.of_type_ref::<BlueprintLevelPlugin>(),
+                };
+                let out = interpreter.call_interface_with_host(
+                    &host,
+                    &call.interface,
+                    &call.function,
+                    &call.args,
+                );
+
+                if let Some(script) = ctx
+                    .scene
+                    .graph
+                    .try_get_script_of_mut::<BlueprintScript>(target)
+                {
+                    script.interpreter = Some(interpreter);
+                }
+                out.events
+            };
+
+            for event in events {
+                match event {
+                    ExecutionEvent::EnterNode(_) => {}
+                    ExecutionEvent::Print(text) => log_print(Some(ctx), text),
+                    ExecutionEvent::InterfaceMessage(call) => queue.push_back((target, call)),
+                    ExecutionEvent::Assertion(result) => log_assertion(Some(ctx), target, result),
+                    ExecutionEvent::Widget(command) => {
+                        broadcast_widget_command(Some(ctx), &command)
+                    }
+                    ExecutionEvent::Animation(command) => {
+                        apply_animation_command(&mut ctx.scene.graph, target, &command);
+                    }
+                    ExecutionEvent::Sound(command) => {
+                        let graph = &mut ctx.scene.graph;
+                        apply_sound_command(graph, ctx.resource_manager, target, &command);
+                    }
+                    ExecutionEvent::LoadScene(path) => request_scene_load(Some(ctx), path),
+                    ExecutionEvent::SetGlobal(name, value) => set_global(Some(ctx), name, value),
+                    ExecutionEvent::Tag(command) => {
+                        apply_tag_command(&mut ctx.scene.graph, target, &command);
+                    }
+                    ExecutionEvent::Timer(command) => {
+                        let scene = &mut *ctx.scene;
+                        let timers = if target == ctx.handle {
+                            Some(&mut self.timers)
+                        } else {
+                            scene
+                                .graph
+                                .try_get_script_of_mut::<BlueprintScript>(target)
+                                .map(|script| &mut script.timers)
+                        };
+                        if let Some(timers) = timers {
+                            apply_timer_command(timers, &mut scene.timers, target, &command);
                         }
                     }
                 }
@@ -145,36 +642,236 @@ impl BlueprintScript {
         }
     }
 
-    fn run_construction(&mut self, ctx: Option<&mut ScriptContext>) {
-        self.ensure_compiled();
+    /// Profiling is enabled while a [`BlueprintProfilerPlugin`] is registered.
+    fn sync_profiling(&mut self, ctx: Option<&ScriptContext>) {
         let Some(interpreter) = self.interpreter.as_mut() else {
             return;
         };
+        let enabled = ctx.is_some_and(|ctx| {
+            ctx.plugins
+                .of_type_ref::<BlueprintProfilerPlugin>()
+                .is_some()
+        });
+        interpreter.set_profiling(enabled);
+    }
+
+    fn report_profile(&mut self, ctx: &mut ScriptContext) {
+        let Some(profile) = self
+            .interpreter
+            .as_mut()
+            .and_then(|interpreter| interpreter.take_profile())
+        else {
+            return;
+        };
+        let Some(profiler) = ctx.plugins.of_type_mut::<BlueprintProfilerPlugin>() else {
+            return;
+        };
+        let blueprint = self
+            .blueprint
+            .as_ref()
+            .and_then(|blueprint| ctx.resource_manager.resource_path(blueprint))
+            .map(|path| path.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_else(|| "<embedded>".to_string());
+        profiler.record(&blueprint, &profile);
+    }
 
-        let out = interpreter.run_construction_script();
-        self.flush_events(ctx, out.events);
+    fn run_construction(&mut self, mut ctx: Option<&mut ScriptContext>) {
+        self.ensure_compiled(ctx.as_deref().map(|ctx| ctx.resource_manager));
+        self.sync_profiling(ctx.as_deref());
+        let Some(interpreter) = self.interpreter.as_mut() else {
+            return;
+        };
+
+        let out = match ctx.as_deref() {
+            Some(ctx) => interpreter.run_construction_script_with_host(&SceneHost {
+                graph: &ctx.scene.graph,
+                handle: ctx.handle,
+                resource_manager: ctx.resource_manager,
+                level: ctx.plugins.of_type_ref::<BlueprintLevelPlugin>(),
+            }),
+            None => interpreter.run_construction_script(),
+        };
+        self.flush_events(ctx.as_deref_mut(), out.events);
+        if let Some(ctx) = ctx {
+            self.report_profile(ctx);
+        }
         *self.construction_ran = true;
     }
 
-    fn run_begin_play(&mut self, ctx: Option<&mut ScriptContext>) {
-        self.ensure_compiled();
+    fn run_begin_play(&mut self, mut ctx: Option<&mut ScriptContext>) {
+        self.ensure_compiled(ctx.as_deref().map(|ctx| ctx.resource_manager));
+        self.sync_profiling(ctx.as_deref());
         let Some(interpreter) = self.interpreter.as_mut() else {
             return;
         };
 
-        let out = interpreter.run_begin_play();
-        self.flush_events(ctx, out.events);
+        let out = match ctx.as_deref() {
+            Some(ctx) => interpreter.run_begin_play_with_host(&SceneHost {
+                graph: &ctx.scene.graph,
+                handle: ctx.handle,
+                resource_manager: ctx.resource_manager,
+                level: ctx.plugins.of_type_ref::<BlueprintLevelPlugin>(),
+            }),
+            None => interpreter.run_begin_play(),
+        };
+        self.flush_events(ctx.as_deref_mut(), out.events);
+        if let Some(ctx) = ctx {
+            self.report_profile(ctx);
+        }
         *self.begin_play_ran = true;
     }
 
     fn run_tick(&mut self, ctx: &mut ScriptContext) {
-        self.ensure_compiled();
+        self.ensure_compiled(Some(ctx.resource_manager));
+        self.sync_profiling(Some(ctx));
         let Some(interpreter) = self.interpreter.as_mut() else {
             return;
         };
 
-        let out = interpreter.tick(ctx.dt);
+        let host = SceneHost {
+            graph: &ctx.scene.graph,
+            handle: ctx.handle,
+            resource_manager: ctx.resource_manager,
+            level: ctx.plugins.of_type_ref::<BlueprintLevelPlugin>(),
+        };
+        let out = interpreter.tick_with_host(&host, ctx.dt);
         self.flush_events(Some(ctx), out.events);
+        self.report_profile(ctx);
+    }
+
+    fn ensure_behavior_runner(&mut self) {
+        if self.behavior_runner.is_some() {
+            return;
+        }
+        let Some(behavior) = self.behavior.clone_inner() else {
+            return;
+        };
+        let asset_guard = behavior.data_ref();
+        let Some(asset) = asset_guard.as_loaded_ref() else {
+            return;
+        };
+        match asset.graph() {
+            Ok(graph) => self.behavior_runner = Some(BehaviorRunner::new(graph)),
+            Err(err) => Log::err(format!("BlueprintScript: invalid behavior tree JSON: {err}")),
+        }
+    }
+
+    fn run_behavior(&mut self, ctx: &mut ScriptContext) {
+        self.ensure_behavior_runner();
+        let (Some(runner), Some(interpreter)) =
+            (self.behavior_runner.as_ref(), self.interpreter.as_mut())
+        else {
+            return;
+        };
+
+        let host = SceneHost {
+            graph: &ctx.scene.graph,
+            handle: ctx.handle,
+            resource_manager: ctx.resource_manager,
+            level: ctx.plugins.of_type_ref::<BlueprintLevelPlugin>(),
+        };
+        let events = runner.tick(interpreter, &host, ctx.dt);
+        self.flush_events(Some(ctx), events);
+    }
+
+    /// Runs the interface calls posted with [`Self::post_interface_call`] as calls to self.
+    fn run_inbox(&mut self, ctx: &mut ScriptContext) {
+        if self.inbox.is_empty() {
+            return;
+        }
+        let queue = self
+            .inbox
+            .drain(..)
+            .map(|call| {
+                let call = InterfaceCall {
+                    target: SELF_TARGET.to_string(),
+                    ..call
+                };
+                (ctx.handle, call)
+            })
+            .collect();
+        self.dispatch_interface_messages(ctx, queue);
+    }
+
+    /// Runs the custom events of the timers that fired since the last update.
+    fn run_custom_events(&mut self, ctx: &mut ScriptContext) {
+        for event in std::mem::take(&mut self.fired_events) {
+            let Some(interpreter) = self.interpreter.as_mut() else {
+                return;
+            };
+            let host = SceneHost {
+                graph: &ctx.scene.graph,
+                handle: ctx.handle,
+                resource_manager: ctx.resource_manager,
+                level: ctx.plugins.of_type_ref::<BlueprintLevelPlugin>(),
+            };
+            let out = interpreter.dispatch_custom_event(&host, &event);
+            self.flush_events(Some(ctx), out.events);
+        }
+    }
+
+    /// Runs `OnSoundFinished` nodes for the sounds that were playing at the last update and are
+    /// not playing anymore.
+    fn run_sound_finished(&mut self, ctx: &mut ScriptContext) {
+        let Some(compiled) = self.compiled.as_ref() else {
+            return;
+        };
+        let mut finished = Vec::new();
+        for sound in compiled.sound_finished_entries.keys() {
+            let actor = resolve_actor(&ctx.scene.graph, ctx.handle, sound);
+            if is_sound_playing(&ctx.scene.graph, actor) {
+                self.playing_sounds.insert(sound.clone());
+            } else if self.playing_sounds.remove(sound) {
+                finished.push(sound.clone());
+            }
+        }
+
+        for sound in finished {
+            let Some(interpreter) = self.interpreter.as_mut() else {
+                return;
+            };
+            let host = SceneHost {
+                graph: &ctx.scene.graph,
+                handle: ctx.handle,
+                resource_manager: ctx.resource_manager,
+                level: ctx.plugins.of_type_ref::<BlueprintLevelPlugin>(),
+            };
+            let out = interpreter.dispatch_sound_finished(&host, &sound);
+            self.flush_events(Some(ctx), out.events);
+        }
+    }
+
+    /// Runs `OnAnimationSignal` nodes for the signals raised by the animations of this actor since
+    /// the last update. Only the signals handled by the graph are taken, the others are left to
+    /// other consumers.
+    fn run_animation_signals(&mut self, ctx: &mut ScriptContext) {
+        let has_signal_events = self
+            .compiled
+            .as_ref()
+            .is_some_and(|compiled| !compiled.animation_signal_entries.is_empty());
+        if !has_signal_events {
+            return;
+        }
+
+        let Some(interpreter) = self.interpreter.as_ref() else {
+            return;
+        };
+        let signals = take_animation_signals(&mut ctx.scene.graph, ctx.handle, |signal| {
+            interpreter.handles_animation_signal(signal)
+        });
+        for (signal, source) in signals {
+            let Some(interpreter) = self.interpreter.as_mut() else {
+                return;
+            };
+            let host = SceneHost {
+                graph: &ctx.scene.graph,
+                handle: ctx.handle,
+                resource_manager: ctx.resource_manager,
+                level: ctx.plugins.of_type_ref::<BlueprintLevelPlugin>(),
+            };
+            let out = interpreter.dispatch_animation_signal(&host, &signal, &source);
+            self.flush_events(Some(ctx), out.events);
+        }
     }
 }
 
@@ -188,6 +885,10 @@ impl ScriptTrait for BlueprintScript {
     }
 
     fn on_start(&mut self, ctx: &mut ScriptContext) {
+        self.subscribe_to_resource_events(ctx.resource_manager);
+        // Subscriptions are not saved, timers of a loaded game must reach the script as well.
+        ctx.message_dispatcher.subscribe_to::<TimerFired>(ctx.handle);
+
         // Ensure Construction Script runs before BeginPlay.
         if !*self.construction_ran {
             self.run_construction(Some(ctx));
@@ -199,7 +900,33 @@ impl ScriptTrait for BlueprintScript {
         }
     }
 
+    fn on_deinit(&mut self, ctx: &mut ScriptDeinitContext) {
+        if let Some((_, handle)) = self.resource_events.take() {
+            ctx.resource_manager
+                .state()
+                .event_broadcaster
+                .remove(handle);
+        }
+    }
+
+    fn on_message(
+        &mut self,
+        message: &mut dyn ScriptMessagePayload,
+        ctx: &mut ScriptMessageContext,
+    ) {
+        // Custom events need a script context, so they run on the next update.
+        if let Some(fired) = message.downcast_ref::<TimerFired>() {
+            if let Some(event) = fired_event(&mut self.timers, &ctx.scene.timers, fired.timer) {
+                self.fired_events.push(event);
+            }
+        }
+    }
+
     fn on_update(&mut self, ctx: &mut ScriptContext) {
+        if self.blueprint_was_reloaded() {
+            self.hot_reload(ctx.resource_manager);
+        }
+
         // If the blueprint resource was still loading during on_start, try again on update.
         if !*self.construction_ran {
             self.run_construction(Some(ctx));
@@ -211,7 +938,12 @@ impl ScriptTrait for BlueprintScript {
 
         // Match typical gameplay order: no ticking before BeginPlay.
         if *self.begin_play_ran {
+            self.run_inbox(ctx);
+            self.run_custom_events(ctx);
+            self.run_animation_signals(ctx);
+            self.run_sound_finished(ctx);
             self.run_tick(ctx);
+            self.run_behavior(ctx);
         }
     }
 }
@@ -228,7 +960,15 @@ pub fn register(container: &ScriptConstructorContainer) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use fyrox_visual_scripting::{model::BuiltinNodeKind, model::GraphId, model::Link, model::Node};
+    use fyrox::{
+        asset::io::FsResourceIo,
+        scene::{base::BaseBuilder, pivot::PivotBuilder},
+    };
+    use fyrox_visual_scripting::{
+        model::BuiltinNodeKind, model::GraphId, model::Link, model::Node, model::NodeId,
+        BlueprintInterface,
+    };
+    use std::sync::Arc;
 
     #[test]
     fn script_compiles_from_json() {
@@ -254,8 +994,55 @@ mod tests {
             version: 1,
             graph_json: json,
             prefab_path: None,
+            ui_path: None,
         };
         let parsed: BlueprintGraph = serde_json::from_str(&asset.graph_json).unwrap();
         assert!(compile(&parsed).is_ok());
     }
+
+    #[test]
+    fn actor_referenced_by_name_implements_its_own_interfaces() {
+        let mut graph = BlueprintGraph::new(GraphId("door".to_string()));
+        graph.implement_interface(BlueprintInterface {
+            name: "Interactable".to_string(),
+            functions: Vec::new(),
+        });
+        let begin = graph.add_node(Node::new(BuiltinNodeKind::BeginPlay));
+        let branch = graph.add_node(Node::new(BuiltinNodeKind::Branch));
+        let mut check = Node::new(BuiltinNodeKind::DoesImplementInterface);
+        check.set_property_string("interface", "Interactable".to_string());
+        check.set_property_string("target", "Door".to_string());
+        let check = graph.add_node(check);
+        let mut print = Node::new(BuiltinNodeKind::Print);
+        print.set_property_string("text", "Interactable".to_string());
+        let print = graph.add_node(print);
+
+        let pin = |node: NodeId, name: &str| graph.nodes[&node].pin_named(name).unwrap();
+        let links = [
+            Link::exec(pin(begin, "then"), pin(branch, "exec")),
+            Link::data(pin(check, "result"), pin(branch, "condition")),
+            Link::exec(pin(branch, "true"), pin(print, "exec")),
+        ];
+        for link in links {
+            graph.add_link(link);
+        }
+
+        // The running script is detached from its node, the interpreter answers for it.
+        let mut scene_graph = Graph::new();
+        let door = PivotBuilder::new(BaseBuilder::new().with_name("Door")).build(&mut scene_graph);
+        let resource_manager =
+            ResourceManager::new(Arc::new(FsResourceIo), Arc::new(Default::default()));
+        let host = SceneHost {
+            graph: &scene_graph,
+            handle: door,
+            resource_manager: &resource_manager,
+            level: None,
+        };
+        let mut interpreter = Interpreter::new(compile(&graph).unwrap());
+        let out = interpreter.run_begin_play_with_host(&host);
+        assert!(out
+            .events
+            .iter()
+            .any(|e| matches!(e, ExecutionEvent::Print(text) if text == "Interactable")));
+    }
 }
@@ -0,0 +1,186 @@
+//! Blueprint profiling. While [`BlueprintProfilerPlugin`] is registered, every blueprint script
+//! collects execution statistics and reports them to the plugin, which aggregates them per
+//! blueprint asset and periodically writes them to a CSV file. The blueprint editor reads that file
+//! to show the results. The editor asks the game to register the plugin in play mode using the
+//! [`BlueprintProfilerPlugin::ENABLE_VAR`] environment variable, see
+//! [`BlueprintProfilerPlugin::from_env`].
+
+use fyrox::{
+    core::{log::Log, reflect::prelude::*, visitor::prelude::*},
+    plugin::{Plugin, PluginContext, PluginRegistrationContext},
+};
+use fyrox_visual_scripting::{profiles_to_csv, BlueprintProfile};
+
+use std::{collections::BTreeMap, path::PathBuf};
+
+use crate::{register, register_resources};
+
+#[derive(Debug, Visit, Reflect)]
+#[reflect(non_cloneable)]
+pub struct BlueprintProfilerPlugin {
+    /// Where the statistics are written. `None` keeps them in memory only.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    output: Option<PathBuf>,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    profiles: BTreeMap<String, BlueprintProfile>,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    dirty: bool,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    since_flush: f32,
+}
+
+impl Default for BlueprintProfilerPlugin {
+    fn default() -> Self {
+        Self::new(Some(PathBuf::from(Self::DEFAULT_OUTPUT)))
+    }
+}
+
+impl BlueprintProfilerPlugin {
+    /// File the statistics are written to by default, relative to the working directory.
+    pub const DEFAULT_OUTPUT: &'static str = "blueprint_profile.csv";
+
+    /// How often (in seconds) the statistics are written to the output file.
+    pub const FLUSH_INTERVAL: f32 = 1.0;
+
+    /// Environment variable that enables profiling of a game, see [`Self::from_env`].
+    pub const ENABLE_VAR: &'static str = "FYROX_PROFILE_BLUEPRINTS";
+
+    /// Creates a profiler with the default output, if profiling is enabled by the
+    /// [`Self::ENABLE_VAR`] environment variable (any value except `0`). Games should register the
+    /// returned plugin, so the editor can profile them in play mode.
+    pub fn from_env() -> Option<Self> {
+        std::env::var_os(Self::ENABLE_VAR)
+            .filter(|value| value != "0")
+            .map(|_| Self::default())
+    }
+
+    pub fn new(output: Option<PathBuf>) -> Self {
+        Self {
+            output,
+            profiles: Default::default(),
+            dirty: false,
+            since_flush: 0.0,
+        }
+    }
+
+    /// Adds statistics of an instance of `blueprint` (the path of the asset).
+    pub fn record(&mut self, blueprint: &str, profile: &BlueprintProfile) {
+        if profile.is_empty() {
+            return;
+        }
+        match self.profiles.get_mut(blueprint) {
+            Some(total) => total.merge(profile),
+            None => {
+                self.profiles.insert(blueprint.to_string(), profile.clone());
+            }
+        }
+        self.dirty = true;
+    }
+
+    pub fn profiles(&self) -> &BTreeMap<String, BlueprintProfile> {
+        &self.profiles
+    }
+
+    pub fn clear(&mut self) {
+        self.profiles.clear();
+        self.dirty = true;
+    }
+
+    pub fn to_csv(&self) -> String {
+        profiles_to_csv(
+            self.profiles
+                .iter()
+                .map(|(blueprint, profile)| (blueprint.as_str(), profile)),
+        )
+    }
+
+    /// Writes the statistics to the output file, if there is one and anything changed.
+    pub fn flush(&mut self) {
+        self.since_flush = 0.0;
+        let Some(output) = self.output.as_ref() else {
+            return;
+        };
+        if !self.dirty {
+            return;
+        }
+        match std::fs::write(output, self.to_csv()) {
+            Ok(()) => self.dirty = false,
+            Err(err) => Log::err(format!(
+                "BlueprintProfiler: unable to write {}: {err}",
+                output.display()
+            )),
+        }
+    }
+}
+
+impl Plugin for BlueprintProfilerPlugin {
+    fn register(&self, context: PluginRegistrationContext) {
+        register_resources(context.resource_manager);
+        register(&context.serialization_context.script_constructors);
+    }
+
+    fn on_deinit(&mut self, _context: PluginContext) {
+        self.flush();
+    }
+
+    fn update(&mut self, context: &mut PluginContext) {
+        self.since_flush += context.dt;
+        if self.since_flush >= Self::FLUSH_INTERVAL {
+            self.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fyrox_visual_scripting::{NodeId, ProfileSample};
+    use std::time::Duration;
+
+    #[test]
+    fn instances_of_a_blueprint_are_aggregated() {
+        let mut instance = BlueprintProfile::default();
+        instance.nodes.insert(
+            NodeId(1),
+            ProfileSample {
+                count: 1,
+                total: Duration::from_micros(5),
+            },
+        );
+
+        let mut profiler = BlueprintProfilerPlugin::new(None);
+        profiler.record("door.blueprint", &instance);
+        profiler.record("door.blueprint", &instance);
+        profiler.record("door.blueprint", &BlueprintProfile::default());
+
+        let total = &profiler.profiles()["door.blueprint"];
+        assert_eq!(total.nodes[&NodeId(1)].count, 2);
+        assert_eq!(total.nodes[&NodeId(1)].total, Duration::from_micros(10));
+        assert_eq!(profiler.profiles().len(), 1);
+    }
+
+    #[test]
+    fn profiler_is_enabled_by_environment() {
+        let var = BlueprintProfilerPlugin::ENABLE_VAR;
+        std::env::remove_var(var);
+        assert!(BlueprintProfilerPlugin::from_env().is_none());
+        std::env::set_var(var, "0");
+        assert!(BlueprintProfilerPlugin::from_env().is_none());
+        std::env::set_var(var, "1");
+        let profiler = BlueprintProfilerPlugin::from_env().unwrap();
+        assert_eq!(
+            profiler.output.as_deref(),
+            Some(std::path::Path::new(
+                BlueprintProfilerPlugin::DEFAULT_OUTPUT
+            ))
+        );
+        std::env::remove_var(var);
+    }
+}
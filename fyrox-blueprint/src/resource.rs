@@ -16,6 +16,12 @@ use fyrox::{
     },
 };
 use fyrox_visual_scripting::{model::GraphId, BlueprintGraph};
+
+use crate::behavior::{BehaviorAsset, BehaviorLoader};
+use crate::interface::{BlueprintInterfaceAsset, BlueprintInterfaceLoader};
+use crate::types::{
+    BlueprintEnumAsset, BlueprintEnumLoader, BlueprintStructAsset, BlueprintStructLoader,
+};
 use std::{
     error::Error,
     path::{Path, PathBuf},
@@ -40,6 +46,13 @@ pub struct BlueprintAsset {
     /// Stored as a path relative to the project's working directory (asset root).
     #[visit(optional)]
     pub prefab_path: Option<String>,
+
+    /// Optional user interface scene (`.ui`) scripted by this blueprint instead of an actor. Such
+    /// blueprints are run by [`crate::BlueprintUiPlugin`].
+    ///
+    /// Stored as a path relative to the project's working directory (asset root).
+    #[visit(optional)]
+    pub ui_path: Option<String>,
 }
 
 impl Default for BlueprintAsset {
@@ -55,6 +68,7 @@ impl Default for BlueprintAsset {
             version: 1,
             graph_json,
             prefab_path: None,
+            ui_path: None,
         }
     }
 }
@@ -122,7 +136,8 @@ impl ResourceLoader for BlueprintLoader {
     }
 }
 
-/// Registers `.blueprint` resource data + loader in the given resource manager.
+/// Registers `.blueprint`, `.blueprint_interface`, `.blueprint_struct`, `.blueprint_enum` and
+/// `.behavior` resource data + loaders in the given resource manager.
 ///
 /// Note: if the manager already loaded/scanned its registry, call
 /// `resource_manager.state().update_or_load_registry()` afterwards to re-scan.
@@ -133,9 +148,19 @@ pub fn register_resources(resource_manager: &ResourceManager) {
         let state = resource_manager.state();
 
         state.constructors_container.add::<BlueprintAsset>();
+        state
+            .constructors_container
+            .add::<BlueprintInterfaceAsset>();
+        state.constructors_container.add::<BlueprintStructAsset>();
+        state.constructors_container.add::<BlueprintEnumAsset>();
+        state.constructors_container.add::<BehaviorAsset>();
 
         let mut loaders = state.loaders.safe_lock();
         loaders.set(BlueprintLoader);
+        loaders.set(BlueprintInterfaceLoader);
+        loaders.set(BlueprintStructLoader);
+        loaders.set(BlueprintEnumLoader);
+        loaders.set(BehaviorLoader);
     });
 }
 
@@ -153,6 +178,7 @@ mod tests {
 
         let mut asset = BlueprintAsset::default();
         asset.prefab_path = Some("data/prefabs/test_actor.rgs".to_string());
+        asset.ui_path = Some("data/ui/main_menu.ui".to_string());
         asset.save(&path).unwrap();
 
         let io = FsResourceIo;
@@ -160,5 +186,6 @@ mod tests {
         assert_eq!(loaded.version, asset.version);
         assert_eq!(loaded.graph_json, asset.graph_json);
         assert_eq!(loaded.prefab_path, asset.prefab_path);
+        assert_eq!(loaded.ui_path, asset.ui_path);
     }
 }
@@ -0,0 +1,172 @@
+//! Scene side of the sound nodes: applies sound commands to sound nodes and spawns one-shot sounds.
+
+use fyrox::{
+    asset::manager::ResourceManager,
+    core::{algebra::Vector3, pool::Handle},
+    graph::SceneGraph,
+    scene::{
+        base::BaseBuilder,
+        graph::Graph,
+        node::Node,
+        sound::{Sound, SoundBuffer, SoundBuilder, Status},
+        transform::TransformBuilder,
+    },
+};
+use fyrox_visual_scripting::{SoundCommand, SoundProperty, Value};
+
+use crate::{find_components, resolve_actor};
+
+/// Gain of the first sound node of the actor.
+pub fn sound_gain(graph: &Graph, actor: Handle<Node>) -> Option<f32> {
+    let sound = *find_components::<Sound>(graph, actor).first()?;
+    graph.try_get_of_type::<Sound>(sound).map(Sound::gain)
+}
+
+/// Whether a sound node of the actor is playing.
+pub fn is_sound_playing(graph: &Graph, actor: Handle<Node>) -> bool {
+    find_components::<Sound>(graph, actor)
+        .into_iter()
+        .any(|sound| {
+            graph
+                .try_get_of_type::<Sound>(sound)
+                .is_some_and(|sound| sound.status() == Status::Playing)
+        })
+}
+
+/// Applies a sound command of the blueprint running on `self_handle` to every sound node of the
+/// target actor. One-shot sounds are added to the scene root and removed by the engine when they
+/// end. Returns `false` if nothing was changed.
+pub fn apply_sound_command(
+    graph: &mut Graph,
+    resource_manager: &ResourceManager,
+    self_handle: Handle<Node>,
+    command: &SoundCommand,
+) -> bool {
+    let target = match command {
+        SoundCommand::Play { sound }
+        | SoundCommand::Stop { sound }
+        | SoundCommand::SetProperty { sound, .. }
+        | SoundCommand::SetBus { sound, .. } => sound,
+        SoundCommand::SpawnOneShot {
+            buffer,
+            position,
+            gain,
+        } => {
+            let buffer = resource_manager.request::<SoundBuffer>(buffer);
+            let [x, y, z] = *position;
+            SoundBuilder::new(
+                BaseBuilder::new().with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(x, y, z))
+                        .build(),
+                ),
+            )
+            .with_buffer(Some(buffer))
+            .with_gain(*gain)
+            .with_play_once(true)
+            .with_status(Status::Playing)
+            .build(graph);
+            return true;
+        }
+    };
+    if let SoundCommand::SetProperty {
+        property, value, ..
+    } = command
+    {
+        if value.data_type() != property.value_type() {
+            return false;
+        }
+    }
+
+    let actor = resolve_actor(graph, self_handle, target);
+    let sounds = find_components::<Sound>(graph, actor);
+    for sound in sounds.iter() {
+        let Some(sound) = graph.try_get_mut_of_type::<Sound>(*sound) else {
+            continue;
+        };
+        match command {
+            SoundCommand::Play { .. } => sound.play(),
+            SoundCommand::Stop { .. } => sound.stop(),
+            SoundCommand::SetProperty {
+                property, value, ..
+            } => match (property, value) {
+                (SoundProperty::Gain, Value::F32(gain)) => {
+                    sound.set_gain(*gain);
+                }
+                (SoundProperty::Pitch, Value::F32(pitch)) => {
+                    sound.set_pitch(f64::from(*pitch));
+                }
+                (SoundProperty::Looping, Value::Bool(looping)) => {
+                    sound.set_looping(*looping);
+                }
+                _ => {}
+            },
+            SoundCommand::SetBus { bus, .. } => sound.set_audio_bus(bus.clone()),
+            SoundCommand::SpawnOneShot { .. } => {}
+        }
+    }
+    !sounds.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fyrox::asset::io::FsResourceIo;
+    use std::sync::Arc;
+
+    #[test]
+    fn sound_commands_change_named_sounds() {
+        let mut graph = Graph::new();
+        let music = SoundBuilder::new(BaseBuilder::new().with_name("Music")).build(&mut graph);
+        let resource_manager =
+            ResourceManager::new(Arc::new(FsResourceIo), Arc::new(Default::default()));
+        let root = graph.get_root();
+
+        let play = SoundCommand::Play {
+            sound: "Music".to_string(),
+        };
+        assert!(apply_sound_command(
+            &mut graph,
+            &resource_manager,
+            root,
+            &play
+        ));
+        assert!(is_sound_playing(&graph, music));
+
+        let gain = SoundCommand::SetProperty {
+            sound: "Music".to_string(),
+            property: SoundProperty::Gain,
+            value: Value::F32(0.25),
+        };
+        assert!(apply_sound_command(
+            &mut graph,
+            &resource_manager,
+            root,
+            &gain
+        ));
+        assert_eq!(sound_gain(&graph, music), Some(0.25));
+
+        let wrong_type = SoundCommand::SetProperty {
+            sound: "Music".to_string(),
+            property: SoundProperty::Looping,
+            value: Value::F32(1.0),
+        };
+        assert!(!apply_sound_command(
+            &mut graph,
+            &resource_manager,
+            root,
+            &wrong_type
+        ));
+
+        let stop = SoundCommand::Stop {
+            sound: "Music".to_string(),
+        };
+        assert!(apply_sound_command(
+            &mut graph,
+            &resource_manager,
+            root,
+            &stop
+        ));
+        assert!(!is_sound_playing(&graph, music));
+    }
+}
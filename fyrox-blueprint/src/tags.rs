@@ -0,0 +1,79 @@
+//! Scene side of the tag nodes: queries and changes the tags of actors through the tag index of
+//! the graph.
+
+use fyrox::{core::pool::Handle, graph::BaseSceneGraph, scene::graph::Graph, scene::node::Node};
+use fyrox_visual_scripting::TagCommand;
+
+use crate::resolve_actor;
+
+/// Names of the actors of the graph that have the tag.
+pub fn actors_with_tag(graph: &Graph, tag: &str) -> Vec<String> {
+    graph
+        .find_by_tag(tag)
+        .filter_map(|handle| graph.try_get_node(handle))
+        .map(|node| node.name_owned())
+        .collect()
+}
+
+/// Whether the actor has the tag.
+pub fn has_tag(graph: &Graph, actor: Handle<Node>, tag: &str) -> bool {
+    graph
+        .try_get_node(actor)
+        .is_some_and(|node| node.has_tag(tag))
+}
+
+/// Adds or removes a tag. Returns `false` if the target actor does not exist in the graph.
+pub fn apply_tag_command(
+    graph: &mut Graph,
+    self_handle: Handle<Node>,
+    command: &TagCommand,
+) -> bool {
+    let (TagCommand::Add { target, .. } | TagCommand::Remove { target, .. }) = command;
+    let actor = resolve_actor(graph, self_handle, target);
+    if !graph.is_valid_handle(actor) {
+        return false;
+    }
+    match command {
+        TagCommand::Add { tag, .. } => {
+            graph.add_tag(actor, tag.as_str());
+        }
+        TagCommand::Remove { tag, .. } => {
+            graph.remove_tag(actor, tag);
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fyrox::scene::{base::BaseBuilder, pivot::PivotBuilder};
+
+    #[test]
+    fn tag_commands_update_the_index() {
+        let mut graph = Graph::new();
+        let orc = PivotBuilder::new(BaseBuilder::new().with_name("Orc")).build(&mut graph);
+        PivotBuilder::new(BaseBuilder::new().with_name("Orc")).build(&mut graph);
+
+        let add = TagCommand::Add {
+            target: "self".to_string(),
+            tag: "enemy".to_string(),
+        };
+        assert!(apply_tag_command(&mut graph, orc, &add));
+        assert_eq!(actors_with_tag(&graph, "enemy"), vec!["Orc".to_string()]);
+        assert!(has_tag(&graph, orc, "enemy"));
+
+        let remove = TagCommand::Remove {
+            target: "self".to_string(),
+            tag: "enemy".to_string(),
+        };
+        assert!(apply_tag_command(&mut graph, orc, &remove));
+        assert!(actors_with_tag(&graph, "enemy").is_empty());
+
+        let missing = TagCommand::Add {
+            target: "Goblin".to_string(),
+            tag: "enemy".to_string(),
+        };
+        assert!(!apply_tag_command(&mut graph, orc, &missing));
+    }
+}
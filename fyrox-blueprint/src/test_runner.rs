@@ -0,0 +1,325 @@
+//! Headless blueprint tests. The runner ticks a scene for a fixed number of frames and collects the
+//! results of `Assert`/`ExpectEqual` nodes into a JUnit-style report.
+
+use fyrox::{
+    core::{log::Log, pool::Handle, reflect::prelude::*, visitor::prelude::*},
+    plugin::{Plugin, PluginContext, PluginRegistrationContext},
+    scene::{base::BaseBuilder, pivot::PivotBuilder, Scene},
+};
+use fyrox_visual_scripting::AssertionResult;
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use crate::{register, register_resources, BlueprintAsset, BlueprintScript};
+
+/// Outcome of a single assertion node over the whole run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlueprintTestCase {
+    /// Name of the actor the blueprint runs on.
+    pub suite: String,
+    pub name: String,
+    /// How many times the node ran.
+    pub runs: usize,
+    /// First failure of the node, if any.
+    pub failure: Option<String>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct BlueprintTestReport {
+    /// Test cases keyed by actor and node, so assertions on Tick produce a single case.
+    cases: BTreeMap<(String, u32), BlueprintTestCase>,
+    /// Problems that prevented tests from running (e.g. a scene that failed to load).
+    errors: Vec<String>,
+}
+
+impl BlueprintTestReport {
+    pub fn record(&mut self, suite: &str, result: AssertionResult) {
+        let case = self
+            .cases
+            .entry((suite.to_string(), result.node.0))
+            .or_insert_with(|| BlueprintTestCase {
+                suite: suite.to_string(),
+                name: result.name.clone(),
+                runs: 0,
+                failure: None,
+            });
+        case.runs += 1;
+        if case.failure.is_none() {
+            case.failure = result.failure;
+        }
+    }
+
+    pub fn record_error(&mut self, error: String) {
+        self.errors.push(error);
+    }
+
+    pub fn cases(&self) -> impl Iterator<Item = &BlueprintTestCase> {
+        self.cases.values()
+    }
+
+    pub fn failures(&self) -> usize {
+        self.cases().filter(|case| case.failure.is_some()).count() + self.errors.len()
+    }
+
+    /// A run without any assertion is not a success: most likely the blueprints did not run.
+    pub fn is_success(&self) -> bool {
+        !self.cases.is_empty() && self.failures() == 0
+    }
+
+    pub fn to_junit_xml(&self) -> String {
+        let mut suites: BTreeMap<&str, Vec<&BlueprintTestCase>> = BTreeMap::new();
+        for case in self.cases() {
+            suites.entry(case.suite.as_str()).or_default().push(case);
+        }
+
+        let mut xml = String::new();
+        let _ = writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        let _ = writeln!(
+            xml,
+            r#"<testsuites name="blueprints" tests="{}" failures="{}">"#,
+            self.cases.len() + self.errors.len(),
+            self.failures()
+        );
+        for (suite, cases) in suites {
+            let failures = cases.iter().filter(|case| case.failure.is_some()).count();
+            let _ = writeln!(
+                xml,
+                r#"  <testsuite name="{}" tests="{}" failures="{}">"#,
+                escape_xml(suite),
+                cases.len(),
+                failures
+            );
+            for case in cases {
+                write_test_case(&mut xml, suite, &case.name, case.failure.as_deref());
+            }
+            let _ = writeln!(xml, "  </testsuite>");
+        }
+        if !self.errors.is_empty() {
+            let _ = writeln!(
+                xml,
+                r#"  <testsuite name="runner" tests="{0}" failures="{0}">"#,
+                self.errors.len()
+            );
+            for (i, error) in self.errors.iter().enumerate() {
+                write_test_case(&mut xml, "runner", &format!("error {i}"), Some(error));
+            }
+            let _ = writeln!(xml, "  </testsuite>");
+        }
+        let _ = writeln!(xml, "</testsuites>");
+        xml
+    }
+}
+
+fn write_test_case(xml: &mut String, suite: &str, name: &str, failure: Option<&str>) {
+    let _ = write!(
+        xml,
+        r#"    <testcase classname="{}" name="{}""#,
+        escape_xml(suite),
+        escape_xml(name)
+    );
+    match failure {
+        Some(failure) => {
+            let _ = writeln!(xml, ">");
+            let _ = writeln!(xml, r#"      <failure message="{}"/>"#, escape_xml(failure));
+            let _ = writeln!(xml, "    </testcase>");
+        }
+        None => {
+            let _ = writeln!(xml, "/>");
+        }
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Runs blueprints for a fixed number of frames and then stops the executor. Meant for headless
+/// executors (`Executor::new(None)`); the report is shared with the caller through
+/// [`Self::report`] and can be read once `Executor::run` returns.
+#[derive(Debug, Default, Visit, Reflect)]
+#[reflect(non_cloneable)]
+pub struct BlueprintTestRunnerPlugin {
+    #[reflect(hidden)]
+    #[visit(skip)]
+    scene_path: Option<PathBuf>,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    blueprints: Vec<PathBuf>,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    frames: usize,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    frame: usize,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    scene: Handle<Scene>,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    report: Arc<Mutex<BlueprintTestReport>>,
+}
+
+impl BlueprintTestRunnerPlugin {
+    /// Loads `scene_path` (or starts from an empty scene), spawns an actor for every blueprint in
+    /// `blueprints` and ticks for `frames` frames.
+    pub fn new(scene_path: Option<PathBuf>, blueprints: Vec<PathBuf>, frames: usize) -> Self {
+        Self {
+            scene_path,
+            blueprints,
+            frames,
+            ..Default::default()
+        }
+    }
+
+    pub fn report(&self) -> Arc<Mutex<BlueprintTestReport>> {
+        self.report.clone()
+    }
+
+    pub fn record(&mut self, suite: &str, result: AssertionResult) {
+        if let Ok(mut report) = self.report.lock() {
+            report.record(suite, result);
+        }
+    }
+
+    fn start(&mut self, scene: Handle<Scene>, context: &mut PluginContext) {
+        self.scene = scene;
+        self.frame = 0;
+
+        let graph = &mut context.scenes[scene].graph;
+        for path in self.blueprints.iter() {
+            let name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.display().to_string());
+            let script = BlueprintScript {
+                blueprint: Some(context.resource_manager.request::<BlueprintAsset>(path)).into(),
+                ..Default::default()
+            };
+            PivotBuilder::new(BaseBuilder::new().with_name(name).with_script(script)).build(graph);
+        }
+    }
+
+    fn fail_and_exit(&mut self, error: String, context: &mut PluginContext) {
+        Log::err(format!("BlueprintTestRunner: {error}"));
+        if let Ok(mut report) = self.report.lock() {
+            report.record_error(error);
+        }
+        context.loop_controller.exit();
+    }
+}
+
+impl Plugin for BlueprintTestRunnerPlugin {
+    fn register(&self, context: PluginRegistrationContext) {
+        register_resources(context.resource_manager);
+        register(&context.serialization_context.script_constructors);
+    }
+
+    fn init(&mut self, _scene_path: Option<&str>, mut context: PluginContext) {
+        for path in self.blueprints.iter() {
+            if !path.exists() {
+                let error = format!("blueprint {} does not exist", path.display());
+                self.fail_and_exit(error, &mut context);
+                return;
+            }
+        }
+
+        match self.scene_path.as_ref() {
+            Some(path) => context.async_scene_loader.request(path),
+            None => {
+                let scene = context.scenes.add(Scene::new());
+                self.start(scene, &mut context);
+            }
+        }
+    }
+
+    fn update(&mut self, context: &mut PluginContext) {
+        if self.scene.is_none() {
+            return;
+        }
+
+        self.frame += 1;
+        if self.frame >= self.frames {
+            context.loop_controller.exit();
+        }
+    }
+
+    fn on_scene_loaded(
+        &mut self,
+        path: &Path,
+        scene: Handle<Scene>,
+        _data: &[u8],
+        context: &mut PluginContext,
+    ) {
+        if self.scene_path.as_deref() == Some(path) {
+            self.start(scene, context);
+        }
+    }
+
+    fn on_scene_loading_failed(
+        &mut self,
+        path: &Path,
+        error: &VisitError,
+        context: &mut PluginContext,
+    ) {
+        let error = format!("unable to load scene {}: {error:?}", path.display());
+        self.fail_and_exit(error, context);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fyrox_visual_scripting::NodeId;
+
+    fn result(node: u32, failure: Option<&str>) -> AssertionResult {
+        AssertionResult {
+            node: NodeId(node),
+            name: format!("check <{node}>"),
+            failure: failure.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn report_merges_repeated_assertions() {
+        let mut report = BlueprintTestReport::default();
+        report.record("Player", result(1, None));
+        report.record("Player", result(1, Some("expected 1, got 2")));
+        report.record("Player", result(1, None));
+        report.record("Door", result(2, None));
+
+        assert_eq!(report.cases().count(), 2);
+        assert_eq!(report.failures(), 1);
+        assert!(!report.is_success());
+
+        let xml = report.to_junit_xml();
+        assert!(xml.contains(r#"<testsuites name="blueprints" tests="2" failures="1">"#));
+        assert!(xml.contains(r#"<testcase classname="Door" name="check &lt;2&gt;"/>"#));
+        assert!(xml.contains(r#"<failure message="expected 1, got 2"/>"#));
+    }
+
+    #[test]
+    fn empty_report_is_not_a_success() {
+        assert!(!BlueprintTestReport::default().is_success());
+    }
+}
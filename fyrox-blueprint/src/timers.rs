@@ -0,0 +1,107 @@
+//! Scene side of the timer nodes: starts and clears engine timers for the custom events of
+//! blueprints.
+
+use fyrox::{
+    core::pool::Handle,
+    scene::{
+        node::Node,
+        timer::{Timer, TimerManager},
+    },
+};
+use fyrox_visual_scripting::TimerCommand;
+use std::collections::HashMap;
+
+/// Timers of a blueprint, keyed by the name of the custom event they fire.
+pub type EventTimers = HashMap<String, Handle<Timer>>;
+
+/// Starts or clears the timer of a custom event. New timers send their messages to `owner`; pass
+/// [`Handle::NONE`] for timers checked with [`TimerManager::fired`].
+pub fn apply_timer_command(
+    timers: &mut EventTimers,
+    manager: &mut TimerManager,
+    owner: Handle<Node>,
+    command: &TimerCommand,
+) {
+    match command {
+        TimerCommand::Set {
+            event,
+            duration,
+            looping,
+        } => {
+            if let Some(previous) = timers.remove(event) {
+                manager.clear(previous);
+            }
+            let timer = manager.set_timer(owner, *duration, *looping);
+            timers.insert(event.clone(), timer);
+        }
+        TimerCommand::Clear { event } => {
+            if let Some(timer) = timers.remove(event) {
+                manager.clear(timer);
+            }
+        }
+    }
+}
+
+/// Name of the custom event of a timer that fired, if the timer belongs to the blueprint. Timers
+/// that will not fire again are forgotten.
+pub fn fired_event(
+    timers: &mut EventTimers,
+    manager: &TimerManager,
+    timer: Handle<Timer>,
+) -> Option<String> {
+    let event = timers
+        .iter()
+        .find_map(|(event, handle)| (*handle == timer).then(|| event.clone()))?;
+    if manager.try_get(timer).is_none() {
+        timers.remove(&event);
+    }
+    Some(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fyrox::scene::graph::Graph;
+
+    #[test]
+    fn timers_are_keyed_by_event() {
+        let graph = Graph::new();
+        let mut manager = TimerManager::default();
+        let mut timers = EventTimers::new();
+        let set = |event: &str, looping| TimerCommand::Set {
+            event: event.to_string(),
+            duration: 1.0,
+            looping,
+        };
+
+        apply_timer_command(&mut timers, &mut manager, Handle::NONE, &set("Spawn", true));
+        apply_timer_command(
+            &mut timers,
+            &mut manager,
+            Handle::NONE,
+            &set("Explode", false),
+        );
+        // Setting the timer of an event again replaces it.
+        let replaced = timers["Spawn"];
+        apply_timer_command(&mut timers, &mut manager, Handle::NONE, &set("Spawn", true));
+        assert!(manager.try_get(replaced).is_none());
+
+        manager.update(1.0, &graph);
+        let mut fired = manager
+            .fired()
+            .iter()
+            .filter_map(|fired| fired_event(&mut timers, &manager, fired.timer))
+            .collect::<Vec<_>>();
+        fired.sort();
+        assert_eq!(fired, ["Explode", "Spawn"]);
+        assert!(!timers.contains_key("Explode"));
+
+        let clear = TimerCommand::Clear {
+            event: "Spawn".to_string(),
+        };
+        apply_timer_command(&mut timers, &mut manager, Handle::NONE, &clear);
+        assert!(timers.is_empty());
+        manager.update(1.0, &graph);
+        assert!(manager.fired().is_empty());
+    }
+}
@@ -0,0 +1,426 @@
+use fyrox::{
+    asset::{
+        io::ResourceIo,
+        loader::{BoxedLoaderFuture, LoaderPayload, ResourceLoader},
+        manager::ResourceManager,
+        state::LoadError,
+        untyped::UntypedResource,
+        Resource, ResourceData,
+    },
+    core::{
+        futures::executor::block_on, log::Log, reflect::prelude::*, type_traits::prelude::*,
+        uuid::Uuid, visitor::prelude::*, SafeLock, TypeUuidProvider,
+    },
+};
+use fyrox_visual_scripting::{
+    DataType, ElementType, EnumDef, ParamDef, StructDef, UserTypeId, UserTypes,
+};
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+/// Type of a struct field. The first four variants match [`crate::InterfaceParamType`], so fields
+/// saved before struct fields had their own type still load.
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+    TypeUuidProvider,
+)]
+#[type_uuid(id = "9a4c2e71-3d8b-4f05-b6e2-7c1f0d5a8e39")]
+pub enum StructFieldType {
+    Bool,
+    I32,
+    F32,
+    #[default]
+    String,
+    /// Array of [`StructFieldDef::element`].
+    Array,
+    /// Set of [`StructFieldDef::element`].
+    Set,
+    /// Map from [`StructFieldDef::key`] to [`StructFieldDef::element`].
+    Map,
+    /// Struct named by [`StructFieldDef::type_name`].
+    Struct,
+    /// Enum named by [`StructFieldDef::type_name`].
+    Enum,
+}
+
+/// Type of the elements, keys or values of a container field.
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+    TypeUuidProvider,
+)]
+#[type_uuid(id = "c5e08b3f-1a72-4d96-8f4b-2e9d7a6c0b13")]
+pub enum StructFieldElement {
+    Bool,
+    I32,
+    F32,
+    #[default]
+    String,
+}
+
+impl From<StructFieldElement> for ElementType {
+    fn from(value: StructFieldElement) -> Self {
+        match value {
+            StructFieldElement::Bool => ElementType::Bool,
+            StructFieldElement::I32 => ElementType::I32,
+            StructFieldElement::F32 => ElementType::F32,
+            StructFieldElement::String => ElementType::String,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Visit, Reflect, TypeUuidProvider)]
+#[type_uuid(id = "4f7d1b96-e230-4c8a-9d5e-83a6f2c1b047")]
+pub struct StructFieldDef {
+    #[visit(optional)]
+    pub name: String,
+    #[visit(optional)]
+    pub data_type: StructFieldType,
+    /// Element type of arrays and sets, or value type of maps.
+    #[visit(optional)]
+    pub element: StructFieldElement,
+    /// Key type of maps.
+    #[visit(optional)]
+    pub key: StructFieldElement,
+    /// Name of the struct or enum asset type of the field.
+    #[visit(optional)]
+    pub type_name: String,
+}
+
+impl StructFieldDef {
+    pub fn to_data_type(&self) -> DataType {
+        let element = ElementType::from(self.element);
+        match self.data_type {
+            StructFieldType::Bool => DataType::Bool,
+            StructFieldType::I32 => DataType::I32,
+            StructFieldType::F32 => DataType::F32,
+            StructFieldType::String => DataType::String,
+            StructFieldType::Array => DataType::Array(element),
+            StructFieldType::Set => DataType::Set(element),
+            StructFieldType::Map => DataType::Map(self.key.into(), element),
+            StructFieldType::Struct => DataType::Struct(UserTypeId::of(&self.type_name)),
+            StructFieldType::Enum => DataType::Enum(UserTypeId::of(&self.type_name)),
+        }
+    }
+}
+
+/// `.blueprint_struct` asset: a named set of fields that blueprints can use as a variable or pin
+/// type.
+#[derive(TypeUuidProvider, Debug, Default, Clone, Visit, Reflect)]
+#[type_uuid(id = "2b7e9c14-58d3-4f0a-8e62-c1d4a9f3b750")]
+pub struct BlueprintStructAsset {
+    /// Asset format version. Useful for future migrations.
+    #[visit(optional)]
+    pub version: u32,
+
+    /// Type name. Blueprints refer to the type by this name.
+    #[visit(optional)]
+    pub name: String,
+
+    #[visit(optional)]
+    pub fields: Vec<StructFieldDef>,
+}
+
+impl BlueprintStructAsset {
+    pub async fn from_file(path: &Path, io: &dyn ResourceIo) -> Result<Self, VisitError> {
+        let bytes = io.load_file(path).await?;
+        let mut visitor = Visitor::load_from_memory(&bytes)?;
+        let mut asset = BlueprintStructAsset::default();
+        asset.visit("BlueprintStruct", &mut visitor)?;
+        Ok(asset)
+    }
+
+    /// Engine-agnostic form of the type, as stored by the graphs that use it.
+    pub fn to_struct_def(&self) -> StructDef {
+        StructDef {
+            name: self.name.clone(),
+            fields: self
+                .fields
+                .iter()
+                .map(|f| ParamDef {
+                    name: f.name.clone(),
+                    data_type: f.to_data_type(),
+                })
+                .collect(),
+        }
+    }
+}
+
+pub type BlueprintStructResource = Resource<BlueprintStructAsset>;
+
+impl ResourceData for BlueprintStructAsset {
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut visitor = Visitor::new();
+        self.visit("BlueprintStruct", &mut visitor)?;
+        visitor.save_ascii_to_file(path)?;
+        Ok(())
+    }
+
+    fn can_be_saved(&self) -> bool {
+        true
+    }
+
+    fn try_clone_box(&self) -> Option<Box<dyn ResourceData>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+#[derive(Default)]
+pub struct BlueprintStructLoader;
+
+impl BlueprintStructLoader {
+    pub const EXT: &'static str = "blueprint_struct";
+}
+
+impl ResourceLoader for BlueprintStructLoader {
+    fn extensions(&self) -> &[&str] {
+        &[Self::EXT]
+    }
+
+    fn is_native_extension(&self, ext: &str) -> bool {
+        fyrox::core::cmp_strings_case_insensitive(ext, Self::EXT)
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        <BlueprintStructAsset as TypeUuidProvider>::type_uuid()
+    }
+
+    fn load(&self, path: PathBuf, io: Arc<dyn ResourceIo>) -> BoxedLoaderFuture {
+        Box::pin(async move {
+            let asset = BlueprintStructAsset::from_file(&path, io.as_ref())
+                .await
+                .map_err(LoadError::new)?;
+            Ok(LoaderPayload::new(asset))
+        })
+    }
+}
+
+/// `.blueprint_enum` asset: a named list of variants that blueprints can use as a variable or pin
+/// type and branch on with `Switch on Enum`.
+#[derive(TypeUuidProvider, Debug, Default, Clone, Visit, Reflect)]
+#[type_uuid(id = "6f1d3a82-c947-4b5e-a0d8-93e2b7c41f6a")]
+pub struct BlueprintEnumAsset {
+    /// Asset format version. Useful for future migrations.
+    #[visit(optional)]
+    pub version: u32,
+
+    /// Type name. Blueprints refer to the type by this name.
+    #[visit(optional)]
+    pub name: String,
+
+    #[visit(optional)]
+    pub variants: Vec<String>,
+}
+
+impl BlueprintEnumAsset {
+    pub async fn from_file(path: &Path, io: &dyn ResourceIo) -> Result<Self, VisitError> {
+        let bytes = io.load_file(path).await?;
+        let mut visitor = Visitor::load_from_memory(&bytes)?;
+        let mut asset = BlueprintEnumAsset::default();
+        asset.visit("BlueprintEnum", &mut visitor)?;
+        Ok(asset)
+    }
+
+    /// Engine-agnostic form of the type, as stored by the graphs that use it.
+    pub fn to_enum_def(&self) -> EnumDef {
+        EnumDef {
+            name: self.name.clone(),
+            variants: self.variants.clone(),
+        }
+    }
+}
+
+pub type BlueprintEnumResource = Resource<BlueprintEnumAsset>;
+
+impl ResourceData for BlueprintEnumAsset {
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut visitor = Visitor::new();
+        self.visit("BlueprintEnum", &mut visitor)?;
+        visitor.save_ascii_to_file(path)?;
+        Ok(())
+    }
+
+    fn can_be_saved(&self) -> bool {
+        true
+    }
+
+    fn try_clone_box(&self) -> Option<Box<dyn ResourceData>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+#[derive(Default)]
+pub struct BlueprintEnumLoader;
+
+impl BlueprintEnumLoader {
+    pub const EXT: &'static str = "blueprint_enum";
+}
+
+impl ResourceLoader for BlueprintEnumLoader {
+    fn extensions(&self) -> &[&str] {
+        &[Self::EXT]
+    }
+
+    fn is_native_extension(&self, ext: &str) -> bool {
+        fyrox::core::cmp_strings_case_insensitive(ext, Self::EXT)
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        <BlueprintEnumAsset as TypeUuidProvider>::type_uuid()
+    }
+
+    fn load(&self, path: PathBuf, io: Arc<dyn ResourceIo>) -> BoxedLoaderFuture {
+        Box::pin(async move {
+            let asset = BlueprintEnumAsset::from_file(&path, io.as_ref())
+                .await
+                .map_err(LoadError::new)?;
+            Ok(LoaderPayload::new(asset))
+        })
+    }
+}
+
+/// Adds the definition of a loaded struct or enum asset to `types`. Returns the name of the type,
+/// or `None` for other resources.
+pub fn define_from_resource(types: &mut UserTypes, resource: &UntypedResource) -> Option<String> {
+    if let Some(resource) = resource.try_cast::<BlueprintStructAsset>() {
+        let def = resource.data_ref().as_loaded_ref()?.to_struct_def();
+        let name = def.name.clone();
+        types.define_struct(def);
+        Some(name)
+    } else if let Some(resource) = resource.try_cast::<BlueprintEnumAsset>() {
+        let def = resource.data_ref().as_loaded_ref()?.to_enum_def();
+        let name = def.name.clone();
+        types.define_enum(def);
+        Some(name)
+    } else {
+        None
+    }
+}
+
+/// Struct and enum definitions of the `.blueprint_struct` and `.blueprint_enum` assets in the
+/// resource registry, sorted by name. Assets that are not loaded yet are loaded.
+pub fn collect_types(resource_manager: &ResourceManager) -> UserTypes {
+    let paths = {
+        let registry = resource_manager.state().resource_registry.clone();
+        let registry = registry.safe_lock();
+        registry
+            .inner()
+            .values()
+            .filter(|path| {
+                path.extension().is_some_and(|ext| {
+                    ext == BlueprintStructLoader::EXT || ext == BlueprintEnumLoader::EXT
+                })
+            })
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+
+    let mut types = UserTypes::default();
+    for path in paths {
+        match block_on(resource_manager.request_untyped(&path)) {
+            Ok(resource) => {
+                define_from_resource(&mut types, &resource);
+            }
+            Err(err) => Log::err(format!(
+                "Blueprint: failed to load type {}: {err:?}",
+                path.display()
+            )),
+        }
+    }
+    types.structs.sort_by(|a, b| a.name.cmp(&b.name));
+    types.enums.sort_by(|a, b| a.name.cmp(&b.name));
+    types
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fyrox::asset::io::FsResourceIo;
+    use fyrox::core::futures::executor::block_on;
+
+    #[test]
+    fn type_assets_save_load_roundtrip() {
+        let dir = std::env::temp_dir().join("fyrox_blueprint_tests");
+        let _ = std::fs::create_dir_all(&dir);
+
+        let path = dir.join(format!("roundtrip.{}", BlueprintStructLoader::EXT));
+        let mut asset = BlueprintStructAsset {
+            version: 1,
+            name: "Damage".to_string(),
+            fields: vec![
+                StructFieldDef {
+                    name: "amount".to_string(),
+                    data_type: StructFieldType::F32,
+                    ..Default::default()
+                },
+                StructFieldDef {
+                    name: "resistances".to_string(),
+                    data_type: StructFieldType::Map,
+                    key: StructFieldElement::String,
+                    element: StructFieldElement::F32,
+                    ..Default::default()
+                },
+                StructFieldDef {
+                    name: "kind".to_string(),
+                    data_type: StructFieldType::Enum,
+                    type_name: "DamageKind".to_string(),
+                    ..Default::default()
+                },
+            ],
+        };
+        asset.save(&path).unwrap();
+        let loaded = block_on(BlueprintStructAsset::from_file(&path, &FsResourceIo)).unwrap();
+        assert_eq!(loaded.fields, asset.fields);
+        let def = loaded.to_struct_def();
+        assert_eq!(def.field("amount").unwrap().data_type, DataType::F32);
+        assert_eq!(
+            def.field("resistances").unwrap().data_type,
+            DataType::Map(ElementType::String, ElementType::F32)
+        );
+        assert_eq!(
+            def.field("kind").unwrap().data_type,
+            DataType::Enum(UserTypeId::of("DamageKind"))
+        );
+
+        let path = dir.join(format!("roundtrip.{}", BlueprintEnumLoader::EXT));
+        let mut asset = BlueprintEnumAsset {
+            version: 1,
+            name: "DamageKind".to_string(),
+            variants: vec!["Fire".to_string(), "Ice".to_string()],
+        };
+        asset.save(&path).unwrap();
+        let loaded = block_on(BlueprintEnumAsset::from_file(&path, &FsResourceIo)).unwrap();
+        assert_eq!(loaded.to_enum_def().variants, asset.variants);
+    }
+}
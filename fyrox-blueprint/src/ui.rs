@@ -0,0 +1,378 @@
+//! Blueprints that script a user interface (`.ui` scene) instead of an actor. Named widgets report
+//! clicks, text commits, value changes etc. to `WidgetEvent` nodes, and `SetWidgetProperty` nodes
+//! change widgets found by name.
+
+use fyrox::{
+    core::{
+        color::Color, futures::executor::block_on, log::Log, pool::Handle, reflect::prelude::*,
+        visitor::prelude::*,
+    },
+    graph::{BaseSceneGraph, SceneGraph},
+    gui::{
+        brush::Brush,
+        button::ButtonMessage,
+        check_box::CheckBoxMessage,
+        dropdown_list::DropdownListMessage,
+        list_view::ListViewMessage,
+        message::{MessageDirection, UiMessage},
+        numeric::NumericUpDownMessage,
+        scroll_bar::ScrollBarMessage,
+        text::TextMessage,
+        text_box::TextBox,
+        widget::WidgetMessage,
+        UserInterface,
+    },
+    plugin::{Plugin, PluginContext, PluginRegistrationContext},
+};
+use fyrox_visual_scripting::{
+    compile, widgets::parse_color, BlueprintGraph, ExecutionEvent, Interpreter, NullHost, Value,
+    WidgetCommand, WidgetEvent, WidgetProperty,
+};
+use std::path::PathBuf;
+
+use crate::{log_print, register, register_resources, BlueprintAsset, BlueprintResource};
+
+/// Translates a message emitted by a named widget into a widget event and its value. Returns `None`
+/// for messages sent to widgets and for unnamed widgets.
+pub fn widget_event(
+    ui: &UserInterface,
+    message: &UiMessage,
+) -> Option<(String, WidgetEvent, Option<Value>)> {
+    if message.direction() != MessageDirection::FromWidget {
+        return None;
+    }
+    let node = ui.try_get_node(message.destination())?;
+    let name = node.name();
+    if name.is_empty() {
+        return None;
+    }
+
+    let (event, value) = if let Some(ButtonMessage::Click) = message.data() {
+        (WidgetEvent::Clicked, None)
+    } else if let Some(TextMessage::Text(text)) = message.data() {
+        // Plain text widgets report their text as well; only text boxes commit it.
+        node.query_component::<TextBox>()?;
+        (
+            WidgetEvent::TextCommitted,
+            Some(Value::String(text.clone())),
+        )
+    } else if let Some(ScrollBarMessage::Value(value)) = message.data() {
+        (WidgetEvent::ValueChanged, Some(Value::F32(*value)))
+    } else if let Some(NumericUpDownMessage::<f32>::Value(value)) = message.data() {
+        (WidgetEvent::ValueChanged, Some(Value::F32(*value)))
+    } else if let Some(CheckBoxMessage::Check(checked)) = message.data() {
+        (
+            WidgetEvent::Toggled,
+            Some(Value::Bool(checked.unwrap_or(false))),
+        )
+    } else if let Some(DropdownListMessage::Selection(selection)) = message.data() {
+        (
+            WidgetEvent::SelectionChanged,
+            Some(selection_value(*selection)),
+        )
+    } else if let Some(ListViewMessage::Selection(selection)) = message.data() {
+        let first = selection.first().copied();
+        (WidgetEvent::SelectionChanged, Some(selection_value(first)))
+    } else {
+        return None;
+    };
+    Some((name.to_string(), event, value))
+}
+
+fn selection_value(selection: Option<usize>) -> Value {
+    Value::I32(selection.map_or(-1, |index| i32::try_from(index).unwrap_or(i32::MAX)))
+}
+
+/// Applies a widget command to the widget with the commanded name. Returns `false` if the user
+/// interface has no such widget or the value does not fit the property.
+pub fn apply_widget_command(ui: &UserInterface, command: &WidgetCommand) -> bool {
+    let widget = ui.find_handle_by_name_from_root(&command.widget);
+    if widget.is_none() {
+        return false;
+    }
+    match (command.property, &command.value) {
+        (WidgetProperty::Visible, Value::Bool(visible)) => {
+            ui.send(widget, WidgetMessage::Visibility(*visible))
+        }
+        (WidgetProperty::Enabled, Value::Bool(enabled)) => {
+            ui.send(widget, WidgetMessage::Enabled(*enabled))
+        }
+        (WidgetProperty::Text, Value::String(text)) => {
+            ui.send(widget, TextMessage::Text(text.clone()))
+        }
+        (WidgetProperty::Brush, Value::String(color)) => {
+            let Some([r, g, b, a]) = parse_color(color) else {
+                return false;
+            };
+            let brush = Brush::Solid(Color::from_rgba(r, g, b, a));
+            ui.send(widget, WidgetMessage::Background(brush.into()))
+        }
+        _ => return false,
+    }
+    true
+}
+
+/// A running blueprint and the user interface it scripts.
+struct UiBlueprint {
+    path: PathBuf,
+    ui: Handle<UserInterface>,
+    interpreter: Interpreter,
+}
+
+impl std::fmt::Debug for UiBlueprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UiBlueprint")
+            .field("path", &self.path)
+            .field("ui", &self.ui)
+            .finish()
+    }
+}
+
+/// Runs blueprints whose asset names a user interface scene (see [`BlueprintAsset::ui_path`]). The
+/// plugin loads the scene, adds it to the user interfaces of the engine, runs Construction Script
+/// and BeginPlay of the blueprint and then Tick every frame.
+#[derive(Debug, Default, Visit, Reflect)]
+#[reflect(non_cloneable)]
+pub struct BlueprintUiPlugin {
+    #[reflect(hidden)]
+    #[visit(skip)]
+    blueprints: Vec<PathBuf>,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    running: Vec<UiBlueprint>,
+}
+
+impl BlueprintUiPlugin {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a `.blueprint` asset to run when the plugin is initialized.
+    pub fn with_blueprint(mut self, path: impl Into<PathBuf>) -> Self {
+        self.blueprints.push(path.into());
+        self
+    }
+
+    fn start(&mut self, context: &mut PluginContext, path: PathBuf) {
+        let blueprint = match block_on(context.resource_manager.request::<BlueprintAsset>(&path)) {
+            Ok(blueprint) => blueprint,
+            Err(err) => {
+                Log::err(format!(
+                    "BlueprintUiPlugin: failed to load {}: {err:?}",
+                    path.display()
+                ));
+                return;
+            }
+        };
+        let Some((ui_path, compiled)) = compile_ui_blueprint(&blueprint, &path) else {
+            return;
+        };
+
+        let ui = match block_on(UserInterface::load_from_file(
+            &ui_path,
+            context.resource_manager.clone(),
+        )) {
+            Ok(ui) => ui,
+            Err(err) => {
+                Log::err(format!(
+                    "BlueprintUiPlugin: failed to load user interface {ui_path}: {err:?}"
+                ));
+                return;
+            }
+        };
+        let ui = context.user_interfaces.add(ui);
+
+        let mut running = UiBlueprint {
+            path,
+            ui,
+            interpreter: Interpreter::new(compiled),
+        };
+        let construction = running.interpreter.run_construction_script();
+        running.apply(context, construction.events);
+        let begin_play = running.interpreter.run_begin_play();
+        running.apply(context, begin_play.events);
+        self.running.push(running);
+    }
+}
+
+fn compile_ui_blueprint(
+    blueprint: &BlueprintResource,
+    path: &std::path::Path,
+) -> Option<(String, fyrox_visual_scripting::CompiledGraph)> {
+    let guard = blueprint.data_ref();
+    let asset = guard.as_loaded_ref()?;
+    let Some(ui_path) = asset.ui_path.clone() else {
+        Log::err(format!(
+            "BlueprintUiPlugin: {} does not script a user interface",
+            path.display()
+        ));
+        return None;
+    };
+    let compiled = serde_json::from_str::<BlueprintGraph>(&asset.graph_json)
+        .map_err(|err| err.to_string())
+        .and_then(|graph| compile(&graph).map_err(|err| err.to_string()));
+    match compiled {
+        Ok(compiled) => Some((ui_path, compiled)),
+        Err(err) => {
+            Log::err(format!(
+                "BlueprintUiPlugin: {} does not compile: {err}",
+                path.display()
+            ));
+            None
+        }
+    }
+}
+
+impl UiBlueprint {
+    fn apply(&mut self, context: &mut PluginContext, events: Vec<ExecutionEvent>) {
+        for event in events {
+            match event {
+                ExecutionEvent::EnterNode(_) => {}
+                ExecutionEvent::Print(text) => log_print(None, text),
+                ExecutionEvent::Widget(command) => {
+                    if let Some(ui) = context.user_interfaces.try_get(self.ui) {
+                        if !apply_widget_command(ui, &command) {
+                            Log::warn(format!(
+                                "BlueprintUiPlugin: {}: no widget {} for {}",
+                                self.path.display(),
+                                command.widget,
+                                command.property.name()
+                            ));
+                        }
+                    }
+                }
+                ExecutionEvent::LoadScene(path) => context.async_scene_loader.request(path),
+                ExecutionEvent::Assertion(result) => {
+                    if let Some(failure) = result.failure {
+                        Log::err(format!(
+                            "[Blueprint] {}: {} failed: {failure}",
+                            self.path.display(),
+                            result.name
+                        ));
+                    }
+                }
+                // There are no actors to deliver animation, sound, tag and timer commands and
+                // interface messages to, and no level plugin to change globals in.
+                ExecutionEvent::Animation(command) => Log::warn(format!(
+                    "BlueprintUiPlugin: {}: dropped animation command {command:?}",
+                    self.path.display()
+                )),
+                ExecutionEvent::Sound(command) => Log::warn(format!(
+                    "BlueprintUiPlugin: {}: dropped sound command {command:?}",
+                    self.path.display()
+                )),
+                ExecutionEvent::Tag(command) => Log::warn(format!(
+                    "BlueprintUiPlugin: {}: dropped tag command {command:?}",
+                    self.path.display()
+                )),
+                ExecutionEvent::Timer(command) => Log::warn(format!(
+                    "BlueprintUiPlugin: {}: dropped timer command {command:?}",
+                    self.path.display()
+                )),
+                ExecutionEvent::SetGlobal(name, _) => Log::warn(format!(
+                    "BlueprintUiPlugin: {}: dropped change of global {name}",
+                    self.path.display()
+                )),
+                ExecutionEvent::InterfaceMessage(call) => Log::warn(format!(
+                    "BlueprintUiPlugin: {}: dropped {}::{} sent to {}",
+                    self.path.display(),
+                    call.interface,
+                    call.function,
+                    call.target
+                )),
+            }
+        }
+    }
+}
+
+impl Plugin for BlueprintUiPlugin {
+    fn register(&self, context: PluginRegistrationContext) {
+        register_resources(context.resource_manager);
+        register(&context.serialization_context.script_constructors);
+    }
+
+    fn init(&mut self, _scene_path: Option<&str>, mut context: PluginContext) {
+        for path in std::mem::take(&mut self.blueprints) {
+            self.start(&mut context, path);
+        }
+    }
+
+    fn update(&mut self, context: &mut PluginContext) {
+        for running in self.running.iter_mut() {
+            let out = running.interpreter.tick(context.dt);
+            running.apply(context, out.events);
+        }
+    }
+
+    fn on_ui_message(
+        &mut self,
+        context: &mut PluginContext,
+        message: &UiMessage,
+        ui_handle: Handle<UserInterface>,
+    ) {
+        let Some(running) = self.running.iter_mut().find(|r| r.ui == ui_handle) else {
+            return;
+        };
+        let Some(ui) = context.user_interfaces.try_get(ui_handle) else {
+            return;
+        };
+        let Some((widget, event, value)) = widget_event(ui, message) else {
+            return;
+        };
+        if running.interpreter.handles_widget_event(&widget, event) {
+            let out = running
+                .interpreter
+                .dispatch_widget_event(&NullHost, &widget, event, value);
+            running.apply(context, out.events);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fyrox::{
+        core::algebra::Vector2,
+        gui::{
+            button::ButtonBuilder,
+            text::{Text, TextBuilder},
+            widget::WidgetBuilder,
+        },
+    };
+
+    #[test]
+    fn widget_messages_map_to_events_and_commands() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let button =
+            ButtonBuilder::new(WidgetBuilder::new().with_name("Play")).build(&mut ui.build_ctx());
+        let label =
+            TextBuilder::new(WidgetBuilder::new().with_name("Title")).build(&mut ui.build_ctx());
+
+        let click = UiMessage::from_widget(button, ButtonMessage::Click);
+        let (widget, event, value) = widget_event(&ui, &click).unwrap();
+        assert_eq!(
+            (widget.as_str(), event, value),
+            ("Play", WidgetEvent::Clicked, None)
+        );
+
+        // Text widgets are not text boxes.
+        let text = UiMessage::from_widget(label, TextMessage::Text("x".to_string()));
+        assert!(widget_event(&ui, &text).is_none());
+
+        let command = WidgetCommand {
+            widget: "Title".to_string(),
+            property: WidgetProperty::Text,
+            value: Value::String("Paused".to_string()),
+        };
+        assert!(apply_widget_command(&ui, &command));
+        while ui.poll_message().is_some() {}
+        assert_eq!(ui.node(label).cast::<Text>().unwrap().text(), "Paused");
+
+        let missing = WidgetCommand {
+            widget: "Missing".to_string(),
+            ..command
+        };
+        assert!(!apply_widget_command(&ui, &missing));
+    }
+}
@@ -143,9 +143,13 @@ pub fn create_field_visits<'a>(
                     #prefix #ident.visit(#name, &mut region).ok();
                 }
             } else {
+                // The error is suppressed if the visitor recovers from errors, see
+                // `VisitorFlags::RECOVER_ERRORS`.
                 quote! {
                     if let Err(err) = #prefix #ident.visit(#name, &mut region) {
-                        return Err(err);
+                        if let Err(err) = region.recover_error(#name, err) {
+                            return Err(err);
+                        }
                     }
                 }
             }
@@ -198,3 +198,40 @@ pub async fn is_file<P: AsRef<Path>>(path: P) -> bool {
         exists(path).await
     }
 }
+
+/// Read-only memory map of a whole file, see [`MappedFile::open`].
+#[cfg(not(target_arch = "wasm32"))]
+pub struct MappedFile(memmap2::Mmap);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl MappedFile {
+    /// Maps the file into memory.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated by this or any other process while the map
+    /// exists. Modifications change the bytes behind shared references and reads from a shrunk
+    /// file crash the process.
+    pub unsafe fn open<P: AsRef<Path>>(path: P) -> Result<Self, FileError> {
+        let file = std::fs::File::open(path)?;
+        // SAFETY: The map is read-only, keeping the file unchanged is guaranteed by the caller.
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        Ok(Self(map))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl std::ops::Deref for MappedFile {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl std::fmt::Debug for MappedFile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MappedFile({} bytes)", self.0.len())
+    }
+}
@@ -0,0 +1,431 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Bridges between the engine log and `log`/`tracing` crates in both directions. They're
+//! available with `log` and `tracing` features respectively.
+//!
+//! - [`LogCrateBridge`] and [`TracingBridge`] pass the messages of the other crates (including
+//!   third-party dependencies) to the engine log, so they appear in the log file, sinks, the
+//!   editor's log panel, etc.
+//! - [`LogCrateSink`] and [`TracingSink`] pass the messages of the engine log to the other crates,
+//!   so they could be handled by the loggers and subscribers that are already used by a game.
+//!
+//! Using a bridge and a sink of the same crate at the same time is safe: messages that came from
+//! a bridge are not passed back.
+
+use crate::log::{Log, LogMessage, LogRecord, LogSink, MessageKind};
+#[cfg(feature = "tracing")]
+use crate::{instant::Instant, parking_lot::Mutex};
+#[cfg(feature = "tracing")]
+use fxhash::FxHashMap;
+#[cfg(feature = "tracing")]
+use std::{
+    fmt::Debug,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// Passes the messages of `log` crate to the engine log. Debug and trace messages are written as
+/// information. Install it with [`LogCrateBridge::install`].
+#[cfg(feature = "log")]
+#[derive(Default)]
+pub struct LogCrateBridge;
+
+#[cfg(feature = "log")]
+impl LogCrateBridge {
+    /// Sets the bridge as the global logger of `log` crate.
+    pub fn install() -> Result<(), ::log::SetLoggerError> {
+        ::log::set_boxed_logger(Box::new(Self))?;
+        ::log::set_max_level(::log::LevelFilter::Trace);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "log")]
+fn kind_from_log_level(level: ::log::Level) -> MessageKind {
+    match level {
+        ::log::Level::Error => MessageKind::Error,
+        ::log::Level::Warn => MessageKind::Warning,
+        ::log::Level::Info | ::log::Level::Debug | ::log::Level::Trace => MessageKind::Information,
+    }
+}
+
+#[cfg(feature = "log")]
+impl ::log::Log for LogCrateBridge {
+    fn enabled(&self, metadata: &::log::Metadata) -> bool {
+        !Log::is_writing_on_current_thread()
+            && Log::is_enabled(kind_from_log_level(metadata.level()), metadata.target())
+    }
+
+    fn log(&self, record: &::log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        struct Fields(Vec<(String, String)>);
+
+        impl<'kvs> ::log::kv::VisitSource<'kvs> for Fields {
+            fn visit_pair(
+                &mut self,
+                key: ::log::kv::Key<'kvs>,
+                value: ::log::kv::Value<'kvs>,
+            ) -> Result<(), ::log::kv::Error> {
+                self.0.push((key.to_string(), value.to_string()));
+                Ok(())
+            }
+        }
+
+        let mut fields = Fields(Vec::new());
+        let _ = record.key_values().visit(&mut fields);
+        let mut log_record = LogRecord::new(
+            kind_from_log_level(record.level()),
+            record.args().to_string(),
+        )
+        .with_target(record.target());
+        for (key, value) in fields.0 {
+            log_record = log_record.with_field(key, value);
+        }
+        log_record.write();
+    }
+
+    fn flush(&self) {
+        if !Log::is_writing_on_current_thread() {
+            Log::flush()
+        }
+    }
+}
+
+/// Passes the messages of the engine log to the global logger of `log` crate. Fields and spans
+/// are appended to the message text.
+#[cfg(feature = "log")]
+#[derive(Default)]
+pub struct LogCrateSink;
+
+#[cfg(feature = "log")]
+impl LogSink for LogCrateSink {
+    fn write(&mut self, message: &LogMessage) {
+        let level = match message.kind {
+            MessageKind::Information => ::log::Level::Info,
+            MessageKind::Warning => ::log::Level::Warn,
+            MessageKind::Error => ::log::Level::Error,
+        };
+        let target = if message.target.is_empty() {
+            "fyrox"
+        } else {
+            message.target.as_str()
+        };
+        let mut content = message.content.trim_end().to_string();
+        for span in message.spans.iter().rev() {
+            content.insert_str(0, &format!("{span}: "));
+        }
+        for (key, value) in message.fields.iter() {
+            content.push_str(&format!(" {key}={value}"));
+        }
+        ::log::logger().log(
+            &::log::Record::builder()
+                .args(format_args!("{content}"))
+                .level(level)
+                .target(target)
+                .build(),
+        );
+    }
+
+    fn flush(&mut self) {
+        ::log::logger().flush()
+    }
+}
+
+#[cfg(feature = "tracing")]
+fn kind_from_tracing_level(level: &tracing::Level) -> MessageKind {
+    if *level == tracing::Level::ERROR {
+        MessageKind::Error
+    } else if *level == tracing::Level::WARN {
+        MessageKind::Warning
+    } else {
+        MessageKind::Information
+    }
+}
+
+#[cfg(feature = "tracing")]
+struct TracingSpan {
+    name: &'static str,
+    target: String,
+    fields: Vec<(String, String)>,
+    entered: Option<Instant>,
+    references: usize,
+}
+
+/// A `tracing` subscriber, that passes events to the engine log. Fields of the events are written
+/// as fields of the messages. Entered spans are added to the span list of the messages (along with
+/// the engine's [`crate::log::LogSpan`]s) and their durations are written when they're exited.
+/// Install it with [`TracingBridge::install`].
+#[cfg(feature = "tracing")]
+#[derive(Default)]
+pub struct TracingBridge {
+    next_id: AtomicU64,
+    spans: Mutex<FxHashMap<u64, TracingSpan>>,
+}
+
+#[cfg(feature = "tracing")]
+impl TracingBridge {
+    /// Sets the bridge as the global default subscriber of `tracing` crate.
+    pub fn install() -> Result<(), tracing::subscriber::SetGlobalDefaultError> {
+        tracing::subscriber::set_global_default(Self::default())
+    }
+}
+
+#[cfg(feature = "tracing")]
+#[derive(Default)]
+struct TracingFields {
+    message: String,
+    fields: Vec<(String, String)>,
+}
+
+#[cfg(feature = "tracing")]
+impl tracing::field::Visit for TracingFields {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            self.fields
+                .push((field.name().to_string(), value.to_string()));
+        }
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn Debug) {
+        if field.name() == "message" {
+            self.message = format!("{value:?}");
+        } else {
+            self.fields
+                .push((field.name().to_string(), format!("{value:?}")));
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl tracing::Subscriber for TracingBridge {
+    fn enabled(&self, metadata: &tracing::Metadata<'_>) -> bool {
+        !Log::is_writing_on_current_thread()
+            && Log::is_enabled(kind_from_tracing_level(metadata.level()), metadata.target())
+    }
+
+    fn new_span(&self, span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let mut fields = TracingFields::default();
+        span.record(&mut fields);
+        self.spans.lock().insert(
+            id,
+            TracingSpan {
+                name: span.metadata().name(),
+                target: span.metadata().target().to_string(),
+                fields: fields.fields,
+                entered: None,
+                references: 1,
+            },
+        );
+        tracing::span::Id::from_u64(id)
+    }
+
+    fn record(&self, span: &tracing::span::Id, values: &tracing::span::Record<'_>) {
+        if let Some(span) = self.spans.lock().get_mut(&span.into_u64()) {
+            let mut fields = TracingFields::default();
+            values.record(&mut fields);
+            span.fields.extend(fields.fields);
+        }
+    }
+
+    fn record_follows_from(&self, _span: &tracing::span::Id, _follows: &tracing::span::Id) {}
+
+    fn event(&self, event: &tracing::Event<'_>) {
+        let mut fields = TracingFields::default();
+        event.record(&mut fields);
+        let mut record = LogRecord::new(
+            kind_from_tracing_level(event.metadata().level()),
+            fields.message,
+        )
+        .with_target(event.metadata().target());
+        for (key, value) in fields.fields {
+            record = record.with_field(key, value);
+        }
+        record.write();
+    }
+
+    fn enter(&self, span: &tracing::span::Id) {
+        if let Some(span) = self.spans.lock().get_mut(&span.into_u64()) {
+            span.entered = Some(Instant::now());
+            super::SPANS.with_borrow_mut(|spans| spans.push(span.name.to_string()));
+        }
+    }
+
+    fn exit(&self, span: &tracing::span::Id) {
+        let mut spans = self.spans.lock();
+        if let Some(span) = spans.get_mut(&span.into_u64()) {
+            super::SPANS.with_borrow_mut(|spans| {
+                if let Some(position) = spans.iter().rposition(|name| name.as_str() == span.name) {
+                    spans.remove(position);
+                }
+            });
+            if let Some(entered) = span.entered.take() {
+                let elapsed = Instant::now() - entered;
+                let mut record = LogRecord::new(
+                    MessageKind::Information,
+                    format!("{} finished in {elapsed:?}", span.name),
+                )
+                .with_target(span.target.clone())
+                .with_field("elapsed_ms", elapsed.as_secs_f64() * 1000.0);
+                for (key, value) in span.fields.iter() {
+                    record = record.with_field(key.clone(), value);
+                }
+                // Do not hold the lock while writing, the log could pass the message back to
+                // tracing.
+                drop(spans);
+                record.write();
+            }
+        }
+    }
+
+    fn clone_span(&self, span: &tracing::span::Id) -> tracing::span::Id {
+        if let Some(span) = self.spans.lock().get_mut(&span.into_u64()) {
+            span.references += 1;
+        }
+        span.clone()
+    }
+
+    fn try_close(&self, span: tracing::span::Id) -> bool {
+        let mut spans = self.spans.lock();
+        let id = span.into_u64();
+        if let Some(span) = spans.get_mut(&id) {
+            span.references -= 1;
+            if span.references == 0 {
+                spans.remove(&id);
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// Passes the messages of the engine log to `tracing` crate as events with `fyrox` target. The
+/// original target, spans and fields are passed as `log_target`, `log_spans` and `log_fields`
+/// fields respectively, because `tracing` requires them to be known at compile time.
+#[cfg(feature = "tracing")]
+#[derive(Default)]
+pub struct TracingSink;
+
+#[cfg(feature = "tracing")]
+impl LogSink for TracingSink {
+    fn write(&mut self, message: &LogMessage) {
+        let content = message.content.trim_end();
+        let target = message.target.as_str();
+        let spans = message.spans.join(" > ");
+        let fields = message
+            .fields
+            .iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        match message.kind {
+            MessageKind::Information => tracing::info!(
+                target: "fyrox",
+                log_target = target,
+                log_spans = spans,
+                log_fields = fields,
+                "{content}"
+            ),
+            MessageKind::Warning => tracing::warn!(
+                target: "fyrox",
+                log_target = target,
+                log_spans = spans,
+                log_fields = fields,
+                "{content}"
+            ),
+            MessageKind::Error => tracing::error!(
+                target: "fyrox",
+                log_target = target,
+                log_spans = spans,
+                log_fields = fields,
+                "{content}"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::log::{sink::RingBufferSink, Log};
+
+    #[cfg(feature = "log")]
+    #[test]
+    fn test_log_crate_bridge() {
+        use crate::log::bridge::{LogCrateBridge, LogCrateSink};
+
+        LogCrateBridge::install().unwrap();
+        let ring_buffer = RingBufferSink::new(64);
+        let ring_buffer_sink = Log::add_sink(ring_buffer.clone());
+        // Messages must not be passed back to the log.
+        let log_crate_sink = Log::add_sink(LogCrateSink);
+
+        ::log::warn!(target: "bridge_test::log", answer = 42; "From log");
+        Log::remove_sink(log_crate_sink);
+        Log::remove_sink(ring_buffer_sink);
+
+        let messages = ring_buffer
+            .messages()
+            .into_iter()
+            .filter(|message| message.target == "bridge_test::log")
+            .collect::<Vec<_>>();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].content.trim_end(), "From log");
+        assert_eq!(
+            messages[0].fields,
+            [("answer".to_string(), "42".to_string())]
+        );
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_tracing_bridge() {
+        use crate::log::bridge::TracingBridge;
+
+        let ring_buffer = RingBufferSink::new(64);
+        let ring_buffer_sink = Log::add_sink(ring_buffer.clone());
+        tracing::subscriber::with_default(TracingBridge::default(), || {
+            let span = tracing::info_span!(target: "bridge_test::tracing", "work", id = 7);
+            let _guard = span.enter();
+            tracing::error!(target: "bridge_test::tracing", value = 1.5, "From tracing");
+        });
+        Log::remove_sink(ring_buffer_sink);
+
+        let messages = ring_buffer
+            .messages()
+            .into_iter()
+            .filter(|message| message.target == "bridge_test::tracing")
+            .collect::<Vec<_>>();
+        assert_eq!(messages[0].content.trim_end(), "From tracing");
+        assert_eq!(messages[0].spans, ["work"]);
+        assert_eq!(
+            messages[0].fields,
+            [("value".to_string(), "1.5".to_string())]
+        );
+        assert!(messages[1].content.starts_with("work finished in"));
+        assert!(messages[1]
+            .fields
+            .contains(&("id".to_string(), "7".to_string())));
+    }
+}
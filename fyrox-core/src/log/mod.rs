@@ -0,0 +1,840 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Simple structured logger. By default, it writes in the console only. To enable logging into a
+//! file, call [`Log::set_file_name`] somewhere in your `main` function.
+//!
+//! Every message has a target (usually a module path, see [`info!`](crate::info) and other
+//! macros), optional key/value fields and a list of active [spans](LogSpan). Verbosity could be
+//! set per target with [`Log::set_target_verbosity`]. Messages could be redirected to any number
+//! of [sinks](LogSink), see [`sink`] module for built-in ones. Optional `log` and `tracing`
+//! features enable the bridges to the respective crates, see `bridge` module.
+
+use crate::instant::Instant;
+use crate::parking_lot::Mutex;
+#[cfg(target_arch = "wasm32")]
+use crate::wasm_bindgen::{self, prelude::*};
+use crate::{reflect::prelude::*, visitor::prelude::*};
+use fxhash::FxHashMap;
+use std::cell::{Cell, RefCell};
+use std::collections::hash_map::Entry;
+use std::fmt::{Debug, Display, Formatter};
+#[cfg(not(target_arch = "wasm32"))]
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::mpsc::Sender;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+#[cfg(any(feature = "log", feature = "tracing"))]
+pub mod bridge;
+pub mod sink;
+
+pub use sink::LogSink;
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+extern "C" {
+    // Use `js_namespace` here to bind `console.log(..)` instead of just
+    // `log(..)`
+    #[wasm_bindgen(js_namespace = console)]
+    fn log(s: &str);
+}
+
+/// A message that could be sent by the logger to all listeners.
+#[derive(Clone, Debug)]
+pub struct LogMessage {
+    /// Kind of the message: information, warning or error.
+    pub kind: MessageKind,
+    /// The source message without logger prefixes.
+    pub content: String,
+    /// Time point at which the message was recorded. It is relative to the moment when the
+    /// logger was initialized.
+    pub time: Duration,
+    /// Target of the message, usually a module path. It is empty for the messages written with
+    /// [`Log::info`] and other non-macro methods.
+    pub target: String,
+    /// Key/value fields of the message.
+    pub fields: Vec<(String, String)>,
+    /// Names of the spans that were active on the thread that wrote the message, starting from the
+    /// outermost one.
+    pub spans: Vec<String>,
+}
+
+impl LogMessage {
+    /// Returns the message in the same form as [`Display`] does, but with the target after the
+    /// message kind (for example, `[INFO] game::level: Loaded`).
+    pub fn to_string_with_target(&self) -> String {
+        let mut string = String::new();
+        let _ = self.write_line(&mut string, true);
+        string
+    }
+
+    fn write_line(&self, f: &mut dyn std::fmt::Write, with_target: bool) -> std::fmt::Result {
+        f.write_str(self.kind.as_str())?;
+        if with_target && !self.target.is_empty() {
+            write!(f, " {}", self.target)?;
+        }
+        f.write_str(": ")?;
+        for span in self.spans.iter() {
+            write!(f, "{span}: ")?;
+        }
+        f.write_str(self.content.strip_suffix('\n').unwrap_or(&self.content))?;
+        if !self.fields.is_empty() {
+            f.write_str(" {")?;
+            for (i, (key, value)) in self.fields.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{key}={value}")?;
+            }
+            f.write_str("}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for LogMessage {
+    /// Writes the message in the same form as it is written in the console, but without the
+    /// trailing new line. The target is not written, see [`Self::to_string_with_target`].
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.write_line(f, false)
+    }
+}
+
+static LOG: LazyLock<Mutex<Log>> = LazyLock::new(|| {
+    Mutex::new(Log {
+        #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+        file: None,
+        verbosity: MessageKind::Information,
+        target_verbosity: Default::default(),
+        listeners: Default::default(),
+        time_origin: Instant::now(),
+        one_shot_sources: Default::default(),
+        write_to_stdout: true,
+    })
+});
+
+static SINKS: LazyLock<Mutex<Sinks>> = LazyLock::new(Default::default);
+
+#[derive(Default)]
+struct Sinks {
+    sinks: Vec<(LogSinkId, Box<dyn LogSink>)>,
+    next_sink_id: usize,
+}
+
+thread_local! {
+    static SPANS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    static WRITING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// A kind of message.
+#[derive(Debug, Default, Copy, Clone, PartialOrd, PartialEq, Eq, Ord, Hash, Visit, Reflect)]
+#[repr(u32)]
+pub enum MessageKind {
+    /// Some useful information.
+    #[default]
+    Information = 0,
+    /// A warning.
+    Warning = 1,
+    /// An error of some kind.
+    Error = 2,
+}
+
+impl MessageKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            MessageKind::Information => "[INFO]",
+            MessageKind::Warning => "[WARNING]",
+            MessageKind::Error => "[ERROR]",
+        }
+    }
+}
+
+/// A message with a target and key/value fields, that is not yet written to the log. It is
+/// usually created by [`info!`](crate::info) and other macros.
+///
+/// ```rust
+/// # use fyrox_core::log::{LogRecord, MessageKind};
+/// LogRecord::new(MessageKind::Information, "Scene loaded")
+///     .with_target("game::level")
+///     .with_field("nodes", 123)
+///     .write();
+/// ```
+#[derive(Clone, Debug)]
+pub struct LogRecord {
+    kind: MessageKind,
+    target: String,
+    fields: Vec<(String, String)>,
+    content: String,
+}
+
+impl LogRecord {
+    /// Creates a new record of the given kind with empty target and no fields.
+    pub fn new(kind: MessageKind, content: impl Into<String>) -> Self {
+        Self {
+            kind,
+            target: Default::default(),
+            fields: Default::default(),
+            content: content.into(),
+        }
+    }
+
+    /// Sets the target of the record.
+    pub fn with_target(mut self, target: impl Into<String>) -> Self {
+        self.target = target.into();
+        self
+    }
+
+    /// Adds a new key/value field to the record.
+    pub fn with_field(mut self, key: impl Into<String>, value: impl Display) -> Self {
+        self.fields.push((key.into(), value.to_string()));
+        self
+    }
+
+    /// Writes the record to the log, adds a new line to the end of the message.
+    pub fn write(mut self) {
+        self.content.push('\n');
+        Log::write_record(None, self);
+    }
+}
+
+/// An identifier of a sink added to the log, see [`Log::add_sink`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LogSinkId(usize);
+
+/// A timing span. Spans are nested, every message that is written while a span is alive has the
+/// span in its [`LogMessage::spans`] list. When a span is dropped, it writes its duration to the
+/// log. Spans are usually created with [`span!`](crate::span) macro.
+///
+/// ```rust
+/// # use fyrox_core::span;
+/// {
+///     let _span = span!("load_level");
+///     // Everything that is written to the log here has `load_level` span.
+/// } // `load_level` span writes its duration here.
+/// ```
+#[must_use = "the span ends when it is dropped"]
+pub struct LogSpan {
+    target: String,
+    name: String,
+    start: Instant,
+}
+
+impl LogSpan {
+    /// Starts a new span on the current thread.
+    pub fn new(target: impl Into<String>, name: impl Into<String>) -> Self {
+        let name = name.into();
+        SPANS.with_borrow_mut(|spans| spans.push(name.clone()));
+        Self {
+            target: target.into(),
+            name,
+            start: Instant::now(),
+        }
+    }
+
+    /// Returns the time passed since the span was started.
+    pub fn elapsed(&self) -> Duration {
+        Instant::now() - self.start
+    }
+}
+
+impl Drop for LogSpan {
+    fn drop(&mut self) {
+        SPANS.with_borrow_mut(|spans| {
+            if let Some(position) = spans.iter().rposition(|name| name == &self.name) {
+                spans.remove(position);
+            }
+        });
+        let elapsed = self.elapsed();
+        LogRecord::new(
+            MessageKind::Information,
+            format!("{} finished in {elapsed:?}", self.name),
+        )
+        .with_target(std::mem::take(&mut self.target))
+        .with_field("elapsed_ms", elapsed.as_secs_f64() * 1000.0)
+        .write();
+    }
+}
+
+/// See module docs.
+pub struct Log {
+    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+    file: Option<std::fs::File>,
+    verbosity: MessageKind,
+    target_verbosity: FxHashMap<String, MessageKind>,
+    listeners: Vec<Sender<LogMessage>>,
+    time_origin: Instant,
+    one_shot_sources: FxHashMap<usize, String>,
+    write_to_stdout: bool,
+}
+
+impl Log {
+    /// Creates a new log file at the specified path.
+    pub fn set_file_name<P: AsRef<Path>>(#[allow(unused_variables)] path: P) {
+        #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+        {
+            let mut guard = LOG.lock();
+            guard.file = std::fs::File::create(path).ok();
+        }
+    }
+
+    /// Sets new file to write the log to.
+    pub fn set_file(#[allow(unused_variables)] file: Option<std::fs::File>) {
+        #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+        {
+            let mut guard = LOG.lock();
+            guard.file = file;
+        }
+    }
+
+    fn verbosity_of(&self, target: &str) -> MessageKind {
+        // The most specific target wins.
+        self.target_verbosity
+            .iter()
+            .filter(|(prefix, _)| is_sub_target(target, prefix))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, kind)| *kind)
+            .unwrap_or(self.verbosity)
+    }
+
+    fn has_target_verbosity(&self, target: &str) -> bool {
+        self.target_verbosity
+            .keys()
+            .any(|prefix| is_sub_target(target, prefix))
+    }
+
+    /// Writes the record to the console, the file and the listeners. Returns `false` if it is a
+    /// repeated one-shot message and the message that must be passed to the sinks (if any).
+    fn write_internal(
+        &mut self,
+        id: Option<usize>,
+        record: LogRecord,
+    ) -> (bool, Option<LogMessage>) {
+        let LogRecord {
+            kind,
+            target,
+            fields,
+            content,
+        } = record;
+
+        if kind as u32 >= self.verbosity_of(&target) as u32 {
+            if let Some(id) = id {
+                let mut need_write = false;
+                match self.one_shot_sources.entry(id) {
+                    Entry::Occupied(mut message) => {
+                        if message.get() != &content {
+                            message.insert(content.clone());
+                            need_write = true;
+                        }
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(content.clone());
+                        need_write = true;
+                    }
+                }
+
+                if !need_write {
+                    return (false, None);
+                }
+            }
+
+            let message = LogMessage {
+                kind,
+                content,
+                time: Instant::now() - self.time_origin,
+                target,
+                fields,
+                spans: SPANS.with_borrow(|spans| spans.clone()),
+            };
+
+            // Notify listeners about the message and remove all disconnected listeners.
+            self.listeners
+                .retain(|listener| listener.send(message.clone()).is_ok());
+
+            // The target is written only for the targets with their own verbosity, so the
+            // messages look the same as before unless the targets are configured.
+            let mut msg = if self.has_target_verbosity(&message.target) {
+                message.to_string_with_target()
+            } else {
+                message.to_string()
+            };
+            if message.content.ends_with('\n') {
+                msg.push('\n');
+            }
+
+            #[cfg(target_arch = "wasm32")]
+            {
+                log(&msg);
+            }
+
+            #[cfg(all(not(target_os = "android"), not(target_arch = "wasm32")))]
+            {
+                if self.write_to_stdout {
+                    let _ = io::stdout().write_all(msg.as_bytes());
+                }
+
+                if let Some(log_file) = self.file.as_mut() {
+                    let _ = log_file.write_all(msg.as_bytes());
+                    let _ = log_file.flush();
+                }
+            }
+
+            #[cfg(target_os = "android")]
+            {
+                if self.write_to_stdout {
+                    let _ = io::stdout().write_all(msg.as_bytes());
+                }
+            }
+
+            return (true, Some(message));
+        }
+
+        (true, None)
+    }
+
+    fn write_record(id: Option<usize>, record: LogRecord) -> bool {
+        // The log must be unlocked when the sinks are called, so they could write to the log too.
+        let (written, message) = LOG.lock().write_internal(id, record);
+        if let Some(message) = message {
+            write_to_sinks(&message);
+        }
+        written
+    }
+
+    fn writeln_internal<S>(id: Option<usize>, kind: MessageKind, message: S) -> bool
+    where
+        S: AsRef<str>,
+    {
+        let mut msg = message.as_ref().to_owned();
+        msg.push('\n');
+        Self::write_record(id, LogRecord::new(kind, msg))
+    }
+
+    /// Writes a string to the console and optionally into the file (if set).
+    pub fn write<S>(kind: MessageKind, msg: S)
+    where
+        S: AsRef<str>,
+    {
+        Self::write_record(None, LogRecord::new(kind, msg.as_ref()));
+    }
+
+    /// Writes a string to the console and optionally into the file (if set). Unlike [`Self::write`]
+    /// this method writes the message only once per given id if the message remains the same. If
+    /// the message changes, then the new version will be printed to the log. This method is useful
+    /// if you need to print error messages, but prevent them from flooding the log.
+    pub fn write_once<S>(id: usize, kind: MessageKind, msg: S) -> bool
+    where
+        S: AsRef<str>,
+    {
+        Self::write_record(Some(id), LogRecord::new(kind, msg.as_ref()))
+    }
+
+    /// Writes a string to the console and optionally into the file (if set), adds a new line to the
+    /// end of the message.
+    pub fn writeln<S>(kind: MessageKind, msg: S)
+    where
+        S: AsRef<str>,
+    {
+        Self::writeln_internal(None, kind, msg);
+    }
+
+    /// Writes a string to the console and optionally into the file (if set), adds a new line to the
+    /// end of the message. Prints the message only once. See [`Self::write_once`] for more info.
+    pub fn writeln_once<S>(id: usize, kind: MessageKind, msg: S) -> bool
+    where
+        S: AsRef<str>,
+    {
+        Self::writeln_internal(Some(id), kind, msg)
+    }
+
+    /// Writes an information message.
+    pub fn info<S>(msg: S)
+    where
+        S: AsRef<str>,
+    {
+        Self::writeln(MessageKind::Information, msg)
+    }
+
+    /// Writes a warning message.
+    pub fn warn<S>(msg: S)
+    where
+        S: AsRef<str>,
+    {
+        Self::writeln(MessageKind::Warning, msg)
+    }
+
+    /// Writes error message.
+    pub fn err<S>(msg: S)
+    where
+        S: AsRef<str>,
+    {
+        Self::writeln(MessageKind::Error, msg)
+    }
+
+    /// Writes an information message once. See [`Self::write_once`] for more info.
+    pub fn info_once<S>(id: usize, msg: S) -> bool
+    where
+        S: AsRef<str>,
+    {
+        Self::writeln_once(id, MessageKind::Information, msg)
+    }
+
+    /// Writes a warning message. See [`Self::write_once`] for more info.
+    pub fn warn_once<S>(id: usize, msg: S) -> bool
+    where
+        S: AsRef<str>,
+    {
+        Self::writeln_once(id, MessageKind::Warning, msg)
+    }
+
+    /// Writes an error message once. See [`Self::write_once`] for more info.
+    pub fn err_once<S>(id: usize, msg: S) -> bool
+    where
+        S: AsRef<str>,
+    {
+        Self::writeln_once(id, MessageKind::Error, msg)
+    }
+
+    /// Enables or disables writing the messages to stdout stream.
+    pub fn enable_writing_to_stdout(enabled: bool) {
+        LOG.lock().write_to_stdout = enabled;
+    }
+
+    /// Returns `true` if the logger allowed writing to stdout stream, `false` - otherwise.
+    pub fn is_writing_to_stdout() -> bool {
+        LOG.lock().write_to_stdout
+    }
+
+    /// Sets verbosity level.
+    pub fn set_verbosity(kind: MessageKind) {
+        LOG.lock().verbosity = kind;
+    }
+
+    /// Returns current verbosity level of the logger.
+    pub fn verbosity() -> MessageKind {
+        LOG.lock().verbosity
+    }
+
+    /// Sets verbosity level of the given target and all its sub-targets (`a::b` covers `a::b::c`
+    /// as well). The most specific target wins. `None` removes the target-specific level, so the
+    /// messages of the target use the global one (see [`Self::set_verbosity`]). The messages of
+    /// the targets with their own verbosity level are written with the target in the console and
+    /// the file.
+    pub fn set_target_verbosity(target: impl Into<String>, kind: Option<MessageKind>) {
+        let target = target.into();
+        let mut log = LOG.lock();
+        match kind {
+            Some(kind) => {
+                log.target_verbosity.insert(target, kind);
+            }
+            None => {
+                log.target_verbosity.remove(&target);
+            }
+        }
+    }
+
+    /// Returns `true` if a message of the given kind and target will be written to the log.
+    pub fn is_enabled(kind: MessageKind, target: &str) -> bool {
+        kind >= LOG.lock().verbosity_of(target)
+    }
+
+    /// Adds a listener that will receive a copy of every message passed into the log.
+    pub fn add_listener(listener: Sender<LogMessage>) {
+        LOG.lock().listeners.push(listener)
+    }
+
+    /// Adds a new sink that will receive every message passed into the log. See [`sink`] module
+    /// for built-in sinks.
+    pub fn add_sink(sink: impl LogSink + 'static) -> LogSinkId {
+        let mut sinks = SINKS.lock();
+        let id = LogSinkId(sinks.next_sink_id);
+        sinks.next_sink_id += 1;
+        sinks.sinks.push((id, Box::new(sink)));
+        id
+    }
+
+    /// Removes a sink with the given id and returns it.
+    pub fn remove_sink(id: LogSinkId) -> Option<Box<dyn LogSink>> {
+        let (_, mut sink) = {
+            let mut sinks = SINKS.lock();
+            let position = sinks.sinks.iter().position(|(sink_id, _)| *sink_id == id)?;
+            sinks.sinks.remove(position)
+        };
+        WRITING.set(true);
+        sink.flush();
+        WRITING.set(false);
+        Some(sink)
+    }
+
+    /// Flushes all the sinks.
+    pub fn flush() {
+        if WRITING.get() {
+            return;
+        }
+        let mut sinks = SINKS.lock();
+        WRITING.set(true);
+        for (_, sink) in sinks.sinks.iter_mut() {
+            sink.flush();
+        }
+        WRITING.set(false);
+    }
+
+    /// Returns `true` if the logger is currently passing a message (or a flush request) to its
+    /// sinks on this thread.
+    /// Bridges to other loggers use it to prevent infinite loops.
+    pub fn is_writing_on_current_thread() -> bool {
+        WRITING.get()
+    }
+
+    /// Allows you to verify that the result of the operation is Ok, or print the error in the log.
+    ///
+    /// # Use cases
+    ///
+    /// Typical use case for this method is that when you _can_ ignore errors, but want them to
+    /// be in the log.
+    pub fn verify<T, E>(result: Result<T, E>)
+    where
+        E: Display,
+    {
+        if let Err(e) = result {
+            Self::writeln(MessageKind::Error, format!("Operation failed! Reason: {e}"));
+        }
+    }
+
+    /// Allows you to verify that the result of the operation is Ok, or print the error in the log.
+    ///
+    /// # Use cases
+    ///
+    /// Typical use case for this method is that when you _can_ ignore errors, but want them to
+    /// be in the log.
+    pub fn verify_message<S, T, E>(result: Result<T, E>, msg: S)
+    where
+        E: Debug,
+        S: Display,
+    {
+        if let Err(e) = result {
+            Self::writeln(MessageKind::Error, format!("{msg}. Reason: {e:?}"));
+        }
+    }
+}
+
+/// `a::b` covers `a::b` and `a::b::c`, but not `a::bc`.
+fn is_sub_target(target: &str, prefix: &str) -> bool {
+    target
+        .strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+}
+
+/// Passes the message to all the sinks. The sinks could write to the log, but such messages are
+/// not passed to the sinks again (the same goes for a message that a bridge receives back from
+/// another logger), otherwise a sink would end up in an infinite loop or a deadlock.
+fn write_to_sinks(message: &LogMessage) {
+    if WRITING.get() {
+        return;
+    }
+    let mut sinks = SINKS.lock();
+    WRITING.set(true);
+    for (_, sink) in sinks.sinks.iter_mut() {
+        sink.write(message);
+    }
+    WRITING.set(false);
+}
+
+/// Writes a message of the given kind with optional target and key/value fields. The target is
+/// the module path of the call site by default. It is used by [`info!`](crate::info),
+/// [`warn!`](crate::warn) and [`err!`](crate::err) macros:
+///
+/// ```rust
+/// # use fyrox_core::info;
+/// # let path = "data/scene.rgs";
+/// info!("Loading {path}");
+/// info!(target: "game::level", "Loading {path}");
+/// info!(nodes = 123, time = 4.5; "Loaded {path}");
+/// info!(target: "game::level", nodes = 123; "Loaded {path}");
+/// ```
+#[macro_export]
+macro_rules! log_record {
+    ($kind:expr, target: $target:expr, $($key:ident = $value:expr),+ ; $($arg:tt)+) => {
+        $crate::log::LogRecord::new($kind, format!($($arg)+))
+            .with_target($target)
+            $(.with_field(stringify!($key), &$value))+
+            .write()
+    };
+    ($kind:expr, target: $target:expr, $($arg:tt)+) => {
+        $crate::log::LogRecord::new($kind, format!($($arg)+))
+            .with_target($target)
+            .write()
+    };
+    ($kind:expr, $($key:ident = $value:expr),+ ; $($arg:tt)+) => {
+        $crate::log_record!($kind, target: module_path!(), $($key = $value),+ ; $($arg)+)
+    };
+    ($kind:expr, $($arg:tt)+) => {
+        $crate::log_record!($kind, target: module_path!(), $($arg)+)
+    };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => {
+        $crate::log_record!($crate::log::MessageKind::Information, $($arg)*)
+    };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        $crate::log_record!($crate::log::MessageKind::Warning, $($arg)*)
+    };
+}
+
+#[macro_export]
+macro_rules! err {
+    ($($arg:tt)*) => {
+        $crate::log_record!($crate::log::MessageKind::Error, $($arg)*)
+    };
+}
+
+#[macro_export]
+macro_rules! info_once {
+    ($id:expr, $($arg:tt)*) => {
+        $crate::log::Log::info_once($id, format!($($arg)*))
+    };
+}
+
+#[macro_export]
+macro_rules! warn_once {
+    ($id:expr, $($arg:tt)*) => {
+        $crate::log::Log::warn_once($id, format!($($arg)*))
+    };
+}
+
+#[macro_export]
+macro_rules! err_once {
+    ($id:expr, $($arg:tt)*) => {
+        $crate::log::Log::err_once($id, format!($($arg)*))
+    };
+}
+
+/// Starts a new [`LogSpan`] with the given name (formatted the same way as in `format!`). The
+/// target is the module path of the call site by default:
+///
+/// ```rust
+/// # use fyrox_core::span;
+/// let _span = span!("load_level");
+/// let _span = span!(target: "game::level", "load_level {}", 1);
+/// ```
+#[macro_export]
+macro_rules! span {
+    (target: $target:expr, $($arg:tt)+) => {
+        $crate::log::LogSpan::new($target, format!($($arg)+))
+    };
+    ($($arg:tt)+) => {
+        $crate::log::LogSpan::new(module_path!(), format!($($arg)+))
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use crate::log::{sink::RingBufferSink, Log, LogMessage, LogSink, MessageKind};
+
+    #[test]
+    fn test_targets_fields_and_spans() {
+        let ring_buffer = RingBufferSink::new(64);
+        let sink = Log::add_sink(ring_buffer.clone());
+        Log::set_target_verbosity("log_test::muted", Some(MessageKind::Error));
+
+        crate::info!(target: "log_test::muted::inner", "Muted");
+        crate::err!(target: "log_test::muted", "Error");
+        crate::info!(target: "log_test::mutedx", "Not muted");
+        {
+            let _span = crate::span!(target: "log_test::span", "outer");
+            crate::warn!(target: "log_test::span", count = 3, name = "x"; "Inside {}", 1);
+        }
+        Log::remove_sink(sink);
+        Log::set_target_verbosity("log_test::muted", None);
+
+        let messages = ring_buffer
+            .messages()
+            .into_iter()
+            .filter(|message| message.target.starts_with("log_test"))
+            .collect::<Vec<_>>();
+        let contents = messages
+            .iter()
+            .map(|message| message.content.trim_end())
+            .collect::<Vec<_>>();
+        assert_eq!(contents[..3], ["Error", "Not muted", "Inside 1"]);
+        assert!(contents[3].starts_with("outer finished in"));
+
+        let inside = &messages[2];
+        assert_eq!(inside.spans, ["outer"]);
+        assert_eq!(
+            inside.fields,
+            [
+                ("count".to_string(), "3".to_string()),
+                ("name".to_string(), "x".to_string())
+            ]
+        );
+        assert_eq!(
+            inside.to_string(),
+            "[WARNING]: outer: Inside 1 {count=3, name=x}"
+        );
+        assert_eq!(
+            inside.to_string_with_target(),
+            "[WARNING] log_test::span: outer: Inside 1 {count=3, name=x}"
+        );
+        assert!(messages[3].spans.is_empty());
+        assert_eq!(messages[3].fields[0].0, "elapsed_ms");
+    }
+
+    /// Writes every message it receives back to the log.
+    struct EchoSink(RingBufferSink);
+
+    impl LogSink for EchoSink {
+        fn write(&mut self, message: &LogMessage) {
+            if message.target == "log_test::echo" {
+                Log::warn(format!("Echo: {}", message.content.trim_end()));
+            }
+            self.0.write(message);
+        }
+
+        fn flush(&mut self) {
+            Log::info("Echo sink flushed");
+        }
+    }
+
+    #[test]
+    fn test_sink_writing_to_log() {
+        let ring_buffer = RingBufferSink::new(64);
+        let sink = Log::add_sink(EchoSink(ring_buffer.clone()));
+        crate::info!(target: "log_test::echo", "Hello");
+        Log::flush();
+        Log::remove_sink(sink);
+
+        let messages = ring_buffer
+            .messages()
+            .into_iter()
+            .filter(|message| message.content.contains("Hello"))
+            .map(|message| message.content)
+            .collect::<Vec<_>>();
+        // The echo is written to the console, but it is not passed to the sinks again.
+        assert_eq!(messages, ["Hello\n"]);
+    }
+}
@@ -0,0 +1,368 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Log sinks receive every message written to the log. See [`LogSink`] docs for more info.
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::log::Log;
+use crate::{
+    log::{LogMessage, MessageKind},
+    parking_lot::Mutex,
+};
+use std::{collections::VecDeque, fmt::Write as _, sync::Arc};
+#[cfg(not(target_arch = "wasm32"))]
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+/// A sink receives every message that passed the verbosity filters of the log. Sinks are added
+/// with [`crate::log::Log::add_sink`]. Built-in sinks are:
+///
+/// - [`RotatingFileSink`] - writes the messages to a file, that is rotated when it becomes too
+///   large;
+/// - [`JsonLinesSink`] - writes every message as a JSON object on a separate line;
+/// - [`RingBufferSink`] - keeps a number of the last messages in memory (for example, to attach
+///   them to crash reports).
+///
+/// A sink could write to the log (for example, to report an I/O error), such messages are written
+/// to the console, the file and the listeners, but they're not passed to the sinks. A sink must not
+/// add or remove sinks, because the list of sinks is locked while the sink is called.
+pub trait LogSink: Send {
+    /// Writes the message.
+    fn write(&mut self, message: &LogMessage);
+
+    /// Flushes buffered messages (if any).
+    fn flush(&mut self) {}
+}
+
+/// Writes the messages to a file in the same form as they're written in the console. When the
+/// file becomes larger than the specified size, it is renamed to `<name>.1.<ext>` (previous
+/// rotated files are shifted: `<name>.1.<ext>` becomes `<name>.2.<ext>` and so on) and a new file
+/// is started. Only the given number of rotated files is kept.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct RotatingFileSink {
+    path: PathBuf,
+    max_size: u64,
+    max_rotated_files: usize,
+    file: Option<BufWriter<File>>,
+    size: u64,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl RotatingFileSink {
+    /// Creates a new sink. The existing file at the given path is rotated, so every run of the
+    /// application starts a new file.
+    pub fn new(
+        path: impl AsRef<Path>,
+        max_size: u64,
+        max_rotated_files: usize,
+    ) -> io::Result<Self> {
+        let mut sink = Self {
+            path: path.as_ref().to_path_buf(),
+            max_size,
+            max_rotated_files,
+            file: None,
+            size: 0,
+        };
+        sink.rotate()?;
+        Ok(sink)
+    }
+
+    /// Returns a path of the rotated file with the given index (starting from 1).
+    pub fn rotated_path(&self, index: usize) -> PathBuf {
+        let stem = self
+            .path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let name = match self.path.extension() {
+            Some(ext) => format!("{stem}.{index}.{}", ext.to_string_lossy()),
+            None => format!("{stem}.{index}"),
+        };
+        self.path.with_file_name(name)
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        if let Some(mut file) = self.file.take() {
+            file.flush()?;
+        }
+        if self.path.exists() {
+            if self.max_rotated_files == 0 {
+                std::fs::remove_file(&self.path)?;
+            } else {
+                for index in (1..self.max_rotated_files).rev() {
+                    let from = self.rotated_path(index);
+                    if from.exists() {
+                        std::fs::rename(from, self.rotated_path(index + 1))?;
+                    }
+                }
+                std::fs::rename(&self.path, self.rotated_path(1))?;
+            }
+        }
+        self.file = Some(BufWriter::new(File::create(&self.path)?));
+        self.size = 0;
+        Ok(())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl LogSink for RotatingFileSink {
+    fn write(&mut self, message: &LogMessage) {
+        let line = format!("{message}\n");
+        if self.size > 0 && self.size + line.len() as u64 > self.max_size {
+            if let Err(err) = self.rotate() {
+                Log::warn(format!(
+                    "Unable to rotate {} log file. Reason: {err}",
+                    self.path.display()
+                ));
+            }
+        }
+        if let Some(file) = self.file.as_mut() {
+            if file.write_all(line.as_bytes()).is_ok() {
+                self.size += line.len() as u64;
+            }
+            // Errors must not be lost in case of a crash.
+            if message.kind == MessageKind::Error {
+                let _ = file.flush();
+            }
+        }
+    }
+
+    fn flush(&mut self) {
+        if let Some(file) = self.file.as_mut() {
+            let _ = file.flush();
+        }
+    }
+}
+
+/// Writes every message as a JSON object on a separate line (also known as JSON Lines format).
+/// Every object has the following fields: `time` (seconds since the log was initialized), `level`
+/// (`info`, `warning` or `error`), `target`, `spans` (array of strings), `message` and `fields`
+/// (an object with string values).
+pub struct JsonLinesSink<W: std::io::Write + Send> {
+    writer: W,
+}
+
+impl<W: std::io::Write + Send> JsonLinesSink<W> {
+    /// Creates a new sink, that writes the messages to the given writer.
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Converts the message into a single-line JSON object.
+    pub fn format(message: &LogMessage) -> String {
+        let mut line = String::new();
+        let _ = write!(
+            line,
+            "{{\"time\":{},\"level\":\"{}\",\"target\":",
+            message.time.as_secs_f64(),
+            match message.kind {
+                MessageKind::Information => "info",
+                MessageKind::Warning => "warning",
+                MessageKind::Error => "error",
+            },
+        );
+        write_json_string(&mut line, &message.target);
+        line.push_str(",\"spans\":[");
+        for (i, span) in message.spans.iter().enumerate() {
+            if i > 0 {
+                line.push(',');
+            }
+            write_json_string(&mut line, span);
+        }
+        line.push_str("],\"message\":");
+        write_json_string(
+            &mut line,
+            message
+                .content
+                .strip_suffix('\n')
+                .unwrap_or(&message.content),
+        );
+        line.push_str(",\"fields\":{");
+        for (i, (key, value)) in message.fields.iter().enumerate() {
+            if i > 0 {
+                line.push(',');
+            }
+            write_json_string(&mut line, key);
+            line.push(':');
+            write_json_string(&mut line, value);
+        }
+        line.push_str("}}");
+        line
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl JsonLinesSink<BufWriter<File>> {
+    /// Creates a new file at the given path and writes the messages to it.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: std::io::Write + Send> LogSink for JsonLinesSink<W> {
+    fn write(&mut self, message: &LogMessage) {
+        let mut line = Self::format(message);
+        line.push('\n');
+        let _ = self.writer.write_all(line.as_bytes());
+        if message.kind == MessageKind::Error {
+            let _ = self.writer.flush();
+        }
+    }
+
+    fn flush(&mut self) {
+        let _ = self.writer.flush();
+    }
+}
+
+fn write_json_string(out: &mut String, string: &str) {
+    out.push('"');
+    for ch in string.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            ch if (ch as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", ch as u32);
+            }
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+}
+
+/// Keeps the given number of the last messages in memory. The sink is shared: it could be cloned
+/// before it is added to the log and the clone could be used to read the messages later. It is
+/// useful to attach the latest messages to crash reports.
+///
+/// ```rust
+/// # use fyrox_core::log::{Log, sink::RingBufferSink};
+/// let ring_buffer = RingBufferSink::new(256);
+/// Log::add_sink(ring_buffer.clone());
+/// Log::info("Hello");
+/// assert!(ring_buffer.messages().iter().any(|m| m.content.starts_with("Hello")));
+/// ```
+#[derive(Clone)]
+pub struct RingBufferSink {
+    messages: Arc<Mutex<VecDeque<LogMessage>>>,
+    capacity: usize,
+}
+
+impl RingBufferSink {
+    /// Creates a new sink, that keeps at most `capacity` messages.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            messages: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    /// Returns a copy of the kept messages, starting from the oldest one.
+    pub fn messages(&self) -> Vec<LogMessage> {
+        self.messages.lock().iter().cloned().collect()
+    }
+
+    /// Removes all the kept messages.
+    pub fn clear(&self) {
+        self.messages.lock().clear();
+    }
+}
+
+impl LogSink for RingBufferSink {
+    fn write(&mut self, message: &LogMessage) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut messages = self.messages.lock();
+        if messages.len() == self.capacity {
+            messages.pop_front();
+        }
+        messages.push_back(message.clone());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    fn message(content: &str) -> LogMessage {
+        LogMessage {
+            kind: MessageKind::Warning,
+            content: content.to_string(),
+            time: Duration::from_millis(1500),
+            target: "game::level".to_string(),
+            fields: vec![("nodes".to_string(), "12".to_string())],
+            spans: vec!["load".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_json_lines() {
+        assert_eq!(
+            JsonLinesSink::<Vec<u8>>::format(&message("Quote \" and\ttab\n")),
+            r#"{"time":1.5,"level":"warning","target":"game::level","spans":["load"],"message":"Quote \" and\ttab","fields":{"nodes":"12"}}"#
+        );
+    }
+
+    #[test]
+    fn test_ring_buffer() {
+        let ring_buffer = RingBufferSink::new(2);
+        let mut sink = ring_buffer.clone();
+        for content in ["a", "b", "c"] {
+            sink.write(&message(content));
+        }
+        let contents = ring_buffer
+            .messages()
+            .into_iter()
+            .map(|message| message.content)
+            .collect::<Vec<_>>();
+        assert_eq!(contents, ["b", "c"]);
+    }
+
+    #[test]
+    fn test_rotating_file() {
+        let dir = std::env::temp_dir().join("fyrox_log_rotation");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("game.log");
+        std::fs::write(&path, "previous run").unwrap();
+
+        let mut sink = RotatingFileSink::new(&path, 100, 2).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(sink.rotated_path(1)).unwrap(),
+            "previous run"
+        );
+        for i in 0..10 {
+            sink.write(&message(&format!("Message {i}")));
+        }
+        sink.flush();
+
+        assert!(std::fs::metadata(&path).unwrap().len() <= 100);
+        assert!(sink.rotated_path(2).exists());
+        assert!(!sink.rotated_path(3).exists());
+        let last = std::fs::read_to_string(&path).unwrap();
+        assert!(last.ends_with("Message 9 {nodes=12}\n"), "{last}");
+    }
+}
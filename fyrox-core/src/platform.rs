@@ -29,6 +29,19 @@ pub enum TargetPlatform {
     Android,
 }
 
+impl TargetPlatform {
+    /// Returns the platform the code is compiled for.
+    pub const fn current() -> Self {
+        if cfg!(target_arch = "wasm32") {
+            Self::WebAssembly
+        } else if cfg!(target_os = "android") {
+            Self::Android
+        } else {
+            Self::PC
+        }
+    }
+}
+
 impl Display for TargetPlatform {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -58,8 +58,8 @@ use crate::{
     io::{self},
     pool::{Handle, Pool},
     visitor::{
-        reader::{ascii::AsciiReader, binary::BinaryReader, Reader},
-        writer::{ascii::AsciiWriter, binary::BinaryWriter, Writer},
+        reader::{ascii::AsciiReader, binary::BinaryReader, json::JsonReader, Reader},
+        writer::{ascii::AsciiWriter, binary::BinaryWriter, json::JsonWriter, Writer},
     },
 };
 use bitflags::bitflags;
@@ -278,6 +278,11 @@ bitflags! {
         /// and therefore write its data. Otherwise, InheritableVariable has the special
         /// property of *not writing itself* when the `MODIFIED` flag is not set.
         const SERIALIZE_EVERYTHING = 1 << 1;
+        /// Tell the derived [Visit::visit] implementations to continue reading when a field cannot
+        /// be read (it is missing, has a wrong type, etc.). The field keeps its current (usually
+        /// default) value and the error is stored in the visitor, see [Visitor::recovered_errors].
+        /// It is used to inspect and repair broken files.
+        const RECOVER_ERRORS = 1 << 2;
     }
 }
 
@@ -333,6 +338,9 @@ pub struct Visitor {
     /// Flags that can activate special behavior in some Visit values, such as
     /// [crate::variable::InheritableVariable].
     pub flags: VisitorFlags,
+    /// Errors that were suppressed in [VisitorFlags::RECOVER_ERRORS] mode, along with the paths of
+    /// the fields that caused them.
+    recovered_errors: Vec<(String, VisitError)>,
 }
 
 impl Debug for Visitor {
@@ -468,6 +476,10 @@ pub enum Format {
     /// Slow and "fat" format, but changes can be merged by a version control system. It makes this
     /// format ideal for collaborative work.
     Ascii,
+    /// Structured text format, the output is a valid JSON document. It is even slower and "fatter"
+    /// than [`Format::Ascii`], but it can be merged by a version control system and processed by
+    /// any external tool that understands JSON (diff viewers, validators, scripts, etc.).
+    Json,
 }
 
 impl Visitor {
@@ -489,6 +501,14 @@ impl Visitor {
     /// these bytes are not at the beginning of the given slice.
     pub const MAGIC_ASCII_CURRENT: &'static str = "FTAX";
 
+    /// Sequence of bytes that is automatically written as the first key of the root object when a
+    /// visitor is encoded into JSON form. It is written by [Visitor::save_json_to_file],
+    /// [Visitor::save_json_to_memory], and [Visitor::save_json_to_string].
+    ///
+    /// [Visitor::load_json_from_file] and [Visitor::load_json_from_memory] will return an error if
+    /// the root object does not start with this key.
+    pub const MAGIC_JSON_CURRENT: &'static str = "FTJS";
+
     /// Checks whether the given reader points to a supported file format or not.
     #[must_use]
     pub fn is_supported(src: &mut dyn Read) -> bool {
@@ -503,6 +523,8 @@ impl Visitor {
                 return Format::Binary;
             } else if magic.eq(Visitor::MAGIC_ASCII_CURRENT.as_bytes()) {
                 return Format::Ascii;
+            } else if JsonReader::is_json(&mut magic.as_slice().chain(src)) {
+                return Format::Json;
             }
         }
         Format::Unknown
@@ -531,6 +553,7 @@ impl Visitor {
             version: CURRENT_VERSION,
             blackboard: Blackboard::new(),
             flags: VisitorFlags::NONE,
+            recovered_errors: Default::default(),
         }
     }
 
@@ -594,6 +617,78 @@ impl Visitor {
         self.nodes.iter().find(|n| n.name == name)
     }
 
+    /// Returns an iterator over the fields of every node, in no particular order. It could be used
+    /// to inspect the stored data without knowing its types, for example to find all the ids stored
+    /// in a file.
+    pub fn fields(&self) -> impl Iterator<Item = &Field> {
+        self.nodes.iter().flat_map(|node| node.fields.iter())
+    }
+
+    /// Handles an error of visiting a field with the given name of the current region. If the
+    /// visitor is reading in [VisitorFlags::RECOVER_ERRORS] mode, the error is stored and `Ok` is
+    /// returned, otherwise the error is returned as-is.
+    pub fn recover_error(&mut self, name: &str, error: VisitError) -> VisitResult {
+        if self.reading && self.flags.contains(VisitorFlags::RECOVER_ERRORS) {
+            let path = self.breadcrumbs() + " > " + name;
+            self.recovered_errors.push((path, error));
+            Ok(())
+        } else {
+            Err(error)
+        }
+    }
+
+    /// Returns the errors that were suppressed in [VisitorFlags::RECOVER_ERRORS] mode, along with
+    /// the paths of the fields that caused them.
+    pub fn recovered_errors(&self) -> &[(String, VisitError)] {
+        &self.recovered_errors
+    }
+
+    /// Returns the paths of the fields and the regions of this visitor that do not exist in the
+    /// other visitor. Regions are matched by their names, and the contents of a missing region are
+    /// not listed. Comparing the loaded data with the data saved back, for example, gives the
+    /// data that was ignored while loading.
+    pub fn unmatched_paths(&self, other: &Visitor) -> Vec<String> {
+        fn compare(
+            this: &Visitor,
+            this_node: &VisitorNode,
+            other: &Visitor,
+            other_node: &VisitorNode,
+            path: &str,
+            out: &mut Vec<String>,
+        ) {
+            for field in this_node.fields.iter() {
+                if !other_node.fields.iter().any(|f| f.name == field.name) {
+                    out.push(format!("{path} > {}", field.name));
+                }
+            }
+            for child in this_node.children.iter() {
+                let child = &this.nodes[*child];
+                let child_path = format!("{path} > {}", child.name);
+                match other_node
+                    .children
+                    .iter()
+                    .map(|c| &other.nodes[*c])
+                    .find(|c| c.name == child.name)
+                {
+                    Some(other_child) => compare(this, child, other, other_child, &child_path, out),
+                    None => out.push(child_path),
+                }
+            }
+        }
+
+        let mut out = Vec::new();
+        let root = &self.nodes[self.root];
+        compare(
+            self,
+            root,
+            other,
+            &other.nodes[other.root],
+            &root.name,
+            &mut out,
+        );
+        out
+    }
+
     /// True if this Visitor is changing the values that it visits. In other words,
     /// `x.visit("MyValue", &mut visitor)` will result in `x` being mutated to match whatever value
     /// is stored in `visitor`.
@@ -770,6 +865,45 @@ impl Visitor {
         Self::load_ascii_from_memory(&io::load_file(path).await?)
     }
 
+    /// Create a string containing all the data of this Visitor in JSON form. The string is
+    /// formatted to be human-readable with each field on its own line and tabs to indent child
+    /// nodes.
+    pub fn save_json_to_string(&self) -> String {
+        let mut cursor = Cursor::<Vec<u8>>::default();
+        self.save_json_to_memory(&mut cursor).unwrap();
+        String::from_utf8(cursor.into_inner()).unwrap()
+    }
+
+    /// Create a string containing all the data of this Visitor in JSON form and saves it to the
+    /// given path.
+    pub fn save_json_to_file(&self, path: impl AsRef<Path>) -> VisitResult {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.save_json_to_memory(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Create a string containing all the data of this Visitor in JSON form and writes it to the
+    /// given writer.
+    pub fn save_json_to_memory(&self, mut dest: impl Write) -> VisitResult {
+        let writer = JsonWriter::default();
+        writer.write(self, &mut dest)
+    }
+
+    /// Tries to create a visitor from the given JSON data. The returned instance can then be used
+    /// to deserialize some data.
+    pub fn load_json_from_memory(data: &[u8]) -> Result<Self, VisitError> {
+        let mut src = Cursor::new(data);
+        let mut reader = JsonReader::new(&mut src);
+        reader.read()
+    }
+
+    /// Tries to create a visitor from the given JSON file. The returned instance can then be used
+    /// to deserialize some data.
+    pub async fn load_json_from_file(path: impl AsRef<Path>) -> Result<Self, VisitError> {
+        Self::load_json_from_memory(&io::load_file(path).await?)
+    }
+
     /// Write the data of this Visitor to the given writer. Begin by writing [Visitor::MAGIC_BINARY_CURRENT].
     pub fn save_binary_to_memory(&self, mut dest: impl Write) -> VisitResult {
         let writer = BinaryWriter::default();
@@ -809,26 +943,38 @@ impl Visitor {
         reader.read()
     }
 
+    /// Saves the data of this visitor to a file at the given path in the given format. Returns
+    /// [`VisitError::NotSupportedFormat`] for [`Format::Unknown`].
+    pub fn save_to_file(&self, path: impl AsRef<Path>, format: Format) -> VisitResult {
+        match format {
+            Format::Unknown => Err(VisitError::NotSupportedFormat),
+            Format::Binary => self.save_binary_to_file(path),
+            Format::Ascii => self.save_ascii_to_file(path),
+            Format::Json => self.save_json_to_file(path),
+        }
+    }
+
     /// Tries to load a visitor from the given file. This method automatically detects a format of
-    /// the incoming data (binary or ASCII) and tries to load it.
+    /// the incoming data (binary, ASCII or JSON) and tries to load it.
     pub async fn load_from_file(path: impl AsRef<Path>) -> Result<Self, VisitError> {
         Self::load_from_memory(&io::load_file(path).await?)
     }
 
     /// Tries to load a visitor from the given data. This method automatically detects a format of
-    /// the incoming data (binary or ASCII) and tries to load it.
+    /// the incoming data (binary, ASCII or JSON) and tries to load it.
     pub fn load_from_memory(data: &[u8]) -> Result<Self, VisitError> {
         match Self::detect_format_from_slice(data) {
             Format::Unknown => Err(VisitError::NotSupportedFormat),
             Format::Binary => Self::load_binary_from_memory(data),
             Format::Ascii => Self::load_ascii_from_memory(data),
+            Format::Json => Self::load_json_from_memory(data),
         }
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::visitor::{BinaryBlob, Visit, VisitResult, Visitor};
+    use crate::visitor::{BinaryBlob, Format, Visit, VisitResult, Visitor, VisitorFlags};
     use nalgebra::{
         Matrix2, Matrix3, Matrix4, UnitComplex, UnitQuaternion, Vector2, Vector3, Vector4,
     };
@@ -1130,4 +1276,110 @@ mod test {
             assert_eq!(objects, expected_objects);
         }
     }
+
+    #[test]
+    fn visitor_test_json() {
+        let path = Path::new("test_json.json");
+
+        // Save
+        {
+            let visitor = serialize();
+            visitor.save_json_to_file(path).unwrap();
+        }
+
+        // Load
+        {
+            let expected_resource = resource();
+            let expected_resource_arc = resource_arc();
+            let expected_objects =
+                objects(expected_resource.clone(), expected_resource_arc.clone());
+
+            // The format must be detected automatically.
+            let mut visitor = futures::executor::block_on(Visitor::load_from_file(path)).unwrap();
+            let mut resource: Rc<Resource> = Rc::new(Default::default());
+            resource.visit("SharedResource", &mut visitor).unwrap();
+            assert_eq!(resource, expected_resource);
+
+            let mut resource_arc: Arc<Resource> = Arc::new(Default::default());
+            resource_arc
+                .visit("SharedResourceArc", &mut visitor)
+                .unwrap();
+            assert_eq!(resource_arc, expected_resource_arc);
+
+            let mut objects: Vec<Foo> = Vec::new();
+            objects.visit("Objects", &mut visitor).unwrap();
+            assert_eq!(objects, expected_objects);
+        }
+    }
+
+    #[test]
+    fn visitor_save_to_file_in_format() {
+        let visitor = serialize();
+        for (format, path) in [
+            (Format::Binary, "test_format.bin"),
+            (Format::Ascii, "test_format.txt"),
+            (Format::Json, "test_format.json"),
+        ] {
+            visitor.save_to_file(path, format).unwrap();
+            let data = std::fs::read(path).unwrap();
+            assert_eq!(Visitor::detect_format_from_slice(&data), format);
+        }
+        assert!(visitor
+            .save_to_file("test_format.unknown", Format::Unknown)
+            .is_err());
+    }
+
+    #[test]
+    fn visitor_recover_errors() {
+        #[derive(Visit, Default)]
+        struct Old {
+            a: u32,
+            legacy: u32,
+        }
+
+        #[derive(Visit, Default, Debug, PartialEq)]
+        struct New {
+            a: u32,
+            b: String,
+            c: Model,
+        }
+
+        let mut old = Old { a: 1, legacy: 2 };
+        let mut visitor = Visitor::new();
+        old.visit("Object", &mut visitor).unwrap();
+        let data = visitor.save_binary_to_vec().unwrap();
+
+        // Missing fields are fatal by default.
+        let mut visitor = Visitor::load_from_memory(&data).unwrap();
+        assert!(New::default().visit("Object", &mut visitor).is_err());
+
+        let mut visitor = Visitor::load_from_memory(&data).unwrap();
+        visitor.flags = VisitorFlags::RECOVER_ERRORS;
+        let mut new = New::default();
+        new.visit("Object", &mut visitor).unwrap();
+        assert_eq!(
+            new,
+            New {
+                a: 1,
+                ..Default::default()
+            }
+        );
+        let paths = visitor
+            .recovered_errors()
+            .iter()
+            .map(|(path, _)| path.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(paths, ["__ROOT__ > Object > B", "__ROOT__ > Object > C"]);
+
+        let mut resaved = Visitor::new();
+        new.visit("Object", &mut resaved).unwrap();
+        assert_eq!(
+            visitor.unmatched_paths(&resaved),
+            ["__ROOT__ > Object > Legacy"]
+        );
+        assert_eq!(
+            resaved.unmatched_paths(&visitor),
+            ["__ROOT__ > Object > B", "__ROOT__ > Object > C"]
+        );
+    }
 }
@@ -36,25 +36,25 @@ use nalgebra::{
 use std::{io::Read, str::FromStr};
 use uuid::Uuid;
 
-struct ByteReader<'a> {
+pub(super) struct ByteReader<'a> {
     peeked: Option<u8>,
     src: &'a mut dyn Read,
 }
 
 impl<'a> ByteReader<'a> {
     #[allow(dead_code)]
-    fn new(src: &'a mut dyn Read) -> Self {
+    pub(super) fn new(src: &'a mut dyn Read) -> Self {
         Self { peeked: None, src }
     }
 
-    fn next(&mut self) -> Result<u8, VisitError> {
+    pub(super) fn next(&mut self) -> Result<u8, VisitError> {
         match self.peeked.take() {
             Some(b) => Ok(b),
             None => Ok(self.src.read_u8()?),
         }
     }
 
-    fn peek(&mut self) -> Result<u8, VisitError> {
+    pub(super) fn peek(&mut self) -> Result<u8, VisitError> {
         match self.peeked {
             Some(b) => Ok(b),
             None => {
@@ -79,11 +79,11 @@ impl<'a> ByteReader<'a> {
         }
     }
 
-    fn skip_ws(&mut self) -> VisitResult {
+    pub(super) fn skip_ws(&mut self) -> VisitResult {
         self.skip_until(|ch| ch.is_ascii_whitespace())
     }
 
-    fn skip_n(&mut self, n: usize) -> VisitResult {
+    pub(super) fn skip_n(&mut self, n: usize) -> VisitResult {
         if n == 0 {
             return Ok(());
         }
@@ -392,6 +392,7 @@ impl Reader for AsciiReader<'_> {
             version,
             blackboard: Blackboard::new(),
             flags: VisitorFlags::NONE,
+            recovered_errors: Default::default(),
         };
         visitor.root = self.read_node(&mut visitor)?;
         visitor.current_node = visitor.root;
@@ -263,6 +263,7 @@ impl Reader for BinaryReader<'_> {
             version,
             blackboard: Blackboard::new(),
             flags: VisitorFlags::NONE,
+            recovered_errors: Default::default(),
         };
         visitor.root = self.read_node(&mut visitor)?;
         visitor.current_node = visitor.root;
@@ -0,0 +1,521 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{
+    pool::{Handle, Pool},
+    visitor::{
+        blackboard::Blackboard,
+        error::VisitError,
+        field::{Field, FieldKind},
+        reader::{ascii::ByteReader, Reader},
+        VisitResult, Visitor, VisitorFlags, VisitorNode,
+    },
+};
+use base64::Engine;
+use nalgebra::{
+    Complex, Quaternion, SMatrix, SVector, Scalar, UnitComplex, UnitQuaternion, Vector2, Vector4,
+};
+use std::{io::Read, str::FromStr};
+use uuid::Uuid;
+
+macro_rules! err {
+    ($($arg:tt)*) => {{
+        let str = format!($($arg)*);
+        Err(VisitError::User(str))
+    }};
+}
+
+impl ByteReader<'_> {
+    fn expect(&mut self, expected: u8) -> VisitResult {
+        self.skip_ws()?;
+        let ch = self.next()?;
+        if ch == expected {
+            Ok(())
+        } else {
+            err!("expected '{}', got '{}'", expected as char, ch as char)
+        }
+    }
+
+    /// Skips the comma after an element of an object or an array. Returns `false` if there's no
+    /// more elements.
+    fn skip_separator(&mut self, end: u8) -> Result<bool, VisitError> {
+        self.skip_ws()?;
+        let ch = self.next()?;
+        if ch == b',' {
+            Ok(true)
+        } else if ch == end {
+            Ok(false)
+        } else {
+            err!("expected ',' or '{}', got '{}'", end as char, ch as char)
+        }
+    }
+
+    fn read_hex_escape(&mut self) -> Result<u32, VisitError> {
+        let mut code = 0;
+        for _ in 0..4 {
+            let digit = (self.next()? as char)
+                .to_digit(16)
+                .ok_or_else(|| VisitError::User("invalid unicode escape".to_string()))?;
+            code = code * 16 + digit;
+        }
+        Ok(code)
+    }
+
+    fn read_json_string(&mut self) -> Result<String, VisitError> {
+        self.expect(b'"')?;
+        let mut bytes = Vec::new();
+        loop {
+            let ch = self.next()?;
+            match ch {
+                b'"' => break,
+                b'\\' => match self.next()? {
+                    b'"' => bytes.push(b'"'),
+                    b'\\' => bytes.push(b'\\'),
+                    b'/' => bytes.push(b'/'),
+                    b'b' => bytes.push(0x08),
+                    b'f' => bytes.push(0x0c),
+                    b'n' => bytes.push(b'\n'),
+                    b'r' => bytes.push(b'\r'),
+                    b't' => bytes.push(b'\t'),
+                    b'u' => {
+                        let mut code = self.read_hex_escape()?;
+                        if (0xD800..0xDC00).contains(&code) {
+                            // Surrogate pair.
+                            self.expect(b'\\')?;
+                            self.expect(b'u')?;
+                            let low = self.read_hex_escape()?;
+                            code = 0x10000 + ((code - 0xD800) << 10) + (low.wrapping_sub(0xDC00));
+                        }
+                        let Some(ch) = char::from_u32(code) else {
+                            return err!("invalid unicode escape {code:x}");
+                        };
+                        let mut buf = [0; 4];
+                        bytes.extend_from_slice(ch.encode_utf8(&mut buf).as_bytes());
+                    }
+                    other => return err!("invalid escape sequence \\{}", other as char),
+                },
+                _ => bytes.push(ch),
+            }
+        }
+        Ok(String::from_utf8(bytes)?)
+    }
+
+    /// Reads a number, a boolean or a string as a string.
+    fn read_json_scalar(&mut self) -> Result<String, VisitError> {
+        self.skip_ws()?;
+        if self.peek()? == b'"' {
+            return self.read_json_string();
+        }
+        let mut bytes = Vec::new();
+        loop {
+            let ch = self.peek()?;
+            if ch == b',' || ch == b']' || ch == b'}' || ch.is_ascii_whitespace() {
+                break;
+            }
+            bytes.push(ch);
+            self.next()?;
+        }
+        Ok(String::from_utf8(bytes)?)
+    }
+
+    fn read_json_num<T, E>(&mut self) -> Result<T, VisitError>
+    where
+        T: FromStr<Err = E>,
+        VisitError: From<E>,
+    {
+        Ok(self.read_json_scalar()?.parse::<T>()?)
+    }
+
+    fn read_json_array<T, E, const N: usize>(&mut self) -> Result<[T; N], VisitError>
+    where
+        T: Scalar + Default + Copy + FromStr<Err = E>,
+        VisitError: From<E>,
+    {
+        let mut array = [T::default(); N];
+        self.expect(b'[')?;
+        for (i, item) in array.iter_mut().enumerate() {
+            *item = self.read_json_num()?;
+            if i + 1 < N {
+                self.expect(b',')?;
+            }
+        }
+        self.expect(b']')?;
+        Ok(array)
+    }
+
+    fn read_json_vec_n<T, E, const N: usize>(&mut self) -> Result<SVector<T, N>, VisitError>
+    where
+        T: Scalar + Default + Copy + FromStr<Err = E>,
+        VisitError: From<E>,
+    {
+        Ok(SVector::from(self.read_json_array::<T, E, N>()?))
+    }
+
+    fn read_json_mat_n<T, E, const N: usize, const NN: usize>(
+        &mut self,
+    ) -> Result<SMatrix<T, N, N>, VisitError>
+    where
+        T: Scalar + Default + Copy + FromStr<Err = E>,
+        VisitError: From<E>,
+    {
+        // Matrices are stored in column-major order.
+        Ok(SMatrix::from_column_slice(
+            &self.read_json_array::<T, E, NN>()?,
+        ))
+    }
+
+    /// Calls the given function for every key of a JSON object. The function must read the value
+    /// of the key.
+    fn read_json_object<F>(&mut self, mut func: F) -> VisitResult
+    where
+        F: FnMut(&mut Self, String) -> VisitResult,
+    {
+        self.expect(b'{')?;
+        self.skip_ws()?;
+        if self.peek()? == b'}' {
+            self.next()?;
+            return Ok(());
+        }
+        loop {
+            let key = self.read_json_string()?;
+            self.expect(b':')?;
+            func(self, key)?;
+            if !self.skip_separator(b'}')? {
+                return Ok(());
+            }
+        }
+    }
+}
+
+fn read_field_kind(src: &mut ByteReader) -> Result<FieldKind, VisitError> {
+    let mut kind = None;
+    src.read_json_object(|src, ty| {
+        if kind.is_some() {
+            return err!("a field must have exactly one type, got {}", ty);
+        }
+        kind = Some(match ty.as_ref() {
+            "bool" => {
+                let value = src.read_json_scalar()?;
+                if value == "true" {
+                    FieldKind::Bool(true)
+                } else if value == "false" {
+                    FieldKind::Bool(false)
+                } else {
+                    return err!("bool must be either true or false, got {}", value);
+                }
+            }
+            "u8" => FieldKind::U8(src.read_json_num()?),
+            "i8" => FieldKind::I8(src.read_json_num()?),
+            "u16" => FieldKind::U16(src.read_json_num()?),
+            "i16" => FieldKind::I16(src.read_json_num()?),
+            "u32" => FieldKind::U32(src.read_json_num()?),
+            "i32" => FieldKind::I32(src.read_json_num()?),
+            "u64" => FieldKind::U64(src.read_json_num()?),
+            "i64" => FieldKind::I64(src.read_json_num()?),
+            "f32" => FieldKind::F32(src.read_json_num()?),
+            "f64" => FieldKind::F64(src.read_json_num()?),
+            "vec2f32" => FieldKind::Vector2F32(src.read_json_vec_n()?),
+            "vec3f32" => FieldKind::Vector3F32(src.read_json_vec_n()?),
+            "vec4f32" => FieldKind::Vector4F32(src.read_json_vec_n()?),
+            "vec2f64" => FieldKind::Vector2F64(src.read_json_vec_n()?),
+            "vec3f64" => FieldKind::Vector3F64(src.read_json_vec_n()?),
+            "vec4f64" => FieldKind::Vector4F64(src.read_json_vec_n()?),
+            "vec2i8" => FieldKind::Vector2I8(src.read_json_vec_n()?),
+            "vec3i8" => FieldKind::Vector3I8(src.read_json_vec_n()?),
+            "vec4i8" => FieldKind::Vector4I8(src.read_json_vec_n()?),
+            "vec2u8" => FieldKind::Vector2U8(src.read_json_vec_n()?),
+            "vec3u8" => FieldKind::Vector3U8(src.read_json_vec_n()?),
+            "vec4u8" => FieldKind::Vector4U8(src.read_json_vec_n()?),
+            "vec2i16" => FieldKind::Vector2I16(src.read_json_vec_n()?),
+            "vec3i16" => FieldKind::Vector3I16(src.read_json_vec_n()?),
+            "vec4i16" => FieldKind::Vector4I16(src.read_json_vec_n()?),
+            "vec2u16" => FieldKind::Vector2U16(src.read_json_vec_n()?),
+            "vec3u16" => FieldKind::Vector3U16(src.read_json_vec_n()?),
+            "vec4u16" => FieldKind::Vector4U16(src.read_json_vec_n()?),
+            "vec2i32" => FieldKind::Vector2I32(src.read_json_vec_n()?),
+            "vec3i32" => FieldKind::Vector3I32(src.read_json_vec_n()?),
+            "vec4i32" => FieldKind::Vector4I32(src.read_json_vec_n()?),
+            "vec2u32" => FieldKind::Vector2U32(src.read_json_vec_n()?),
+            "vec3u32" => FieldKind::Vector3U32(src.read_json_vec_n()?),
+            "vec4u32" => FieldKind::Vector4U32(src.read_json_vec_n()?),
+            "vec2i64" => FieldKind::Vector2I64(src.read_json_vec_n()?),
+            "vec3i64" => FieldKind::Vector3I64(src.read_json_vec_n()?),
+            "vec4i64" => FieldKind::Vector4I64(src.read_json_vec_n()?),
+            "vec2u64" => FieldKind::Vector2U64(src.read_json_vec_n()?),
+            "vec3u64" => FieldKind::Vector3U64(src.read_json_vec_n()?),
+            "vec4u64" => FieldKind::Vector4U64(src.read_json_vec_n()?),
+            "quat" => {
+                let v: Vector4<f32> = src.read_json_vec_n()?;
+                FieldKind::UnitQuaternion(UnitQuaternion::new_normalize(Quaternion::new(
+                    v.w, v.x, v.y, v.z,
+                )))
+            }
+            "mat4" => FieldKind::Matrix4(src.read_json_mat_n::<_, _, 4, 16>()?),
+            "data" => {
+                let base64 = src.read_json_string()?;
+                FieldKind::BinaryBlob(base64::engine::general_purpose::STANDARD.decode(base64)?)
+            }
+            "mat3" => FieldKind::Matrix3(src.read_json_mat_n::<_, _, 3, 9>()?),
+            "uuid" => FieldKind::Uuid(Uuid::from_str(&src.read_json_string()?)?),
+            "complex" => {
+                let v: Vector2<f32> = src.read_json_vec_n()?;
+                FieldKind::UnitComplex(UnitComplex::from_complex(Complex::new(v.x, v.y)))
+            }
+            "podarray" => {
+                src.expect(b'[')?;
+                let type_id: u8 = src.read_json_num()?;
+                src.expect(b',')?;
+                let element_size: u32 = src.read_json_num()?;
+                src.expect(b',')?;
+                let base64 = src.read_json_string()?;
+                src.expect(b']')?;
+                FieldKind::PodArray {
+                    type_id,
+                    element_size,
+                    bytes: base64::engine::general_purpose::STANDARD.decode(base64)?,
+                }
+            }
+            "mat2" => FieldKind::Matrix2(src.read_json_mat_n::<_, _, 2, 4>()?),
+            "str" => FieldKind::String(src.read_json_string()?),
+            _ => return err!("unexpected field type: {}", ty),
+        });
+        Ok(())
+    })?;
+    match kind {
+        Some(kind) => Ok(kind),
+        None => err!("a field must have a type"),
+    }
+}
+
+fn read_node(
+    src: &mut ByteReader,
+    name: String,
+    visitor: &mut Visitor,
+) -> Result<Handle<VisitorNode>, VisitError> {
+    let mut node = VisitorNode {
+        name,
+        ..VisitorNode::default()
+    };
+
+    let mut children = Vec::new();
+
+    src.read_json_object(|src, key| match key.as_str() {
+        "fields" => src.read_json_object(|src, name| {
+            let kind = read_field_kind(src)?;
+            node.fields.push(Field { name, kind });
+            Ok(())
+        }),
+        "children" => src.read_json_object(|src, name| {
+            children.push(read_node(src, name, visitor)?);
+            Ok(())
+        }),
+        _ => err!("unexpected key {} in node {}", key, node.name),
+    })?;
+
+    node.children.clone_from(&children);
+
+    let handle = visitor.nodes.spawn(node);
+    for child_handle in children.iter() {
+        let child = visitor.nodes.borrow_mut(*child_handle);
+        child.parent = handle;
+    }
+
+    Ok(handle)
+}
+
+/// Reads the data written by [`crate::visitor::writer::json::JsonWriter`]. The reader accepts any
+/// valid JSON document of the same structure, so the data could be edited or reformatted by
+/// external tools.
+pub struct JsonReader<'a> {
+    src: ByteReader<'a>,
+}
+
+impl<'a> JsonReader<'a> {
+    pub fn new(src: &'a mut dyn Read) -> Self {
+        Self {
+            src: ByteReader::new(src),
+        }
+    }
+
+    /// Checks whether the given source starts with the header of the JSON format or not.
+    pub fn is_json(src: &mut dyn Read) -> bool {
+        let mut src = ByteReader::new(src);
+        src.expect(b'{').is_ok()
+            && src
+                .read_json_string()
+                .is_ok_and(|magic| magic == Visitor::MAGIC_JSON_CURRENT)
+    }
+}
+
+impl Reader for JsonReader<'_> {
+    fn read_field(&mut self) -> Result<Field, VisitError> {
+        let name = self.src.read_json_string()?;
+        self.src.expect(b':')?;
+        let kind = read_field_kind(&mut self.src)?;
+        Ok(Field { name, kind })
+    }
+
+    fn read_node(&mut self, visitor: &mut Visitor) -> Result<Handle<VisitorNode>, VisitError> {
+        let name = self.src.read_json_string()?;
+        self.src.expect(b':')?;
+        read_node(&mut self.src, name, visitor)
+    }
+
+    fn read(&mut self) -> Result<Visitor, VisitError> {
+        let src = &mut self.src;
+
+        src.expect(b'{')?;
+        if src.read_json_string()? != Visitor::MAGIC_JSON_CURRENT {
+            return Err(VisitError::NotSupportedFormat);
+        }
+        src.expect(b':')?;
+        let version = src.read_json_num::<u32, _>()?;
+        src.expect(b',')?;
+
+        let mut visitor = Visitor {
+            nodes: Pool::new(),
+            unique_id_counter: 1,
+            type_name_map: Default::default(),
+            rc_map: Default::default(),
+            arc_map: Default::default(),
+            reading: true,
+            current_node: Handle::NONE,
+            root: Handle::NONE,
+            version,
+            blackboard: Blackboard::new(),
+            flags: VisitorFlags::NONE,
+            recovered_errors: Default::default(),
+        };
+        visitor.root = self.read_node(&mut visitor)?;
+        visitor.current_node = visitor.root;
+        self.src.expect(b'}')?;
+        Ok(visitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::visitor;
+    use crate::visitor::{
+        field::{Field, FieldKind},
+        reader::{json::JsonReader, Reader},
+    };
+    use nalgebra::{Matrix3, Quaternion, UnitQuaternion, Vector3};
+    use std::io::Cursor;
+    use uuid::uuid;
+
+    #[test]
+    fn test_parse_field() {
+        let input = r#"
+        "U8": {"u8": 123}
+        "I64": { "i64" : -123 }
+        "F32": {"f32": 123.321}
+        "Nan": {"f64": "NaN"}
+        "Vec3F32": {"vec3f32": [1.1, 2.2, 3.3]}
+        "Quat": {"quat": [1.1, 2.2, 3.3, 4.4]}
+        "Mat3": {"mat3": [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]}
+        "Data": {"data": "SGVsbG8h"}
+        "Uuid": {"uuid": "ecdaa0b6-1867-424f-b86d-86818b9514c6"}
+        "PodArray": {"podarray": [0, 1, "SGVsbG8h"]}
+        "Str": {"str": "\"quoted\"\né😀"}
+        "Bool": {"bool": true}
+        "#;
+        let mut cursor = Cursor::new(input);
+        let mut reader = JsonReader::new(&mut cursor);
+
+        macro_rules! read_next {
+            ($name:expr => $value:expr) => {
+                assert_eq!(reader.read_field().unwrap(), Field::new($name, $value));
+            };
+        }
+
+        read_next!("U8" => FieldKind::U8(123));
+        read_next!("I64" => FieldKind::I64(-123));
+        read_next!("F32" => FieldKind::F32(123.321));
+        let nan = reader.read_field().unwrap();
+        assert!(matches!(nan.kind, FieldKind::F64(value) if value.is_nan()));
+        read_next!("Vec3F32" => FieldKind::Vector3F32(Vector3::new(1.1, 2.2, 3.3)));
+        read_next!("Quat" => FieldKind::UnitQuaternion(
+            // w, i, j, k
+            UnitQuaternion::new_normalize(Quaternion::new(4.4, 1.1, 2.2, 3.3)))
+        );
+        read_next!("Mat3" => FieldKind::Matrix3(
+            // column major constructor
+            Matrix3::new(1.0, 4.0, 7.0,
+                         2.0, 5.0, 8.0,
+                         3.0, 6.0, 9.0)
+        ));
+        read_next!("Data" => FieldKind::BinaryBlob(b"Hello!".to_vec()));
+        read_next!("Uuid" => FieldKind::Uuid(uuid!("ecdaa0b6-1867-424f-b86d-86818b9514c6")));
+        read_next!("PodArray" => FieldKind::PodArray {
+            type_id: 0,
+            element_size: 1,
+            bytes: b"Hello!".to_vec()
+        });
+        read_next!("Str" => FieldKind::String("\"quoted\"\n\u{e9}\u{1f600}".to_string()));
+        read_next!("Bool" => FieldKind::Bool(true));
+    }
+
+    #[test]
+    fn test_parse_visitor() {
+        // Reformatted by an external tool.
+        let input = format!(
+            r#"  {{ "FTJS" : {},
+            "SomeNode": {{
+                "fields": {{ "U8": {{"u8": 123}}, "I8": {{"i8": -123}} }},
+                "children": {{
+                    "NestedNode1": {{ "fields": {{ "F32": {{"f32": 123.1}} }} }},
+                    "NestedNode2": {{}}
+                }}
+            }}
+        }}"#,
+            visitor::CURRENT_VERSION
+        );
+        assert!(JsonReader::is_json(&mut Cursor::new(input.as_bytes())));
+
+        let mut cursor = Cursor::new(input);
+        let mut reader = JsonReader::new(&mut cursor);
+
+        let visitor = reader.read().unwrap();
+
+        assert_eq!(visitor.version, visitor::CURRENT_VERSION);
+
+        let some_node = visitor.find_node("SomeNode").unwrap();
+        assert_eq!(
+            some_node.fields,
+            vec![
+                Field::new("U8", FieldKind::U8(123)),
+                Field::new("I8", FieldKind::I8(-123))
+            ]
+        );
+        assert_eq!(some_node.children.len(), 2);
+
+        let nested_node_1 = visitor.find_node("NestedNode1").unwrap();
+        assert_eq!(
+            nested_node_1.fields,
+            vec![Field::new("F32", FieldKind::F32(123.1))]
+        );
+        assert_eq!(nested_node_1.parent, visitor.root);
+
+        let nested_node_2 = visitor.find_node("NestedNode2").unwrap();
+        assert!(nested_node_2.fields.is_empty());
+        assert!(nested_node_2.children.is_empty());
+    }
+}
@@ -20,6 +20,7 @@
 
 pub mod ascii;
 pub mod binary;
+pub mod json;
 
 use crate::{
     pool::Handle,
@@ -0,0 +1,437 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::visitor::{
+    field::{Field, FieldKind},
+    writer::Writer,
+    VisitResult, Visitor, VisitorNode,
+};
+use base64::Engine;
+use std::{fmt::Display, io::Write};
+
+/// A number that can be written to JSON. Non-finite floats have no JSON representation, so they
+/// are written as strings (`"NaN"`, `"inf"`, `"-inf"`) that Rust is able to parse back.
+trait JsonNumber: Display {
+    fn is_finite(&self) -> bool {
+        true
+    }
+}
+
+macro_rules! impl_json_number {
+    ($($ty:ty),*) => {
+        $(impl JsonNumber for $ty {})*
+    };
+}
+
+impl_json_number!(u8, i8, u16, i16, u32, i32, u64, i64);
+
+impl JsonNumber for f32 {
+    fn is_finite(&self) -> bool {
+        f32::is_finite(*self)
+    }
+}
+
+impl JsonNumber for f64 {
+    fn is_finite(&self) -> bool {
+        f64::is_finite(*self)
+    }
+}
+
+fn write_number(dest: &mut dyn Write, number: &impl JsonNumber) -> VisitResult {
+    if number.is_finite() {
+        write!(dest, "{number}")?;
+    } else {
+        write!(dest, "\"{number}\"")?;
+    }
+    Ok(())
+}
+
+fn write_array<'a, T: JsonNumber + 'a>(
+    dest: &mut dyn Write,
+    iter: impl Iterator<Item = &'a T>,
+) -> VisitResult {
+    write!(dest, "[")?;
+    for (i, number) in iter.enumerate() {
+        if i != 0 {
+            write!(dest, ", ")?;
+        }
+        write_number(dest, number)?;
+    }
+    write!(dest, "]")?;
+    Ok(())
+}
+
+pub(crate) fn write_string(dest: &mut dyn Write, str: &str) -> VisitResult {
+    write!(dest, "\"")?;
+    for ch in str.chars() {
+        match ch {
+            '"' => write!(dest, "\\\"")?,
+            '\\' => write!(dest, "\\\\")?,
+            '\n' => write!(dest, "\\n")?,
+            '\r' => write!(dest, "\\r")?,
+            '\t' => write!(dest, "\\t")?,
+            // The rest of the control characters must be escaped as well.
+            ch if (ch as u32) < 0x20 => write!(dest, "\\u{:04x}", ch as u32)?,
+            ch => write!(dest, "{ch}")?,
+        }
+    }
+    write!(dest, "\"")?;
+    Ok(())
+}
+
+fn align(count: usize, dest: &mut dyn Write) -> VisitResult {
+    for _ in 0..count {
+        write!(dest, "\t")?;
+    }
+    Ok(())
+}
+
+/// Writes the data of a visitor as a valid JSON document. Every node is an object with optional
+/// `fields` and `children` objects, every field is a single-entry object that maps the type of the
+/// field to its value, for example `"Position": {"vec3f32": [1, 2, 3]}`. Every field is written on
+/// its own line, so the changes can be merged by a version control system and the output could be
+/// processed by any external tool that understands JSON.
+#[derive(Default)]
+pub struct JsonWriter {}
+
+impl Writer for JsonWriter {
+    fn write_field(&self, field: &Field, dest: &mut dyn Write) -> VisitResult {
+        write_string(dest, &field.name)?;
+        write!(dest, ": {{")?;
+        match field.kind {
+            FieldKind::Bool(data) => write!(dest, "\"bool\": {data}")?,
+            FieldKind::U8(data) => write!(dest, "\"u8\": {data}")?,
+            FieldKind::I8(data) => write!(dest, "\"i8\": {data}")?,
+            FieldKind::U16(data) => write!(dest, "\"u16\": {data}")?,
+            FieldKind::I16(data) => write!(dest, "\"i16\": {data}")?,
+            FieldKind::U32(data) => write!(dest, "\"u32\": {data}")?,
+            FieldKind::I32(data) => write!(dest, "\"i32\": {data}")?,
+            FieldKind::U64(data) => write!(dest, "\"u64\": {data}")?,
+            FieldKind::I64(data) => write!(dest, "\"i64\": {data}")?,
+            FieldKind::F32(data) => {
+                write!(dest, "\"f32\": ")?;
+                write_number(dest, &data)?;
+            }
+            FieldKind::F64(data) => {
+                write!(dest, "\"f64\": ")?;
+                write_number(dest, &data)?;
+            }
+            FieldKind::Vector2F32(data) => {
+                write!(dest, "\"vec2f32\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector3F32(data) => {
+                write!(dest, "\"vec3f32\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector4F32(data) => {
+                write!(dest, "\"vec4f32\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector2F64(data) => {
+                write!(dest, "\"vec2f64\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector3F64(data) => {
+                write!(dest, "\"vec3f64\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector4F64(data) => {
+                write!(dest, "\"vec4f64\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector2I8(data) => {
+                write!(dest, "\"vec2i8\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector3I8(data) => {
+                write!(dest, "\"vec3i8\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector4I8(data) => {
+                write!(dest, "\"vec4i8\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector2U8(data) => {
+                write!(dest, "\"vec2u8\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector3U8(data) => {
+                write!(dest, "\"vec3u8\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector4U8(data) => {
+                write!(dest, "\"vec4u8\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector2I16(data) => {
+                write!(dest, "\"vec2i16\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector3I16(data) => {
+                write!(dest, "\"vec3i16\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector4I16(data) => {
+                write!(dest, "\"vec4i16\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector2U16(data) => {
+                write!(dest, "\"vec2u16\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector3U16(data) => {
+                write!(dest, "\"vec3u16\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector4U16(data) => {
+                write!(dest, "\"vec4u16\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector2I32(data) => {
+                write!(dest, "\"vec2i32\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector3I32(data) => {
+                write!(dest, "\"vec3i32\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector4I32(data) => {
+                write!(dest, "\"vec4i32\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector2U32(data) => {
+                write!(dest, "\"vec2u32\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector3U32(data) => {
+                write!(dest, "\"vec3u32\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector4U32(data) => {
+                write!(dest, "\"vec4u32\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector2I64(data) => {
+                write!(dest, "\"vec2i64\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector3I64(data) => {
+                write!(dest, "\"vec3i64\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector4I64(data) => {
+                write!(dest, "\"vec4i64\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector2U64(data) => {
+                write!(dest, "\"vec2u64\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector3U64(data) => {
+                write!(dest, "\"vec3u64\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Vector4U64(data) => {
+                write!(dest, "\"vec4u64\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::UnitQuaternion(data) => {
+                write!(dest, "\"quat\": ")?;
+                write_array(dest, [data.i, data.j, data.k, data.w].iter())?;
+            }
+            FieldKind::Matrix4(data) => {
+                write!(dest, "\"mat4\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::BinaryBlob(ref data) => write!(
+                dest,
+                "\"data\": \"{}\"",
+                base64::engine::general_purpose::STANDARD.encode(data)
+            )?,
+            FieldKind::Matrix3(data) => {
+                write!(dest, "\"mat3\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::Uuid(uuid) => write!(dest, "\"uuid\": \"{uuid}\"")?,
+            FieldKind::UnitComplex(data) => {
+                write!(dest, "\"complex\": ")?;
+                write_array(dest, [data.re, data.im].iter())?;
+            }
+            FieldKind::PodArray {
+                type_id,
+                element_size,
+                ref bytes,
+            } => write!(
+                dest,
+                "\"podarray\": [{type_id}, {element_size}, \"{}\"]",
+                base64::engine::general_purpose::STANDARD.encode(bytes)
+            )?,
+            FieldKind::Matrix2(data) => {
+                write!(dest, "\"mat2\": ")?;
+                write_array(dest, data.iter())?;
+            }
+            FieldKind::String(ref str) => {
+                write!(dest, "\"str\": ")?;
+                write_string(dest, str)?;
+            }
+        }
+        write!(dest, "}}")?;
+
+        Ok(())
+    }
+
+    fn write_node(
+        &self,
+        visitor: &Visitor,
+        node: &VisitorNode,
+        hierarchy_level: usize,
+        dest: &mut dyn Write,
+    ) -> VisitResult {
+        write_string(dest, &node.name)?;
+
+        if node.fields.is_empty() && node.children.is_empty() {
+            write!(dest, ": {{}}")?;
+            return Ok(());
+        }
+
+        writeln!(dest, ": {{")?;
+
+        if !node.fields.is_empty() {
+            align(hierarchy_level + 1, dest)?;
+            writeln!(dest, "\"fields\": {{")?;
+            for (i, field) in node.fields.iter().enumerate() {
+                if i != 0 {
+                    writeln!(dest, ",")?;
+                }
+                align(hierarchy_level + 2, dest)?;
+                self.write_field(field, dest)?;
+            }
+            writeln!(dest)?;
+            align(hierarchy_level + 1, dest)?;
+            write!(dest, "}}")?;
+        }
+
+        if !node.children.is_empty() {
+            if !node.fields.is_empty() {
+                writeln!(dest, ",")?;
+            }
+            align(hierarchy_level + 1, dest)?;
+            writeln!(dest, "\"children\": {{")?;
+            for (i, child_handle) in node.children.iter().enumerate() {
+                if i != 0 {
+                    writeln!(dest, ",")?;
+                }
+                align(hierarchy_level + 2, dest)?;
+                let child = visitor.nodes.borrow(*child_handle);
+                self.write_node(visitor, child, hierarchy_level + 2, dest)?;
+            }
+            writeln!(dest)?;
+            align(hierarchy_level + 1, dest)?;
+            write!(dest, "}}")?;
+        }
+
+        writeln!(dest)?;
+        align(hierarchy_level, dest)?;
+        write!(dest, "}}")?;
+
+        Ok(())
+    }
+
+    fn write(&self, visitor: &Visitor, dest: &mut dyn Write) -> VisitResult {
+        writeln!(dest, "{{")?;
+        writeln!(
+            dest,
+            "\t\"{}\": {},",
+            Visitor::MAGIC_JSON_CURRENT,
+            visitor.version
+        )?;
+        align(1, dest)?;
+        self.write_node(visitor, &visitor.nodes[visitor.root], 1, dest)?;
+        writeln!(dest)?;
+        writeln!(dest, "}}")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::visitor::prelude::*;
+    use nalgebra::Vector3;
+    use std::io::Cursor;
+
+    #[derive(Visit)]
+    struct MyOtherObject {
+        data: f32,
+        position: Vector3<f32>,
+    }
+
+    #[derive(Visit)]
+    struct MyObject {
+        foo: String,
+        object: MyOtherObject,
+        bar: u32,
+    }
+
+    #[test]
+    fn test_write_json() {
+        let mut object = MyObject {
+            foo: "Some \"String\"".to_string(),
+            bar: 123,
+            object: MyOtherObject {
+                data: f32::NAN,
+                position: Vector3::new(1.0, 2.5, -3.0),
+            },
+        };
+
+        let mut visitor = Visitor::new();
+        object.visit("MyObject", &mut visitor).unwrap();
+
+        let mut cursor = Cursor::<Vec<u8>>::default();
+        visitor.save_json_to_memory(&mut cursor).unwrap();
+
+        let expected = format!(
+            r#"{{
+	"FTJS": {},
+	"__ROOT__": {{
+		"children": {{
+			"MyObject": {{
+				"fields": {{
+					"Foo": {{"str": "Some \"String\""}},
+					"Bar": {{"u32": 123}}
+				}},
+				"children": {{
+					"Object": {{
+						"fields": {{
+							"Data": {{"f32": "NaN"}},
+							"Position": {{"vec3f32": [1, 2.5, -3]}}
+						}}
+					}}
+				}}
+			}}
+		}}
+	}}
+}}
+"#,
+            crate::visitor::CURRENT_VERSION
+        );
+        assert_eq!(String::from_utf8(cursor.into_inner()).unwrap(), expected);
+    }
+}
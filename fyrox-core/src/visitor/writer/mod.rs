@@ -20,6 +20,7 @@
 
 pub mod ascii;
 pub mod binary;
+pub mod json;
 
 use crate::visitor::{field::Field, VisitResult, Visitor, VisitorNode};
 use std::io::Write;
@@ -50,6 +50,12 @@ pub struct ServerCapabilities {
     /// The maximum, absolute value of the texture level-of-detail bias. The value must be at least
     /// 2.0.
     pub max_lod_bias: f32,
+    /// Name of the company responsible for the graphics API implementation.
+    pub vendor: String,
+    /// Name of the device (usually a GPU model) that is used by the graphics server.
+    pub renderer: String,
+    /// Version of the graphics API that is used by the graphics server.
+    pub version: String,
 }
 
 /// Contains information about used memory per each category of GPU resource. This is not precise
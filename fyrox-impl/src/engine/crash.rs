@@ -0,0 +1,428 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Crash reporter, that writes a diagnostic bundle to disk when the game panics. See
+//! [`CrashReporter`] docs for more info.
+
+use crate::{
+    asset::io::{PackedArchiveWriter, PackedCompression, PackedResourceIo},
+    core::{
+        instant::Instant,
+        io::FileError,
+        log::{sink::RingBufferSink, Log, LogSinkId},
+        parking_lot::Mutex,
+        pool::Handle,
+    },
+    engine::{Engine, GraphicsContext},
+    plugin::PluginContainer,
+    scene::Scene,
+};
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    io::Cursor,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Options of the crash reporter.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CrashReporterOptions {
+    /// A folder, where the bundles will be written to. Default is [`Self::DEFAULT_DIRECTORY`].
+    pub directory: PathBuf,
+    /// Amount of the last log messages, that will be put in a bundle. Default is 500.
+    pub log_lines: usize,
+}
+
+impl CrashReporterOptions {
+    /// Default folder of crash bundles, relative to the working directory of the game.
+    pub const DEFAULT_DIRECTORY: &'static str = "crashes";
+}
+
+impl Default for CrashReporterOptions {
+    fn default() -> Self {
+        Self {
+            directory: PathBuf::from(Self::DEFAULT_DIRECTORY),
+            log_lines: 500,
+        }
+    }
+}
+
+struct CrashContext {
+    options: CrashReporterOptions,
+    log: RingBufferSink,
+    log_sink: Option<LogSinkId>,
+    renderer: String,
+    settings: String,
+    scenes: Vec<(Handle<Scene>, PathBuf)>,
+    plugins: Vec<String>,
+    entries: BTreeMap<String, String>,
+    last_update: Option<Instant>,
+}
+
+static CONTEXT: Mutex<Option<CrashContext>> = Mutex::new(None);
+
+const UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+
+impl CrashContext {
+    fn new(options: CrashReporterOptions) -> Self {
+        Self {
+            log: RingBufferSink::new(options.log_lines),
+            options,
+            log_sink: None,
+            renderer: Default::default(),
+            settings: Default::default(),
+            scenes: Default::default(),
+            plugins: Default::default(),
+            entries: Default::default(),
+            last_update: None,
+        }
+    }
+
+    fn update(&mut self, engine: &Engine) {
+        self.scenes
+            .retain(|(handle, _)| engine.scenes.is_valid_handle(*handle));
+
+        self.plugins = engine
+            .plugins()
+            .iter()
+            .map(|plugin| match plugin {
+                PluginContainer::Static(plugin) => plugin.type_name().to_string(),
+                PluginContainer::Dynamic(plugin) => {
+                    format!("{} (dynamic)", plugin.display_name())
+                }
+            })
+            .collect();
+
+        let (params, renderer) = match engine.graphics_context {
+            GraphicsContext::Initialized(ref ctx) => (&ctx.params, Some(&ctx.renderer)),
+            GraphicsContext::Uninitialized(ref params) => (params, None),
+        };
+
+        let mut settings = format!(
+            "V-Sync: {}\nMSAA Samples: {:?}\nNamed Objects: {}\n",
+            params.vsync, params.msaa_sample_count, params.named_objects
+        );
+        match renderer {
+            Some(renderer) => {
+                let server = renderer.graphics_server();
+                self.renderer = format!(
+                    "{:#?}\n\nMemory Usage:\n{}",
+                    server.capabilities(),
+                    server.memory_usage()
+                );
+                let _ = writeln!(
+                    settings,
+                    "Quality Settings: {:#?}",
+                    renderer.get_quality_settings()
+                );
+            }
+            None => self.renderer = "Graphics context is not initialized.".to_string(),
+        }
+        self.settings = settings;
+    }
+
+    fn write_bundle(&self, panic_message: &str, backtrace: &str) -> Result<PathBuf, FileError> {
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+
+        let mut panic = format!("{panic_message}\n\nBacktrace:\n{backtrace}");
+        if let Some(name) = std::thread::current().name() {
+            panic.insert_str(0, &format!("Thread: {name}\n"));
+        }
+
+        // A panic in a log sink could leave the log buffer locked by this thread.
+        let log = if Log::is_writing_on_current_thread() {
+            "The log is unavailable, the panic has happened while writing a log message.\n"
+                .to_string()
+        } else {
+            self.log
+                .messages()
+                .iter()
+                .fold(String::new(), |mut log, message| {
+                    let _ = writeln!(log, "{message}");
+                    log
+                })
+        };
+
+        let scenes = self
+            .scenes
+            .iter()
+            .fold(String::new(), |mut scenes, (_, path)| {
+                let _ = writeln!(scenes, "{}", path.display());
+                scenes
+            });
+
+        let system = format!(
+            "Engine Version: {}\nOS: {}\nArchitecture: {}\nTime: {} s since Unix epoch\n",
+            env!("CARGO_PKG_VERSION"),
+            std::env::consts::OS,
+            std::env::consts::ARCH,
+            time.as_secs()
+        );
+
+        let mut writer = PackedArchiveWriter::new(Cursor::new(Vec::new()))?;
+        for (name, contents) in [
+            ("panic.txt", panic.as_str()),
+            ("system.txt", &system),
+            ("log.txt", &log),
+            ("renderer.txt", &self.renderer),
+            ("scenes.txt", &scenes),
+            ("plugins.txt", &self.plugins.join("\n")),
+            ("settings.txt", &self.settings),
+        ]
+        .into_iter()
+        .chain(
+            self.entries
+                .iter()
+                .map(|(name, contents)| (name.as_str(), contents.as_str())),
+        ) {
+            writer.add_file(
+                Path::new(name),
+                contents.as_bytes(),
+                PackedCompression::Deflate,
+            )?;
+        }
+        let bytes = writer.finish()?.into_inner();
+
+        std::fs::create_dir_all(&self.options.directory)?;
+        let mut path = self.options.directory.join(format!(
+            "crash-{}.{}",
+            time.as_millis(),
+            CrashReporter::EXTENSION
+        ));
+        let mut index = 1;
+        while path.exists() {
+            path = self.options.directory.join(format!(
+                "crash-{}-{index}.{}",
+                time.as_millis(),
+                CrashReporter::EXTENSION
+            ));
+            index += 1;
+        }
+        std::fs::write(&path, bytes)?;
+        Ok(path)
+    }
+}
+
+/// Crash reporter writes a diagnostic bundle to disk when the game panics. The bundle is a single
+/// archive (in the same format as [`PackedResourceIo`] archives), that contains the panic message
+/// with a backtrace, the last log messages, renderer and graphics server info, paths of the loaded
+/// scenes, the plugin list and engine settings. The bundle is written to disk only, the player
+/// should send it to the developers manually.
+///
+/// The reporter is usually installed by the [`crate::engine::executor::Executor`], see its
+/// `set_crash_reporter` method. If you're using a custom game loop, call [`Self::install`] once and
+/// [`Self::update`] every frame.
+pub struct CrashReporter;
+
+impl CrashReporter {
+    /// Extension of crash bundles.
+    pub const EXTENSION: &'static str = "crash";
+
+    /// Name of the folder (inside the crash bundle folder), where the bundles are moved by
+    /// [`Self::acknowledge`].
+    pub const SEEN_DIRECTORY: &'static str = "seen";
+
+    /// Installs the crash reporter. The previous panic hook is called after the bundle is written.
+    /// Installing the reporter again replaces its options.
+    pub fn install(options: CrashReporterOptions) {
+        let mut context = CrashContext::new(options);
+        context.log_sink = Some(Log::add_sink(context.log.clone()));
+
+        let previous = CONTEXT.lock().replace(context);
+        match previous {
+            Some(previous) => {
+                if let Some(sink) = previous.log_sink {
+                    Log::remove_sink(sink);
+                }
+            }
+            None => {
+                let previous_hook = std::panic::take_hook();
+                std::panic::set_hook(Box::new(move |info| {
+                    Self::on_panic(&info.to_string());
+                    previous_hook(info);
+                }));
+            }
+        }
+    }
+
+    /// Disables the crash reporter. The panic hook is left in place, but it does nothing.
+    pub fn uninstall() {
+        if let Some(sink) = CONTEXT.lock().take().and_then(|context| context.log_sink) {
+            Log::remove_sink(sink);
+        }
+    }
+
+    /// Returns `true` if the crash reporter is installed, `false` - otherwise.
+    pub fn is_installed() -> bool {
+        CONTEXT.lock().is_some()
+    }
+
+    /// Collects the state of the engine (renderer info, loaded scenes, plugins, settings), that will
+    /// be written to a bundle on panic. The state is collected at most once per second, so the method
+    /// could be called every frame.
+    pub fn update(engine: &Engine) {
+        let mut context = CONTEXT.lock();
+        if let Some(context) = context.as_mut() {
+            if context
+                .last_update
+                .is_some_and(|last_update| last_update.elapsed() < UPDATE_INTERVAL)
+            {
+                return;
+            }
+            context.last_update = Some(Instant::now());
+            context.update(engine);
+        }
+    }
+
+    /// Adds a custom file to the next bundles, it could be used to add some game-specific info
+    /// (current level, save slot, etc.). A file with the same name replaces the previous one.
+    pub fn set_entry(name: impl Into<String>, contents: impl Into<String>) {
+        if let Some(context) = CONTEXT.lock().as_mut() {
+            context.entries.insert(name.into(), contents.into());
+        }
+    }
+
+    pub(crate) fn register_scene(handle: Handle<Scene>, path: &Path) {
+        if let Some(context) = CONTEXT.lock().as_mut() {
+            context.scenes.push((handle, path.to_path_buf()));
+        }
+    }
+
+    fn on_panic(panic_message: &str) {
+        let backtrace = std::backtrace::Backtrace::force_capture();
+        // The panic could happen while the context is locked, do not wait for it forever.
+        let Some(context) = CONTEXT.try_lock_for(Duration::from_millis(250)) else {
+            eprintln!("Unable to write a crash report: the crash reporter is busy.");
+            return;
+        };
+        if let Some(context) = context.as_ref() {
+            match context.write_bundle(panic_message, &backtrace.to_string()) {
+                Ok(path) => eprintln!("Crash report was written to {}", path.display()),
+                Err(err) => eprintln!("Unable to write a crash report. Reason: {err:?}"),
+            }
+        }
+    }
+
+    /// Returns paths of the bundles in the given folder, that were not acknowledged yet, starting
+    /// from the oldest one.
+    pub fn pending_bundles(directory: &Path) -> Vec<PathBuf> {
+        let Ok(entries) = std::fs::read_dir(directory) else {
+            return Vec::new();
+        };
+        let mut bundles = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.is_file()
+                    && path
+                        .extension()
+                        .is_some_and(|extension| extension == Self::EXTENSION)
+            })
+            .collect::<Vec<_>>();
+        bundles.sort();
+        bundles
+    }
+
+    /// Moves the bundle to the [`Self::SEEN_DIRECTORY`] folder next to it, so it won't be returned by
+    /// [`Self::pending_bundles`] anymore. Returns the new path of the bundle.
+    pub fn acknowledge(bundle: &Path) -> Result<PathBuf, FileError> {
+        let file_name = bundle
+            .file_name()
+            .ok_or_else(|| FileError::Custom(format!("{bundle:?} is not a file")))?;
+        let seen = bundle
+            .parent()
+            .unwrap_or(Path::new("."))
+            .join(Self::SEEN_DIRECTORY);
+        std::fs::create_dir_all(&seen)?;
+        let destination = seen.join(file_name);
+        std::fs::rename(bundle, &destination)?;
+        Ok(destination)
+    }
+
+    /// Reads all the files of the bundle, starting from the panic message.
+    pub fn read_bundle(bundle: &Path) -> Result<Vec<(PathBuf, String)>, FileError> {
+        let archive = PackedResourceIo::open(bundle)?;
+        let mut files = archive
+            .entries()
+            .map(|(path, _)| {
+                let data = archive.read(path)?;
+                Ok((
+                    path.to_path_buf(),
+                    String::from_utf8_lossy(&data).into_owned(),
+                ))
+            })
+            .collect::<Result<Vec<_>, FileError>>()?;
+        files.sort_by_key(|(path, _)| path != Path::new("panic.txt"));
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_crash_bundle() {
+        let directory = std::env::temp_dir().join("fyrox_crash_bundle_test");
+        let _ = std::fs::remove_dir_all(&directory);
+
+        let mut context = CrashContext::new(CrashReporterOptions {
+            directory: directory.clone(),
+            log_lines: 2,
+        });
+        context
+            .scenes
+            .push((Handle::NONE, PathBuf::from("data/level.rgs")));
+        context
+            .entries
+            .insert("game.txt".to_string(), "Level: 3".to_string());
+
+        let bundle = context
+            .write_bundle("Out of bounds", "<backtrace>")
+            .unwrap();
+        assert_eq!(
+            CrashReporter::pending_bundles(&directory),
+            vec![bundle.clone()]
+        );
+
+        let files = CrashReporter::read_bundle(&bundle).unwrap();
+        assert_eq!(files[0].0, Path::new("panic.txt"));
+        assert!(files[0].1.contains("Out of bounds"));
+        assert!(files[0].1.contains("<backtrace>"));
+        let file = |name: &str| {
+            files
+                .iter()
+                .find(|(path, _)| path == Path::new(name))
+                .map(|(_, contents)| contents.as_str())
+        };
+        assert_eq!(file("scenes.txt"), Some("data/level.rgs\n"));
+        assert_eq!(file("game.txt"), Some("Level: 3"));
+        assert!(file("log.txt").is_some());
+
+        let seen = CrashReporter::acknowledge(&bundle).unwrap();
+        assert!(seen.exists());
+        assert!(CrashReporter::pending_bundles(&directory).is_empty());
+
+        let _ = std::fs::remove_dir_all(&directory);
+    }
+}
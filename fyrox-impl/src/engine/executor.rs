@@ -23,13 +23,16 @@
 use crate::engine::ApplicationLoopController;
 use crate::scene::Scene;
 use crate::{
-    asset::manager::ResourceManager,
+    asset::{derived::DerivedDataCache, manager::ResourceManager},
     core::{
         instant::Instant,
         log::{Log, MessageKind},
+        platform::TargetPlatform,
         task::TaskPool,
     },
     engine::{
+        crash::{CrashReporter, CrashReporterOptions},
+        replay::{InputRecorder, InputReplay},
         Engine, EngineInitParams, GraphicsContext, GraphicsContextParams, SerializationContext,
     },
     event::{Event, WindowEvent},
@@ -40,12 +43,13 @@ use crate::{
 };
 use clap::Parser;
 use fyrox_core::pool::Handle;
-use fyrox_resource::io::FsResourceIo;
+use fyrox_resource::io::{FsResourceIo, PackedResourceIo, ResourceIo};
 use fyrox_ui::constructor::new_widget_constructor_container;
 use std::cell::Cell;
 use std::time::Duration;
 use std::{
     ops::{Deref, DerefMut},
+    path::PathBuf,
     sync::Arc,
 };
 use winit::event_loop::ActiveEventLoop;
@@ -55,6 +59,55 @@ use winit::event_loop::ActiveEventLoop;
 struct Args {
     #[clap(short, long, default_value = None)]
     override_scene: Option<String>,
+    /// Records input of the game to the given replay file.
+    #[clap(long, default_value = None)]
+    record_input: Option<PathBuf>,
+    /// Replays the given replay file headless and exits with code 1 if the game diverges.
+    #[clap(long, default_value = None)]
+    replay_input: Option<PathBuf>,
+    /// Name of a node, that is used to calculate scene checksums when recording input. Could be
+    /// specified multiple times, all nodes are used if none is specified.
+    #[clap(long)]
+    checksum_node: Vec<String>,
+}
+
+/// Options of input recording, see [`Executor::set_input_recording`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InputRecordingOptions {
+    /// Path of the replay file.
+    pub path: PathBuf,
+    /// Names of the nodes, that are used to calculate scene checksums. Empty list means all nodes.
+    pub checksum_nodes: Vec<String>,
+}
+
+/// Maps the packed archive of the game into memory.
+#[cfg(not(target_arch = "wasm32"))]
+fn open_archive(path: &std::path::Path) -> Result<PackedResourceIo, fyrox_core::io::FileError> {
+    // SAFETY: The archive is a part of the installed game, nothing writes to it while the game is
+    // running.
+    let map = unsafe { fyrox_core::io::MappedFile::open(path)? };
+    PackedResourceIo::from_mapped(map)
+}
+
+/// Resource IO of the game: the packed archive at [`PackedResourceIo::DEFAULT_PATH`] if the
+/// exported game has one, loose files otherwise.
+fn default_resource_io() -> Arc<dyn ResourceIo> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let archive = PackedResourceIo::DEFAULT_PATH;
+        if std::path::Path::new(archive).exists() {
+            match open_archive(std::path::Path::new(archive)) {
+                Ok(io) => {
+                    Log::info(format!("Loading resources from {archive} archive."));
+                    return Arc::new(io);
+                }
+                Err(err) => Log::err(format!(
+                    "Unable to open {archive} archive, loose files will be used. Reason: {err}"
+                )),
+            }
+        }
+    }
+    Arc::new(FsResourceIo)
 }
 
 /// Executor is a small wrapper that manages plugins and scripts for your game.
@@ -65,6 +118,10 @@ pub struct Executor {
     throttle_threshold: f32,
     throttle_frame_interval: usize,
     resource_hot_reloading: bool,
+    crash_reporter: Option<CrashReporterOptions>,
+    input_recording: Option<InputRecordingOptions>,
+    input_replay: Option<PathBuf>,
+    lockstep: bool,
 }
 
 impl Deref for Executor {
@@ -96,10 +153,19 @@ impl Executor {
     ) -> Self {
         let serialization_context = Arc::new(SerializationContext::new());
         let task_pool = Arc::new(TaskPool::new());
-        let io = Arc::new(FsResourceIo);
+        let io = default_resource_io();
+        let resource_manager = ResourceManager::new(io, task_pool.clone());
+        // Exported games ship the derived data (imported textures, models, etc.) built by the
+        // editor for the target platform, so the assets are not imported on every run.
+        resource_manager
+            .state()
+            .set_derived_data_cache(Some(DerivedDataCache::new(
+                DerivedDataCache::DEFAULT_PATH,
+                TargetPlatform::current(),
+            )));
         let engine = Engine::new(EngineInitParams {
             graphics_context_params,
-            resource_manager: ResourceManager::new(io, task_pool.clone()),
+            resource_manager,
             serialization_context,
             task_pool,
             widget_constructors: Arc::new(new_widget_constructor_container()),
@@ -113,6 +179,10 @@ impl Executor {
             throttle_threshold: 2.0 * Self::DEFAULT_TIME_STEP,
             throttle_frame_interval: 5,
             resource_hot_reloading: true,
+            crash_reporter: None,
+            input_recording: None,
+            input_replay: None,
+            lockstep: false,
         }
     }
 
@@ -152,6 +222,56 @@ impl Executor {
         self.resource_hot_reloading
     }
 
+    /// Enables or disables the crash reporter, that writes a diagnostic bundle to disk when the
+    /// game panics. See [`CrashReporter`] docs for more info. Disabled by default.
+    pub fn set_crash_reporter(&mut self, options: Option<CrashReporterOptions>) {
+        self.crash_reporter = options;
+    }
+
+    /// Returns the options of the crash reporter, if it is enabled.
+    pub fn crash_reporter(&self) -> Option<&CrashReporterOptions> {
+        self.crash_reporter.as_ref()
+    }
+
+    /// Enables or disables recording of input to a replay file, see [`crate::engine::replay`] docs
+    /// for more info. Could also be enabled with `--record-input <path>` command line argument.
+    /// Disabled by default.
+    pub fn set_input_recording(&mut self, options: Option<InputRecordingOptions>) {
+        self.input_recording = options;
+    }
+
+    /// Returns the options of input recording, if it is enabled.
+    pub fn input_recording(&self) -> Option<&InputRecordingOptions> {
+        self.input_recording.as_ref()
+    }
+
+    /// Sets a replay file, that will be replayed instead of running the game normally. The replay
+    /// runs headless with the recorded time steps, if the game diverges from the recording, the
+    /// process exits with code 1. Could also be set with `--replay-input <path>` command line
+    /// argument.
+    pub fn set_input_replay(&mut self, path: Option<PathBuf>) {
+        self.input_replay = path;
+    }
+
+    /// Returns the replay file, if any.
+    pub fn input_replay(&self) -> Option<&PathBuf> {
+        self.input_replay.as_ref()
+    }
+
+    /// Enables or disables lockstep mode of the headless executor. In this mode, every update uses
+    /// exactly one fixed time step (see [`Self::set_desired_update_rate`]) and the updates run as
+    /// fast as possible, without waiting for the real time to pass. It makes headless runs (such
+    /// as automated tests) fast and deterministic. Does nothing if the executor has a window.
+    /// Disabled by default.
+    pub fn set_lockstep(&mut self, lockstep: bool) {
+        self.lockstep = lockstep;
+    }
+
+    /// Returns `true` if lockstep mode is enabled, see [`Self::set_lockstep`] docs for more info.
+    pub fn is_lockstep(&self) -> bool {
+        self.lockstep
+    }
+
     /// Sets the desired throttle threshold (in seconds), at which the engine will stop trying to
     /// stabilize the update rate of the game logic and will increase the time step. This option
     /// could be useful to prevent potential hang up of the game if its logic or rendering takes too
@@ -207,14 +327,73 @@ impl Executor {
 
     /// Runs the executor - starts your game.
     pub fn run(self) {
+        if let Some(options) = self.crash_reporter.clone() {
+            CrashReporter::install(options);
+            CrashReporter::set_entry(
+                "executor.txt",
+                format!(
+                    "Desired Update Rate: {}\nThrottle Threshold: {}\n\
+                    Throttle Frame Interval: {}\nResource Hot Reloading: {}\n",
+                    self.desired_update_rate,
+                    self.throttle_threshold,
+                    self.throttle_frame_interval,
+                    self.resource_hot_reloading
+                ),
+            );
+        }
+
         Log::info("Initializing resource registry.");
         self.engine.resource_manager.update_or_load_registry();
 
-        let engine = self.engine;
+        let mut engine = self.engine;
         let event_loop = self.event_loop;
         let throttle_threshold = self.throttle_threshold;
         let throttle_frame_interval = self.throttle_frame_interval;
 
+        let args = Args::try_parse().unwrap_or_default();
+
+        if let Some(path) = args.replay_input.or(self.input_replay) {
+            match InputReplay::load(&path) {
+                Ok(replay) => run_replay(engine, args.override_scene.as_deref(), replay),
+                Err(err) => {
+                    Log::err(format!(
+                        "Unable to load {} replay. Reason: {err}",
+                        path.display()
+                    ));
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+
+        let recording = match args.record_input {
+            Some(path) => Some(InputRecordingOptions {
+                path,
+                checksum_nodes: args.checksum_node,
+            }),
+            None => self.input_recording,
+        };
+        let recorder = recording.and_then(|options| {
+            match InputRecorder::new(
+                &options.path,
+                1.0 / self.desired_update_rate,
+                options.checksum_nodes,
+                &mut engine,
+            ) {
+                Ok(recorder) => {
+                    Log::info(format!("Recording input to {}.", options.path.display()));
+                    Some(recorder)
+                }
+                Err(err) => {
+                    Log::err(format!(
+                        "Unable to record input to {}. Reason: {err}",
+                        options.path.display()
+                    ));
+                    None
+                }
+            }
+        });
+
         if self.resource_hot_reloading {
             #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
             {
@@ -231,8 +410,6 @@ impl Executor {
             }
         }
 
-        let args = Args::try_parse().unwrap_or_default();
-
         match event_loop {
             Some(event_loop) => run_normal(
                 engine,
@@ -241,6 +418,7 @@ impl Executor {
                 throttle_threshold,
                 throttle_frame_interval,
                 self.desired_update_rate,
+                recorder,
             ),
             None => run_headless(
                 engine,
@@ -248,6 +426,8 @@ impl Executor {
                 throttle_threshold,
                 throttle_frame_interval,
                 self.desired_update_rate,
+                self.lockstep,
+                recorder,
             ),
         }
     }
@@ -259,6 +439,8 @@ fn run_headless(
     throttle_threshold: f32,
     throttle_frame_interval: usize,
     desired_update_rate: f32,
+    lockstep: bool,
+    mut recorder: Option<InputRecorder>,
 ) {
     let mut previous = Instant::now();
     let fixed_time_step = 1.0 / desired_update_rate;
@@ -278,6 +460,22 @@ fn run_headless(
     while is_running.get() {
         register_scripted_scenes(&mut engine);
 
+        if lockstep {
+            // Exactly one time step per update, no matter how much real time has passed.
+            let mut lag = 0.0;
+            engine.update(
+                fixed_time_step,
+                ApplicationLoopController::Headless {
+                    running: &is_running,
+                },
+                &mut lag,
+                Default::default(),
+            );
+            end_recorded_frame(&mut engine, &mut recorder, fixed_time_step);
+            CrashReporter::update(&engine);
+            continue;
+        }
+
         game_loop_iteration(
             &mut engine,
             ApplicationLoopController::Headless {
@@ -290,6 +488,7 @@ fn run_headless(
             throttle_frame_interval,
             frame_counter,
             &mut last_throttle_frame_number,
+            &mut recorder,
         );
 
         frame_counter += 1;
@@ -310,6 +509,7 @@ fn run_normal(
     throttle_threshold: f32,
     throttle_frame_interval: usize,
     desired_update_rate: f32,
+    mut recorder: Option<InputRecorder>,
 ) {
     let mut previous = Instant::now();
     let fixed_time_step = 1.0 / desired_update_rate;
@@ -326,6 +526,10 @@ fn run_normal(
     run_executor(event_loop, move |event, active_event_loop| {
         active_event_loop.set_control_flow(ControlFlow::Wait);
 
+        if let Some(recorder) = recorder.as_mut() {
+            recorder.record_event(&event);
+        }
+
         engine.handle_os_events(
             &event,
             fixed_time_step,
@@ -372,6 +576,7 @@ fn run_normal(
                     throttle_frame_interval,
                     frame_counter,
                     &mut last_throttle_frame_number,
+                    &mut recorder,
                 );
             }
             Event::WindowEvent { event, .. } => {
@@ -410,6 +615,33 @@ fn run_normal(
     })
 }
 
+fn run_replay(mut engine: Engine, override_scene: Option<&str>, replay: InputReplay) {
+    let is_running = Cell::new(true);
+    let controller = ApplicationLoopController::Headless {
+        running: &is_running,
+    };
+    let mut lag = 0.0;
+
+    replay.prepare();
+    engine.enable_plugins(override_scene, true, controller);
+
+    Log::info(format!("Replaying {} frames.", replay.frames.len()));
+    for index in 0..replay.frames.len() {
+        if !is_running.get() {
+            break;
+        }
+
+        let scripted_scenes = register_scripted_scenes(&mut engine);
+        if let Err(divergence) =
+            replay.play_frame(index, &mut engine, &scripted_scenes, controller, &mut lag)
+        {
+            Log::err(divergence.to_string());
+            std::process::exit(1);
+        }
+    }
+    Log::info("The replay has finished without divergence.");
+}
+
 fn register_scripted_scenes(engine: &mut Engine) -> Vec<Handle<Scene>> {
     let scenes = engine
         .scenes
@@ -436,6 +668,7 @@ fn game_loop_iteration(
     throttle_frame_interval: usize,
     frame_counter: usize,
     last_throttle_frame_number: &mut usize,
+    recorder: &mut Option<InputRecorder>,
 ) {
     let elapsed = previous.elapsed();
     *previous = Instant::now();
@@ -461,6 +694,8 @@ fn game_loop_iteration(
 
         engine.update(time_step, controller, lag, Default::default());
 
+        end_recorded_frame(engine, recorder, time_step);
+
         // Additional check is needed, because the `update` call above could modify
         // the lag.
         if *lag >= fixed_time_step {
@@ -471,11 +706,22 @@ fn game_loop_iteration(
         }
     }
 
+    CrashReporter::update(engine);
+
     if let GraphicsContext::Initialized(ref ctx) = engine.graphics_context {
         ctx.window.request_redraw();
     }
 }
 
+fn end_recorded_frame(engine: &mut Engine, recorder: &mut Option<InputRecorder>, time_step: f32) {
+    if let Some(active_recorder) = recorder.as_mut() {
+        if let Err(err) = active_recorder.end_frame(engine, time_step) {
+            Log::err(format!("Unable to record input. Reason: {err}"));
+            *recorder = None;
+        }
+    }
+}
+
 #[allow(deprecated)] // TODO
 fn run_executor<F>(event_loop: EventLoop<()>, callback: F)
 where
@@ -492,3 +738,30 @@ where
         event_loop.run(callback).unwrap();
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use fyrox_resource::io::{PackedArchiveWriter, PackedCompression};
+    use std::{io::Cursor, path::Path};
+
+    #[test]
+    fn test_open_archive() {
+        let mut writer = PackedArchiveWriter::new(Cursor::new(Vec::new())).unwrap();
+        writer
+            .add_file(
+                Path::new("data/level.rgs"),
+                b"level",
+                PackedCompression::None,
+            )
+            .unwrap();
+        let archive = Path::new("./test_open_archive.pak");
+        std::fs::write(archive, writer.finish().unwrap().into_inner()).unwrap();
+
+        let io = open_archive(archive).unwrap();
+        assert_eq!(io.read(Path::new("data/level.rgs")).unwrap(), b"level");
+
+        drop(io);
+        std::fs::remove_file(archive).unwrap();
+    }
+}
@@ -0,0 +1,152 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Gamepad backend of the engine, see [`GamepadBackend`] docs for more info.
+
+use crate::{
+    core::log::Log,
+    engine::input::{
+        GamepadAxis, GamepadButton, GamepadEvent, GamepadEventKind, GamepadId, InputActions,
+    },
+};
+use gilrs::{Axis, Button, EventType, Gilrs};
+
+/// Polls gamepads using `gilrs` and feeds their events to [`InputActions`]. The engine polls it
+/// once per frame, before the input actions are updated. Available with the `gamepad` feature.
+pub struct GamepadBackend {
+    gilrs: Option<Gilrs>,
+    // Gamepads that were connected before the backend was created.
+    connected: Vec<GamepadId>,
+}
+
+impl Default for GamepadBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GamepadBackend {
+    /// Creates a new backend. Gamepads are unavailable (with a warning in the log) if the platform
+    /// does not support them.
+    pub fn new() -> Self {
+        match Gilrs::new() {
+            Ok(gilrs) => Self {
+                connected: gilrs
+                    .gamepads()
+                    .map(|(id, _)| GamepadId(usize::from(id) as u32))
+                    .collect(),
+                gilrs: Some(gilrs),
+            },
+            Err(err) => {
+                Log::warn(format!("Gamepads are unavailable: {err}"));
+                Self {
+                    gilrs: None,
+                    connected: Vec::new(),
+                }
+            }
+        }
+    }
+
+    /// Pushes the events of every gamepad that happened since the last poll to `actions`.
+    pub fn poll(&mut self, actions: &mut InputActions) {
+        for gamepad in self.connected.drain(..) {
+            actions.push_gamepad_event(GamepadEvent {
+                gamepad,
+                kind: GamepadEventKind::Connected,
+            });
+        }
+
+        let Some(gilrs) = self.gilrs.as_mut() else {
+            return;
+        };
+        while let Some(event) = gilrs.next_event() {
+            if let Some(kind) = translate_event(event.event) {
+                actions.push_gamepad_event(GamepadEvent {
+                    gamepad: GamepadId(usize::from(event.id) as u32),
+                    kind,
+                });
+            }
+        }
+    }
+}
+
+fn translate_event(event: EventType) -> Option<GamepadEventKind> {
+    Some(match event {
+        EventType::Connected => GamepadEventKind::Connected,
+        EventType::Disconnected => GamepadEventKind::Disconnected,
+        EventType::ButtonPressed(button, _) => GamepadEventKind::Button {
+            button: translate_button(button)?,
+            pressed: true,
+        },
+        EventType::ButtonReleased(button, _) => GamepadEventKind::Button {
+            button: translate_button(button)?,
+            pressed: false,
+        },
+        // Analog triggers are reported as buttons with a value.
+        EventType::ButtonChanged(Button::LeftTrigger2, value, _) => GamepadEventKind::Axis {
+            axis: GamepadAxis::LeftTrigger,
+            value,
+        },
+        EventType::ButtonChanged(Button::RightTrigger2, value, _) => GamepadEventKind::Axis {
+            axis: GamepadAxis::RightTrigger,
+            value,
+        },
+        EventType::AxisChanged(axis, value, _) => GamepadEventKind::Axis {
+            axis: translate_axis(axis)?,
+            value,
+        },
+        _ => return None,
+    })
+}
+
+fn translate_button(button: Button) -> Option<GamepadButton> {
+    Some(match button {
+        Button::South => GamepadButton::South,
+        Button::East => GamepadButton::East,
+        Button::North => GamepadButton::North,
+        Button::West => GamepadButton::West,
+        Button::LeftTrigger => GamepadButton::LeftBumper,
+        Button::RightTrigger => GamepadButton::RightBumper,
+        Button::LeftTrigger2 => GamepadButton::LeftTrigger,
+        Button::RightTrigger2 => GamepadButton::RightTrigger,
+        Button::Select => GamepadButton::Select,
+        Button::Start => GamepadButton::Start,
+        Button::Mode => GamepadButton::Mode,
+        Button::LeftThumb => GamepadButton::LeftThumb,
+        Button::RightThumb => GamepadButton::RightThumb,
+        Button::DPadUp => GamepadButton::DPadUp,
+        Button::DPadDown => GamepadButton::DPadDown,
+        Button::DPadLeft => GamepadButton::DPadLeft,
+        Button::DPadRight => GamepadButton::DPadRight,
+        Button::C | Button::Z | Button::Unknown => return None,
+    })
+}
+
+fn translate_axis(axis: Axis) -> Option<GamepadAxis> {
+    Some(match axis {
+        Axis::LeftStickX => GamepadAxis::LeftStickX,
+        Axis::LeftStickY => GamepadAxis::LeftStickY,
+        Axis::RightStickX => GamepadAxis::RightStickX,
+        Axis::RightStickY => GamepadAxis::RightStickY,
+        Axis::LeftZ => GamepadAxis::LeftTrigger,
+        Axis::RightZ => GamepadAxis::RightTrigger,
+        Axis::DPadX | Axis::DPadY | Axis::Unknown => return None,
+    })
+}
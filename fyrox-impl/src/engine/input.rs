@@ -18,13 +18,33 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-//! This module contains a set of "shortcuts" that allows getting the state of a mouse and a keyboard
-//! in a simplified manner (read - without using "verbose" event-based approach). It may be useful
-//! in simple scenarios where you just need to know if a button (on keyboard, mouse) was pressed
-//! and do something. You should always prefer the event-based approach when possible.
+//! This module contains a set of "shortcuts" that allows getting the state of a mouse, a keyboard
+//! and gamepads in a simplified manner (read - without using "verbose" event-based approach). It may
+//! be useful in simple scenarios where you just need to know if a button (on keyboard, mouse) was
+//! pressed and do something. You should always prefer the event-based approach when possible.
+//!
+//! It also contains [`InputActions`], which evaluates named actions and axes of an
+//! [`InputMap`](crate::resource::input_map::InputMap) for every player of a game.
 
+use crate::{
+    core::{reflect::prelude::*, uuid_provider, visitor::prelude::*},
+    gui::message::KeyboardModifiers,
+    resource::input_map::{
+        ActionBinding, AxisBinding, AxisDirection, InputAxis, InputMapResource, InputSource,
+        MouseAxis,
+    },
+    utils::{translate_key_from_ui, translate_key_to_ui},
+};
 use fxhash::{FxHashMap, FxHashSet};
 use fyrox_core::algebra::Vector2;
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+    path::Path,
+};
+use strum_macros::{AsRefStr, EnumString, VariantNames};
 use winit::event::{ButtonId, ElementState};
 use winit::keyboard::{KeyCode, PhysicalKey};
 
@@ -91,6 +111,33 @@ pub struct Keyboard {
     pub released_keys: FxHashSet<PhysicalKey>,
 }
 
+impl Keyboard {
+    pub(crate) fn process_key(&mut self, key: PhysicalKey, state: ElementState) {
+        match state {
+            ElementState::Pressed => {
+                if self
+                    .keys
+                    .get(&key)
+                    .is_none_or(|state| *state == ElementState::Released)
+                {
+                    self.pressed_keys.insert(key);
+                }
+            }
+            ElementState::Released => {
+                if self
+                    .keys
+                    .get(&key)
+                    .is_some_and(|state| *state == ElementState::Pressed)
+                {
+                    self.released_keys.insert(key);
+                }
+            }
+        }
+
+        self.keys.insert(key, state);
+    }
+}
+
 /// A stored state of most common input events. It is used a "shortcut" in cases where event-based
 /// approach is too verbose. **Important:** this structure does not track from which device the
 /// corresponding event has come from, if you have more than one keyboard and/or mouse, use
@@ -101,6 +148,8 @@ pub struct InputState {
     pub mouse: Mouse,
     /// Represents the keyboard state in the current frame.
     pub keyboard: Keyboard,
+    /// Represents the state of all connected gamepads in the current frame.
+    pub gamepads: FxHashMap<GamepadId, Gamepad>,
 }
 
 impl InputState {
@@ -244,4 +293,918 @@ impl InputState {
     pub fn mouse_position(&self) -> Vector2<f32> {
         self.mouse.position
     }
+
+    /// Returns the state of the given gamepad, `None` if the gamepad is not connected.
+    #[inline]
+    pub fn gamepad(&self, gamepad: GamepadId) -> Option<&Gamepad> {
+        self.gamepads.get(&gamepad)
+    }
+
+    /// Returns `true` if the specified button of the given gamepad is pressed, `false` - otherwise.
+    #[inline]
+    pub fn is_gamepad_button_down(&self, gamepad: GamepadId, button: GamepadButton) -> bool {
+        self.gamepad(gamepad)
+            .is_some_and(|gamepad| gamepad.is_button_down(button))
+    }
+
+    /// Returns `true` if the specified button of the given gamepad was pressed in the current
+    /// frame, `false` - otherwise.
+    #[inline]
+    pub fn is_gamepad_button_pressed(&self, gamepad: GamepadId, button: GamepadButton) -> bool {
+        self.gamepad(gamepad)
+            .is_some_and(|gamepad| gamepad.pressed_buttons.contains(&button))
+    }
+
+    /// Returns `true` if the specified button of the given gamepad was released in the current
+    /// frame, `false` - otherwise.
+    #[inline]
+    pub fn is_gamepad_button_released(&self, gamepad: GamepadId, button: GamepadButton) -> bool {
+        self.gamepad(gamepad)
+            .is_some_and(|gamepad| gamepad.released_buttons.contains(&button))
+    }
+
+    /// Returns the value of the specified axis of the given gamepad in `[-1; 1]` range. The value
+    /// is zero if the gamepad is not connected.
+    #[inline]
+    pub fn gamepad_axis(&self, gamepad: GamepadId, axis: GamepadAxis) -> f32 {
+        self.gamepad(gamepad)
+            .map_or(0.0, |gamepad| gamepad.axis(axis))
+    }
+
+    pub(crate) fn process_gamepad_event(&mut self, event: &GamepadEvent) {
+        match event.kind {
+            GamepadEventKind::Connected => {
+                self.gamepads.entry(event.gamepad).or_default();
+            }
+            GamepadEventKind::Disconnected => {
+                self.gamepads.remove(&event.gamepad);
+            }
+            GamepadEventKind::Button { button, pressed } => {
+                let gamepad = self.gamepads.entry(event.gamepad).or_default();
+                if pressed {
+                    if gamepad.buttons.insert(button) {
+                        gamepad.pressed_buttons.insert(button);
+                    }
+                } else if gamepad.buttons.remove(&button) {
+                    gamepad.released_buttons.insert(button);
+                }
+            }
+            GamepadEventKind::Axis { axis, value } => {
+                let gamepad = self.gamepads.entry(event.gamepad).or_default();
+                gamepad.axes.insert(axis, value.clamp(-1.0, 1.0));
+            }
+        }
+    }
+}
+
+/// A unique identifier of a gamepad, it is assigned by a gamepad backend.
+#[derive(
+    Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub struct GamepadId(pub u32);
+
+/// A button of a gamepad. The face buttons are named by their position, so the same binding works
+/// for gamepads with different labels on the buttons.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+    Serialize,
+    Deserialize,
+)]
+pub enum GamepadButton {
+    /// The bottom face button (`A` on Xbox gamepads, `Cross` on PlayStation gamepads).
+    #[default]
+    South,
+    /// The right face button (`B` on Xbox gamepads, `Circle` on PlayStation gamepads).
+    East,
+    /// The left face button (`X` on Xbox gamepads, `Square` on PlayStation gamepads).
+    West,
+    /// The top face button (`Y` on Xbox gamepads, `Triangle` on PlayStation gamepads).
+    North,
+    /// The left shoulder button.
+    LeftBumper,
+    /// The right shoulder button.
+    RightBumper,
+    /// The left trigger, when a backend reports it as a button.
+    LeftTrigger,
+    /// The right trigger, when a backend reports it as a button.
+    RightTrigger,
+    /// The left menu button (`Back`, `View`, `Share`).
+    Select,
+    /// The right menu button (`Start`, `Menu`, `Options`).
+    Start,
+    /// The central button with a logo of the vendor.
+    Mode,
+    /// A click of the left stick.
+    LeftThumb,
+    /// A click of the right stick.
+    RightThumb,
+    /// Up button of the directional pad.
+    DPadUp,
+    /// Down button of the directional pad.
+    DPadDown,
+    /// Left button of the directional pad.
+    DPadLeft,
+    /// Right button of the directional pad.
+    DPadRight,
+}
+
+uuid_provider!(GamepadButton = "cfa502b5-0783-4b9c-84ef-4c6df4032f16");
+
+/// An analog axis of a gamepad. Stick axes are in `[-1; 1]` range, where positive values are right
+/// and up. Trigger axes are in `[0; 1]` range.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+    Serialize,
+    Deserialize,
+)]
+pub enum GamepadAxis {
+    /// Horizontal axis of the left stick.
+    #[default]
+    LeftStickX,
+    /// Vertical axis of the left stick.
+    LeftStickY,
+    /// Horizontal axis of the right stick.
+    RightStickX,
+    /// Vertical axis of the right stick.
+    RightStickY,
+    /// The left trigger.
+    LeftTrigger,
+    /// The right trigger.
+    RightTrigger,
+}
+
+uuid_provider!(GamepadAxis = "bbb8b8a1-d7f9-49fc-8c23-49907a3dd592");
+
+/// Represents the state of a gamepad in the current frame.
+#[derive(Default, Clone, Debug)]
+pub struct Gamepad {
+    /// A hash set that contains all the buttons that are held.
+    pub buttons: FxHashSet<GamepadButton>,
+    /// A hash set that contains all the buttons that were pressed in the current frame.
+    pub pressed_buttons: FxHashSet<GamepadButton>,
+    /// A hash set that contains all the buttons that were released in the current frame.
+    pub released_buttons: FxHashSet<GamepadButton>,
+    /// Values of the axes, the axes that were never moved are not stored.
+    pub axes: FxHashMap<GamepadAxis, f32>,
+}
+
+impl Gamepad {
+    /// Returns `true` if the specified button is pressed, `false` - otherwise.
+    #[inline]
+    pub fn is_button_down(&self, button: GamepadButton) -> bool {
+        self.buttons.contains(&button)
+    }
+
+    /// Returns the value of the specified axis, without any dead zone applied.
+    #[inline]
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        self.axes.get(&axis).copied().unwrap_or_default()
+    }
+
+    pub(crate) fn clear_frame_state(&mut self) {
+        self.pressed_buttons.clear();
+        self.released_buttons.clear();
+    }
+}
+
+/// A change of a gamepad state.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GamepadEventKind {
+    /// The gamepad was connected.
+    Connected,
+    /// The gamepad was disconnected.
+    Disconnected,
+    /// A button of the gamepad was pressed or released.
+    Button {
+        /// The button that has changed its state.
+        button: GamepadButton,
+        /// `true` if the button was pressed, `false` if it was released.
+        pressed: bool,
+    },
+    /// An axis of the gamepad has changed its value.
+    Axis {
+        /// The axis that has changed its value.
+        axis: GamepadAxis,
+        /// New value of the axis, see [`GamepadAxis`] for the ranges.
+        value: f32,
+    },
+}
+
+/// An event of a gamepad, see [`InputActions::push_gamepad_event`] docs for more info.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GamepadEvent {
+    /// The gamepad that has sent the event.
+    pub gamepad: GamepadId,
+    /// Actual change of the gamepad state.
+    pub kind: GamepadEventKind,
+}
+
+/// An analog input must be pushed at least this far to act as a held button.
+const ACTIVATION_THRESHOLD: f32 = 0.5;
+
+/// Devices of a single player.
+struct PlayerDevices<'a> {
+    state: &'a InputState,
+    keyboard_mouse: bool,
+    gamepad: Option<&'a Gamepad>,
+}
+
+impl PlayerDevices<'_> {
+    fn is_key_down(&self, key: KeyCode) -> bool {
+        self.keyboard_mouse && self.state.is_key_down(key)
+    }
+
+    fn mouse_axis(&self, axis: MouseAxis) -> f32 {
+        if !self.keyboard_mouse {
+            return 0.0;
+        }
+        match axis {
+            MouseAxis::X => self.state.mouse.speed.x,
+            MouseAxis::Y => self.state.mouse.speed.y,
+        }
+    }
+
+    fn gamepad_axis(&self, axis: GamepadAxis) -> f32 {
+        self.gamepad.map_or(0.0, |gamepad| gamepad.axis(axis))
+    }
+
+    /// Returns a non-negative value of the source, digital sources are either `0.0` or `1.0`.
+    fn source_value(&self, source: &InputSource) -> f32 {
+        let digital = |down: bool| if down { 1.0 } else { 0.0 };
+        match *source {
+            InputSource::Key(key) => digital(self.is_key_down(translate_key_from_ui(key))),
+            InputSource::MouseButton(button) => {
+                digital(self.keyboard_mouse && self.state.is_mouse_button_down(button))
+            }
+            InputSource::MouseAxis { axis, direction } => {
+                (self.mouse_axis(axis) * direction.sign()).max(0.0)
+            }
+            InputSource::GamepadButton(button) => digital(
+                self.gamepad
+                    .is_some_and(|gamepad| gamepad.is_button_down(button)),
+            ),
+            InputSource::GamepadAxis { axis, direction } => {
+                (self.gamepad_axis(axis) * direction.sign()).max(0.0)
+            }
+        }
+    }
+
+    fn is_source_active(&self, source: &InputSource) -> bool {
+        let value = self.source_value(source);
+        if let InputSource::MouseAxis { .. } = source {
+            value > 0.0
+        } else {
+            value >= ACTIVATION_THRESHOLD
+        }
+    }
+
+    fn are_modifiers_held(&self, modifiers: KeyboardModifiers) -> bool {
+        let held = |left, right| self.is_key_down(left) || self.is_key_down(right);
+        (!modifiers.alt || held(KeyCode::AltLeft, KeyCode::AltRight))
+            && (!modifiers.shift || held(KeyCode::ShiftLeft, KeyCode::ShiftRight))
+            && (!modifiers.control || held(KeyCode::ControlLeft, KeyCode::ControlRight))
+            && (!modifiers.system || held(KeyCode::SuperLeft, KeyCode::SuperRight))
+    }
+
+    fn is_binding_active(&self, binding: &ActionBinding) -> bool {
+        !binding.inputs.is_empty()
+            && binding
+                .inputs
+                .iter()
+                .all(|source| self.is_source_active(source))
+            && self.are_modifiers_held(binding.modifiers)
+    }
+
+    fn axis_value(&self, axis: &InputAxis, bindings: &[AxisBinding]) -> f32 {
+        let button_value = |source: &InputSource| {
+            let value = self.source_value(source);
+            if let InputSource::GamepadAxis { .. } = source {
+                axis.apply_dead_zone(value)
+            } else {
+                value
+            }
+        };
+
+        bindings
+            .iter()
+            .map(|binding| match binding {
+                AxisBinding::Buttons { negative, positive } => {
+                    button_value(positive) - button_value(negative)
+                }
+                AxisBinding::MouseAxis { axis, scale } => self.mouse_axis(*axis) * *scale,
+                AxisBinding::GamepadAxis {
+                    axis: gamepad_axis,
+                    scale,
+                } => axis.apply_dead_zone(self.gamepad_axis(*gamepad_axis)) * *scale,
+            })
+            .fold(0.0, |value: f32, binding_value: f32| {
+                if binding_value.abs() > value.abs() {
+                    binding_value
+                } else {
+                    value
+                }
+            })
+    }
+}
+
+/// An error that may occur during saving or loading of player bindings.
+#[derive(Debug)]
+pub enum InputBindingsError {
+    /// An i/o error has occurred.
+    Io(std::io::Error),
+    /// The bindings file is malformed.
+    RonSpanned(ron::error::SpannedError),
+    /// The bindings could not be serialized.
+    Ron(ron::Error),
+}
+
+impl std::error::Error for InputBindingsError {}
+
+impl Display for InputBindingsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputBindingsError::Io(error) => Display::fmt(error, f),
+            InputBindingsError::RonSpanned(error) => Display::fmt(error, f),
+            InputBindingsError::Ron(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+impl From<std::io::Error> for InputBindingsError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<ron::error::SpannedError> for InputBindingsError {
+    fn from(e: ron::error::SpannedError) -> Self {
+        Self::RonSpanned(e)
+    }
+}
+
+impl From<ron::Error> for InputBindingsError {
+    fn from(e: ron::Error) -> Self {
+        Self::Ron(e)
+    }
+}
+
+/// Bindings of a player, that override the default bindings of an input map. Actions and axes,
+/// that are not listed here, use the default bindings.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct InputBindings {
+    /// Overridden bindings of actions, the key is the name of an action.
+    pub actions: BTreeMap<String, Vec<ActionBinding>>,
+    /// Overridden bindings of axes, the key is the name of an axis.
+    pub axes: BTreeMap<String, Vec<AxisBinding>>,
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+struct ActionState {
+    down: bool,
+    pressed: bool,
+    released: bool,
+}
+
+/// Input context of a single player. It evaluates the actions and axes of an input map using the
+/// devices of the player, and stores the bindings rebound by the player. The state of actions and
+/// axes is updated once per frame by the engine, before plugins and scripts are updated.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox_impl::{
+/// #     engine::input::{GamepadId, InputContext},
+/// #     plugin::PluginContext,
+/// #     resource::input_map::InputMapResource,
+/// # };
+/// fn add_players(map: InputMapResource, context: &mut PluginContext) {
+///     // The first player uses the keyboard and the mouse, the second one - the first gamepad.
+///     context.input_actions.add_player(InputContext::new(map.clone()));
+///     context.input_actions.add_player(
+///         InputContext::new(map)
+///             .with_keyboard_mouse(false)
+///             .with_gamepad(Some(GamepadId(0))),
+///     );
+/// }
+///
+/// fn update(context: &PluginContext) {
+///     for player in context.input_actions.players() {
+///         if player.is_action_pressed("Jump") {
+///             // Jump.
+///         }
+///         let _speed = player.axis_value("MoveRight");
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct InputContext {
+    map: InputMapResource,
+    /// `true` if the player uses the keyboard and the mouse. Only one player should use them.
+    pub keyboard_mouse: bool,
+    /// A gamepad used by the player, if any.
+    pub gamepad: Option<GamepadId>,
+    bindings: InputBindings,
+    actions: FxHashMap<String, ActionState>,
+    axes: FxHashMap<String, f32>,
+}
+
+impl InputContext {
+    /// Creates a new context of a player that uses the keyboard and the mouse.
+    pub fn new(map: InputMapResource) -> Self {
+        Self {
+            map,
+            keyboard_mouse: true,
+            gamepad: None,
+            bindings: Default::default(),
+            actions: Default::default(),
+            axes: Default::default(),
+        }
+    }
+
+    /// Sets whether the player uses the keyboard and the mouse.
+    pub fn with_keyboard_mouse(mut self, keyboard_mouse: bool) -> Self {
+        self.keyboard_mouse = keyboard_mouse;
+        self
+    }
+
+    /// Sets a gamepad used by the player.
+    pub fn with_gamepad(mut self, gamepad: Option<GamepadId>) -> Self {
+        self.gamepad = gamepad;
+        self
+    }
+
+    /// Returns the input map of the context.
+    pub fn map(&self) -> &InputMapResource {
+        &self.map
+    }
+
+    /// Sets a new input map, the rebound bindings are kept.
+    pub fn set_map(&mut self, map: InputMapResource) {
+        self.map = map;
+    }
+
+    /// Returns `true` if the action is active, `false` - otherwise or if there's no such action.
+    pub fn is_action_down(&self, action: &str) -> bool {
+        self.actions.get(action).is_some_and(|state| state.down)
+    }
+
+    /// Returns `true` if the action has become active in the current frame, `false` - otherwise.
+    pub fn is_action_pressed(&self, action: &str) -> bool {
+        self.actions.get(action).is_some_and(|state| state.pressed)
+    }
+
+    /// Returns `true` if the action has become inactive in the current frame, `false` - otherwise.
+    pub fn is_action_released(&self, action: &str) -> bool {
+        self.actions.get(action).is_some_and(|state| state.released)
+    }
+
+    /// Returns the value of the axis in the current frame, zero if there's no such axis.
+    pub fn axis_value(&self, axis: &str) -> f32 {
+        self.axes.get(axis).copied().unwrap_or_default()
+    }
+
+    /// Returns the current bindings of the action: the rebound ones if the action was rebound or
+    /// the default ones from the input map. `None` if there's no such action or the input map is
+    /// not loaded.
+    pub fn action_bindings(&self, action: &str) -> Option<Vec<ActionBinding>> {
+        if let Some(bindings) = self.bindings.actions.get(action) {
+            return Some(bindings.clone());
+        }
+        let state = self.map.state();
+        let map = state.data_ref()?;
+        map.action(action).map(|action| action.bindings.clone())
+    }
+
+    /// Returns the current bindings of the axis, see [`Self::action_bindings`] for more info.
+    pub fn axis_bindings(&self, axis: &str) -> Option<Vec<AxisBinding>> {
+        if let Some(bindings) = self.bindings.axes.get(axis) {
+            return Some(bindings.clone());
+        }
+        let state = self.map.state();
+        let map = state.data_ref()?;
+        map.axis(axis).map(|axis| axis.bindings.clone())
+    }
+
+    /// Replaces the bindings of the action for this player only. The input map is not changed.
+    pub fn rebind_action(&mut self, action: impl Into<String>, bindings: Vec<ActionBinding>) {
+        self.bindings.actions.insert(action.into(), bindings);
+    }
+
+    /// Replaces the bindings of the axis for this player only. The input map is not changed.
+    pub fn rebind_axis(&mut self, axis: impl Into<String>, bindings: Vec<AxisBinding>) {
+        self.bindings.axes.insert(axis.into(), bindings);
+    }
+
+    /// Restores the default bindings of the action.
+    pub fn reset_action(&mut self, action: &str) {
+        self.bindings.actions.remove(action);
+    }
+
+    /// Restores the default bindings of the axis.
+    pub fn reset_axis(&mut self, axis: &str) {
+        self.bindings.axes.remove(axis);
+    }
+
+    /// Restores the default bindings of all actions and axes.
+    pub fn reset_bindings(&mut self) {
+        self.bindings = Default::default();
+    }
+
+    /// Returns the bindings rebound by the player.
+    pub fn bindings(&self) -> &InputBindings {
+        &self.bindings
+    }
+
+    /// Replaces all the bindings rebound by the player.
+    pub fn set_bindings(&mut self, bindings: InputBindings) {
+        self.bindings = bindings;
+    }
+
+    /// Saves the bindings rebound by the player to a settings file.
+    pub fn save_bindings(&self, path: &Path) -> Result<(), InputBindingsError> {
+        let text = ron::ser::to_string_pretty(&self.bindings, PrettyConfig::default())?;
+        std::fs::write(path, text)?;
+        Ok(())
+    }
+
+    /// Loads the bindings of the player from a settings file, that was written by
+    /// [`Self::save_bindings`].
+    pub fn load_bindings(&mut self, path: &Path) -> Result<(), InputBindingsError> {
+        let text = std::fs::read_to_string(path)?;
+        self.bindings = ron::de::from_str(&text)?;
+        Ok(())
+    }
+
+    fn update(&mut self, state: &InputState) {
+        let devices = PlayerDevices {
+            state,
+            keyboard_mouse: self.keyboard_mouse,
+            gamepad: self
+                .gamepad
+                .and_then(|gamepad| state.gamepads.get(&gamepad)),
+        };
+
+        let map_state = self.map.state();
+        let Some(map) = map_state.data_ref() else {
+            self.actions.clear();
+            self.axes.clear();
+            return;
+        };
+
+        for action in map.actions.iter() {
+            let bindings = self
+                .bindings
+                .actions
+                .get(&action.name)
+                .unwrap_or(&action.bindings);
+            let down = bindings
+                .iter()
+                .any(|binding| devices.is_binding_active(binding));
+            let previous = self.actions.get(&action.name).copied().unwrap_or_default();
+            self.actions.insert(
+                action.name.clone(),
+                ActionState {
+                    down,
+                    pressed: down && !previous.down,
+                    released: !down && previous.down,
+                },
+            );
+        }
+        self.actions.retain(|name, _| map.action(name).is_some());
+
+        self.axes.clear();
+        for axis in map.axes.iter() {
+            let bindings = self.bindings.axes.get(&axis.name).unwrap_or(&axis.bindings);
+            self.axes
+                .insert(axis.name.clone(), devices.axis_value(axis, bindings));
+        }
+    }
+}
+
+/// Evaluates input maps for every player of a game. The engine updates it once per frame, before
+/// plugins and scripts are updated, and it is available in plugin and script contexts.
+///
+/// ## Gamepads
+///
+/// With the `gamepad` feature, the engine polls gamepads using `gilrs`. Without it, a game feeds
+/// events of its own gamepad backend using [`Self::push_gamepad_event`]. The events are applied at
+/// the beginning of the next frame, and the resulting state is available in
+/// [`InputState::gamepads`].
+///
+/// ## Rebinding
+///
+/// A typical "press a key" rebinding menu waits for [`Self::last_pressed_source`] to return some
+/// input, then passes it to [`InputContext::rebind_action`] of the player and saves the bindings
+/// using [`InputContext::save_bindings`].
+#[derive(Default, Clone, Debug)]
+pub struct InputActions {
+    players: Vec<InputContext>,
+    gamepad_events: Vec<GamepadEvent>,
+    last_pressed: Option<InputSource>,
+}
+
+impl InputActions {
+    /// Adds a new player and returns its index.
+    pub fn add_player(&mut self, context: InputContext) -> usize {
+        self.players.push(context);
+        self.players.len() - 1
+    }
+
+    /// Removes the player with the given index, indices of the next players are shifted down.
+    pub fn remove_player(&mut self, index: usize) -> Option<InputContext> {
+        (index < self.players.len()).then(|| self.players.remove(index))
+    }
+
+    /// Returns the input context of the player with the given index.
+    pub fn player(&self, index: usize) -> Option<&InputContext> {
+        self.players.get(index)
+    }
+
+    /// Returns the input context of the player with the given index.
+    pub fn player_mut(&mut self, index: usize) -> Option<&mut InputContext> {
+        self.players.get_mut(index)
+    }
+
+    /// Returns the input contexts of all players.
+    pub fn players(&self) -> &[InputContext] {
+        &self.players
+    }
+
+    /// Returns the input contexts of all players.
+    pub fn players_mut(&mut self) -> &mut [InputContext] {
+        &mut self.players
+    }
+
+    /// Queues an event of a gamepad, it will be applied at the beginning of the next frame.
+    pub fn push_gamepad_event(&mut self, event: GamepadEvent) {
+        self.gamepad_events.push(event);
+    }
+
+    /// Returns an input, that was pressed in the current frame: a key, a mouse button, a gamepad
+    /// button or a gamepad axis pushed over the half of its range.
+    pub fn last_pressed_source(&self) -> Option<InputSource> {
+        self.last_pressed
+    }
+
+    pub(crate) fn update(&mut self, state: &mut InputState) {
+        self.last_pressed = None;
+
+        for event in self.gamepad_events.drain(..) {
+            if let GamepadEventKind::Axis { axis, value } = event.kind {
+                let previous = state.gamepad_axis(event.gamepad, axis);
+                if previous.abs() < ACTIVATION_THRESHOLD && value.abs() >= ACTIVATION_THRESHOLD {
+                    let direction = if value > 0.0 {
+                        AxisDirection::Positive
+                    } else {
+                        AxisDirection::Negative
+                    };
+                    self.last_pressed = Some(InputSource::GamepadAxis { axis, direction });
+                }
+            }
+            state.process_gamepad_event(&event);
+        }
+
+        let pressed_key = state
+            .keyboard
+            .pressed_keys
+            .iter()
+            .find_map(|key| match key {
+                PhysicalKey::Code(code) => Some(InputSource::Key(translate_key_to_ui(*code))),
+                PhysicalKey::Unidentified(_) => None,
+            });
+        let pressed_mouse_button = state
+            .mouse
+            .pressed_buttons
+            .iter()
+            .min()
+            .map(|button| InputSource::MouseButton(*button));
+        let pressed_gamepad_button = state.gamepads.values().find_map(|gamepad| {
+            gamepad
+                .pressed_buttons
+                .iter()
+                .next()
+                .map(|button| InputSource::GamepadButton(*button))
+        });
+        if let Some(source) = pressed_key
+            .or(pressed_mouse_button)
+            .or(pressed_gamepad_button)
+        {
+            self.last_pressed = Some(source);
+        }
+
+        for player in self.players.iter_mut() {
+            player.update(state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::Resource,
+        engine::input::{
+            GamepadAxis, GamepadButton, GamepadEvent, GamepadEventKind, GamepadId, InputActions,
+            InputContext, InputState,
+        },
+        gui::message::{KeyCode, KeyboardModifiers},
+        resource::input_map::{
+            ActionBinding, AxisBinding, InputAction, InputAxis, InputMap, InputSource,
+        },
+    };
+    use winit::{event::ElementState, keyboard::PhysicalKey};
+
+    fn map() -> InputMap {
+        InputMap {
+            actions: vec![
+                InputAction::new(
+                    "Jump",
+                    vec![
+                        ActionBinding::new(InputSource::Key(KeyCode::Space)),
+                        ActionBinding::new(InputSource::GamepadButton(GamepadButton::South)),
+                    ],
+                ),
+                InputAction::new(
+                    "Save",
+                    vec![
+                        ActionBinding::new(InputSource::Key(KeyCode::KeyS)).with_modifiers(
+                            KeyboardModifiers {
+                                control: true,
+                                ..Default::default()
+                            },
+                        ),
+                    ],
+                ),
+                InputAction::new(
+                    "Combo",
+                    vec![ActionBinding::chord([
+                        InputSource::Key(KeyCode::KeyQ),
+                        InputSource::Key(KeyCode::KeyE),
+                    ])],
+                ),
+            ],
+            axes: vec![InputAxis::new(
+                "MoveRight",
+                vec![
+                    AxisBinding::Buttons {
+                        negative: InputSource::Key(KeyCode::KeyA),
+                        positive: InputSource::Key(KeyCode::KeyD),
+                    },
+                    AxisBinding::GamepadAxis {
+                        axis: GamepadAxis::LeftStickX,
+                        scale: 1.0,
+                    },
+                ],
+            )],
+        }
+    }
+
+    fn key(state: &mut InputState, key: winit::keyboard::KeyCode, pressed: bool) {
+        let element_state = if pressed {
+            ElementState::Pressed
+        } else {
+            ElementState::Released
+        };
+        state
+            .keyboard
+            .process_key(PhysicalKey::Code(key), element_state);
+    }
+
+    fn end_frame(state: &mut InputState) {
+        state.keyboard.pressed_keys.clear();
+        state.keyboard.released_keys.clear();
+        for gamepad in state.gamepads.values_mut() {
+            gamepad.clear_frame_state();
+        }
+    }
+
+    #[test]
+    fn test_input_actions() {
+        use winit::keyboard::KeyCode as Key;
+
+        let map = Resource::new_embedded(map());
+        let mut actions = InputActions::default();
+        let keyboard = actions.add_player(InputContext::new(map.clone()));
+        let gamepad = actions.add_player(
+            InputContext::new(map)
+                .with_keyboard_mouse(false)
+                .with_gamepad(Some(GamepadId(0))),
+        );
+        let mut state = InputState::default();
+
+        key(&mut state, Key::Space, true);
+        key(&mut state, Key::KeyS, true);
+        key(&mut state, Key::KeyQ, true);
+        actions.update(&mut state);
+        let player = actions.player(keyboard).unwrap();
+        assert!(player.is_action_down("Jump") && player.is_action_pressed("Jump"));
+        // Modifiers and chords.
+        assert!(!player.is_action_down("Save"));
+        assert!(!player.is_action_down("Combo"));
+        assert!(!actions.player(gamepad).unwrap().is_action_down("Jump"));
+        assert_eq!(
+            actions.last_pressed_source().map(|s| s.is_gamepad()),
+            Some(false)
+        );
+        end_frame(&mut state);
+
+        key(&mut state, Key::ControlLeft, true);
+        key(&mut state, Key::KeyE, true);
+        key(&mut state, Key::Space, false);
+        actions.update(&mut state);
+        let player = actions.player(keyboard).unwrap();
+        assert!(player.is_action_pressed("Save"));
+        assert!(player.is_action_pressed("Combo"));
+        assert!(!player.is_action_down("Jump") && player.is_action_released("Jump"));
+        end_frame(&mut state);
+
+        // Gamepad buttons and dead zone.
+        actions.push_gamepad_event(GamepadEvent {
+            gamepad: GamepadId(0),
+            kind: GamepadEventKind::Button {
+                button: GamepadButton::South,
+                pressed: true,
+            },
+        });
+        actions.push_gamepad_event(GamepadEvent {
+            gamepad: GamepadId(0),
+            kind: GamepadEventKind::Axis {
+                axis: GamepadAxis::LeftStickX,
+                value: 0.1,
+            },
+        });
+        actions.update(&mut state);
+        let player = actions.player(gamepad).unwrap();
+        assert!(player.is_action_pressed("Jump"));
+        assert_eq!(player.axis_value("MoveRight"), 0.0);
+        assert_eq!(
+            actions.last_pressed_source(),
+            Some(InputSource::GamepadButton(GamepadButton::South))
+        );
+        end_frame(&mut state);
+
+        actions.push_gamepad_event(GamepadEvent {
+            gamepad: GamepadId(0),
+            kind: GamepadEventKind::Axis {
+                axis: GamepadAxis::LeftStickX,
+                value: -1.0,
+            },
+        });
+        key(&mut state, Key::KeyD, true);
+        actions.update(&mut state);
+        assert_eq!(
+            actions.player(gamepad).unwrap().axis_value("MoveRight"),
+            -1.0
+        );
+        assert_eq!(
+            actions.player(keyboard).unwrap().axis_value("MoveRight"),
+            1.0
+        );
+        end_frame(&mut state);
+
+        // Rebinding and its persistence.
+        let path = std::env::temp_dir().join("fyrox_test_input_bindings.ron");
+        let player = actions.player_mut(keyboard).unwrap();
+        player.rebind_action(
+            "Jump",
+            vec![ActionBinding::new(InputSource::MouseButton(1))],
+        );
+        player.save_bindings(&path).unwrap();
+        player.reset_bindings();
+        assert_eq!(
+            player.action_bindings("Jump").unwrap()[0].inputs,
+            vec![InputSource::Key(KeyCode::Space)]
+        );
+        player.load_bindings(&path).unwrap();
+        assert_eq!(
+            player.action_bindings("Jump").unwrap()[0].inputs,
+            vec![InputSource::MouseButton(1)]
+        );
+        std::fs::remove_file(&path).unwrap();
+
+        key(&mut state, Key::Space, true);
+        actions.update(&mut state);
+        assert!(!actions.player(keyboard).unwrap().is_action_down("Jump"));
+    }
 }
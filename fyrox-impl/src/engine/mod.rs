@@ -23,15 +23,19 @@
 
 #![warn(missing_docs)]
 
+pub mod crash;
 pub mod error;
 pub mod executor;
+#[cfg(feature = "gamepad")]
+pub mod gamepad;
 pub mod input;
+pub mod replay;
 pub mod task;
 
 mod hotreload;
 mod wasm_utils;
 
-use crate::engine::input::InputState;
+use crate::engine::input::{InputActions, InputState};
 use crate::renderer::ui_renderer::UiRenderInfo;
 use crate::resource::gltf::material::GLTF_SHADER;
 use crate::scene::skybox::SkyBoxKind;
@@ -53,7 +57,7 @@ use crate::{
         variable::try_inherit_properties,
         SafeLock,
     },
-    engine::{error::EngineError, task::TaskPoolHandler},
+    engine::{crash::CrashReporter, error::EngineError, task::TaskPoolHandler},
     event::Event,
     graph::{BaseSceneGraph, NodeMapping, SceneGraph},
     graphics::error::FrameworkError,
@@ -77,6 +81,8 @@ use crate::{
     renderer::Renderer,
     resource::{
         curve::{loader::CurveLoader, CurveResourceState},
+        data_table::{loader::DataTableLoader, DataTable},
+        input_map::{loader::InputMapLoader, InputMap},
         model::{loader::ModelLoader, Model, ModelResource},
         texture::{
             self, loader::TextureLoader, CompressionOptions, Texture, TextureImportOptions,
@@ -98,6 +104,7 @@ use crate::{
             tileset::{TileSet, TileSetLoader},
             CustomTileCollider, TileMapData,
         },
+        timer::TimerFired,
         Scene, SceneContainer, SceneLoader,
     },
     script::{
@@ -353,8 +360,33 @@ pub struct AsyncSceneLoader {
     receiver: Receiver<SceneLoadingResult>,
     sender: Sender<SceneLoadingResult>,
     loading_scenes: FxHashMap<PathBuf, LoadingScene>,
+    loading_order: LoadingOrder,
+    ready_scenes: FxHashMap<PathBuf, SceneLoadingResult>,
 }
 
+/// Order in which the results of scene loading are handled. Input recordings and replays use it
+/// to finish loading of scenes at the same frames.
+#[derive(Default)]
+enum LoadingOrder {
+    /// The results are handled as soon as they're ready.
+    #[default]
+    Free,
+    /// The results are handled as soon as they're ready, the paths of the handled scenes are
+    /// collected.
+    Record(Vec<PathBuf>),
+    /// The results are handled in the given order, the loader waits for each of them (but no
+    /// longer than [`REPLAY_SCENE_LOADING_TIMEOUT`]). A scene, that was not loaded in time, is
+    /// stored as missing and the rest of the scenes are not handled.
+    Replay {
+        expected: VecDeque<PathBuf>,
+        missing: Option<PathBuf>,
+    },
+}
+
+/// Maximum time the loader waits for a scene, that is expected to be loaded during a replayed
+/// frame.
+const REPLAY_SCENE_LOADING_TIMEOUT: Duration = Duration::from_secs(60);
+
 struct LoadingScene {
     reported: bool,
     path: PathBuf,
@@ -379,6 +411,70 @@ impl AsyncSceneLoader {
             receiver,
             sender,
             loading_scenes: Default::default(),
+            loading_order: Default::default(),
+            ready_scenes: Default::default(),
+        }
+    }
+
+    fn next_result(&mut self) -> Option<SceneLoadingResult> {
+        match &mut self.loading_order {
+            LoadingOrder::Free => self.receiver.try_recv().ok(),
+            LoadingOrder::Record(loaded) => {
+                let result = self.receiver.try_recv().ok()?;
+                loaded.push(result.path.clone());
+                Some(result)
+            }
+            LoadingOrder::Replay { expected, missing } => {
+                if missing.is_some() {
+                    return None;
+                }
+                let path = expected.pop_front()?;
+                if let Some(result) = self.ready_scenes.remove(&path) {
+                    return Some(result);
+                }
+                if self.loading_scenes.contains_key(&path) {
+                    while let Ok(result) = self.receiver.recv_timeout(REPLAY_SCENE_LOADING_TIMEOUT)
+                    {
+                        if result.path == path {
+                            return Some(result);
+                        }
+                        self.ready_scenes.insert(result.path.clone(), result);
+                    }
+                }
+                *missing = Some(path);
+                None
+            }
+        }
+    }
+
+    /// Starts collecting the paths of the loaded scenes, see [`Self::take_loaded_scenes`].
+    pub(crate) fn record_loading_order(&mut self) {
+        self.loading_order = LoadingOrder::Record(Default::default());
+    }
+
+    /// Returns the paths of the scenes, that were loaded since the last call.
+    pub(crate) fn take_loaded_scenes(&mut self) -> Vec<PathBuf> {
+        match &mut self.loading_order {
+            LoadingOrder::Record(loaded) => std::mem::take(loaded),
+            _ => Default::default(),
+        }
+    }
+
+    /// Forces the loader to finish loading of the given scenes (and only them) at the next update,
+    /// blocking until they're loaded or [`Self::missing_replayed_scene`] is set.
+    pub(crate) fn replay_loading_order(&mut self, paths: impl IntoIterator<Item = PathBuf>) {
+        self.loading_order = LoadingOrder::Replay {
+            expected: paths.into_iter().collect(),
+            missing: None,
+        };
+    }
+
+    /// Returns the scene, that was expected to be loaded during the last replayed update, but
+    /// wasn't requested or wasn't loaded in time.
+    pub(crate) fn missing_replayed_scene(&self) -> Option<&PathBuf> {
+        match &self.loading_order {
+            LoadingOrder::Replay { missing, .. } => missing.as_ref(),
+            _ => None,
         }
     }
 
@@ -507,6 +603,12 @@ pub struct Engine {
 
     input_state: InputState,
 
+    /// Named actions and axes of all players of a game, see [`InputActions`] docs for more info.
+    pub input_actions: InputActions,
+
+    #[cfg(feature = "gamepad")]
+    gamepad_backend: gamepad::GamepadBackend,
+
     /// A special container that is able to create nodes by their type UUID. Use a copy of this
     /// value whenever you need it as a parameter in other parts of the engine.
     pub serialization_context: Arc<SerializationContext>,
@@ -597,6 +699,7 @@ impl ScriptMessageDispatcher {
         graphics_context: &mut GraphicsContext,
         task_pool: &mut TaskPoolHandler,
         input_state: &InputState,
+        input_actions: &mut InputActions,
     ) {
         while let Ok(message) = self.message_receiver.try_recv() {
             let type_id = match message.payload.get_dynamic_type_id() {
@@ -632,6 +735,7 @@ impl ScriptMessageDispatcher {
                                 user_interfaces,
                                 script_index: 0,
                                 input_state,
+                                input_actions,
                             };
 
                             process_node_scripts(&mut context, &mut |s, ctx| {
@@ -659,6 +763,7 @@ impl ScriptMessageDispatcher {
                                     user_interfaces,
                                     script_index: 0,
                                     input_state,
+                                    input_actions,
                                 };
 
                                 if receivers.contains(&node) {
@@ -686,6 +791,7 @@ impl ScriptMessageDispatcher {
                                     user_interfaces,
                                     script_index: 0,
                                     input_state,
+                                    input_actions,
                                 };
 
                                 if receivers.contains(&node) {
@@ -712,6 +818,7 @@ impl ScriptMessageDispatcher {
                                 user_interfaces,
                                 script_index: 0,
                                 input_state,
+                                input_actions,
                             };
 
                             process_node_scripts(&mut context, &mut |s, ctx| {
@@ -777,6 +884,7 @@ impl ScriptProcessor {
         dt: f32,
         elapsed_time: f32,
         input_state: &InputState,
+        input_actions: &mut InputActions,
     ) {
         self.wait_list
             .retain_mut(|context| !context.is_all_loaded());
@@ -849,6 +957,7 @@ impl ScriptProcessor {
                     user_interfaces,
                     script_index: 0,
                     input_state,
+                    input_actions,
                 };
 
                 'init_loop: for init_loop_iteration in 0..max_iterations {
@@ -944,6 +1053,21 @@ impl ScriptProcessor {
                 }
             }
 
+            // Timers are advanced by the engine before the scripts are updated, their messages are
+            // dispatched below on the same frame.
+            for fired in scene.timers.fired() {
+                if fired.owner.is_some() {
+                    // Subscriptions are not saved, timers are. The owner of a timer that was
+                    // restored from a saved game is not subscribed yet.
+                    scripted_scene
+                        .message_dispatcher
+                        .subscribe_to::<TimerFired>(fired.owner);
+                    scripted_scene
+                        .message_sender
+                        .send_to_target(fired.owner, *fired);
+                }
+            }
+
             // Dispatch script messages only when everything is initialized and updated. This has to
             // be done this way, because all those methods could spawn new messages. However, if a new
             // message is spawned directly in `on_message` the dispatcher will correctly handle it
@@ -960,6 +1084,7 @@ impl ScriptProcessor {
                 graphics_context,
                 task_pool,
                 input_state,
+                input_actions,
             );
 
             // As the last step, destroy queued scripts.
@@ -976,6 +1101,7 @@ impl ScriptProcessor {
                 task_pool,
                 script_index: 0,
                 input_state,
+                input_actions,
             };
             while let Some((handle, mut script, index)) = destruction_queue.pop_front() {
                 context.node_handle = handle;
@@ -1006,6 +1132,7 @@ impl ScriptProcessor {
                     user_interfaces,
                     script_index: 0,
                     input_state,
+                    input_actions,
                 };
 
                 // Destroy every script instance from nodes that were still alive.
@@ -1263,6 +1390,7 @@ pub(crate) fn process_scripts<T>(
     dt: f32,
     elapsed_time: f32,
     input_state: &InputState,
+    input_actions: &mut InputActions,
     mut func: T,
 ) where
     T: FnMut(&mut Script, &mut ScriptContext),
@@ -1282,6 +1410,7 @@ pub(crate) fn process_scripts<T>(
         user_interfaces,
         script_index: 0,
         input_state,
+        input_actions,
     };
 
     for node_index in 0..context.scene.graph.capacity() {
@@ -1348,6 +1477,8 @@ pub(crate) fn initialize_resource_manager_loaders(
     state.constructors_container.add::<Shader>();
     state.constructors_container.add::<Model>();
     state.constructors_container.add::<CurveResourceState>();
+    state.constructors_container.add::<DataTable>();
+    state.constructors_container.add::<InputMap>();
     state.constructors_container.add::<SoundBuffer>();
     state.constructors_container.add::<HrirSphereResourceData>();
     state.constructors_container.add::<Material>();
@@ -1376,6 +1507,8 @@ pub(crate) fn initialize_resource_manager_loaders(
     });
     loaders.set(ShaderLoader);
     loaders.set(CurveLoader);
+    loaders.set(DataTableLoader);
+    loaders.set(InputMapLoader);
     loaders.set(HrirSphereLoader);
     loaders.set(MaterialLoader {
         resource_manager: resource_manager.clone(),
@@ -1523,6 +1656,9 @@ impl Engine {
             elapsed_time: 0.0,
             task_pool: TaskPoolHandler::new(task_pool),
             input_state: Default::default(),
+            input_actions: Default::default(),
+            #[cfg(feature = "gamepad")]
+            gamepad_backend: Default::default(),
         })
     }
 
@@ -1724,6 +1860,7 @@ impl Engine {
                             loop_controller: controller,
                             task_pool: &mut self.task_pool,
                             input_state: &self.input_state,
+                            input_actions: &mut self.input_actions,
                         };
 
                         for plugin in self.plugins.iter_mut() {
@@ -1736,7 +1873,7 @@ impl Engine {
             n += 1;
         }
 
-        while let Ok(loading_result) = self.async_scene_loader.receiver.try_recv() {
+        while let Some(loading_result) = self.async_scene_loader.next_result() {
             if let Some(request) = self
                 .async_scene_loader
                 .loading_scenes
@@ -1758,6 +1895,7 @@ impl Engine {
                     loop_controller: controller,
                     task_pool: &mut self.task_pool,
                     input_state: &self.input_state,
+                    input_actions: &mut self.input_actions,
                 };
 
                 match loading_result.result {
@@ -1826,6 +1964,7 @@ impl Engine {
                         }
 
                         let scene_handle = context.scenes.add(scene);
+                        CrashReporter::register_scene(scene_handle, &request.path);
 
                         // Notify plugins about newly loaded scene.
                         if self.plugins_enabled {
@@ -1883,9 +2022,17 @@ impl Engine {
         lag: &mut f32,
         switches: FxHashMap<Handle<Scene>, GraphUpdateSwitches>,
     ) {
+        #[cfg(feature = "gamepad")]
+        self.gamepad_backend.poll(&mut self.input_actions);
+        self.input_actions.update(&mut self.input_state);
+
         // Run some plugin and script methods, potentially causing nodes to be added
         // or removed. This is where most of the rules of the game happen.
         self.update_plugins(dt, controller, lag);
+        // Timers run in every enabled scene, not only in the scenes with scripts.
+        for scene in self.scenes.iter_mut().filter(|s| *s.enabled) {
+            scene.timers.update(dt, &scene.graph);
+        }
         self.handle_scripts(dt);
 
         // Now that the plugins and scripts have made whatever changes are needed, we must respond
@@ -1954,12 +2101,15 @@ impl Engine {
         self.performance_statistics.ui_time = instant::Instant::now() - time;
         self.elapsed_time += dt;
 
-        if let GraphicsContext::Initialized(_) = self.graphics_context {
-            self.post_update_plugins(dt, controller, lag);
+        self.post_update_plugins(dt, controller, lag);
 
-            self.input_state.mouse.speed = Vector2::default();
-            self.input_state.keyboard.released_keys.clear();
-            self.input_state.keyboard.pressed_keys.clear();
+        // Per-frame input state must be reset in headless mode as well, otherwise replayed input
+        // would diverge from the one recorded with a window.
+        self.input_state.mouse.speed = Vector2::default();
+        self.input_state.keyboard.released_keys.clear();
+        self.input_state.keyboard.pressed_keys.clear();
+        for gamepad in self.input_state.gamepads.values_mut() {
+            gamepad.clear_frame_state();
         }
     }
 
@@ -1987,6 +2137,7 @@ impl Engine {
             dt,
             self.elapsed_time,
             &self.input_state,
+            &mut self.input_actions,
         );
 
         self.performance_statistics.scripts_time = instant::Instant::now() - time;
@@ -2020,6 +2171,7 @@ impl Engine {
                         loop_controller: controller,
                         task_pool: &mut self.task_pool,
                         input_state: &self.input_state,
+                        input_actions: &mut self.input_actions,
                     },
                 )
             } else if let Some(node_task_handler) = self.task_pool.pop_node_task_handler(result.id)
@@ -2059,6 +2211,7 @@ impl Engine {
                                         user_interfaces: &mut self.user_interfaces,
                                         script_index: node_task_handler.script_index,
                                         input_state: &self.input_state,
+                                        input_actions: &mut self.input_actions,
                                     },
                                 );
 
@@ -2113,6 +2266,7 @@ impl Engine {
                 loop_controller: controller,
                 task_pool: &mut self.task_pool,
                 input_state: &self.input_state,
+                input_actions: &mut self.input_actions,
             };
 
             for plugin in self.plugins.iter_mut() {
@@ -2147,6 +2301,7 @@ impl Engine {
                         loop_controller: controller,
                         task_pool: &mut self.task_pool,
                         input_state: &self.input_state,
+                        input_actions: &mut self.input_actions,
                     };
 
                     for plugin in self.plugins.iter_mut() {
@@ -2184,6 +2339,7 @@ impl Engine {
                 loop_controller: controller,
                 task_pool: &mut self.task_pool,
                 input_state: &self.input_state,
+                input_actions: &mut self.input_actions,
             };
 
             for plugin in self.plugins.iter_mut() {
@@ -2205,30 +2361,9 @@ impl Engine {
         match event {
             Event::WindowEvent { event, .. } => match event {
                 WindowEvent::KeyboardInput { event, .. } => {
-                    let keyboard = &mut self.input_state.keyboard;
-
-                    match event.state {
-                        ElementState::Pressed => {
-                            if keyboard
-                                .keys
-                                .get(&event.physical_key)
-                                .is_none_or(|state| *state == ElementState::Released)
-                            {
-                                keyboard.pressed_keys.insert(event.physical_key);
-                            }
-                        }
-                        ElementState::Released => {
-                            if keyboard
-                                .keys
-                                .get(&event.physical_key)
-                                .is_some_and(|state| *state == ElementState::Pressed)
-                            {
-                                keyboard.released_keys.insert(event.physical_key);
-                            }
-                        }
-                    }
-
-                    keyboard.keys.insert(event.physical_key, event.state);
+                    self.input_state
+                        .keyboard
+                        .process_key(event.physical_key, event.state);
                 }
                 WindowEvent::CursorMoved { position, .. } => {
                     self.input_state.mouse.position =
@@ -2291,6 +2426,7 @@ impl Engine {
                         loop_controller: controller,
                         task_pool: &mut self.task_pool,
                         input_state: &self.input_state,
+                        input_actions: &mut self.input_actions,
                     },
                 );
             }
@@ -2321,6 +2457,7 @@ impl Engine {
                     loop_controller: controller,
                     task_pool: &mut self.task_pool,
                     input_state: &self.input_state,
+                    input_actions: &mut self.input_actions,
                 });
             }
         }
@@ -2350,6 +2487,7 @@ impl Engine {
                     loop_controller: controller,
                     task_pool: &mut self.task_pool,
                     input_state: &self.input_state,
+                    input_actions: &mut self.input_actions,
                 });
             }
         }
@@ -2379,6 +2517,7 @@ impl Engine {
                     loop_controller: controller,
                     task_pool: &mut self.task_pool,
                     input_state: &self.input_state,
+                    input_actions: &mut self.input_actions,
                 });
             }
         }
@@ -2418,6 +2557,7 @@ impl Engine {
                     dt,
                     self.elapsed_time,
                     &self.input_state,
+                    &mut self.input_actions,
                     |script, context| {
                         if script.initialized && script.started {
                             script.on_os_event(event, context);
@@ -2523,6 +2663,7 @@ impl Engine {
                             loop_controller: controller,
                             task_pool: &mut self.task_pool,
                             input_state: &self.input_state,
+                            input_actions: &mut self.input_actions,
                         },
                     );
                 }
@@ -2547,6 +2688,7 @@ impl Engine {
                         loop_controller: controller,
                         task_pool: &mut self.task_pool,
                         input_state: &self.input_state,
+                        input_actions: &mut self.input_actions,
                     });
                 }
             }
@@ -2860,6 +3002,7 @@ impl Engine {
             loop_controller: controller,
             task_pool: &mut self.task_pool,
             input_state: &self.input_state,
+            input_actions: &mut self.input_actions,
         });
 
         Log::info(format!("Plugin {plugin_index} was successfully reloaded!"));
@@ -2937,9 +3080,12 @@ mod test {
             pool::Handle, reflect::prelude::*, task::TaskPool, type_traits::prelude::*,
             visitor::prelude::*,
         },
-        engine::{task::TaskPoolHandler, GraphicsContext, ScriptProcessor},
+        engine::{task::TaskPoolHandler, GraphicsContext, ScriptProcessor, SerializationContext},
         graph::BaseSceneGraph,
-        scene::{base::BaseBuilder, node::Node, pivot::PivotBuilder, Scene, SceneContainer},
+        scene::{
+            base::BaseBuilder, node::Node, pivot::PivotBuilder, timer::TimerFired, Scene,
+            SceneContainer,
+        },
         script::{
             ScriptContext, ScriptDeinitContext, ScriptMessageContext, ScriptMessagePayload,
             ScriptTrait,
@@ -3148,6 +3294,7 @@ mod test {
                 0.0,
                 0.0,
                 &Default::default(),
+                &mut Default::default(),
             );
 
             match iteration {
@@ -3271,6 +3418,134 @@ mod test {
         }
     }
 
+    #[derive(Debug, Clone, Default, Reflect, Visit, TypeUuidProvider, ComponentProvider)]
+    #[type_uuid(id = "c3b8e3f4-3b0d-4d0e-8a4e-1f5f0f6b9d21")]
+    struct ScriptWithTimer {
+        fired: u32,
+    }
+
+    impl ScriptTrait for ScriptWithTimer {
+        fn on_init(&mut self, ctx: &mut ScriptContext) {
+            ctx.set_timer(1.0, false);
+        }
+
+        fn on_message(
+            &mut self,
+            message: &mut dyn ScriptMessagePayload,
+            _ctx: &mut ScriptMessageContext,
+        ) {
+            if message.downcast_ref::<TimerFired>().is_some() {
+                self.fired += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn test_timer_fires_after_save_and_load() {
+        let resource_manager =
+            ResourceManager::new(Arc::new(FsResourceIo), Arc::new(Default::default()));
+        let serialization_context = Arc::new(SerializationContext::new());
+        serialization_context
+            .script_constructors
+            .add::<ScriptWithTimer>("ScriptWithTimer");
+        let mut task_pool = TaskPoolHandler::new(Arc::new(TaskPool::new()));
+        let mut gc = GraphicsContext::Uninitialized(Default::default());
+        let mut user_interfaces = UiContainer::default();
+
+        let mut run = |scenes: &mut SceneContainer, dt: f32| {
+            // Scripts are not processed until every resource is loaded.
+            while !resource_manager.state().get_wait_context().is_all_loaded() {
+                std::thread::yield_now();
+            }
+            for scene in scenes.iter_mut() {
+                scene.timers.update(dt, &scene.graph);
+            }
+            let mut script_processor = ScriptProcessor::default();
+            for (handle, _) in scenes.pair_iter() {
+                script_processor.register_scripted_scene(handle, &resource_manager);
+            }
+            script_processor.handle_scripts(
+                scenes,
+                &mut Vec::new(),
+                &resource_manager,
+                &mut task_pool,
+                &mut gc,
+                &mut user_interfaces,
+                dt,
+                0.0,
+                &Default::default(),
+                &mut Default::default(),
+            );
+        };
+
+        let mut scene = Scene::new();
+        let node = PivotBuilder::new(BaseBuilder::new().with_script(ScriptWithTimer::default()))
+            .build(&mut scene.graph);
+        let mut scenes = SceneContainer::new(Default::default());
+        let scene_handle = scenes.add(scene);
+
+        // The timer is set and runs for half of its duration before the game is saved.
+        run(&mut scenes, 0.5);
+        run(&mut scenes, 0.5);
+        let mut visitor = Visitor::new();
+        scenes[scene_handle].save("Scene", &mut visitor).unwrap();
+        let data = visitor.save_binary_to_vec().unwrap();
+
+        let mut visitor = Visitor::load_from_memory(&data).unwrap();
+        visitor.blackboard.register(serialization_context);
+        visitor
+            .blackboard
+            .register(Arc::new(resource_manager.clone()));
+        let mut loaded = Scene::default();
+        loaded.visit("Scene", &mut visitor).unwrap();
+        let mut scenes = SceneContainer::new(Default::default());
+        let scene_handle = scenes.add(loaded);
+
+        // The restored timer fires once and is not set again.
+        run(&mut scenes, 0.6);
+        run(&mut scenes, 1.0);
+        let script = scenes[scene_handle].graph[node]
+            .try_get_script::<ScriptWithTimer>()
+            .unwrap();
+        assert_eq!(script.fired, 1);
+    }
+
+    #[test]
+    fn test_timers_run_in_scenes_without_scripts() {
+        use crate::engine::{Engine, EngineInitParams};
+
+        let task_pool = Arc::new(TaskPool::default());
+        let mut engine = Engine::new(EngineInitParams {
+            graphics_context_params: Default::default(),
+            serialization_context: Arc::new(Default::default()),
+            widget_constructors: Arc::new(Default::default()),
+            resource_manager: ResourceManager::new(Arc::new(FsResourceIo), task_pool.clone()),
+            task_pool,
+        })
+        .unwrap();
+
+        let is_running = Cell::new(true);
+        let controller = ApplicationLoopController::Headless {
+            running: &is_running,
+        };
+
+        let mut scene = Scene::new();
+        let timer = scene.timers.set_timer(Handle::NONE, 1.0, false);
+        let scene_handle = engine.scenes.add(scene);
+
+        let mut lag = 0.0;
+        engine.update(0.6, controller, &mut lag, Default::default());
+        assert!(engine.scenes[scene_handle].timers.fired().is_empty());
+        engine.update(0.6, controller, &mut lag, Default::default());
+        assert_eq!(
+            engine.scenes[scene_handle].timers.fired(),
+            [TimerFired {
+                timer,
+                owner: Handle::NONE
+            }]
+        );
+    }
+
     #[test]
     fn test_messages() {
         let resource_manager =
@@ -3315,6 +3590,7 @@ mod test {
                 0.0,
                 0.0,
                 &Default::default(),
+                &mut Default::default(),
             );
 
             match iteration {
@@ -3587,6 +3863,7 @@ mod test {
                 0.0,
                 0.0,
                 &Default::default(),
+                &mut Default::default(),
             );
 
             match iteration {
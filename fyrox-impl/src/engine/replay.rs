@@ -0,0 +1,899 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Deterministic input recording and replay. [`InputRecorder`] writes every OS event fed into the
+//! engine, the time step of every update, the paths of the scenes loaded at every update and the
+//! seeds requested from [`RngSeeds`] to a replay file. [`InputReplay`] feeds them back to the
+//! engine, which runs headless, and compares checksums of the scene state at every frame to detect
+//! divergence.
+//!
+//! The executor records input with the `--record-input <path>` argument and replays it with the
+//! `--replay-input <path>` argument, see [`crate::engine::executor::Executor`] for more info.
+//!
+//! ## Limitations
+//!
+//! `winit` does not allow creating keyboard events, so replayed keyboard input is delivered to the
+//! [`crate::engine::input::InputState`] and to the user interfaces, but not to
+//! [`crate::plugin::Plugin::on_os_event`] and script event handlers. Resources other than scenes
+//! are still loaded asynchronously, so a game must wait for the resources it needs to stay
+//! deterministic.
+
+use crate::{
+    core::{
+        io::FileError,
+        log::Log,
+        parking_lot::Mutex,
+        pool::Handle,
+        rand::{self, Rng},
+    },
+    dpi::{PhysicalPosition, PhysicalSize},
+    engine::{ApplicationLoopController, Engine},
+    event::{
+        DeviceEvent, DeviceId, ElementState, Event, MouseButton, MouseScrollDelta, TouchPhase,
+        WindowEvent,
+    },
+    graph::SceneGraph,
+    keyboard::{Key, KeyLocation, ModifiersState, PhysicalKey},
+    scene::Scene,
+    utils::{translate_event, translate_key_to_ui, translate_state},
+    window::WindowId,
+};
+use fxhash::{FxHashMap, FxHasher64};
+use fyrox_ui::message::OsEvent;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Display, Formatter},
+    fs::File,
+    hash::{Hash, Hasher},
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+/// An OS event in a replay file.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum RecordedEvent {
+    /// [`WindowEvent::KeyboardInput`].
+    KeyboardInput {
+        /// Physical key of the event.
+        physical_key: PhysicalKey,
+        /// Logical key of the event.
+        logical_key: Key,
+        /// Text produced by the key.
+        text: Option<String>,
+        /// Location of the key.
+        location: KeyLocation,
+        /// State of the key.
+        state: ElementState,
+        /// `true` if the key is held down.
+        repeat: bool,
+    },
+    /// [`WindowEvent::ModifiersChanged`].
+    ModifiersChanged(ModifiersState),
+    /// [`WindowEvent::CursorMoved`].
+    CursorMoved {
+        /// Horizontal position of the cursor, in pixels.
+        x: f64,
+        /// Vertical position of the cursor, in pixels.
+        y: f64,
+    },
+    /// [`WindowEvent::CursorEntered`].
+    CursorEntered,
+    /// [`WindowEvent::CursorLeft`].
+    CursorLeft,
+    /// [`WindowEvent::MouseInput`].
+    MouseInput {
+        /// Mouse button.
+        button: MouseButton,
+        /// State of the button.
+        state: ElementState,
+    },
+    /// [`WindowEvent::MouseWheel`].
+    MouseWheel {
+        /// Scroll amount.
+        delta: MouseScrollDelta,
+        /// Touch phase of the scroll.
+        phase: TouchPhase,
+    },
+    /// [`WindowEvent::Resized`].
+    Resized {
+        /// New width of the window.
+        width: u32,
+        /// New height of the window.
+        height: u32,
+    },
+    /// [`WindowEvent::Focused`].
+    Focused(bool),
+    /// [`WindowEvent::CloseRequested`].
+    CloseRequested,
+    /// [`DeviceEvent::MouseMotion`].
+    MouseMotion {
+        /// Horizontal motion of the mouse.
+        dx: f64,
+        /// Vertical motion of the mouse.
+        dy: f64,
+    },
+    /// [`DeviceEvent::Button`].
+    DeviceButton {
+        /// Device-specific index of the button.
+        button: u32,
+        /// State of the button.
+        state: ElementState,
+    },
+}
+
+impl RecordedEvent {
+    /// Converts an OS event to its recorded form. Returns [`None`] for the events, that do not
+    /// affect the game (such as redraw requests).
+    pub fn from_event(event: &Event<()>) -> Option<Self> {
+        match event {
+            Event::WindowEvent { event, .. } => match event {
+                WindowEvent::KeyboardInput { event, .. } => Some(Self::KeyboardInput {
+                    physical_key: event.physical_key,
+                    logical_key: event.logical_key.clone(),
+                    text: event.text.as_ref().map(|text| text.to_string()),
+                    location: event.location,
+                    state: event.state,
+                    repeat: event.repeat,
+                }),
+                WindowEvent::ModifiersChanged(modifiers) => {
+                    Some(Self::ModifiersChanged(modifiers.state()))
+                }
+                WindowEvent::CursorMoved { position, .. } => Some(Self::CursorMoved {
+                    x: position.x,
+                    y: position.y,
+                }),
+                WindowEvent::CursorEntered { .. } => Some(Self::CursorEntered),
+                WindowEvent::CursorLeft { .. } => Some(Self::CursorLeft),
+                WindowEvent::MouseInput { button, state, .. } => Some(Self::MouseInput {
+                    button: *button,
+                    state: *state,
+                }),
+                WindowEvent::MouseWheel { delta, phase, .. } => Some(Self::MouseWheel {
+                    delta: *delta,
+                    phase: *phase,
+                }),
+                WindowEvent::Resized(size) => Some(Self::Resized {
+                    width: size.width,
+                    height: size.height,
+                }),
+                WindowEvent::Focused(focused) => Some(Self::Focused(*focused)),
+                WindowEvent::CloseRequested => Some(Self::CloseRequested),
+                _ => None,
+            },
+            Event::DeviceEvent { event, .. } => match event {
+                DeviceEvent::MouseMotion { delta } => Some(Self::MouseMotion {
+                    dx: delta.0,
+                    dy: delta.1,
+                }),
+                DeviceEvent::Button { button, state } => Some(Self::DeviceButton {
+                    button: *button,
+                    state: *state,
+                }),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Converts the recorded event back to an OS event. Returns [`None`] for keyboard events,
+    /// because `winit` does not allow creating them.
+    pub fn to_event(&self) -> Option<Event<()>> {
+        let window_event = |event| {
+            Some(Event::WindowEvent {
+                window_id: WindowId::dummy(),
+                event,
+            })
+        };
+        let device_event = |event| {
+            Some(Event::DeviceEvent {
+                device_id: DeviceId::dummy(),
+                event,
+            })
+        };
+        match *self {
+            Self::KeyboardInput { .. } => None,
+            Self::ModifiersChanged(state) => {
+                window_event(WindowEvent::ModifiersChanged(state.into()))
+            }
+            Self::CursorMoved { x, y } => window_event(WindowEvent::CursorMoved {
+                device_id: DeviceId::dummy(),
+                position: PhysicalPosition::new(x, y),
+            }),
+            Self::CursorEntered => window_event(WindowEvent::CursorEntered {
+                device_id: DeviceId::dummy(),
+            }),
+            Self::CursorLeft => window_event(WindowEvent::CursorLeft {
+                device_id: DeviceId::dummy(),
+            }),
+            Self::MouseInput { button, state } => window_event(WindowEvent::MouseInput {
+                device_id: DeviceId::dummy(),
+                state,
+                button,
+            }),
+            Self::MouseWheel { delta, phase } => window_event(WindowEvent::MouseWheel {
+                device_id: DeviceId::dummy(),
+                delta,
+                phase,
+            }),
+            Self::Resized { width, height } => {
+                window_event(WindowEvent::Resized(PhysicalSize::new(width, height)))
+            }
+            Self::Focused(focused) => window_event(WindowEvent::Focused(focused)),
+            Self::CloseRequested => window_event(WindowEvent::CloseRequested),
+            Self::MouseMotion { dx, dy } => {
+                device_event(DeviceEvent::MouseMotion { delta: (dx, dy) })
+            }
+            Self::DeviceButton { button, state } => {
+                device_event(DeviceEvent::Button { button, state })
+            }
+        }
+    }
+
+    /// Feeds the event to the engine, its plugins, the scripts of the given scenes and the user
+    /// interfaces, the same way the executor does it with OS events.
+    pub(crate) fn apply(
+        &self,
+        engine: &mut Engine,
+        scripted_scenes: &[Handle<Scene>],
+        dt: f32,
+        controller: ApplicationLoopController,
+        lag: &mut f32,
+    ) {
+        let os_event = match self.to_event() {
+            Some(event) => {
+                engine.handle_os_events(&event, dt, controller, lag);
+                for &scene in scripted_scenes {
+                    engine.handle_os_event_by_scripts(&event, scene, dt);
+                }
+                match event {
+                    Event::WindowEvent { event, .. } => {
+                        if let WindowEvent::Resized(size) = event {
+                            Log::verify(engine.set_frame_size(size.into()));
+                        }
+                        translate_event(&event)
+                    }
+                    _ => None,
+                }
+            }
+            None => match self {
+                Self::KeyboardInput {
+                    physical_key,
+                    text,
+                    state,
+                    ..
+                } => {
+                    engine
+                        .input_state
+                        .keyboard
+                        .process_key(*physical_key, *state);
+                    match physical_key {
+                        PhysicalKey::Code(key) => Some(OsEvent::KeyboardInput {
+                            button: translate_key_to_ui(*key),
+                            state: translate_state(*state),
+                            text: text.clone().unwrap_or_default(),
+                        }),
+                        PhysicalKey::Unidentified(_) => None,
+                    }
+                }
+                _ => None,
+            },
+        };
+
+        if let Some(os_event) = os_event {
+            for ui in engine.user_interfaces.iter_mut() {
+                ui.process_os_event(&os_event);
+            }
+        }
+    }
+}
+
+/// The first line of a replay file.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ReplayHeader {
+    /// Version of the replay format.
+    pub version: u32,
+    /// Fixed time step of the recorded game.
+    pub fixed_time_step: f32,
+    /// Names of the nodes, that are used to calculate scene checksums. Empty list means all nodes.
+    pub checksum_nodes: Vec<String>,
+}
+
+/// A single update of the engine in a replay file.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ReplayFrame {
+    /// Time step of the update.
+    pub time_step: f32,
+    /// OS events, that were fed into the engine before the update.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<RecordedEvent>,
+    /// Paths of the scenes, that were loaded during the update.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub loaded_scenes: Vec<PathBuf>,
+    /// Random number generator seeds, that were requested for the first time before or during the
+    /// update.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub seeds: Vec<(String, u64)>,
+    /// Checksum of the scene state after the update, see [`scene_checksum`].
+    pub checksum: u64,
+}
+
+/// Calculates a checksum of the state of all the scenes: the transforms of the nodes with the given
+/// names (or of all nodes, if the list is empty). The checksum is stable between runs, so it could
+/// be used to compare the states of a recorded and a replayed game.
+pub fn scene_checksum(engine: &Engine, node_names: &[String]) -> u64 {
+    let mut hasher = FxHasher64::default();
+    for (scene_handle, scene) in engine.scenes.pair_iter() {
+        scene_handle.hash(&mut hasher);
+        for (handle, node) in scene.graph.pair_iter() {
+            if !node_names.is_empty() && !node_names.iter().any(|name| name == node.name()) {
+                continue;
+            }
+            handle.hash(&mut hasher);
+            let transform = node.local_transform();
+            let rotation = transform.rotation().coords;
+            for value in transform
+                .position()
+                .iter()
+                .chain(rotation.iter())
+                .chain(transform.scale().iter())
+                .chain(node.global_position().iter())
+            {
+                value.to_bits().hash(&mut hasher);
+            }
+        }
+    }
+    hasher.finish()
+}
+
+#[derive(Default)]
+struct SeedState {
+    seeds: FxHashMap<String, u64>,
+    recording: bool,
+    new_seeds: Vec<(String, u64)>,
+}
+
+static SEEDS: LazyLock<Mutex<SeedState>> = LazyLock::new(Default::default);
+
+/// Named random number generator seeds, that are stored in replays. A game that wants to be replayed
+/// deterministically must seed its random number generators with these seeds:
+///
+/// ```rust
+/// use fyrox_impl::{
+///     engine::replay::RngSeeds,
+///     rand::{rngs::StdRng, Rng, SeedableRng},
+/// };
+///
+/// let mut rng = StdRng::seed_from_u64(RngSeeds::get("enemies"));
+/// let health = rng.gen_range(50..100);
+/// # assert!(health >= 50);
+/// ```
+pub struct RngSeeds;
+
+impl RngSeeds {
+    /// Returns the seed with the given name. The seed is random, unless it is replayed from a
+    /// replay file, the same name gives the same seed during the whole run.
+    pub fn get(name: &str) -> u64 {
+        let mut state = SEEDS.lock();
+        if let Some(seed) = state.seeds.get(name) {
+            return *seed;
+        }
+        let seed = rand::thread_rng().gen();
+        state.seeds.insert(name.to_string(), seed);
+        if state.recording {
+            state.new_seeds.push((name.to_string(), seed));
+        }
+        seed
+    }
+
+    fn start_recording() {
+        let mut state = SEEDS.lock();
+        state.recording = true;
+        state.new_seeds = state
+            .seeds
+            .iter()
+            .map(|(name, seed)| (name.clone(), *seed))
+            .collect();
+    }
+
+    fn take_new_seeds() -> Vec<(String, u64)> {
+        std::mem::take(&mut SEEDS.lock().new_seeds)
+    }
+
+    fn set(seeds: impl IntoIterator<Item = (String, u64)>) {
+        SEEDS.lock().seeds.extend(seeds);
+    }
+}
+
+/// Version of the replay format.
+pub const REPLAY_VERSION: u32 = 1;
+
+/// Writes every OS event fed into the engine, the time steps of the updates, the loaded scenes and
+/// the random number generator seeds to a replay file. The file is written as JSON lines: the
+/// [`ReplayHeader`] first and then a [`ReplayFrame`] per update.
+pub struct InputRecorder {
+    writer: BufWriter<File>,
+    checksum_nodes: Vec<String>,
+    events: Vec<RecordedEvent>,
+}
+
+impl InputRecorder {
+    /// Creates a new replay file at the given path. Checksums are calculated for the nodes with the
+    /// given names, see [`scene_checksum`].
+    pub fn new(
+        path: &Path,
+        fixed_time_step: f32,
+        checksum_nodes: Vec<String>,
+        engine: &mut Engine,
+    ) -> Result<Self, FileError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        let header = ReplayHeader {
+            version: REPLAY_VERSION,
+            fixed_time_step,
+            checksum_nodes: checksum_nodes.clone(),
+        };
+        serde_json::to_writer(&mut writer, &header)
+            .map_err(|err| FileError::Custom(err.to_string()))?;
+        writer.write_all(b"\n")?;
+
+        engine.async_scene_loader.record_loading_order();
+        RngSeeds::start_recording();
+
+        Ok(Self {
+            writer,
+            checksum_nodes,
+            events: Default::default(),
+        })
+    }
+
+    /// Remembers the event, it will be written with the next frame.
+    pub fn record_event(&mut self, event: &Event<()>) {
+        if let Some(event) = RecordedEvent::from_event(event) {
+            self.events.push(event);
+        }
+    }
+
+    /// Writes a frame with the remembered events. Must be called after every update of the engine.
+    pub fn end_frame(&mut self, engine: &mut Engine, time_step: f32) -> Result<(), FileError> {
+        let frame = ReplayFrame {
+            time_step,
+            events: std::mem::take(&mut self.events),
+            loaded_scenes: engine.async_scene_loader.take_loaded_scenes(),
+            seeds: RngSeeds::take_new_seeds(),
+            checksum: scene_checksum(engine, &self.checksum_nodes),
+        };
+        serde_json::to_writer(&mut self.writer, &frame)
+            .map_err(|err| FileError::Custom(err.to_string()))?;
+        self.writer.write_all(b"\n")?;
+        // Bug reports often come with crashes, so the file must be complete at any moment.
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// A divergence of a replayed game from the recorded one.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReplayDivergence {
+    /// The scene state after a frame differs from the recorded one.
+    Checksum {
+        /// Index of the frame.
+        frame: usize,
+        /// Checksum of the recorded frame.
+        expected: u64,
+        /// Checksum of the replayed frame.
+        actual: u64,
+    },
+    /// A scene, that was loaded during a recorded frame, wasn't requested or wasn't loaded in time.
+    SceneLoading {
+        /// Index of the frame.
+        frame: usize,
+        /// Path of the scene.
+        path: PathBuf,
+    },
+}
+
+impl Display for ReplayDivergence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Checksum {
+                frame,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "The replay has diverged at frame {frame}: expected checksum {expected:x}, \
+                got {actual:x}."
+            ),
+            Self::SceneLoading { frame, path } => write!(
+                f,
+                "The replay has diverged at frame {frame}: scene {} is expected to be loaded, \
+                but it wasn't requested or wasn't loaded in time.",
+                path.display()
+            ),
+        }
+    }
+}
+
+/// A replay file loaded into memory.
+pub struct InputReplay {
+    /// Header of the replay.
+    pub header: ReplayHeader,
+    /// Recorded frames.
+    pub frames: Vec<ReplayFrame>,
+}
+
+impl InputReplay {
+    /// Loads a replay file.
+    pub fn load(path: &Path) -> Result<Self, FileError> {
+        let mut lines = BufReader::new(File::open(path)?).lines();
+        let parse_error = |line: usize, err: serde_json::Error| {
+            FileError::Custom(format!("{}:{line}: {err}", path.display()))
+        };
+
+        let header = lines
+            .next()
+            .ok_or_else(|| FileError::Custom(format!("{} is empty", path.display())))??;
+        let header: ReplayHeader =
+            serde_json::from_str(&header).map_err(|err| parse_error(1, err))?;
+        if header.version != REPLAY_VERSION {
+            return Err(FileError::Custom(format!(
+                "Unsupported replay version {}",
+                header.version
+            )));
+        }
+
+        let mut frames = Vec::new();
+        for (index, line) in lines.enumerate() {
+            let line = line?;
+            // The last line could be incomplete if the game has crashed while writing it.
+            match serde_json::from_str(&line) {
+                Ok(frame) => frames.push(frame),
+                Err(err) if err.is_eof() => break,
+                Err(err) => return Err(parse_error(index + 2, err)),
+            }
+        }
+
+        Ok(Self { header, frames })
+    }
+
+    /// Prepares the engine for the replay: makes the recorded seeds available through
+    /// [`RngSeeds`]. Must be called before plugins are enabled.
+    pub fn prepare(&self) {
+        RngSeeds::set(
+            self.frames
+                .iter()
+                .flat_map(|frame| frame.seeds.iter().cloned()),
+        );
+    }
+
+    /// Feeds the events of the frame with the given index to the engine and updates it with the
+    /// recorded time step. Returns an error if the replayed frame differs from the recorded one.
+    pub fn play_frame(
+        &self,
+        index: usize,
+        engine: &mut Engine,
+        scripted_scenes: &[Handle<Scene>],
+        controller: ApplicationLoopController,
+        lag: &mut f32,
+    ) -> Result<(), ReplayDivergence> {
+        let frame = &self.frames[index];
+        for event in frame.events.iter() {
+            event.apply(
+                engine,
+                scripted_scenes,
+                self.header.fixed_time_step,
+                controller,
+                lag,
+            );
+        }
+        engine
+            .async_scene_loader
+            .replay_loading_order(frame.loaded_scenes.iter().cloned());
+        engine.update(frame.time_step, controller, lag, Default::default());
+
+        if let Some(path) = engine.async_scene_loader.missing_replayed_scene() {
+            return Err(ReplayDivergence::SceneLoading {
+                frame: index,
+                path: path.clone(),
+            });
+        }
+
+        let checksum = scene_checksum(engine, &self.header.checksum_nodes);
+        if checksum != frame.checksum {
+            return Err(ReplayDivergence::Checksum {
+                frame: index,
+                expected: frame.checksum,
+                actual: checksum,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        asset::{io::FsResourceIo, manager::ResourceManager},
+        core::{reflect::prelude::*, task::TaskPool, visitor::prelude::*},
+        engine::{input::InputState, EngineInitParams},
+        keyboard::KeyCode,
+        plugin::{Plugin, PluginContext},
+    };
+    use std::{cell::Cell, sync::Arc};
+
+    /// Collects the input state, that plugins see at every update and post update.
+    #[derive(Default, Debug, Visit, Reflect)]
+    #[reflect(non_cloneable)]
+    struct InputProbe {
+        #[visit(skip)]
+        #[reflect(hidden)]
+        states: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Plugin for InputProbe {
+        fn update(&mut self, context: &mut PluginContext) {
+            self.states
+                .lock()
+                .push(format!("update {}", input_snapshot(context.input_state)));
+        }
+
+        fn post_update(&mut self, context: &mut PluginContext) {
+            self.states.lock().push(format!(
+                "post update {}",
+                input_snapshot(context.input_state)
+            ));
+        }
+    }
+
+    fn input_snapshot(input: &InputState) -> String {
+        let sorted = |items: Vec<String>| {
+            let mut items = items;
+            items.sort();
+            items
+        };
+        let keyboard = &input.keyboard;
+        let keys = sorted(
+            keyboard
+                .keys
+                .iter()
+                .map(|(key, state)| format!("{key:?} {state:?}"))
+                .collect(),
+        );
+        let pressed = sorted(
+            keyboard
+                .pressed_keys
+                .iter()
+                .map(|key| format!("{key:?}"))
+                .collect(),
+        );
+        let released = sorted(
+            keyboard
+                .released_keys
+                .iter()
+                .map(|key| format!("{key:?}"))
+                .collect(),
+        );
+        format!(
+            "keys: {keys:?}, pressed: {pressed:?}, released: {released:?}, mouse: {:?} {:?}",
+            input.mouse.position, input.mouse.speed
+        )
+    }
+
+    fn headless_engine(states: Arc<Mutex<Vec<String>>>, is_running: &Cell<bool>) -> Engine {
+        let task_pool = Arc::new(TaskPool::default());
+        let mut engine = Engine::new(EngineInitParams {
+            graphics_context_params: Default::default(),
+            serialization_context: Arc::new(Default::default()),
+            widget_constructors: Arc::new(Default::default()),
+            resource_manager: ResourceManager::new(Arc::new(FsResourceIo), task_pool.clone()),
+            task_pool,
+        })
+        .unwrap();
+        engine.add_plugin(InputProbe { states });
+        engine.enable_plugins(
+            None,
+            true,
+            ApplicationLoopController::Headless {
+                running: is_running,
+            },
+        );
+        engine
+    }
+
+    fn key(code: KeyCode, state: ElementState) -> RecordedEvent {
+        RecordedEvent::KeyboardInput {
+            physical_key: PhysicalKey::Code(code),
+            logical_key: Key::Unidentified(crate::keyboard::NativeKey::Unidentified),
+            text: None,
+            location: KeyLocation::Standard,
+            state,
+            repeat: false,
+        }
+    }
+
+    #[test]
+    fn test_record_and_replay_input() {
+        let dt = 1.0 / 60.0;
+        let frames = [
+            vec![
+                key(KeyCode::KeyW, ElementState::Pressed),
+                RecordedEvent::CursorMoved { x: 10.0, y: 20.0 },
+            ],
+            vec![RecordedEvent::MouseMotion { dx: 3.0, dy: -2.0 }],
+            vec![],
+            vec![key(KeyCode::KeyW, ElementState::Released)],
+            vec![
+                key(KeyCode::Space, ElementState::Pressed),
+                key(KeyCode::Space, ElementState::Released),
+            ],
+            vec![],
+        ];
+        let path = std::env::temp_dir().join("fyrox_record_and_replay_test.jsonl");
+
+        // Record the input the same way the executor does it in windowed mode.
+        let recorded = Arc::new(Mutex::new(Vec::new()));
+        let is_running = Cell::new(true);
+        let controller = ApplicationLoopController::Headless {
+            running: &is_running,
+        };
+        let mut engine = headless_engine(recorded.clone(), &is_running);
+        let mut recorder = InputRecorder::new(&path, dt, Vec::new(), &mut engine).unwrap();
+        let mut lag = 0.0;
+        for events in frames.iter() {
+            for event in events {
+                match (event.to_event(), event) {
+                    (Some(os_event), _) => {
+                        recorder.record_event(&os_event);
+                        engine.handle_os_events(&os_event, dt, controller, &mut lag);
+                    }
+                    // `winit` does not allow creating keyboard events.
+                    (
+                        None,
+                        RecordedEvent::KeyboardInput {
+                            physical_key,
+                            state,
+                            ..
+                        },
+                    ) => {
+                        recorder.events.push(event.clone());
+                        engine
+                            .input_state
+                            .keyboard
+                            .process_key(*physical_key, *state);
+                    }
+                    (None, _) => unreachable!(),
+                }
+            }
+            engine.update(dt, controller, &mut lag, Default::default());
+            recorder.end_frame(&mut engine, dt).unwrap();
+            assert!(engine.input_state.keyboard.pressed_keys.is_empty());
+            assert!(engine.input_state.keyboard.released_keys.is_empty());
+        }
+        drop(recorder);
+
+        let replay = InputReplay::load(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(replay.frames.len(), frames.len());
+
+        let replayed = Arc::new(Mutex::new(Vec::new()));
+        let mut engine = headless_engine(replayed.clone(), &is_running);
+        let mut lag = 0.0;
+        for index in 0..replay.frames.len() {
+            replay
+                .play_frame(index, &mut engine, &[], controller, &mut lag)
+                .unwrap();
+        }
+
+        {
+            let recorded = recorded.lock();
+            let replayed = replayed.lock();
+            assert_eq!(recorded.len(), 2 * frames.len());
+            for (frame, (recorded, replayed)) in recorded.iter().zip(replayed.iter()).enumerate() {
+                assert_eq!(
+                    recorded,
+                    replayed,
+                    "input state differs at frame {}",
+                    frame / 2
+                );
+            }
+            assert_eq!(recorded.len(), replayed.len());
+            assert!(recorded[0].contains("pressed: [\"Code(KeyW)\"]"));
+            assert!(recorded[2].contains("pressed: []"));
+        }
+
+        // A scene, that was never requested, must not block the replay.
+        let desynced = InputReplay {
+            header: replay.header.clone(),
+            frames: vec![ReplayFrame {
+                time_step: dt,
+                loaded_scenes: vec![PathBuf::from("missing.rgs")],
+                ..Default::default()
+            }],
+        };
+        assert_eq!(
+            desynced.play_frame(0, &mut engine, &[], controller, &mut lag),
+            Err(ReplayDivergence::SceneLoading {
+                frame: 0,
+                path: PathBuf::from("missing.rgs")
+            })
+        );
+    }
+
+    #[test]
+    fn test_replay_file() {
+        let event = RecordedEvent::KeyboardInput {
+            physical_key: PhysicalKey::Code(KeyCode::KeyW),
+            logical_key: Key::Character("w".into()),
+            text: Some("w".to_string()),
+            location: KeyLocation::Standard,
+            state: ElementState::Pressed,
+            repeat: false,
+        };
+        assert!(event.to_event().is_none());
+        let mouse = RecordedEvent::MouseInput {
+            button: MouseButton::Left,
+            state: ElementState::Released,
+        };
+        assert_eq!(
+            RecordedEvent::from_event(&mouse.to_event().unwrap()),
+            Some(mouse.clone())
+        );
+
+        let header = ReplayHeader {
+            version: REPLAY_VERSION,
+            fixed_time_step: 1.0 / 60.0,
+            checksum_nodes: vec!["Player".to_string()],
+        };
+        let frames = vec![
+            ReplayFrame {
+                time_step: 1.0 / 60.0,
+                events: vec![event, mouse],
+                loaded_scenes: vec![PathBuf::from("data/scene.rgs")],
+                seeds: vec![("replay_test".to_string(), 42)],
+                checksum: 7,
+            },
+            ReplayFrame {
+                time_step: 1.0 / 30.0,
+                checksum: 8,
+                ..Default::default()
+            },
+        ];
+        let mut file = serde_json::to_string(&header).unwrap();
+        for frame in frames.iter() {
+            file.push('\n');
+            file.push_str(&serde_json::to_string(frame).unwrap());
+        }
+        // Incomplete last line.
+        file.push_str("\n{\"time_step\":0.01");
+
+        let path = std::env::temp_dir().join("fyrox_replay_test.jsonl");
+        std::fs::write(&path, file).unwrap();
+        let replay = InputReplay::load(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(replay.header, header);
+        assert_eq!(replay.frames, frames);
+
+        replay.prepare();
+        assert_eq!(RngSeeds::get("replay_test"), 42);
+    }
+}
@@ -24,7 +24,7 @@
 
 pub mod dylib;
 
-use crate::engine::input::InputState;
+use crate::engine::input::{InputActions, InputState};
 use crate::engine::ApplicationLoopController;
 use crate::{
     asset::manager::ResourceManager,
@@ -38,7 +38,7 @@ use crate::{
         constructor::WidgetConstructorContainer,
         inspector::editors::PropertyEditorDefinitionContainer, message::UiMessage, UiContainer,
     },
-    scene::{Scene, SceneContainer},
+    scene::{node::Node, timer::Timer, Scene, SceneContainer},
 };
 use fyrox_core::define_as_any_trait;
 use fyrox_core::visitor::error::VisitError;
@@ -193,6 +193,28 @@ pub struct PluginContext<'a, 'b> {
     /// **Important:** this structure does not track from which device the corresponding event has
     /// come from, if you have more than one keyboard and/or mouse, use event-based approach instead!
     pub input_state: &'a InputState,
+
+    /// Named actions and axes of all players of a game, evaluated from the input state above. Use
+    /// it to query actions and axes instead of particular keys or buttons, so players can rebind
+    /// them. See [`InputActions`] docs for more info.
+    pub input_actions: &'a mut InputActions,
+}
+
+impl PluginContext<'_, '_> {
+    /// Creates a timer in the given scene. Timers without an owner do not send messages, check
+    /// [`crate::scene::timer::TimerManager::fired`] in [`Plugin::update`] instead. A script of the
+    /// owner node must be subscribed to [`crate::scene::timer::TimerFired`] messages to receive
+    /// them. Returns `None` if the scene handle is invalid.
+    pub fn set_timer(
+        &mut self,
+        scene: Handle<Scene>,
+        owner: Handle<Node>,
+        duration: f32,
+        looping: bool,
+    ) -> Option<Handle<Timer>> {
+        let scene = self.scenes.try_get_mut(scene)?;
+        Some(scene.timers.set_timer(owner, duration, looping))
+    }
 }
 
 define_as_any_trait!(PluginAsAny => Plugin);
@@ -0,0 +1,864 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Data table resource holds rows of typed values, usually gameplay tuning data kept in
+//! spreadsheets. See [`DataTable`] docs for more info.
+
+use crate::{
+    asset::{io::ResourceIo, Resource, ResourceData},
+    core::{
+        io::FileError, reflect::prelude::*, uuid::Uuid, uuid_provider, visitor::prelude::*,
+        TypeUuidProvider,
+    },
+};
+use serde::de::DeserializeOwned;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    path::Path,
+};
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+use uuid::uuid;
+
+pub mod loader;
+
+/// An error that may occur during data table loading, saving or editing.
+#[derive(Debug)]
+pub enum DataTableError {
+    /// An i/o error has occurred.
+    Io(FileError),
+
+    /// A JSON table is not valid JSON, or a row could not be converted to a Rust type.
+    Json(serde_json::Error),
+
+    /// The schema or a row of the table is malformed. Rows are counted from 1, the header (or the
+    /// column list of a JSON table) is row 0.
+    Format {
+        /// Index of the malformed row.
+        row: usize,
+        /// Description of the problem.
+        message: String,
+    },
+
+    /// The table file is neither a CSV nor a JSON file.
+    UnsupportedFormat(String),
+}
+
+impl Display for DataTableError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataTableError::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            DataTableError::Json(v) => {
+                write!(f, "A JSON error has occurred {v}")
+            }
+            DataTableError::Format { row, message } => {
+                write!(f, "Malformed data table at row {row}: {message}")
+            }
+            DataTableError::UnsupportedFormat(v) => {
+                write!(f, "Unsupported data table format {v}, must be csv or json")
+            }
+        }
+    }
+}
+
+impl Error for DataTableError {}
+
+impl From<FileError> for DataTableError {
+    fn from(e: FileError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for DataTableError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Json(e)
+    }
+}
+
+fn format_error(row: usize, message: impl Into<String>) -> DataTableError {
+    DataTableError::Format {
+        row,
+        message: message.into(),
+    }
+}
+
+/// Type of the values of a data table column.
+#[derive(
+    Copy, Clone, Debug, Default, PartialEq, Eq, Visit, Reflect, AsRefStr, EnumString, VariantNames,
+)]
+pub enum DataColumnType {
+    /// `true` or `false`.
+    Bool,
+    /// A 64-bit signed integer.
+    Integer,
+    /// A 32-bit floating point number.
+    Float,
+    /// Any text.
+    #[default]
+    String,
+}
+
+uuid_provider!(DataColumnType = "3a9b4b6b-9f0e-4a57-9f4a-8c1f3f0f0b1e");
+
+impl DataColumnType {
+    /// Name of the type in the schema of a table file.
+    pub fn name(self) -> &'static str {
+        match self {
+            DataColumnType::Bool => "bool",
+            DataColumnType::Integer => "int",
+            DataColumnType::Float => "float",
+            DataColumnType::String => "string",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [Self::Bool, Self::Integer, Self::Float, Self::String]
+            .into_iter()
+            .find(|ty| ty.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Default value of the type, used for empty cells.
+    pub fn default_cell(self) -> DataCell {
+        match self {
+            DataColumnType::Bool => DataCell::Bool(false),
+            DataColumnType::Integer => DataCell::Integer(0),
+            DataColumnType::Float => DataCell::Float(0.0),
+            DataColumnType::String => DataCell::String(String::new()),
+        }
+    }
+
+    /// Parses the text of a CSV cell. Empty cells get the default value of the type.
+    pub fn parse_cell(self, text: &str) -> Option<DataCell> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Some(self.default_cell());
+        }
+        match self {
+            DataColumnType::Bool => match text.to_lowercase().as_str() {
+                "true" | "1" => Some(DataCell::Bool(true)),
+                "false" | "0" => Some(DataCell::Bool(false)),
+                _ => None,
+            },
+            DataColumnType::Integer => text.parse().ok().map(DataCell::Integer),
+            DataColumnType::Float => text.parse().ok().map(DataCell::Float),
+            DataColumnType::String => Some(DataCell::String(text.to_string())),
+        }
+    }
+
+    fn json_cell(self, value: &serde_json::Value) -> Option<DataCell> {
+        use serde_json::Value as Json;
+        match (self, value) {
+            (_, Json::Null) => Some(self.default_cell()),
+            (DataColumnType::Bool, Json::Bool(v)) => Some(DataCell::Bool(*v)),
+            (DataColumnType::Integer, Json::Number(v)) => v.as_i64().map(DataCell::Integer),
+            (DataColumnType::Float, Json::Number(v)) => {
+                v.as_f64().map(|v| DataCell::Float(v as f32))
+            }
+            (DataColumnType::String, Json::String(v)) => Some(DataCell::String(v.clone())),
+            _ => None,
+        }
+    }
+}
+
+/// A named and typed column of a data table.
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect)]
+pub struct DataColumn {
+    /// Name of the column.
+    pub name: String,
+    /// Type of the values of the column.
+    pub column_type: DataColumnType,
+}
+
+uuid_provider!(DataColumn = "1f0f0a5e-6f55-4c3a-b0e5-0c6b8d7f2a41");
+
+impl DataColumn {
+    /// Creates a new column.
+    pub fn new(name: impl Into<String>, column_type: DataColumnType) -> Self {
+        Self {
+            name: name.into(),
+            column_type,
+        }
+    }
+
+    /// Parses a column declaration of a table file: `name:type`, where the type is one of `bool`,
+    /// `int`, `float` or `string`. Columns without a type are string columns.
+    fn parse(declaration: &str) -> Result<Self, DataTableError> {
+        let (name, column_type) = match declaration.split_once(':') {
+            Some((name, ty)) => {
+                let column_type = DataColumnType::from_name(ty.trim())
+                    .ok_or_else(|| format_error(0, format!("unknown column type {ty}")))?;
+                (name, column_type)
+            }
+            None => (declaration, DataColumnType::String),
+        };
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(format_error(0, "a column has no name"));
+        }
+        Ok(Self::new(name, column_type))
+    }
+
+    fn declaration(&self) -> String {
+        match self.column_type {
+            DataColumnType::String => self.name.clone(),
+            ty => format!("{}:{}", self.name, ty.name()),
+        }
+    }
+}
+
+/// A single value of a data table.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect, AsRefStr, EnumString, VariantNames)]
+pub enum DataCell {
+    /// A value of a [`DataColumnType::Bool`] column.
+    Bool(bool),
+    /// A value of a [`DataColumnType::Integer`] column.
+    Integer(i64),
+    /// A value of a [`DataColumnType::Float`] column.
+    Float(f32),
+    /// A value of a [`DataColumnType::String`] column.
+    String(String),
+}
+
+uuid_provider!(DataCell = "b6c2d5f4-2e7a-4b8e-9a3d-5f1e6c7a8b90");
+
+impl Default for DataCell {
+    fn default() -> Self {
+        Self::String(String::new())
+    }
+}
+
+impl DataCell {
+    /// Type of the column the value belongs to.
+    pub fn column_type(&self) -> DataColumnType {
+        match self {
+            DataCell::Bool(_) => DataColumnType::Bool,
+            DataCell::Integer(_) => DataColumnType::Integer,
+            DataCell::Float(_) => DataColumnType::Float,
+            DataCell::String(_) => DataColumnType::String,
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            DataCell::Bool(v) => (*v).into(),
+            DataCell::Integer(v) => (*v).into(),
+            // Going through the shortest text form keeps `0.1` from turning into
+            // `0.10000000149011612`.
+            DataCell::Float(v) => v
+                .to_string()
+                .parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+                .map_or(serde_json::Value::Null, serde_json::Value::Number),
+            DataCell::String(v) => v.as_str().into(),
+        }
+    }
+}
+
+impl Display for DataCell {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataCell::Bool(v) => write!(f, "{v}"),
+            DataCell::Integer(v) => write!(f, "{v}"),
+            DataCell::Float(v) => write!(f, "{v}"),
+            DataCell::String(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+/// A Rust type that can be read from a cell of a data table.
+pub trait FromDataCell: Sized {
+    /// Converts the cell, returns `None` if the cell has another type.
+    fn from_cell(cell: &DataCell) -> Option<Self>;
+}
+
+impl FromDataCell for bool {
+    fn from_cell(cell: &DataCell) -> Option<Self> {
+        match cell {
+            DataCell::Bool(v) => Some(*v),
+            _ => None,
+        }
+    }
+}
+
+impl FromDataCell for i64 {
+    fn from_cell(cell: &DataCell) -> Option<Self> {
+        match cell {
+            DataCell::Integer(v) => Some(*v),
+            _ => None,
+        }
+    }
+}
+
+impl FromDataCell for i32 {
+    fn from_cell(cell: &DataCell) -> Option<Self> {
+        i64::from_cell(cell).and_then(|v| v.try_into().ok())
+    }
+}
+
+impl FromDataCell for f32 {
+    fn from_cell(cell: &DataCell) -> Option<Self> {
+        match cell {
+            DataCell::Float(v) => Some(*v),
+            _ => None,
+        }
+    }
+}
+
+impl FromDataCell for String {
+    fn from_cell(cell: &DataCell) -> Option<Self> {
+        match cell {
+            DataCell::String(v) => Some(v.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// A row of a data table: the key of the row and a value per column.
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect)]
+pub struct DataRow {
+    /// Unique key of the row.
+    pub key: String,
+    /// Values of the row, in the order of the columns of the table.
+    pub cells: Vec<DataCell>,
+}
+
+uuid_provider!(DataRow = "7d4e2c1a-8b3f-4e6d-a5c9-2f8e1b0d6c37");
+
+impl DataRow {
+    /// Creates a new row.
+    pub fn new(key: impl Into<String>, cells: Vec<DataCell>) -> Self {
+        Self {
+            key: key.into(),
+            cells,
+        }
+    }
+}
+
+/// File format of a data table, picked by the extension of the file.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum DataTableFormat {
+    Csv,
+    Json,
+}
+
+impl DataTableFormat {
+    fn from_path(path: &Path) -> Result<Self, DataTableError> {
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        match extension.as_str() {
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            _ => Err(DataTableError::UnsupportedFormat(extension)),
+        }
+    }
+}
+
+/// Data table is a set of rows with typed columns, that is loaded from a CSV or a JSON file. It is
+/// meant for data that designers keep in spreadsheets, such as weapon stats or loot tables. Tables
+/// are regular resources, so they are hot reloaded when their file changes.
+///
+/// Every table declares its schema: a key column, whose values identify the rows, and a list of
+/// typed columns. Column declarations have the form `name:type`, where the type is one of `bool`,
+/// `int`, `float` or `string` (the default). The first declaration names the key column. In a CSV
+/// file the declarations form the header:
+///
+/// ```text
+/// name,damage:float,ammo:int,automatic:bool
+/// rifle,12.5,30,true
+/// shotgun,40,8,false
+/// ```
+///
+/// A JSON table lists the declarations under `columns` and the rows as objects:
+///
+/// ```json
+/// {
+///     "columns": ["name", "damage:float", "ammo:int", "automatic:bool"],
+///     "rows": [
+///         { "name": "rifle", "damage": 12.5, "ammo": 30, "automatic": true }
+///     ]
+/// }
+/// ```
+///
+/// Empty or missing cells get the default value of their column type. Values are read either one
+/// by one, by columns or by whole rows converted to a Rust type:
+///
+/// ```rust
+/// # use fyrox_impl::resource::data_table::DataTableResource;
+/// # use serde::Deserialize;
+/// #[derive(Deserialize)]
+/// struct Weapon {
+///     damage: f32,
+///     ammo: i32,
+/// }
+///
+/// fn weapon_stats(table: &DataTableResource) {
+///     let table = table.data_ref();
+///     let damage = table.value::<f32>("rifle", "damage");
+///     let ammo = table.column::<i32>("ammo").map(|ammo| ammo.collect::<Vec<_>>());
+///     let shotgun = table.row_as::<Weapon>("shotgun");
+///     # let _ = (damage, ammo, shotgun);
+/// }
+/// ```
+#[derive(Clone, Debug, Default, Visit, Reflect)]
+pub struct DataTable {
+    #[reflect(read_only)]
+    key_column: String,
+    // Editing the schema in the inspector would leave the cells with the wrong types, the file
+    // has to be changed instead.
+    #[reflect(read_only)]
+    columns: Vec<DataColumn>,
+    /// Rows of the table.
+    pub rows: Vec<DataRow>,
+}
+
+impl ResourceData for DataTable {
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let text = match DataTableFormat::from_path(path)? {
+            DataTableFormat::Csv => self.to_csv(),
+            DataTableFormat::Json => self.to_json()?,
+        };
+        std::fs::write(path, text)?;
+        Ok(())
+    }
+
+    fn can_be_saved(&self) -> bool {
+        true
+    }
+
+    fn try_clone_box(&self) -> Option<Box<dyn ResourceData>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+impl TypeUuidProvider for DataTable {
+    fn type_uuid() -> Uuid {
+        uuid!("c4a7e0d2-5b6f-4f1e-8d2a-9e3b7c5a1f08")
+    }
+}
+
+impl DataTable {
+    /// Creates an empty table with the given schema.
+    pub fn new(key_column: impl Into<String>, columns: Vec<DataColumn>) -> Self {
+        Self {
+            key_column: key_column.into(),
+            columns,
+            rows: Vec::new(),
+        }
+    }
+
+    /// Loads a data table from the specific file path. The format is picked by the extension.
+    pub async fn from_file(path: &Path, io: &dyn ResourceIo) -> Result<Self, DataTableError> {
+        let format = DataTableFormat::from_path(path)?;
+        let bytes = io.load_file(path).await?;
+        let text = String::from_utf8_lossy(&bytes);
+        match format {
+            DataTableFormat::Csv => Self::from_csv(&text),
+            DataTableFormat::Json => Self::from_json(&text),
+        }
+    }
+
+    /// Parses a CSV table, whose header declares the columns.
+    pub fn from_csv(text: &str) -> Result<Self, DataTableError> {
+        let mut records = parse_csv(text).into_iter();
+        let header = records
+            .next()
+            .ok_or_else(|| format_error(0, "the table has no header"))?;
+        let mut table = Self::from_declarations(header.iter().map(String::as_str))?;
+        for (index, record) in records.enumerate() {
+            let row = index + 1;
+            if record.len() != table.columns.len() + 1 {
+                return Err(format_error(
+                    row,
+                    format!(
+                        "expected {} cells, got {}",
+                        table.columns.len() + 1,
+                        record.len()
+                    ),
+                ));
+            }
+            let mut record = record.into_iter();
+            let key = record.next().unwrap_or_default();
+            let cells = table
+                .columns
+                .iter()
+                .zip(record)
+                .map(|(column, text)| {
+                    column.column_type.parse_cell(&text).ok_or_else(|| {
+                        format_error(
+                            row,
+                            format!(
+                                "{text} is not a {} ({})",
+                                column.column_type.name(),
+                                column.name
+                            ),
+                        )
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            table.push_row(row, DataRow::new(key.trim(), cells))?;
+        }
+        Ok(table)
+    }
+
+    /// Parses a JSON table: an object with the column declarations in `columns` and the rows
+    /// as objects in `rows`.
+    pub fn from_json(text: &str) -> Result<Self, DataTableError> {
+        use serde_json::Value as Json;
+
+        let json: Json = serde_json::from_str(text)?;
+        let declarations = json
+            .get("columns")
+            .and_then(Json::as_array)
+            .ok_or_else(|| format_error(0, "the table has no columns array"))?
+            .iter()
+            .map(|declaration| {
+                declaration
+                    .as_str()
+                    .ok_or_else(|| format_error(0, "column declarations must be strings"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut table = Self::from_declarations(declarations.into_iter())?;
+
+        let rows = match json.get("rows") {
+            Some(Json::Array(rows)) => rows.as_slice(),
+            None => &[],
+            Some(_) => return Err(format_error(0, "rows must be an array")),
+        };
+        for (index, object) in rows.iter().enumerate() {
+            let row = index + 1;
+            let object = object
+                .as_object()
+                .ok_or_else(|| format_error(row, "a row must be an object"))?;
+            let key = match object.get(&table.key_column) {
+                Some(Json::String(key)) => key.clone(),
+                Some(Json::Number(key)) => key.to_string(),
+                _ => return Err(format_error(row, format!("no {} key", table.key_column))),
+            };
+            let cells = table
+                .columns
+                .iter()
+                .map(|column| {
+                    let value = object.get(&column.name).unwrap_or(&Json::Null);
+                    column.column_type.json_cell(value).ok_or_else(|| {
+                        format_error(
+                            row,
+                            format!(
+                                "{value} is not a {} ({})",
+                                column.column_type.name(),
+                                column.name
+                            ),
+                        )
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            table.push_row(row, DataRow::new(key, cells))?;
+        }
+        Ok(table)
+    }
+
+    fn from_declarations<'a>(
+        mut declarations: impl Iterator<Item = &'a str>,
+    ) -> Result<Self, DataTableError> {
+        let key = DataColumn::parse(
+            declarations
+                .next()
+                .ok_or_else(|| format_error(0, "the table has no key column"))?,
+        )?;
+        if key.column_type != DataColumnType::String {
+            return Err(format_error(0, "the key column must be a string column"));
+        }
+        let columns = declarations
+            .map(DataColumn::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        for (i, column) in columns.iter().enumerate() {
+            if column.name == key.name || columns[..i].iter().any(|c| c.name == column.name) {
+                return Err(format_error(0, format!("duplicate column {}", column.name)));
+            }
+        }
+        Ok(Self::new(key.name, columns))
+    }
+
+    fn push_row(&mut self, index: usize, row: DataRow) -> Result<(), DataTableError> {
+        if row.key.is_empty() {
+            return Err(format_error(index, "the row has no key"));
+        }
+        if self.row(&row.key).is_some() {
+            return Err(format_error(index, format!("duplicate key {}", row.key)));
+        }
+        let schema_matches = row.cells.len() == self.columns.len()
+            && row
+                .cells
+                .iter()
+                .zip(self.columns.iter())
+                .all(|(cell, column)| cell.column_type() == column.column_type);
+        if !schema_matches {
+            return Err(format_error(index, "the cells do not match the columns"));
+        }
+        self.rows.push(row);
+        Ok(())
+    }
+
+    /// Adds a row to the table. Fails if the key is empty or already used, or if the cells do not
+    /// match the columns.
+    pub fn add_row(&mut self, row: DataRow) -> Result<(), DataTableError> {
+        self.push_row(self.rows.len() + 1, row)
+    }
+
+    /// Returns the name of the key column.
+    pub fn key_column(&self) -> &str {
+        &self.key_column
+    }
+
+    /// Returns the columns of the table, without the key column.
+    pub fn columns(&self) -> &[DataColumn] {
+        &self.columns
+    }
+
+    /// Returns the index of the column with the given name in the cells of a row.
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|column| column.name == name)
+    }
+
+    /// Returns the row with the given key.
+    pub fn row(&self, key: &str) -> Option<&DataRow> {
+        self.rows.iter().find(|row| row.key == key)
+    }
+
+    /// Returns the value of a column of the row with the given key.
+    pub fn cell(&self, key: &str, column: &str) -> Option<&DataCell> {
+        let index = self.column_index(column)?;
+        self.row(key)?.cells.get(index)
+    }
+
+    /// Returns the value of a column of the row with the given key, if it has the requested type.
+    pub fn value<T: FromDataCell>(&self, key: &str, column: &str) -> Option<T> {
+        self.cell(key, column).and_then(T::from_cell)
+    }
+
+    /// Returns the values of a column, in the order of the rows. Returns `None` if there is no such
+    /// column; values of another type are skipped.
+    pub fn column<T: FromDataCell>(&self, name: &str) -> Option<impl Iterator<Item = T> + '_> {
+        let index = self.column_index(name)?;
+        Some(
+            self.rows
+                .iter()
+                .filter_map(move |row| row.cells.get(index).and_then(T::from_cell)),
+        )
+    }
+
+    /// Converts the row with the given key to a Rust type. The fields of the type are filled from
+    /// the columns of the same name, including the key column.
+    pub fn row_as<T: DeserializeOwned>(&self, key: &str) -> Option<Result<T, DataTableError>> {
+        let row = self.row(key)?;
+        Some(serde_json::from_value(self.row_to_json(row)).map_err(DataTableError::from))
+    }
+
+    fn row_to_json(&self, row: &DataRow) -> serde_json::Value {
+        let mut object = serde_json::Map::new();
+        object.insert(self.key_column.clone(), row.key.as_str().into());
+        for (column, cell) in self.columns.iter().zip(row.cells.iter()) {
+            object.insert(column.name.clone(), cell.to_json());
+        }
+        serde_json::Value::Object(object)
+    }
+
+    fn declarations(&self) -> Vec<String> {
+        std::iter::once(self.key_column.clone())
+            .chain(self.columns.iter().map(DataColumn::declaration))
+            .collect()
+    }
+
+    /// Writes the table in the CSV format.
+    pub fn to_csv(&self) -> String {
+        let mut text = String::new();
+        write_csv_record(&mut text, self.declarations().iter().map(String::as_str));
+        for row in self.rows.iter() {
+            let cells = row
+                .cells
+                .iter()
+                .map(|cell| cell.to_string())
+                .collect::<Vec<_>>();
+            write_csv_record(
+                &mut text,
+                std::iter::once(row.key.as_str()).chain(cells.iter().map(String::as_str)),
+            );
+        }
+        text
+    }
+
+    /// Writes the table in the JSON format.
+    pub fn to_json(&self) -> Result<String, DataTableError> {
+        let json = serde_json::json!({
+            "columns": self.declarations(),
+            "rows": self.rows.iter().map(|row| self.row_to_json(row)).collect::<Vec<_>>(),
+        });
+        Ok(serde_json::to_string_pretty(&json)?)
+    }
+}
+
+/// Splits CSV text into records of fields. Fields may be quoted, quotes inside quoted fields are
+/// doubled. Empty lines are skipped.
+fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => quoted = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' => quoted = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                if !(record.len() == 1 && record[0].trim().is_empty()) {
+                    records.push(std::mem::take(&mut record));
+                }
+                record.clear();
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+fn write_csv_record<'a>(text: &mut String, fields: impl Iterator<Item = &'a str>) {
+    for (i, field) in fields.enumerate() {
+        if i > 0 {
+            text.push(',');
+        }
+        if field.contains([',', '"', '\n', '\r']) {
+            text.push('"');
+            text.push_str(&field.replace('"', "\"\""));
+            text.push('"');
+        } else {
+            text.push_str(field);
+        }
+    }
+    text.push('\n');
+}
+
+/// Type alias for data table resources.
+pub type DataTableResource = Resource<DataTable>;
+
+#[cfg(test)]
+mod test {
+    use super::{DataCell, DataColumn, DataColumnType, DataRow, DataTable, DataTableError};
+    use serde::Deserialize;
+
+    const CSV: &str = "name,damage:float,ammo:int,automatic:bool,label\n\
+                       rifle,12.5,30,true,\"Rifle, \"\"M4\"\"\"\n\
+                       shotgun,40,,false,Shotgun\n";
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Weapon {
+        name: String,
+        damage: f32,
+        ammo: i32,
+    }
+
+    #[test]
+    fn test_csv_table() {
+        let table = DataTable::from_csv(CSV).unwrap();
+        assert_eq!(table.key_column(), "name");
+        assert_eq!(
+            table.columns()[1],
+            DataColumn::new("ammo", DataColumnType::Integer)
+        );
+        assert_eq!(table.value::<f32>("rifle", "damage"), Some(12.5));
+        assert_eq!(
+            table.value::<String>("rifle", "label").as_deref(),
+            Some("Rifle, \"M4\"")
+        );
+        // Empty cells get the default value.
+        assert_eq!(table.value::<i64>("shotgun", "ammo"), Some(0));
+        assert_eq!(table.value::<i64>("rifle", "damage"), None);
+        assert_eq!(
+            table
+                .column::<bool>("automatic")
+                .unwrap()
+                .collect::<Vec<_>>(),
+            [true, false]
+        );
+        assert_eq!(
+            table.row_as::<Weapon>("rifle").unwrap().unwrap(),
+            Weapon {
+                name: "rifle".to_string(),
+                damage: 12.5,
+                ammo: 30
+            }
+        );
+
+        // Saving and loading gives the same table, in both formats.
+        assert_eq!(
+            DataTable::from_csv(&table.to_csv()).unwrap().rows,
+            table.rows
+        );
+        let json = table.to_json().unwrap();
+        assert_eq!(DataTable::from_json(&json).unwrap().rows, table.rows);
+    }
+
+    #[test]
+    fn test_table_errors() {
+        let err = |text: &str| match DataTable::from_csv(text) {
+            Err(DataTableError::Format { row, .. }) => row,
+            other => panic!("unexpected {other:?}"),
+        };
+        assert_eq!(err("name,damage:double\n"), 0);
+        assert_eq!(err("name,damage:float\nrifle,fast\n"), 1);
+        assert_eq!(err("name,ammo:int\nrifle,1\nrifle,2\n"), 2);
+        assert_eq!(err("name,ammo:int\nrifle\n"), 1);
+
+        let mut table = DataTable::new(
+            "name",
+            vec![DataColumn::new("ammo", DataColumnType::Integer)],
+        );
+        assert!(table
+            .add_row(DataRow::new("rifle", vec![DataCell::Integer(30)]))
+            .is_ok());
+        assert!(table
+            .add_row(DataRow::new("pistol", vec![DataCell::Float(1.0)]))
+            .is_err());
+    }
+}
@@ -0,0 +1,458 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Input map resource holds named actions and axes of a game along with the inputs they're bound
+//! to. See [`InputMap`] docs for more info.
+
+use crate::{
+    asset::{io::ResourceIo, Resource, ResourceData},
+    core::{
+        io::FileError, reflect::prelude::*, uuid::Uuid, uuid_provider, visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    engine::input::{GamepadAxis, GamepadButton},
+    gui::message::{KeyCode, KeyboardModifiers},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    path::Path,
+};
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+use uuid::uuid;
+
+pub mod loader;
+
+/// An error that may occur during input map loading.
+#[derive(Debug)]
+pub enum InputMapError {
+    /// An i/o error has occurred.
+    Io(FileError),
+
+    /// An error that may occur due to version incompatibilities.
+    Visit(VisitError),
+}
+
+impl Display for InputMapError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputMapError::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            InputMapError::Visit(v) => {
+                write!(
+                    f,
+                    "An error that may occur due to version incompatibilities. {v:?}"
+                )
+            }
+        }
+    }
+}
+
+impl From<FileError> for InputMapError {
+    fn from(e: FileError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for InputMapError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+/// Direction of an analog input (mouse motion or a gamepad axis), that is used as a button.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+    Serialize,
+    Deserialize,
+)]
+pub enum AxisDirection {
+    /// Right for horizontal axes, down for the vertical mouse axis, up for vertical gamepad axes.
+    #[default]
+    Positive,
+    /// The opposite of [`Self::Positive`].
+    Negative,
+}
+
+uuid_provider!(AxisDirection = "a0029be0-0cf7-4b53-99b1-26d0ca6dffdb");
+
+impl AxisDirection {
+    /// Returns `1.0` for the positive direction and `-1.0` for the negative one.
+    pub fn sign(self) -> f32 {
+        match self {
+            AxisDirection::Positive => 1.0,
+            AxisDirection::Negative => -1.0,
+        }
+    }
+}
+
+/// Axis of the mouse motion.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+    Serialize,
+    Deserialize,
+)]
+pub enum MouseAxis {
+    /// Horizontal motion of the mouse.
+    #[default]
+    X,
+    /// Vertical motion of the mouse.
+    Y,
+}
+
+uuid_provider!(MouseAxis = "a919f2d2-af86-4584-baf9-99394d867a5c");
+
+/// A single physical input, that could be bound to an action or an axis.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+    Serialize,
+    Deserialize,
+)]
+pub enum InputSource {
+    /// A key on a keyboard.
+    Key(KeyCode),
+    /// A mouse button, see [`crate::engine::input::Mouse::buttons_state`] docs for button indices.
+    MouseButton(u32),
+    /// Motion of the mouse along an axis in the given direction.
+    MouseAxis {
+        /// Axis of the motion.
+        axis: MouseAxis,
+        /// Direction of the motion.
+        direction: AxisDirection,
+    },
+    /// A button of a gamepad.
+    GamepadButton(GamepadButton),
+    /// A gamepad axis (a stick or a trigger) pushed in the given direction.
+    GamepadAxis {
+        /// Axis of the gamepad.
+        axis: GamepadAxis,
+        /// Direction in which the axis must be pushed.
+        direction: AxisDirection,
+    },
+}
+
+uuid_provider!(InputSource = "f7c197d4-1a13-4ff8-aa69-bad6d5d88073");
+
+impl Default for InputSource {
+    fn default() -> Self {
+        Self::Key(KeyCode::Unknown)
+    }
+}
+
+impl InputSource {
+    /// Returns `true` if the source is a part of a gamepad.
+    pub fn is_gamepad(&self) -> bool {
+        matches!(
+            self,
+            InputSource::GamepadButton(_) | InputSource::GamepadAxis { .. }
+        )
+    }
+}
+
+/// A combination of inputs that triggers an action. All the inputs of the binding must be held at
+/// the same time (a chord, for example `Q` + `E`), as well as the keyboard modifiers.
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect, Serialize, Deserialize)]
+pub struct ActionBinding {
+    /// Inputs that must be held together. A binding without inputs is never active.
+    pub inputs: Vec<InputSource>,
+    /// Keyboard modifiers that must be held in addition to the inputs. Modifiers that are not set
+    /// here are not checked, so `Shift` + `W` still triggers a binding of `W`.
+    pub modifiers: KeyboardModifiers,
+}
+
+uuid_provider!(ActionBinding = "ec31ea1f-435c-4ce0-82a3-8b707c6c2b09");
+
+impl ActionBinding {
+    /// Creates a binding of a single input without modifiers.
+    pub fn new(input: InputSource) -> Self {
+        Self {
+            inputs: vec![input],
+            modifiers: Default::default(),
+        }
+    }
+
+    /// Creates a binding of a chord, all the given inputs must be held together.
+    pub fn chord(inputs: impl IntoIterator<Item = InputSource>) -> Self {
+        Self {
+            inputs: inputs.into_iter().collect(),
+            modifiers: Default::default(),
+        }
+    }
+
+    /// Sets the keyboard modifiers, that must be held in addition to the inputs.
+    pub fn with_modifiers(mut self, modifiers: KeyboardModifiers) -> Self {
+        self.modifiers = modifiers;
+        self
+    }
+}
+
+/// A named digital action, for example "Jump" or "Fire". An action is active when any of its
+/// bindings is active.
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect)]
+pub struct InputAction {
+    /// Name of the action, it is used to query the state of the action.
+    pub name: String,
+    /// Default bindings of the action.
+    pub bindings: Vec<ActionBinding>,
+}
+
+uuid_provider!(InputAction = "3acd2a5d-b206-4dd5-af0a-9fe1cc07f863");
+
+impl InputAction {
+    /// Creates a new action with the given bindings.
+    pub fn new(name: impl Into<String>, bindings: Vec<ActionBinding>) -> Self {
+        Self {
+            name: name.into(),
+            bindings,
+        }
+    }
+}
+
+/// A source of values of an [`InputAxis`].
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+    Serialize,
+    Deserialize,
+)]
+pub enum AxisBinding {
+    /// A pair of inputs, the positive one adds `1.0` to the axis value and the negative one
+    /// subtracts `1.0` from it (for example, `D` and `A` keys for horizontal movement). Analog
+    /// inputs add their value in the given direction instead.
+    Buttons {
+        /// An input that decreases the axis value.
+        negative: InputSource,
+        /// An input that increases the axis value.
+        positive: InputSource,
+    },
+    /// Motion of the mouse, in the units of [`crate::engine::input::Mouse::speed`].
+    MouseAxis {
+        /// Axis of the motion.
+        axis: MouseAxis,
+        /// A multiplier of the motion, negative values invert the axis.
+        scale: f32,
+    },
+    /// A gamepad axis in `[-1; 1]` range, the dead zone of the [`InputAxis`] is applied to it.
+    GamepadAxis {
+        /// Axis of the gamepad.
+        axis: GamepadAxis,
+        /// A multiplier of the axis value, negative values invert the axis.
+        scale: f32,
+    },
+}
+
+uuid_provider!(AxisBinding = "ab47df65-b24f-4a1c-a3eb-fb76e496c9dd");
+
+impl Default for AxisBinding {
+    fn default() -> Self {
+        Self::Buttons {
+            negative: Default::default(),
+            positive: Default::default(),
+        }
+    }
+}
+
+/// A named analog value, for example "MoveForward" or "LookX". The value of an axis is the value
+/// of its binding with the largest magnitude.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct InputAxis {
+    /// Name of the axis, it is used to query the value of the axis.
+    pub name: String,
+    /// Default bindings of the axis.
+    pub bindings: Vec<AxisBinding>,
+    /// Gamepad axis values with smaller magnitude are treated as zero, larger values are rescaled
+    /// to start from zero. Must be in `[0; 1)` range.
+    #[reflect(min_value = 0.0, max_value = 0.99)]
+    pub dead_zone: f32,
+}
+
+uuid_provider!(InputAxis = "8acfddb3-f06c-4dfe-a94f-495f4f0111ed");
+
+impl Default for InputAxis {
+    fn default() -> Self {
+        Self {
+            name: Default::default(),
+            bindings: Default::default(),
+            dead_zone: Self::DEFAULT_DEAD_ZONE,
+        }
+    }
+}
+
+impl InputAxis {
+    /// Default dead zone of gamepad axes.
+    pub const DEFAULT_DEAD_ZONE: f32 = 0.15;
+
+    /// Creates a new axis with the given bindings and the default dead zone.
+    pub fn new(name: impl Into<String>, bindings: Vec<AxisBinding>) -> Self {
+        Self {
+            name: name.into(),
+            bindings,
+            dead_zone: Self::DEFAULT_DEAD_ZONE,
+        }
+    }
+
+    /// Applies the dead zone to a raw gamepad axis value.
+    pub fn apply_dead_zone(&self, value: f32) -> f32 {
+        let dead_zone = self.dead_zone.clamp(0.0, 0.99);
+        if value.abs() <= dead_zone {
+            0.0
+        } else {
+            value.signum() * (value.abs() - dead_zone) / (1.0 - dead_zone)
+        }
+    }
+}
+
+/// Input map is a set of named actions and axes along with their default bindings. Game code
+/// queries actions and axes by their names, instead of checking particular keys or buttons, which
+/// allows players to rebind the inputs and to use different devices without any changes in the
+/// game code. Use [`crate::engine::input::InputContext`] to evaluate an input map for a player.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox_impl::{
+/// #     gui::message::KeyCode,
+/// #     engine::input::{GamepadAxis, GamepadButton},
+/// #     resource::input_map::{
+/// #         ActionBinding, AxisBinding, InputAction, InputAxis, InputMap, InputSource,
+/// #     },
+/// # };
+/// let map = InputMap {
+///     actions: vec![InputAction::new(
+///         "Jump",
+///         vec![
+///             ActionBinding::new(InputSource::Key(KeyCode::Space)),
+///             ActionBinding::new(InputSource::GamepadButton(GamepadButton::South)),
+///         ],
+///     )],
+///     axes: vec![InputAxis::new(
+///         "MoveRight",
+///         vec![
+///             AxisBinding::Buttons {
+///                 negative: InputSource::Key(KeyCode::KeyA),
+///                 positive: InputSource::Key(KeyCode::KeyD),
+///             },
+///             AxisBinding::GamepadAxis {
+///                 axis: GamepadAxis::LeftStickX,
+///                 scale: 1.0,
+///             },
+///         ],
+///     )],
+/// };
+/// assert!(map.action("Jump").is_some());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect)]
+pub struct InputMap {
+    /// Named digital actions.
+    pub actions: Vec<InputAction>,
+    /// Named analog axes.
+    pub axes: Vec<InputAxis>,
+}
+
+impl ResourceData for InputMap {
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut visitor = Visitor::new();
+        self.visit("InputMap", &mut visitor)?;
+        visitor.save_ascii_to_file(path)?;
+        Ok(())
+    }
+
+    fn can_be_saved(&self) -> bool {
+        true
+    }
+
+    fn try_clone_box(&self) -> Option<Box<dyn ResourceData>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+impl TypeUuidProvider for InputMap {
+    fn type_uuid() -> Uuid {
+        uuid!("dc7ef9d6-8d41-4a80-a916-5787604ce3b7")
+    }
+}
+
+impl InputMap {
+    /// Loads an input map from the specific file path.
+    pub async fn from_file(path: &Path, io: &dyn ResourceIo) -> Result<Self, InputMapError> {
+        let bytes = io.load_file(path).await?;
+        let mut visitor = Visitor::load_from_memory(&bytes)?;
+        let mut map = InputMap::default();
+        map.visit("InputMap", &mut visitor)?;
+        Ok(map)
+    }
+
+    /// Searches for an action with the given name.
+    pub fn action(&self, name: &str) -> Option<&InputAction> {
+        self.actions.iter().find(|action| action.name == name)
+    }
+
+    /// Searches for an axis with the given name.
+    pub fn axis(&self, name: &str) -> Option<&InputAxis> {
+        self.axes.iter().find(|axis| axis.name == name)
+    }
+}
+
+/// Type alias for input map resources.
+pub type InputMapResource = Resource<InputMap>;
@@ -23,7 +23,9 @@
 #![warn(missing_docs)]
 
 pub mod curve;
+pub mod data_table;
 pub mod fbx;
 pub mod gltf;
+pub mod input_map;
 pub mod model;
 pub mod texture;
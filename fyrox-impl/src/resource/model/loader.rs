@@ -22,16 +22,22 @@
 
 use crate::{
     asset::{
+        derived::{BoxedImportFuture, ResourceImporter},
         io::ResourceIo,
         loader::{
             BoxedImportOptionsLoaderFuture, BoxedLoaderFuture, LoaderPayload, ResourceLoader,
         },
         manager::ResourceManager,
-        options::{try_get_import_settings, try_get_import_settings_opaque, BaseImportOptions},
+        options::{
+            try_get_import_settings, try_get_import_settings_opaque, BaseImportOptions,
+            ImportOptions,
+        },
     },
-    core::{uuid::Uuid, TypeUuidProvider},
+    core::{platform::TargetPlatform, uuid::Uuid, visitor::Visitor, TypeUuidProvider},
     engine::SerializationContext,
+    graph::NodeMapping,
     resource::model::{Model, ModelImportOptions},
+    scene::SceneLoader,
 };
 use fyrox_resource::state::LoadError;
 use std::{path::PathBuf, sync::Arc};
@@ -99,4 +105,101 @@ impl ResourceLoader for ModelLoader {
     fn default_import_options(&self) -> Option<Box<dyn BaseImportOptions>> {
         Some(Box::<ModelImportOptions>::default())
     }
+
+    fn importer(&self) -> Option<Arc<dyn ResourceImporter>> {
+        Some(Arc::new(ModelImporter {
+            resource_manager: self.resource_manager.clone(),
+            serialization_context: self.serialization_context.clone(),
+            default_import_options: self.default_import_options.clone(),
+        }))
+    }
+}
+
+/// Imports models into derived data. An artifact is the model in the native scene format, so FBX
+/// files are parsed and their mesh buffers are built only once. Native scenes (`rgs`) need no
+/// conversion and are loaded directly. The first byte of an artifact is the node mapping of the
+/// model.
+pub struct ModelImporter {
+    /// Resource manager to allow complex model loading.
+    pub resource_manager: ResourceManager,
+    /// Node constructors contains a set of constructors that allows to build a node using its
+    /// type UUID.
+    pub serialization_context: Arc<SerializationContext>,
+    /// Import options that are used when a model has no import options file.
+    pub default_import_options: ModelImportOptions,
+}
+
+impl ResourceImporter for ModelImporter {
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn settings(&self) -> Vec<u8> {
+        self.default_import_options.to_bytes()
+    }
+
+    fn import(
+        &self,
+        path: PathBuf,
+        _source: Vec<u8>,
+        _platform: TargetPlatform,
+        io: Arc<dyn ResourceIo>,
+    ) -> BoxedImportFuture {
+        let resource_manager = self.resource_manager.clone();
+        let serialization_context = self.serialization_context.clone();
+        let default_import_options = self.default_import_options.clone();
+        Box::pin(async move {
+            let import_options = try_get_import_settings(&path, &*io)
+                .await
+                .unwrap_or(default_import_options);
+            let mut model = Model::load(
+                path,
+                &*io,
+                serialization_context,
+                resource_manager,
+                import_options,
+            )
+            .await
+            .map_err(LoadError::new)?;
+
+            let mut visitor = Visitor::new();
+            model
+                .scene
+                .save("Scene", &mut visitor)
+                .map_err(LoadError::new)?;
+            let mut artifact = vec![model.mapping as u8];
+            artifact.extend(visitor.save_binary_to_vec().map_err(LoadError::new)?);
+            Ok(artifact)
+        })
+    }
+
+    fn load_derived(&self, artifact: Vec<u8>) -> BoxedLoaderFuture {
+        let resource_manager = self.resource_manager.clone();
+        let serialization_context = self.serialization_context.clone();
+        Box::pin(async move {
+            let (mapping, scene) = artifact
+                .split_first()
+                .ok_or_else(|| LoadError::new("Empty model artifact."))?;
+            let mapping = if *mapping == NodeMapping::UseNames as u8 {
+                NodeMapping::UseNames
+            } else {
+                NodeMapping::UseHandles
+            };
+
+            let loader = {
+                let mut visitor = Visitor::load_from_memory(scene).map_err(LoadError::new)?;
+                SceneLoader::load(
+                    "Scene",
+                    serialization_context,
+                    resource_manager,
+                    &mut visitor,
+                    None,
+                )
+                .map_err(LoadError::new)?
+            };
+            let scene = loader.finish().await;
+
+            Ok(LoaderPayload::new(Model::new(mapping, scene)))
+        })
+    }
 }
@@ -344,12 +344,13 @@ impl Visit for ScriptRecord {
     }
 }
 
-#[allow(clippy::enum_variant_names)] // STFU
+#[allow(clippy::enum_variant_names)]// STFU
 #[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
 pub(crate) enum NodeMessageKind {
     TransformChanged,
     VisibilityChanged,
     EnabledFlagChanged,
+    TagsChanged,
 }
 
 #[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
@@ -475,6 +476,9 @@ pub struct Base {
     #[reflect(setter = "set_tag")]
     tag: InheritableVariable<String>,
 
+    #[reflect(deref)]
+    tags: TrackedProperty<InheritableVariable<Vec<String>>>,
+
     #[reflect(setter = "set_cast_shadows")]
     cast_shadows: InheritableVariable<bool>,
 
@@ -585,7 +589,9 @@ impl Base {
             .set_message_data(message_sender.clone(), self_handle);
         self.visibility
             .set_message_data(message_sender.clone(), self_handle);
-        self.enabled.set_message_data(message_sender, self_handle);
+        self.enabled
+            .set_message_data(message_sender.clone(), self_handle);
+        self.tags.set_message_data(message_sender, self_handle);
         // Kick off initial hierarchical property propagation.
         self.notify(self.self_handle, NodeMessageKind::TransformChanged);
         self.notify(self.self_handle, NodeMessageKind::VisibilityChanged);
@@ -907,6 +913,56 @@ impl Base {
         self.tag.set_value_and_mark_modified(tag)
     }
 
+    /// Returns a list of string tags of the node. Unlike [`Self::tag`], a node can have any number
+    /// of tags and the graph keeps an index of them, see
+    /// [`crate::scene::graph::Graph::find_by_tag`].
+    #[inline]
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// Checks whether the node has the given tag.
+    #[inline]
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// Sets new list of tags and returns the previous one.
+    #[inline]
+    pub fn set_tags(&mut self, tags: Vec<String>) -> Vec<String> {
+        let prev = self.tags.property.set_value_and_mark_modified(tags);
+        self.notify(self.self_handle, NodeMessageKind::TagsChanged);
+        prev
+    }
+
+    /// Adds a tag to the node. Returns `false` if the node already has the tag.
+    #[inline]
+    pub fn add_tag(&mut self, tag: String) -> bool {
+        if self.has_tag(&tag) {
+            return false;
+        }
+        self.tags
+            .property
+            .get_value_mut_and_mark_modified()
+            .push(tag);
+        self.notify(self.self_handle, NodeMessageKind::TagsChanged);
+        true
+    }
+
+    /// Removes a tag from the node. Returns `false` if the node does not have the tag.
+    #[inline]
+    pub fn remove_tag(&mut self, tag: &str) -> bool {
+        let Some(index) = self.tags.iter().position(|t| t == tag) else {
+            return false;
+        };
+        self.tags
+            .property
+            .get_value_mut_and_mark_modified()
+            .remove(index);
+        self.notify(self.self_handle, NodeMessageKind::TagsChanged);
+        true
+    }
+
     /// Return the frustum_culling flag
     #[inline]
     pub fn frustum_culling(&self) -> bool {
@@ -1204,6 +1260,14 @@ impl Default for Base {
     }
 }
 
+const UNKNOWN_SCRIPT_ERROR: &str = "There is no corresponding script constructor for";
+
+/// Returns `true` if the error is the one that [`visit_opt_script`] recovers from, when the type of
+/// a script is not registered.
+pub(crate) fn is_unknown_script_error(error: &VisitError) -> bool {
+    matches!(error, VisitError::User(message) if message.starts_with(UNKNOWN_SCRIPT_ERROR))
+}
+
 // Serializes Option<Script> using given serializer.
 pub(crate) fn visit_opt_script(
     name: &str,
@@ -1224,16 +1288,22 @@ pub(crate) fn visit_opt_script(
                 .get::<SerializationContext>()
                 .expect("Visitor blackboard must contain serialization context!");
 
-            Some(
-                serialization_context
-                    .script_constructors
-                    .try_create(&script_type_uuid)
-                    .ok_or_else(|| {
+            match serialization_context
+                .script_constructors
+                .try_create(&script_type_uuid)
+            {
+                Some(script) => Some(script),
+                None => {
+                    // The script is dropped if the visitor recovers from errors.
+                    region.recover_error(
+                        "TypeUuid",
                         VisitError::User(format!(
-                            "There is no corresponding script constructor for {script_type_uuid} type!"
-                        ))
-                    })?,
-            )
+                            "{UNKNOWN_SCRIPT_ERROR} {script_type_uuid} type!"
+                        )),
+                    )?;
+                    None
+                }
+            }
         };
     }
 
@@ -1248,26 +1318,38 @@ impl Visit for Base {
     fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
         let mut region = visitor.enter_region(name)?;
 
-        self.name.visit("Name", &mut region)?;
-        self.local_transform.visit("Transform", &mut region)?;
-        self.visibility.visit("Visibility", &mut region)?;
-        self.parent.visit("Parent", &mut region)?;
-        self.children.visit("Children", &mut region)?;
-        self.resource.visit("Resource", &mut region)?;
-        self.is_resource_instance_root
-            .visit("IsResourceInstance", &mut region)?;
-        self.lifetime.visit("Lifetime", &mut region)?;
-        self.lod_group.visit("LodGroup", &mut region)?;
-        self.mobility.visit("Mobility", &mut region)?;
-        self.original_handle_in_resource
-            .visit("Original", &mut region)?;
-        self.tag.visit("Tag", &mut region)?;
-        self.properties.visit("Properties", &mut region)?;
-        self.frustum_culling.visit("FrustumCulling", &mut region)?;
-        self.cast_shadows.visit("CastShadows", &mut region)?;
-        self.instance_id.visit("InstanceId", &mut region)?;
-        self.enabled.visit("Enabled", &mut region)?;
-        self.render_mask.visit("RenderMask", &mut region)?;
+        // Broken fields are skipped when the visitor recovers from errors, which allows to repair
+        // corrupted scenes (see `VisitorFlags::RECOVER_ERRORS`).
+        macro_rules! visit_field {
+            ($field:expr, $name:literal) => {
+                if let Err(err) = $field.visit($name, &mut region) {
+                    region.recover_error($name, err)?;
+                }
+            };
+        }
+
+        visit_field!(self.name, "Name");
+        visit_field!(self.local_transform, "Transform");
+        visit_field!(self.visibility, "Visibility");
+        visit_field!(self.parent, "Parent");
+        visit_field!(self.children, "Children");
+        visit_field!(self.resource, "Resource");
+        visit_field!(self.is_resource_instance_root, "IsResourceInstance");
+        visit_field!(self.lifetime, "Lifetime");
+        visit_field!(self.lod_group, "LodGroup");
+        visit_field!(self.mobility, "Mobility");
+        visit_field!(self.original_handle_in_resource, "Original");
+        visit_field!(self.tag, "Tag");
+        // Scenes saved before tags were added do not have this field.
+        if !region.is_reading() || region.has_region("Tags") {
+            visit_field!(self.tags, "Tags");
+        }
+        visit_field!(self.properties, "Properties");
+        visit_field!(self.frustum_culling, "FrustumCulling");
+        visit_field!(self.cast_shadows, "CastShadows");
+        visit_field!(self.instance_id, "InstanceId");
+        visit_field!(self.enabled, "Enabled");
+        visit_field!(self.render_mask, "RenderMask");
 
         // Script visiting may fail for various reasons:
         //
@@ -1295,6 +1377,7 @@ pub struct BaseBuilder {
     mobility: Mobility,
     inv_bind_pose_transform: Matrix4<f32>,
     tag: String,
+    tags: Vec<String>,
     frustum_culling: bool,
     cast_shadows: bool,
     scripts: Vec<ScriptRecord>,
@@ -1322,6 +1405,7 @@ impl BaseBuilder {
             mobility: Default::default(),
             inv_bind_pose_transform: Matrix4::identity(),
             tag: Default::default(),
+            tags: Default::default(),
             frustum_culling: true,
             cast_shadows: true,
             scripts: vec![],
@@ -1406,6 +1490,13 @@ impl BaseBuilder {
         self
     }
 
+    /// Sets desired list of tags.
+    #[inline]
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
     /// Sets desired frustum_culling flag.
     #[inline]
     pub fn with_frustum_culling(mut self, frustum_culling: bool) -> Self {
@@ -1469,6 +1560,7 @@ impl BaseBuilder {
             lod_group: self.lod_group.into(),
             mobility: self.mobility.into(),
             tag: self.tag.into(),
+            tags: TrackedProperty::unbound(self.tags.into(), NodeMessageKind::TagsChanged),
             properties: Default::default(),
             frustum_culling: self.frustum_culling.into(),
             cast_shadows: self.cast_shadows.into(),
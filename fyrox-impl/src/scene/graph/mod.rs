@@ -170,6 +170,8 @@ pub struct Graph {
     pub(crate) message_receiver: Receiver<NodeMessage>,
 
     instance_id_map: FxHashMap<SceneNodeId, Handle<Node>>,
+
+    tag_index: FxHashMap<String, Vec<Handle<Node>>>,
 }
 
 impl Debug for Graph {
@@ -182,6 +184,7 @@ impl Debug for Graph {
             .field("event_broadcaster", &self.event_broadcaster)
             .field("lightmap", &self.lightmap)
             .field("instance_id_map", &self.instance_id_map)
+            .field("tag_index", &self.tag_index)
             .finish()?;
         f.write_char('\n')?;
         f.write_str(&self.summary())
@@ -219,6 +222,7 @@ impl Default for Graph {
             script_message_sender,
             lightmap: None,
             instance_id_map: Default::default(),
+            tag_index: Default::default(),
             message_receiver,
         }
     }
@@ -356,6 +360,7 @@ impl Graph {
             script_message_sender,
             lightmap: None,
             instance_id_map,
+            tag_index: Default::default(),
             message_receiver,
         }
     }
@@ -881,6 +886,8 @@ impl Graph {
         // `restore_original_handles_and_inherit_properties` the handles will have been reset back to their original values
         // which refer to nodes *within* the resource instead of nodes in this graph.
         self.remap_handles(&instances);
+        // Tags could be inherited from the resources, so the index is built once they are final.
+        self.rebuild_tag_index();
 
         self.apply_lightmap();
 
@@ -1177,6 +1184,17 @@ impl Graph {
                 NodeMessageKind::TransformChanged => Flags::TRANSFORM_CHANGED,
                 NodeMessageKind::VisibilityChanged => Flags::VISIBILITY_CHANGED,
                 NodeMessageKind::EnabledFlagChanged => Flags::ENABLED_FLAG_CHANGED,
+                NodeMessageKind::TagsChanged => {
+                    // Tags do not propagate through the hierarchy, only the index must be updated.
+                    // The previous tags are unknown at this point, so the node is removed from
+                    // every entry.
+                    self.tag_index.retain(|_, handles| {
+                        handles.retain(|h| *h != message.node);
+                        !handles.is_empty()
+                    });
+                    self.index_tags(message.node);
+                    continue;
+                }
             };
 
             let visit_flags = &mut visited_flags[message.node.index() as usize];
@@ -1427,6 +1445,7 @@ impl Graph {
     }
 
     pub(crate) fn take_reserve_internal(&mut self, handle: Handle<Node>) -> (Ticket<Node>, Node) {
+        self.unindex_tags(handle);
         let (ticket, mut node) = self.pool.take_reserve(handle);
         self.instance_id_map.remove(&node.instance_id);
         node.on_removed_from_graph(self);
@@ -1445,6 +1464,7 @@ impl Graph {
         let instance_id = node.instance_id;
         let handle = self.pool.put_back(ticket, node);
         self.instance_id_map.insert(instance_id, handle);
+        self.index_tags(handle);
         handle
     }
 
@@ -1743,6 +1763,79 @@ impl Graph {
             .get(&id)
             .and_then(|h| self.pool.try_borrow_mut(*h).map(|n| (*h, n)))
     }
+
+    /// Returns an iterator over the handles of the nodes that have the given tag. Unlike a search
+    /// by name, the lookup does not traverse the graph, it uses an index of tags instead.
+    pub fn find_by_tag<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = Handle<Node>> + 'a {
+        self.tag_index.get(tag).into_iter().flatten().copied()
+    }
+
+    /// Adds a tag to the node and updates the index immediately, so [`Self::find_by_tag`] returns
+    /// the node right away. Returns `false` if the node is invalid or already has the tag.
+    pub fn add_tag(&mut self, handle: Handle<Node>, tag: impl Into<String>) -> bool {
+        let tag = tag.into();
+        let Some(node) = self.pool.try_borrow_mut(handle) else {
+            return false;
+        };
+        if !node.add_tag(tag.clone()) {
+            return false;
+        }
+        self.tag_index.entry(tag).or_default().push(handle);
+        true
+    }
+
+    /// Removes a tag from the node and updates the index immediately. Returns `false` if the node
+    /// is invalid or does not have the tag.
+    pub fn remove_tag(&mut self, handle: Handle<Node>, tag: &str) -> bool {
+        let Some(node) = self.pool.try_borrow_mut(handle) else {
+            return false;
+        };
+        if !node.remove_tag(tag) {
+            return false;
+        }
+        if let Some(handles) = self.tag_index.get_mut(tag) {
+            handles.retain(|h| *h != handle);
+            if handles.is_empty() {
+                self.tag_index.remove(tag);
+            }
+        }
+        true
+    }
+
+    fn index_tags(&mut self, handle: Handle<Node>) {
+        let Some(node) = self.pool.try_borrow(handle) else {
+            return;
+        };
+        for tag in node.tags() {
+            let handles = self.tag_index.entry(tag.clone()).or_default();
+            if !handles.contains(&handle) {
+                handles.push(handle);
+            }
+        }
+    }
+
+    fn unindex_tags(&mut self, handle: Handle<Node>) {
+        let Some(node) = self.pool.try_borrow(handle) else {
+            return;
+        };
+        for tag in node.tags() {
+            if let Some(handles) = self.tag_index.get_mut(tag) {
+                handles.retain(|h| *h != handle);
+                if handles.is_empty() {
+                    self.tag_index.remove(tag);
+                }
+            }
+        }
+    }
+
+    fn rebuild_tag_index(&mut self) {
+        self.tag_index.clear();
+        for (handle, node) in self.pool.pair_iter() {
+            for tag in node.tags() {
+                self.tag_index.entry(tag.clone()).or_default().push(handle);
+            }
+        }
+    }
 }
 
 impl<T: ObjectOrVariant<Node>> Index<Handle<T>> for Graph {
@@ -1867,6 +1960,7 @@ impl BaseSceneGraph for Graph {
         node.on_connected_to_graph(handle, message_sender, script_message_sender);
 
         self.instance_id_map.insert(node.instance_id, handle);
+        self.index_tags(handle);
 
         handle
     }
@@ -1883,6 +1977,7 @@ impl BaseSceneGraph for Graph {
             }
 
             // Remove associated entities.
+            self.unindex_tags(handle);
             let mut node = self.pool.free(handle);
             self.instance_id_map.remove(&node.instance_id);
             node.on_removed_from_graph(self);
@@ -2237,6 +2332,47 @@ mod test {
         resource_manager
     }
 
+    #[test]
+    fn test_tag_index() {
+        let mut graph = Graph::new();
+        let a = PivotBuilder::new(BaseBuilder::new().with_tags(vec!["enemy".to_string()]))
+            .build(&mut graph);
+        let b = PivotBuilder::new(BaseBuilder::new().with_tags(vec!["enemy".to_string()]))
+            .build(&mut graph);
+        let tagged = |graph: &Graph, tag: &str| {
+            let mut handles = graph.find_by_tag(tag).collect::<Vec<_>>();
+            handles.sort_by_key(|h| h.index());
+            handles
+        };
+        assert_eq!(tagged(&graph, "enemy"), vec![a, b]);
+
+        assert!(graph.add_tag(a, "boss"));
+        assert!(!graph.add_tag(a, "boss"));
+        assert_eq!(tagged(&graph, "boss"), vec![a]);
+
+        assert!(graph.remove_tag(b, "enemy"));
+        assert_eq!(tagged(&graph, "enemy"), vec![a]);
+
+        graph[b].set_tags(vec!["boss".to_string()]);
+        graph.process_node_messages(None);
+        assert_eq!(tagged(&graph, "boss"), vec![a, b]);
+
+        // Edits through reflection (the Inspector) update the index too.
+        let base: &mut crate::scene::base::Base = &mut graph[b];
+        base.get_resolve_path_mut::<String>("tags[0]", &mut |result| {
+            *result.unwrap() = "guard".to_string();
+        });
+        graph.process_node_messages(None);
+        assert_eq!(tagged(&graph, "boss"), vec![a]);
+        assert_eq!(tagged(&graph, "guard"), vec![b]);
+        graph[b].set_tags(vec!["boss".to_string()]);
+        graph.process_node_messages(None);
+
+        graph.remove_node(a);
+        assert_eq!(tagged(&graph, "boss"), vec![b]);
+        assert!(tagged(&graph, "enemy").is_empty());
+    }
+
     #[test]
     fn test_restore_integrity() {
         let root = Path::new("test_restore_integrity");
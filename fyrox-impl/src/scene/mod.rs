@@ -48,6 +48,7 @@ pub mod sound;
 pub mod sprite;
 pub mod terrain;
 pub mod tilemap;
+pub mod timer;
 pub mod transform;
 
 use crate::{
@@ -74,6 +75,7 @@ use crate::{
         node::Node,
         skybox::{SkyBox, SkyBoxKind},
         sound::SoundEngine,
+        timer::TimerManager,
     },
     utils::navmesh::Navmesh,
 };
@@ -269,6 +271,10 @@ pub struct Scene {
     /// to false for menu's scene and when you need to open a menu - set it to true and
     /// set `enabled` flag to false for level's scene.
     pub enabled: InheritableVariable<bool>,
+
+    /// Timers of the scene. See [`TimerManager`] docs for more info.
+    #[reflect(hidden)]
+    pub timers: TimerManager,
 }
 
 impl Clone for Scene {
@@ -286,6 +292,7 @@ impl Default for Scene {
             performance_statistics: Default::default(),
             enabled: true.into(),
             sky_box: Some(SkyBoxKind::built_in_skybox().clone()).into(),
+            timers: Default::default(),
         }
     }
 }
@@ -468,6 +475,7 @@ impl Scene {
             performance_statistics: Default::default(),
             enabled: true.into(),
             sky_box: Some(SkyBoxKind::built_in_skybox().clone()).into(),
+            timers: Default::default(),
         }
     }
 
@@ -547,6 +555,7 @@ impl Scene {
                 performance_statistics: Default::default(),
                 enabled: self.enabled.clone(),
                 sky_box: self.sky_box.clone(),
+                timers: self.timers.clone_remapped(&old_new_map),
             },
             old_new_map,
         )
@@ -562,7 +571,7 @@ impl Scene {
         )
     }
 
-    fn visit(&mut self, region_name: &str, visitor: &mut Visitor) -> VisitResult {
+    pub(crate) fn visit(&mut self, region_name: &str, visitor: &mut Visitor) -> VisitResult {
         let mut region = visitor.enter_region(region_name)?;
 
         self.graph.visit("Graph", &mut region)?;
@@ -570,6 +579,10 @@ impl Scene {
         self.rendering_options
             .visit("RenderingOptions", &mut region)?;
         self.sky_box.visit("SkyBox", &mut region)?;
+        // Scenes saved before timers were added do not have this field.
+        if !region.is_reading() || region.has_region("Timers") {
+            self.timers.visit("Timers", &mut region)?;
+        }
 
         Ok(())
     }
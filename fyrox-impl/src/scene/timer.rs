@@ -0,0 +1,275 @@
+//! Timer manager of a scene. See [`TimerManager`] docs for more info.
+
+use crate::{
+    core::{
+        pool::{Handle, Pool},
+        visitor::prelude::*,
+    },
+    graph::{BaseSceneGraph, NodeHandleMap},
+    scene::{graph::Graph, node::Node},
+    script::ScriptMessagePayload,
+};
+
+/// A timer that fires once after its duration, or every time its duration passes if it is looping.
+/// See [`TimerManager`] docs for more info.
+#[derive(Clone, Debug, Default, PartialEq, Visit)]
+pub struct Timer {
+    owner: Handle<Node>,
+    duration: f32,
+    remaining: f32,
+    looping: bool,
+    paused: bool,
+}
+
+impl Timer {
+    /// Returns a handle of the node that receives [`TimerFired`] messages of the timer.
+    pub fn owner(&self) -> Handle<Node> {
+        self.owner
+    }
+
+    /// Returns the duration of the timer (in seconds).
+    pub fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    /// Returns the time (in seconds) left until the timer fires.
+    pub fn remaining(&self) -> f32 {
+        self.remaining
+    }
+
+    /// Returns `true` if the timer restarts every time it fires.
+    pub fn is_looping(&self) -> bool {
+        self.looping
+    }
+
+    /// Returns `true` if the timer is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+}
+
+/// A script message that is sent to the owner of a timer when the timer fires. The engine subscribes
+/// the owner to the message, including owners of timers restored from a saved game.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TimerFired {
+    /// A handle of the timer that fired. Handles of non-looping timers are invalid at this point.
+    pub timer: Handle<Timer>,
+    /// A handle of the owner node of the timer.
+    pub owner: Handle<Node>,
+}
+
+impl ScriptMessagePayload for TimerFired {}
+
+/// Timer manager stores the timers of a scene and advances them every frame. Timers are saved
+/// together with the scene, so they keep running after a saved game is loaded. Set timers in
+/// `on_init`, which is not called again for scripts loaded from a saved game; `on_start` is, and
+/// would set a second timer.
+///
+/// When a timer fires, the engine sends a [`TimerFired`] script message to the owner node of the
+/// timer. Timers without an owner are meant for plugins, which can check [`Self::fired`] on every
+/// update:
+///
+/// ```rust
+/// # use fyrox_impl::{
+/// #     script::{ScriptContext, ScriptMessageContext, ScriptMessagePayload},
+/// #     scene::timer::TimerFired,
+/// # };
+/// fn on_init(ctx: &mut ScriptContext) {
+///     // Fire every two seconds.
+///     let _timer = ctx.set_timer(2.0, true);
+/// }
+///
+/// fn on_message(message: &mut dyn ScriptMessagePayload, ctx: &mut ScriptMessageContext) {
+///     if let Some(fired) = message.downcast_ref::<TimerFired>() {
+///         println!("Timer {} fired!", fired.timer);
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, Default, Visit)]
+pub struct TimerManager {
+    pool: Pool<Timer>,
+    #[visit(skip)]
+    fired: Vec<TimerFired>,
+}
+
+impl TimerManager {
+    /// Creates a new timer that fires after `duration` seconds and returns its handle. A looping
+    /// timer fires every `duration` seconds until it is cleared. Pass [`Handle::NONE`] as `owner`
+    /// for a timer that does not send messages.
+    pub fn set_timer(
+        &mut self,
+        owner: Handle<Node>,
+        duration: f32,
+        looping: bool,
+    ) -> Handle<Timer> {
+        self.pool.spawn(Timer {
+            owner,
+            duration,
+            remaining: duration,
+            looping,
+            paused: false,
+        })
+    }
+
+    /// Returns a reference to the timer, if the handle is valid.
+    pub fn try_get(&self, timer: Handle<Timer>) -> Option<&Timer> {
+        self.pool.try_borrow(timer)
+    }
+
+    /// Stops the timer until it is resumed. Returns `false` if the handle is invalid.
+    pub fn pause(&mut self, timer: Handle<Timer>) -> bool {
+        self.set_paused(timer, true)
+    }
+
+    /// Resumes a paused timer. Returns `false` if the handle is invalid.
+    pub fn resume(&mut self, timer: Handle<Timer>) -> bool {
+        self.set_paused(timer, false)
+    }
+
+    fn set_paused(&mut self, timer: Handle<Timer>, paused: bool) -> bool {
+        match self.pool.try_borrow_mut(timer) {
+            Some(timer) => {
+                timer.paused = paused;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes the timer, so it will never fire. Returns `false` if the handle is invalid.
+    pub fn clear(&mut self, timer: Handle<Timer>) -> bool {
+        self.pool.try_free(timer).is_some()
+    }
+
+    /// Removes every timer of the owner.
+    pub fn clear_all_of(&mut self, owner: Handle<Node>) {
+        let timers = self
+            .pool
+            .pair_iter()
+            .filter(|(_, timer)| timer.owner == owner)
+            .map(|(handle, _)| handle)
+            .collect::<Vec<_>>();
+        for timer in timers {
+            self.pool.free(timer);
+        }
+    }
+
+    /// Returns the time (in seconds) left until the timer fires, or `None` if the handle is
+    /// invalid. Non-looping timers are removed once they fire.
+    pub fn time_remaining(&self, timer: Handle<Timer>) -> Option<f32> {
+        self.pool.try_borrow(timer).map(|timer| timer.remaining)
+    }
+
+    /// Clones the timers of a cloned graph, changing their owners to the copies of the owners.
+    /// Timers, which owners were not copied, are left out.
+    pub(crate) fn clone_remapped(&self, map: &NodeHandleMap<Node>) -> Self {
+        let mut pool = self.pool.clone();
+        let orphans = pool
+            .pair_iter_mut()
+            .filter_map(|(handle, timer)| {
+                (timer.owner.is_some() && !map.try_map(&mut timer.owner)).then_some(handle)
+            })
+            .collect::<Vec<_>>();
+        for handle in orphans {
+            pool.free(handle);
+        }
+        Self {
+            pool,
+            fired: Default::default(),
+        }
+    }
+
+    /// Returns the timers that fired during the last update.
+    pub fn fired(&self) -> &[TimerFired] {
+        &self.fired
+    }
+
+    /// Advances every running timer by `dt` seconds. Timers, which owners were removed from the
+    /// graph, are removed as well. A timer fires at most once per update; a looping timer that
+    /// overshoots several periods skips the extra ones.
+    pub fn update(&mut self, dt: f32, graph: &Graph) {
+        self.fired.clear();
+
+        let mut finished = Vec::new();
+        for (handle, timer) in self.pool.pair_iter_mut() {
+            if timer.owner.is_some() && !graph.is_valid_handle(timer.owner) {
+                finished.push(handle);
+                continue;
+            }
+
+            if timer.paused {
+                continue;
+            }
+
+            timer.remaining -= dt;
+            if timer.remaining > 0.0 {
+                continue;
+            }
+
+            self.fired.push(TimerFired {
+                timer: handle,
+                owner: timer.owner,
+            });
+
+            if timer.looping && timer.duration > 0.0 {
+                let periods = (-timer.remaining / timer.duration).floor() + 1.0;
+                timer.remaining += periods * timer.duration;
+            } else {
+                finished.push(handle);
+            }
+        }
+
+        for handle in finished {
+            self.pool.free(handle);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::scene::{
+        base::BaseBuilder,
+        graph::Graph,
+        pivot::PivotBuilder,
+        timer::{TimerFired, TimerManager},
+    };
+    use fyrox_core::pool::Handle;
+    use fyrox_graph::BaseSceneGraph;
+
+    #[test]
+    fn test_timers() {
+        let mut graph = Graph::new();
+        let owner = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+        let mut timers = TimerManager::default();
+
+        let once = timers.set_timer(owner, 1.0, false);
+        let looping = timers.set_timer(Handle::NONE, 0.5, true);
+
+        timers.update(0.6, &graph);
+        assert_eq!(
+            timers.fired(),
+            [TimerFired {
+                timer: looping,
+                owner: Handle::NONE
+            }]
+        );
+        let remaining = |timers: &TimerManager, timer| timers.time_remaining(timer).unwrap();
+        assert!((remaining(&timers, looping) - 0.4).abs() < 1.0e-5);
+
+        assert!(timers.pause(looping));
+        timers.update(0.6, &graph);
+        assert_eq!(timers.fired(), [TimerFired { timer: once, owner }]);
+        assert_eq!(timers.time_remaining(once), None);
+        assert!((remaining(&timers, looping) - 0.4).abs() < 1.0e-5);
+
+        assert!(timers.resume(looping));
+        assert!(timers.clear(looping));
+        assert!(!timers.clear(looping));
+
+        let orphan = timers.set_timer(owner, 1.0, false);
+        graph.remove_node(owner);
+        timers.update(0.1, &graph);
+        assert!(timers.fired().is_empty());
+        assert_eq!(timers.time_remaining(orphan), None);
+    }
+}
@@ -37,7 +37,12 @@ use crate::{
     event::Event,
     gui::UiContainer,
     plugin::{Plugin, PluginContainer},
-    scene::{base::NodeScriptMessage, node::Node, Scene},
+    scene::{
+        base::NodeScriptMessage,
+        node::Node,
+        timer::{Timer, TimerFired},
+        Scene,
+    },
 };
 use fyrox_core::reflect::FieldMut;
 use std::{
@@ -48,7 +53,7 @@ use std::{
     sync::mpsc::Sender,
 };
 
-use crate::engine::input::InputState;
+use crate::engine::input::{InputActions, InputState};
 pub use fyrox_core_derive::ScriptMessagePayload;
 use fyrox_graph::BaseSceneGraph;
 pub mod constructor;
@@ -414,6 +419,23 @@ pub struct ScriptContext<'a, 'b, 'c> {
     /// **Important:** this structure does not track from which device the corresponding event has
     /// come from, if you have more than one keyboard and/or mouse, use event-based approach instead!
     pub input_state: &'a InputState,
+
+    /// Named actions and axes of all players of a game, evaluated from the input state above. Use
+    /// it to query actions and axes instead of particular keys or buttons, so players can rebind
+    /// them. See [`InputActions`] docs for more info.
+    pub input_actions: &'a mut InputActions,
+}
+
+impl ScriptContext<'_, '_, '_> {
+    /// Creates a timer owned by the node of the script and subscribes the node to [`TimerFired`]
+    /// messages. The script receives a message in [`ScriptTrait::on_message`] every time the
+    /// timer fires. Use `scene.timers` to pause, resume or clear the timer. See
+    /// [`crate::scene::timer::TimerManager`] docs for more info.
+    pub fn set_timer(&mut self, duration: f32, looping: bool) -> Handle<Timer> {
+        self.message_dispatcher
+            .subscribe_to::<TimerFired>(self.handle);
+        self.scene.timers.set_timer(self.handle, duration, looping)
+    }
 }
 
 impl UniversalScriptContext for ScriptContext<'_, '_, '_> {
@@ -498,6 +520,11 @@ pub struct ScriptMessageContext<'a, 'b, 'c> {
     /// **Important:** this structure does not track from which device the corresponding event has
     /// come from, if you have more than one keyboard and/or mouse, use event-based approach instead!
     pub input_state: &'a InputState,
+
+    /// Named actions and axes of all players of a game, evaluated from the input state above. Use
+    /// it to query actions and axes instead of particular keys or buttons, so players can rebind
+    /// them. See [`InputActions`] docs for more info.
+    pub input_actions: &'a mut InputActions,
 }
 
 impl UniversalScriptContext for ScriptMessageContext<'_, '_, '_> {
@@ -573,6 +600,11 @@ pub struct ScriptDeinitContext<'a, 'b, 'c> {
     /// **Important:** this structure does not track from which device the corresponding event has
     /// come from, if you have more than one keyboard and/or mouse, use event-based approach instead!
     pub input_state: &'a InputState,
+
+    /// Named actions and axes of all players of a game, evaluated from the input state above. Use
+    /// it to query actions and axes instead of particular keys or buttons, so players can rebind
+    /// them. See [`InputActions`] docs for more info.
+    pub input_actions: &'a mut InputActions,
 }
 
 impl UniversalScriptContext for ScriptDeinitContext<'_, '_, '_> {
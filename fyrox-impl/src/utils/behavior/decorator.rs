@@ -0,0 +1,203 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Decorators are nodes with a single child that change when and how the child runs: they limit
+//! how often the child can run ([`DecoratorKind::Cooldown`]), run it several times in a row
+//! ([`DecoratorKind::Repeat`]) or give up on it after a while ([`DecoratorKind::Timeout`]).
+//! [`ConditionalNode`] runs its child only while a user-defined condition holds.
+//!
+//! Decorators measure time with the clock of the tree, which is advanced by
+//! [`BehaviorTree::tick_with_dt`].
+
+use crate::{
+    core::{pool::Handle, visitor::prelude::*},
+    utils::behavior::{BehaviorNode, BehaviorTree, Status},
+};
+use std::{
+    cell::{Cell, RefCell},
+    time::Duration,
+};
+
+/// Defines exact behavior of the decorator node.
+#[derive(Debug, PartialEq, Eq, Visit, Clone)]
+pub enum DecoratorKind {
+    /// Once the child finishes (succeeds or fails), the decorator fails without running the child
+    /// until the given amount of time passes.
+    Cooldown {
+        /// Time after the child finished, during which the child will not run.
+        duration: Duration,
+    },
+    /// Runs the child again every time it succeeds, until it succeeded the given amount of times.
+    /// Fails as soon as the child fails. Zero count repeats the child forever.
+    Repeat {
+        /// Amount of successful runs of the child.
+        count: u32,
+    },
+    /// Fails if the child is still running after the given amount of time.
+    Timeout {
+        /// Time the child is allowed to run.
+        duration: Duration,
+    },
+}
+
+impl Default for DecoratorKind {
+    fn default() -> Self {
+        Self::Repeat { count: 0 }
+    }
+}
+
+/// See module docs.
+#[derive(Debug, PartialEq, Eq, Visit, Clone)]
+pub struct Decorator<B>
+where
+    B: Clone,
+{
+    /// A handle of the decorated node.
+    pub child: Handle<BehaviorNode<B>>,
+    /// Current kind of the node.
+    pub kind: DecoratorKind,
+    // Time of the tree when the cooldown ends.
+    ready_at: Cell<Duration>,
+    // Successful runs of the child, used by repeat decorators.
+    iteration: Cell<u32>,
+    // Time of the tree when the child started running, used by timeout decorators.
+    started_at: Cell<Option<Duration>>,
+}
+
+impl<B> Default for Decorator<B>
+where
+    B: Clone,
+{
+    fn default() -> Self {
+        Self {
+            child: Default::default(),
+            kind: Default::default(),
+            ready_at: Default::default(),
+            iteration: Default::default(),
+            started_at: Default::default(),
+        }
+    }
+}
+
+impl<B> Decorator<B>
+where
+    B: Clone + 'static,
+{
+    /// Creates new decorator node of given kind.
+    pub fn new(kind: DecoratorKind, child: Handle<BehaviorNode<B>>) -> Self {
+        Self {
+            child,
+            kind,
+            ..Default::default()
+        }
+    }
+
+    /// Adds self to the tree and return handle to self.
+    pub fn add_to(self, tree: &mut BehaviorTree<B>) -> Handle<BehaviorNode<B>> {
+        tree.add_node(BehaviorNode::Decorator(self))
+    }
+
+    pub(super) fn tick(&self, time: Duration, tick_child: impl FnOnce() -> Status) -> Status {
+        match self.kind {
+            DecoratorKind::Cooldown { duration } => {
+                if time < self.ready_at.get() {
+                    return Status::Failure;
+                }
+                let status = tick_child();
+                if !matches!(status, Status::Running) {
+                    self.ready_at.set(time + duration);
+                }
+                status
+            }
+            DecoratorKind::Repeat { count } => match tick_child() {
+                Status::Success => {
+                    let iteration = self.iteration.get() + 1;
+                    if count != 0 && iteration >= count {
+                        self.iteration.set(0);
+                        Status::Success
+                    } else {
+                        self.iteration.set(iteration);
+                        Status::Running
+                    }
+                }
+                Status::Failure => {
+                    self.iteration.set(0);
+                    Status::Failure
+                }
+                Status::Running => Status::Running,
+            },
+            DecoratorKind::Timeout { duration } => {
+                let started_at = self.started_at.get().unwrap_or(time);
+                if time.saturating_sub(started_at) >= duration {
+                    self.started_at.set(None);
+                    return Status::Failure;
+                }
+                let status = tick_child();
+                self.started_at
+                    .set(matches!(status, Status::Running).then_some(started_at));
+                status
+            }
+        }
+    }
+}
+
+/// A node that runs its child only if its condition succeeds, and fails otherwise. The condition
+/// is a user-defined behavior, which can check any state of the context (for example a value on
+/// a blackboard).
+#[derive(Debug, PartialEq, Eq, Visit, Clone)]
+pub struct ConditionalNode<B>
+where
+    B: Clone,
+{
+    /// User-defined condition.
+    pub condition: Option<RefCell<B>>,
+    /// A handle of the node that runs while the condition holds.
+    pub child: Handle<BehaviorNode<B>>,
+}
+
+impl<B> Default for ConditionalNode<B>
+where
+    B: Clone,
+{
+    fn default() -> Self {
+        Self {
+            condition: None,
+            child: Default::default(),
+        }
+    }
+}
+
+impl<B> ConditionalNode<B>
+where
+    B: Clone + 'static,
+{
+    /// Creates new conditional node with given condition.
+    pub fn new(condition: B, child: Handle<BehaviorNode<B>>) -> Self {
+        Self {
+            condition: Some(RefCell::new(condition)),
+            child,
+        }
+    }
+
+    /// Adds self to the tree and return handle to self.
+    pub fn add_to(self, tree: &mut BehaviorTree<B>) -> Handle<BehaviorNode<B>> {
+        tree.add_node(BehaviorNode::Conditional(self))
+    }
+}
@@ -27,7 +27,8 @@
 //! have single parent and zero or more children nodes. Execution path of the tree is defined by the
 //! actions of the nodes. Behavior tree has a set of hard coded nodes as well as leaf nodes with
 //! user-defined logic. Hard coded nodes are: Sequence, Selector, Leaf. Leaf is special - it has
-//! custom method `tick` that can contain any logic you want.
+//! custom method `tick` that can contain any logic you want. Decorators (cooldown, repeat, timeout)
+//! and conditional nodes control when their child runs, see [`decorator`] module docs.
 //!
 //! For more info see:
 //! - [Wikipedia article](https://en.wikipedia.org/wiki/Behavior_tree_(artificial_intelligence,_robotics_and_control))
@@ -40,16 +41,20 @@ use crate::{
     },
     utils::behavior::{
         composite::{CompositeNode, CompositeNodeKind},
+        decorator::{ConditionalNode, Decorator, DecoratorKind},
         inverter::Inverter,
         leaf::LeafNode,
     },
 };
 use std::{
+    cell::Cell,
     fmt::Debug,
     ops::{Index, IndexMut},
+    time::Duration,
 };
 
 pub mod composite;
+pub mod decorator;
 pub mod inverter;
 pub mod leaf;
 
@@ -112,6 +117,10 @@ where
     /// A node, that inverts its child state ([`Status::Failure`] becomes [`Status::Success`] and vice versa, [`Status::Running`] remains
     /// unchanged)
     Inverter(Inverter<B>),
+    /// A node, that controls when and how long its child runs.
+    Decorator(Decorator<B>),
+    /// A node, that runs its child only if its condition succeeds.
+    Conditional(ConditionalNode<B>),
 }
 
 /// See module docs.
@@ -122,6 +131,9 @@ where
 {
     nodes: Pool<BehaviorNode<B>>,
     root: Handle<BehaviorNode<B>>,
+    // Time the tree was ticked for, used by decorators.
+    #[visit(optional)]
+    time: Cell<Duration>,
 }
 
 impl<B> Default for BehaviorTree<B>
@@ -132,6 +144,7 @@ where
         Self {
             nodes: Default::default(),
             root: Default::default(),
+            time: Default::default(),
         }
     }
 }
@@ -146,7 +159,11 @@ where
         let root = nodes.spawn(BehaviorNode::Root(RootNode {
             child: Default::default(),
         }));
-        Self { nodes, root }
+        Self {
+            nodes,
+            root,
+            time: Default::default(),
+        }
     }
 
     /// Adds a node to the tree, returns its handle.
@@ -217,6 +234,16 @@ where
                     Status::Running => Status::Running,
                 }
             }
+            BehaviorNode::Decorator(ref decorator) => decorator.tick(self.time.get(), || {
+                self.tick_recursive(decorator.child, context)
+            }),
+            BehaviorNode::Conditional(ref conditional) => {
+                let condition = conditional.condition.as_ref().unwrap();
+                match condition.borrow_mut().tick(context) {
+                    Status::Success => self.tick_recursive(conditional.child, context),
+                    status => status,
+                }
+            }
             BehaviorNode::Unknown => {
                 unreachable!()
             }
@@ -240,6 +267,23 @@ where
     {
         self.tick_recursive(self.root, context)
     }
+
+    /// Advances the clock of the tree by `dt` seconds and performs a single update tick with given
+    /// context. Use it instead of [`Self::tick`] if the tree has time-based decorators. Negative
+    /// `dt` does not advance the clock.
+    pub fn tick_with_dt<'a, Ctx>(&self, dt: f32, context: &mut Ctx) -> Status
+    where
+        B: Behavior<'a, Context = Ctx>,
+    {
+        let dt = Duration::try_from_secs_f32(dt).unwrap_or_default();
+        self.time.set(self.time.get() + dt);
+        self.tick(context)
+    }
+
+    /// Returns the time the tree was ticked for with [`Self::tick_with_dt`].
+    pub fn time(&self) -> Duration {
+        self.time.get()
+    }
 }
 
 impl<B: Clone + 'static> Index<Handle<BehaviorNode<B>>> for BehaviorTree<B> {
@@ -297,17 +341,67 @@ where
     Inverter::new(child).add_to(tree)
 }
 
+/// Creates a new decorator, that does not run its child for `duration` after the child finished.
+pub fn cooldown<B>(
+    duration: Duration,
+    child: Handle<BehaviorNode<B>>,
+    tree: &mut BehaviorTree<B>,
+) -> Handle<BehaviorNode<B>>
+where
+    B: Clone + 'static,
+{
+    Decorator::new(DecoratorKind::Cooldown { duration }, child).add_to(tree)
+}
+
+/// Creates a new decorator, that runs its child until it succeeded `count` times (forever if
+/// `count` is zero).
+pub fn repeat<B>(
+    count: u32,
+    child: Handle<BehaviorNode<B>>,
+    tree: &mut BehaviorTree<B>,
+) -> Handle<BehaviorNode<B>>
+where
+    B: Clone + 'static,
+{
+    Decorator::new(DecoratorKind::Repeat { count }, child).add_to(tree)
+}
+
+/// Creates a new decorator, that fails if its child is still running after `duration`.
+pub fn timeout<B>(
+    duration: Duration,
+    child: Handle<BehaviorNode<B>>,
+    tree: &mut BehaviorTree<B>,
+) -> Handle<BehaviorNode<B>>
+where
+    B: Clone + 'static,
+{
+    Decorator::new(DecoratorKind::Timeout { duration }, child).add_to(tree)
+}
+
+/// Creates a new conditional node, that runs its child only if the condition succeeds.
+pub fn conditional<B>(
+    condition: B,
+    child: Handle<BehaviorNode<B>>,
+    tree: &mut BehaviorTree<B>,
+) -> Handle<BehaviorNode<B>>
+where
+    B: Clone + 'static,
+{
+    ConditionalNode::new(condition, child).add_to(tree)
+}
+
 #[cfg(test)]
 mod test {
     use crate::{
-        core::{futures::executor::block_on, visitor::prelude::*},
+        core::{futures::executor::block_on, pool::Handle, visitor::prelude::*},
         utils::behavior::{
             composite::{CompositeNode, CompositeNodeKind},
+            conditional, cooldown, leaf,
             leaf::LeafNode,
-            Behavior, BehaviorTree, Status,
+            repeat, timeout, Behavior, BehaviorNode, BehaviorTree, Status,
         },
     };
-    use std::{env, fs::File, io::Write, path::PathBuf};
+    use std::{env, fs::File, io::Write, path::PathBuf, time::Duration};
 
     #[derive(Debug, PartialEq, Default, Visit, Clone)]
     struct WalkAction;
@@ -447,6 +541,93 @@ mod test {
         }
     }
 
+    #[derive(Default)]
+    struct Counters {
+        runs: u32,
+        // The action keeps running until this many ticks passed.
+        ticks_to_finish: u32,
+        allowed: bool,
+    }
+
+    #[derive(Debug, PartialEq, Visit, Clone, Default)]
+    enum CountBehavior {
+        #[default]
+        Run,
+        IsAllowed,
+    }
+
+    impl Behavior<'_> for CountBehavior {
+        type Context = Counters;
+
+        fn tick(&mut self, context: &mut Self::Context) -> Status {
+            match self {
+                CountBehavior::Run => {
+                    if context.ticks_to_finish > 0 {
+                        context.ticks_to_finish -= 1;
+                        return Status::Running;
+                    }
+                    context.runs += 1;
+                    Status::Success
+                }
+                CountBehavior::IsAllowed if context.allowed => Status::Success,
+                CountBehavior::IsAllowed => Status::Failure,
+            }
+        }
+    }
+
+    fn decorated(
+        decorate: impl FnOnce(
+            Handle<BehaviorNode<CountBehavior>>,
+            &mut BehaviorTree<CountBehavior>,
+        ) -> Handle<BehaviorNode<CountBehavior>>,
+    ) -> BehaviorTree<CountBehavior> {
+        let mut tree = BehaviorTree::new();
+        let run = leaf(CountBehavior::Run, &mut tree);
+        let entry = decorate(run, &mut tree);
+        tree.set_entry_node(entry);
+        tree
+    }
+
+    #[test]
+    fn test_decorators() {
+        let mut ctx = Counters::default();
+
+        let tree = decorated(|child, tree| cooldown(Duration::from_secs(1), child, tree));
+        assert!(matches!(tree.tick_with_dt(0.1, &mut ctx), Status::Success));
+        assert!(matches!(tree.tick_with_dt(0.5, &mut ctx), Status::Failure));
+        assert!(matches!(tree.tick_with_dt(0.6, &mut ctx), Status::Success));
+        assert_eq!(ctx.runs, 2);
+
+        let tree = decorated(|child, tree| repeat(3, child, tree));
+        ctx.runs = 0;
+        assert!(matches!(tree.tick(&mut ctx), Status::Running));
+        assert!(matches!(tree.tick(&mut ctx), Status::Running));
+        assert!(matches!(tree.tick(&mut ctx), Status::Success));
+        assert_eq!(ctx.runs, 3);
+
+        let tree = decorated(|child, tree| timeout(Duration::from_secs(1), child, tree));
+        ctx.ticks_to_finish = 100;
+        assert!(matches!(tree.tick_with_dt(0.5, &mut ctx), Status::Running));
+        assert!(matches!(tree.tick_with_dt(0.6, &mut ctx), Status::Running));
+        assert!(matches!(tree.tick_with_dt(0.5, &mut ctx), Status::Failure));
+        // The timeout starts over on the next run of the child.
+        assert!(matches!(tree.tick_with_dt(0.6, &mut ctx), Status::Running));
+
+        let tree = decorated(|child, tree| conditional(CountBehavior::IsAllowed, child, tree));
+        ctx.ticks_to_finish = 0;
+        ctx.runs = 0;
+        assert!(matches!(tree.tick(&mut ctx), Status::Failure));
+        ctx.allowed = true;
+        assert!(matches!(tree.tick(&mut ctx), Status::Success));
+        assert_eq!(ctx.runs, 1);
+    }
+
+    #[test]
+    fn test_behavior_node_is_eq() {
+        fn is_eq<T: Eq>() {}
+        is_eq::<BehaviorNode<u32>>();
+    }
+
     #[test]
     fn test_behavior_save_load() {
         let (bin, txt) = {
@@ -28,6 +28,7 @@ pub mod lightmap;
 pub mod navmesh;
 pub mod raw_mesh;
 pub mod uvgen;
+pub mod validation;
 
 use crate::{
     core::algebra::{Vector2, Vector3},
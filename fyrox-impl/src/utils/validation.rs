@@ -0,0 +1,895 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Validation and repair of native asset files (scenes, user interfaces and materials). See
+//! [`AssetValidator`] docs for more info.
+
+use crate::{
+    asset::{
+        collect_used_resources,
+        constructor::ResourceConstructorContainer,
+        io::FsResourceIo,
+        manager::ResourceManager,
+        untyped::{ResourceKind, UntypedResource},
+    },
+    core::{
+        futures::executor::block_on,
+        log::Log,
+        pool::Handle,
+        reflect::prelude::*,
+        task::TaskPool,
+        uuid::Uuid,
+        visitor::{error::VisitError, Format, Visit, Visitor, VisitorFlags},
+        SafeLock,
+    },
+    engine::{initialize_resource_manager_loaders, SerializationContext},
+    fxhash::FxHashSet,
+    graph::{BaseSceneGraph, SceneGraph},
+    gui::{
+        constructor::{new_widget_constructor_container, WidgetConstructorContainer},
+        widget::Widget,
+        UiNode, UserInterface,
+    },
+    material::Material,
+    scene::{
+        base::{is_unknown_script_error, Base},
+        node::Node,
+        Scene,
+    },
+};
+use std::{
+    any::TypeId,
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+    sync::{mpsc, Arc},
+};
+
+/// A kind of problem found in an asset file.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ValidationIssueKind {
+    /// The file contains data that is not read by the engine (for example, a field was removed or
+    /// renamed).
+    UnknownField,
+    /// A field is missing in the file or cannot be read (for example, because its type was
+    /// changed).
+    MissingField,
+    /// A handle points to a node that does not exist.
+    DanglingHandle,
+    /// A resource UUID is not in the resource registry.
+    MissingResource,
+    /// A script type UUID is not registered in the script constructor container.
+    UnknownScript,
+}
+
+impl Display for ValidationIssueKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ValidationIssueKind::UnknownField => "unknown field",
+            ValidationIssueKind::MissingField => "missing field",
+            ValidationIssueKind::DanglingHandle => "dangling handle",
+            ValidationIssueKind::MissingResource => "missing resource",
+            ValidationIssueKind::UnknownScript => "unknown script",
+        })
+    }
+}
+
+/// A problem found in an asset file.
+#[derive(Clone, Debug)]
+pub struct ValidationIssue {
+    /// Kind of the issue.
+    pub kind: ValidationIssueKind,
+    /// Location of the issue. It is either a path in the serialized data (for example,
+    /// `__ROOT__ > Scene > Graph > ...`), or a node name with a field path.
+    pub path: String,
+    /// Human-readable description of the issue.
+    pub description: String,
+    /// `true` if the issue was repaired, that is it is not found anymore in the repaired data.
+    pub fixed: bool,
+}
+
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}: {}", self.kind, self.path, self.description)?;
+        if self.fixed {
+            write!(f, " (fixed)")?;
+        }
+        Ok(())
+    }
+}
+
+/// A result of validation of a single asset file.
+#[derive(Clone, Debug, Default)]
+pub struct ValidationReport {
+    /// A list of found issues.
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Returns an iterator over the issues that were not repaired.
+    pub fn unresolved(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues.iter().filter(|issue| !issue.fixed)
+    }
+
+    /// Returns `true` if the file has no unresolved issues.
+    pub fn is_valid(&self) -> bool {
+        self.unresolved().next().is_none()
+    }
+}
+
+enum LoadedAsset {
+    Scene(Box<Scene>),
+    Ui(Box<UserInterface>),
+    Material(Box<Material>),
+}
+
+impl LoadedAsset {
+    fn region_name(&self) -> &'static str {
+        match self {
+            LoadedAsset::Scene(_) => "Scene",
+            LoadedAsset::Ui(_) => "Ui",
+            LoadedAsset::Material(_) => "Material",
+        }
+    }
+
+    fn visit(&mut self, visitor: &mut Visitor) -> Result<(), VisitError> {
+        let name = self.region_name();
+        match self {
+            LoadedAsset::Scene(scene) => scene.visit(name, visitor),
+            LoadedAsset::Ui(ui) => ui.visit(name, visitor),
+            LoadedAsset::Material(material) => material.visit(name, visitor),
+        }
+    }
+
+    fn as_reflect_mut(&mut self, func: &mut dyn FnMut(&mut dyn Reflect)) {
+        match self {
+            LoadedAsset::Scene(scene) => func(&mut **scene),
+            LoadedAsset::Ui(ui) => func(&mut **ui),
+            LoadedAsset::Material(material) => func(&mut **material),
+        }
+    }
+
+    fn used_resources(&self) -> FxHashSet<UntypedResource> {
+        match self {
+            LoadedAsset::Scene(scene) => scene.collect_used_resources(),
+            LoadedAsset::Ui(ui) => ui.collect_used_resources(),
+            LoadedAsset::Material(material) => {
+                let mut resources = FxHashSet::default();
+                collect_used_resources(&**material, &mut resources);
+                resources
+            }
+        }
+    }
+}
+
+/// Asset validator loads scenes (`.rgs`), user interfaces (`.ui`) and materials (`.material`)
+/// in error-recovering mode (see [`VisitorFlags::RECOVER_ERRORS`]) and reports:
+///
+/// - unknown fields, that are stored in the file, but not read by the engine;
+/// - missing required fields;
+/// - dangling node handles (handles pointing to nonexistent pool slots);
+/// - resource UUIDs that are not in the resource registry;
+/// - script UUIDs that are not registered in the script constructor container.
+///
+/// In fix mode, unknown fields and scripts are stripped, missing fields are written with their
+/// default values, dangling handles are reset (orphaned nodes are attached to the root) and the
+/// file is saved back in its original format. The original file is kept next to it with the `.bak`
+/// extension appended. Missing resources are replaced with embedded resources in their default
+/// state.
+pub struct AssetValidator {
+    /// Resource manager that is used to resolve resource UUIDs. Its registry must be loaded
+    /// before validation.
+    pub resource_manager: ResourceManager,
+    /// Serialization context with registered scripts. Scripts that are not registered in the
+    /// context are reported as unknown.
+    pub serialization_context: Arc<SerializationContext>,
+    /// Widget constructors that are used to load user interfaces.
+    pub widget_constructors: Arc<WidgetConstructorContainer>,
+}
+
+impl Default for AssetValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AssetValidator {
+    /// Creates a new validator with a headless resource manager that uses the file system and has
+    /// all the standard resource loaders. Custom resources and scripts (for example, from plugins)
+    /// should be registered before [`ResourceManager::update_or_load_registry`] is called.
+    pub fn new() -> Self {
+        let resource_manager =
+            ResourceManager::new(Arc::new(FsResourceIo), Arc::new(TaskPool::new()));
+        let serialization_context = Arc::new(SerializationContext::new());
+        initialize_resource_manager_loaders(&resource_manager, serialization_context.clone());
+        Self {
+            resource_manager,
+            serialization_context,
+            widget_constructors: Arc::new(new_widget_constructor_container()),
+        }
+    }
+
+    /// Validates the asset file at the given path and, if `fix` is `true`, repairs it. An error
+    /// is returned only if the file cannot be loaded even in error-recovering mode (for example,
+    /// it is not a valid serialized data at all).
+    pub async fn validate(&self, path: &Path, fix: bool) -> Result<ValidationReport, VisitError> {
+        if !self.resource_manager.registry_is_loaded().await {
+            return Err(VisitError::User(format!(
+                "Unable to validate {}, because the resource registry isn't loaded!",
+                path.display()
+            )));
+        }
+
+        let data = self.resource_manager.resource_io().load_file(path).await?;
+        let format = Visitor::detect_format_from_slice(&data);
+        let (mut report, resaved) = self.check(path, &data, fix)?;
+
+        if fix && !report.issues.is_empty() {
+            // An issue is fixed only if it is not found in the repaired data.
+            let (remaining, _) = self.check(path, &resaved.save_binary_to_vec()?, false)?;
+            for issue in report.issues.iter_mut() {
+                issue.fixed = !remaining
+                    .issues
+                    .iter()
+                    .any(|remaining| remaining.kind == issue.kind && remaining.path == issue.path);
+            }
+        }
+
+        if report.issues.iter().any(|issue| issue.fixed) {
+            let mut backup_path = path.as_os_str().to_owned();
+            backup_path.push(".bak");
+            std::fs::write(PathBuf::from(backup_path), &data)?;
+
+            match format {
+                Format::Binary => resaved.save_binary_to_file(path)?,
+                Format::Ascii => resaved.save_ascii_to_file(path)?,
+                Format::Json => resaved.save_json_to_file(path)?,
+                Format::Unknown => {
+                    return Err(VisitError::User(format!(
+                        "Unable to save {}, because its format is unknown!",
+                        path.display()
+                    )))
+                }
+            }
+
+            Log::info(format!("{} was repaired.", path.display()));
+        }
+
+        Ok(report)
+    }
+
+    /// Loads the asset from the data in error-recovering mode and checks it. If `fix` is `true`,
+    /// the asset is repaired in memory. Returns the found issues (none of them is marked as fixed)
+    /// and the asset saved back.
+    fn check(
+        &self,
+        path: &Path,
+        data: &[u8],
+        fix: bool,
+    ) -> Result<(ValidationReport, Visitor), VisitError> {
+        let mut visitor = Visitor::load_from_memory(data)?;
+        visitor.flags = VisitorFlags::RECOVER_ERRORS;
+        visitor
+            .blackboard
+            .register(self.serialization_context.clone());
+        visitor
+            .blackboard
+            .register(Arc::new(self.resource_manager.clone()));
+
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or_default()
+            .to_lowercase();
+        let mut asset = match extension.as_str() {
+            "rgs" => LoadedAsset::Scene(Box::default()),
+            "ui" => {
+                let (sender, receiver) = mpsc::channel();
+                visitor
+                    .blackboard
+                    .register(self.widget_constructors.clone());
+                visitor.blackboard.register(Arc::new(sender.clone()));
+                LoadedAsset::Ui(Box::new(UserInterface::new_with_channel(
+                    sender,
+                    receiver,
+                    Default::default(),
+                )))
+            }
+            "material" => LoadedAsset::Material(Box::default()),
+            _ => {
+                return Err(VisitError::User(format!(
+                    "{} is not a scene, user interface or material!",
+                    path.display()
+                )))
+            }
+        };
+        asset.visit(&mut visitor)?;
+
+        let mut report = ValidationReport::default();
+
+        for (error_path, error) in visitor.recovered_errors() {
+            let kind = if is_unknown_script_error(error) {
+                ValidationIssueKind::UnknownScript
+            } else {
+                ValidationIssueKind::MissingField
+            };
+            report.issues.push(ValidationIssue {
+                kind,
+                path: error_path.clone(),
+                description: error.to_string(),
+                fixed: false,
+            });
+        }
+
+        match &mut asset {
+            LoadedAsset::Scene(scene) => check_scene_handles(scene, fix, &mut report),
+            LoadedAsset::Ui(ui) => check_ui_handles(ui, fix, &mut report),
+            LoadedAsset::Material(_) => (),
+        }
+
+        let missing = asset
+            .used_resources()
+            .into_iter()
+            .map(|resource| (resource.is_embedded(), resource.resource_uuid()))
+            .filter(|(is_embedded, uuid)| {
+                !is_embedded
+                    && !uuid.is_nil()
+                    && self.resource_manager.uuid_to_resource_path(*uuid).is_none()
+            })
+            .map(|(_, uuid)| uuid)
+            .collect::<FxHashSet<_>>();
+        if fix && !missing.is_empty() {
+            let constructors = &self.resource_manager.state().constructors_container;
+            asset.as_reflect_mut(&mut |asset| {
+                reset_missing_resources(asset, None, &missing, constructors)
+            });
+        }
+        for uuid in missing {
+            report.issues.push(ValidationIssue {
+                kind: ValidationIssueKind::MissingResource,
+                path: uuid.to_string(),
+                description: "The resource is not in the resource registry.".to_string(),
+                fixed: false,
+            });
+        }
+
+        let mut resaved = Visitor::new();
+        asset.visit(&mut resaved)?;
+        for unknown_path in visitor.unmatched_paths(&resaved) {
+            report.issues.push(ValidationIssue {
+                kind: ValidationIssueKind::UnknownField,
+                path: unknown_path,
+                description: "The data is not used by the engine.".to_string(),
+                fixed: false,
+            });
+        }
+
+        Ok((report, resaved))
+    }
+
+    /// Synchronous version of [`Self::validate`].
+    pub fn validate_blocking(
+        &self,
+        path: &Path,
+        fix: bool,
+    ) -> Result<ValidationReport, VisitError> {
+        block_on(self.validate(path, fix))
+    }
+}
+
+fn check_scene_handles(scene: &mut Scene, fix: bool, report: &mut ValidationReport) {
+    let graph = &mut scene.graph;
+    let valid = graph
+        .pair_iter()
+        .map(|(handle, _)| handle)
+        .collect::<FxHashSet<_>>();
+
+    for (handle, node) in graph.pair_iter_mut() {
+        let node_path = format!("{} ({handle})", node.name());
+        node.as_reflect_mut(&mut |node| {
+            check_handles::<Node>(node, &node_path, &valid, fix, report)
+        });
+        let base: &mut Base = node;
+        check_hierarchy(
+            &node_path,
+            &mut base.parent,
+            &mut base.children,
+            &valid,
+            fix,
+            report,
+        );
+        if fix {
+            node.scripts.retain(|record| record.script.is_some());
+        }
+    }
+
+    if fix {
+        let root = graph.get_root();
+        let orphans = graph
+            .pair_iter()
+            .filter(|(handle, node)| *handle != root && node.parent().is_none())
+            .map(|(handle, _)| handle)
+            .collect::<Vec<_>>();
+        for orphan in orphans {
+            graph.link_nodes(orphan, root);
+        }
+    }
+}
+
+fn check_ui_handles(ui: &mut UserInterface, fix: bool, report: &mut ValidationReport) {
+    let valid = ui
+        .nodes()
+        .pair_iter()
+        .map(|(handle, _)| handle)
+        .collect::<FxHashSet<_>>();
+
+    for &handle in valid.iter() {
+        let node = ui.node_mut(handle);
+        let node_path = format!("{} ({handle})", node.name());
+        node.as_reflect_mut(&mut |node| {
+            check_handles::<UiNode>(node, &node_path, &valid, fix, report)
+        });
+        let widget: &mut Widget = node;
+        check_hierarchy(
+            &node_path,
+            &mut widget.parent,
+            &mut widget.children,
+            &valid,
+            fix,
+            report,
+        );
+    }
+
+    if fix {
+        let root = ui.root();
+        let orphans = valid
+            .iter()
+            .filter(|handle| **handle != root && ui.node(**handle).parent().is_none())
+            .cloned()
+            .collect::<Vec<_>>();
+        for orphan in orphans {
+            ui.link_nodes(orphan, root, false);
+        }
+    }
+}
+
+/// Checks the parent and the children of a node. They are not visible to reflection, and
+/// therefore must be checked separately. Dangling children are removed in fix mode.
+fn check_hierarchy<N>(
+    path: &str,
+    parent: &mut Handle<N>,
+    children: &mut Vec<Handle<N>>,
+    valid: &FxHashSet<Handle<N>>,
+    fix: bool,
+    report: &mut ValidationReport,
+) {
+    let mut push_issue = |field_path: String, handle: Handle<N>| {
+        report.issues.push(ValidationIssue {
+            kind: ValidationIssueKind::DanglingHandle,
+            path: field_path,
+            description: format!("The handle {handle} points to a nonexistent node."),
+            fixed: false,
+        })
+    };
+
+    if parent.is_some() && !valid.contains(parent) {
+        push_issue(format!("{path}.parent"), *parent);
+        if fix {
+            *parent = Handle::NONE;
+        }
+    }
+
+    for (i, child) in children.iter().enumerate() {
+        if !valid.contains(child) {
+            push_issue(format!("{path}.children[{i}]"), *child);
+        }
+    }
+    if fix {
+        children.retain(|child| valid.contains(child));
+    }
+}
+
+/// Finds handles to `N` that point to nonexistent nodes. It walks the entity the same way as
+/// [`crate::graph::NodeHandleMap::remap_handles`] does.
+fn check_handles<N: Reflect>(
+    entity: &mut dyn Reflect,
+    path: &str,
+    valid: &FxHashSet<Handle<N>>,
+    fix: bool,
+    report: &mut ValidationReport,
+) {
+    if (*entity).type_id() == TypeId::of::<UntypedResource>() {
+        return;
+    }
+
+    let mut dangling = None;
+    let mut checked = false;
+
+    entity.downcast_mut::<Handle<N>>(&mut |handle| {
+        if let Some(handle) = handle {
+            if handle.is_some() && !valid.contains(handle) {
+                dangling = Some(handle.to_string());
+                if fix {
+                    *handle = Handle::NONE;
+                }
+            }
+            checked = true;
+        }
+    });
+
+    if !checked {
+        // Handles of derived types.
+        entity.as_handle_mut(&mut |handle| {
+            if let Some(handle) = handle {
+                if handle.query_derived_types().contains(&TypeId::of::<N>())
+                    && handle.reflect_is_some()
+                {
+                    let erased =
+                        Handle::<N>::new(handle.reflect_index(), handle.reflect_generation());
+                    if !valid.contains(&erased) {
+                        dangling = Some(erased.to_string());
+                        if fix {
+                            handle.reflect_set_index(0);
+                            handle.reflect_set_generation(0);
+                        }
+                    }
+                }
+                checked = true;
+            }
+        });
+    }
+
+    if let Some(dangling) = dangling {
+        report.issues.push(ValidationIssue {
+            kind: ValidationIssueKind::DanglingHandle,
+            path: path.to_string(),
+            description: format!("The handle {dangling} points to a nonexistent node."),
+            fixed: false,
+        });
+    }
+
+    if checked {
+        return;
+    }
+
+    entity.as_inheritable_variable_mut(&mut |inheritable| {
+        if let Some(inheritable) = inheritable {
+            check_handles(inheritable.inner_value_mut(), path, valid, fix, report);
+            checked = true;
+        }
+    });
+
+    if checked {
+        return;
+    }
+
+    entity.as_array_mut(&mut |array| {
+        if let Some(array) = array {
+            for i in 0..array.reflect_len() {
+                if let Some(item) = array.reflect_index_mut(i) {
+                    check_handles(item, &format!("{path}[{i}]"), valid, fix, report);
+                }
+            }
+            checked = true;
+        }
+    });
+
+    if checked {
+        return;
+    }
+
+    entity.as_hash_map_mut(&mut |hash_map| {
+        if let Some(hash_map) = hash_map {
+            for i in 0..hash_map.reflect_len() {
+                if let Some(item) = hash_map.reflect_get_nth_value_mut(i) {
+                    check_handles(item, &format!("{path}[{i}]"), valid, fix, report);
+                }
+            }
+            checked = true;
+        }
+    });
+
+    if checked {
+        return;
+    }
+
+    entity.fields_mut(&mut |fields| {
+        for field in fields {
+            // This handle points to a node in the prefab the node was instantiated from.
+            if field.metadata.name == "original_handle_in_resource" {
+                continue;
+            }
+            let field_path = format!("{path}.{}", field.metadata.name);
+            field
+                .value
+                .field_value_as_reflect_mut()
+                .as_reflect_mut(&mut |field| check_handles(field, &field_path, valid, fix, report));
+        }
+    });
+}
+
+/// Replaces the resources with the given UUIDs with embedded resources in their default state.
+/// The data of a typed resource is created by the constructor of its data type (the same as
+/// [`crate::asset::Resource::default`] does), other resources are replaced with [`UntypedResource::default`].
+/// It walks the entity the same way as [`collect_used_resources`] does.
+fn reset_missing_resources(
+    entity: &mut dyn Reflect,
+    data_type_name: Option<&str>,
+    missing: &FxHashSet<Uuid>,
+    constructors: &ResourceConstructorContainer,
+) {
+    fn type_is<T: Reflect>(entity: &dyn Reflect) -> bool {
+        let mut types_match = false;
+        entity.downcast_ref::<T>(&mut |v| {
+            types_match = v.is_some();
+        });
+        types_match
+    }
+
+    // Skip potentially large chunks of numeric data, that cannot contain any resources.
+    let mut finished = type_is::<Vec<u8>>(entity)
+        || type_is::<Vec<u16>>(entity)
+        || type_is::<Vec<u32>>(entity)
+        || type_is::<Vec<f32>>(entity)
+        || type_is::<Vec<f64>>(entity);
+
+    if finished {
+        return;
+    }
+
+    entity.downcast_mut::<UntypedResource>(&mut |resource| {
+        if let Some(resource) = resource {
+            if missing.contains(&resource.resource_uuid()) {
+                let data = data_type_name.and_then(|data_type_name| {
+                    constructors
+                        .map
+                        .safe_lock()
+                        .values_mut()
+                        .find(|constructor| constructor.type_name == data_type_name)
+                        .map(|constructor| constructor.create_instance())
+                });
+                *resource = match data {
+                    Some(data) => UntypedResource::new_ok_untyped(
+                        Uuid::new_v4(),
+                        ResourceKind::Embedded,
+                        data,
+                    ),
+                    None => UntypedResource::default(),
+                };
+            }
+            finished = true;
+        }
+    });
+
+    if finished {
+        return;
+    }
+
+    entity.as_inheritable_variable_mut(&mut |inheritable| {
+        if let Some(inheritable) = inheritable {
+            reset_missing_resources(
+                inheritable.inner_value_mut(),
+                data_type_name,
+                missing,
+                constructors,
+            );
+            finished = true;
+        }
+    });
+
+    if finished {
+        return;
+    }
+
+    entity.as_array_mut(&mut |array| {
+        if let Some(array) = array {
+            for i in 0..array.reflect_len() {
+                if let Some(item) = array.reflect_index_mut(i) {
+                    reset_missing_resources(item, None, missing, constructors);
+                }
+            }
+            finished = true;
+        }
+    });
+
+    if finished {
+        return;
+    }
+
+    entity.as_hash_map_mut(&mut |hash_map| {
+        if let Some(hash_map) = hash_map {
+            for i in 0..hash_map.reflect_len() {
+                if let Some(item) = hash_map.reflect_get_nth_value_mut(i) {
+                    reset_missing_resources(item, None, missing, constructors);
+                }
+            }
+            finished = true;
+        }
+    });
+
+    if finished {
+        return;
+    }
+
+    // `Resource<T>` keeps its untyped resource in a field, remember the data type for it.
+    let data_type_name = entity
+        .type_name()
+        .strip_prefix("fyrox_resource::Resource<")
+        .and_then(|name| name.strip_suffix('>'));
+
+    entity.fields_mut(&mut |fields| {
+        for field in fields {
+            field
+                .value
+                .field_value_as_reflect_mut()
+                .as_reflect_mut(&mut |field| {
+                    reset_missing_resources(field, data_type_name, missing, constructors)
+                });
+        }
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            impl_component_provider,
+            pool::Handle,
+            reflect::prelude::*,
+            uuid::{uuid, Uuid},
+            visitor::prelude::*,
+            SafeLock, TypeUuidProvider,
+        },
+        material::MaterialResource,
+        scene::{base::BaseBuilder, pivot::PivotBuilder, sprite::SpriteBuilder, Scene},
+        script::ScriptTrait,
+        utils::validation::{AssetValidator, ValidationIssueKind},
+    };
+    use std::{fs, path::Path};
+
+    #[derive(Debug, Clone, Reflect, Visit, Default)]
+    struct UnregisteredScript {
+        value: u32,
+    }
+
+    impl_component_provider!(UnregisteredScript);
+
+    impl TypeUuidProvider for UnregisteredScript {
+        fn type_uuid() -> Uuid {
+            uuid!("5b1a0a0c-3f53-4c1b-9d7e-2f6a1c0b9e41")
+        }
+    }
+
+    impl ScriptTrait for UnregisteredScript {}
+
+    #[derive(Debug, Clone, Reflect, Visit, Default)]
+    struct OldScript {
+        value: u32,
+    }
+
+    impl_component_provider!(OldScript);
+
+    impl TypeUuidProvider for OldScript {
+        fn type_uuid() -> Uuid {
+            uuid!("9d4e2c71-0b6a-4f3e-8a15-7c2d9e0f4b38")
+        }
+    }
+
+    impl ScriptTrait for OldScript {}
+
+    // The same script as `OldScript`, but its field was renamed.
+    #[derive(Debug, Clone, Reflect, Visit, Default)]
+    struct RenamedFieldScript {
+        amount: u32,
+    }
+
+    impl_component_provider!(RenamedFieldScript);
+
+    impl TypeUuidProvider for RenamedFieldScript {
+        fn type_uuid() -> Uuid {
+            OldScript::type_uuid()
+        }
+    }
+
+    impl ScriptTrait for RenamedFieldScript {}
+
+    #[test]
+    fn test_validate_and_fix_scene() {
+        if !Path::new("test_output").exists() {
+            fs::create_dir_all("test_output").unwrap();
+        }
+        let path = Path::new("test_output/broken.rgs");
+
+        let mut scene = Scene::new();
+        let pivot = PivotBuilder::new(
+            BaseBuilder::new()
+                .with_name("Pivot")
+                .with_script(UnregisteredScript { value: 1 }),
+        )
+        .build(&mut scene.graph);
+        scene.graph[pivot].children.push(Handle::new(100, 1));
+        PivotBuilder::new(
+            BaseBuilder::new()
+                .with_name("Renamed")
+                .with_script(OldScript { value: 2 }),
+        )
+        .build(&mut scene.graph);
+        SpriteBuilder::new(BaseBuilder::new().with_name("Sprite"))
+            .with_material(MaterialResource::from(uuid!(
+                "0c2b7e4d-6a1f-4e8b-9d3c-5f7a2e1b8c64"
+            )))
+            .build(&mut scene.graph);
+        let mut visitor = Visitor::new();
+        scene.save("Scene", &mut visitor).unwrap();
+        visitor.save_ascii_to_file(path).unwrap();
+
+        let validator = AssetValidator::new();
+        validator
+            .resource_manager
+            .state()
+            .resource_registry
+            .safe_lock()
+            .set_path("test_output/validation.registry");
+        validator.resource_manager.update_or_load_registry();
+        validator
+            .serialization_context
+            .script_constructors
+            .add::<RenamedFieldScript>("RenamedFieldScript");
+
+        let report = validator.validate_blocking(path, false).unwrap();
+        assert!(!report.is_valid());
+        // A missing field of a registered script must not be reported as an unknown script.
+        assert_eq!(
+            report
+                .issues
+                .iter()
+                .filter(|issue| issue.kind == ValidationIssueKind::UnknownScript)
+                .count(),
+            1,
+            "{:?}",
+            report.issues
+        );
+        assert!(
+            report.issues.iter().any(|issue| {
+                issue.kind == ValidationIssueKind::MissingField && issue.path.ends_with("Amount")
+            }),
+            "{:?}",
+            report.issues
+        );
+        for kind in [
+            ValidationIssueKind::UnknownScript,
+            ValidationIssueKind::DanglingHandle,
+            ValidationIssueKind::MissingResource,
+        ] {
+            assert!(
+                report.issues.iter().any(|issue| issue.kind == kind),
+                "{:?}",
+                report.issues
+            );
+        }
+
+        let report = validator.validate_blocking(path, true).unwrap();
+        assert!(report.is_valid(), "{:?}", report.issues);
+        assert!(report.issues.iter().all(|issue| issue.fixed));
+        assert!(Path::new("test_output/broken.rgs.bak").exists());
+
+        let report = validator.validate_blocking(path, false).unwrap();
+        assert!(report.issues.is_empty(), "{:?}", report.issues);
+    }
+}
@@ -0,0 +1,508 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Derived data cache. Some source assets are expensive to turn into engine data: images must be
+//! decoded (and optionally compressed and mip-mapped), sounds must be decoded, etc. A loader can
+//! provide a [`ResourceImporter`] that converts the source file into a platform-specific artifact
+//! once, and the resource manager will then load the artifact from the [`DerivedDataCache`]
+//! instead of importing the source file over and over again.
+//!
+//! The cache is content-addressed: an artifact is stored under a [`DerivedDataKey`] which is a hash
+//! of the source file, its import options, the version of the importer and the target platform.
+//! Any change of these produces a new key, so stale artifacts are never used. The previous
+//! artifact of a source file is removed when a new one is stored for it.
+
+use crate::{
+    io::ResourceIo,
+    loader::{BoxedLoaderFuture, LoaderPayload},
+    options::OPTIONS_EXTENSION,
+    state::LoadError,
+};
+use fxhash::FxHashMap;
+use fyrox_core::{
+    append_extension, io::FileError, log::Log, parking_lot::Mutex, platform::TargetPlatform,
+    uuid::Uuid,
+};
+use std::{
+    fmt::{Debug, Display, Formatter},
+    future::Future,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::Arc,
+};
+
+/// Version of the cache layout. It is mixed into every key, so changing it invalidates the
+/// whole cache.
+const CACHE_VERSION: u32 = 1;
+
+/// Future type for resource importing. See [`ResourceImporter`].
+#[cfg(target_arch = "wasm32")]
+pub type BoxedImportFuture = Pin<Box<dyn Future<Output = Result<Vec<u8>, LoadError>>>>;
+
+/// Future type for resource importing. See [`ResourceImporter`].
+#[cfg(not(target_arch = "wasm32"))]
+pub type BoxedImportFuture = Pin<Box<dyn Future<Output = Result<Vec<u8>, LoadError>> + Send>>;
+
+/// Converts source files into derived data and loads resources back from it. Importers are
+/// provided by resource loaders, see [`crate::loader::ResourceLoader::importer`].
+pub trait ResourceImporter: Send + Sync + 'static {
+    /// Version of the importer. It must be increased every time the importer starts producing
+    /// different artifacts (a new compression algorithm, a bug fix, new defaults of the import
+    /// options, etc.), so the artifacts made by the previous version are not used anymore.
+    fn version(&self) -> u32;
+
+    /// Returns settings of the importer that affect the artifacts, but are not stored in the source
+    /// file or its import options file. A typical example is default import options, which are
+    /// used when there's no options file. The bytes could be in any form, as long as it is stable.
+    /// The default implementation returns no settings.
+    fn settings(&self) -> Vec<u8> {
+        Vec::new()
+    }
+
+    /// Converts the source file at the given path into an artifact for the given platform. `source`
+    /// is the content of the file, the import options (if needed) could be read using `io`.
+    fn import(
+        &self,
+        path: PathBuf,
+        source: Vec<u8>,
+        platform: TargetPlatform,
+        io: Arc<dyn ResourceIo>,
+    ) -> BoxedImportFuture;
+
+    /// Loads a resource from the artifact previously produced by [`Self::import`].
+    fn load_derived(&self, artifact: Vec<u8>) -> BoxedLoaderFuture;
+}
+
+/// A content hash that identifies a single artifact in the [`DerivedDataCache`].
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct DerivedDataKey(pub u128);
+
+impl Display for DerivedDataKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:032x}", self.0)
+    }
+}
+
+/// 128-bit FNV-1a. It is stable across platforms and runs, unlike the std hasher.
+struct Fnv128(u128);
+
+impl Fnv128 {
+    const OFFSET_BASIS: u128 = 0x6c62272e07bb014262b821756295c58d;
+    const PRIME: u128 = 0x0000000001000000000000000000013b;
+
+    fn new() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u128;
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    /// Writes a length-prefixed chunk of bytes, so the boundaries of the parts are hashed too.
+    fn write_chunk(&mut self, bytes: &[u8]) {
+        self.write(&(bytes.len() as u64).to_le_bytes());
+        self.write(bytes);
+    }
+}
+
+impl DerivedDataKey {
+    /// Calculates a key for the given inputs of an importer. `data_type_uuid` is the type of the
+    /// resource produced by the importer, it separates the artifacts of different importers of the
+    /// same source file. See [`ResourceImporter::settings`] for `importer_settings`.
+    pub fn new(
+        source: &[u8],
+        import_options: &[u8],
+        data_type_uuid: Uuid,
+        importer_version: u32,
+        importer_settings: &[u8],
+        platform: TargetPlatform,
+    ) -> Self {
+        let mut hasher = Fnv128::new();
+        hasher.write(&CACHE_VERSION.to_le_bytes());
+        hasher.write(data_type_uuid.as_bytes());
+        hasher.write(&importer_version.to_le_bytes());
+        hasher.write_chunk(importer_settings);
+        hasher.write_chunk(platform.to_string().as_bytes());
+        hasher.write_chunk(import_options);
+        hasher.write_chunk(source);
+        Self(hasher.0)
+    }
+}
+
+/// Local on-disk storage of artifacts made by [`ResourceImporter`]s for a single target platform.
+/// Assign it to a resource manager using
+/// [`crate::manager::ResourceManagerState::set_derived_data_cache`] to make the manager load
+/// resources from the cached artifacts. The editor and the executor do this by default, the cache
+/// is located in [`Self::DEFAULT_PATH`] folder. The editor builds the cache of an exported game for
+/// its target platform, see [`Self::prepare`].
+///
+/// Artifacts are read and written using the resource IO of the resource manager. If the IO is
+/// read-only (for example, a packed archive), missing artifacts are imported on every load.
+pub struct DerivedDataCache {
+    root: PathBuf,
+    platform: TargetPlatform,
+    // Source path -> key of the artifact that was stored for it the last time.
+    entries: Mutex<FxHashMap<PathBuf, DerivedDataKey>>,
+}
+
+impl Debug for DerivedDataCache {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DerivedDataCache")
+            .field("root", &self.root)
+            .field("platform", &self.platform)
+            .finish()
+    }
+}
+
+impl DerivedDataCache {
+    /// Default location of the cache.
+    pub const DEFAULT_PATH: &'static str = ".cache/derived";
+
+    /// Creates a cache that stores artifacts for the given platform in the given folder. The folder
+    /// is created on demand.
+    pub fn new(root: impl AsRef<Path>, platform: TargetPlatform) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+            platform,
+            entries: Default::default(),
+        }
+    }
+
+    /// Returns the folder where the artifacts are stored.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns the platform the artifacts are made for.
+    pub fn platform(&self) -> TargetPlatform {
+        self.platform
+    }
+
+    /// Returns a path of the artifact with the given key. Artifacts are spread over 256 sub-folders
+    /// to keep the folders reasonably small.
+    pub fn artifact_path(&self, key: DerivedDataKey) -> PathBuf {
+        let name = key.to_string();
+        self.root.join(&name[..2]).join(name)
+    }
+
+    /// Calculates a key of the artifact for the given source file. The import options are read from
+    /// the options file next to the source file, if there's any.
+    pub async fn key_for(
+        &self,
+        path: &Path,
+        source: &[u8],
+        data_type_uuid: Uuid,
+        importer: &dyn ResourceImporter,
+        io: &dyn ResourceIo,
+    ) -> DerivedDataKey {
+        let options_path = append_extension(path, OPTIONS_EXTENSION);
+        let import_options = io.load_file(&options_path).await.unwrap_or_default();
+        DerivedDataKey::new(
+            source,
+            &import_options,
+            data_type_uuid,
+            importer.version(),
+            &importer.settings(),
+            self.platform,
+        )
+    }
+
+    /// Loads a resource from the artifact of the given source file. The artifact is imported and
+    /// stored in the cache first, if it does not exist yet (or it was made from different inputs).
+    pub async fn load_or_import(
+        &self,
+        path: PathBuf,
+        data_type_uuid: Uuid,
+        importer: Arc<dyn ResourceImporter>,
+        io: Arc<dyn ResourceIo>,
+    ) -> Result<LoaderPayload, LoadError> {
+        let source = io.load_file(&path).await.map_err(LoadError::new)?;
+        let key = self
+            .key_for(&path, &source, data_type_uuid, &*importer, &*io)
+            .await;
+        let artifact_path = self.artifact_path(key);
+
+        if let Ok(artifact) = io.load_file(&artifact_path).await {
+            match importer.load_derived(artifact).await {
+                Ok(payload) => {
+                    self.entries.lock().insert(path, key);
+                    return Ok(payload);
+                }
+                Err(err) => Log::warn(format!(
+                    "Unable to load derived data {} of {}, it will be imported again. \
+                    Reason: {err}",
+                    artifact_path.display(),
+                    path.display()
+                )),
+            }
+        }
+
+        let artifact = importer
+            .import(path.clone(), source, self.platform, io.clone())
+            .await?;
+
+        if io.can_write() {
+            if let Err(err) = self.store(&path, key, &artifact, &*io) {
+                Log::warn(format!(
+                    "Unable to store derived data of {}. Reason: {err}",
+                    path.display()
+                ))
+            }
+        }
+
+        importer.load_derived(artifact).await
+    }
+
+    /// Imports the given source file and stores its artifact, unless the cache already has the
+    /// artifact for the current content and import options of the file. It is used to build the
+    /// cache ahead of time, for example when a game is exported.
+    pub async fn prepare(
+        &self,
+        path: PathBuf,
+        data_type_uuid: Uuid,
+        importer: Arc<dyn ResourceImporter>,
+        io: Arc<dyn ResourceIo>,
+    ) -> Result<(), LoadError> {
+        let source = io.load_file(&path).await.map_err(LoadError::new)?;
+        let key = self
+            .key_for(&path, &source, data_type_uuid, &*importer, &*io)
+            .await;
+        if io.exists(&self.artifact_path(key)).await {
+            self.entries.lock().insert(path, key);
+            return Ok(());
+        }
+
+        let artifact = importer
+            .import(path.clone(), source, self.platform, io.clone())
+            .await?;
+        self.store(&path, key, &artifact, &*io)
+            .map_err(LoadError::new)
+    }
+
+    /// Writes the artifact of the given source file and removes the previous artifact of the file,
+    /// if its key was different.
+    fn store(
+        &self,
+        path: &Path,
+        key: DerivedDataKey,
+        artifact: &[u8],
+        io: &dyn ResourceIo,
+    ) -> Result<(), FileError> {
+        let artifact_path = self.artifact_path(key);
+        if let Some(folder) = artifact_path.parent() {
+            io.create_dir_all_sync(folder)?;
+        }
+        io.write_file_sync(&artifact_path, artifact)?;
+
+        let previous = self.entries.lock().insert(path.to_path_buf(), key);
+        if let Some(previous) = previous.filter(|previous| *previous != key) {
+            // Artifacts are shared between identical sources, keep the ones that are still in use.
+            if !self.entries.lock().values().any(|key| *key == previous) {
+                let _ = io.delete_file_sync(&self.artifact_path(previous));
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes every artifact from the cache, so every resource will be imported again on the next
+    /// load.
+    pub fn clear(&self) -> Result<(), FileError> {
+        self.entries.lock().clear();
+        match std::fs::remove_dir_all(&self.root) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{io::FsResourceIo, ResourceData};
+    use fyrox_core::{
+        futures::executor::block_on, reflect::prelude::*, visitor::prelude::*, TypeUuidProvider,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug, Default, Clone, Reflect, Visit)]
+    struct Length(usize);
+
+    impl TypeUuidProvider for Length {
+        fn type_uuid() -> Uuid {
+            fyrox_core::uuid!("a9bb8f1e-6a3f-4f53-a09f-7a3a2e0b1f51")
+        }
+    }
+
+    impl ResourceData for Length {
+        fn type_uuid(&self) -> Uuid {
+            <Self as TypeUuidProvider>::type_uuid()
+        }
+
+        fn save(&mut self, _path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+            Ok(())
+        }
+
+        fn can_be_saved(&self) -> bool {
+            false
+        }
+
+        fn try_clone_box(&self) -> Option<Box<dyn ResourceData>> {
+            None
+        }
+    }
+
+    /// Stores the length of the source file as the artifact.
+    #[derive(Default)]
+    struct LengthImporter {
+        imports: AtomicUsize,
+    }
+
+    impl ResourceImporter for LengthImporter {
+        fn version(&self) -> u32 {
+            1
+        }
+
+        fn import(
+            &self,
+            _path: PathBuf,
+            source: Vec<u8>,
+            _platform: TargetPlatform,
+            _io: Arc<dyn ResourceIo>,
+        ) -> BoxedImportFuture {
+            self.imports.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async move { Ok((source.len() as u64).to_le_bytes().to_vec()) })
+        }
+
+        fn load_derived(&self, artifact: Vec<u8>) -> BoxedLoaderFuture {
+            Box::pin(async move {
+                let bytes = <[u8; 8]>::try_from(artifact.as_slice())
+                    .map_err(|_| LoadError::new("Malformed artifact!"))?;
+                Ok(LoaderPayload::new(Length(
+                    u64::from_le_bytes(bytes) as usize
+                )))
+            })
+        }
+    }
+
+    #[test]
+    fn test_key() {
+        let uuid = <Length as TypeUuidProvider>::type_uuid();
+        let pc = TargetPlatform::PC;
+        let key = DerivedDataKey::new(b"source", b"", uuid, 1, b"", pc);
+        assert_eq!(key, DerivedDataKey::new(b"source", b"", uuid, 1, b"", pc));
+        for other in [
+            DerivedDataKey::new(b"source2", b"", uuid, 1, b"", pc),
+            DerivedDataKey::new(b"source", b"(srgb:true)", uuid, 1, b"", pc),
+            DerivedDataKey::new(b"source", b"", Uuid::nil(), 1, b"", pc),
+            DerivedDataKey::new(b"source", b"", uuid, 2, b"", pc),
+            DerivedDataKey::new(b"source", b"", uuid, 1, b"defaults", pc),
+            DerivedDataKey::new(b"source", b"", uuid, 1, b"", TargetPlatform::Android),
+            // Chunk boundaries must matter.
+            DerivedDataKey::new(b"ource", b"s", uuid, 1, b"", pc),
+        ] {
+            assert_ne!(key, other);
+        }
+        assert_eq!(key.to_string().len(), 32);
+    }
+
+    #[test]
+    fn test_load_or_import() {
+        let root = PathBuf::from("./test_derived_data");
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        let source_path = root.join("file.txt");
+        std::fs::write(&source_path, "12345").unwrap();
+
+        let cache = DerivedDataCache::new(root.join("cache"), TargetPlatform::PC);
+        let importer = Arc::new(LengthImporter::default());
+        let io: Arc<dyn ResourceIo> = Arc::new(FsResourceIo);
+        let load = || {
+            let payload = block_on(cache.load_or_import(
+                source_path.clone(),
+                <Length as TypeUuidProvider>::type_uuid(),
+                importer.clone(),
+                io.clone(),
+            ))
+            .unwrap();
+            (&*payload.0 as &dyn std::any::Any)
+                .downcast_ref::<Length>()
+                .unwrap()
+                .0
+        };
+
+        assert_eq!(load(), 5);
+        assert_eq!(load(), 5);
+        assert_eq!(importer.imports.load(Ordering::SeqCst), 1);
+        let first_key = *cache.entries.lock().get(&source_path).unwrap();
+        assert!(cache.artifact_path(first_key).exists());
+
+        // A changed source is imported again and the stale artifact is removed.
+        std::fs::write(&source_path, "1234567").unwrap();
+        assert_eq!(load(), 7);
+        assert_eq!(importer.imports.load(Ordering::SeqCst), 2);
+        assert!(!cache.artifact_path(first_key).exists());
+
+        // Changed import options are a different key as well.
+        std::fs::write(append_extension(&source_path, OPTIONS_EXTENSION), "()").unwrap();
+        assert_eq!(load(), 7);
+        assert_eq!(importer.imports.load(Ordering::SeqCst), 3);
+
+        cache.clear().unwrap();
+        assert!(!cache.root().exists());
+        assert_eq!(load(), 7);
+        assert_eq!(importer.imports.load(Ordering::SeqCst), 4);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_prepare() {
+        let root = PathBuf::from("./test_derived_data_prepare");
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        let source_path = root.join("file.txt");
+        std::fs::write(&source_path, "123").unwrap();
+
+        let cache = DerivedDataCache::new(root.join("cache"), TargetPlatform::Android);
+        let importer = Arc::new(LengthImporter::default());
+        let io: Arc<dyn ResourceIo> = Arc::new(FsResourceIo);
+        let uuid = <Length as TypeUuidProvider>::type_uuid();
+        let prepare = || {
+            block_on(cache.prepare(source_path.clone(), uuid, importer.clone(), io.clone()))
+                .unwrap()
+        };
+
+        prepare();
+        prepare();
+        assert_eq!(importer.imports.load(Ordering::SeqCst), 1);
+        let key = *cache.entries.lock().get(&source_path).unwrap();
+        assert!(cache.artifact_path(key).exists());
+
+        // A prepared artifact is loaded without importing the source again.
+        block_on(cache.load_or_import(source_path.clone(), uuid, importer.clone(), io.clone()))
+            .unwrap();
+        assert_eq!(importer.imports.load(Ordering::SeqCst), 1);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
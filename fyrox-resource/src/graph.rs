@@ -18,10 +18,25 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-//! Resource dependency graph. See [`ResourceDependencyGraph`] docs for more info.
+//! Resource dependency graph and reference index. See [`ResourceDependencyGraph`] and
+//! [`ResourceReferenceIndex`] docs for more info.
 
-use crate::{collect_used_resources, state::ResourceState, untyped::UntypedResource};
-use fxhash::FxHashSet;
+use crate::{
+    collect_used_resources,
+    core::{
+        uuid::Uuid,
+        visitor::{Format, Visitor},
+        SafeLock,
+    },
+    io::try_normalize_path,
+    manager::ResourceManager,
+    registry::RegistryContainer,
+    state::ResourceState,
+    untyped::UntypedResource,
+};
+use fxhash::{FxHashMap, FxHashSet};
+use fyrox_core::visitor::field::FieldKind;
+use std::path::{Path, PathBuf};
 
 /// A node of [`ResourceDependencyGraph`].
 pub struct ResourceGraphNode {
@@ -113,12 +128,229 @@ impl ResourceDependencyGraph {
         out
     }
 }
+
+/// Project-wide index of references between registered resources. Unlike
+/// [`ResourceDependencyGraph`], which walks the dependencies of a single loaded resource, the index
+/// knows the reverse relation as well: which resources refer to a given one. It is used to find
+/// references to an asset, to report assets that are not used by anything and to export only the
+/// assets reachable from a set of root assets.
+///
+/// Resources in the native format are not loaded to build the index, their data is scanned for
+/// the ids of registered resources (resource handles are stored as ids) and, in text, for paths
+/// of registered resources (blueprints, for example, refer to prefabs by path). Resources in
+/// foreign formats (3D models, for example) are loaded and inspected via reflection.
+#[derive(Default, Debug, Clone)]
+pub struct ResourceReferenceIndex {
+    paths: RegistryContainer,
+    uuids: FxHashMap<PathBuf, Uuid>,
+    references: FxHashMap<Uuid, FxHashSet<Uuid>>,
+    referencers: FxHashMap<Uuid, FxHashSet<Uuid>>,
+}
+
+impl ResourceReferenceIndex {
+    /// Creates an index of the given registered resources without any references.
+    pub fn new(paths: RegistryContainer) -> Self {
+        let uuids = paths
+            .iter()
+            .filter_map(|(uuid, path)| Some((try_normalize_path(path)?, *uuid)))
+            .collect();
+        Self {
+            paths,
+            uuids,
+            references: Default::default(),
+            referencers: Default::default(),
+        }
+    }
+
+    /// Builds the index of every resource in the registry of the given resource manager. This
+    /// method could be very slow for big projects, since it reads every resource and loads every
+    /// resource in a foreign format.
+    pub async fn build(resource_manager: &ResourceManager) -> Self {
+        let (registry, loaders, io) = {
+            let state = resource_manager.state();
+            (
+                state.resource_registry.clone(),
+                state.loaders.clone(),
+                state.resource_io.clone(),
+            )
+        };
+        let status = registry.safe_lock().status_flag();
+        status.await;
+        let mut index = Self::new(registry.safe_lock().inner().clone());
+
+        for (uuid, path) in index.paths.clone() {
+            let is_native = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| {
+                    loaders
+                        .safe_lock()
+                        .iter()
+                        .any(|loader| loader.is_native_extension(ext))
+                });
+            if is_native {
+                if let Ok(data) = io.load_file(&path).await {
+                    index.scan_data(uuid, &data);
+                }
+            } else if let Ok(resource) = resource_manager.request_untyped(&path).await {
+                index.scan_resource(uuid, &resource);
+            }
+        }
+
+        index
+    }
+
+    /// Adds a reference from one resource to another. References of a resource to itself are
+    /// ignored.
+    pub fn add_reference(&mut self, from: Uuid, to: Uuid) {
+        if from != to {
+            self.references.entry(from).or_default().insert(to);
+            self.referencers.entry(to).or_default().insert(from);
+        }
+    }
+
+    /// Adds references of the given resource found in its raw data. The data could be in the
+    /// native binary or text format, or it could be any text (every quoted string is checked
+    /// against the registered paths). Anything else is ignored.
+    pub fn scan_data(&mut self, uuid: Uuid, data: &[u8]) {
+        let mut found = FxHashSet::default();
+        if Visitor::detect_format_from_slice(data) != Format::Unknown {
+            if let Ok(visitor) = Visitor::load_from_memory(data) {
+                for field in visitor.fields() {
+                    match field.kind {
+                        FieldKind::Uuid(id) => {
+                            found.insert(id);
+                        }
+                        FieldKind::String(ref text) => self.scan_text(text, &mut found),
+                        FieldKind::BinaryBlob(ref bytes) => {
+                            if let Ok(text) = std::str::from_utf8(bytes) {
+                                self.scan_text(text, &mut found);
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+            }
+        } else if let Ok(text) = std::str::from_utf8(data) {
+            self.scan_text(text, &mut found);
+        }
+        for referenced in found {
+            if self.paths.contains_key(&referenced) {
+                self.add_reference(uuid, referenced);
+            }
+        }
+    }
+
+    fn scan_text(&self, text: &str, found: &mut FxHashSet<Uuid>) {
+        // Look for ids first: 8-4-4-4-12 hex digits.
+        let bytes = text.as_bytes();
+        for start in 0..bytes.len().saturating_sub(35) {
+            let candidate = &bytes[start..start + 36];
+            if [8, 13, 18, 23].iter().all(|&i| candidate[i] == b'-') {
+                if let Some(uuid) = std::str::from_utf8(candidate)
+                    .ok()
+                    .and_then(|candidate| Uuid::parse_str(candidate).ok())
+                {
+                    found.insert(uuid);
+                }
+            }
+        }
+
+        // Then for paths: the whole text or any quoted part of it.
+        for candidate in std::iter::once(text).chain(text.split('"')) {
+            let candidate = candidate.trim();
+            if !candidate.is_empty() && candidate.len() < 1024 {
+                if let Some(uuid) = self.find_uuid(Path::new(candidate)) {
+                    found.insert(uuid);
+                }
+            }
+        }
+    }
+
+    /// Adds references of the given loaded resource, using reflection to find the resources it
+    /// uses.
+    pub fn scan_resource(&mut self, uuid: Uuid, resource: &UntypedResource) {
+        let mut used = FxHashSet::default();
+        let header = resource.lock();
+        if let ResourceState::Ok { ref data, .. } = header.state {
+            (**data).as_reflect(&mut |entity| {
+                collect_used_resources(entity, &mut used);
+            });
+        }
+        drop(header);
+        for used in used {
+            let used = used.resource_uuid();
+            if self.paths.contains_key(&used) {
+                self.add_reference(uuid, used);
+            }
+        }
+    }
+
+    /// Returns the registered resources of the index.
+    pub fn paths(&self) -> &RegistryContainer {
+        &self.paths
+    }
+
+    /// Returns a path of the resource with the given id.
+    pub fn path(&self, uuid: Uuid) -> Option<&Path> {
+        self.paths.get(&uuid).map(|path| path.as_path())
+    }
+
+    /// Tries to find an id of the resource at the given path. The path is normalized first.
+    pub fn find_uuid(&self, path: &Path) -> Option<Uuid> {
+        self.uuids.get(&try_normalize_path(path)?).cloned()
+    }
+
+    /// Returns an iterator over the resources that are referenced by the given resource.
+    pub fn references(&self, uuid: Uuid) -> impl Iterator<Item = Uuid> + '_ {
+        self.references.get(&uuid).into_iter().flatten().cloned()
+    }
+
+    /// Returns an iterator over the resources that refer to the given resource.
+    pub fn referencers(&self, uuid: Uuid) -> impl Iterator<Item = Uuid> + '_ {
+        self.referencers.get(&uuid).into_iter().flatten().cloned()
+    }
+
+    /// Returns `true` if at least one other resource refers to the given resource.
+    pub fn is_referenced(&self, uuid: Uuid) -> bool {
+        self.referencers
+            .get(&uuid)
+            .is_some_and(|referencers| !referencers.is_empty())
+    }
+
+    /// Returns the resources that are not referenced by any other resource, sorted by path. Keep in
+    /// mind, that some of them could still be used by the game code directly (a scene that the game
+    /// loads, for example).
+    pub fn unreferenced(&self) -> Vec<Uuid> {
+        let mut unreferenced = self
+            .paths
+            .keys()
+            .filter(|uuid| !self.is_referenced(**uuid))
+            .cloned()
+            .collect::<Vec<_>>();
+        unreferenced.sort_by(|a, b| self.paths[a].cmp(&self.paths[b]));
+        unreferenced
+    }
+
+    /// Returns the given resources and every resource they refer to, directly or indirectly.
+    pub fn reachable_from(&self, roots: impl IntoIterator<Item = Uuid>) -> FxHashSet<Uuid> {
+        let mut reachable = FxHashSet::default();
+        let mut stack = roots.into_iter().collect::<Vec<_>>();
+        while let Some(uuid) = stack.pop() {
+            if reachable.insert(uuid) {
+                stack.extend(self.references(uuid));
+            }
+        }
+        reachable
+    }
+}
+
 #[cfg(test)]
 mod test {
     use fyrox_core::Uuid;
 
     use super::*;
-    use crate::untyped::ResourceKind;
+    use crate::{core::visitor::Visit, untyped::ResourceKind};
 
     #[test]
     fn resource_graph_node_new() {
@@ -172,4 +404,58 @@ mod test {
         let s = graph.pretty_print();
         assert_eq!(s, "External\n\tExternal\n".to_string());
     }
+
+    #[test]
+    fn resource_reference_index() {
+        let scene = Uuid::new_v4();
+        let material = Uuid::new_v4();
+        let texture = Uuid::new_v4();
+        let blueprint = Uuid::new_v4();
+        let unused = Uuid::new_v4();
+        let mut index = ResourceReferenceIndex::new(
+            [
+                (scene, "data/level.rgs"),
+                (material, "data/wall.material"),
+                (texture, "data/wall.png"),
+                (blueprint, "data/door.blueprint"),
+                (unused, "data/old.png"),
+            ]
+            .into_iter()
+            .map(|(uuid, path)| (uuid, PathBuf::from(path)))
+            .collect(),
+        );
+
+        // Resource handles are stored as ids.
+        let mut visitor = Visitor::new();
+        let mut handle = material;
+        handle.visit("Uuid", &mut visitor).unwrap();
+        // Unknown ids are not references.
+        let mut other = Uuid::new_v4();
+        other.visit("Other", &mut visitor).unwrap();
+        let mut blueprint_path = "./data/door.blueprint".to_string();
+        blueprint_path.visit("Blueprint", &mut visitor).unwrap();
+        index.scan_data(scene, &visitor.save_binary_to_vec().unwrap());
+
+        index.scan_data(material, format!("(texture: \"{texture}\")").as_bytes());
+        index.scan_data(blueprint, br#"{"prefab": "data/level.rgs"}"#);
+
+        let sorted = |iter: &mut dyn Iterator<Item = Uuid>| {
+            let mut vec = iter.collect::<Vec<_>>();
+            vec.sort();
+            vec
+        };
+        let mut expected = vec![material, blueprint];
+        expected.sort();
+        assert_eq!(sorted(&mut index.references(scene)), expected);
+        assert_eq!(sorted(&mut index.referencers(texture)), vec![material]);
+        assert_eq!(sorted(&mut index.referencers(scene)), vec![blueprint]);
+        assert_eq!(index.unreferenced(), vec![unused]);
+        assert_eq!(index.find_uuid(Path::new("./data/wall.png")), Some(texture));
+
+        let reachable = index.reachable_from([material]);
+        assert_eq!(reachable.len(), 2);
+        assert!(reachable.contains(&texture));
+        // Cycles are fine.
+        assert_eq!(index.reachable_from([scene]).len(), 4);
+    }
 }
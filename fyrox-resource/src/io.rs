@@ -19,7 +19,16 @@
 // SOFTWARE.
 
 //! Provides an interface for IO operations that a resource loader will use, this facilitates
-//! things such as loading assets within archive files
+//! things such as loading assets within archive files (see [`PackedResourceIo`]) or stacking
+//! mods on top of the game files (see [`LayeredResourceIo`])
+
+mod directory;
+mod layered;
+mod packed;
+
+pub use directory::DirectoryResourceIo;
+pub use layered::LayeredResourceIo;
+pub use packed::{PackedArchiveWriter, PackedCompression, PackedEntry, PackedResourceIo};
 
 use fyrox_core::io::FileError;
 use fyrox_core::{make_relative_path, replace_slashes};
@@ -160,6 +169,15 @@ pub trait ResourceIo: Send + Sync + 'static {
         })
     }
 
+    /// Priority of the source that provides the file at the given path, for objects that combine
+    /// several sources (see [`LayeredResourceIo`]). When two files claim the same resource, the
+    /// resource registry picks the file with the higher priority.
+    ///
+    /// Default implementation returns zero.
+    fn source_priority(&self, #[allow(unused)] path: &Path) -> usize {
+        0
+    }
+
     /// Checks whether the given file name is valid or not.
     fn is_valid_file_name(&self, name: &OsStr) -> bool;
 
@@ -232,6 +250,27 @@ pub fn normalize_path(path: impl AsRef<Path>) -> Result<PathBuf, FileError> {
     Ok(replace_slashes(ret))
 }
 
+/// Same as [`normalize_path`], but returns `None` instead of failing for paths outside of the
+/// working directory. Absolute paths inside of the working directory are made relative to it.
+pub(crate) fn try_normalize_path(path: &Path) -> Option<PathBuf> {
+    let relative = if path.is_absolute() {
+        let current_dir = std::env::current_dir().ok()?;
+        path.strip_prefix(current_dir).ok()?.to_path_buf()
+    } else {
+        path.to_path_buf()
+    };
+    let mut depth = 0usize;
+    for component in relative.components() {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::ParentDir => depth = depth.checked_sub(1)?,
+            Component::CurDir => (),
+            Component::Prefix(_) | Component::RootDir => return None,
+        }
+    }
+    normalize_path(relative).ok()
+}
+
 impl ResourceIo for FsResourceIo {
     fn can_write(&self) -> bool {
         cfg!(all(not(target_os = "android"), not(target_arch = "wasm32")))
@@ -0,0 +1,195 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Resource IO for a folder other than the working directory, see [`DirectoryResourceIo`].
+
+use crate::io::{
+    try_normalize_path, FileReader, FsResourceIo, PathIter, ResourceIo, ResourceIoFuture,
+};
+use fyrox_core::io::FileError;
+use std::{
+    ffi::OsStr,
+    future::ready,
+    path::{Path, PathBuf},
+};
+
+/// File system IO that serves resource paths from a root folder: `data/level.rgs` is read from
+/// `<root>/data/level.rgs`. Useful as a layer of [`super::LayeredResourceIo`], for example for a
+/// folder with user mods.
+#[derive(Debug, Clone)]
+pub struct DirectoryResourceIo {
+    root: PathBuf,
+}
+
+impl DirectoryResourceIo {
+    /// Creates IO that serves files from the given folder.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// The folder the files are served from.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn full_path(&self, path: &Path) -> Result<PathBuf, FileError> {
+        Ok(self.root.join(self.canonicalize_path(path)?))
+    }
+
+    /// Turns a file system path back into a resource path.
+    fn resource_path(&self, full_path: &Path) -> Option<PathBuf> {
+        try_normalize_path(full_path.strip_prefix(&self.root).ok()?)
+    }
+
+    fn resource_paths(&self, iter: PathIter) -> PathIter {
+        let paths = iter
+            .filter_map(|path| self.resource_path(&path))
+            .collect::<Vec<_>>();
+        Box::new(paths.into_iter())
+    }
+}
+
+impl ResourceIo for DirectoryResourceIo {
+    fn can_write(&self) -> bool {
+        FsResourceIo.can_write()
+    }
+
+    fn can_read_directories(&self) -> bool {
+        FsResourceIo.can_read_directories()
+    }
+
+    fn load_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, Result<Vec<u8>, FileError>> {
+        Box::pin(async move { FsResourceIo.load_file(&self.full_path(path)?).await })
+    }
+
+    fn write_file<'a>(
+        &'a self,
+        path: &'a Path,
+        data: Vec<u8>,
+    ) -> ResourceIoFuture<'a, Result<(), FileError>> {
+        Box::pin(async move { FsResourceIo.write_file(&self.full_path(path)?, data).await })
+    }
+
+    fn write_file_sync(&self, path: &Path, data: &[u8]) -> Result<(), FileError> {
+        FsResourceIo.write_file_sync(&self.full_path(path)?, data)
+    }
+
+    fn create_dir_all_sync(&self, path: &Path) -> Result<(), FileError> {
+        FsResourceIo.create_dir_all_sync(&self.full_path(path)?)
+    }
+
+    fn move_file<'a>(
+        &'a self,
+        source: &'a Path,
+        dest: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<(), FileError>> {
+        Box::pin(async move {
+            FsResourceIo
+                .move_file(&self.full_path(source)?, &self.full_path(dest)?)
+                .await
+        })
+    }
+
+    fn delete_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, Result<(), FileError>> {
+        Box::pin(async move { FsResourceIo.delete_file(&self.full_path(path)?).await })
+    }
+
+    fn delete_file_sync(&self, path: &Path) -> Result<(), FileError> {
+        FsResourceIo.delete_file_sync(&self.full_path(path)?)
+    }
+
+    fn copy_file<'a>(
+        &'a self,
+        source: &'a Path,
+        dest: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<(), FileError>> {
+        Box::pin(async move {
+            FsResourceIo
+                .copy_file(&self.full_path(source)?, &self.full_path(dest)?)
+                .await
+        })
+    }
+
+    fn canonicalize_path<'a>(&'a self, path: &'a Path) -> Result<PathBuf, FileError> {
+        let path = path.strip_prefix(&self.root).unwrap_or(path);
+        try_normalize_path(path)
+            .ok_or_else(|| FileError::Custom(format!("{path:?} is outside of {:?}", self.root)))
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathIter, FileError>> {
+        Box::pin(async move {
+            let iter = FsResourceIo.read_directory(&self.full_path(path)?).await?;
+            Ok(self.resource_paths(iter))
+        })
+    }
+
+    fn walk_directory<'a>(
+        &'a self,
+        path: &'a Path,
+        max_depth: usize,
+    ) -> ResourceIoFuture<'a, Result<PathIter, FileError>> {
+        Box::pin(async move {
+            let iter = FsResourceIo
+                .walk_directory(&self.full_path(path)?, max_depth)
+                .await?;
+            Ok(self.resource_paths(iter))
+        })
+    }
+
+    fn file_reader<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn FileReader>, FileError>> {
+        Box::pin(async move { FsResourceIo.file_reader(&self.full_path(path)?).await })
+    }
+
+    fn is_valid_file_name(&self, name: &OsStr) -> bool {
+        FsResourceIo.is_valid_file_name(name)
+    }
+
+    fn exists<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        match self.full_path(path) {
+            Ok(path) => Box::pin(async move { FsResourceIo.exists(&path).await }),
+            Err(_) => Box::pin(ready(false)),
+        }
+    }
+
+    fn exists_sync(&self, path: &Path) -> bool {
+        self.full_path(path)
+            .is_ok_and(|path| FsResourceIo.exists_sync(&path))
+    }
+
+    fn is_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        match self.full_path(path) {
+            Ok(path) => Box::pin(async move { FsResourceIo.is_file(&path).await }),
+            Err(_) => Box::pin(ready(false)),
+        }
+    }
+
+    fn is_dir<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        match self.full_path(path) {
+            Ok(path) => Box::pin(async move { FsResourceIo.is_dir(&path).await }),
+            Err(_) => Box::pin(ready(false)),
+        }
+    }
+}
@@ -0,0 +1,343 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Stacks of resource IO backends, see [`LayeredResourceIo`].
+
+use crate::io::{try_normalize_path, FileReader, PathIter, ResourceIo, ResourceIoFuture};
+use fyrox_core::io::FileError;
+use std::{
+    collections::BTreeSet,
+    ffi::OsStr,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Resource IO that stacks several backends, for example a packed base game, a folder with DLC
+/// and a folder with user mods. A file of an upper layer hides the file with the same path in
+/// the layers below, so patches and mods only need to contain the files they change.
+///
+/// - Reads come from the top-most layer that has the file.
+/// - Directory listings are merged from all layers.
+/// - Writes go to the top-most writable layer. Deleting a file there reveals the file of a lower
+///   layer, if there is one.
+/// - [`ResourceIo::source_priority`] is the index of the layer that provides a file, so the
+///   resource registry prefers upper layers when two files share a resource UUID.
+///
+/// ```rust,no_run
+/// # use fyrox_resource::io::{DirectoryResourceIo, LayeredResourceIo, PackedResourceIo};
+/// # use std::sync::Arc;
+/// let io = LayeredResourceIo::new(Arc::new(PackedResourceIo::open("assets.pak").unwrap()))
+///     .with_layer(Arc::new(DirectoryResourceIo::new("dlc")))
+///     .with_layer(Arc::new(DirectoryResourceIo::new("mods")));
+/// ```
+pub struct LayeredResourceIo {
+    /// From the bottom to the top.
+    layers: Vec<Arc<dyn ResourceIo>>,
+}
+
+impl LayeredResourceIo {
+    /// Creates a stack with a single (bottom) layer.
+    pub fn new(base: Arc<dyn ResourceIo>) -> Self {
+        Self { layers: vec![base] }
+    }
+
+    /// Puts the layer on top of the stack.
+    pub fn with_layer(mut self, layer: Arc<dyn ResourceIo>) -> Self {
+        self.push_layer(layer);
+        self
+    }
+
+    /// Puts the layer on top of the stack.
+    pub fn push_layer(&mut self, layer: Arc<dyn ResourceIo>) {
+        self.layers.push(layer);
+    }
+
+    /// Layers from the bottom to the top.
+    pub fn layers(&self) -> &[Arc<dyn ResourceIo>] {
+        &self.layers
+    }
+
+    fn top_down(&self) -> impl Iterator<Item = &Arc<dyn ResourceIo>> {
+        self.layers.iter().rev()
+    }
+
+    async fn layer_with(&self, path: &Path) -> Option<&Arc<dyn ResourceIo>> {
+        for layer in self.top_down() {
+            if layer.exists(path).await {
+                return Some(layer);
+            }
+        }
+        None
+    }
+
+    fn writable_layer(&self) -> Result<&Arc<dyn ResourceIo>, FileError> {
+        self.top_down()
+            .find(|layer| layer.can_write())
+            .ok_or_else(|| FileError::Custom("No layer of the resource IO is writable".to_string()))
+    }
+
+    fn not_found(path: &Path) -> FileError {
+        FileError::Custom(format!("{path:?} does not exist in any layer"))
+    }
+
+    /// Merges listings of every layer that has the directory. Paths are normalized, so the same
+    /// file of several layers is listed once.
+    async fn merge_listings<'a>(
+        &'a self,
+        list: impl Fn(&'a Arc<dyn ResourceIo>) -> ResourceIoFuture<'a, Result<PathIter, FileError>>,
+    ) -> Result<PathIter, FileError> {
+        let mut merged = BTreeSet::new();
+        let mut error = None;
+        let mut listed = false;
+        for layer in self.layers.iter() {
+            match list(layer).await {
+                Ok(iter) => {
+                    listed = true;
+                    merged.extend(iter.map(|path| try_normalize_path(&path).unwrap_or(path)));
+                }
+                Err(err) => error = Some(err),
+            }
+        }
+        match error {
+            Some(error) if !listed => Err(error),
+            _ => {
+                let iter: PathIter = Box::new(merged.into_iter());
+                Ok(iter)
+            }
+        }
+    }
+}
+
+impl ResourceIo for LayeredResourceIo {
+    fn can_write(&self) -> bool {
+        self.writable_layer().is_ok()
+    }
+
+    fn can_read_directories(&self) -> bool {
+        self.layers.iter().any(|layer| layer.can_read_directories())
+    }
+
+    fn load_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, Result<Vec<u8>, FileError>> {
+        Box::pin(async move {
+            match self.layer_with(path).await {
+                Some(layer) => layer.load_file(path).await,
+                None => Err(Self::not_found(path)),
+            }
+        })
+    }
+
+    fn write_file<'a>(
+        &'a self,
+        path: &'a Path,
+        data: Vec<u8>,
+    ) -> ResourceIoFuture<'a, Result<(), FileError>> {
+        Box::pin(async move { self.writable_layer()?.write_file(path, data).await })
+    }
+
+    fn write_file_sync(&self, path: &Path, data: &[u8]) -> Result<(), FileError> {
+        self.writable_layer()?.write_file_sync(path, data)
+    }
+
+    fn create_dir_all_sync(&self, path: &Path) -> Result<(), FileError> {
+        self.writable_layer()?.create_dir_all_sync(path)
+    }
+
+    fn move_file<'a>(
+        &'a self,
+        source: &'a Path,
+        dest: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<(), FileError>> {
+        Box::pin(async move { self.writable_layer()?.move_file(source, dest).await })
+    }
+
+    fn delete_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, Result<(), FileError>> {
+        Box::pin(async move { self.writable_layer()?.delete_file(path).await })
+    }
+
+    fn delete_file_sync(&self, path: &Path) -> Result<(), FileError> {
+        self.writable_layer()?.delete_file_sync(path)
+    }
+
+    /// Copies the visible file (which may come from a lower layer) into the writable layer.
+    fn copy_file<'a>(
+        &'a self,
+        source: &'a Path,
+        dest: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<(), FileError>> {
+        Box::pin(async move {
+            let data = self.load_file(source).await?;
+            self.write_file(dest, data).await
+        })
+    }
+
+    fn canonicalize_path<'a>(&'a self, path: &'a Path) -> Result<PathBuf, FileError> {
+        let mut error = None;
+        for layer in self.top_down() {
+            match layer.canonicalize_path(path) {
+                Ok(path) => return Ok(path),
+                Err(err) => error = Some(err),
+            }
+        }
+        Err(error.unwrap_or_else(|| Self::not_found(path)))
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathIter, FileError>> {
+        Box::pin(self.merge_listings(move |layer| layer.read_directory(path)))
+    }
+
+    fn walk_directory<'a>(
+        &'a self,
+        path: &'a Path,
+        max_depth: usize,
+    ) -> ResourceIoFuture<'a, Result<PathIter, FileError>> {
+        Box::pin(self.merge_listings(move |layer| layer.walk_directory(path, max_depth)))
+    }
+
+    fn file_reader<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn FileReader>, FileError>> {
+        Box::pin(async move {
+            match self.layer_with(path).await {
+                Some(layer) => layer.file_reader(path).await,
+                None => Err(Self::not_found(path)),
+            }
+        })
+    }
+
+    fn is_valid_file_name(&self, name: &OsStr) -> bool {
+        match self.writable_layer() {
+            Ok(layer) => layer.is_valid_file_name(name),
+            Err(_) => self
+                .layers
+                .iter()
+                .all(|layer| layer.is_valid_file_name(name)),
+        }
+    }
+
+    fn exists<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(async move { self.layer_with(path).await.is_some() })
+    }
+
+    fn exists_sync(&self, path: &Path) -> bool {
+        self.layers.iter().any(|layer| layer.exists_sync(path))
+    }
+
+    fn is_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(async move {
+            match self.layer_with(path).await {
+                Some(layer) => layer.is_file(path).await,
+                None => false,
+            }
+        })
+    }
+
+    fn is_dir<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(async move {
+            for layer in self.layers.iter() {
+                if layer.is_dir(path).await {
+                    return true;
+                }
+            }
+            false
+        })
+    }
+
+    fn source_priority(&self, path: &Path) -> usize {
+        self.layers
+            .iter()
+            .rposition(|layer| layer.exists_sync(path))
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::io::{
+        DirectoryResourceIo, PackedArchiveWriter, PackedCompression, PackedResourceIo,
+    };
+    use fyrox_core::futures::executor::block_on;
+    use std::io::Cursor;
+
+    const TEST_FOLDER: &str = "./test_layered_io";
+
+    fn packed_base() -> PackedResourceIo {
+        let mut writer = PackedArchiveWriter::new(Cursor::new(Vec::new())).unwrap();
+        for (path, data) in [
+            ("data/level.rgs", "base level"),
+            ("data/music.ogg", "base music"),
+        ] {
+            writer
+                .add_file(Path::new(path), data.as_bytes(), PackedCompression::None)
+                .unwrap();
+        }
+        PackedResourceIo::from_bytes(writer.finish().unwrap().into_inner()).unwrap()
+    }
+
+    #[test]
+    fn test_layers() {
+        let _ = std::fs::remove_dir_all(TEST_FOLDER);
+        std::fs::create_dir_all(Path::new(TEST_FOLDER).join("data")).unwrap();
+        std::fs::write(
+            Path::new(TEST_FOLDER).join("data/level.rgs"),
+            "modded level",
+        )
+        .unwrap();
+        std::fs::write(Path::new(TEST_FOLDER).join("data/new.rgs"), "new level").unwrap();
+
+        let io = LayeredResourceIo::new(Arc::new(packed_base()))
+            .with_layer(Arc::new(DirectoryResourceIo::new(TEST_FOLDER)));
+
+        let load = |path: &str| String::from_utf8(block_on(io.load_file(Path::new(path))).unwrap());
+        assert_eq!(load("data/level.rgs").unwrap(), "modded level");
+        assert_eq!(load("data/music.ogg").unwrap(), "base music");
+        assert_eq!(load("data/new.rgs").unwrap(), "new level");
+        assert!(block_on(io.load_file(Path::new("data/missing.rgs"))).is_err());
+
+        let listing = block_on(io.read_directory(Path::new("data")))
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            listing,
+            ["data/level.rgs", "data/music.ogg", "data/new.rgs"].map(PathBuf::from)
+        );
+        assert!(block_on(io.is_dir(Path::new("data"))));
+
+        assert_eq!(io.source_priority(Path::new("data/music.ogg")), 0);
+        assert_eq!(io.source_priority(Path::new("data/level.rgs")), 1);
+
+        // Writes go to the mod folder, the packed base is read-only.
+        assert!(io.can_write());
+        block_on(io.copy_file(Path::new("data/music.ogg"), Path::new("data/copy.ogg"))).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(Path::new(TEST_FOLDER).join("data/copy.ogg")).unwrap(),
+            "base music"
+        );
+        // Removing the modded file reveals the base one.
+        io.delete_file_sync(Path::new("data/level.rgs")).unwrap();
+        assert_eq!(load("data/level.rgs").unwrap(), "base level");
+
+        std::fs::remove_dir_all(TEST_FOLDER).unwrap();
+    }
+}
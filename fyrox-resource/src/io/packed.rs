@@ -0,0 +1,700 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Packed archives: a single file that stores many resource files, so a shipped game does not
+//! need thousands of loose files. See [`PackedResourceIo`] for reading and [`PackedArchiveWriter`]
+//! for building archives.
+//!
+//! The layout is a fixed header, the data of every entry and an index at the end of the file:
+//!
+//! ```text
+//! magic "FYRXPACK" | version: u32 | index offset: u64 | entry data ... |
+//! entry count: u32 | (path length: u32 | path | compression: u8 | offset: u64 |
+//!                     stored size: u64 | size: u64)*
+//! ```
+//!
+//! All numbers are little-endian, paths are normalized (see [`normalize_path`]) and relative to
+//! the working directory of the game.
+
+use crate::io::{
+    try_normalize_path, FileReader, FsResourceIo, PathIter, ResourceIo, ResourceIoFuture,
+};
+use flate2::{read::DeflateDecoder, write::DeflateEncoder, Compression};
+use fyrox_core::{io::FileError, parking_lot::Mutex};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ffi::OsStr,
+    fmt::{Debug, Formatter},
+    fs::File,
+    future::ready,
+    io::{Cursor, Read, Seek, SeekFrom, Write},
+    path::{Component, Path, PathBuf},
+};
+
+const MAGIC: &[u8; 8] = b"FYRXPACK";
+const VERSION: u32 = 1;
+const HEADER_SIZE: u64 = 8 + 4 + 8;
+/// Paths in the index are never longer than this, so a corrupted length does not allocate much.
+const MAX_PATH_LENGTH: u32 = 4096;
+/// Decompression buffers are never preallocated larger than this, the rest is allocated as the
+/// data is decompressed.
+const MAX_CAPACITY_HINT: u64 = 64 * 1024 * 1024;
+
+/// Compression of a single archive entry.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum PackedCompression {
+    /// The entry is stored as is.
+    None,
+    /// The entry is compressed with Deflate. Entries that do not get smaller are stored as is.
+    #[default]
+    Deflate,
+}
+
+impl PackedCompression {
+    fn to_byte(self) -> u8 {
+        match self {
+            PackedCompression::None => 0,
+            PackedCompression::Deflate => 1,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Result<Self, FileError> {
+        match byte {
+            0 => Ok(PackedCompression::None),
+            1 => Ok(PackedCompression::Deflate),
+            _ => Err(FileError::Custom(format!(
+                "Unknown compression {byte} of a packed archive entry"
+            ))),
+        }
+    }
+}
+
+/// Location of a file in a packed archive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackedEntry {
+    /// Compression of the stored data.
+    pub compression: PackedCompression,
+    /// Offset of the stored data from the beginning of the archive.
+    pub offset: u64,
+    /// Size of the stored (possibly compressed) data.
+    pub stored_size: u64,
+    /// Size of the file.
+    pub size: u64,
+}
+
+/// Writes packed archives. Files are written as they are added, the index is written by
+/// [`Self::finish`].
+pub struct PackedArchiveWriter<W: Write + Seek> {
+    writer: W,
+    entries: BTreeMap<PathBuf, PackedEntry>,
+    position: u64,
+}
+
+impl<W: Write + Seek> PackedArchiveWriter<W> {
+    /// Starts a new archive in the given writer.
+    pub fn new(mut writer: W) -> Result<Self, FileError> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        // Index offset, patched by `finish`.
+        writer.write_all(&0u64.to_le_bytes())?;
+        Ok(Self {
+            writer,
+            entries: Default::default(),
+            position: HEADER_SIZE,
+        })
+    }
+
+    /// Adds a file to the archive. The path must be relative to the working directory of the
+    /// game, a file with the same path replaces the previous one in the index.
+    pub fn add_file(
+        &mut self,
+        path: &Path,
+        data: &[u8],
+        compression: PackedCompression,
+    ) -> Result<(), FileError> {
+        let path = try_normalize_path(path)
+            .ok_or_else(|| FileError::Custom(format!("Invalid archive path {path:?}")))?;
+
+        let compressed = match compression {
+            PackedCompression::None => None,
+            PackedCompression::Deflate => {
+                let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(data)?;
+                Some(encoder.finish()?).filter(|compressed| compressed.len() < data.len())
+            }
+        };
+        let (compression, stored) = match compressed.as_ref() {
+            Some(compressed) => (PackedCompression::Deflate, compressed.as_slice()),
+            None => (PackedCompression::None, data),
+        };
+
+        self.writer.write_all(stored)?;
+        self.entries.insert(
+            path,
+            PackedEntry {
+                compression,
+                offset: self.position,
+                stored_size: stored.len() as u64,
+                size: data.len() as u64,
+            },
+        );
+        self.position += stored.len() as u64;
+        Ok(())
+    }
+
+    /// Adds every file of the `directory` (recursively) that passes the filter. Files are stored
+    /// with paths relative to `root`, which must contain the directory. Returns the number of
+    /// added files.
+    pub fn add_directory(
+        &mut self,
+        root: &Path,
+        directory: &Path,
+        filter: &dyn Fn(&Path) -> bool,
+        compression: PackedCompression,
+    ) -> Result<usize, FileError> {
+        let mut count = 0;
+        for entry in walkdir::WalkDir::new(directory).sort_by_file_name() {
+            let entry = entry.map_err(|err| FileError::Custom(err.to_string()))?;
+            if !entry.file_type().is_file() || !filter(entry.path()) {
+                continue;
+            }
+            let relative = if root.components().all(|c| c == Component::CurDir) {
+                entry.path()
+            } else {
+                entry.path().strip_prefix(root).map_err(|_| {
+                    FileError::Custom(format!("{:?} is not inside of {root:?}", entry.path()))
+                })?
+            };
+            let data = std::fs::read(entry.path())?;
+            self.add_file(relative, &data, compression)?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Writes the index and returns the underlying writer.
+    pub fn finish(mut self) -> Result<W, FileError> {
+        let index_offset = self.position;
+        self.writer
+            .write_all(&(self.entries.len() as u32).to_le_bytes())?;
+        for (path, entry) in self.entries.iter() {
+            let path = path.to_string_lossy();
+            self.writer.write_all(&(path.len() as u32).to_le_bytes())?;
+            self.writer.write_all(path.as_bytes())?;
+            self.writer.write_all(&[entry.compression.to_byte()])?;
+            self.writer.write_all(&entry.offset.to_le_bytes())?;
+            self.writer.write_all(&entry.stored_size.to_le_bytes())?;
+            self.writer.write_all(&entry.size.to_le_bytes())?;
+        }
+        self.writer.seek(SeekFrom::Start(8 + 4))?;
+        self.writer.write_all(&index_offset.to_le_bytes())?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+fn read_u32(reader: &mut impl Read) -> Result<u32, FileError> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(reader: &mut impl Read) -> Result<u64, FileError> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn corrupted(reason: &str) -> FileError {
+    FileError::Custom(format!("Corrupted packed archive: {reason}"))
+}
+
+/// Reads the index of an archive. Every size and offset is checked against the length of the
+/// archive, so reading the entries later never goes out of the archive.
+fn read_index(
+    reader: &mut (impl Read + Seek),
+) -> Result<BTreeMap<PathBuf, PackedEntry>, FileError> {
+    let length = reader.seek(SeekFrom::End(0))?;
+    reader.seek(SeekFrom::Start(0))?;
+
+    let mut magic = [0; 8];
+    reader.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(FileError::Custom("Not a packed archive".to_string()));
+    }
+    let version = read_u32(reader)?;
+    if version != VERSION {
+        return Err(FileError::Custom(format!(
+            "Unsupported packed archive version {version}"
+        )));
+    }
+    let index_offset = read_u64(reader)?;
+    if !(HEADER_SIZE..=length).contains(&index_offset) {
+        return Err(corrupted("index is out of the archive"));
+    }
+    reader.seek(SeekFrom::Start(index_offset))?;
+
+    let count = read_u32(reader)?;
+    let mut entries = BTreeMap::new();
+    for _ in 0..count {
+        let path_length = read_u32(reader)?;
+        if path_length > MAX_PATH_LENGTH {
+            return Err(corrupted("path is too long"));
+        }
+        let mut path = vec![0; path_length as usize];
+        reader.read_exact(&mut path)?;
+        let path = String::from_utf8(path)
+            .map_err(|_| FileError::Custom("Invalid path in a packed archive".to_string()))?;
+        let mut compression = [0; 1];
+        reader.read_exact(&mut compression)?;
+        let entry = PackedEntry {
+            compression: PackedCompression::from_byte(compression[0])?,
+            offset: read_u64(reader)?,
+            stored_size: read_u64(reader)?,
+            size: read_u64(reader)?,
+        };
+        // Entry data is stored between the header and the index.
+        let end = entry.offset.checked_add(entry.stored_size);
+        if entry.offset < HEADER_SIZE || end.is_none_or(|end| end > index_offset) {
+            return Err(corrupted("entry is out of the archive"));
+        }
+        entries.insert(PathBuf::from(path), entry);
+    }
+    Ok(entries)
+}
+
+enum ArchiveSource {
+    Memory(Vec<u8>),
+    #[cfg(not(target_arch = "wasm32"))]
+    Mapped(fyrox_core::io::MappedFile),
+    File(Mutex<File>),
+}
+
+/// Read-only resource IO that serves files from a packed archive, see the module docs. Files that
+/// are not in the archive do not exist for this IO, every write fails.
+pub struct PackedResourceIo {
+    entries: BTreeMap<PathBuf, PackedEntry>,
+    /// Children of every directory, `.` is the root.
+    directories: BTreeMap<PathBuf, BTreeSet<PathBuf>>,
+    source: ArchiveSource,
+}
+
+impl Debug for PackedResourceIo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PackedResourceIo")
+            .field("entries", &self.entries.len())
+            .finish()
+    }
+}
+
+impl PackedResourceIo {
+    /// Default name of the archive, the executor uses it if the file exists in its working
+    /// directory.
+    pub const DEFAULT_PATH: &'static str = "assets.pak";
+
+    fn new(entries: BTreeMap<PathBuf, PackedEntry>, source: ArchiveSource) -> Self {
+        let mut directories = BTreeMap::<PathBuf, BTreeSet<PathBuf>>::new();
+        directories.entry(PathBuf::from(".")).or_default();
+        for path in entries.keys() {
+            let mut child = path.as_path();
+            while let Some(parent) = child.parent() {
+                let parent = if parent.as_os_str().is_empty() {
+                    Path::new(".")
+                } else {
+                    parent
+                };
+                let children = directories.entry(parent.to_path_buf()).or_default();
+                let is_new = children.insert(child.to_path_buf());
+                if !is_new || parent == Path::new(".") {
+                    break;
+                }
+                child = parent;
+            }
+        }
+        Self {
+            entries,
+            directories,
+            source,
+        }
+    }
+
+    /// Opens an archive, file data is read from the file on demand.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, FileError> {
+        let mut file = File::open(path)?;
+        let entries = read_index(&mut file)?;
+        Ok(Self::new(entries, ArchiveSource::File(Mutex::new(file))))
+    }
+
+    /// Reads an archive from a file mapped into memory, which makes reads cheaper than
+    /// [`Self::open`]. Mapping is unsafe, so it is done by the caller: the archive file must not
+    /// be modified or truncated while the returned instance exists, see
+    /// [`fyrox_core::io::MappedFile::open`].
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_mapped(map: fyrox_core::io::MappedFile) -> Result<Self, FileError> {
+        let entries = read_index(&mut Cursor::new(&map[..]))?;
+        Ok(Self::new(entries, ArchiveSource::Mapped(map)))
+    }
+
+    /// Reads an archive from memory, for example from bytes embedded into the executable.
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, FileError> {
+        let entries = read_index(&mut Cursor::new(&bytes[..]))?;
+        Ok(Self::new(entries, ArchiveSource::Memory(bytes)))
+    }
+
+    /// Files of the archive, sorted by path.
+    pub fn entries(&self) -> impl Iterator<Item = (&Path, &PackedEntry)> {
+        self.entries
+            .iter()
+            .map(|(path, entry)| (path.as_path(), entry))
+    }
+
+    /// Returns the entry of the file at the given path.
+    pub fn entry(&self, path: &Path) -> Option<&PackedEntry> {
+        self.entries.get(&try_normalize_path(path)?)
+    }
+
+    fn stored_data(&self, entry: &PackedEntry) -> Result<Vec<u8>, FileError> {
+        let start = usize::try_from(entry.offset).map_err(|_| corrupted("entry is too large"))?;
+        let size =
+            usize::try_from(entry.stored_size).map_err(|_| corrupted("entry is too large"))?;
+        let range = start..start.saturating_add(size);
+        let slice = |bytes: &[u8]| {
+            bytes
+                .get(range.clone())
+                .map(|data| data.to_vec())
+                .ok_or_else(|| FileError::Custom("Packed archive entry is truncated".to_string()))
+        };
+        match &self.source {
+            ArchiveSource::Memory(bytes) => slice(bytes),
+            #[cfg(not(target_arch = "wasm32"))]
+            ArchiveSource::Mapped(map) => slice(map),
+            ArchiveSource::File(file) => {
+                let mut file = file.lock();
+                file.seek(SeekFrom::Start(entry.offset))?;
+                let mut data = vec![0; size];
+                file.read_exact(&mut data)?;
+                Ok(data)
+            }
+        }
+    }
+
+    /// Reads and decompresses the file at the given path.
+    pub fn read(&self, path: &Path) -> Result<Vec<u8>, FileError> {
+        let entry = self
+            .entry(path)
+            .ok_or_else(|| FileError::Custom(format!("{path:?} is not in the packed archive")))?;
+        let stored = self.stored_data(entry)?;
+        match entry.compression {
+            PackedCompression::None => Ok(stored),
+            PackedCompression::Deflate => {
+                let mut data = Vec::with_capacity(entry.size.min(MAX_CAPACITY_HINT) as usize);
+                DeflateDecoder::new(stored.as_slice())
+                    .take(entry.size)
+                    .read_to_end(&mut data)?;
+                if data.len() as u64 != entry.size {
+                    return Err(corrupted("entry size does not match its data"));
+                }
+                Ok(data)
+            }
+        }
+    }
+
+    fn is_dir_sync(&self, path: &Path) -> bool {
+        try_normalize_path(path).is_some_and(|path| self.directories.contains_key(&path))
+    }
+
+    fn read_only_error(path: &Path) -> FileError {
+        FileError::Custom(format!(
+            "Unable to modify {path:?}, the packed archive is read-only"
+        ))
+    }
+
+    fn walk(&self, path: &Path, max_depth: usize) -> Vec<PathBuf> {
+        let Some(path) = try_normalize_path(path) else {
+            return Vec::new();
+        };
+        if self.entries.contains_key(&path) {
+            return vec![path];
+        }
+        if !self.directories.contains_key(&path) {
+            return Vec::new();
+        }
+        let mut paths = vec![path.clone()];
+        let mut level = vec![path];
+        for _ in 0..max_depth {
+            let next = level
+                .iter()
+                .filter_map(|dir| self.directories.get(dir))
+                .flatten()
+                .cloned()
+                .collect::<Vec<_>>();
+            if next.is_empty() {
+                break;
+            }
+            paths.extend(next.iter().cloned());
+            level = next;
+        }
+        paths
+    }
+}
+
+impl ResourceIo for PackedResourceIo {
+    fn can_write(&self) -> bool {
+        false
+    }
+
+    fn can_read_directories(&self) -> bool {
+        true
+    }
+
+    fn load_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, Result<Vec<u8>, FileError>> {
+        Box::pin(ready(self.read(path)))
+    }
+
+    fn write_file<'a>(
+        &'a self,
+        path: &'a Path,
+        _data: Vec<u8>,
+    ) -> ResourceIoFuture<'a, Result<(), FileError>> {
+        Box::pin(ready(Err(Self::read_only_error(path))))
+    }
+
+    fn write_file_sync(&self, path: &Path, _data: &[u8]) -> Result<(), FileError> {
+        Err(Self::read_only_error(path))
+    }
+
+    fn create_dir_all_sync(&self, path: &Path) -> Result<(), FileError> {
+        Err(Self::read_only_error(path))
+    }
+
+    fn move_file<'a>(
+        &'a self,
+        source: &'a Path,
+        _dest: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<(), FileError>> {
+        Box::pin(ready(Err(Self::read_only_error(source))))
+    }
+
+    fn delete_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, Result<(), FileError>> {
+        Box::pin(ready(Err(Self::read_only_error(path))))
+    }
+
+    fn delete_file_sync(&self, path: &Path) -> Result<(), FileError> {
+        Err(Self::read_only_error(path))
+    }
+
+    fn copy_file<'a>(
+        &'a self,
+        _source: &'a Path,
+        dest: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<(), FileError>> {
+        Box::pin(ready(Err(Self::read_only_error(dest))))
+    }
+
+    fn canonicalize_path<'a>(&'a self, path: &'a Path) -> Result<PathBuf, FileError> {
+        try_normalize_path(path)
+            .ok_or_else(|| FileError::Custom(format!("{path:?} is outside of the archive")))
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathIter, FileError>> {
+        let children = try_normalize_path(path)
+            .and_then(|path| self.directories.get(&path))
+            .map(|children| children.iter().cloned().collect::<Vec<_>>())
+            .ok_or_else(|| FileError::Custom(format!("{path:?} is not a directory")));
+        Box::pin(ready(children.map(|children| {
+            let iter: PathIter = Box::new(children.into_iter());
+            iter
+        })))
+    }
+
+    fn walk_directory<'a>(
+        &'a self,
+        path: &'a Path,
+        max_depth: usize,
+    ) -> ResourceIoFuture<'a, Result<PathIter, FileError>> {
+        let iter: PathIter = Box::new(self.walk(path, max_depth).into_iter());
+        Box::pin(ready(Ok(iter)))
+    }
+
+    fn file_reader<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn FileReader>, FileError>> {
+        Box::pin(ready(self.read(path).map(|data| {
+            let reader: Box<dyn FileReader> = Box::new(Cursor::new(data));
+            reader
+        })))
+    }
+
+    fn is_valid_file_name(&self, name: &OsStr) -> bool {
+        FsResourceIo.is_valid_file_name(name)
+    }
+
+    fn exists<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(ready(self.exists_sync(path)))
+    }
+
+    fn exists_sync(&self, path: &Path) -> bool {
+        self.entry(path).is_some() || self.is_dir_sync(path)
+    }
+
+    fn is_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(ready(self.entry(path).is_some()))
+    }
+
+    fn is_dir<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(ready(self.is_dir_sync(path)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use fyrox_core::futures::executor::block_on;
+
+    /// Writes test files into a folder of its own for every test, since tests run in parallel.
+    fn write_test_files(folder: &str) -> PathBuf {
+        let root = PathBuf::from(folder);
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("data/models")).unwrap();
+        std::fs::write(root.join("data/level.rgs"), "level ".repeat(100)).unwrap();
+        std::fs::write(root.join("data/models/box.fbx"), [1, 2, 3]).unwrap();
+        std::fs::write(root.join("data/notes.log"), "skipped").unwrap();
+        root
+    }
+
+    fn pack(root: &Path) -> Vec<u8> {
+        let mut writer = PackedArchiveWriter::new(Cursor::new(Vec::new())).unwrap();
+        let count = writer
+            .add_directory(
+                root,
+                &root.join("data"),
+                &|path| path.extension().is_none_or(|ext| ext != "log"),
+                PackedCompression::Deflate,
+            )
+            .unwrap();
+        assert_eq!(count, 2);
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_directory_round_trip() {
+        let root = write_test_files("./test_packed_round_trip");
+        let io = PackedResourceIo::from_bytes(pack(&root)).unwrap();
+
+        let level = block_on(io.load_file(Path::new("./data/level.rgs"))).unwrap();
+        assert_eq!(level, "level ".repeat(100).into_bytes());
+        assert_eq!(
+            io.entry(Path::new("data/level.rgs")).unwrap().compression,
+            PackedCompression::Deflate
+        );
+        // Tiny files do not get smaller, so they are stored as is.
+        let model = io.entry(Path::new("data/models/box.fbx")).unwrap();
+        assert_eq!(model.compression, PackedCompression::None);
+        assert_eq!(
+            block_on(io.load_file(Path::new("data/models/box.fbx"))).unwrap(),
+            vec![1, 2, 3]
+        );
+        assert!(block_on(io.load_file(Path::new("data/notes.log"))).is_err());
+        assert!(block_on(io.write_file(Path::new("data/new.rgs"), vec![])).is_err());
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_directories() {
+        let root = write_test_files("./test_packed_directories");
+        let io = PackedResourceIo::from_bytes(pack(&root)).unwrap();
+
+        assert!(block_on(io.is_dir(Path::new("."))));
+        assert!(block_on(io.is_dir(Path::new("data/models"))));
+        assert!(block_on(io.is_file(Path::new("data/level.rgs"))));
+        assert!(!block_on(io.is_dir(Path::new("data/level.rgs"))));
+        assert!(io.exists_sync(Path::new("data")));
+        assert!(!io.exists_sync(Path::new("../data")));
+
+        let root_items = block_on(io.read_directory(Path::new(".")))
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(root_items, vec![PathBuf::from("data")]);
+        let data_items = block_on(io.read_directory(Path::new("data")))
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            data_items,
+            vec![
+                PathBuf::from("data/level.rgs"),
+                PathBuf::from("data/models")
+            ]
+        );
+
+        let walked = block_on(io.walk_directory(Path::new("data"), 1))
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(walked.len(), 3);
+        let walked = block_on(io.walk_directory(Path::new("."), usize::MAX))
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert!(walked.contains(&PathBuf::from("data/models/box.fbx")));
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_corrupted_index() {
+        let root = write_test_files("./test_packed_corrupted");
+        let bytes = pack(&root);
+        std::fs::remove_dir_all(root).unwrap();
+
+        let index = u64::from_le_bytes(bytes[12..20].try_into().unwrap()) as usize;
+        // The first entry is `data/level.rgs`, its fields follow the 14 bytes of its path.
+        let corrupt = |at: usize, value: &[u8]| {
+            let mut bytes = bytes.clone();
+            bytes[at..at + value.len()].copy_from_slice(value);
+            PackedResourceIo::from_bytes(bytes)
+        };
+        assert!(corrupt(12, &u64::MAX.to_le_bytes()).is_err());
+        assert!(corrupt(index + 4, &u32::MAX.to_le_bytes()).is_err());
+        assert!(corrupt(index + 23, &u64::MAX.to_le_bytes()).is_err());
+        assert!(corrupt(index + 31, &(index as u64).to_le_bytes()).is_err());
+
+        let io = corrupt(index + 39, &u64::MAX.to_le_bytes()).unwrap();
+        assert!(io.read(Path::new("data/level.rgs")).is_err());
+    }
+
+    #[test]
+    fn test_open_from_file() {
+        let root = write_test_files("./test_packed_open");
+        let archive = root.join("assets.pak");
+        std::fs::write(&archive, pack(&root)).unwrap();
+
+        let io = PackedResourceIo::open(&archive).unwrap();
+        assert_eq!(io.entries().count(), 2);
+        assert_eq!(
+            io.read(Path::new("data/level.rgs")).unwrap(),
+            "level ".repeat(100).into_bytes()
+        );
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -57,6 +57,7 @@ use std::{
 
 pub mod builtin;
 pub mod constructor;
+pub mod derived;
 pub mod entry;
 pub mod event;
 pub mod graph;
@@ -699,7 +700,10 @@ pub fn collect_used_resources(
 mod tests {
     use super::*;
     use crate::{
-        io::{FsResourceIo, ResourceIo},
+        io::{
+            DirectoryResourceIo, FsResourceIo, LayeredResourceIo, PackedArchiveWriter,
+            PackedCompression, PackedResourceIo, ResourceIo,
+        },
         loader::{BoxedLoaderFuture, LoaderPayload, ResourceLoader, ResourceLoadersContainer},
         manager::ResourceManager,
         metadata::ResourceMetadata,
@@ -717,7 +721,7 @@ mod tests {
     use std::{
         error::Error,
         fs::File,
-        io::Write,
+        io::{Cursor, Write},
         ops::Range,
         path::{Path, PathBuf},
         sync::Arc,
@@ -801,6 +805,8 @@ mod tests {
     const TEST_FOLDER1: &str = "./test_output1";
     const TEST_FOLDER2: &str = "./test_output2";
     const TEST_FOLDER3: &str = "./test_output3";
+    const TEST_FOLDER4: &str = "./test_output4";
+    const TEST_FOLDER5: &str = "./test_output5";
 
     fn make_file_path(root: &str, n: usize) -> PathBuf {
         Path::new(root).join(format!("test{n}.{}", MyDataLoader::EXT))
@@ -894,6 +900,84 @@ mod tests {
         assert_eq!(block_on(res2).unwrap().data_ref().data, 3);
     }
 
+    #[test]
+    fn test_resource_manager_request_packed() {
+        write_test_resources(TEST_FOLDER4, 0..2);
+        let registry_path = Path::new(TEST_FOLDER4).join("resources.registry");
+
+        // Create the metadata files, then pack the folder and remove it.
+        let mut loaders = ResourceLoadersContainer::new();
+        loaders.set(MyDataLoader {});
+        block_on(ResourceRegistry::scan(
+            Arc::new(FsResourceIo),
+            Arc::new(Mutex::new(loaders)),
+            &registry_path,
+            Default::default(),
+        ));
+        let mut writer = PackedArchiveWriter::new(Cursor::new(Vec::new())).unwrap();
+        writer
+            .add_directory(
+                Path::new("."),
+                Path::new(TEST_FOLDER4),
+                &|_| true,
+                PackedCompression::Deflate,
+            )
+            .unwrap();
+        let archive = writer.finish().unwrap().into_inner();
+        std::fs::remove_dir_all(TEST_FOLDER4).unwrap();
+
+        let io = PackedResourceIo::from_bytes(archive).unwrap();
+        let resource_manager = ResourceManager::new(Arc::new(io), Arc::new(TaskPool::new()));
+        resource_manager
+            .state()
+            .resource_registry
+            .safe_lock()
+            .set_path(registry_path);
+        resource_manager.add_loader(MyDataLoader {});
+        resource_manager.update_or_load_registry();
+        let registry = resource_manager.state().resource_registry.clone();
+        assert_eq!(registry.safe_lock().inner().len(), 2);
+
+        let res = resource_manager.request::<MyData>(make_file_path(TEST_FOLDER4, 1));
+        assert_eq!(block_on(res).unwrap().data_ref().data, 1);
+    }
+
+    #[test]
+    fn test_registry_scan_layer_collision() {
+        let base = Path::new(TEST_FOLDER5).join("base");
+        let mods = Path::new(TEST_FOLDER5).join("mods");
+        let uuid = uuid!("0c7b0f4e-5c2a-4d4e-9d1f-2a3b4c5d6e7f");
+        // A mod copied a base resource together with its metadata, both files claim the UUID.
+        for (root, name) in [(&base, "a"), (&base, "z"), (&mods, "b")] {
+            let path = root.join(format!("data/{name}.{}", MyDataLoader::EXT));
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            MyData { data: 0 }.save(&path).unwrap();
+            ResourceMetadata { resource_id: uuid }
+                .save_sync(
+                    &append_extension(&path, ResourceMetadata::EXTENSION),
+                    &FsResourceIo,
+                )
+                .unwrap();
+        }
+
+        let io = LayeredResourceIo::new(Arc::new(DirectoryResourceIo::new(&base)))
+            .with_layer(Arc::new(DirectoryResourceIo::new(&mods)));
+        let mut loaders = ResourceLoadersContainer::new();
+        loaders.set(MyDataLoader {});
+        let registry = block_on(ResourceRegistry::scan(
+            Arc::new(io),
+            Arc::new(Mutex::new(loaders)),
+            "data/resources.registry",
+            Default::default(),
+        ));
+
+        // The mod layer wins over the base, even though its path sorts between the base files.
+        assert_eq!(registry.len(), 1);
+        assert_eq!(registry[&uuid], Path::new("data/b.my_data"));
+
+        std::fs::remove_dir_all(TEST_FOLDER5).unwrap();
+    }
+
     #[test]
     fn test_move_resource() {
         write_test_resources(TEST_FOLDER3, 0..2);
@@ -22,6 +22,7 @@
 
 use crate::{
     core::{uuid::Uuid, TypeUuidProvider},
+    derived::ResourceImporter,
     io::ResourceIo,
     options::BaseImportOptions,
     state::LoadError,
@@ -73,8 +74,8 @@ where
                     // Copy the binary format as-is.
                     Ok(io.copy_file(&src_path, &dest_path).await?)
                 }
-                Format::Ascii => {
-                    // Resave the ascii format as binary.
+                Format::Ascii | Format::Json => {
+                    // Resave the text formats as binary.
                     let visitor = Visitor::load_from_memory(&data).map_err(|err| {
                         FileError::Custom(format!(
                             "Unable to load {}. Reason: {err}",
@@ -156,6 +157,29 @@ pub trait ResourceLoader: BaseResourceLoader {
     fn default_import_options(&self) -> Option<Box<dyn BaseImportOptions>> {
         None
     }
+
+    /// Returns an importer that converts source files of this loader into derived data. When the
+    /// resource manager has a [`crate::derived::DerivedDataCache`], it loads resources through the
+    /// importer and the cache instead of calling [`Self::load`]. The default implementation
+    /// returns [`None`], which means that the source files are always loaded directly.
+    fn importer(&self) -> Option<Arc<dyn ResourceImporter>> {
+        None
+    }
+
+    /// Returns an importer for the given source file, if the file needs to be converted. Files in
+    /// native formats of the loader (see [`Self::is_native_extension`]) are already in their final
+    /// form and are always loaded directly.
+    fn importer_for(&self, path: &Path) -> Option<Arc<dyn ResourceImporter>> {
+        let is_native = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| self.is_native_extension(ext));
+        if is_native {
+            None
+        } else {
+            self.importer()
+        }
+    }
 }
 
 /// A result of executing a resource loader.
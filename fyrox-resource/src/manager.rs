@@ -36,10 +36,11 @@ use crate::{
         watcher::FileSystemWatcher,
         SafeLock, TypeUuidProvider, Uuid,
     },
+    derived::DerivedDataCache,
     entry::{TimedEntry, DEFAULT_RESOURCE_LIFETIME},
     event::{ResourceEvent, ResourceEventBroadcaster},
     io::ResourceIo,
-    loader::{ResourceLoader, ResourceLoadersContainer},
+    loader::{BoxedLoaderFuture, ResourceLoader, ResourceLoadersContainer},
     metadata::ResourceMetadata,
     options::OPTIONS_EXTENSION,
     registry::{RegistryUpdate, ResourceRegistry, ResourceRegistryRefMut, ResourceRegistryStatus},
@@ -98,6 +99,7 @@ pub struct ResourceManagerState {
     pub resource_registry: Arc<Mutex<ResourceRegistry>>,
 
     resources: Vec<TimedEntry<UntypedResource>>,
+    derived_data_cache: Option<Arc<DerivedDataCache>>,
     task_pool: Arc<TaskPool>,
     watcher: Option<FileSystemWatcher>,
 }
@@ -856,6 +858,13 @@ impl ResourceManager {
         join_all(resources).await;
     }
 
+    /// Clears the cache of derived data and reloads all loaded resources, so the resources are
+    /// imported from their source files again. See [`ResourceManagerState::reimport_resources`].
+    pub async fn reimport_resources(&self) {
+        let resources = self.state().reimport_resources();
+        join_all(resources).await;
+    }
+
     /// Checks if there's a loader for the given resource path.
     pub fn is_supported_resource(&self, path: &Path) -> bool {
         self.state().is_supported_resource(path)
@@ -924,6 +933,7 @@ impl ResourceManagerState {
             loaders: Default::default(),
             event_broadcaster: Default::default(),
             constructors_container: Default::default(),
+            derived_data_cache: None,
             watcher: None,
             built_in_resources: Default::default(),
             resource_registry: Arc::new(Mutex::new(ResourceRegistry::new(io.clone()))),
@@ -948,9 +958,10 @@ impl ResourceManagerState {
         let task_loaders = self.loaders.clone();
         let path = resource_registry.safe_lock().path().to_path_buf();
 
-        // Try to update the registry first.
+        // Try to update the registry first. Read-only IO (such as packed archives) is scanned
+        // too, the registry is just not saved then.
         #[cfg(not(any(target_arch = "wasm32", target_os = "android")))]
-        if resource_io.can_read_directories() && resource_io.can_write() {
+        if resource_io.can_read_directories() {
             block_on(async move {
                 let new_data = ResourceRegistry::scan(
                     resource_io.clone(),
@@ -1020,6 +1031,18 @@ impl ResourceManagerState {
         self.resource_io = resource_io;
     }
 
+    /// Sets a cache of derived data. When the cache is set, resources of loaders that provide a
+    /// [`crate::derived::ResourceImporter`] are imported once and then loaded from the cached
+    /// artifacts. There's no cache by default.
+    pub fn set_derived_data_cache(&mut self, cache: Option<DerivedDataCache>) {
+        self.derived_data_cache = cache.map(Arc::new);
+    }
+
+    /// Returns the cache of derived data, if any.
+    pub fn derived_data_cache(&self) -> Option<&Arc<DerivedDataCache>> {
+        self.derived_data_cache.as_ref()
+    }
+
     /// Sets resource watcher which will track any modifications in file system and forcing
     /// the manager to reload changed resources. By default there is no watcher, since it
     /// may be an undesired effect to reload resources at runtime. This is very useful thing
@@ -1556,6 +1579,7 @@ impl ResourceManagerState {
         let loaders = self.loaders.clone();
         let registry = self.resource_registry.clone();
         let io = self.resource_io.clone();
+        let derived_data_cache = self.derived_data_cache.clone();
         let registry_status = registry.safe_lock().status_flag();
 
         self.task_pool.spawn_task(async move {
@@ -1585,10 +1609,21 @@ impl ResourceManagerState {
             };
 
             // Try to find a loader for the resource.
-            let loader_future = loaders
-                .safe_lock()
-                .loader_for(&path)
-                .map(|loader| loader.load(path.clone(), io));
+            let loader_future = loaders.safe_lock().loader_for(&path).map(|loader| {
+                match (derived_data_cache, loader.importer_for(&path)) {
+                    (Some(cache), Some(importer)) => {
+                        let path = path.clone();
+                        let data_type_uuid = loader.data_type_uuid();
+                        let future: BoxedLoaderFuture = Box::pin(async move {
+                            cache
+                                .load_or_import(path, data_type_uuid, importer, io)
+                                .await
+                        });
+                        future
+                    }
+                    _ => loader.load(path.clone(), io),
+                }
+            });
 
             if let Some(loader_future) = loader_future {
                 match loader_future.await {
@@ -1807,6 +1842,16 @@ impl ResourceManagerState {
         resources
     }
 
+    /// Clears the cache of derived data (if any) and reloads all resources, so every resource that
+    /// has an importer is imported from its source file again. Returns a list of resources that
+    /// will be reloaded.
+    pub fn reimport_resources(&mut self) -> Vec<UntypedResource> {
+        if let Some(cache) = self.derived_data_cache.as_ref() {
+            Log::verify(cache.clear());
+        }
+        self.reload_resources()
+    }
+
     /// Wait until all resources are loaded (or failed to load).
     pub fn get_wait_context(&self) -> ResourceWaitContext {
         ResourceWaitContext {
@@ -59,6 +59,18 @@ pub trait ImportOptions:
         Log::verify(result);
         is_ok
     }
+
+    /// Serializes import options into a stable sequence of bytes. It could be used to identify
+    /// the options, for example in [`crate::derived::ResourceImporter::settings`].
+    fn to_bytes(&self) -> Vec<u8> {
+        match ron::ser::to_string(self) {
+            Ok(string) => string.into_bytes(),
+            Err(err) => {
+                Log::err(format!("Unable to serialize import options. Reason: {err}"));
+                Vec::new()
+            }
+        }
+    }
 }
 
 impl<T> BaseImportOptions for T
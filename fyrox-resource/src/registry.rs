@@ -478,7 +478,9 @@ impl ResourceRegistry {
     /// create the metadata file with a new UUID and add the resource to the registry.
     ///
     /// This method does **not** load any resource, instead it checks extension of every file in the
-    /// given directory, and if there's a loader for it, "remember" the resource.
+    /// given directory, and if there's a loader for it, "remember" the resource. When several files
+    /// share a UUID, the one with the higher [`ResourceIo::source_priority`] is used, ties are
+    /// resolved by path.
     pub async fn scan(
         resource_io: Arc<dyn ResourceIo>,
         loaders: Arc<Mutex<ResourceLoadersContainer>>,
@@ -498,11 +500,14 @@ impl ResourceRegistry {
 
         let mut container = RegistryContainer::default();
 
+        // Paths are visited in a fixed order (a stack sorted in reverse), so the result does not
+        // depend on the order of directory listings.
         let mut paths_to_visit = ok_or_return!(
             resource_io.read_directory(&registry_folder).await,
             container
         )
         .collect::<Vec<_>>();
+        paths_to_visit.sort_unstable_by(|a, b| b.cmp(a));
 
         while let Some(fs_path) = paths_to_visit.pop() {
             let path = match resource_io.canonicalize_path(&fs_path) {
@@ -523,7 +528,9 @@ impl ResourceRegistry {
             if resource_io.is_dir(&path).await {
                 // Continue iterating on subfolders.
                 if let Ok(iter) = resource_io.read_directory(&path).await {
-                    paths_to_visit.extend(iter);
+                    let mut children = iter.collect::<Vec<_>>();
+                    children.sort_unstable_by(|a, b| b.cmp(a));
+                    paths_to_visit.extend(children);
                 }
 
                 continue;
@@ -553,11 +560,33 @@ impl ResourceRegistry {
                     }
                 };
 
-            if let Some(former) = container.insert(metadata.resource_id, path.clone()) {
-                warn!("Resource UUID collision between {path:?} and {former:?}");
+            match container.get(&metadata.resource_id) {
+                Some(former) if !Self::takes_precedence(&*resource_io, &path, former) => {
+                    warn!(
+                        "Resource UUID collision between {path:?} and {former:?}, {former:?} \
+                        is used."
+                    );
+                }
+                former => {
+                    if let Some(former) = former {
+                        warn!(
+                            "Resource UUID collision between {path:?} and {former:?}, {path:?} \
+                            is used."
+                        );
+                    }
+                    container.insert(metadata.resource_id, path.clone());
+                }
             }
         }
 
         container
     }
+
+    /// Resolves UUID collisions: the file from the source with the higher priority (for example,
+    /// an upper layer of [`crate::io::LayeredResourceIo`]) wins, ties are resolved by path.
+    fn takes_precedence(resource_io: &dyn ResourceIo, path: &Path, former: &Path) -> bool {
+        let priority = resource_io.source_priority(path);
+        let former_priority = resource_io.source_priority(former);
+        priority > former_priority || (priority == former_priority && path < former)
+    }
 }
@@ -20,9 +20,16 @@
 
 //! Sound buffer loader.
 
-use crate::buffer::{DataSource, SoundBuffer};
-use fyrox_core::{reflect::prelude::*, uuid::Uuid, TypeUuidProvider};
+use crate::buffer::{generic::GenericBuffer, DataSource, SoundBuffer};
+use fyrox_core::{
+    platform::TargetPlatform,
+    reflect::prelude::*,
+    uuid::Uuid,
+    visitor::{pod::PodVecView, prelude::*},
+    TypeUuidProvider,
+};
 use fyrox_resource::{
+    derived::{BoxedImportFuture, ResourceImporter},
     io::ResourceIo,
     loader::{BoxedImportOptionsLoaderFuture, BoxedLoaderFuture, LoaderPayload, ResourceLoader},
     options::{
@@ -97,4 +104,111 @@ impl ResourceLoader for SoundBufferLoader {
     fn default_import_options(&self) -> Option<Box<dyn BaseImportOptions>> {
         Some(Box::<SoundBufferImportOptions>::default())
     }
+
+    fn importer(&self) -> Option<Arc<dyn ResourceImporter>> {
+        Some(Arc::new(SoundBufferImporter {
+            default_import_options: self.default_import_options.clone(),
+        }))
+    }
+}
+
+/// Imports sound buffers into derived data. An artifact of a generic buffer contains decoded
+/// samples, so the sound is not decoded on every load. Streaming buffers decode their data while
+/// playing, their artifacts contain the source file.
+pub struct SoundBufferImporter {
+    /// Import options that are used when a sound buffer has no import options file.
+    pub default_import_options: SoundBufferImportOptions,
+}
+
+impl ResourceImporter for SoundBufferImporter {
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn settings(&self) -> Vec<u8> {
+        self.default_import_options.to_bytes()
+    }
+
+    fn import(
+        &self,
+        path: PathBuf,
+        mut source: Vec<u8>,
+        _platform: TargetPlatform,
+        io: Arc<dyn ResourceIo>,
+    ) -> BoxedImportFuture {
+        let default_import_options = self.default_import_options.clone();
+        Box::pin(async move {
+            let mut import_options = try_get_import_settings(&path, &*io)
+                .await
+                .unwrap_or(default_import_options);
+
+            let mut visitor = Visitor::new();
+            import_options
+                .stream
+                .visit("Stream", &mut visitor)
+                .map_err(LoadError::new)?;
+            if import_options.stream {
+                PodVecView::from_pod_vec(&mut source)
+                    .visit("Source", &mut visitor)
+                    .map_err(LoadError::new)?;
+            } else {
+                let mut buffer = GenericBuffer::new(DataSource::from_memory(source))
+                    .map_err(|_| LoadError::new("Invalid data source."))?;
+                let mut sample_rate = buffer.sample_rate as u32;
+                let mut channel_count = buffer.channel_count as u32;
+                sample_rate
+                    .visit("SampleRate", &mut visitor)
+                    .map_err(LoadError::new)?;
+                channel_count
+                    .visit("ChannelCount", &mut visitor)
+                    .map_err(LoadError::new)?;
+                PodVecView::from_pod_vec(&mut buffer.samples.0)
+                    .visit("Samples", &mut visitor)
+                    .map_err(LoadError::new)?;
+            }
+            visitor.save_binary_to_vec().map_err(LoadError::new)
+        })
+    }
+
+    fn load_derived(&self, artifact: Vec<u8>) -> BoxedLoaderFuture {
+        Box::pin(async move {
+            let mut visitor =
+                Visitor::load_binary_from_memory(&artifact).map_err(LoadError::new)?;
+            let mut stream = false;
+            stream
+                .visit("Stream", &mut visitor)
+                .map_err(LoadError::new)?;
+
+            let result = if stream {
+                let mut source = Vec::<u8>::new();
+                PodVecView::from_pod_vec(&mut source)
+                    .visit("Source", &mut visitor)
+                    .map_err(LoadError::new)?;
+                SoundBuffer::raw_streaming(DataSource::from_memory(source))
+            } else {
+                let mut sample_rate = 0u32;
+                let mut channel_count = 0u32;
+                let mut samples = Vec::<f32>::new();
+                sample_rate
+                    .visit("SampleRate", &mut visitor)
+                    .map_err(LoadError::new)?;
+                channel_count
+                    .visit("ChannelCount", &mut visitor)
+                    .map_err(LoadError::new)?;
+                PodVecView::from_pod_vec(&mut samples)
+                    .visit("Samples", &mut visitor)
+                    .map_err(LoadError::new)?;
+                SoundBuffer::raw_generic(DataSource::Raw {
+                    sample_rate: sample_rate as usize,
+                    channel_count: channel_count as usize,
+                    samples,
+                })
+            };
+
+            match result {
+                Ok(buffer) => Ok(LoaderPayload::new(buffer)),
+                Err(_) => Err(LoadError::new("Invalid data source.")),
+            }
+        })
+    }
 }
@@ -363,7 +363,7 @@ impl Visit for Texture {
         self.anisotropy.visit("Anisotropy", &mut region)?;
         self.s_wrap_mode.visit("SWrapMode", &mut region)?;
         self.t_wrap_mode.visit("TWrapMode", &mut region)?;
-        self.t_wrap_mode.visit("RWrapMode", &mut region)?;
+        self.r_wrap_mode.visit("RWrapMode", &mut region)?;
         self.mip_count.visit("MipCount", &mut region)?;
         self.kind.visit("Kind", &mut region)?;
         let mut bytes_view = PodVecView::from_pod_vec(&mut self.bytes);
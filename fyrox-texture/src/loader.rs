@@ -21,11 +21,18 @@
 //! Texture loader.
 
 use crate::{Texture, TextureImportOptions};
-use fyrox_core::{uuid::Uuid, TypeUuidProvider};
+use fyrox_core::{
+    platform::TargetPlatform,
+    uuid::Uuid,
+    visitor::{Visit, Visitor},
+    TypeUuidProvider,
+};
 use fyrox_resource::{
-    io::ResourceIo, loader::BoxedImportOptionsLoaderFuture, loader::BoxedLoaderFuture,
-    loader::LoaderPayload, loader::ResourceLoader, options::try_get_import_settings,
-    options::try_get_import_settings_opaque, options::BaseImportOptions, state::LoadError,
+    derived::BoxedImportFuture, derived::ResourceImporter, io::ResourceIo,
+    loader::BoxedImportOptionsLoaderFuture, loader::BoxedLoaderFuture, loader::LoaderPayload,
+    loader::ResourceLoader, options::try_get_import_settings,
+    options::try_get_import_settings_opaque, options::BaseImportOptions, options::ImportOptions,
+    state::LoadError,
 };
 use std::{path::PathBuf, sync::Arc};
 
@@ -76,4 +83,64 @@ impl ResourceLoader for TextureLoader {
     fn default_import_options(&self) -> Option<Box<dyn BaseImportOptions>> {
         Some(Box::<TextureImportOptions>::default())
     }
+
+    fn importer(&self) -> Option<Arc<dyn ResourceImporter>> {
+        Some(Arc::new(TextureImporter {
+            default_import_options: self.default_import_options.clone(),
+        }))
+    }
+}
+
+/// Imports textures into derived data. An artifact is the texture in its native binary form: the
+/// pixels are already decoded, compressed and have all the mip levels, as requested by the import
+/// options. This saves a lot of time on loading, especially for compressed textures.
+pub struct TextureImporter {
+    /// Import options that are used when a texture has no import options file.
+    pub default_import_options: TextureImportOptions,
+}
+
+impl ResourceImporter for TextureImporter {
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn settings(&self) -> Vec<u8> {
+        self.default_import_options.to_bytes()
+    }
+
+    fn import(
+        &self,
+        path: PathBuf,
+        source: Vec<u8>,
+        _platform: TargetPlatform,
+        io: Arc<dyn ResourceIo>,
+    ) -> BoxedImportFuture {
+        let default_import_options = self.default_import_options.clone();
+        Box::pin(async move {
+            let import_options = try_get_import_settings(&path, &*io)
+                .await
+                .unwrap_or(default_import_options);
+
+            let mut texture =
+                Texture::load_from_memory(&source, import_options).map_err(LoadError::new)?;
+
+            let mut visitor = Visitor::new();
+            texture
+                .visit("Texture", &mut visitor)
+                .map_err(LoadError::new)?;
+            visitor.save_binary_to_vec().map_err(LoadError::new)
+        })
+    }
+
+    fn load_derived(&self, artifact: Vec<u8>) -> BoxedLoaderFuture {
+        Box::pin(async move {
+            let mut visitor =
+                Visitor::load_binary_from_memory(&artifact).map_err(LoadError::new)?;
+            let mut texture = Texture::default();
+            texture
+                .visit("Texture", &mut visitor)
+                .map_err(LoadError::new)?;
+            Ok(LoaderPayload::new(texture))
+        })
+    }
 }
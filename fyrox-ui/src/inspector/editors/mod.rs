@@ -429,6 +429,7 @@ impl PropertyEditorDefinitionContainer {
         container.insert(StringPropertyEditorDefinition);
         container.insert(InheritablePropertyEditorDefinition::<String>::new());
         container.insert(VecCollectionPropertyEditorDefinition::<String>::new());
+        container.insert(InheritablePropertyEditorDefinition::<Vec<String>>::new());
 
         // ImmutableString
         container.insert(ImmutableStringPropertyEditorDefinition);
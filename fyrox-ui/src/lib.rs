@@ -309,7 +309,7 @@ use crate::{
         reflect::prelude::*,
         uuid::{uuid, Uuid},
         uuid_provider,
-        visitor::prelude::*,
+        visitor::{prelude::*, Format},
         SafeLock, TypeUuidProvider,
     },
     draw::{CommandTexture, Draw, DrawingContext},
@@ -3381,9 +3381,15 @@ impl UserInterface {
     }
 
     pub fn save(&mut self, path: &Path) -> Result<Visitor, VisitError> {
+        self.save_with_format(path, Format::Ascii)
+    }
+
+    /// Saves the user interface to a file at the given path in the given format. Returns the
+    /// visitor with the saved data.
+    pub fn save_with_format(&mut self, path: &Path, format: Format) -> Result<Visitor, VisitError> {
         let mut visitor = Visitor::new();
         self.visit("Ui", &mut visitor)?;
-        visitor.save_ascii_to_file(path)?;
+        visitor.save_to_file(path, format)?;
         Ok(visitor)
     }
 
@@ -33,6 +33,7 @@ use crate::{
     message::{MessageDirection, UiMessage},
     popup::{Placement, PopupBuilder, PopupMessage},
     scroll_viewer::{ScrollViewerBuilder, ScrollViewerMessage},
+    searchbar::{SearchBarBuilder, SearchBarMessage},
     stack_panel::StackPanelBuilder,
     style::{resource::StyleResourceExt, Style},
     text::{Text, TextBuilder},
@@ -106,6 +107,10 @@ pub struct LogPanel {
     receiver: Receiver<LogMessage>,
     severity: MessageKind,
     severity_list: Handle<UiNode>,
+    target_filter: String,
+    target_filter_bar: Handle<UiNode>,
+    // Targets of the messages in the same order as they're in the list.
+    entries: Vec<(Handle<UiNode>, String)>,
     context_menu: ContextMenu,
     pub message_count: usize,
 }
@@ -120,6 +125,7 @@ impl LogPanel {
         let messages;
         let clear;
         let severity_list;
+        let target_filter_bar;
         let window = WindowBuilder::new(
             WidgetBuilder::new()
                 .with_width(400.0)
@@ -166,6 +172,16 @@ impl LogPanel {
                                     .with_selected(1)
                                     .build(ctx);
                                     severity_list
+                                })
+                                .with_child({
+                                    target_filter_bar = SearchBarBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_tab_index(Some(2))
+                                            .with_width(200.0)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .build(ctx);
+                                    target_filter_bar
                                 }),
                         )
                         .with_orientation(Orientation::Horizontal)
@@ -206,6 +222,9 @@ impl LogPanel {
             receiver: message_receiver,
             severity: MessageKind::Warning,
             severity_list,
+            target_filter: Default::default(),
+            target_filter_bar,
+            entries: Default::default(),
             context_menu,
             message_count: 0,
         }
@@ -235,6 +254,7 @@ impl LogPanel {
         if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
             if message.destination() == self.clear {
                 ui.send(self.messages, WidgetMessage::ReplaceChildren(vec![]));
+                self.entries.clear();
             }
         } else if let Some(DropdownListMessage::Selection(Some(idx))) =
             message.data::<DropdownListMessage>()
@@ -249,11 +269,24 @@ impl LogPanel {
                     _ => (),
                 };
             }
+        } else if let Some(SearchBarMessage::Text(text)) = message.data() {
+            if message.destination() == self.target_filter_bar
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.target_filter = text.to_lowercase();
+                for (entry, target) in self.entries.iter() {
+                    ui.send(*entry, WidgetMessage::Visibility(self.is_shown(target)));
+                }
+            }
         }
 
         self.context_menu.handle_ui_message(message, ui);
     }
 
+    fn is_shown(&self, target: &str) -> bool {
+        self.target_filter.is_empty() || target.to_lowercase().contains(&self.target_filter)
+    }
+
     pub fn update(&mut self, max_log_entries: usize, ui: &mut UserInterface) -> bool {
         let existing_items = ui.node(self.messages).children();
 
@@ -269,6 +302,7 @@ impl LogPanel {
             for item in existing_items.iter().take(delta) {
                 ui.send(*item, WidgetMessage::Remove);
             }
+            self.entries.drain(..delta.min(self.entries.len()));
 
             count -= delta;
         }
@@ -295,6 +329,7 @@ impl LogPanel {
             let ctx = &mut ui.build_ctx();
             let item = BorderBuilder::new(
                 WidgetBuilder::new()
+                    .with_visibility(self.is_shown(&msg.target))
                     .with_background(if count.is_multiple_of(2) {
                         ctx.style.property(Style::BRUSH_LIGHT)
                     } else {
@@ -323,6 +358,7 @@ impl LogPanel {
             .build(ctx);
 
             ui.send(item, WidgetMessage::LinkWith(self.messages));
+            self.entries.push((item, msg.target));
 
             item_to_bring_into_view = item;
 
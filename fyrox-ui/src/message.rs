@@ -683,6 +683,8 @@ pub struct KeyboardModifiers {
     pub system: bool,
 }
 
+uuid_provider!(KeyboardModifiers = "84b87139-fb6e-4f91-b318-1cb7203c2ad0");
+
 impl KeyboardModifiers {
     /// Checks if the modifiers is empty (nothing is pressed).
     pub fn is_none(self) -> bool {
@@ -1159,6 +1161,8 @@ pub enum KeyCode {
     F35,
 }
 
+uuid_provider!(KeyCode = "c85b207c-26a0-4d0b-9a19-eb09a5723aaf");
+
 impl KeyCode {
     /// Attempts to convert the key code to its respective character.
     pub fn to_char(self) -> Option<char> {
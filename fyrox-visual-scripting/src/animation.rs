@@ -0,0 +1,77 @@
+//! Animation control for blueprints: ABSM parameters, animation playback and animation signals.
+//!
+//! Like widgets, animations are addressed by actor and by name. `GetActiveState` asks the host
+//! ([`crate::BlueprintHost::active_state`]), changes are reported as
+//! [`crate::ExecutionEvent::Animation`] commands, and the host raises signals with
+//! [`crate::Interpreter::dispatch_animation_signal`].
+
+use crate::model::{DataType, Value};
+
+/// Kind of an ABSM parameter changed by `SetAbsmParameter` nodes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AbsmParameterKind {
+    /// Enables or disables transitions.
+    Rule,
+    /// Blend weight of blending nodes.
+    Weight,
+    /// Index of the pose picked by an index blending node.
+    Index,
+}
+
+impl AbsmParameterKind {
+    pub const ALL: [AbsmParameterKind; 3] = [
+        AbsmParameterKind::Rule,
+        AbsmParameterKind::Weight,
+        AbsmParameterKind::Index,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AbsmParameterKind::Rule => "Rule",
+            AbsmParameterKind::Weight => "Weight",
+            AbsmParameterKind::Index => "Index",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+
+    pub fn value_type(self) -> DataType {
+        match self {
+            AbsmParameterKind::Rule => DataType::Bool,
+            AbsmParameterKind::Weight => DataType::F32,
+            AbsmParameterKind::Index => DataType::I32,
+        }
+    }
+}
+
+/// A change requested by `SetAbsmParameter`, `PlayAnimation` and `StopAnimation` nodes. The target
+/// is an actor reference (see [`crate::host::is_self_target`]); the host looks for the state
+/// machine or the animation player on the actor and its descendants.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnimationCommand {
+    SetParameter {
+        target: String,
+        name: String,
+        value: Value,
+    },
+    Play {
+        target: String,
+        animation: String,
+    },
+    Stop {
+        target: String,
+        animation: String,
+    },
+}
+
+/// Name of the signal raised when a state machine enters the state.
+pub fn state_enter_signal(state: &str) -> String {
+    format!("{state}.Enter")
+}
+
+/// Name of the signal raised when a state machine leaves the state.
+pub fn state_leave_signal(state: &str) -> String {
+    format!("{state}.Leave")
+}
@@ -0,0 +1,261 @@
+//! Behavior trees for blueprints.
+//!
+//! A behavior tree asset stores a [`BehaviorGraph`]: composites, decorators and blackboard
+//! conditions with leaf tasks at the bottom. The blackboard of the tree is the set of variables of
+//! the blueprint of the actor, so conditions read them and tasks change them with regular
+//! variable nodes.
+//!
+//! A leaf task is implemented by a function graph that starts with a `BehaviorTask` node. Every
+//! time the tree ticks the task, the host runs the graph with
+//! [`crate::Interpreter::run_behavior_task`]. A `FinishTask` node reports whether the task
+//! succeeded; a run that does not reach one leaves the task running, so it runs again on the next
+//! tick.
+
+use crate::model::Value;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Outcome of a run of a behavior tree task.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TaskStatus {
+    Success,
+    Failure,
+    Running,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct BehaviorNodeId(pub u32);
+
+/// Comparison of a blackboard condition.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConditionOp {
+    #[default]
+    Equal,
+    NotEqual,
+    Less,
+    Greater,
+}
+
+impl ConditionOp {
+    pub const ALL: [ConditionOp; 4] = [
+        ConditionOp::Equal,
+        ConditionOp::NotEqual,
+        ConditionOp::Less,
+        ConditionOp::Greater,
+    ];
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            ConditionOp::Equal => "==",
+            ConditionOp::NotEqual => "!=",
+            ConditionOp::Less => "<",
+            ConditionOp::Greater => ">",
+        }
+    }
+}
+
+/// Compares a blueprint variable with a literal.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlackboardCondition {
+    pub variable: String,
+    #[serde(default)]
+    pub op: ConditionOp,
+    /// JSON literal of the value (`true`, `3`, `"text"`); text that is not JSON is read as a
+    /// string, so quotes are optional. Enum variables are compared by variant name.
+    #[serde(default)]
+    pub value: String,
+}
+
+impl BlackboardCondition {
+    /// Whether the condition holds for the given variables. Fails if the variable does not exist
+    /// or the literal does not match its type. `Less` and `Greater` only hold for numbers.
+    pub fn evaluate(&self, variables: &BTreeMap<String, Value>) -> bool {
+        let Some(variable) = variables.get(&self.variable) else {
+            return false;
+        };
+        let json = serde_json::from_str(&self.value)
+            .unwrap_or_else(|_| serde_json::Value::String(self.value.clone()));
+        let literal = match variable {
+            Value::Enum(ty, _) => match json {
+                serde_json::Value::String(variant) => Value::Enum(*ty, variant),
+                _ => return false,
+            },
+            _ => match Value::from_json(&json, variable.data_type()) {
+                Some(literal) => literal,
+                None => return false,
+            },
+        };
+
+        let number = |value: &Value| match value {
+            Value::I32(v) => Some(*v as f32),
+            Value::F32(v) => Some(*v),
+            _ => None,
+        };
+        match self.op {
+            ConditionOp::Equal => *variable == literal,
+            ConditionOp::NotEqual => *variable != literal,
+            ConditionOp::Less => number(variable)
+                .zip(number(&literal))
+                .is_some_and(|(a, b)| a < b),
+            ConditionOp::Greater => number(variable)
+                .zip(number(&literal))
+                .is_some_and(|(a, b)| a > b),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BehaviorNodeKind {
+    /// Runs the children in order until one of them fails.
+    Sequence,
+    /// Runs the children in order until one of them succeeds.
+    Selector,
+    /// Swaps success and failure of the child.
+    Inverter,
+    /// Fails without running the child for `duration` seconds after the child finished.
+    Cooldown { duration: f32 },
+    /// Runs the child until it succeeded `count` times; zero repeats it forever.
+    Repeat { count: u32 },
+    /// Fails if the child is still running after `duration` seconds.
+    Timeout { duration: f32 },
+    /// Runs the child only while the condition holds. Without a child it only checks the
+    /// condition.
+    Condition(BlackboardCondition),
+    /// Runs the function graph of the task with the name, see module docs.
+    Task { task: String },
+}
+
+impl BehaviorNodeKind {
+    /// Whether the node has a single child.
+    pub fn is_decorator(&self) -> bool {
+        !matches!(
+            self,
+            BehaviorNodeKind::Sequence | BehaviorNodeKind::Selector | BehaviorNodeKind::Task { .. }
+        )
+    }
+
+    /// Whether the node can have children.
+    pub fn has_children(&self) -> bool {
+        !matches!(self, BehaviorNodeKind::Task { .. })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BehaviorNode {
+    pub kind: BehaviorNodeKind,
+    /// Children in the order they run. Decorators only use the first one.
+    #[serde(default)]
+    pub children: Vec<BehaviorNodeId>,
+    /// Position in the editor.
+    #[serde(default)]
+    pub position: (f32, f32),
+}
+
+/// Serialized behavior tree, see module docs.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BehaviorGraph {
+    pub nodes: BTreeMap<BehaviorNodeId, BehaviorNode>,
+    /// The node the tree starts from.
+    #[serde(default)]
+    pub root: Option<BehaviorNodeId>,
+    #[serde(default)]
+    pub next_node_id: u32,
+}
+
+impl BehaviorGraph {
+    pub fn add_node(&mut self, kind: BehaviorNodeKind) -> BehaviorNodeId {
+        let id = BehaviorNodeId(self.next_node_id);
+        self.next_node_id += 1;
+        self.nodes.insert(
+            id,
+            BehaviorNode {
+                kind,
+                children: Vec::new(),
+                position: (0.0, 0.0),
+            },
+        );
+        id
+    }
+
+    /// Removes the node and its links; children of the node stay in the graph.
+    pub fn remove_node(&mut self, id: BehaviorNodeId) -> Option<BehaviorNode> {
+        let node = self.nodes.remove(&id)?;
+        for other in self.nodes.values_mut() {
+            other.children.retain(|child| *child != id);
+        }
+        if self.root == Some(id) {
+            self.root = None;
+        }
+        Some(node)
+    }
+
+    /// Returns the parent of the node, if it has one.
+    pub fn parent_of(&self, id: BehaviorNodeId) -> Option<BehaviorNodeId> {
+        self.nodes
+            .iter()
+            .find_map(|(parent, node)| node.children.contains(&id).then_some(*parent))
+    }
+
+    /// Makes `child` the last child of `parent` (the only one, for decorators), detaching it from
+    /// its previous parent. Returns `false` if the link would create a cycle or the parent cannot
+    /// have children.
+    pub fn link(&mut self, parent: BehaviorNodeId, child: BehaviorNodeId) -> bool {
+        let can_have_children = self
+            .nodes
+            .get(&parent)
+            .is_some_and(|node| node.kind.has_children());
+        if !can_have_children || !self.nodes.contains_key(&child) || self.is_ancestor(child, parent)
+        {
+            return false;
+        }
+
+        self.unlink(child);
+        if self.root == Some(child) {
+            self.root = None;
+        }
+        let node = self.nodes.get_mut(&parent).unwrap();
+        if node.kind.is_decorator() {
+            node.children.clear();
+        }
+        node.children.push(child);
+        true
+    }
+
+    /// Detaches the node from its parent. Returns `false` if it has no parent.
+    pub fn unlink(&mut self, child: BehaviorNodeId) -> bool {
+        let Some(parent) = self.parent_of(child) else {
+            return false;
+        };
+        if let Some(parent) = self.nodes.get_mut(&parent) {
+            parent.children.retain(|c| *c != child);
+        }
+        true
+    }
+
+    /// Whether `ancestor` is `id` or one of the nodes above it.
+    fn is_ancestor(&self, ancestor: BehaviorNodeId, id: BehaviorNodeId) -> bool {
+        let mut current = Some(id);
+        while let Some(node) = current {
+            if node == ancestor {
+                return true;
+            }
+            current = self.parent_of(node);
+        }
+        false
+    }
+
+    /// Names of the tasks used by the tree.
+    pub fn tasks(&self) -> Vec<String> {
+        let mut tasks = self
+            .nodes
+            .values()
+            .filter_map(|node| match &node.kind {
+                BehaviorNodeKind::Task { task } => Some(task.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        tasks.sort();
+        tasks.dedup();
+        tasks
+    }
+}
@@ -0,0 +1,689 @@
+//! Rust code generation from blueprint graphs ("nativization").
+//!
+//! The generated `ScriptTrait` implementation mirrors the interpreter: the Construction Script runs
+//! in `on_init`, BeginPlay in `on_start`, Tick in `on_update`, and interface implementations become
+//! methods. Variables become fields of the script. Exec chains are emitted as straight-line code,
+//! with `Branch` nodes turned into `if`/`else`.
+
+use crate::{
+    compile::{compile, CompiledGraph},
+    error::NativizeError,
+    model::{BlueprintGraph, BuiltinNodeKind, DataType, NodeId, PinDirection, PinId, Value},
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+};
+
+/// First line of every generated file. Tools use it to tell generated scripts from hand-written
+/// ones before overwriting them.
+pub const GENERATED_HEADER: &str = "// Generated from a blueprint graph.";
+
+/// Returns the `#[type_uuid]` of a script generated by [`nativize`], so that regenerating the
+/// script keeps the type UUID that scenes already refer to.
+pub fn generated_type_uuid(source: &str) -> Option<&str> {
+    if !source.starts_with(GENERATED_HEADER) {
+        return None;
+    }
+    source.lines().find_map(|line| {
+        line.trim()
+            .strip_prefix("#[type_uuid(id = \"")?
+            .strip_suffix("\")]")
+    })
+}
+
+/// Generates the source of a Rust script named `script_name` that behaves like the given graph.
+/// `type_uuid` becomes the `#[type_uuid]` of the script and must be unique in the project, see
+/// [`BlueprintGraph::native_type_uuid`]. The module also has a `register` function that adds the
+/// script to the script constructors of the game.
+pub fn nativize(
+    graph: &BlueprintGraph,
+    script_name: &str,
+    type_uuid: &str,
+) -> Result<String, NativizeError> {
+    let compiled = compile(graph)?;
+    Generator::new(graph, &compiled)?.generate(&to_camel_ident(script_name), type_uuid)
+}
+
+struct Field {
+    ident: String,
+    data_type: DataType,
+    default: Value,
+}
+
+struct Generator<'a> {
+    graph: &'a BlueprintGraph,
+    compiled: &'a CompiledGraph,
+    pin_owners: BTreeMap<PinId, NodeId>,
+    fields: BTreeMap<String, Field>,
+    // Parameters of the interface method being generated, keyed by entry node output pins.
+    params: BTreeMap<PinId, String>,
+    out: String,
+}
+
+impl<'a> Generator<'a> {
+    fn new(graph: &'a BlueprintGraph, compiled: &'a CompiledGraph) -> Result<Self, NativizeError> {
+        // Scripts run on scene nodes; widget events would never fire.
+        if let Some(node_id) = compiled.widget_entries.values().flatten().next() {
+            return Err(NativizeError::UnsupportedNode(
+                BuiltinNodeKind::WidgetEvent,
+                *node_id,
+            ));
+        }
+        // Animation signals and finished sounds are only collected for blueprint scripts.
+        if let Some(node_id) = compiled.animation_signal_entries.values().flatten().next() {
+            return Err(NativizeError::UnsupportedNode(
+                BuiltinNodeKind::OnAnimationSignal,
+                *node_id,
+            ));
+        }
+        if let Some(node_id) = compiled.sound_finished_entries.values().flatten().next() {
+            return Err(NativizeError::UnsupportedNode(
+                BuiltinNodeKind::OnSoundFinished,
+                *node_id,
+            ));
+        }
+        // Timers are owned by the blueprint script that starts them.
+        if let Some(node_id) = compiled.custom_event_entries.values().flatten().next() {
+            return Err(NativizeError::UnsupportedNode(
+                BuiltinNodeKind::CustomEvent,
+                *node_id,
+            ));
+        }
+        // Tasks are run by the behavior tree of the blueprint script.
+        if let Some(node_id) = compiled.behavior_task_entries.values().next() {
+            return Err(NativizeError::UnsupportedNode(
+                BuiltinNodeKind::BehaviorTask,
+                *node_id,
+            ));
+        }
+        // Scene events are raised for level blueprints, which are not scripts.
+        if let Some(node_id) = compiled.scene_loaded_entry {
+            return Err(NativizeError::UnsupportedNode(
+                BuiltinNodeKind::OnSceneLoaded,
+                node_id,
+            ));
+        }
+        if let Some(node_id) = compiled.scene_unloaded_entry {
+            return Err(NativizeError::UnsupportedNode(
+                BuiltinNodeKind::OnSceneUnloaded,
+                node_id,
+            ));
+        }
+
+        let pin_owners = compiled
+            .nodes
+            .iter()
+            .flat_map(|(node_id, node)| node.pins.values().map(|(pin, _, _)| (*pin, *node_id)))
+            .collect();
+
+        let mut idents = BTreeSet::new();
+        let mut fields = BTreeMap::new();
+        for var in graph.variables.iter() {
+            // Struct and enum types have no generated Rust counterpart yet.
+            if matches!(
+                var.data_type,
+                DataType::Exec | DataType::Unit | DataType::Struct(_) | DataType::Enum(_)
+            ) {
+                return Err(NativizeError::UnsupportedVariable(var.name.clone()));
+            }
+            let default = compiled
+                .variables
+                .get(&var.name)
+                .cloned()
+                .unwrap_or(Value::Unit);
+            fields.insert(
+                var.name.clone(),
+                Field {
+                    ident: unique_ident(&mut idents, to_snake_ident(&var.name)),
+                    data_type: var.data_type,
+                    default,
+                },
+            );
+        }
+
+        Ok(Self {
+            graph,
+            compiled,
+            pin_owners,
+            fields,
+            params: BTreeMap::new(),
+            out: String::new(),
+        })
+    }
+
+    fn generate(mut self, name: &str, type_uuid: &str) -> Result<String, NativizeError> {
+        let compiled = self.compiled;
+
+        self.line(0, GENERATED_HEADER);
+        self.line(0, &format!("// Source blueprint: {:?}.", self.graph.id.0));
+        self.line(
+            0,
+            "// Add this module to the game and call its `register` function in `Plugin::register`.",
+        );
+        self.line(0, "");
+        self.line(0, "use fyrox::{");
+        self.line(
+            1,
+            "core::{log::Log, reflect::prelude::*, type_traits::prelude::*, visitor::prelude::*},",
+        );
+        self.line(1, "plugin::PluginRegistrationContext,");
+        self.line(1, "script::{ScriptContext, ScriptTrait},");
+        self.line(0, "};");
+        self.line(0, "");
+
+        self.line(
+            0,
+            "#[derive(Visit, Reflect, Debug, Clone, TypeUuidProvider, ComponentProvider)]",
+        );
+        self.line(0, &format!("#[type_uuid(id = \"{type_uuid}\")]"));
+        self.line(0, "#[visit(optional)]");
+        self.line(0, &format!("pub struct {name} {{"));
+        let fields = self
+            .fields
+            .values()
+            .map(|f| format!("pub {}: {},", f.ident, rust_type(f.data_type)))
+            .collect::<Vec<_>>();
+        for field in fields {
+            self.line(1, &field);
+        }
+        self.line(0, "}");
+        self.line(0, "");
+
+        self.line(0, &format!("impl Default for {name} {{"));
+        self.line(1, "fn default() -> Self {");
+        self.line(2, "Self {");
+        let defaults = self
+            .fields
+            .values()
+            .map(|f| format!("{}: {},", f.ident, literal(&f.default)))
+            .collect::<Vec<_>>();
+        for default in defaults {
+            self.line(3, &default);
+        }
+        self.line(2, "}");
+        self.line(1, "}");
+        self.line(0, "}");
+        self.line(0, "");
+
+        self.line(0, "#[allow(unused_variables)]");
+        self.line(0, &format!("impl {name} {{"));
+        let mut first = true;
+        let entries = [
+            ("construction_script", compiled.construction_entry),
+            ("begin_play", compiled.begin_play_entry),
+            ("tick", compiled.tick_entry),
+        ];
+        for (method, entry) in entries {
+            let Some(entry) = entry else {
+                continue;
+            };
+            if !std::mem::take(&mut first) {
+                self.line(0, "");
+            }
+            self.line(
+                1,
+                &format!("fn {method}(&mut self, ctx: &mut ScriptContext) {{"),
+            );
+            self.emit_chain(entry, "then", 2)?;
+            self.line(1, "}");
+        }
+        for ((interface, function), entry) in compiled.interface_entries.iter() {
+            if !std::mem::take(&mut first) {
+                self.line(0, "");
+            }
+            self.emit_interface_method(interface, function, *entry)?;
+        }
+        self.line(0, "}");
+        self.line(0, "");
+
+        self.line(0, &format!("impl ScriptTrait for {name} {{"));
+        let callbacks = [
+            (
+                "on_init",
+                "construction_script",
+                compiled.construction_entry,
+            ),
+            ("on_start", "begin_play", compiled.begin_play_entry),
+            ("on_update", "tick", compiled.tick_entry),
+        ];
+        let mut first = true;
+        for (callback, method, entry) in callbacks {
+            if entry.is_none() {
+                continue;
+            }
+            if !std::mem::take(&mut first) {
+                self.line(0, "");
+            }
+            self.line(
+                1,
+                &format!("fn {callback}(&mut self, ctx: &mut ScriptContext) {{"),
+            );
+            self.line(2, &format!("self.{method}(ctx);"));
+            self.line(1, "}");
+        }
+        self.line(0, "}");
+        self.line(0, "");
+
+        self.line(
+            0,
+            &format!("/// Registers [`{name}`], so scenes can use it."),
+        );
+        self.line(0, "pub fn register(context: &PluginRegistrationContext) {");
+        self.line(1, "context");
+        self.line(2, ".serialization_context");
+        self.line(2, ".script_constructors");
+        self.line(2, &format!(".add::<{name}>(\"{name}\");"));
+        self.line(0, "}");
+
+        Ok(self.out)
+    }
+
+    fn emit_interface_method(
+        &mut self,
+        interface: &str,
+        function: &str,
+        entry: NodeId,
+    ) -> Result<(), NativizeError> {
+        let mut params = String::new();
+        self.params.clear();
+        let mut idents = BTreeSet::from(["ctx".to_string()]);
+        for pin in self.graph.nodes[&entry].pins.iter() {
+            if pin.direction != PinDirection::Output || pin.data_type == DataType::Exec {
+                continue;
+            }
+            let ident = unique_ident(&mut idents, to_snake_ident(&pin.name));
+            let _ = write!(params, ", {ident}: {}", rust_type(pin.data_type));
+            self.params.insert(pin.id, ident);
+        }
+
+        self.line(
+            1,
+            &format!(
+                "pub fn {}(&mut self, ctx: &mut ScriptContext{params}) {{",
+                interface_method(interface, function)
+            ),
+        );
+        self.emit_chain(entry, "then", 2)?;
+        self.line(1, "}");
+        self.params.clear();
+        Ok(())
+    }
+
+    /// Emits the nodes reachable from the given exec output of `node_id`.
+    fn emit_chain(
+        &mut self,
+        node_id: NodeId,
+        exec_out: &str,
+        indent: usize,
+    ) -> Result<(), NativizeError> {
+        let mut next = self.follow(node_id, exec_out);
+        while let Some(node_id) = next {
+            let node = &self.compiled.nodes[&node_id];
+            next = self.follow(node_id, "then");
+            match node.kind {
+                BuiltinNodeKind::Print => {
+                    let text = self.input(node_id, "text")?;
+                    self.line(
+                        indent,
+                        &format!("Log::info(format!(\"[Blueprint] {{}}\", {text}));"),
+                    );
+                }
+                BuiltinNodeKind::SetVariable => {
+                    let name = node.property_string("name").unwrap_or_default();
+                    let Some(field) = self.fields.get(name) else {
+                        return Err(NativizeError::UnsupportedNode(node.kind, node_id));
+                    };
+                    let ident = field.ident.clone();
+                    let value = self.input(node_id, "value")?;
+                    self.line(indent, &format!("self.{ident} = {value};"));
+                }
+                BuiltinNodeKind::Branch => {
+                    let condition = self.input(node_id, "condition")?;
+                    self.line(indent, &format!("if {condition} {{"));
+                    self.emit_chain(node_id, "true", indent + 1)?;
+                    if self.follow(node_id, "false").is_some() {
+                        self.line(indent, "} else {");
+                        self.emit_chain(node_id, "false", indent + 1)?;
+                    }
+                    self.line(indent, "}");
+                }
+                BuiltinNodeKind::CallInterface => self.emit_interface_call(node_id, indent)?,
+                BuiltinNodeKind::Assert => {
+                    let condition = self.input(node_id, "condition")?;
+                    let message = self.assertion_message(node_id, "Assert")?;
+                    self.line(indent, &format!("if !{condition} {{"));
+                    self.line(
+                        indent + 1,
+                        &format!("Log::err(format!(\"[Blueprint] {{}} failed\", {message}));"),
+                    );
+                    self.line(indent, "}");
+                }
+                BuiltinNodeKind::ExpectEqual => {
+                    let expected = self.input(node_id, "expected")?;
+                    let actual = self.input(node_id, "actual")?;
+                    let message = self.assertion_message(node_id, "ExpectEqual")?;
+                    self.line(indent, &format!("if {expected} != {actual} {{"));
+                    self.line(
+                        indent + 1,
+                        &format!("Log::err(format!(\"[Blueprint] {{}} failed\", {message}));"),
+                    );
+                    self.line(indent, "}");
+                }
+                // No-ops in the interpreter as well.
+                BuiltinNodeKind::SetActorTransform | BuiltinNodeKind::SpawnActor => {}
+                BuiltinNodeKind::BeginPlay
+                | BuiltinNodeKind::Tick
+                | BuiltinNodeKind::ConstructionScript
+                | BuiltinNodeKind::InterfaceEvent
+                | BuiltinNodeKind::GetVariable
+                | BuiltinNodeKind::Self_
+                | BuiltinNodeKind::GetActorTransform
+                | BuiltinNodeKind::GetActorByName
+                | BuiltinNodeKind::GetActorName
+                | BuiltinNodeKind::DoesImplementInterface
+                | BuiltinNodeKind::ContainerAdd
+                | BuiltinNodeKind::ContainerRemove
+                | BuiltinNodeKind::ContainerGet
+                | BuiltinNodeKind::ContainerLength
+                | BuiltinNodeKind::ContainerContains
+                | BuiltinNodeKind::ContainerFind
+                | BuiltinNodeKind::ContainerClear
+                | BuiltinNodeKind::ForEach
+                | BuiltinNodeKind::MakeStruct
+                | BuiltinNodeKind::BreakStruct
+                | BuiltinNodeKind::SwitchOnEnum
+                | BuiltinNodeKind::WidgetEvent
+                | BuiltinNodeKind::SetWidgetProperty
+                | BuiltinNodeKind::SetAbsmParameter
+                | BuiltinNodeKind::GetActiveState
+                | BuiltinNodeKind::PlayAnimation
+                | BuiltinNodeKind::StopAnimation
+                | BuiltinNodeKind::OnAnimationSignal
+                | BuiltinNodeKind::RhaiScript
+                | BuiltinNodeKind::PlaySound
+                | BuiltinNodeKind::StopSound
+                | BuiltinNodeKind::SpawnSound
+                | BuiltinNodeKind::SetSoundProperty
+                | BuiltinNodeKind::FadeInSound
+                | BuiltinNodeKind::FadeOutSound
+                | BuiltinNodeKind::SetSoundBus
+                | BuiltinNodeKind::OnSoundFinished
+                | BuiltinNodeKind::OnSceneLoaded
+                | BuiltinNodeKind::OnSceneUnloaded
+                | BuiltinNodeKind::LoadScene
+                | BuiltinNodeKind::GetActorsWithBlueprint
+                | BuiltinNodeKind::GetGlobal
+                | BuiltinNodeKind::SetGlobal
+                | BuiltinNodeKind::GetActorsWithTag
+                | BuiltinNodeKind::HasTag
+                | BuiltinNodeKind::AddTag
+                | BuiltinNodeKind::RemoveTag
+                | BuiltinNodeKind::CustomEvent
+                | BuiltinNodeKind::SetTimerByEvent
+                | BuiltinNodeKind::ClearTimer
+                | BuiltinNodeKind::GetDataTableRow
+                | BuiltinNodeKind::ForEachRow
+                | BuiltinNodeKind::BehaviorTask
+                | BuiltinNodeKind::FinishTask => {
+                    return Err(NativizeError::UnsupportedNode(node.kind, node_id));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn emit_interface_call(&mut self, node_id: NodeId, indent: usize) -> Result<(), NativizeError> {
+        let node = &self.compiled.nodes[&node_id];
+        let interface = node
+            .property_string("interface")
+            .unwrap_or_default()
+            .to_string();
+        let function = node
+            .property_string("function")
+            .unwrap_or_default()
+            .to_string();
+
+        // Messages to other actors are delivered by blueprint scripts only.
+        if !self.target_is_self(node_id)? {
+            return Err(NativizeError::UnsupportedNode(node.kind, node_id));
+        }
+
+        let key = (interface.clone(), function.clone());
+        let Some(entry) = self.compiled.interface_entries.get(&key).copied() else {
+            // Calling a function the graph does not implement does nothing.
+            return Ok(());
+        };
+
+        let mut args = String::new();
+        for pin in self.graph.nodes[&entry].pins.iter() {
+            if pin.direction != PinDirection::Output || pin.data_type == DataType::Exec {
+                continue;
+            }
+            let arg = match node.pin(&pin.name) {
+                Some((_, PinDirection::Input, ty)) if ty == pin.data_type => {
+                    self.input(node_id, &pin.name)?
+                }
+                _ => literal(&pin.data_type.default_value()),
+            };
+            let _ = write!(args, ", {arg}");
+        }
+        self.line(
+            indent,
+            &format!(
+                "self.{}(ctx{args});",
+                interface_method(&interface, &function)
+            ),
+        );
+        Ok(())
+    }
+
+    fn assertion_message(&self, node_id: NodeId, kind: &str) -> Result<String, NativizeError> {
+        let node = &self.compiled.nodes[&node_id];
+        let linked = node
+            .pin("message")
+            .is_some_and(|(pin, _, _)| self.compiled.data_edges.contains_key(&pin));
+        if linked {
+            return self.input(node_id, "message");
+        }
+        Ok(match node.property_string("message") {
+            Some(message) if !message.is_empty() => format!("{message:?}"),
+            _ => format!("\"{kind} #{}\"", node_id.0),
+        })
+    }
+
+    /// Whether the `target` input of the node is the actor itself. Only literal targets are
+    /// supported.
+    fn target_is_self(&self, node_id: NodeId) -> Result<bool, NativizeError> {
+        let node = &self.compiled.nodes[&node_id];
+        if let Some((pin, _, _)) = node.pin("target") {
+            if self.compiled.data_edges.contains_key(&pin) {
+                return Err(NativizeError::UnsupportedNode(node.kind, node_id));
+            }
+        }
+        let target = node.property_string("target").unwrap_or_default();
+        Ok(crate::host::is_self_target(target))
+    }
+
+    /// Rust expression of a data input: the linked output, the literal property of the node or the
+    /// default value of the pin type.
+    fn input(&self, node_id: NodeId, name: &str) -> Result<String, NativizeError> {
+        let node = &self.compiled.nodes[&node_id];
+        let Some((pin, _, ty)) = node.pin(name) else {
+            return Err(NativizeError::UnsupportedNode(node.kind, node_id));
+        };
+
+        let Some(from) = self.compiled.data_edges.get(&pin).copied() else {
+            let value = node
+                .properties
+                .get(name)
+                .filter(|v| v.data_type() == ty)
+                .cloned()
+                .unwrap_or_else(|| ty.default_value());
+            return Ok(literal(&value));
+        };
+
+        let from_id = self.pin_owners[&from];
+        let from_node = &self.compiled.nodes[&from_id];
+        match from_node.kind {
+            BuiltinNodeKind::GetVariable => {
+                let name = from_node.property_string("name").unwrap_or_default();
+                match self.fields.get(name) {
+                    Some(field)
+                        if field.data_type == DataType::String
+                            || field.data_type.is_container() =>
+                    {
+                        Ok(format!("self.{}.clone()", field.ident))
+                    }
+                    Some(field) => Ok(format!("self.{}", field.ident)),
+                    None => Err(NativizeError::UnsupportedNode(from_node.kind, from_id)),
+                }
+            }
+            BuiltinNodeKind::Tick if from_node.pin_name(from) == Some("dt") => {
+                Ok("ctx.dt".to_string())
+            }
+            BuiltinNodeKind::InterfaceEvent => match self.params.get(&from) {
+                Some(param) if ty == DataType::String || ty.is_container() => {
+                    Ok(format!("{param}.clone()"))
+                }
+                Some(param) => Ok(param.clone()),
+                None => Err(NativizeError::UnsupportedNode(from_node.kind, from_id)),
+            },
+            BuiltinNodeKind::DoesImplementInterface => {
+                if !self.target_is_self(from_id)? {
+                    return Err(NativizeError::UnsupportedNode(from_node.kind, from_id));
+                }
+                let interface = from_node.property_string("interface").unwrap_or_default();
+                Ok(self.compiled.interfaces.contains(interface).to_string())
+            }
+            _ => Err(NativizeError::UnsupportedNode(from_node.kind, from_id)),
+        }
+    }
+
+    fn follow(&self, node_id: NodeId, exec_out: &str) -> Option<NodeId> {
+        let (pin, _, _) = self.compiled.nodes.get(&node_id)?.pin(exec_out)?;
+        let to = self.compiled.exec_edges.get(&pin)?;
+        self.pin_owners.get(to).copied()
+    }
+
+    fn line(&mut self, indent: usize, text: &str) {
+        if !text.is_empty() {
+            for _ in 0..indent {
+                self.out.push_str("    ");
+            }
+            self.out.push_str(text);
+        }
+        self.out.push('\n');
+    }
+}
+
+fn rust_type(data_type: DataType) -> String {
+    match data_type {
+        DataType::Bool => "bool".to_string(),
+        DataType::I32 => "i32".to_string(),
+        DataType::F32 => "f32".to_string(),
+        DataType::String => "String".to_string(),
+        // Rejected by the generator before any code is emitted.
+        DataType::Exec | DataType::Unit | DataType::Struct(_) | DataType::Enum(_) => {
+            "()".to_string()
+        }
+        // Sets and maps keep their insertion order in blueprints, so they stay vectors.
+        DataType::Array(element) | DataType::Set(element) => {
+            format!("Vec<{}>", rust_type(element.data_type()))
+        }
+        DataType::Map(key, value) => format!(
+            "Vec<({}, {})>",
+            rust_type(key.data_type()),
+            rust_type(value.data_type())
+        ),
+    }
+}
+
+fn literal(value: &Value) -> String {
+    match value {
+        Value::Bool(v) => v.to_string(),
+        Value::I32(v) => v.to_string(),
+        Value::F32(v) if v.is_nan() => "f32::NAN".to_string(),
+        Value::F32(v) if v.is_infinite() && *v > 0.0 => "f32::INFINITY".to_string(),
+        Value::F32(v) if v.is_infinite() => "f32::NEG_INFINITY".to_string(),
+        Value::F32(v) => format!("{v:?}_f32"),
+        Value::String(v) => format!("{v:?}.to_string()"),
+        Value::Unit | Value::Struct(..) | Value::Enum(..) => "()".to_string(),
+        Value::Array(_, items) | Value::Set(_, items) => {
+            let items = items.iter().map(literal).collect::<Vec<_>>();
+            format!("vec![{}]", items.join(", "))
+        }
+        Value::Map(_, _, entries) => {
+            let entries = entries
+                .iter()
+                .map(|(key, value)| format!("({}, {})", literal(key), literal(value)))
+                .collect::<Vec<_>>();
+            format!("vec![{}]", entries.join(", "))
+        }
+    }
+}
+
+fn interface_method(interface: &str, function: &str) -> String {
+    format!("{}_{}", to_snake_ident(interface), to_snake_ident(function))
+}
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use",
+    "where", "while", "yield",
+];
+
+fn to_snake_ident(name: &str) -> String {
+    let mut ident = String::new();
+    let mut previous_lowercase = false;
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            if c.is_ascii_uppercase() && previous_lowercase {
+                ident.push('_');
+            }
+            ident.push(c.to_ascii_lowercase());
+            previous_lowercase = c.is_ascii_lowercase() || c.is_ascii_digit();
+        } else {
+            if !ident.is_empty() && !ident.ends_with('_') {
+                ident.push('_');
+            }
+            previous_lowercase = false;
+        }
+    }
+    let ident = ident.trim_end_matches('_');
+    match ident.chars().next() {
+        None => "value".to_string(),
+        Some(c) if c.is_ascii_digit() => format!("_{ident}"),
+        _ if KEYWORDS.contains(&ident) => format!("{ident}_"),
+        _ => ident.to_string(),
+    }
+}
+
+fn to_camel_ident(name: &str) -> String {
+    let ident = to_snake_ident(name)
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect::<String>();
+    if ident.starts_with(|c: char| c.is_ascii_digit()) || ident == "Self" {
+        format!("Blueprint{ident}")
+    } else {
+        ident
+    }
+}
+
+fn unique_ident(taken: &mut BTreeSet<String>, ident: String) -> String {
+    let mut candidate = ident.clone();
+    let mut suffix = 2;
+    while !taken.insert(candidate.clone()) {
+        candidate = format!("{ident}_{suffix}");
+        suffix += 1;
+    }
+    candidate
+}
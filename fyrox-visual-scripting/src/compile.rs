@@ -1,6 +1,10 @@
 use crate::{
+    animation::AbsmParameterKind,
     error::{CompileError, ValidationError},
     model::{BlueprintGraph, BuiltinNodeKind, DataType, Link, NodeId, PinDirection, PinId, Value},
+    sound::SoundProperty,
+    types::UserTypes,
+    widgets::{WidgetEvent, WidgetProperty},
 };
 use std::collections::{BTreeMap, BTreeSet};
 
@@ -9,6 +13,25 @@ pub struct CompiledGraph {
     pub begin_play_entry: Option<NodeId>,
     pub construction_entry: Option<NodeId>,
     pub tick_entry: Option<NodeId>,
+    /// Entry nodes of interface function implementations, keyed by `(interface, function)`.
+    pub interface_entries: BTreeMap<(String, String), NodeId>,
+    /// Names of the interfaces implemented by the graph.
+    pub interfaces: BTreeSet<String>,
+    /// Entry nodes of widget events, keyed by widget name and event.
+    pub widget_entries: BTreeMap<(String, WidgetEvent), Vec<NodeId>>,
+    /// Entry nodes of animation signals, keyed by signal name.
+    pub animation_signal_entries: BTreeMap<String, Vec<NodeId>>,
+    /// Entry nodes of `OnSoundFinished` events, keyed by sound node name.
+    pub sound_finished_entries: BTreeMap<String, Vec<NodeId>>,
+    /// Entry nodes of custom events, keyed by event name.
+    pub custom_event_entries: BTreeMap<String, Vec<NodeId>>,
+    /// Entry nodes of behavior tree tasks, keyed by task name.
+    pub behavior_task_entries: BTreeMap<String, NodeId>,
+    /// Scene events, only raised for level blueprints.
+    pub scene_loaded_entry: Option<NodeId>,
+    pub scene_unloaded_entry: Option<NodeId>,
+    /// Definitions of the struct and enum types used by the graph.
+    pub types: UserTypes,
 
     pub variables: BTreeMap<String, Value>,
 
@@ -30,6 +53,19 @@ impl CompiledNode {
     pub fn pin(&self, name: &str) -> Option<(PinId, PinDirection, DataType)> {
         self.pins.get(name).copied()
     }
+
+    pub fn pin_name(&self, pin_id: PinId) -> Option<&str> {
+        self.pins
+            .iter()
+            .find_map(|(name, (id, _, _))| (*id == pin_id).then_some(name.as_str()))
+    }
+
+    pub fn property_string(&self, key: &str) -> Option<&str> {
+        match self.properties.get(key)? {
+            Value::String(s) => Some(s.as_str()),
+            _ => None,
+        }
+    }
 }
 
 pub fn compile(graph: &BlueprintGraph) -> Result<CompiledGraph, CompileError> {
@@ -40,13 +76,7 @@ pub fn compile(graph: &BlueprintGraph) -> Result<CompiledGraph, CompileError> {
         let value = var
             .default_value
             .clone()
-            .unwrap_or_else(|| match var.data_type {
-                DataType::Bool => Value::Bool(false),
-                DataType::I32 => Value::I32(0),
-                DataType::F32 => Value::F32(0.0),
-                DataType::String => Value::String(String::new()),
-                DataType::Exec | DataType::Unit => Value::Unit,
-            });
+            .unwrap_or_else(|| graph.types.default_value(var.data_type));
         variables.insert(var.name.clone(), value);
     }
 
@@ -107,10 +137,99 @@ pub fn compile(graph: &BlueprintGraph) -> Result<CompiledGraph, CompileError> {
         }
     }
 
+    let mut interface_entries = BTreeMap::new();
+    for (node_id, node) in graph.nodes.iter() {
+        if node.kind != BuiltinNodeKind::InterfaceEvent {
+            continue;
+        }
+        if let (Some(Value::String(interface)), Some(Value::String(function))) = (
+            node.properties.get("interface"),
+            node.properties.get("function"),
+        ) {
+            interface_entries.insert((interface.clone(), function.clone()), *node_id);
+        }
+    }
+
+    let mut widget_entries: BTreeMap<_, Vec<_>> = BTreeMap::new();
+    for (node_id, node) in graph.nodes.iter() {
+        if node.kind != BuiltinNodeKind::WidgetEvent {
+            continue;
+        }
+        if let (Some(Value::String(widget)), Some(Value::String(event))) =
+            (node.properties.get("widget"), node.properties.get("event"))
+        {
+            if let Some(event) = WidgetEvent::from_name(event) {
+                widget_entries
+                    .entry((widget.clone(), event))
+                    .or_default()
+                    .push(*node_id);
+            }
+        }
+    }
+
+    let mut animation_signal_entries: BTreeMap<_, Vec<_>> = BTreeMap::new();
+    for (node_id, node) in graph.nodes.iter() {
+        if node.kind != BuiltinNodeKind::OnAnimationSignal {
+            continue;
+        }
+        if let Some(Value::String(signal)) = node.properties.get("signal") {
+            animation_signal_entries
+                .entry(signal.clone())
+                .or_default()
+                .push(*node_id);
+        }
+    }
+
+    let mut sound_finished_entries: BTreeMap<_, Vec<_>> = BTreeMap::new();
+    for (node_id, node) in graph.nodes.iter() {
+        if node.kind != BuiltinNodeKind::OnSoundFinished {
+            continue;
+        }
+        if let Some(Value::String(sound)) = node.properties.get("sound") {
+            sound_finished_entries
+                .entry(sound.clone())
+                .or_default()
+                .push(*node_id);
+        }
+    }
+
+    let mut custom_event_entries: BTreeMap<_, Vec<_>> = BTreeMap::new();
+    for (node_id, node) in graph.nodes.iter() {
+        if node.kind != BuiltinNodeKind::CustomEvent {
+            continue;
+        }
+        if let Some(Value::String(event)) = node.properties.get("event") {
+            custom_event_entries
+                .entry(event.clone())
+                .or_default()
+                .push(*node_id);
+        }
+    }
+
+    let mut behavior_task_entries = BTreeMap::new();
+    for (node_id, node) in graph.nodes.iter() {
+        if node.kind != BuiltinNodeKind::BehaviorTask {
+            continue;
+        }
+        if let Some(Value::String(task)) = node.properties.get("task") {
+            behavior_task_entries.insert(task.clone(), *node_id);
+        }
+    }
+
     Ok(CompiledGraph {
         begin_play_entry: find_entry(graph, BuiltinNodeKind::BeginPlay),
         construction_entry: find_entry(graph, BuiltinNodeKind::ConstructionScript),
         tick_entry: find_entry(graph, BuiltinNodeKind::Tick),
+        interface_entries,
+        widget_entries,
+        animation_signal_entries,
+        sound_finished_entries,
+        custom_event_entries,
+        behavior_task_entries,
+        scene_loaded_entry: find_entry(graph, BuiltinNodeKind::OnSceneLoaded),
+        scene_unloaded_entry: find_entry(graph, BuiltinNodeKind::OnSceneUnloaded),
+        interfaces: graph.interfaces.iter().map(|i| i.name.clone()).collect(),
+        types: graph.types.clone(),
         variables,
         nodes,
         exec_edges,
@@ -167,6 +286,26 @@ fn validate(graph: &BlueprintGraph) -> Result<(), CompileError> {
 
     let vars: BTreeSet<&str> = graph.variables.iter().map(|v| v.name.as_str()).collect();
 
+    // Struct and enum types: every variable and pin type must be defined.
+    if graph
+        .variables
+        .iter()
+        .any(|v| !graph.types.is_defined(v.data_type))
+    {
+        return Err(CompileError::new(ValidationError::UnknownType));
+    }
+    for (node_id, node) in graph.nodes.iter() {
+        if let Some(pin) = node
+            .pins
+            .iter()
+            .find(|p| !graph.types.is_defined(p.data_type))
+        {
+            return Err(CompileError::new(ValidationError::UnknownType)
+                .with_node(*node_id)
+                .with_pin(pin.id));
+        }
+    }
+
     // Links: pin existence, direction and type correctness.
     for Link { from, to } in graph.links.iter() {
         let from_pin = graph
@@ -255,6 +394,107 @@ fn validate(graph: &BlueprintGraph) -> Result<(), CompileError> {
                         .with_node(*node_id));
                 }
             }
+            // Implementations must match a function of an implemented interface.
+            BuiltinNodeKind::InterfaceEvent => {
+                let declared = match (
+                    node.properties.get("interface"),
+                    node.properties.get("function"),
+                ) {
+                    (Some(Value::String(interface)), Some(Value::String(function))) => graph
+                        .interfaces
+                        .iter()
+                        .find(|i| i.name == *interface)
+                        .and_then(|i| i.function(function))
+                        .is_some(),
+                    _ => false,
+                };
+                if !declared {
+                    return Err(CompileError::new(ValidationError::UnknownInterfaceFunction)
+                        .with_node(*node_id));
+                }
+            }
+            // Callers may use interfaces they do not implement, but must name one.
+            BuiltinNodeKind::CallInterface | BuiltinNodeKind::DoesImplementInterface => {
+                let named = |key: &str| {
+                    matches!(node.properties.get(key), Some(Value::String(s)) if !s.is_empty())
+                };
+                let valid = named("interface")
+                    && (node.kind == BuiltinNodeKind::DoesImplementInterface || named("function"));
+                if !valid {
+                    return Err(CompileError::new(ValidationError::UnknownInterfaceFunction)
+                        .with_node(*node_id));
+                }
+            }
+            // Widget events need a widget name; both kinds need a known event or property.
+            BuiltinNodeKind::WidgetEvent | BuiltinNodeKind::SetWidgetProperty => {
+                let property = |key: &str| match node.properties.get(key) {
+                    Some(Value::String(s)) => s.as_str(),
+                    _ => "",
+                };
+                let valid = match node.kind {
+                    BuiltinNodeKind::WidgetEvent => {
+                        !property("widget").is_empty()
+                            && WidgetEvent::from_name(property("event")).is_some()
+                    }
+                    _ => WidgetProperty::from_name(property("property")).is_some(),
+                };
+                if !valid {
+                    return Err(
+                        CompileError::new(ValidationError::UnknownWidgetMember).with_node(*node_id)
+                    );
+                }
+            }
+            // Parameters need a known kind, signal events a signal name.
+            BuiltinNodeKind::SetAbsmParameter | BuiltinNodeKind::OnAnimationSignal => {
+                let property = |key: &str| match node.properties.get(key) {
+                    Some(Value::String(s)) => s.as_str(),
+                    _ => "",
+                };
+                let valid = match node.kind {
+                    BuiltinNodeKind::SetAbsmParameter => {
+                        AbsmParameterKind::from_name(property("parameter")).is_some()
+                    }
+                    _ => !property("signal").is_empty(),
+                };
+                if !valid {
+                    return Err(CompileError::new(ValidationError::UnknownAnimationMember)
+                        .with_node(*node_id));
+                }
+            }
+            // Finished events need a sound name, property changes a known property.
+            BuiltinNodeKind::OnSoundFinished | BuiltinNodeKind::SetSoundProperty => {
+                let property = |key: &str| match node.properties.get(key) {
+                    Some(Value::String(s)) => s.as_str(),
+                    _ => "",
+                };
+                let valid = match node.kind {
+                    BuiltinNodeKind::OnSoundFinished => !property("sound").is_empty(),
+                    _ => SoundProperty::from_name(property("property")).is_some(),
+                };
+                if !valid {
+                    return Err(
+                        CompileError::new(ValidationError::UnknownSoundMember).with_node(*node_id)
+                    );
+                }
+            }
+            // Changes are written back to a variable, so the target must be read from one.
+            BuiltinNodeKind::ContainerAdd
+            | BuiltinNodeKind::ContainerRemove
+            | BuiltinNodeKind::ContainerClear => {
+                let from_variable = node.pin_named("target").is_some_and(|target| {
+                    graph
+                        .links
+                        .iter()
+                        .find(|link| link.to == target)
+                        .and_then(|link| graph.pin_owner(link.from))
+                        .and_then(|owner| graph.nodes.get(&owner))
+                        .is_some_and(|owner| owner.kind == BuiltinNodeKind::GetVariable)
+                });
+                if !from_variable {
+                    return Err(CompileError::new(ValidationError::ContainerTargetNotVariable)
+                        .with_node(*node_id));
+                }
+            }
             _ => {}
         }
     }
@@ -289,10 +529,10 @@ fn detect_exec_cycles(graph: &BlueprintGraph) -> Result<(), CompileError> {
     let mut stack = BTreeSet::new();
 
     for node_id in graph.nodes.keys().copied() {
-        if !visited.contains(&node_id) {
-            if dfs_cycle(node_id, &adjacency, &mut visited, &mut stack) {
-                return Err(CompileError::new(ValidationError::ExecCycle).with_node(node_id));
-            }
+        if !visited.contains(&node_id)
+            && dfs_cycle(node_id, &adjacency, &mut visited, &mut stack)
+        {
+            return Err(CompileError::new(ValidationError::ExecCycle).with_node(node_id));
         }
     }
 
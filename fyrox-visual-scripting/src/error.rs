@@ -1,4 +1,4 @@
-use crate::model::{NodeId, PinId};
+use crate::model::{BuiltinNodeKind, NodeId, PinId};
 use thiserror::Error;
 
 #[derive(Debug, Error, Clone, PartialEq, Eq)]
@@ -32,6 +32,24 @@ pub enum ValidationError {
 
     #[error("unknown variable")]
     UnknownVariable,
+
+    #[error("unknown interface function")]
+    UnknownInterfaceFunction,
+
+    #[error("unknown struct or enum type")]
+    UnknownType,
+
+    #[error("unknown widget event or property")]
+    UnknownWidgetMember,
+
+    #[error("unknown ABSM parameter kind or empty animation signal")]
+    UnknownAnimationMember,
+
+    #[error("unknown sound property or empty sound name")]
+    UnknownSoundMember,
+
+    #[error("container to modify must come from a Get Variable node")]
+    ContainerTargetNotVariable,
 }
 
 #[derive(Debug, Error, Clone, PartialEq, Eq)]
@@ -61,3 +79,15 @@ impl CompileError {
         self
     }
 }
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum NativizeError {
+    #[error(transparent)]
+    Compile(#[from] CompileError),
+
+    #[error("node {1:?} ({0:?}) has no native equivalent")]
+    UnsupportedNode(BuiltinNodeKind, NodeId),
+
+    #[error("variable {0:?} has no native type")]
+    UnsupportedVariable(String),
+}
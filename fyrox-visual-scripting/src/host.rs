@@ -0,0 +1,76 @@
+//! Engine-side services available to a running graph.
+
+use crate::model::Value;
+
+/// Actor reference that always means the actor running the graph. An empty string means the same.
+pub const SELF_TARGET: &str = "self";
+
+pub fn is_self_target(target: &str) -> bool {
+    target.is_empty() || target == SELF_TARGET
+}
+
+/// Answers questions about the world a graph runs in.
+///
+/// The interpreter knows nothing about scenes, so nodes that need world state ask the host while
+/// they run. Side effects are not performed through the host; they are reported as
+/// [`crate::ExecutionEvent`]s and applied by the caller.
+pub trait BlueprintHost {
+    /// Whether `target` references the actor running the graph. Besides [`is_self_target`]
+    /// references, hosts that know the world could recognize the actor by its name. Questions
+    /// about the running actor are answered by the interpreter itself, not by the host.
+    fn is_self(&self, target: &str) -> bool {
+        is_self_target(target)
+    }
+
+    /// Whether the actor referenced by `target` runs a blueprint that implements `interface`.
+    fn does_implement_interface(&self, _target: &str, _interface: &str) -> bool {
+        false
+    }
+
+    /// Name of the active state of a layer of the state machine on the actor referenced by
+    /// `target` (or on one of its descendants).
+    fn active_state(&self, _target: &str, _layer: usize) -> Option<String> {
+        None
+    }
+
+    /// Gain of the sound node referenced by `target`. Fade outs start from it.
+    fn sound_gain(&self, _target: &str) -> Option<f32> {
+        None
+    }
+
+    /// Names of the actors that run the blueprint asset at the given path.
+    fn actors_with_blueprint(&self, _blueprint: &str) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Value of the global variable with the given name, a variable of a level blueprint.
+    fn global(&self, _name: &str) -> Option<Value> {
+        None
+    }
+
+    /// Names of the actors that have the tag.
+    fn actors_with_tag(&self, _tag: &str) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Whether the actor referenced by `target` has the tag.
+    fn has_tag(&self, _target: &str, _tag: &str) -> bool {
+        false
+    }
+
+    /// Cells of the row with the given key of the data table asset at the given path, by column
+    /// name. The key column is included.
+    fn data_table_row(&self, _table: &str, _key: &str) -> Option<Vec<(String, Value)>> {
+        None
+    }
+
+    /// Keys of the rows of the data table asset at the given path, in row order.
+    fn data_table_keys(&self, _table: &str) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// Host without a world: every query gets a negative answer.
+pub struct NullHost;
+
+impl BlueprintHost for NullHost {}
@@ -1,10 +1,21 @@
 use crate::{
-    compile::CompiledGraph,
-    model::{BuiltinNodeKind, DataType, NodeId, Value},
+    animation::AnimationCommand,
+    behavior::TaskStatus,
+    compile::{CompiledGraph, CompiledNode},
+    host::{BlueprintHost, NullHost},
+    model::{BuiltinNodeKind, DataType, ElementType, NodeId, PinDirection, PinId, Value},
+    profile::BlueprintProfile,
     runtime::runtime_for,
+    sound::{SoundCommand, SoundFade, SoundProperty},
+    tags::TagCommand,
+    timers::TimerCommand,
+    types::UserTypes,
+    widgets::{WidgetCommand, WidgetEvent},
 };
+use instant::Instant;
 use std::collections::BTreeMap;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use rhai::{Dynamic, Engine, EvalAltResult, FLOAT, INT};
 
@@ -12,6 +23,49 @@ use rhai::{Dynamic, Engine, EvalAltResult, FLOAT, INT};
 pub enum ExecutionEvent {
     EnterNode(NodeId),
     Print(String),
+    /// A `CallInterface` node targeted another actor. The host delivers the call; it does nothing
+    /// if the target does not implement the interface.
+    InterfaceMessage(InterfaceCall),
+    /// Outcome of an `Assert` or `ExpectEqual` node.
+    Assertion(AssertionResult),
+    /// A `SetWidgetProperty` node changed a widget of the scripted user interface.
+    Widget(WidgetCommand),
+    /// An animation node changed a state machine parameter or the playback of an animation.
+    Animation(AnimationCommand),
+    /// A sound node played, stopped, spawned or changed a sound.
+    Sound(SoundCommand),
+    /// A `LoadScene` node requested loading of the scene at the given path.
+    LoadScene(String),
+    /// A `SetGlobal` node changed the global variable with the given name.
+    SetGlobal(String, Value),
+    /// An `AddTag` or `RemoveTag` node changed the tags of an actor.
+    Tag(TagCommand),
+    /// A `SetTimerByEvent` or `ClearTimer` node started or stopped the timer of a custom event.
+    Timer(TimerCommand),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssertionResult {
+    pub node: NodeId,
+    /// The message of the node, or a generated name if it has none.
+    pub name: String,
+    /// Why the assertion failed; `None` if it passed.
+    pub failure: Option<String>,
+}
+
+impl AssertionResult {
+    pub fn passed(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+/// Interface function call addressed to an actor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterfaceCall {
+    pub target: String,
+    pub interface: String,
+    pub function: String,
+    pub args: BTreeMap<String, Value>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -23,6 +77,21 @@ pub struct InterpreterOutput {
 pub struct Interpreter {
     compiled: CompiledGraph,
     variables: BTreeMap<String, Value>,
+    // Values of output pins produced by executed nodes (e.g. interface event arguments). Scoped to
+    // a single execution, cleared when an entry starts outside of any running execution.
+    pin_values: BTreeMap<PinId, Value>,
+    call_depth: usize,
+    // Number of nested `run_from_exec_out` calls in progress.
+    exec_depth: usize,
+    // Sound fades in progress, advanced on every tick.
+    fades: Vec<SoundFade>,
+    // Collected statistics, `None` while profiling is disabled.
+    profile: Option<BlueprintProfile>,
+    // Time of the profiled nodes that have finished running. The time of the nodes that a node runs
+    // (interface functions, etc.) is subtracted from its own time using this counter.
+    profiled_time: Duration,
+    // Outcome reported by a `FinishTask` node during the current run of a behavior tree task.
+    task_status: Option<TaskStatus>,
 }
 
 fn value_to_dynamic(v: &Value) -> Dynamic {
@@ -32,6 +101,92 @@ fn value_to_dynamic(v: &Value) -> Dynamic {
         Value::F32(f) => (FLOAT::from(*f)).into(),
         Value::String(s) => s.clone().into(),
         Value::Unit => Dynamic::UNIT,
+        Value::Array(_, items) | Value::Set(_, items) => {
+            Dynamic::from_array(items.iter().map(value_to_dynamic).collect())
+        }
+        // Rhai maps are keyed by strings.
+        Value::Map(_, _, entries) => Dynamic::from_map(
+            entries
+                .iter()
+                .map(|(key, value)| (key.to_string().into(), value_to_dynamic(value)))
+                .collect::<rhai::Map>(),
+        ),
+        Value::Struct(_, fields) => Dynamic::from_map(
+            fields
+                .iter()
+                .map(|(name, value)| (name.as_str().into(), value_to_dynamic(value)))
+                .collect::<rhai::Map>(),
+        ),
+        Value::Enum(_, variant) => variant.clone().into(),
+    }
+}
+
+/// Converts a Rhai value to a value of the same type as `current`. Structs are updated field by
+/// field, so a script may assign a map with only some of the fields.
+fn dynamic_like(v: &Dynamic, current: &Value, types: &UserTypes) -> Option<Value> {
+    match current {
+        Value::Struct(id, fields) => {
+            let map = v.read_lock::<rhai::Map>()?;
+            let fields = fields
+                .iter()
+                .map(|(name, old)| {
+                    let value = match map.get(name.as_str()) {
+                        Some(new) => dynamic_like(new, old, types)?,
+                        None => old.clone(),
+                    };
+                    Some((name.clone(), value))
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some(Value::Struct(*id, fields))
+        }
+        Value::Enum(id, _) => {
+            let variant = v.clone().into_string().ok()?;
+            let known = types
+                .enum_def(*id)
+                .is_some_and(|def| def.variants.contains(&variant));
+            known.then_some(Value::Enum(*id, variant))
+        }
+        _ => dynamic_to_typed_value(v, current.data_type()),
+    }
+}
+
+/// Converts a Rhai value to a value of the given type. Integers are accepted for floats, and map
+/// keys are parsed from strings.
+fn dynamic_to_typed_value(v: &Dynamic, ty: DataType) -> Option<Value> {
+    match ty {
+        DataType::F32 if v.is::<INT>() => Some(Value::F32(v.clone_cast::<INT>() as f32)),
+        DataType::Array(element) | DataType::Set(element) => {
+            let mut items = Vec::new();
+            for item in v.read_lock::<rhai::Array>()?.iter() {
+                let item = dynamic_to_typed_value(item, element.data_type())?;
+                if matches!(ty, DataType::Array(_)) || !items.contains(&item) {
+                    items.push(item);
+                }
+            }
+            Some(match ty {
+                DataType::Array(_) => Value::Array(element, items),
+                _ => Value::Set(element, items),
+            })
+        }
+        DataType::Map(key_ty, value_ty) => {
+            let mut entries = Vec::new();
+            for (key, value) in v.read_lock::<rhai::Map>()?.iter() {
+                let key = parse_scalar(key.as_str(), key_ty)?;
+                let value = dynamic_to_typed_value(value, value_ty.data_type())?;
+                entries.push((key, value));
+            }
+            Some(Value::Map(key_ty, value_ty, entries))
+        }
+        _ => dynamic_to_value(v).filter(|value| value.data_type() == ty),
+    }
+}
+
+fn parse_scalar(text: &str, ty: ElementType) -> Option<Value> {
+    match ty {
+        ElementType::Bool => text.parse().ok().map(Value::Bool),
+        ElementType::I32 => text.parse().ok().map(Value::I32),
+        ElementType::F32 => text.parse().ok().map(Value::F32),
+        ElementType::String => Some(Value::String(text.to_string())),
     }
 }
 
@@ -58,42 +213,461 @@ fn dynamic_to_value(v: &Dynamic) -> Option<Value> {
 }
 
 impl Interpreter {
+    /// Interface calls nested deeper than this are dropped (guards against mutual recursion).
+    pub const MAX_CALL_DEPTH: usize = 32;
+
     pub fn new(compiled: CompiledGraph) -> Self {
         Self {
             variables: compiled.variables.clone(),
             compiled,
+            pin_values: BTreeMap::new(),
+            call_depth: 0,
+            exec_depth: 0,
+            fades: Vec::new(),
+            profile: None,
+            profiled_time: Duration::ZERO,
+            task_status: None,
         }
     }
 
+    /// Enables or disables collection of execution statistics. Disabling drops the statistics
+    /// collected so far.
+    pub fn set_profiling(&mut self, enabled: bool) {
+        if !enabled {
+            self.profile = None;
+        } else if self.profile.is_none() {
+            self.profile = Some(BlueprintProfile::default());
+        }
+    }
+
+    pub fn is_profiling(&self) -> bool {
+        self.profile.is_some()
+    }
+
+    /// Returns the statistics collected since the previous call and starts over. `None` if
+    /// profiling is disabled.
+    pub fn take_profile(&mut self) -> Option<BlueprintProfile> {
+        self.profile.as_mut().map(std::mem::take)
+    }
+
+    fn start_timer(&self) -> Option<Instant> {
+        self.profile.is_some().then(Instant::now)
+    }
+
+    fn record_event(&mut self, event: &str, started: Option<Instant>) {
+        if let (Some(profile), Some(started)) = (self.profile.as_mut(), started) {
+            profile
+                .events
+                .entry(event.to_string())
+                .or_default()
+                .add(started.elapsed());
+        }
+    }
+
+    pub fn variables(&self) -> &BTreeMap<String, Value> {
+        &self.variables
+    }
+
+    /// Changes a variable from outside of the graph. Returns `false` if the graph has no variable
+    /// with the name or the variable has another type.
+    pub fn set_variable_value(&mut self, name: &str, value: Value) -> bool {
+        match self.variables.get_mut(name) {
+            Some(variable) if variable.data_type() == value.data_type() => {
+                *variable = value;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Copies live variable values from an interpreter of an older version of the graph (hot
+    /// reload). Variables that were removed or changed their type keep the new defaults. Returns
+    /// the number of migrated variables.
+    pub fn migrate_variables(&mut self, previous: &Interpreter) -> usize {
+        let mut migrated = 0;
+        for (name, value) in self.variables.iter_mut() {
+            if let Some(old) = previous.variables.get(name) {
+                if old.data_type() == value.data_type() {
+                    *value = old.clone();
+                    migrated += 1;
+                }
+            }
+        }
+        migrated
+    }
+
     pub fn run_begin_play(&mut self) -> InterpreterOutput {
-        self.run_entry(self.compiled.begin_play_entry)
+        self.run_begin_play_with_host(&NullHost)
+    }
+
+    pub fn run_begin_play_with_host(&mut self, host: &dyn BlueprintHost) -> InterpreterOutput {
+        self.begin_entry();
+        let started = self.start_timer();
+        let out = self.run_entry(host, self.compiled.begin_play_entry);
+        self.record_event("BeginPlay", started);
+        out
     }
 
     pub fn run_construction_script(&mut self) -> InterpreterOutput {
-        self.run_entry(self.compiled.construction_entry)
+        self.run_construction_script_with_host(&NullHost)
+    }
+
+    pub fn run_construction_script_with_host(
+        &mut self,
+        host: &dyn BlueprintHost,
+    ) -> InterpreterOutput {
+        self.begin_entry();
+        let started = self.start_timer();
+        let out = self.run_entry(host, self.compiled.construction_entry);
+        self.record_event("ConstructionScript", started);
+        out
     }
 
     pub fn tick(&mut self, dt: f32) -> InterpreterOutput {
+        self.tick_with_host(&NullHost, dt)
+    }
+
+    pub fn tick_with_host(&mut self, host: &dyn BlueprintHost, dt: f32) -> InterpreterOutput {
+        let mut out = self.advance_fades(host, dt);
+
         // For now, only supports a single Tick node.
         if let Some(tick_node) = self.compiled.tick_entry {
             // Populate dt output for consumers (MVP: none consume it yet).
             self.variables
                 .insert("__dt".to_string(), Value::F32(dt));
-            self.run_from_exec_out(tick_node, "then")
-        } else {
-            InterpreterOutput::default()
+            self.begin_entry();
+            let started = self.start_timer();
+            let nested = self.run_from_exec_out(host, tick_node, "then");
+            self.record_event("Tick", started);
+            out.events.extend(nested.events);
+            out.variables = nested.variables;
+        }
+        out
+    }
+
+    /// Starts a fade, replacing the fade of the same sound if there is one.
+    pub(crate) fn start_fade(&mut self, fade: SoundFade) {
+        self.fades.retain(|running| running.sound != fade.sound);
+        self.fades.push(fade);
+    }
+
+    /// Ramps the gain of the fading sounds and continues from the `completed` output of the fades
+    /// that are over.
+    fn advance_fades(&mut self, host: &dyn BlueprintHost, dt: f32) -> InterpreterOutput {
+        let mut out = InterpreterOutput::default();
+        if self.fades.is_empty() {
+            return out;
+        }
+
+        let mut finished = Vec::new();
+        for fade in self.fades.iter_mut() {
+            fade.elapsed += dt;
+            out.events.push(ExecutionEvent::Sound(SoundCommand::SetProperty {
+                sound: fade.sound.clone(),
+                property: SoundProperty::Gain,
+                value: Value::F32(fade.gain()),
+            }));
+            if fade.is_finished() {
+                finished.push(fade.clone());
+            }
+        }
+        self.fades.retain(|fade| !fade.is_finished());
+
+        for fade in finished {
+            if fade.stop_at_end {
+                out.events.push(ExecutionEvent::Sound(SoundCommand::Stop {
+                    sound: fade.sound.clone(),
+                }));
+                out.events.push(ExecutionEvent::Sound(SoundCommand::SetProperty {
+                    sound: fade.sound,
+                    property: SoundProperty::Gain,
+                    value: Value::F32(fade.from),
+                }));
+            }
+            self.begin_entry();
+            let nested = self.run_from_exec_out(host, fade.node, "completed");
+            out.events.extend(nested.events);
+        }
+        out.variables = self.variables.clone();
+        out
+    }
+
+    pub fn implements_interface(&self, interface: &str) -> bool {
+        self.compiled.interfaces.contains(interface)
+    }
+
+    /// Runs the implementation of `interface::function`, if there is one. Arguments are matched to
+    /// the entry node outputs by name; arguments of a wrong type are ignored.
+    pub fn call_interface(
+        &mut self,
+        interface: &str,
+        function: &str,
+        args: &BTreeMap<String, Value>,
+    ) -> InterpreterOutput {
+        self.call_interface_with_host(&NullHost, interface, function, args)
+    }
+
+    pub fn call_interface_with_host(
+        &mut self,
+        host: &dyn BlueprintHost,
+        interface: &str,
+        function: &str,
+        args: &BTreeMap<String, Value>,
+    ) -> InterpreterOutput {
+        let key = (interface.to_string(), function.to_string());
+        let Some(entry) = self.compiled.interface_entries.get(&key).copied() else {
+            return InterpreterOutput::default();
+        };
+        if self.call_depth >= Self::MAX_CALL_DEPTH {
+            return InterpreterOutput::default();
+        }
+
+        self.begin_entry();
+
+        if let Some(node) = self.compiled.nodes.get(&entry) {
+            for (name, (pin, direction, ty)) in node.pins.iter() {
+                if *direction != PinDirection::Output || *ty == DataType::Exec {
+                    continue;
+                }
+                if let Some(value) = args.get(name).filter(|v| v.data_type() == *ty) {
+                    self.pin_values.insert(*pin, value.clone());
+                }
+            }
+        }
+
+        let started = self.start_timer();
+        self.call_depth += 1;
+        let out = self.run_from_exec_out(host, entry, "then");
+        self.call_depth -= 1;
+        self.record_event(&format!("{interface}::{function}"), started);
+        out
+    }
+
+    /// Whether the graph has an event node for the event of the named widget.
+    pub fn handles_widget_event(&self, widget: &str, event: WidgetEvent) -> bool {
+        self.compiled
+            .widget_entries
+            .contains_key(&(widget.to_string(), event))
+    }
+
+    /// Runs every event node of the named widget's event. The value is passed to the `value`
+    /// output of the nodes; values of a wrong type are ignored.
+    pub fn dispatch_widget_event(
+        &mut self,
+        host: &dyn BlueprintHost,
+        widget: &str,
+        event: WidgetEvent,
+        value: Option<Value>,
+    ) -> InterpreterOutput {
+        let mut out = InterpreterOutput::default();
+        let key = (widget.to_string(), event);
+        let Some(entries) = self.compiled.widget_entries.get(&key).cloned() else {
+            return out;
+        };
+
+        let started = self.start_timer();
+        for entry in entries {
+            self.begin_entry();
+            let value_pin = self
+                .compiled
+                .nodes
+                .get(&entry)
+                .and_then(|node| node.pin("value"));
+            if let (Some((pin, _, ty)), Some(value)) = (value_pin, value.as_ref()) {
+                if value.data_type() == ty {
+                    self.pin_values.insert(pin, value.clone());
+                }
+            }
+            let nested = self.run_from_exec_out(host, entry, "then");
+            out.events.extend(nested.events);
+        }
+        self.record_event(&format!("{widget}.{}", event.name()), started);
+        out.variables = self.variables.clone();
+        out
+    }
+
+    /// Whether the graph has an `OnAnimationSignal` node for the signal.
+    pub fn handles_animation_signal(&self, signal: &str) -> bool {
+        self.compiled.animation_signal_entries.contains_key(signal)
+    }
+
+    /// Runs every `OnAnimationSignal` node of the signal. `source` is the name of the animation or
+    /// the state that raised the signal.
+    pub fn dispatch_animation_signal(
+        &mut self,
+        host: &dyn BlueprintHost,
+        signal: &str,
+        source: &str,
+    ) -> InterpreterOutput {
+        let mut out = InterpreterOutput::default();
+        let Some(entries) = self.compiled.animation_signal_entries.get(signal).cloned() else {
+            return out;
+        };
+
+        let started = self.start_timer();
+        for entry in entries {
+            self.begin_entry();
+            let source_pin = self
+                .compiled
+                .nodes
+                .get(&entry)
+                .and_then(|node| node.pin("source"));
+            if let Some((pin, _, _)) = source_pin {
+                self.pin_values
+                    .insert(pin, Value::String(source.to_string()));
+            }
+            let nested = self.run_from_exec_out(host, entry, "then");
+            out.events.extend(nested.events);
+        }
+        self.record_event(signal, started);
+        out.variables = self.variables.clone();
+        out
+    }
+
+    /// Whether the graph has an `OnSoundFinished` node for the sound.
+    pub fn handles_sound_finished(&self, sound: &str) -> bool {
+        self.compiled.sound_finished_entries.contains_key(sound)
+    }
+
+    /// Runs every `OnSoundFinished` node of the sound.
+    pub fn dispatch_sound_finished(
+        &mut self,
+        host: &dyn BlueprintHost,
+        sound: &str,
+    ) -> InterpreterOutput {
+        let mut out = InterpreterOutput::default();
+        let Some(entries) = self.compiled.sound_finished_entries.get(sound).cloned() else {
+            return out;
+        };
+
+        let started = self.start_timer();
+        for entry in entries {
+            self.begin_entry();
+            let nested = self.run_from_exec_out(host, entry, "then");
+            out.events.extend(nested.events);
         }
+        self.record_event(&format!("{sound}.Finished"), started);
+        out.variables = self.variables.clone();
+        out
     }
 
-    fn run_entry(&mut self, entry: Option<NodeId>) -> InterpreterOutput {
+    /// Whether the graph has a `BehaviorTask` node with the name.
+    pub fn handles_behavior_task(&self, task: &str) -> bool {
+        self.compiled.behavior_task_entries.contains_key(task)
+    }
+
+    /// Runs the function graph of the behavior tree task with the name. The task succeeds or fails
+    /// once the graph reaches a `FinishTask` node and keeps running otherwise. Unknown tasks fail.
+    pub fn run_behavior_task(
+        &mut self,
+        host: &dyn BlueprintHost,
+        task: &str,
+    ) -> (InterpreterOutput, TaskStatus) {
+        let Some(entry) = self.compiled.behavior_task_entries.get(task).copied() else {
+            return (InterpreterOutput::default(), TaskStatus::Failure);
+        };
+
+        let started = self.start_timer();
+        self.task_status = None;
+        self.begin_entry();
+        let mut out = self.run_from_exec_out(host, entry, "then");
+        let status = self.task_status.take().unwrap_or(TaskStatus::Running);
+        self.record_event(task, started);
+        out.variables = self.variables.clone();
+        (out, status)
+    }
+
+    pub(crate) fn finish_task(&mut self, status: TaskStatus) {
+        self.task_status = Some(status);
+    }
+
+    /// Whether the graph has a `CustomEvent` node with the name.
+    pub fn handles_custom_event(&self, event: &str) -> bool {
+        self.compiled.custom_event_entries.contains_key(event)
+    }
+
+    /// Runs every `CustomEvent` node with the name. Hosts call it when the timer of the event
+    /// fires.
+    pub fn dispatch_custom_event(
+        &mut self,
+        host: &dyn BlueprintHost,
+        event: &str,
+    ) -> InterpreterOutput {
+        let mut out = InterpreterOutput::default();
+        let Some(entries) = self.compiled.custom_event_entries.get(event).cloned() else {
+            return out;
+        };
+
+        let started = self.start_timer();
+        for entry in entries {
+            self.begin_entry();
+            let nested = self.run_from_exec_out(host, entry, "then");
+            out.events.extend(nested.events);
+        }
+        self.record_event(event, started);
+        out.variables = self.variables.clone();
+        out
+    }
+
+    /// Runs the `OnSceneLoaded` node of a level blueprint. `scene` is the path of the scene.
+    pub fn run_scene_loaded(&mut self, host: &dyn BlueprintHost, scene: &str) -> InterpreterOutput {
+        let entry = self.compiled.scene_loaded_entry;
+        self.run_scene_event(host, entry, scene, "OnSceneLoaded")
+    }
+
+    /// Runs the `OnSceneUnloaded` node of a level blueprint. `scene` is the path of the scene.
+    pub fn run_scene_unloaded(
+        &mut self,
+        host: &dyn BlueprintHost,
+        scene: &str,
+    ) -> InterpreterOutput {
+        let entry = self.compiled.scene_unloaded_entry;
+        self.run_scene_event(host, entry, scene, "OnSceneUnloaded")
+    }
+
+    fn run_scene_event(
+        &mut self,
+        host: &dyn BlueprintHost,
+        entry: Option<NodeId>,
+        scene: &str,
+        event: &str,
+    ) -> InterpreterOutput {
+        self.begin_entry();
+        let scene_pin = entry
+            .and_then(|entry| self.compiled.nodes.get(&entry))
+            .and_then(|node| node.pin("scene"));
+        if let Some((pin, _, _)) = scene_pin {
+            self.pin_values.insert(pin, Value::String(scene.to_string()));
+        }
+
+        let started = self.start_timer();
+        let out = self.run_entry(host, entry);
+        self.record_event(event, started);
+        out
+    }
+
+    /// Drops the pin values of the previous execution, unless the entry is run from a node of an
+    /// execution in progress (a nested interface call), which still needs its values.
+    fn begin_entry(&mut self) {
+        if self.exec_depth == 0 {
+            self.pin_values.clear();
+        }
+    }
+
+    fn run_entry(&mut self, host: &dyn BlueprintHost, entry: Option<NodeId>) -> InterpreterOutput {
         let Some(entry_node) = entry else {
             return InterpreterOutput::default();
         };
         // Entry nodes start execution from their "then" pin.
-        self.run_from_exec_out(entry_node, "then")
+        self.run_from_exec_out(host, entry_node, "then")
     }
 
-    fn run_from_exec_out(&mut self, start_node: NodeId, exec_out_pin: &str) -> InterpreterOutput {
+    pub(crate) fn run_from_exec_out(
+        &mut self,
+        host: &dyn BlueprintHost,
+        start_node: NodeId,
+        exec_out_pin: &str,
+    ) -> InterpreterOutput {
         let mut out = InterpreterOutput::default();
 
         let Some(start) = self.compiled.nodes.get(&start_node) else {
@@ -106,6 +680,7 @@ impl Interpreter {
             return out;
         }
 
+        self.exec_depth += 1;
         let mut next_exec_in_pin = self.compiled.exec_edges.get(&out_pin_id).copied();
         while let Some(exec_in_pin) = next_exec_in_pin {
             let Some(node_id) = self.pin_owner(exec_in_pin) else {
@@ -118,8 +693,22 @@ impl Interpreter {
                 break;
             };
 
-            next_exec_in_pin = runtime_for(node.kind).execute(self, &mut out, node_id, &node);
+            let started = self.start_timer();
+            let profiled_before = self.profiled_time;
+            next_exec_in_pin =
+                runtime_for(node.kind).execute(self, host, &mut out, node_id, &node);
+            if let (Some(profile), Some(started)) = (self.profile.as_mut(), started) {
+                let elapsed = started.elapsed();
+                let nested = self.profiled_time.saturating_sub(profiled_before);
+                profile
+                    .nodes
+                    .entry(node_id)
+                    .or_default()
+                    .add(elapsed.saturating_sub(nested));
+                self.profiled_time = profiled_before + elapsed;
+            }
         }
+        self.exec_depth -= 1;
 
         out.variables = self.variables.clone();
         out
@@ -133,6 +722,7 @@ impl Interpreter {
         // Shared buffers for Rhai callbacks.
         let emitted = Arc::new(Mutex::new(Vec::<ExecutionEvent>::new()));
         let vars = Arc::new(Mutex::new(self.variables.clone()));
+        let types = Arc::new(self.compiled.types.clone());
 
         let mut engine = Engine::new();
 
@@ -160,11 +750,23 @@ impl Interpreter {
         }
         {
             let vars = vars.clone();
+            let types = types.clone();
             engine.register_fn("set_var", move |name: &str, value: Dynamic| {
-                let Some(v) = dynamic_to_value(&value) else {
+                let Ok(mut vars) = vars.lock() else {
                     return;
                 };
-                if let Ok(mut vars) = vars.lock() {
+                // Containers, structs and enums keep their type; Rhai arrays and maps carry no
+                // element types.
+                let v = match vars.get(name) {
+                    Some(current)
+                        if current.data_type().is_container()
+                            || matches!(current, Value::Struct(..) | Value::Enum(..)) =>
+                    {
+                        dynamic_like(&value, current, &types)
+                    }
+                    _ => dynamic_to_value(&value),
+                };
+                if let Some(v) = v {
                     vars.insert(name.to_string(), v);
                 }
             });
@@ -182,7 +784,11 @@ impl Interpreter {
             });
         }
 
+        let started = self.start_timer();
         let result: Result<Dynamic, Box<EvalAltResult>> = engine.eval(code);
+        if let (Some(profile), Some(started)) = (self.profile.as_mut(), started) {
+            profile.rhai.add(started.elapsed());
+        }
 
         if let Ok(vars_guard) = vars.lock() {
             self.variables = vars_guard.clone();
@@ -199,6 +805,30 @@ impl Interpreter {
         self.variables.insert(name, value);
     }
 
+    pub(crate) fn variable_mut(&mut self, name: &str) -> Option<&mut Value> {
+        self.variables.get_mut(name)
+    }
+
+    pub(crate) fn set_pin_value(&mut self, pin: PinId, value: Value) {
+        self.pin_values.insert(pin, value);
+    }
+
+    /// Name of the variable read by the `GetVariable` node connected to the given input. Nodes
+    /// that modify containers use it to update the variable in place.
+    pub(crate) fn input_variable(&self, node_id: NodeId, input_name: &str) -> Option<String> {
+        let (input_pin, _, _) = self.compiled.nodes.get(&node_id)?.pin(input_name)?;
+        let from_pin = *self.compiled.data_edges.get(&input_pin)?;
+        let from_node = self.compiled.nodes.get(&self.pin_owner(from_pin)?)?;
+        if from_node.kind != BuiltinNodeKind::GetVariable {
+            return None;
+        }
+        from_node.property_string("name").map(str::to_string)
+    }
+
+    pub(crate) fn types(&self) -> &UserTypes {
+        &self.compiled.types
+    }
+
     pub(crate) fn input_pin_type(&self, node_id: NodeId, input_name: &str) -> Option<DataType> {
         let node = self.compiled.nodes.get(&node_id)?;
         let (_, _, ty) = node.pin(input_name)?;
@@ -231,21 +861,53 @@ impl Interpreter {
         self.compiled.exec_edges.get(&out_pin).copied()
     }
 
-    pub(crate) fn read_string_input(&self, node_id: NodeId, input_name: &str) -> Option<String> {
-        match self.read_value_input(node_id, input_name)? {
+    pub(crate) fn read_string_input(
+        &self,
+        host: &dyn BlueprintHost,
+        node_id: NodeId,
+        input_name: &str,
+    ) -> Option<String> {
+        match self.read_value_input(host, node_id, input_name)? {
             Value::String(s) => Some(s),
             _ => None,
         }
     }
 
-    pub(crate) fn read_bool_input(&self, node_id: NodeId, input_name: &str) -> Option<bool> {
-        match self.read_value_input(node_id, input_name)? {
+    pub(crate) fn read_bool_input(
+        &self,
+        host: &dyn BlueprintHost,
+        node_id: NodeId,
+        input_name: &str,
+    ) -> Option<bool> {
+        match self.read_value_input(host, node_id, input_name)? {
             Value::Bool(b) => Some(b),
             _ => None,
         }
     }
 
-    pub(crate) fn read_value_input(&self, node_id: NodeId, input_name: &str) -> Option<Value> {
+    /// Reads a linked input, falling back to the literal property of the same name.
+    pub(crate) fn read_input_or_property(
+        &self,
+        host: &dyn BlueprintHost,
+        node_id: NodeId,
+        node: &CompiledNode,
+        input_name: &str,
+    ) -> Option<Value> {
+        self.read_value_input(host, node_id, input_name).or_else(|| {
+            let expected_ty = self.input_pin_type(node_id, input_name)?;
+            node.properties
+                .get(input_name)
+                .filter(|v| v.data_type() == expected_ty)
+                .cloned()
+        })
+    }
+
+    pub(crate) fn read_value_input(
+        &self,
+        host: &dyn BlueprintHost,
+        node_id: NodeId,
+        input_name: &str,
+    ) -> Option<Value> {
         let node = self.compiled.nodes.get(&node_id)?;
         let (input_pin, _, expected_ty) = node.pin(input_name)?;
 
@@ -265,7 +927,20 @@ impl Interpreter {
                     })?;
                 self.variables.get(&name).cloned()?
             }
-            _ => return None,
+            _ => match self.pin_values.get(&from_pin) {
+                Some(value) => value.clone(),
+                // Pure nodes are evaluated on demand.
+                None => {
+                    let output = from_compiled.pin_name(from_pin)?;
+                    runtime_for(from_compiled.kind).evaluate(
+                        self,
+                        host,
+                        from_node,
+                        from_compiled,
+                        output,
+                    )?
+                }
+            },
         };
 
         if value.data_type() != expected_ty {
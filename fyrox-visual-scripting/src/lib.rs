@@ -1,21 +1,44 @@
 #![forbid(unsafe_code)]
 
+pub mod animation;
+pub mod behavior;
+pub mod codegen;
 pub mod compile;
 pub mod error;
+pub mod host;
 pub mod interpret;
 pub mod model;
 pub mod nodes;
+pub mod profile;
 mod runtime;
+pub mod sound;
+pub mod tags;
+pub mod timers;
+pub mod types;
+pub mod widgets;
 
 pub use crate::{
+    animation::{AbsmParameterKind, AnimationCommand},
+    behavior::{BehaviorGraph, BehaviorNodeKind, BlackboardCondition, ConditionOp, TaskStatus},
+    codegen::{generated_type_uuid, nativize},
     compile::{compile, CompiledGraph},
-    error::{CompileError, ValidationError},
-    interpret::{ExecutionEvent, Interpreter, InterpreterOutput},
+    error::{CompileError, NativizeError, ValidationError},
+    host::{BlueprintHost, NullHost},
+    interpret::{
+        AssertionResult, ExecutionEvent, InterfaceCall, Interpreter, InterpreterOutput,
+    },
     model::{
-        BlueprintGraph, BuiltinNodeKind, DataType, GraphDef, GraphId, GraphKind, Link, Node, NodeId,
-        Pin, PinDirection, PinId, Value,
+        BlueprintGraph, BlueprintInterface, BuiltinNodeKind, DataType, ElementType, GraphDef, GraphId,
+        GraphKind, InterfaceFunction, Link, Node, NodeId, ParamDef, Pin, PinDirection, PinId,
+        UserTypeId, Value,
     },
     nodes::{NodeCategory, NodeDefinition, all_node_definitions, get_node_definition, pin_color_for_type},
+    profile::{profiles_from_csv, profiles_to_csv, BlueprintProfile, ProfileSample},
+    sound::{SoundCommand, SoundProperty},
+    tags::TagCommand,
+    timers::TimerCommand,
+    types::{EnumDef, StructDef, UserTypes},
+    widgets::{WidgetCommand, WidgetEvent, WidgetProperty},
 };
 
 #[cfg(test)]
@@ -121,7 +144,7 @@ mod tests {
         // Connect data flow: GetVariable.value -> Print.text
         let var_value_out = graph.nodes.get(&get_var_id).unwrap().pin_named("value").unwrap();
         let print_text_in = graph.nodes.get(&print).unwrap().pin_named("text").unwrap();
-        graph.add_link(Link::exec(var_value_out, print_text_in));
+        graph.add_link(Link::data(var_value_out, print_text_in));
 
         // This should now compile successfully with dynamic typing
         let compiled = compile(&graph).expect("compile should succeed with dynamic variable typing");
@@ -162,4 +185,1320 @@ mod tests {
             .iter()
             .any(|e| matches!(e, ExecutionEvent::Print(s) if s == "Hello from Rhai")));
     }
+
+    #[test]
+    fn reload_migrates_variables_by_name_and_type() {
+        use model::VariableDef;
+
+        fn variable(name: &str, value: Value) -> VariableDef {
+            VariableDef {
+                name: name.to_string(),
+                data_type: value.data_type(),
+                default_value: Some(value),
+            }
+        }
+
+        let mut graph = BlueprintGraph::new(GraphId("test".to_string()));
+        graph.variables.push(variable("health", Value::F32(100.0)));
+        graph.variables.push(variable("ammo", Value::I32(10)));
+        graph.variables.push(variable("label", Value::String("a".to_string())));
+
+        let begin_play = graph.add_node(Node::new(BuiltinNodeKind::BeginPlay));
+        let mut script = Node::new(BuiltinNodeKind::RhaiScript);
+        script.set_property_string(
+            "code",
+            "set_var(\"health\", 25.0); set_var(\"ammo\", 3); set_var(\"label\", \"b\");"
+                .to_string(),
+        );
+        let script = graph.add_node(script);
+        let begin_then = graph.nodes[&begin_play].pin_named("then").unwrap();
+        let script_exec = graph.nodes[&script].pin_named("exec").unwrap();
+        graph.add_link(Link::exec(begin_then, script_exec));
+
+        let mut old = Interpreter::new(compile(&graph).expect("compile"));
+        old.run_begin_play();
+
+        // Edited graph: `ammo` changed its type, `label` was removed, `shield` was added.
+        graph.variables = vec![
+            variable("health", Value::F32(100.0)),
+            variable("ammo", Value::String(String::new())),
+            variable("shield", Value::I32(5)),
+        ];
+        let mut new = Interpreter::new(compile(&graph).expect("compile"));
+
+        assert_eq!(new.migrate_variables(&old), 1);
+        assert_eq!(new.variables()["health"], Value::F32(25.0));
+        assert_eq!(new.variables()["ammo"], Value::String(String::new()));
+        assert_eq!(new.variables()["shield"], Value::I32(5));
+        assert!(!new.variables().contains_key("label"));
+    }
+
+    #[test]
+    fn assertions_report_pass_and_failure() {
+        use model::VariableDef;
+
+        let mut graph = BlueprintGraph::new(GraphId("test".to_string()));
+        graph.variables.push(VariableDef {
+            name: "count".to_string(),
+            data_type: DataType::I32,
+            default_value: Some(Value::I32(2)),
+        });
+
+        let begin_play = graph.add_node(Node::new(BuiltinNodeKind::BeginPlay));
+        let mut assert = Node::new(BuiltinNodeKind::Assert);
+        assert.set_property_bool("condition", true);
+        let assert = graph.add_node(assert);
+        let mut expect = Node::expect_equal(DataType::I32);
+        expect.set_property_i32("expected", 3);
+        expect.set_property_string("message", "count is three".to_string());
+        let expect = graph.add_node(expect);
+        // Nothing is compared here, it must not pass as `nothing == nothing`.
+        let expect_nothing = graph.add_node(Node::expect_equal(DataType::I32));
+        let mut get_count = Node::new(BuiltinNodeKind::GetVariable);
+        get_count.set_property_string("name", "count".to_string());
+        let get_count = graph.add_node(get_count);
+
+        let begin_then = graph.nodes[&begin_play].pin_named("then").unwrap();
+        let assert_exec = graph.nodes[&assert].pin_named("exec").unwrap();
+        let assert_then = graph.nodes[&assert].pin_named("then").unwrap();
+        let expect_exec = graph.nodes[&expect].pin_named("exec").unwrap();
+        let expect_then = graph.nodes[&expect].pin_named("then").unwrap();
+        let expect_actual = graph.nodes[&expect].pin_named("actual").unwrap();
+        let expect_nothing_exec = graph.nodes[&expect_nothing].pin_named("exec").unwrap();
+        let count_value = graph.nodes[&get_count].pin_named("value").unwrap();
+        graph.add_link(Link::exec(begin_then, assert_exec));
+        graph.add_link(Link::exec(assert_then, expect_exec));
+        graph.add_link(Link::exec(expect_then, expect_nothing_exec));
+        graph.add_link(Link::data(count_value, expect_actual));
+
+        let mut interpreter = Interpreter::new(compile(&graph).expect("compile"));
+        let results = interpreter
+            .run_begin_play()
+            .events
+            .into_iter()
+            .filter_map(|e| match e {
+                ExecutionEvent::Assertion(result) => Some(result),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].passed());
+        assert_eq!(results[0].name, format!("Assert #{}", assert.0));
+        assert_eq!(results[1].name, "count is three");
+        assert_eq!(results[1].failure.as_deref(), Some("expected 3, got 2"));
+        assert_eq!(
+            results[2].failure.as_deref(),
+            Some("the expected value is missing")
+        );
+    }
+
+    fn interactable() -> BlueprintInterface {
+        BlueprintInterface {
+            name: "Interactable".to_string(),
+            functions: vec![InterfaceFunction {
+                name: "Interact".to_string(),
+                inputs: vec![ParamDef {
+                    name: "who".to_string(),
+                    data_type: DataType::String,
+                }],
+            }],
+        }
+    }
+
+    #[test]
+    fn interface_call_runs_implementation() {
+        let mut graph = BlueprintGraph::new(GraphId("door".to_string()));
+        graph.implement_interface(interactable());
+
+        let entry = graph
+            .nodes
+            .iter()
+            .find_map(|(id, n)| (n.kind == BuiltinNodeKind::InterfaceEvent).then_some(*id))
+            .unwrap();
+        let graph_name = graph.nodes[&entry].graph.clone();
+
+        let mut print = Node::new(BuiltinNodeKind::Print);
+        print.graph = graph_name;
+        let print = graph.add_node(print);
+
+        let entry_then = graph.nodes[&entry].pin_named("then").unwrap();
+        let entry_who = graph.nodes[&entry].pin_named("who").unwrap();
+        let print_exec = graph.nodes[&print].pin_named("exec").unwrap();
+        let print_text = graph.nodes[&print].pin_named("text").unwrap();
+        graph.add_link(Link::exec(entry_then, print_exec));
+        graph.add_link(Link::data(entry_who, print_text));
+
+        let compiled = compile(&graph).expect("compile");
+        let mut interpreter = Interpreter::new(compiled);
+        assert!(interpreter.implements_interface("Interactable"));
+
+        let args = [("who".to_string(), Value::String("Player".to_string()))].into();
+        let out = interpreter.call_interface("Interactable", "Interact", &args);
+        assert!(out
+            .events
+            .iter()
+            .any(|e| matches!(e, ExecutionEvent::Print(s) if s == "Player")));
+
+        // Unknown functions do nothing.
+        let out = interpreter.call_interface("Interactable", "Open", &args);
+        assert!(out.events.is_empty());
+
+        // Arguments of the previous call are not reused.
+        let out = interpreter.call_interface("Interactable", "Interact", &Default::default());
+        assert!(!out
+            .events
+            .iter()
+            .any(|e| matches!(e, ExecutionEvent::Print(s) if s == "Player")));
+    }
+
+    #[test]
+    fn interface_event_requires_declared_function() {
+        let mut graph = BlueprintGraph::new(GraphId("test".to_string()));
+        let function = InterfaceFunction {
+            name: "Interact".to_string(),
+            inputs: Vec::new(),
+        };
+        graph.add_node(Node::interface_event("Interactable", &function));
+
+        let err = compile(&graph).unwrap_err();
+        assert_eq!(err.kind, ValidationError::UnknownInterfaceFunction);
+    }
+
+    struct DoorHost;
+
+    impl BlueprintHost for DoorHost {
+        fn does_implement_interface(&self, target: &str, interface: &str) -> bool {
+            target == "Door" && interface == "Interactable"
+        }
+    }
+
+    #[test]
+    fn interface_message_is_sent_to_implementing_actor() {
+        let interface = interactable();
+        let mut graph = BlueprintGraph::new(GraphId("player".to_string()));
+
+        let begin_play = graph.add_node(Node::new(BuiltinNodeKind::BeginPlay));
+        let branch = graph.add_node(Node::new(BuiltinNodeKind::Branch));
+
+        let mut check = Node::new(BuiltinNodeKind::DoesImplementInterface);
+        check.set_property_string("interface", interface.name.clone());
+        check.set_property_string("target", "Door".to_string());
+        let check = graph.add_node(check);
+
+        let mut call = Node::interface_call(&interface.name, &interface.functions[0]);
+        call.set_property_string("target", "Door".to_string());
+        call.set_property_string("who", "Player".to_string());
+        let call = graph.add_node(call);
+
+        let begin_then = graph.nodes[&begin_play].pin_named("then").unwrap();
+        let branch_exec = graph.nodes[&branch].pin_named("exec").unwrap();
+        let branch_condition = graph.nodes[&branch].pin_named("condition").unwrap();
+        let branch_true = graph.nodes[&branch].pin_named("true").unwrap();
+        let check_result = graph.nodes[&check].pin_named("result").unwrap();
+        let call_exec = graph.nodes[&call].pin_named("exec").unwrap();
+        graph.add_link(Link::exec(begin_then, branch_exec));
+        graph.add_link(Link::data(check_result, branch_condition));
+        graph.add_link(Link::exec(branch_true, call_exec));
+
+        let compiled = compile(&graph).expect("compile");
+
+        let mut interpreter = Interpreter::new(compiled.clone());
+        let out = interpreter.run_begin_play_with_host(&DoorHost);
+        let expected = InterfaceCall {
+            target: "Door".to_string(),
+            interface: "Interactable".to_string(),
+            function: "Interact".to_string(),
+            args: [("who".to_string(), Value::String("Player".to_string()))].into(),
+        };
+        assert!(out
+            .events
+            .iter()
+            .any(|e| *e == ExecutionEvent::InterfaceMessage(expected.clone())));
+
+        // Without a host that knows the door, the branch is not taken.
+        let mut interpreter = Interpreter::new(compiled);
+        let out = interpreter.run_begin_play();
+        assert!(!out
+            .events
+            .iter()
+            .any(|e| matches!(e, ExecutionEvent::InterfaceMessage(_))));
+
+        // Messages to other actors have no native counterpart.
+        let mut graph = BlueprintGraph::new(GraphId("player".to_string()));
+        let begin_play = graph.add_node(Node::new(BuiltinNodeKind::BeginPlay));
+        let mut call = Node::interface_call(&interface.name, &interface.functions[0]);
+        call.set_property_string("target", "Door".to_string());
+        let call = graph.add_node(call);
+        let begin_then = graph.nodes[&begin_play].pin_named("then").unwrap();
+        let call_exec = graph.nodes[&call].pin_named("exec").unwrap();
+        graph.add_link(Link::exec(begin_then, call_exec));
+        assert_eq!(
+            nativize(&graph, "Player", "00000000-0000-0000-0000-000000000000"),
+            Err(NativizeError::UnsupportedNode(
+                BuiltinNodeKind::CallInterface,
+                call
+            ))
+        );
+    }
+
+    #[test]
+    fn nativize_emits_script_with_fields_and_branches() {
+        use model::VariableDef;
+
+        let mut graph = BlueprintGraph::new(GraphId("test".to_string()));
+        graph.variables.push(VariableDef {
+            name: "Is Open".to_string(),
+            data_type: DataType::Bool,
+            default_value: Some(Value::Bool(true)),
+        });
+
+        let begin_play = graph.add_node(Node::new(BuiltinNodeKind::BeginPlay));
+        let mut get_var = Node::new(BuiltinNodeKind::GetVariable);
+        get_var.set_property_string("name", "Is Open".to_string());
+        let get_var = graph.add_node(get_var);
+        let branch = graph.add_node(Node::new(BuiltinNodeKind::Branch));
+        let mut print = Node::new(BuiltinNodeKind::Print);
+        print.set_property_string("text", "Open".to_string());
+        let print = graph.add_node(print);
+
+        let pin = |graph: &BlueprintGraph, node: NodeId, name: &str| {
+            graph.nodes[&node].pin_named(name).unwrap()
+        };
+        let links = [
+            (pin(&graph, begin_play, "then"), pin(&graph, branch, "exec")),
+            (pin(&graph, branch, "true"), pin(&graph, print, "exec")),
+        ];
+        for (from, to) in links {
+            graph.add_link(Link::exec(from, to));
+        }
+        graph.add_link(Link::data(
+            pin(&graph, get_var, "value"),
+            pin(&graph, branch, "condition"),
+        ));
+
+        let source = nativize(&graph, "door", "6b0bdbd4-21f4-4b4e-9a66-7c3f0d6f7a11").unwrap();
+
+        assert!(source.contains("pub struct Door {"));
+        assert!(source.contains("pub is_open: bool,"));
+        assert!(source.contains("is_open: true,"));
+        assert!(source.contains("if self.is_open {"));
+        assert!(source.contains(r#"Log::info(format!("[Blueprint] {}", "Open".to_string()));"#));
+        assert!(source.contains("self.begin_play(ctx);"));
+        assert!(!source.contains("fn on_update"));
+        assert!(source.contains("pub fn register(context: &PluginRegistrationContext) {"));
+        assert!(source.contains(r#".add::<Door>("Door");"#));
+        assert_eq!(
+            generated_type_uuid(&source),
+            Some("6b0bdbd4-21f4-4b4e-9a66-7c3f0d6f7a11")
+        );
+    }
+
+    #[test]
+    fn profiling_counts_nodes_and_events() {
+        let mut graph = BlueprintGraph::new(GraphId("test".to_string()));
+        let tick = graph.add_node(Node::new(BuiltinNodeKind::Tick));
+        let mut script = Node::new(BuiltinNodeKind::RhaiScript);
+        script.set_property_string("code", "let x = 1;".to_string());
+        let script = graph.add_node(script);
+        let tick_then = graph.nodes[&tick].pin_named("then").unwrap();
+        let script_exec = graph.nodes[&script].pin_named("exec").unwrap();
+        graph.add_link(Link::exec(tick_then, script_exec));
+
+        let mut interpreter = Interpreter::new(compile(&graph).unwrap());
+        interpreter.tick(0.1);
+        assert!(interpreter.take_profile().is_none());
+
+        interpreter.set_profiling(true);
+        interpreter.tick(0.1);
+        interpreter.tick(0.1);
+        let profile = interpreter.take_profile().unwrap();
+        assert_eq!(profile.nodes[&script].count, 2);
+        assert_eq!(profile.events["Tick"].count, 2);
+        assert_eq!(profile.rhai.count, 2);
+        assert_eq!(profile.heat(script), 1.0);
+
+        // Taking the profile starts over.
+        assert!(interpreter.take_profile().unwrap().is_empty());
+    }
+
+    #[test]
+    fn profiling_records_self_time_of_nodes() {
+        let interface = interactable();
+        let mut graph = BlueprintGraph::new(GraphId("door".to_string()));
+        graph.implement_interface(interface.clone());
+        let entry = graph
+            .nodes
+            .iter()
+            .find_map(|(id, n)| (n.kind == BuiltinNodeKind::InterfaceEvent).then_some(*id))
+            .unwrap();
+
+        let mut script = Node::new(BuiltinNodeKind::RhaiScript);
+        script.graph = graph.nodes[&entry].graph.clone();
+        script.set_property_string(
+            "code",
+            "let x = 0; for i in 0..20000 { x += i; }".to_string(),
+        );
+        let script = graph.add_node(script);
+        let tick = graph.add_node(Node::new(BuiltinNodeKind::Tick));
+        let call = graph.add_node(Node::interface_call(
+            &interface.name,
+            &interface.functions[0],
+        ));
+
+        let entry_then = graph.nodes[&entry].pin_named("then").unwrap();
+        let script_exec = graph.nodes[&script].pin_named("exec").unwrap();
+        let tick_then = graph.nodes[&tick].pin_named("then").unwrap();
+        let call_exec = graph.nodes[&call].pin_named("exec").unwrap();
+        graph.add_link(Link::exec(entry_then, script_exec));
+        graph.add_link(Link::exec(tick_then, call_exec));
+
+        let mut interpreter = Interpreter::new(compile(&graph).unwrap());
+        interpreter.set_profiling(true);
+        interpreter.tick(0.1);
+        let profile = interpreter.take_profile().unwrap();
+
+        // The script runs inside the call, its time is not counted twice.
+        assert_eq!(profile.nodes[&call].count, 1);
+        assert_eq!(profile.nodes[&script].count, 1);
+        assert!(profile.nodes[&call].total < profile.nodes[&script].total);
+        assert!(profile.events["Tick"].total >= profile.nodes[&script].total);
+    }
+
+    #[test]
+    fn profiles_round_trip_through_csv() {
+        let mut profile = BlueprintProfile::default();
+        profile.nodes.insert(
+            NodeId(3),
+            ProfileSample {
+                count: 10,
+                total: std::time::Duration::from_micros(250),
+            },
+        );
+        profile.events.insert(
+            "Damageable::TakeDamage".to_string(),
+            ProfileSample {
+                count: 2,
+                total: std::time::Duration::from_micros(40),
+            },
+        );
+
+        let csv = profiles_to_csv([("data/door, big.blueprint", &profile)]);
+        assert!(csv.starts_with(BlueprintProfile::CSV_HEADER));
+        assert!(csv.contains("\"data/door, big.blueprint\",node,3,10,250,25"));
+
+        let parsed = profiles_from_csv(&csv);
+        assert_eq!(parsed["data/door, big.blueprint"], profile);
+    }
+
+    fn container_variable(graph: &mut BlueprintGraph, name: &str, value: Value) -> NodeId {
+        graph.variables.push(model::VariableDef {
+            name: name.to_string(),
+            data_type: value.data_type(),
+            default_value: Some(value),
+        });
+        let mut get = Node::new(BuiltinNodeKind::GetVariable);
+        get.set_property_string("name", name.to_string());
+        graph.add_node(get)
+    }
+
+    fn link(graph: &mut BlueprintGraph, from: (NodeId, &str), to: (NodeId, &str)) {
+        let from = graph.nodes[&from.0].pin_named(from.1).unwrap();
+        let to = graph.nodes[&to.0].pin_named(to.1).unwrap();
+        let link = if graph.pin(from).unwrap().data_type == DataType::Exec {
+            Link::exec(from, to)
+        } else {
+            Link::data(from, to)
+        };
+        graph.add_link(link);
+    }
+
+    #[test]
+    fn for_each_runs_loop_body_per_element() {
+        let names = DataType::Array(ElementType::String);
+        let mut graph = BlueprintGraph::new(GraphId("test".to_string()));
+        let get = container_variable(
+            &mut graph,
+            "names",
+            Value::Array(
+                ElementType::String,
+                vec![Value::String("a".to_string()), Value::String("b".to_string())],
+            ),
+        );
+        let begin_play = graph.add_node(Node::new(BuiltinNodeKind::BeginPlay));
+        let for_each = graph.add_node(Node::container(BuiltinNodeKind::ForEach, names));
+        let print = graph.add_node(Node::new(BuiltinNodeKind::Print));
+        let mut done = Node::new(BuiltinNodeKind::Print);
+        done.set_property_string("text", "done".to_string());
+        let done = graph.add_node(done);
+
+        link(&mut graph, (begin_play, "then"), (for_each, "exec"));
+        link(&mut graph, (get, "value"), (for_each, "target"));
+        link(&mut graph, (for_each, "loop_body"), (print, "exec"));
+        link(&mut graph, (for_each, "element"), (print, "text"));
+        link(&mut graph, (for_each, "completed"), (done, "exec"));
+
+        let mut interpreter = Interpreter::new(compile(&graph).unwrap());
+        let printed = interpreter
+            .run_begin_play()
+            .events
+            .into_iter()
+            .filter_map(|e| match e {
+                ExecutionEvent::Print(text) => Some(text),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(printed, ["a", "b", "done"]);
+    }
+
+    #[test]
+    fn set_add_keeps_elements_unique() {
+        let tags = DataType::Set(ElementType::String);
+        let mut graph = BlueprintGraph::new(GraphId("test".to_string()));
+        let get = container_variable(&mut graph, "tags", Value::Set(ElementType::String, vec![]));
+        let begin_play = graph.add_node(Node::new(BuiltinNodeKind::BeginPlay));
+
+        let mut previous = (begin_play, "then");
+        for tag in ["red", "blue", "red"] {
+            let mut add = Node::container(BuiltinNodeKind::ContainerAdd, tags);
+            add.set_property_string("value", tag.to_string());
+            let add = graph.add_node(add);
+            link(&mut graph, previous, (add, "exec"));
+            link(&mut graph, (get, "value"), (add, "target"));
+            previous = (add, "then");
+        }
+
+        let mut contains = Node::container(BuiltinNodeKind::ContainerContains, tags);
+        contains.set_property_string("item", "blue".to_string());
+        let contains = graph.add_node(contains);
+        link(&mut graph, (get, "value"), (contains, "target"));
+        let mut assert = Node::new(BuiltinNodeKind::Assert);
+        assert.set_property_string("message", "has blue".to_string());
+        let assert = graph.add_node(assert);
+        link(&mut graph, previous, (assert, "exec"));
+        link(&mut graph, (contains, "result"), (assert, "condition"));
+
+        let mut interpreter = Interpreter::new(compile(&graph).unwrap());
+        let out = interpreter.run_begin_play();
+        assert!(out
+            .events
+            .iter()
+            .any(|e| matches!(e, ExecutionEvent::Assertion(a) if a.failure.is_none())));
+        assert_eq!(
+            interpreter.variables()["tags"],
+            Value::Set(
+                ElementType::String,
+                vec![Value::String("red".to_string()), Value::String("blue".to_string())],
+            )
+        );
+    }
+
+    #[test]
+    fn container_changes_require_variable_target() {
+        let tags = DataType::Set(ElementType::String);
+        let mut graph = BlueprintGraph::new(GraphId("test".to_string()));
+        let get = container_variable(&mut graph, "tags", Value::Set(ElementType::String, vec![]));
+        let clear = graph.add_node(Node::container(BuiltinNodeKind::ContainerClear, tags));
+
+        let err = compile(&graph).unwrap_err();
+        assert_eq!(err.kind, ValidationError::ContainerTargetNotVariable);
+        assert_eq!(err.node, Some(clear));
+
+        link(&mut graph, (get, "value"), (clear, "target"));
+        assert!(compile(&graph).is_ok());
+    }
+
+    #[test]
+    fn rhai_round_trips_maps() {
+        let mut graph = BlueprintGraph::new(GraphId("test".to_string()));
+        container_variable(
+            &mut graph,
+            "scores",
+            Value::Map(
+                ElementType::String,
+                ElementType::I32,
+                vec![(Value::String("bob".to_string()), Value::I32(1))],
+            ),
+        );
+        let begin_play = graph.add_node(Node::new(BuiltinNodeKind::BeginPlay));
+        let mut script = Node::new(BuiltinNodeKind::RhaiScript);
+        script.set_property_string(
+            "code",
+            "let s = get_var(\"scores\"); s.bob += 1; set_var(\"scores\", s);".to_string(),
+        );
+        let script = graph.add_node(script);
+        link(&mut graph, (begin_play, "then"), (script, "exec"));
+
+        let mut interpreter = Interpreter::new(compile(&graph).unwrap());
+        interpreter.run_begin_play();
+        assert_eq!(
+            interpreter.variables()["scores"],
+            Value::Map(
+                ElementType::String,
+                ElementType::I32,
+                vec![(Value::String("bob".to_string()), Value::I32(2))],
+            )
+        );
+    }
+
+    #[test]
+    fn container_defaults_round_trip_through_json() {
+        let ty = DataType::Map(ElementType::I32, ElementType::String);
+        let json = serde_json::from_str(r#"{"2": "b", "1": "a"}"#).unwrap();
+        let value = Value::from_json(&json, ty).unwrap();
+        assert_eq!(value.data_type(), ty);
+        assert_eq!(value.len(), Some(2));
+        assert_eq!(Value::from_json(&value.to_json(), ty), Some(value));
+
+        let set = serde_json::from_str("[1, 2, 1]").unwrap();
+        assert_eq!(
+            Value::from_json(&set, DataType::Set(ElementType::I32)),
+            Some(Value::Set(ElementType::I32, vec![Value::I32(1), Value::I32(2)]))
+        );
+        assert_eq!(Value::from_json(&set, DataType::Array(ElementType::Bool)), None);
+    }
+
+    fn damage_types() -> (StructDef, EnumDef) {
+        let kind = EnumDef {
+            name: "DamageKind".to_string(),
+            variants: vec!["Fire".to_string(), "Ice".to_string()],
+        };
+        let damage = StructDef {
+            name: "Damage".to_string(),
+            fields: vec![
+                ParamDef {
+                    name: "amount".to_string(),
+                    data_type: DataType::F32,
+                },
+                ParamDef {
+                    name: "kind".to_string(),
+                    data_type: kind.data_type(),
+                },
+            ],
+        };
+        (damage, kind)
+    }
+
+    #[test]
+    fn struct_fields_drive_switch_on_enum() {
+        let (damage, kind) = damage_types();
+        let mut graph = BlueprintGraph::new(GraphId("test".to_string()));
+        graph.define_enum(kind.clone());
+        graph.define_struct(damage.clone());
+        let hit = graph
+            .types
+            .value_from_json(&serde_json::json!({ "kind": "Ice" }), damage.data_type())
+            .unwrap();
+        let get = container_variable(&mut graph, "hit", hit);
+
+        let print = |graph: &mut BlueprintGraph, text: &str| {
+            let mut print = Node::new(BuiltinNodeKind::Print);
+            print.set_property_string("text", text.to_string());
+            graph.add_node(print)
+        };
+        let begin_play = graph.add_node(Node::new(BuiltinNodeKind::BeginPlay));
+        let first_break = graph.add_node(Node::break_struct(&damage));
+        let first_switch = graph.add_node(Node::switch_on_enum(&kind));
+        let ice = print(&mut graph, "ice");
+        let make = graph.add_node(Node::make_struct(&damage));
+        let mut set = Node::new(BuiltinNodeKind::SetVariable);
+        set.set_property_string("name", "hit".to_string());
+        let set = graph.add_node(set);
+        let second_break = graph.add_node(Node::break_struct(&damage));
+        let second_switch = graph.add_node(Node::switch_on_enum(&kind));
+        let fire = print(&mut graph, "fire");
+
+        link(&mut graph, (begin_play, "then"), (first_switch, "exec"));
+        link(&mut graph, (get, "value"), (first_break, "struct"));
+        link(&mut graph, (first_break, "kind"), (first_switch, "selection"));
+        link(&mut graph, (first_switch, "Ice"), (ice, "exec"));
+        // The made struct leaves `kind` unconnected, so it defaults to the first variant.
+        link(&mut graph, (ice, "then"), (set, "exec"));
+        link(&mut graph, (make, "struct"), (set, "value"));
+        link(&mut graph, (set, "then"), (second_switch, "exec"));
+        link(&mut graph, (get, "value"), (second_break, "struct"));
+        link(&mut graph, (second_break, "kind"), (second_switch, "selection"));
+        link(&mut graph, (second_switch, "Fire"), (fire, "exec"));
+
+        let mut interpreter = Interpreter::new(compile(&graph).unwrap());
+        let printed = interpreter
+            .run_begin_play()
+            .events
+            .into_iter()
+            .filter_map(|e| match e {
+                ExecutionEvent::Print(text) => Some(text),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(printed, ["ice", "fire"]);
+        assert_eq!(
+            interpreter.variables()["hit"].field("kind"),
+            Some(&Value::Enum(kind.id(), "Fire".to_string()))
+        );
+    }
+
+    #[test]
+    fn redefining_a_struct_migrates_defaults_and_nodes() {
+        let (mut damage, kind) = damage_types();
+        let mut graph = BlueprintGraph::new(GraphId("test".to_string()));
+        graph.define_enum(kind);
+        graph.define_struct(damage.clone());
+        let hit = graph
+            .types
+            .value_from_json(&serde_json::json!({ "amount": 5.0 }), damage.data_type())
+            .unwrap();
+        let get = container_variable(&mut graph, "hit", hit);
+        let break_node = graph.add_node(Node::break_struct(&damage));
+        let expect = graph.add_node(Node::expect_equal(DataType::F32));
+        link(&mut graph, (get, "value"), (break_node, "struct"));
+        link(&mut graph, (break_node, "amount"), (expect, "actual"));
+        assert!(!graph.define_struct(damage.clone()));
+
+        // `kind` is removed, `critical` is added and `amount` keeps its value and links.
+        damage.fields.remove(1);
+        damage.fields.push(ParamDef {
+            name: "critical".to_string(),
+            data_type: DataType::Bool,
+        });
+        assert!(graph.define_struct(damage.clone()));
+
+        assert_eq!(
+            graph.variables[0].default_value,
+            Some(Value::Struct(
+                damage.id(),
+                vec![
+                    ("amount".to_string(), Value::F32(5.0)),
+                    ("critical".to_string(), Value::Bool(false)),
+                ],
+            ))
+        );
+        let node = &graph.nodes[&break_node];
+        assert!(node.pin_named("kind").is_none());
+        assert!(node.pin_named("critical").is_some());
+        assert_eq!(graph.links.len(), 2);
+        compile(&graph).unwrap();
+
+        // Changing the type of a field drops its links.
+        damage.fields[0].data_type = DataType::I32;
+        graph.define_struct(damage);
+        assert_eq!(graph.links.len(), 1);
+    }
+
+    #[test]
+    fn undefined_types_do_not_compile() {
+        let (damage, _) = damage_types();
+        let mut graph = BlueprintGraph::new(GraphId("test".to_string()));
+        graph.variables.push(model::VariableDef {
+            name: "hit".to_string(),
+            data_type: damage.data_type(),
+            default_value: None,
+        });
+        let err = compile(&graph).unwrap_err();
+        assert_eq!(err.kind, ValidationError::UnknownType);
+    }
+
+    #[test]
+    fn widget_events_drive_widget_commands() {
+        let mut graph = BlueprintGraph::new(GraphId("test".to_string()));
+        let committed = graph.add_node(Node::widget_event("NameBox", WidgetEvent::TextCommitted));
+        let mut set_text = Node::set_widget_property(WidgetProperty::Text);
+        set_text.set_property_string("widget", "Greeting".to_string());
+        let set_text = graph.add_node(set_text);
+        link(&mut graph, (committed, "then"), (set_text, "exec"));
+        link(&mut graph, (committed, "value"), (set_text, "value"));
+
+        let mut interpreter = Interpreter::new(compile(&graph).unwrap());
+        assert!(interpreter.handles_widget_event("NameBox", WidgetEvent::TextCommitted));
+        assert!(!interpreter.handles_widget_event("NameBox", WidgetEvent::Clicked));
+
+        let out = interpreter.dispatch_widget_event(
+            &NullHost,
+            "NameBox",
+            WidgetEvent::TextCommitted,
+            Some(Value::String("Bob".to_string())),
+        );
+        let commands = out
+            .events
+            .into_iter()
+            .filter_map(|event| match event {
+                ExecutionEvent::Widget(command) => Some(command),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            commands,
+            vec![WidgetCommand {
+                widget: "Greeting".to_string(),
+                property: WidgetProperty::Text,
+                value: Value::String("Bob".to_string()),
+            }]
+        );
+
+        let other = interpreter.dispatch_widget_event(&NullHost, "Other", WidgetEvent::Clicked, None);
+        assert!(other.events.is_empty());
+
+        graph
+            .nodes
+            .get_mut(&committed)
+            .unwrap()
+            .set_property_string("event", "Hovered".to_string());
+        let err = compile(&graph).unwrap_err();
+        assert_eq!(err.kind, ValidationError::UnknownWidgetMember);
+    }
+
+    #[test]
+    fn animation_signals_drive_animation_commands() {
+        struct AnimatedHost;
+
+        impl BlueprintHost for AnimatedHost {
+            fn active_state(&self, target: &str, layer: usize) -> Option<String> {
+                (target.is_empty() && layer == 0).then(|| "Run".to_string())
+            }
+        }
+
+        let mut graph = BlueprintGraph::new(GraphId("test".to_string()));
+        let footstep = graph.add_node(Node::animation_signal("Footstep"));
+        let mut set_rule = Node::set_absm_parameter(AbsmParameterKind::Rule);
+        set_rule.set_property_string("name", "Grounded".to_string());
+        set_rule.set_property_bool("value", true);
+        let set_rule = graph.add_node(set_rule);
+        let play = graph.add_node(Node::new(BuiltinNodeKind::PlayAnimation));
+        let print = graph.add_node(Node::new(BuiltinNodeKind::Print));
+        let state = graph.add_node(Node::new(BuiltinNodeKind::GetActiveState));
+        link(&mut graph, (footstep, "then"), (set_rule, "exec"));
+        link(&mut graph, (set_rule, "then"), (play, "exec"));
+        link(&mut graph, (footstep, "source"), (play, "animation"));
+        link(&mut graph, (play, "then"), (print, "exec"));
+        link(&mut graph, (state, "state"), (print, "text"));
+
+        let mut interpreter = Interpreter::new(compile(&graph).unwrap());
+        assert!(interpreter.handles_animation_signal("Footstep"));
+        assert!(!interpreter.handles_animation_signal("Idle.Enter"));
+
+        let out = interpreter.dispatch_animation_signal(&AnimatedHost, "Footstep", "Walk");
+        let events = out
+            .events
+            .into_iter()
+            .filter(|event| !matches!(event, ExecutionEvent::EnterNode(_)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            vec![
+                ExecutionEvent::Animation(AnimationCommand::SetParameter {
+                    target: String::new(),
+                    name: "Grounded".to_string(),
+                    value: Value::Bool(true),
+                }),
+                ExecutionEvent::Animation(AnimationCommand::Play {
+                    target: String::new(),
+                    animation: "Walk".to_string(),
+                }),
+                ExecutionEvent::Print("Run".to_string()),
+            ]
+        );
+
+        graph
+            .nodes
+            .get_mut(&set_rule)
+            .unwrap()
+            .set_property_string("parameter", "SamplingPoint".to_string());
+        let err = compile(&graph).unwrap_err();
+        assert_eq!(err.kind, ValidationError::UnknownAnimationMember);
+    }
+
+    #[test]
+    fn sound_fades_are_latent() {
+        struct QuietHost;
+
+        impl BlueprintHost for QuietHost {
+            fn sound_gain(&self, _target: &str) -> Option<f32> {
+                Some(0.5)
+            }
+        }
+
+        let mut graph = BlueprintGraph::new(GraphId("test".to_string()));
+        let begin = graph.add_node(Node::new(BuiltinNodeKind::BeginPlay));
+        let mut fade = Node::new(BuiltinNodeKind::FadeOutSound);
+        fade.set_property_string("sound", "Music".to_string());
+        fade.set_property_f32("duration", 1.0);
+        let fade = graph.add_node(fade);
+        let mut print = Node::new(BuiltinNodeKind::Print);
+        print.set_property_string("text", "faded".to_string());
+        let print = graph.add_node(print);
+        let finished = graph.add_node(Node::sound_finished("Music"));
+        let mut restart = Node::new(BuiltinNodeKind::PlaySound);
+        restart.set_property_string("sound", "Music".to_string());
+        let restart = graph.add_node(restart);
+        link(&mut graph, (begin, "then"), (fade, "exec"));
+        link(&mut graph, (fade, "completed"), (print, "exec"));
+        link(&mut graph, (finished, "then"), (restart, "exec"));
+
+        let sounds = |out: InterpreterOutput| {
+            out.events
+                .into_iter()
+                .filter(|event| !matches!(event, ExecutionEvent::EnterNode(_)))
+                .collect::<Vec<_>>()
+        };
+        let gain = |gain: f32| {
+            ExecutionEvent::Sound(SoundCommand::SetProperty {
+                sound: "Music".to_string(),
+                property: SoundProperty::Gain,
+                value: Value::F32(gain),
+            })
+        };
+
+        let mut interpreter = Interpreter::new(compile(&graph).unwrap());
+        assert!(sounds(interpreter.run_begin_play_with_host(&QuietHost)).is_empty());
+        assert_eq!(sounds(interpreter.tick_with_host(&QuietHost, 0.5)), vec![gain(0.25)]);
+        assert_eq!(
+            sounds(interpreter.tick_with_host(&QuietHost, 0.5)),
+            vec![
+                gain(0.0),
+                ExecutionEvent::Sound(SoundCommand::Stop {
+                    sound: "Music".to_string()
+                }),
+                gain(0.5),
+                ExecutionEvent::Print("faded".to_string()),
+            ]
+        );
+        assert!(sounds(interpreter.tick_with_host(&QuietHost, 0.5)).is_empty());
+
+        assert!(interpreter.handles_sound_finished("Music"));
+        assert_eq!(
+            sounds(interpreter.dispatch_sound_finished(&QuietHost, "Music")),
+            vec![ExecutionEvent::Sound(SoundCommand::Play {
+                sound: "Music".to_string()
+            })]
+        );
+    }
+
+    #[test]
+    fn level_blueprint_scene_events() {
+        struct LevelHost;
+
+        impl BlueprintHost for LevelHost {
+            fn actors_with_blueprint(&self, blueprint: &str) -> Vec<String> {
+                if blueprint == "data/enemy.blueprint" {
+                    vec!["Orc".to_string(), "Goblin".to_string()]
+                } else {
+                    Vec::new()
+                }
+            }
+        }
+
+        let mut graph = BlueprintGraph::new(GraphId("level".to_string()));
+        let loaded = graph.add_node(Node::new(BuiltinNodeKind::OnSceneLoaded));
+        let print_scene = graph.add_node(Node::new(BuiltinNodeKind::Print));
+        let mut load = Node::new(BuiltinNodeKind::LoadScene);
+        load.set_property_string("path", "data/level2.rgs".to_string());
+        let load = graph.add_node(load);
+        link(&mut graph, (loaded, "then"), (print_scene, "exec"));
+        link(&mut graph, (loaded, "scene"), (print_scene, "text"));
+        link(&mut graph, (print_scene, "then"), (load, "exec"));
+
+        let unloaded = graph.add_node(Node::new(BuiltinNodeKind::OnSceneUnloaded));
+        let mut enemies = Node::new(BuiltinNodeKind::GetActorsWithBlueprint);
+        enemies.set_property_string("blueprint", "data/enemy.blueprint".to_string());
+        let enemies = graph.add_node(enemies);
+        let for_each = graph.add_node(Node::container(
+            BuiltinNodeKind::ForEach,
+            DataType::Array(ElementType::String),
+        ));
+        let print_enemy = graph.add_node(Node::new(BuiltinNodeKind::Print));
+        link(&mut graph, (unloaded, "then"), (for_each, "exec"));
+        link(&mut graph, (enemies, "actors"), (for_each, "target"));
+        link(&mut graph, (for_each, "loop_body"), (print_enemy, "exec"));
+        link(&mut graph, (for_each, "element"), (print_enemy, "text"));
+
+        let events = |out: InterpreterOutput| {
+            out.events
+                .into_iter()
+                .filter(|event| !matches!(event, ExecutionEvent::EnterNode(_)))
+                .collect::<Vec<_>>()
+        };
+
+        let mut interpreter = Interpreter::new(compile(&graph).unwrap());
+        assert_eq!(
+            events(interpreter.run_scene_loaded(&LevelHost, "data/level1.rgs")),
+            vec![
+                ExecutionEvent::Print("data/level1.rgs".to_string()),
+                ExecutionEvent::LoadScene("data/level2.rgs".to_string()),
+            ]
+        );
+        assert_eq!(
+            events(interpreter.run_scene_unloaded(&LevelHost, "data/level1.rgs")),
+            vec![
+                ExecutionEvent::Print("Orc".to_string()),
+                ExecutionEvent::Print("Goblin".to_string()),
+            ]
+        );
+        assert!(matches!(
+            nativize(&graph, "Level", "00000000-0000-0000-0000-000000000000"),
+            Err(NativizeError::UnsupportedNode(BuiltinNodeKind::OnSceneLoaded, _))
+        ));
+    }
+
+    #[test]
+    fn global_nodes_read_host_and_emit_changes() {
+        use model::VariableDef;
+
+        struct GlobalHost;
+
+        impl BlueprintHost for GlobalHost {
+            fn global(&self, name: &str) -> Option<Value> {
+                match name {
+                    "player" => Some(Value::String("Ann".to_string())),
+                    "score" => Some(Value::I32(5)),
+                    _ => None,
+                }
+            }
+        }
+
+        let mut graph = BlueprintGraph::new(GraphId("actor".to_string()));
+        let begin_play = graph.add_node(Node::new(BuiltinNodeKind::BeginPlay));
+        let player = graph.add_node(Node::global(
+            BuiltinNodeKind::GetGlobal,
+            "player",
+            DataType::String,
+        ));
+        // Globals of another type read as the default value.
+        let score = graph.add_node(Node::global(
+            BuiltinNodeKind::GetGlobal,
+            "score",
+            DataType::String,
+        ));
+        let print_player = graph.add_node(Node::new(BuiltinNodeKind::Print));
+        let print_score = graph.add_node(Node::new(BuiltinNodeKind::Print));
+        let mut set_score = Node::global(BuiltinNodeKind::SetGlobal, "score", DataType::I32);
+        set_score.set_property_i32("value", 7);
+        let set_score = graph.add_node(set_score);
+        link(&mut graph, (begin_play, "then"), (print_player, "exec"));
+        link(&mut graph, (player, "value"), (print_player, "text"));
+        link(&mut graph, (print_player, "then"), (print_score, "exec"));
+        link(&mut graph, (score, "value"), (print_score, "text"));
+        link(&mut graph, (print_score, "then"), (set_score, "exec"));
+        graph.variables.push(VariableDef {
+            name: "player".to_string(),
+            data_type: DataType::String,
+            default_value: Some(Value::String("Local".to_string())),
+        });
+
+        let events = |out: InterpreterOutput| {
+            out.events
+                .into_iter()
+                .filter(|event| !matches!(event, ExecutionEvent::EnterNode(_)))
+                .collect::<Vec<_>>()
+        };
+
+        let mut interpreter = Interpreter::new(compile(&graph).unwrap());
+        assert_eq!(
+            events(interpreter.run_begin_play_with_host(&GlobalHost)),
+            vec![
+                ExecutionEvent::Print("Ann".to_string()),
+                ExecutionEvent::Print(String::new()),
+                ExecutionEvent::SetGlobal("score".to_string(), Value::I32(7)),
+            ]
+        );
+
+        // Level blueprints have no host globals and read their own variables.
+        let mut interpreter = Interpreter::new(compile(&graph).unwrap());
+        assert_eq!(
+            events(interpreter.run_begin_play()),
+            vec![
+                ExecutionEvent::Print("Local".to_string()),
+                ExecutionEvent::Print(String::new()),
+                ExecutionEvent::SetGlobal("score".to_string(), Value::I32(7)),
+            ]
+        );
+        assert!(interpreter.set_variable_value("player", Value::String("Bob".to_string())));
+        assert!(!interpreter.set_variable_value("player", Value::I32(1)));
+        assert!(!interpreter.set_variable_value("score", Value::I32(1)));
+    }
+
+    #[test]
+    fn tag_nodes_query_host_and_emit_commands() {
+        struct TaggedHost;
+
+        impl BlueprintHost for TaggedHost {
+            fn actors_with_tag(&self, tag: &str) -> Vec<String> {
+                if tag == "enemy" {
+                    vec!["Orc".to_string(), "Goblin".to_string()]
+                } else {
+                    Vec::new()
+                }
+            }
+
+            fn has_tag(&self, target: &str, tag: &str) -> bool {
+                target == "Orc" && tag == "boss"
+            }
+        }
+
+        let mut graph = BlueprintGraph::new(GraphId("tags".to_string()));
+        let begin = graph.add_node(Node::new(BuiltinNodeKind::BeginPlay));
+        let mut enemies = Node::new(BuiltinNodeKind::GetActorsWithTag);
+        enemies.set_property_string("tag", "enemy".to_string());
+        let enemies = graph.add_node(enemies);
+        let for_each = graph.add_node(Node::container(
+            BuiltinNodeKind::ForEach,
+            DataType::Array(ElementType::String),
+        ));
+        let mut is_boss = Node::new(BuiltinNodeKind::HasTag);
+        is_boss.set_property_string("tag", "boss".to_string());
+        let is_boss = graph.add_node(is_boss);
+        let branch = graph.add_node(Node::new(BuiltinNodeKind::Branch));
+        let mut remove = Node::new(BuiltinNodeKind::RemoveTag);
+        remove.set_property_string("tag", "enemy".to_string());
+        let remove = graph.add_node(remove);
+        let mut add = Node::new(BuiltinNodeKind::AddTag);
+        add.set_property_string("tag", "minion".to_string());
+        let add = graph.add_node(add);
+        link(&mut graph, (begin, "then"), (for_each, "exec"));
+        link(&mut graph, (enemies, "actors"), (for_each, "target"));
+        link(&mut graph, (for_each, "loop_body"), (branch, "exec"));
+        link(&mut graph, (for_each, "element"), (is_boss, "target"));
+        link(&mut graph, (is_boss, "result"), (branch, "condition"));
+        link(&mut graph, (branch, "true"), (remove, "exec"));
+        link(&mut graph, (for_each, "element"), (remove, "target"));
+        link(&mut graph, (branch, "false"), (add, "exec"));
+        link(&mut graph, (for_each, "element"), (add, "target"));
+
+        let mut interpreter = Interpreter::new(compile(&graph).unwrap());
+        let events = interpreter
+            .run_begin_play_with_host(&TaggedHost)
+            .events
+            .into_iter()
+            .filter(|event| !matches!(event, ExecutionEvent::EnterNode(_)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            vec![
+                ExecutionEvent::Tag(TagCommand::Remove {
+                    target: "Orc".to_string(),
+                    tag: "enemy".to_string(),
+                }),
+                ExecutionEvent::Tag(TagCommand::Add {
+                    target: "Goblin".to_string(),
+                    tag: "minion".to_string(),
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn timers_trigger_custom_events() {
+        let mut graph = BlueprintGraph::new(GraphId("timers".to_string()));
+        let begin = graph.add_node(Node::new(BuiltinNodeKind::BeginPlay));
+        let mut set_timer = Node::new(BuiltinNodeKind::SetTimerByEvent);
+        set_timer.set_property_string("event", "Spawn".to_string());
+        set_timer.set_property_f32("time", 2.0);
+        set_timer.set_property_bool("looping", true);
+        let set_timer = graph.add_node(set_timer);
+        link(&mut graph, (begin, "then"), (set_timer, "exec"));
+
+        let spawn = graph.add_node(Node::custom_event("Spawn"));
+        let mut print = Node::new(BuiltinNodeKind::Print);
+        print.set_property_string("text", "spawn".to_string());
+        let print = graph.add_node(print);
+        let mut clear = Node::new(BuiltinNodeKind::ClearTimer);
+        clear.set_property_string("event", "Spawn".to_string());
+        let clear = graph.add_node(clear);
+        link(&mut graph, (spawn, "then"), (print, "exec"));
+        link(&mut graph, (print, "then"), (clear, "exec"));
+
+        let events = |out: InterpreterOutput| {
+            out.events
+                .into_iter()
+                .filter(|event| !matches!(event, ExecutionEvent::EnterNode(_)))
+                .collect::<Vec<_>>()
+        };
+
+        let mut interpreter = Interpreter::new(compile(&graph).unwrap());
+        assert_eq!(
+            events(interpreter.run_begin_play()),
+            vec![ExecutionEvent::Timer(TimerCommand::Set {
+                event: "Spawn".to_string(),
+                duration: 2.0,
+                looping: true,
+            })]
+        );
+        assert!(interpreter.handles_custom_event("Spawn"));
+        assert!(!interpreter.handles_custom_event("Despawn"));
+        assert_eq!(
+            events(interpreter.dispatch_custom_event(&NullHost, "Spawn")),
+            vec![
+                ExecutionEvent::Print("spawn".to_string()),
+                ExecutionEvent::Timer(TimerCommand::Clear {
+                    event: "Spawn".to_string()
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn data_table_rows_fill_row_structs() {
+        struct TableHost;
+
+        impl BlueprintHost for TableHost {
+            fn data_table_row(&self, table: &str, key: &str) -> Option<Vec<(String, Value)>> {
+                let ammo = match (table, key) {
+                    ("weapons.csv", "rifle") => 30,
+                    ("weapons.csv", "shotgun") => 8,
+                    _ => return None,
+                };
+                Some(vec![
+                    ("name".to_string(), Value::String(key.to_string())),
+                    ("ammo".to_string(), Value::I32(ammo)),
+                ])
+            }
+
+            fn data_table_keys(&self, table: &str) -> Vec<String> {
+                match table {
+                    "weapons.csv" => vec!["rifle".to_string(), "shotgun".to_string()],
+                    _ => Vec::new(),
+                }
+            }
+        }
+
+        // The ammo column is read as text.
+        let weapon = StructDef {
+            name: "Weapon".to_string(),
+            fields: vec![
+                ParamDef {
+                    name: "ammo".to_string(),
+                    data_type: DataType::String,
+                },
+                ParamDef {
+                    name: "damage".to_string(),
+                    data_type: DataType::F32,
+                },
+            ],
+        };
+        let mut graph = BlueprintGraph::new(GraphId("tables".to_string()));
+        graph.define_struct(weapon.clone());
+        let begin = graph.add_node(Node::new(BuiltinNodeKind::BeginPlay));
+        let mut for_each = Node::new(BuiltinNodeKind::ForEachRow);
+        for_each.set_property_string("table", "weapons.csv".to_string());
+        let for_each = graph.add_node(for_each);
+        let print_key = graph.add_node(Node::new(BuiltinNodeKind::Print));
+        let mut get_row = Node::data_table(BuiltinNodeKind::GetDataTableRow, &weapon);
+        get_row.set_property_string("table", "weapons.csv".to_string());
+        get_row.set_property_string("key", "shotgun".to_string());
+        let get_row = graph.add_node(get_row);
+        let break_row = graph.add_node(Node::break_struct(&weapon));
+        let print_ammo = graph.add_node(Node::new(BuiltinNodeKind::Print));
+        link(&mut graph, (begin, "then"), (for_each, "exec"));
+        link(&mut graph, (for_each, "loop_body"), (print_key, "exec"));
+        link(&mut graph, (for_each, "key"), (print_key, "text"));
+        link(&mut graph, (for_each, "completed"), (print_ammo, "exec"));
+        link(&mut graph, (get_row, "row"), (break_row, "struct"));
+        link(&mut graph, (break_row, "ammo"), (print_ammo, "text"));
+
+        let mut interpreter = Interpreter::new(compile(&graph).unwrap());
+        let prints = interpreter
+            .run_begin_play_with_host(&TableHost)
+            .events
+            .into_iter()
+            .filter_map(|event| match event {
+                ExecutionEvent::Print(text) => Some(text),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(prints, ["rifle", "shotgun", "8"]);
+    }
+
+    #[test]
+    fn behavior_tasks_report_their_status() {
+        let mut graph = BlueprintGraph::new(GraphId("tasks".to_string()));
+        let task = |graph: &mut BlueprintGraph, name: &str, success: Option<bool>| {
+            graph.add_graph(name.to_string(), GraphKind::Function);
+            let mut entry = Node::behavior_task(name);
+            entry.graph = name.to_string();
+            let entry = graph.add_node(entry);
+            let mut print = Node::new(BuiltinNodeKind::Print);
+            print.set_property_string("text", name.to_string());
+            print.graph = name.to_string();
+            let print = graph.add_node(print);
+            link(graph, (entry, "then"), (print, "exec"));
+            if let Some(success) = success {
+                let mut finish = Node::new(BuiltinNodeKind::FinishTask);
+                finish.set_property_bool("success", success);
+                finish.graph = name.to_string();
+                let finish = graph.add_node(finish);
+                link(graph, (print, "then"), (finish, "exec"));
+            }
+        };
+        task(&mut graph, "Attack", Some(true));
+        task(&mut graph, "Flee", Some(false));
+        task(&mut graph, "Wait", None);
+
+        let mut interpreter = Interpreter::new(compile(&graph).unwrap());
+        assert!(interpreter.handles_behavior_task("Wait"));
+        let mut run = |task: &str| {
+            let (out, status) = interpreter.run_behavior_task(&NullHost, task);
+            let prints = out
+                .events
+                .iter()
+                .filter(|event| matches!(event, ExecutionEvent::Print(_)))
+                .count();
+            (prints, status)
+        };
+        assert_eq!(run("Attack"), (1, TaskStatus::Success));
+        assert_eq!(run("Flee"), (1, TaskStatus::Failure));
+        assert_eq!(run("Wait"), (1, TaskStatus::Running));
+        assert_eq!(run("Missing"), (0, TaskStatus::Failure));
+    }
+
+    #[test]
+    fn blackboard_conditions_compare_variables() {
+        let variables = std::collections::BTreeMap::from([
+            ("health".to_string(), Value::I32(30)),
+            ("target".to_string(), Value::String("player".to_string())),
+            ("alerted".to_string(), Value::Bool(true)),
+        ]);
+        let holds = |variable: &str, op, value: &str| {
+            BlackboardCondition {
+                variable: variable.to_string(),
+                op,
+                value: value.to_string(),
+            }
+            .evaluate(&variables)
+        };
+        assert!(holds("health", ConditionOp::Less, "50"));
+        assert!(!holds("health", ConditionOp::Greater, "50"));
+        assert!(holds("target", ConditionOp::Equal, "player"));
+        assert!(holds("target", ConditionOp::Equal, "\"player\""));
+        assert!(holds("alerted", ConditionOp::NotEqual, "false"));
+        assert!(!holds("target", ConditionOp::Less, "player"));
+        assert!(!holds("missing", ConditionOp::NotEqual, "1"));
+        assert!(!holds("health", ConditionOp::Equal, "lots"));
+    }
+
+    #[test]
+    fn behavior_graph_links_keep_a_tree() {
+        let mut graph = BehaviorGraph::default();
+        let selector = graph.add_node(BehaviorNodeKind::Selector);
+        let cooldown = graph.add_node(BehaviorNodeKind::Cooldown { duration: 1.0 });
+        let attack = graph.add_node(BehaviorNodeKind::Task {
+            task: "Attack".to_string(),
+        });
+        let flee = graph.add_node(BehaviorNodeKind::Task {
+            task: "Flee".to_string(),
+        });
+        graph.root = Some(selector);
+
+        assert!(graph.link(selector, cooldown));
+        assert!(graph.link(cooldown, attack));
+        // Decorators have a single child.
+        assert!(graph.link(cooldown, flee));
+        assert_eq!(graph.nodes[&cooldown].children, [flee]);
+        assert!(graph.link(selector, attack));
+        assert_eq!(graph.nodes[&selector].children, [cooldown, attack]);
+        // Tasks have no children and links must not form cycles.
+        assert!(!graph.link(attack, flee));
+        assert!(!graph.link(cooldown, selector));
+
+        graph.remove_node(cooldown);
+        assert_eq!(graph.parent_of(flee), None);
+        assert_eq!(graph.tasks(), ["Attack", "Flee"]);
+    }
 }
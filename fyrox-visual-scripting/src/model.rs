@@ -1,23 +1,22 @@
+use crate::animation::AbsmParameterKind;
+use crate::sound::SoundProperty;
+use crate::types::{EnumDef, StructDef, UserTypes};
+use crate::widgets::{WidgetEvent, WidgetProperty};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct GraphId(pub String);
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GraphKind {
     Event,
     Construction,
     Function,
+    #[default]
     Graph,
 }
 
-impl Default for GraphKind {
-    fn default() -> Self {
-        Self::Graph
-    }
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GraphDef {
     pub name: String,
@@ -50,6 +49,61 @@ pub enum PinDirection {
     Output,
 }
 
+/// Type of the elements of a container. Containers cannot be nested.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ElementType {
+    Bool,
+    I32,
+    F32,
+    String,
+}
+
+impl ElementType {
+    pub const ALL: [ElementType; 4] = [
+        ElementType::Bool,
+        ElementType::I32,
+        ElementType::F32,
+        ElementType::String,
+    ];
+
+    pub fn data_type(self) -> DataType {
+        match self {
+            ElementType::Bool => DataType::Bool,
+            ElementType::I32 => DataType::I32,
+            ElementType::F32 => DataType::F32,
+            ElementType::String => DataType::String,
+        }
+    }
+
+    pub fn from_data_type(data_type: DataType) -> Option<Self> {
+        match data_type {
+            DataType::Bool => Some(ElementType::Bool),
+            DataType::I32 => Some(ElementType::I32),
+            DataType::F32 => Some(ElementType::F32),
+            DataType::String => Some(ElementType::String),
+            _ => None,
+        }
+    }
+}
+
+/// Identifier of a user-defined struct or enum type (see [`crate::types`]). It is a stable hash of
+/// the type name, which keeps [`DataType`] `Copy` and lets blueprints that use the same type agree
+/// on its identity.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct UserTypeId(pub u64);
+
+impl UserTypeId {
+    pub fn of(name: &str) -> Self {
+        // 64-bit FNV-1a.
+        let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+        for byte in name.bytes() {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+        Self(hash)
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DataType {
     Exec,
@@ -58,6 +112,55 @@ pub enum DataType {
     F32,
     String,
     Unit,
+    Array(ElementType),
+    Set(ElementType),
+    /// Map from keys of the first type to values of the second one.
+    Map(ElementType, ElementType),
+    Struct(UserTypeId),
+    Enum(UserTypeId),
+}
+
+impl DataType {
+    pub fn is_container(self) -> bool {
+        matches!(self, DataType::Array(_) | DataType::Set(_) | DataType::Map(..))
+    }
+
+    /// Type of the elements of arrays and sets, or of the values of maps.
+    pub fn element_type(self) -> Option<DataType> {
+        match self {
+            DataType::Array(element) | DataType::Set(element) | DataType::Map(_, element) => {
+                Some(element.data_type())
+            }
+            _ => None,
+        }
+    }
+
+    /// Type that addresses an element of a container: an index for arrays, the element itself for
+    /// sets and the key for maps.
+    pub fn key_type(self) -> Option<DataType> {
+        match self {
+            DataType::Array(_) => Some(DataType::I32),
+            DataType::Set(element) | DataType::Map(element, _) => Some(element.data_type()),
+            _ => None,
+        }
+    }
+
+    pub fn default_value(self) -> Value {
+        match self {
+            DataType::Bool => Value::Bool(false),
+            DataType::I32 => Value::I32(0),
+            DataType::F32 => Value::F32(0.0),
+            DataType::String => Value::String(String::new()),
+            DataType::Array(element) => Value::Array(element, Vec::new()),
+            DataType::Set(element) => Value::Set(element, Vec::new()),
+            DataType::Map(key, value) => Value::Map(key, value, Vec::new()),
+            // Without the definitions there are no fields or variants to default to, see
+            // `UserTypes::default_value`.
+            DataType::Struct(id) => Value::Struct(id, Vec::new()),
+            DataType::Enum(id) => Value::Enum(id, String::new()),
+            DataType::Exec | DataType::Unit => Value::Unit,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -67,6 +170,15 @@ pub enum Value {
     F32(f32),
     String(String),
     Unit,
+    Array(ElementType, Vec<Value>),
+    /// Elements are unique and keep their insertion order.
+    Set(ElementType, Vec<Value>),
+    /// Keys are unique and keep their insertion order.
+    Map(ElementType, ElementType, Vec<(Value, Value)>),
+    /// Field values in the order of the struct definition.
+    Struct(UserTypeId, Vec<(String, Value)>),
+    /// Name of the variant.
+    Enum(UserTypeId, String),
 }
 
 impl Value {
@@ -77,6 +189,175 @@ impl Value {
             Value::F32(_) => DataType::F32,
             Value::String(_) => DataType::String,
             Value::Unit => DataType::Unit,
+            Value::Array(element, _) => DataType::Array(*element),
+            Value::Set(element, _) => DataType::Set(*element),
+            Value::Map(key, value, _) => DataType::Map(*key, *value),
+            Value::Struct(id, _) => DataType::Struct(*id),
+            Value::Enum(id, _) => DataType::Enum(*id),
+        }
+    }
+
+    /// Value of a struct field.
+    pub fn field(&self, name: &str) -> Option<&Value> {
+        match self {
+            Value::Struct(_, fields) => fields.iter().find_map(|(n, v)| (n == name).then_some(v)),
+            _ => None,
+        }
+    }
+
+    /// Number of elements of a container, `None` for other values.
+    pub fn len(&self) -> Option<usize> {
+        match self {
+            Value::Array(_, items) | Value::Set(_, items) => Some(items.len()),
+            Value::Map(_, _, entries) => Some(entries.len()),
+            _ => None,
+        }
+    }
+
+    /// Whether the value is an empty container.
+    pub fn is_empty(&self) -> bool {
+        self.len() == Some(0)
+    }
+
+    /// JSON literal of the value: arrays and sets become JSON arrays, maps become JSON objects
+    /// (keys are written as strings).
+    pub fn to_json(&self) -> serde_json::Value {
+        use serde_json::Value as Json;
+        match self {
+            Value::Bool(v) => Json::Bool(*v),
+            Value::I32(v) => Json::from(*v),
+            Value::F32(v) => Json::from(*v),
+            Value::String(v) => Json::String(v.clone()),
+            Value::Unit => Json::Null,
+            Value::Array(_, items) | Value::Set(_, items) => {
+                Json::Array(items.iter().map(Value::to_json).collect())
+            }
+            Value::Map(_, _, entries) => Json::Object(
+                entries
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value.to_json()))
+                    .collect(),
+            ),
+            Value::Struct(_, fields) => Json::Object(
+                fields
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.to_json()))
+                    .collect(),
+            ),
+            Value::Enum(_, variant) => Json::String(variant.clone()),
+        }
+    }
+
+    /// Reads a JSON literal written by [`Value::to_json`] as a value of the given type. Returns
+    /// `None` if the literal does not match the type. Structs and enums need their definitions, see
+    /// [`UserTypes::value_from_json`](crate::types::UserTypes::value_from_json).
+    pub fn from_json(json: &serde_json::Value, ty: DataType) -> Option<Value> {
+        use serde_json::Value as Json;
+        let elements = |items: &Vec<Json>, element: ElementType| {
+            items
+                .iter()
+                .map(|item| Value::from_json(item, element.data_type()))
+                .collect::<Option<Vec<_>>>()
+        };
+        match (ty, json) {
+            (DataType::Bool, Json::Bool(v)) => Some(Value::Bool(*v)),
+            (DataType::I32, Json::Number(v)) => {
+                v.as_i64().and_then(|v| i32::try_from(v).ok()).map(Value::I32)
+            }
+            (DataType::F32, Json::Number(v)) => v.as_f64().map(|v| Value::F32(v as f32)),
+            (DataType::String, Json::String(v)) => Some(Value::String(v.clone())),
+            (DataType::Unit, Json::Null) => Some(Value::Unit),
+            (DataType::Array(element), Json::Array(items)) => {
+                Some(Value::Array(element, elements(items, element)?))
+            }
+            (DataType::Set(element), Json::Array(items)) => {
+                let mut unique = Vec::new();
+                for item in elements(items, element)? {
+                    if !unique.contains(&item) {
+                        unique.push(item);
+                    }
+                }
+                Some(Value::Set(element, unique))
+            }
+            (DataType::Map(key, value), Json::Object(entries)) => {
+                let entries = entries
+                    .iter()
+                    .map(|(k, v)| {
+                        let k = match key {
+                            ElementType::String => Json::String(k.clone()),
+                            _ => serde_json::from_str(k).ok()?,
+                        };
+                        Some((
+                            Value::from_json(&k, key.data_type())?,
+                            Value::from_json(v, value.data_type())?,
+                        ))
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                Some(Value::Map(key, value, entries))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn list<'a>(
+            f: &mut std::fmt::Formatter<'_>,
+            items: impl Iterator<Item = &'a Value>,
+        ) -> std::fmt::Result {
+            for (i, item) in items.enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                match item {
+                    Value::String(s) => write!(f, "{s:?}")?,
+                    item => write!(f, "{item}")?,
+                }
+            }
+            Ok(())
+        }
+
+        match self {
+            Value::Bool(v) => write!(f, "{v}"),
+            Value::I32(v) => write!(f, "{v}"),
+            Value::F32(v) => write!(f, "{v}"),
+            Value::String(v) => write!(f, "{v}"),
+            Value::Unit => Ok(()),
+            Value::Array(_, items) => {
+                write!(f, "[")?;
+                list(f, items.iter())?;
+                write!(f, "]")
+            }
+            Value::Set(_, items) => {
+                write!(f, "{{")?;
+                list(f, items.iter())?;
+                write!(f, "}}")
+            }
+            Value::Map(_, _, entries) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    list(f, std::iter::once(key))?;
+                    write!(f, ": ")?;
+                    list(f, std::iter::once(value))?;
+                }
+                write!(f, "}}")
+            }
+            Value::Struct(_, fields) => {
+                write!(f, "(")?;
+                for (i, (name, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{name}: ")?;
+                    list(f, std::iter::once(value))?;
+                }
+                write!(f, ")")
+            }
+            Value::Enum(_, variant) => write!(f, "{variant}"),
         }
     }
 }
@@ -97,6 +378,70 @@ pub enum BuiltinNodeKind {
     SpawnActor,
     GetActorByName,
     GetActorName,
+    InterfaceEvent,
+    CallInterface,
+    DoesImplementInterface,
+    Assert,
+    ExpectEqual,
+    ContainerAdd,
+    ContainerRemove,
+    ContainerGet,
+    ContainerLength,
+    ContainerContains,
+    ContainerFind,
+    ContainerClear,
+    ForEach,
+    MakeStruct,
+    BreakStruct,
+    SwitchOnEnum,
+    WidgetEvent,
+    SetWidgetProperty,
+    SetAbsmParameter,
+    GetActiveState,
+    PlayAnimation,
+    StopAnimation,
+    OnAnimationSignal,
+    PlaySound,
+    StopSound,
+    SpawnSound,
+    SetSoundProperty,
+    FadeInSound,
+    FadeOutSound,
+    SetSoundBus,
+    OnSoundFinished,
+    OnSceneLoaded,
+    OnSceneUnloaded,
+    LoadScene,
+    GetActorsWithBlueprint,
+    GetGlobal,
+    SetGlobal,
+    GetActorsWithTag,
+    HasTag,
+    AddTag,
+    RemoveTag,
+    CustomEvent,
+    SetTimerByEvent,
+    ClearTimer,
+    GetDataTableRow,
+    ForEachRow,
+    BehaviorTask,
+    FinishTask,
+}
+
+impl BuiltinNodeKind {
+    pub fn is_container_node(self) -> bool {
+        matches!(
+            self,
+            BuiltinNodeKind::ContainerAdd
+                | BuiltinNodeKind::ContainerRemove
+                | BuiltinNodeKind::ContainerGet
+                | BuiltinNodeKind::ContainerLength
+                | BuiltinNodeKind::ContainerContains
+                | BuiltinNodeKind::ContainerFind
+                | BuiltinNodeKind::ContainerClear
+                | BuiltinNodeKind::ForEach
+        )
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -159,6 +504,189 @@ impl Node {
     pub fn set_property_f32(&mut self, key: &str, value: f32) {
         self.properties.insert(key.to_string(), Value::F32(value));
     }
+
+    /// Appends a pin with a placeholder ID (remapped by the graph on insertion).
+    pub fn add_pin(
+        &mut self,
+        name: impl Into<String>,
+        direction: PinDirection,
+        data_type: DataType,
+    ) {
+        self.pins.push(Pin {
+            id: PinId(self.pins.len() as u32),
+            name: name.into(),
+            direction,
+            data_type,
+        });
+    }
+
+    /// Creates the entry node of an interface function implementation. Every parameter of the
+    /// function becomes an output pin.
+    pub fn interface_event(interface: &str, function: &InterfaceFunction) -> Self {
+        let mut node = Self::new(BuiltinNodeKind::InterfaceEvent);
+        node.set_property_string("interface", interface.to_string());
+        node.set_property_string("function", function.name.clone());
+        for param in function.inputs.iter() {
+            node.add_pin(param.name.clone(), PinDirection::Output, param.data_type);
+        }
+        node
+    }
+
+    /// Creates a node that sends an interface message to a target actor. Every parameter of the
+    /// function becomes an input pin.
+    pub fn interface_call(interface: &str, function: &InterfaceFunction) -> Self {
+        let mut node = Self::new(BuiltinNodeKind::CallInterface);
+        node.set_property_string("interface", interface.to_string());
+        node.set_property_string("function", function.name.clone());
+        for param in function.inputs.iter() {
+            node.add_pin(param.name.clone(), PinDirection::Input, param.data_type);
+        }
+        node
+    }
+
+    /// Creates a container node (see [`BuiltinNodeKind::is_container_node`]) working on containers
+    /// of the given type. The container is connected to the `target` input; nodes that modify it
+    /// require `target` to come from a variable.
+    pub fn container(kind: BuiltinNodeKind, container: DataType) -> Self {
+        let mut node = Self::new(kind);
+        node.pins = container_pins(kind, container);
+        node
+    }
+
+    /// Changes the pin types of a container node to match another container type, keeping the pins
+    /// (and thus their links).
+    pub fn set_container_type(&mut self, container: DataType) {
+        for template in container_pins(self.kind, container) {
+            if let Some(pin) = self.pins.iter_mut().find(|p| p.name == template.name) {
+                pin.data_type = template.data_type;
+            }
+        }
+    }
+
+    /// Creates a node that builds a struct value from a value per field.
+    pub fn make_struct(def: &StructDef) -> Self {
+        let mut node = Self::new(BuiltinNodeKind::MakeStruct);
+        node.set_property_string("type", def.name.clone());
+        node.pins = user_type_pins(BuiltinNodeKind::MakeStruct, def.data_type(), &def.fields);
+        node
+    }
+
+    /// Creates a node that splits a struct value into its fields.
+    pub fn break_struct(def: &StructDef) -> Self {
+        let mut node = Self::new(BuiltinNodeKind::BreakStruct);
+        node.set_property_string("type", def.name.clone());
+        node.pins = user_type_pins(BuiltinNodeKind::BreakStruct, def.data_type(), &def.fields);
+        node
+    }
+
+    /// Creates a node that continues with the exec output named after the variant of an enum value.
+    pub fn switch_on_enum(def: &EnumDef) -> Self {
+        let mut node = Self::new(BuiltinNodeKind::SwitchOnEnum);
+        node.set_property_string("type", def.name.clone());
+        node.pins = user_type_pins(BuiltinNodeKind::SwitchOnEnum, def.data_type(), &def.params());
+        node
+    }
+
+    /// Creates the entry node for an event of the widget with the given name. Events that carry a
+    /// value (see [`WidgetEvent::value_type`]) get a `value` output.
+    pub fn widget_event(widget: &str, event: WidgetEvent) -> Self {
+        let mut node = Self::new(BuiltinNodeKind::WidgetEvent);
+        node.set_property_string("widget", widget.to_string());
+        node.set_property_string("event", event.name().to_string());
+        node.pins = widget_pins(BuiltinNodeKind::WidgetEvent, event.value_type());
+        node
+    }
+
+    /// Creates a node that changes a property of the widget named by its `widget` input.
+    pub fn set_widget_property(property: WidgetProperty) -> Self {
+        let mut node = Self::new(BuiltinNodeKind::SetWidgetProperty);
+        node.set_property_string("property", property.name().to_string());
+        node.pins = widget_pins(
+            BuiltinNodeKind::SetWidgetProperty,
+            Some(property.value_type()),
+        );
+        node
+    }
+
+    /// Creates a node that sets an ABSM parameter of the given kind. The `value` input takes the
+    /// type of the parameter.
+    pub fn set_absm_parameter(kind: AbsmParameterKind) -> Self {
+        let mut node = Self::new(BuiltinNodeKind::SetAbsmParameter);
+        node.set_property_string("parameter", kind.name().to_string());
+        node.pins = animation_pins(BuiltinNodeKind::SetAbsmParameter, kind.value_type());
+        node
+    }
+
+    /// Creates the entry node for the animation signal (or state machine event) with the given
+    /// name.
+    pub fn animation_signal(signal: &str) -> Self {
+        let mut node = Self::new(BuiltinNodeKind::OnAnimationSignal);
+        node.set_property_string("signal", signal.to_string());
+        node
+    }
+
+    /// Creates a node that changes a property of a sound node. The `value` input takes the type of
+    /// the property.
+    pub fn set_sound_property(property: SoundProperty) -> Self {
+        let mut node = Self::new(BuiltinNodeKind::SetSoundProperty);
+        node.set_property_string("property", property.name().to_string());
+        node.pins = sound_pins(BuiltinNodeKind::SetSoundProperty, property.value_type());
+        node
+    }
+
+    /// Creates the entry node that fires when the sound node with the given name stops playing.
+    pub fn sound_finished(sound: &str) -> Self {
+        let mut node = Self::new(BuiltinNodeKind::OnSoundFinished);
+        node.set_property_string("sound", sound.to_string());
+        node
+    }
+
+    /// Creates the entry node of the behavior tree task with the given name.
+    pub fn behavior_task(task: &str) -> Self {
+        let mut node = Self::new(BuiltinNodeKind::BehaviorTask);
+        node.set_property_string("task", task.to_string());
+        node
+    }
+
+    /// Creates a custom event entry node with the given name.
+    pub fn custom_event(event: &str) -> Self {
+        let mut node = Self::new(BuiltinNodeKind::CustomEvent);
+        node.set_property_string("event", event.to_string());
+        node
+    }
+
+    /// Creates a data table node (`GetDataTableRow` or `ForEachRow`) that reads rows as the given
+    /// struct. Without a struct, rows are maps from column names to cell text.
+    pub fn data_table(kind: BuiltinNodeKind, row: &StructDef) -> Self {
+        let mut node = Self::new(kind);
+        node.set_property_string("type", row.name.clone());
+        node.pins = data_table_pins(kind, row.data_type());
+        node
+    }
+
+    /// Creates an ExpectEqual node comparing values of the given type.
+    pub fn expect_equal(data_type: DataType) -> Self {
+        let mut node = Self::new(BuiltinNodeKind::ExpectEqual);
+        for pin in node.pins.iter_mut() {
+            if pin.name == "expected" || pin.name == "actual" {
+                pin.data_type = data_type;
+            }
+        }
+        node
+    }
+
+    /// Creates a `GetGlobal` or `SetGlobal` node for the global variable with the given name. The
+    /// `value` pin takes the given type.
+    pub fn global(kind: BuiltinNodeKind, name: &str, data_type: DataType) -> Self {
+        let mut node = Self::new(kind);
+        node.set_property_string("name", name.to_string());
+        for pin in node.pins.iter_mut() {
+            if pin.name == "value" {
+                pin.data_type = data_type;
+            }
+        }
+        node
+    }
 }
 
 fn default_node_graph() -> String {
@@ -178,6 +706,446 @@ fn default_variable_type() -> DataType {
     DataType::String
 }
 
+/// Parameter of an interface function.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParamDef {
+    pub name: String,
+    pub data_type: DataType,
+}
+
+/// Function signature declared by a blueprint interface. It has no implementation of its own.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InterfaceFunction {
+    pub name: String,
+    #[serde(default)]
+    pub inputs: Vec<ParamDef>,
+}
+
+/// A named set of function signatures that unrelated blueprints can implement, so that callers
+/// can send messages like "Interact" without knowing the concrete blueprint of the target.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlueprintInterface {
+    pub name: String,
+    #[serde(default)]
+    pub functions: Vec<InterfaceFunction>,
+}
+
+impl BlueprintInterface {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            functions: Vec::new(),
+        }
+    }
+
+    pub fn function(&self, name: &str) -> Option<&InterfaceFunction> {
+        self.functions.iter().find(|f| f.name == name)
+    }
+}
+
+/// Name of the function graph that holds the implementation of `interface::function`.
+pub fn interface_graph_name(interface: &str, function: &str) -> String {
+    format!("{interface}::{function}")
+}
+
+/// Pins of the nodes of a user-defined type: one per struct field or enum variant (enum variants
+/// are passed as `Exec` parameters).
+pub(crate) fn user_type_pins(kind: BuiltinNodeKind, ty: DataType, members: &[ParamDef]) -> Vec<Pin> {
+    use PinDirection as D;
+
+    let mut pins: Vec<(String, D, DataType)> = match kind {
+        BuiltinNodeKind::MakeStruct => vec![("struct".to_string(), D::Output, ty)],
+        BuiltinNodeKind::BreakStruct => vec![("struct".to_string(), D::Input, ty)],
+        BuiltinNodeKind::SwitchOnEnum => vec![
+            ("exec".to_string(), D::Input, DataType::Exec),
+            ("selection".to_string(), D::Input, ty),
+        ],
+        _ => Vec::new(),
+    };
+    let member_direction = match kind {
+        BuiltinNodeKind::MakeStruct => D::Input,
+        _ => D::Output,
+    };
+    pins.extend(
+        members
+            .iter()
+            .map(|member| (member.name.clone(), member_direction, member.data_type)),
+    );
+
+    pins.into_iter()
+        .enumerate()
+        .map(|(i, (name, direction, data_type))| Pin {
+            id: PinId(i as u32),
+            name,
+            direction,
+            data_type,
+        })
+        .collect()
+}
+
+fn container_pins(kind: BuiltinNodeKind, container: DataType) -> Vec<Pin> {
+    use BuiltinNodeKind as K;
+    use DataType as T;
+    use PinDirection as D;
+
+    let key = container.key_type().unwrap_or(T::I32);
+    let element = container.element_type().unwrap_or(T::String);
+    // Arrays and sets are searched by element, maps by key.
+    let item = match container {
+        T::Map(..) => key,
+        _ => element,
+    };
+
+    let pins: Vec<(&str, D, T)> = match kind {
+        K::ContainerAdd => {
+            let mut pins = vec![
+                ("exec", D::Input, T::Exec),
+                ("then", D::Output, T::Exec),
+                ("target", D::Input, container),
+            ];
+            if matches!(container, T::Map(..)) {
+                pins.push(("key", D::Input, key));
+            }
+            pins.push(("value", D::Input, element));
+            pins
+        }
+        K::ContainerRemove => vec![
+            ("exec", D::Input, T::Exec),
+            ("then", D::Output, T::Exec),
+            ("target", D::Input, container),
+            ("key", D::Input, key),
+            ("removed", D::Output, T::Bool),
+        ],
+        K::ContainerGet => vec![
+            ("target", D::Input, container),
+            ("key", D::Input, key),
+            ("value", D::Output, element),
+            ("found", D::Output, T::Bool),
+        ],
+        K::ContainerLength => vec![
+            ("target", D::Input, container),
+            ("length", D::Output, T::I32),
+        ],
+        K::ContainerContains => vec![
+            ("target", D::Input, container),
+            ("item", D::Input, item),
+            ("result", D::Output, T::Bool),
+        ],
+        K::ContainerFind => vec![
+            ("target", D::Input, container),
+            ("item", D::Input, element),
+            ("key", D::Output, key),
+            ("found", D::Output, T::Bool),
+        ],
+        K::ContainerClear => vec![
+            ("exec", D::Input, T::Exec),
+            ("then", D::Output, T::Exec),
+            ("target", D::Input, container),
+        ],
+        K::ForEach => vec![
+            ("exec", D::Input, T::Exec),
+            ("target", D::Input, container),
+            ("loop_body", D::Output, T::Exec),
+            ("key", D::Output, key),
+            ("element", D::Output, element),
+            ("completed", D::Output, T::Exec),
+        ],
+        _ => Vec::new(),
+    };
+
+    pins.into_iter()
+        .enumerate()
+        .map(|(i, (name, direction, data_type))| Pin {
+            id: PinId(i as u32),
+            name: name.to_string(),
+            direction,
+            data_type,
+        })
+        .collect()
+}
+
+fn widget_pins(kind: BuiltinNodeKind, value: Option<DataType>) -> Vec<Pin> {
+    use BuiltinNodeKind as K;
+    use DataType as T;
+    use PinDirection as D;
+
+    let mut pins: Vec<(&str, D, T)> = match kind {
+        K::WidgetEvent => vec![("then", D::Output, T::Exec)],
+        K::SetWidgetProperty => vec![
+            ("exec", D::Input, T::Exec),
+            ("then", D::Output, T::Exec),
+            ("widget", D::Input, T::String),
+        ],
+        _ => Vec::new(),
+    };
+    if let Some(value) = value {
+        let direction = match kind {
+            K::WidgetEvent => D::Output,
+            _ => D::Input,
+        };
+        pins.push(("value", direction, value));
+    }
+
+    pins.into_iter()
+        .enumerate()
+        .map(|(i, (name, direction, data_type))| Pin {
+            id: PinId(i as u32),
+            name: name.to_string(),
+            direction,
+            data_type,
+        })
+        .collect()
+}
+
+fn animation_pins(kind: BuiltinNodeKind, parameter: DataType) -> Vec<Pin> {
+    use BuiltinNodeKind as K;
+    use DataType as T;
+    use PinDirection as D;
+
+    let pins: Vec<(&str, D, T)> = match kind {
+        K::SetAbsmParameter => vec![
+            ("exec", D::Input, T::Exec),
+            ("then", D::Output, T::Exec),
+            ("target", D::Input, T::String),
+            ("name", D::Input, T::String),
+            ("value", D::Input, parameter),
+        ],
+        K::GetActiveState => vec![
+            ("target", D::Input, T::String),
+            ("layer", D::Input, T::I32),
+            ("state", D::Output, T::String),
+        ],
+        K::PlayAnimation | K::StopAnimation => vec![
+            ("exec", D::Input, T::Exec),
+            ("then", D::Output, T::Exec),
+            ("target", D::Input, T::String),
+            ("animation", D::Input, T::String),
+        ],
+        K::OnAnimationSignal => vec![
+            ("then", D::Output, T::Exec),
+            ("source", D::Output, T::String),
+        ],
+        _ => Vec::new(),
+    };
+
+    pins.into_iter()
+        .enumerate()
+        .map(|(i, (name, direction, data_type))| Pin {
+            id: PinId(i as u32),
+            name: name.to_string(),
+            direction,
+            data_type,
+        })
+        .collect()
+}
+
+fn sound_pins(kind: BuiltinNodeKind, property: DataType) -> Vec<Pin> {
+    use BuiltinNodeKind as K;
+    use DataType as T;
+    use PinDirection as D;
+
+    let mut pins: Vec<(&str, D, T)> = match kind {
+        K::OnSoundFinished => vec![("then", D::Output, T::Exec)],
+        _ => vec![("exec", D::Input, T::Exec), ("then", D::Output, T::Exec)],
+    };
+    match kind {
+        K::PlaySound | K::StopSound => pins.push(("sound", D::Input, T::String)),
+        K::SpawnSound => pins.extend([
+            ("buffer", D::Input, T::String),
+            ("x", D::Input, T::F32),
+            ("y", D::Input, T::F32),
+            ("z", D::Input, T::F32),
+            ("gain", D::Input, T::F32),
+        ]),
+        K::SetSoundProperty => {
+            pins.extend([("sound", D::Input, T::String), ("value", D::Input, property)])
+        }
+        K::FadeInSound | K::FadeOutSound => pins.extend([
+            ("completed", D::Output, T::Exec),
+            ("sound", D::Input, T::String),
+            ("duration", D::Input, T::F32),
+        ]),
+        K::SetSoundBus => {
+            pins.extend([("sound", D::Input, T::String), ("bus", D::Input, T::String)])
+        }
+        _ => {}
+    }
+
+    pins.into_iter()
+        .enumerate()
+        .map(|(i, (name, direction, data_type))| Pin {
+            id: PinId(i as u32),
+            name: name.to_string(),
+            direction,
+            data_type,
+        })
+        .collect()
+}
+
+fn level_pins(kind: BuiltinNodeKind) -> Vec<Pin> {
+    use BuiltinNodeKind as K;
+    use DataType as T;
+    use PinDirection as D;
+
+    let pins: Vec<(&str, D, T)> = match kind {
+        K::OnSceneLoaded | K::OnSceneUnloaded => vec![
+            ("then", D::Output, T::Exec),
+            ("scene", D::Output, T::String),
+        ],
+        K::LoadScene => vec![
+            ("exec", D::Input, T::Exec),
+            ("then", D::Output, T::Exec),
+            ("path", D::Input, T::String),
+        ],
+        K::GetActorsWithBlueprint => vec![
+            ("blueprint", D::Input, T::String),
+            ("actors", D::Output, T::Array(ElementType::String)),
+        ],
+        // Globals start as strings, see `Node::global`.
+        K::GetGlobal => vec![("value", D::Output, T::String)],
+        K::SetGlobal => vec![
+            ("exec", D::Input, T::Exec),
+            ("then", D::Output, T::Exec),
+            ("value", D::Input, T::String),
+        ],
+        _ => Vec::new(),
+    };
+
+    pins.into_iter()
+        .enumerate()
+        .map(|(i, (name, direction, data_type))| Pin {
+            id: PinId(i as u32),
+            name: name.to_string(),
+            direction,
+            data_type,
+        })
+        .collect()
+}
+
+fn tag_pins(kind: BuiltinNodeKind) -> Vec<Pin> {
+    use BuiltinNodeKind as K;
+    use DataType as T;
+    use PinDirection as D;
+
+    let pins: Vec<(&str, D, T)> = match kind {
+        K::GetActorsWithTag => vec![
+            ("tag", D::Input, T::String),
+            ("actors", D::Output, T::Array(ElementType::String)),
+        ],
+        K::HasTag => vec![
+            ("target", D::Input, T::String),
+            ("tag", D::Input, T::String),
+            ("result", D::Output, T::Bool),
+        ],
+        K::AddTag | K::RemoveTag => vec![
+            ("exec", D::Input, T::Exec),
+            ("then", D::Output, T::Exec),
+            ("target", D::Input, T::String),
+            ("tag", D::Input, T::String),
+        ],
+        _ => Vec::new(),
+    };
+
+    pins.into_iter()
+        .enumerate()
+        .map(|(i, (name, direction, data_type))| Pin {
+            id: PinId(i as u32),
+            name: name.to_string(),
+            direction,
+            data_type,
+        })
+        .collect()
+}
+
+fn data_table_pins(kind: BuiltinNodeKind, row: DataType) -> Vec<Pin> {
+    use BuiltinNodeKind as K;
+    use DataType as T;
+    use PinDirection as D;
+
+    let pins: Vec<(&str, D, T)> = match kind {
+        K::GetDataTableRow => vec![
+            ("table", D::Input, T::String),
+            ("key", D::Input, T::String),
+            ("found", D::Output, T::Bool),
+            ("row", D::Output, row),
+        ],
+        K::ForEachRow => vec![
+            ("exec", D::Input, T::Exec),
+            ("table", D::Input, T::String),
+            ("loop_body", D::Output, T::Exec),
+            ("key", D::Output, T::String),
+            ("row", D::Output, row),
+            ("completed", D::Output, T::Exec),
+        ],
+        _ => Vec::new(),
+    };
+
+    pins.into_iter()
+        .enumerate()
+        .map(|(i, (name, direction, data_type))| Pin {
+            id: PinId(i as u32),
+            name: name.to_string(),
+            direction,
+            data_type,
+        })
+        .collect()
+}
+
+fn behavior_pins(kind: BuiltinNodeKind) -> Vec<Pin> {
+    use BuiltinNodeKind as K;
+    use DataType as T;
+    use PinDirection as D;
+
+    let pins: Vec<(&str, D, T)> = match kind {
+        K::BehaviorTask => vec![("then", D::Output, T::Exec)],
+        K::FinishTask => vec![("exec", D::Input, T::Exec), ("success", D::Input, T::Bool)],
+        _ => Vec::new(),
+    };
+
+    pins.into_iter()
+        .enumerate()
+        .map(|(i, (name, direction, data_type))| Pin {
+            id: PinId(i as u32),
+            name: name.to_string(),
+            direction,
+            data_type,
+        })
+        .collect()
+}
+
+fn timer_pins(kind: BuiltinNodeKind) -> Vec<Pin> {
+    use BuiltinNodeKind as K;
+    use DataType as T;
+    use PinDirection as D;
+
+    let pins: Vec<(&str, D, T)> = match kind {
+        K::CustomEvent => vec![("then", D::Output, T::Exec)],
+        K::SetTimerByEvent => vec![
+            ("exec", D::Input, T::Exec),
+            ("then", D::Output, T::Exec),
+            ("event", D::Input, T::String),
+            ("time", D::Input, T::F32),
+            ("looping", D::Input, T::Bool),
+        ],
+        K::ClearTimer => vec![
+            ("exec", D::Input, T::Exec),
+            ("then", D::Output, T::Exec),
+            ("event", D::Input, T::String),
+        ],
+        _ => Vec::new(),
+    };
+
+    pins.into_iter()
+        .enumerate()
+        .map(|(i, (name, direction, data_type))| Pin {
+            id: PinId(i as u32),
+            name: name.to_string(),
+            direction,
+            data_type,
+        })
+        .collect()
+}
+
 fn default_pins(kind: BuiltinNodeKind) -> Vec<Pin> {
     use BuiltinNodeKind as K;
     use DataType as T;
@@ -426,6 +1394,149 @@ fn default_pins(kind: BuiltinNodeKind) -> Vec<Pin> {
                 data_type: T::String,
             },
         ],
+        // Parameter pins are added per signature, see `Node::interface_event`.
+        K::InterfaceEvent => vec![
+            Pin {
+                id: PinId(0),
+                name: "then".to_string(),
+                direction: D::Output,
+                data_type: T::Exec,
+            },
+        ],
+        // Parameter pins are added per signature, see `Node::interface_call`.
+        K::CallInterface => vec![
+            Pin {
+                id: PinId(0),
+                name: "exec".to_string(),
+                direction: D::Input,
+                data_type: T::Exec,
+            },
+            Pin {
+                id: PinId(1),
+                name: "then".to_string(),
+                direction: D::Output,
+                data_type: T::Exec,
+            },
+            Pin {
+                id: PinId(2),
+                name: "target".to_string(),
+                direction: D::Input,
+                data_type: T::String,
+            },
+        ],
+        K::DoesImplementInterface => vec![
+            Pin {
+                id: PinId(0),
+                name: "target".to_string(),
+                direction: D::Input,
+                data_type: T::String,
+            },
+            Pin {
+                id: PinId(1),
+                name: "result".to_string(),
+                direction: D::Output,
+                data_type: T::Bool,
+            },
+        ],
+        K::Assert => vec![
+            Pin {
+                id: PinId(0),
+                name: "exec".to_string(),
+                direction: D::Input,
+                data_type: T::Exec,
+            },
+            Pin {
+                id: PinId(1),
+                name: "then".to_string(),
+                direction: D::Output,
+                data_type: T::Exec,
+            },
+            Pin {
+                id: PinId(2),
+                name: "condition".to_string(),
+                direction: D::Input,
+                data_type: T::Bool,
+            },
+            Pin {
+                id: PinId(3),
+                name: "message".to_string(),
+                direction: D::Input,
+                data_type: T::String,
+            },
+        ],
+        K::ExpectEqual => vec![
+            Pin {
+                id: PinId(0),
+                name: "exec".to_string(),
+                direction: D::Input,
+                data_type: T::Exec,
+            },
+            Pin {
+                id: PinId(1),
+                name: "then".to_string(),
+                direction: D::Output,
+                data_type: T::Exec,
+            },
+            Pin {
+                id: PinId(2),
+                name: "expected".to_string(),
+                direction: D::Input,
+                data_type: T::String,
+            },
+            Pin {
+                id: PinId(3),
+                name: "actual".to_string(),
+                direction: D::Input,
+                data_type: T::String,
+            },
+            Pin {
+                id: PinId(4),
+                name: "message".to_string(),
+                direction: D::Input,
+                data_type: T::String,
+            },
+        ],
+        K::ContainerAdd
+        | K::ContainerRemove
+        | K::ContainerGet
+        | K::ContainerLength
+        | K::ContainerContains
+        | K::ContainerFind
+        | K::ContainerClear
+        | K::ForEach => container_pins(kind, T::Array(ElementType::String)),
+        // Pins are added per type definition, see `Node::make_struct`, `Node::break_struct` and
+        // `Node::switch_on_enum`.
+        K::MakeStruct | K::BreakStruct | K::SwitchOnEnum => Vec::new(),
+        K::WidgetEvent => widget_pins(kind, None),
+        K::SetWidgetProperty => widget_pins(kind, Some(T::Bool)),
+        // `SetAbsmParameter` starts as a Rule, see `Node::set_absm_parameter`.
+        K::SetAbsmParameter
+        | K::GetActiveState
+        | K::PlayAnimation
+        | K::StopAnimation
+        | K::OnAnimationSignal => animation_pins(kind, T::Bool),
+        // `SetSoundProperty` starts as Gain, see `Node::set_sound_property`.
+        K::PlaySound
+        | K::StopSound
+        | K::SpawnSound
+        | K::SetSoundProperty
+        | K::FadeInSound
+        | K::FadeOutSound
+        | K::SetSoundBus
+        | K::OnSoundFinished => sound_pins(kind, T::F32),
+        K::OnSceneLoaded
+        | K::OnSceneUnloaded
+        | K::LoadScene
+        | K::GetActorsWithBlueprint
+        | K::GetGlobal
+        | K::SetGlobal => level_pins(kind),
+        K::GetActorsWithTag | K::HasTag | K::AddTag | K::RemoveTag => tag_pins(kind),
+        K::CustomEvent | K::SetTimerByEvent | K::ClearTimer => timer_pins(kind),
+        // Rows start as maps of cell text, see `Node::data_table`.
+        K::GetDataTableRow | K::ForEachRow => {
+            data_table_pins(kind, T::Map(ElementType::String, ElementType::String))
+        }
+        K::BehaviorTask | K::FinishTask => behavior_pins(kind),
     }
 }
 
@@ -436,9 +1547,15 @@ pub struct Link {
 }
 
 impl Link {
+    /// Connects an exec output to an exec input.
     pub fn exec(from: PinId, to: PinId) -> Self {
         Self { from, to }
     }
+
+    /// Connects a data output to a data input of the same type.
+    pub fn data(from: PinId, to: PinId) -> Self {
+        Self { from, to }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -450,6 +1567,17 @@ pub struct BlueprintGraph {
     pub links: Vec<Link>,
     #[serde(default)]
     pub variables: Vec<VariableDef>,
+    /// Interfaces implemented by this blueprint (signatures are copied from the interface asset).
+    #[serde(default)]
+    pub interfaces: Vec<BlueprintInterface>,
+    /// Struct and enum types used by this blueprint (definitions are copied from their assets).
+    #[serde(default)]
+    pub types: UserTypes,
+    /// `#[type_uuid]` of the Rust script generated from this blueprint (see [`crate::nativize`]).
+    /// It is generated once and kept, so scenes that use the generated script keep working after
+    /// the script is regenerated.
+    #[serde(default)]
+    pub native_type_uuid: Option<String>,
 
     next_node_id: u32,
     next_pin_id: u32,
@@ -463,6 +1591,9 @@ impl BlueprintGraph {
             nodes: BTreeMap::new(),
             links: Vec::new(),
             variables: Vec::new(),
+            interfaces: Vec::new(),
+            types: UserTypes::default(),
+            native_type_uuid: None,
             next_node_id: 1,
             next_pin_id: 1,
         }
@@ -496,6 +1627,163 @@ impl BlueprintGraph {
         self.graphs.push(GraphDef { name, kind });
     }
 
+    pub fn implements_interface(&self, name: &str) -> bool {
+        self.interfaces.iter().any(|i| i.name == name)
+    }
+
+    /// Implements the given interface: stores its signatures and creates a function graph with an
+    /// `InterfaceEvent` entry for every function that has no implementation yet. Implementing an
+    /// already implemented interface refreshes its signatures.
+    pub fn implement_interface(&mut self, interface: BlueprintInterface) {
+        for function in interface.functions.iter() {
+            let has_entry = self.nodes.values().any(|n| {
+                n.kind == BuiltinNodeKind::InterfaceEvent
+                    && n.properties.get("interface") == Some(&Value::String(interface.name.clone()))
+                    && n.properties.get("function") == Some(&Value::String(function.name.clone()))
+            });
+            if has_entry {
+                continue;
+            }
+
+            let graph_name = interface_graph_name(&interface.name, &function.name);
+            self.add_graph(graph_name.clone(), GraphKind::Function);
+
+            let mut entry = Node::interface_event(&interface.name, function);
+            entry.graph = graph_name;
+            self.add_node(entry);
+        }
+
+        if let Some(existing) = self
+            .interfaces
+            .iter_mut()
+            .find(|i| i.name == interface.name)
+        {
+            *existing = interface;
+        } else {
+            self.interfaces.push(interface);
+        }
+    }
+
+    /// Adds or updates a struct definition. Variable defaults of the struct are migrated (fields
+    /// that kept their name and type keep their value) and Make/Break nodes get pins for the new
+    /// fields; links of removed fields are dropped. Returns `false` if the definition is unchanged.
+    pub fn define_struct(&mut self, def: StructDef) -> bool {
+        if !self.types.define_struct(def.clone()) {
+            return false;
+        }
+        for kind in [BuiltinNodeKind::MakeStruct, BuiltinNodeKind::BreakStruct] {
+            self.reshape_user_type_nodes(kind, &def.name, def.data_type(), &def.fields);
+        }
+        self.migrate_variable_defaults();
+        true
+    }
+
+    /// Adds or updates an enum definition, see [`BlueprintGraph::define_struct`]. Values of removed
+    /// variants become the first variant.
+    pub fn define_enum(&mut self, def: EnumDef) -> bool {
+        if !self.types.define_enum(def.clone()) {
+            return false;
+        }
+        self.reshape_user_type_nodes(
+            BuiltinNodeKind::SwitchOnEnum,
+            &def.name,
+            def.data_type(),
+            &def.params(),
+        );
+        self.migrate_variable_defaults();
+        true
+    }
+
+    /// Whether a variable or a pin of the graph has the given user-defined type.
+    pub fn uses_type(&self, ty: DataType) -> bool {
+        self.variables.iter().any(|v| v.data_type == ty)
+            || self
+                .nodes
+                .values()
+                .flat_map(|n| n.pins.iter())
+                .any(|p| p.data_type == ty)
+    }
+
+    /// Updates the definitions the graph already has from `known` (usually loaded from type
+    /// assets). Returns the names of the types that changed; the graph has to be recompiled.
+    pub fn refresh_types(&mut self, known: &UserTypes) -> Vec<String> {
+        let mut changed = Vec::new();
+        for def in known.structs.iter() {
+            if self.types.struct_def(def.id()).is_some() && self.define_struct(def.clone()) {
+                changed.push(def.name.clone());
+            }
+        }
+        for def in known.enums.iter() {
+            if self.types.enum_def(def.id()).is_some() && self.define_enum(def.clone()) {
+                changed.push(def.name.clone());
+            }
+        }
+        changed
+    }
+
+    fn migrate_variable_defaults(&mut self) {
+        for var in self.variables.iter_mut() {
+            if matches!(var.data_type, DataType::Struct(_) | DataType::Enum(_)) {
+                var.default_value = Some(match var.default_value.take() {
+                    Some(value) => self.types.migrate_value(value),
+                    None => self.types.default_value(var.data_type),
+                });
+            }
+        }
+    }
+
+    /// Replaces the member pins of the nodes of a user-defined type, keeping the IDs (and links) of
+    /// pins whose name and direction did not change.
+    fn reshape_user_type_nodes(
+        &mut self,
+        kind: BuiltinNodeKind,
+        type_name: &str,
+        ty: DataType,
+        members: &[ParamDef],
+    ) {
+        let node_ids = self
+            .nodes
+            .iter()
+            .filter(|(_, n)| {
+                n.kind == kind
+                    && n.properties.get("type") == Some(&Value::String(type_name.to_string()))
+            })
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+
+        for node_id in node_ids {
+            let mut pins = user_type_pins(kind, ty, members);
+            let old_pins = std::mem::take(&mut self.nodes.get_mut(&node_id).unwrap().pins);
+            for pin in pins.iter_mut() {
+                match old_pins
+                    .iter()
+                    .find(|old| old.name == pin.name && old.direction == pin.direction)
+                {
+                    Some(old) => pin.id = old.id,
+                    None => {
+                        pin.id = PinId(self.next_pin_id);
+                        self.next_pin_id += 1;
+                    }
+                }
+            }
+
+            // Drop links of removed pins and of pins that changed their type.
+            let stale = old_pins
+                .iter()
+                .filter(|old| {
+                    !pins
+                        .iter()
+                        .any(|pin| pin.id == old.id && pin.data_type == old.data_type)
+                })
+                .map(|old| old.id)
+                .collect::<Vec<_>>();
+            self.links
+                .retain(|l| !stale.contains(&l.from) && !stale.contains(&l.to));
+
+            self.nodes.get_mut(&node_id).unwrap().pins = pins;
+        }
+    }
+
     pub fn add_node(&mut self, mut node: Node) -> NodeId {
         let node_id = NodeId(self.next_node_id);
         self.next_node_id += 1;
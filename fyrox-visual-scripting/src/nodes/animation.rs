@@ -0,0 +1,182 @@
+//! Animation nodes (ABSM parameters, animation playback and signals).
+
+use super::{NodeCategory, NodeDefinition, PinDef, PropertyDef};
+use crate::model::{DataType, Value};
+
+/// SetAbsmParameter node - sets a Rule, Weight or Index parameter of a state machine.
+///
+/// The type of the `value` input depends on the parameter kind, see
+/// [`Node::set_absm_parameter`](crate::model::Node::set_absm_parameter).
+pub struct SetAbsmParameterNode;
+
+impl NodeDefinition for SetAbsmParameterNode {
+    fn kind_name(&self) -> &'static str {
+        "SetAbsmParameter"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Set ABSM Parameter"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Utility
+    }
+
+    fn description(&self) -> &'static str {
+        "Sets a parameter of the animation state machine of an actor."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_in("exec"),
+            PinDef::exec_out("then"),
+            PinDef::input("target", DataType::String),
+            PinDef::input("name", DataType::String),
+            PinDef::input("value", DataType::Bool),
+        ]
+    }
+
+    fn properties(&self) -> Vec<PropertyDef> {
+        vec![PropertyDef::new(
+            "parameter",
+            Value::String("Rule".to_string()),
+        )]
+    }
+}
+
+/// GetActiveState node - returns the name of the active state of a state machine layer.
+pub struct GetActiveStateNode;
+
+impl NodeDefinition for GetActiveStateNode {
+    fn kind_name(&self) -> &'static str {
+        "GetActiveState"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Get Active State"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Utility
+    }
+
+    fn description(&self) -> &'static str {
+        "Returns the active state of a layer of the actor's animation state machine."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::input("target", DataType::String),
+            PinDef::input("layer", DataType::I32),
+            PinDef::output("state", DataType::String),
+        ]
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+}
+
+/// PlayAnimation node - enables an animation of an animation player and rewinds it.
+pub struct PlayAnimationNode;
+
+impl NodeDefinition for PlayAnimationNode {
+    fn kind_name(&self) -> &'static str {
+        "PlayAnimation"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Play Animation"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Utility
+    }
+
+    fn description(&self) -> &'static str {
+        "Plays the named animation of the actor's animation player from the start."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_in("exec"),
+            PinDef::exec_out("then"),
+            PinDef::input("target", DataType::String),
+            PinDef::input("animation", DataType::String),
+        ]
+    }
+}
+
+/// StopAnimation node - disables an animation of an animation player.
+pub struct StopAnimationNode;
+
+impl NodeDefinition for StopAnimationNode {
+    fn kind_name(&self) -> &'static str {
+        "StopAnimation"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Stop Animation"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Utility
+    }
+
+    fn description(&self) -> &'static str {
+        "Stops the named animation of the actor's animation player."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_in("exec"),
+            PinDef::exec_out("then"),
+            PinDef::input("target", DataType::String),
+            PinDef::input("animation", DataType::String),
+        ]
+    }
+}
+
+/// OnAnimationSignal node - fires when an animation signal or a state machine event is raised.
+///
+/// State machine events are named after their state, see
+/// [`state_enter_signal`](crate::animation::state_enter_signal).
+pub struct OnAnimationSignalNode;
+
+impl NodeDefinition for OnAnimationSignalNode {
+    fn kind_name(&self) -> &'static str {
+        "OnAnimationSignal"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "On Animation Signal"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Event
+    }
+
+    fn description(&self) -> &'static str {
+        "Fires when an animation of the actor passes the named signal or its state machine enters \
+         or leaves a state."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_out("then"),
+            PinDef::output("source", DataType::String),
+        ]
+    }
+
+    fn properties(&self) -> Vec<PropertyDef> {
+        vec![PropertyDef::new("signal", Value::String(String::new()))]
+    }
+
+    fn is_entry(&self) -> bool {
+        true
+    }
+
+    fn allowed_graphs(&self) -> Vec<&'static str> {
+        vec!["EventGraph"]
+    }
+}
@@ -0,0 +1,81 @@
+//! Assert and ExpectEqual nodes, checked by the headless blueprint test runner.
+
+use super::{NodeCategory, NodeDefinition, PinDef, PropertyDef};
+use crate::model::{DataType, Value};
+
+/// Assert node - records a failed assertion if the condition is false.
+pub struct AssertNode;
+
+impl NodeDefinition for AssertNode {
+    fn kind_name(&self) -> &'static str {
+        "Assert"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Assert"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Utility
+    }
+
+    fn description(&self) -> &'static str {
+        "Fails the blueprint test if the condition is false."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_in("exec"),
+            PinDef::exec_out("then"),
+            PinDef::input("condition", DataType::Bool),
+            PinDef::input("message", DataType::String),
+        ]
+    }
+
+    fn properties(&self) -> Vec<PropertyDef> {
+        vec![PropertyDef::new("message", Value::String(String::new())).inline()]
+    }
+
+    fn inline_property_key(&self) -> Option<&'static str> {
+        Some("message")
+    }
+}
+
+/// ExpectEqual node - records a failed assertion if the two inputs differ.
+pub struct ExpectEqualNode;
+
+impl NodeDefinition for ExpectEqualNode {
+    fn kind_name(&self) -> &'static str {
+        "ExpectEqual"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Expect Equal"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Utility
+    }
+
+    fn description(&self) -> &'static str {
+        "Fails the blueprint test if the actual value differs from the expected one."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_in("exec"),
+            PinDef::exec_out("then"),
+            PinDef::input("expected", DataType::String),
+            PinDef::input("actual", DataType::String),
+            PinDef::input("message", DataType::String),
+        ]
+    }
+
+    fn properties(&self) -> Vec<PropertyDef> {
+        vec![PropertyDef::new("message", Value::String(String::new())).inline()]
+    }
+
+    fn inline_property_key(&self) -> Option<&'static str> {
+        Some("message")
+    }
+}
@@ -0,0 +1,75 @@
+//! Behavior tree task nodes.
+
+use super::{NodeCategory, NodeDefinition, PinDef, PropertyDef};
+use crate::model::{DataType, Value};
+
+/// BehaviorTask node - entry point of a leaf task of a behavior tree.
+pub struct BehaviorTaskNode;
+
+impl NodeDefinition for BehaviorTaskNode {
+    fn kind_name(&self) -> &'static str {
+        "BehaviorTask"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Behavior Task"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Event
+    }
+
+    fn description(&self) -> &'static str {
+        "Runs every time the behavior tree of the actor ticks the task with the given name. The \
+         task keeps running until the graph reaches Finish Task."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![PinDef::exec_out("then")]
+    }
+
+    fn properties(&self) -> Vec<PropertyDef> {
+        vec![PropertyDef::new("task", Value::String(String::new()))]
+    }
+
+    fn is_entry(&self) -> bool {
+        true
+    }
+
+    fn allowed_graphs(&self) -> Vec<&'static str> {
+        // Lives in a function graph of the blueprint.
+        vec![]
+    }
+}
+
+/// FinishTask node - reports the outcome of the behavior tree task.
+pub struct FinishTaskNode;
+
+impl NodeDefinition for FinishTaskNode {
+    fn kind_name(&self) -> &'static str {
+        "FinishTask"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Finish Task"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::FlowControl
+    }
+
+    fn description(&self) -> &'static str {
+        "Finishes the behavior tree task, which succeeds or fails depending on the input."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_in("exec"),
+            PinDef::input("success", DataType::Bool),
+        ]
+    }
+
+    fn allowed_graphs(&self) -> Vec<&'static str> {
+        vec![]
+    }
+}
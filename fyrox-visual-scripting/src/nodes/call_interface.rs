@@ -0,0 +1,43 @@
+//! Call Interface node.
+
+use super::{NodeCategory, NodeDefinition, PinDef, PropertyDef};
+use crate::model::{DataType, Value};
+
+/// CallInterface node - sends an interface message to an actor.
+///
+/// The function runs on whatever blueprint the target carries; nothing happens if it does not
+/// implement the interface. Parameter input pins depend on the function signature.
+pub struct CallInterfaceNode;
+
+impl NodeDefinition for CallInterfaceNode {
+    fn kind_name(&self) -> &'static str {
+        "CallInterface"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Call Interface Function"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Utility
+    }
+
+    fn description(&self) -> &'static str {
+        "Calls an interface function on the target actor. Does nothing if it is not implemented."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_in("exec"),
+            PinDef::exec_out("then"),
+            PinDef::input("target", DataType::String),
+        ]
+    }
+
+    fn properties(&self) -> Vec<PropertyDef> {
+        vec![
+            PropertyDef::new("interface", Value::String(String::new())),
+            PropertyDef::new("function", Value::String(String::new())),
+        ]
+    }
+}
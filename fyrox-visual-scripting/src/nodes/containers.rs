@@ -0,0 +1,265 @@
+//! Array, set and map nodes.
+//!
+//! Pins are typed for an array of strings; the editor retypes them for the container variable
+//! wired into `target` (see [`Node::set_container_type`](crate::model::Node::set_container_type)).
+
+use super::{NodeCategory, NodeDefinition, PinDef};
+use crate::model::{DataType, ElementType};
+
+const TARGET: DataType = DataType::Array(ElementType::String);
+
+/// Add node - appends to an array, inserts into a set or sets a map entry.
+pub struct ContainerAddNode;
+
+impl NodeDefinition for ContainerAddNode {
+    fn kind_name(&self) -> &'static str {
+        "ContainerAdd"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Add"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Variable
+    }
+
+    fn description(&self) -> &'static str {
+        "Adds an element to an array or a set, or sets the value of a map key."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_in("exec"),
+            PinDef::exec_out("then"),
+            PinDef::input("target", TARGET),
+            PinDef::input("value", DataType::String),
+        ]
+    }
+}
+
+/// Remove node - removes an array index, a set element or a map key.
+pub struct ContainerRemoveNode;
+
+impl NodeDefinition for ContainerRemoveNode {
+    fn kind_name(&self) -> &'static str {
+        "ContainerRemove"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Remove"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Variable
+    }
+
+    fn description(&self) -> &'static str {
+        "Removes the element at an array index, a set element or a map key."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_in("exec"),
+            PinDef::exec_out("then"),
+            PinDef::input("target", TARGET),
+            PinDef::input("key", DataType::I32),
+            PinDef::output("removed", DataType::Bool),
+        ]
+    }
+}
+
+/// Get node - reads an array index or a map key.
+pub struct ContainerGetNode;
+
+impl NodeDefinition for ContainerGetNode {
+    fn kind_name(&self) -> &'static str {
+        "ContainerGet"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Get Element"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Variable
+    }
+
+    fn description(&self) -> &'static str {
+        "Gets the element at an array index or the value of a map key."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::input("target", TARGET),
+            PinDef::input("key", DataType::I32),
+            PinDef::output("value", DataType::String),
+            PinDef::output("found", DataType::Bool),
+        ]
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+}
+
+/// Length node - number of elements of a container.
+pub struct ContainerLengthNode;
+
+impl NodeDefinition for ContainerLengthNode {
+    fn kind_name(&self) -> &'static str {
+        "ContainerLength"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Length"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Variable
+    }
+
+    fn description(&self) -> &'static str {
+        "Gets the number of elements of an array, a set or a map."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::input("target", TARGET),
+            PinDef::output("length", DataType::I32),
+        ]
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+}
+
+/// Contains node - membership test.
+pub struct ContainerContainsNode;
+
+impl NodeDefinition for ContainerContainsNode {
+    fn kind_name(&self) -> &'static str {
+        "ContainerContains"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Contains"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Variable
+    }
+
+    fn description(&self) -> &'static str {
+        "Checks whether an array or a set has the element, or a map has the key."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::input("target", TARGET),
+            PinDef::input("item", DataType::String),
+            PinDef::output("result", DataType::Bool),
+        ]
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+}
+
+/// Find node - reverse lookup of an element.
+pub struct ContainerFindNode;
+
+impl NodeDefinition for ContainerFindNode {
+    fn kind_name(&self) -> &'static str {
+        "ContainerFind"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Find"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Variable
+    }
+
+    fn description(&self) -> &'static str {
+        "Finds the array index (or -1) or the map key of the first element equal to the item."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::input("target", TARGET),
+            PinDef::input("item", DataType::String),
+            PinDef::output("key", DataType::I32),
+            PinDef::output("found", DataType::Bool),
+        ]
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+}
+
+/// Clear node - removes every element.
+pub struct ContainerClearNode;
+
+impl NodeDefinition for ContainerClearNode {
+    fn kind_name(&self) -> &'static str {
+        "ContainerClear"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Clear"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Variable
+    }
+
+    fn description(&self) -> &'static str {
+        "Removes every element of an array, a set or a map."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_in("exec"),
+            PinDef::exec_out("then"),
+            PinDef::input("target", TARGET),
+        ]
+    }
+}
+
+/// ForEach node - loops over the elements of a container.
+pub struct ForEachNode;
+
+impl NodeDefinition for ForEachNode {
+    fn kind_name(&self) -> &'static str {
+        "ForEach"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "For Each"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::FlowControl
+    }
+
+    fn description(&self) -> &'static str {
+        "Runs the loop body for every element (and its index or key), then continues with \
+        completed."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_in("exec"),
+            PinDef::input("target", TARGET),
+            PinDef::exec_out("loop_body"),
+            PinDef::output("key", DataType::I32),
+            PinDef::output("element", DataType::String),
+            PinDef::exec_out("completed"),
+        ]
+    }
+}
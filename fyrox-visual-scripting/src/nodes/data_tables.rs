@@ -0,0 +1,73 @@
+//! Data table nodes.
+//!
+//! Rows are typed as maps from column names to cell text; the editor creates nodes that read rows
+//! as a user-defined struct (see [`Node::data_table`](crate::model::Node::data_table)).
+
+use super::{NodeCategory, NodeDefinition, PinDef};
+use crate::model::{DataType, ElementType};
+
+const ROW: DataType = DataType::Map(ElementType::String, ElementType::String);
+
+/// GetDataTableRow node - reads a row of a data table.
+pub struct GetDataTableRowNode;
+
+impl NodeDefinition for GetDataTableRowNode {
+    fn kind_name(&self) -> &'static str {
+        "GetDataTableRow"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Get Data Table Row"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Utility
+    }
+
+    fn description(&self) -> &'static str {
+        "The row with the given key of the data table asset at the table path. Fields of a row \
+         struct are filled from the columns of the same name."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::input("table", DataType::String),
+            PinDef::input("key", DataType::String),
+            PinDef::output("found", DataType::Bool),
+            PinDef::output("row", ROW),
+        ]
+    }
+}
+
+/// ForEachRow node - loops over the rows of a data table.
+pub struct ForEachRowNode;
+
+impl NodeDefinition for ForEachRowNode {
+    fn kind_name(&self) -> &'static str {
+        "ForEachRow"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "For Each Row"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::FlowControl
+    }
+
+    fn description(&self) -> &'static str {
+        "Runs the loop body for every row of the data table (and its key), then continues with \
+         completed."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_in("exec"),
+            PinDef::input("table", DataType::String),
+            PinDef::exec_out("loop_body"),
+            PinDef::output("key", DataType::String),
+            PinDef::output("row", ROW),
+            PinDef::exec_out("completed"),
+        ]
+    }
+}
@@ -0,0 +1,44 @@
+//! Does Implement Interface node.
+
+use super::{NodeCategory, NodeDefinition, PinDef, PropertyDef};
+use crate::model::{DataType, Value};
+
+/// DoesImplementInterface node - checks whether an actor implements an interface.
+pub struct DoesImplementInterfaceNode;
+
+impl NodeDefinition for DoesImplementInterfaceNode {
+    fn kind_name(&self) -> &'static str {
+        "DoesImplementInterface"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Does Implement Interface"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Utility
+    }
+
+    fn description(&self) -> &'static str {
+        "Returns true if the target actor's blueprint implements the interface."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::input("target", DataType::String),
+            PinDef::output("result", DataType::Bool),
+        ]
+    }
+
+    fn properties(&self) -> Vec<PropertyDef> {
+        vec![PropertyDef::new("interface", Value::String(String::new())).inline()]
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn inline_property_key(&self) -> Option<&'static str> {
+        Some("interface")
+    }
+}
@@ -0,0 +1,48 @@
+//! Interface event node.
+
+use super::{NodeCategory, NodeDefinition, PinDef, PropertyDef};
+use crate::model::Value;
+
+/// Interface event - entry point of an interface function implementation.
+///
+/// Parameter output pins depend on the function signature and are created by
+/// `BlueprintGraph::implement_interface`.
+pub struct InterfaceEventNode;
+
+impl NodeDefinition for InterfaceEventNode {
+    fn kind_name(&self) -> &'static str {
+        "InterfaceEvent"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Interface Event"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Event
+    }
+
+    fn description(&self) -> &'static str {
+        "Fires when another actor calls this interface function on this actor."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![PinDef::exec_out("then")]
+    }
+
+    fn properties(&self) -> Vec<PropertyDef> {
+        vec![
+            PropertyDef::new("interface", Value::String(String::new())),
+            PropertyDef::new("function", Value::String(String::new())),
+        ]
+    }
+
+    fn is_entry(&self) -> bool {
+        true
+    }
+
+    fn allowed_graphs(&self) -> Vec<&'static str> {
+        // Lives in the function graph generated for the implementation.
+        vec![]
+    }
+}
@@ -0,0 +1,190 @@
+//! Level blueprint nodes (scene events, scene loading, actor queries and global variables).
+
+use super::{NodeCategory, NodeDefinition, PinDef};
+use crate::model::{DataType, ElementType};
+
+/// OnSceneLoaded node - fires when a scene finishes loading.
+pub struct OnSceneLoadedNode;
+
+impl NodeDefinition for OnSceneLoadedNode {
+    fn kind_name(&self) -> &'static str {
+        "OnSceneLoaded"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "On Scene Loaded"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Event
+    }
+
+    fn description(&self) -> &'static str {
+        "Fires in level blueprints when a scene finishes loading. Outputs the path of the scene."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_out("then"),
+            PinDef::output("scene", DataType::String),
+        ]
+    }
+
+    fn is_entry(&self) -> bool {
+        true
+    }
+
+    fn allowed_graphs(&self) -> Vec<&'static str> {
+        vec!["EventGraph"]
+    }
+}
+
+/// OnSceneUnloaded node - fires when a loaded scene is removed.
+pub struct OnSceneUnloadedNode;
+
+impl NodeDefinition for OnSceneUnloadedNode {
+    fn kind_name(&self) -> &'static str {
+        "OnSceneUnloaded"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "On Scene Unloaded"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Event
+    }
+
+    fn description(&self) -> &'static str {
+        "Fires in level blueprints when a loaded scene is removed. Outputs the path of the scene."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_out("then"),
+            PinDef::output("scene", DataType::String),
+        ]
+    }
+
+    fn is_entry(&self) -> bool {
+        true
+    }
+
+    fn allowed_graphs(&self) -> Vec<&'static str> {
+        vec!["EventGraph"]
+    }
+}
+
+/// LoadScene node - requests loading of a scene.
+pub struct LoadSceneNode;
+
+impl NodeDefinition for LoadSceneNode {
+    fn kind_name(&self) -> &'static str {
+        "LoadScene"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Load Scene"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Utility
+    }
+
+    fn description(&self) -> &'static str {
+        "Starts loading the scene at the given path. The scene replaces the current level once it \
+         is loaded."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_in("exec"),
+            PinDef::exec_out("then"),
+            PinDef::input("path", DataType::String),
+        ]
+    }
+}
+
+/// GetActorsWithBlueprint node - finds the actors that run a blueprint.
+pub struct GetActorsWithBlueprintNode;
+
+impl NodeDefinition for GetActorsWithBlueprintNode {
+    fn kind_name(&self) -> &'static str {
+        "GetActorsWithBlueprint"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Get Actors With Blueprint"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Utility
+    }
+
+    fn description(&self) -> &'static str {
+        "Names of the actors that run the blueprint asset at the given path."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::input("blueprint", DataType::String),
+            PinDef::output("actors", DataType::Array(ElementType::String)),
+        ]
+    }
+}
+
+/// GetGlobal node - reads a variable of the running level blueprints.
+pub struct GetGlobalNode;
+
+impl NodeDefinition for GetGlobalNode {
+    fn kind_name(&self) -> &'static str {
+        "GetGlobal"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Get Global"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Variable
+    }
+
+    fn description(&self) -> &'static str {
+        "Value of the variable of a level blueprint with the given name, or the default value if \
+         no level blueprint has it. Level blueprints read their own variables."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![PinDef::output("value", DataType::String)]
+    }
+}
+
+/// SetGlobal node - changes a variable of the running level blueprints.
+pub struct SetGlobalNode;
+
+impl NodeDefinition for SetGlobalNode {
+    fn kind_name(&self) -> &'static str {
+        "SetGlobal"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Set Global"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Variable
+    }
+
+    fn description(&self) -> &'static str {
+        "Changes the variable of a level blueprint with the given name. Level blueprints change \
+         their own variables."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_in("exec"),
+            PinDef::exec_out("then"),
+            PinDef::input("value", DataType::String),
+        ]
+    }
+}
@@ -8,37 +8,65 @@
 
 mod base;
 
+mod animation;
+mod assert;
 mod begin_play;
+mod behavior;
 mod branch;
+mod call_interface;
 mod construction_script;
+mod containers;
+mod data_tables;
+mod does_implement_interface;
 mod get_actor_by_name;
 mod get_actor_name;
 mod get_actor_transform;
 mod get_variable;
+mod interface_event;
+mod level;
 mod print;
 mod rhai_script;
 mod self_node;
 mod set_actor_transform;
 mod set_variable;
+mod sound;
 mod spawn_actor;
+mod tags;
 mod tick;
+mod timers;
+mod user_types;
+mod widgets;
 
 pub use base::*;
 
+pub use animation::*;
+pub use assert::*;
 pub use begin_play::*;
+pub use behavior::*;
 pub use branch::*;
+pub use call_interface::*;
 pub use construction_script::*;
+pub use containers::*;
+pub use data_tables::*;
+pub use does_implement_interface::*;
 pub use get_actor_by_name::*;
 pub use get_actor_name::*;
 pub use get_actor_transform::*;
 pub use get_variable::*;
+pub use interface_event::*;
+pub use level::*;
 pub use print::*;
 pub use rhai_script::*;
 pub use self_node::*;
 pub use set_actor_transform::*;
 pub use set_variable::*;
+pub use sound::*;
 pub use spawn_actor::*;
+pub use tags::*;
 pub use tick::*;
+pub use timers::*;
+pub use user_types::*;
+pub use widgets::*;
 
 /// Registry of all available node definitions.
 /// Used by the editor to populate the node palette.
@@ -50,12 +78,25 @@ pub fn all_node_definitions() -> Vec<&'static dyn NodeDefinition> {
         &ConstructionScriptNode,
         // Flow Control
         &BranchNode,
+        &ForEachNode,
+        &SwitchOnEnumNode,
         // Utilities
         &PrintNode,
         &RhaiScriptNode,
         // Variables
         &GetVariableNode,
         &SetVariableNode,
+        // Containers
+        &ContainerAddNode,
+        &ContainerRemoveNode,
+        &ContainerGetNode,
+        &ContainerLengthNode,
+        &ContainerContainsNode,
+        &ContainerFindNode,
+        &ContainerClearNode,
+        // Structs
+        &MakeStructNode,
+        &BreakStructNode,
         // World Interaction
         &SelfNode,
         &GetActorTransformNode,
@@ -63,6 +104,53 @@ pub fn all_node_definitions() -> Vec<&'static dyn NodeDefinition> {
         &SpawnActorNode,
         &GetActorByNameNode,
         &GetActorNameNode,
+        // Interfaces
+        &InterfaceEventNode,
+        &CallInterfaceNode,
+        &DoesImplementInterfaceNode,
+        // User Interface
+        &WidgetEventNode,
+        &SetWidgetPropertyNode,
+        // Animation
+        &OnAnimationSignalNode,
+        &SetAbsmParameterNode,
+        &GetActiveStateNode,
+        &PlayAnimationNode,
+        &StopAnimationNode,
+        // Sound
+        &OnSoundFinishedNode,
+        &PlaySoundNode,
+        &StopSoundNode,
+        &SpawnSoundNode,
+        &SetSoundPropertyNode,
+        &FadeInSoundNode,
+        &FadeOutSoundNode,
+        &SetSoundBusNode,
+        // Level
+        &OnSceneLoadedNode,
+        &OnSceneUnloadedNode,
+        &LoadSceneNode,
+        &GetActorsWithBlueprintNode,
+        &GetGlobalNode,
+        &SetGlobalNode,
+        // Tags
+        &GetActorsWithTagNode,
+        &HasTagNode,
+        &AddTagNode,
+        &RemoveTagNode,
+        // Timers
+        &CustomEventNode,
+        &SetTimerByEventNode,
+        &ClearTimerNode,
+        // Data tables
+        &GetDataTableRowNode,
+        &ForEachRowNode,
+        // Behavior trees
+        &BehaviorTaskNode,
+        &FinishTaskNode,
+        // Testing
+        &AssertNode,
+        &ExpectEqualNode,
     ]
 }
 
@@ -123,5 +211,11 @@ pub fn pin_color_for_type(data_type: DataType) -> (u8, u8, u8) {
         DataType::F32 => (120, 220, 100),      // Bright green
         DataType::String => (255, 100, 220),   // Hot pink/magenta
         DataType::Unit => (160, 160, 160),     // Light gray
+        // Containers share the color of their elements (or values, for maps).
+        DataType::Array(element) | DataType::Set(element) | DataType::Map(_, element) => {
+            pin_color_for_type(element.data_type())
+        }
+        DataType::Struct(_) => (0, 90, 200),   // Deep blue
+        DataType::Enum(_) => (0, 140, 120),    // Teal
     }
 }
@@ -0,0 +1,266 @@
+//! Sound nodes (playback, one-shot sounds, fades and audio buses).
+
+use super::{NodeCategory, NodeDefinition, PinDef, PropertyDef};
+use crate::model::{DataType, Value};
+
+/// PlaySound node - starts playing a sound node.
+pub struct PlaySoundNode;
+
+impl NodeDefinition for PlaySoundNode {
+    fn kind_name(&self) -> &'static str {
+        "PlaySound"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Play Sound"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Utility
+    }
+
+    fn description(&self) -> &'static str {
+        "Starts playing the sound node with the given name."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_in("exec"),
+            PinDef::exec_out("then"),
+            PinDef::input("sound", DataType::String),
+        ]
+    }
+}
+
+/// StopSound node - stops a sound node and rewinds it.
+pub struct StopSoundNode;
+
+impl NodeDefinition for StopSoundNode {
+    fn kind_name(&self) -> &'static str {
+        "StopSound"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Stop Sound"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Utility
+    }
+
+    fn description(&self) -> &'static str {
+        "Stops the sound node with the given name."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_in("exec"),
+            PinDef::exec_out("then"),
+            PinDef::input("sound", DataType::String),
+        ]
+    }
+}
+
+/// SpawnSound node - plays a sound buffer once at a position.
+pub struct SpawnSoundNode;
+
+impl NodeDefinition for SpawnSoundNode {
+    fn kind_name(&self) -> &'static str {
+        "SpawnSound"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Spawn Sound At Location"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Utility
+    }
+
+    fn description(&self) -> &'static str {
+        "Plays a sound buffer resource once at a world position; the sound is removed when it ends."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_in("exec"),
+            PinDef::exec_out("then"),
+            PinDef::input("buffer", DataType::String),
+            PinDef::input("x", DataType::F32),
+            PinDef::input("y", DataType::F32),
+            PinDef::input("z", DataType::F32),
+            PinDef::input("gain", DataType::F32),
+        ]
+    }
+}
+
+/// SetSoundProperty node - changes the gain, pitch or looping of a sound node.
+///
+/// The type of the `value` input depends on the property, see
+/// [`Node::set_sound_property`](crate::model::Node::set_sound_property).
+pub struct SetSoundPropertyNode;
+
+impl NodeDefinition for SetSoundPropertyNode {
+    fn kind_name(&self) -> &'static str {
+        "SetSoundProperty"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Set Sound Property"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Utility
+    }
+
+    fn description(&self) -> &'static str {
+        "Changes the gain, pitch or looping of the sound node with the given name."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_in("exec"),
+            PinDef::exec_out("then"),
+            PinDef::input("sound", DataType::String),
+            PinDef::input("value", DataType::F32),
+        ]
+    }
+
+    fn properties(&self) -> Vec<PropertyDef> {
+        vec![PropertyDef::new(
+            "property",
+            Value::String("Gain".to_string()),
+        )]
+    }
+}
+
+/// FadeInSound node - plays a sound node with its gain rising from silence.
+pub struct FadeInSoundNode;
+
+impl NodeDefinition for FadeInSoundNode {
+    fn kind_name(&self) -> &'static str {
+        "FadeInSound"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Fade In Sound"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Utility
+    }
+
+    fn description(&self) -> &'static str {
+        "Plays a sound node and raises its gain to full over the duration. Continues from \
+         'completed' when the fade is over."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_in("exec"),
+            PinDef::exec_out("then"),
+            PinDef::exec_out("completed"),
+            PinDef::input("sound", DataType::String),
+            PinDef::input("duration", DataType::F32),
+        ]
+    }
+}
+
+/// FadeOutSound node - lowers the gain of a sound node to silence and stops it.
+pub struct FadeOutSoundNode;
+
+impl NodeDefinition for FadeOutSoundNode {
+    fn kind_name(&self) -> &'static str {
+        "FadeOutSound"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Fade Out Sound"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Utility
+    }
+
+    fn description(&self) -> &'static str {
+        "Lowers the gain of a sound node to silence over the duration, then stops it. Continues \
+         from 'completed' when the fade is over."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_in("exec"),
+            PinDef::exec_out("then"),
+            PinDef::exec_out("completed"),
+            PinDef::input("sound", DataType::String),
+            PinDef::input("duration", DataType::F32),
+        ]
+    }
+}
+
+/// SetSoundBus node - routes a sound node to an audio bus.
+pub struct SetSoundBusNode;
+
+impl NodeDefinition for SetSoundBusNode {
+    fn kind_name(&self) -> &'static str {
+        "SetSoundBus"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Set Sound Bus"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Utility
+    }
+
+    fn description(&self) -> &'static str {
+        "Routes the sound node to the audio bus with the given name."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_in("exec"),
+            PinDef::exec_out("then"),
+            PinDef::input("sound", DataType::String),
+            PinDef::input("bus", DataType::String),
+        ]
+    }
+}
+
+/// OnSoundFinished node - fires when the named sound node stops playing on its own.
+pub struct OnSoundFinishedNode;
+
+impl NodeDefinition for OnSoundFinishedNode {
+    fn kind_name(&self) -> &'static str {
+        "OnSoundFinished"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "On Sound Finished"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Event
+    }
+
+    fn description(&self) -> &'static str {
+        "Fires when the sound node with the given name stops playing."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![PinDef::exec_out("then")]
+    }
+
+    fn properties(&self) -> Vec<PropertyDef> {
+        vec![PropertyDef::new("sound", Value::String(String::new()))]
+    }
+
+    fn is_entry(&self) -> bool {
+        true
+    }
+
+    fn allowed_graphs(&self) -> Vec<&'static str> {
+        vec!["EventGraph"]
+    }
+}
@@ -0,0 +1,122 @@
+//! Actor tag nodes.
+
+use super::{NodeCategory, NodeDefinition, PinDef};
+use crate::model::{DataType, ElementType};
+
+/// GetActorsWithTag node - finds the actors with a tag.
+pub struct GetActorsWithTagNode;
+
+impl NodeDefinition for GetActorsWithTagNode {
+    fn kind_name(&self) -> &'static str {
+        "GetActorsWithTag"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Get Actors With Tag"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Utility
+    }
+
+    fn description(&self) -> &'static str {
+        "Names of the actors that have the tag. Uses the tag index of the scene, so it is cheap \
+         even in large scenes."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::input("tag", DataType::String),
+            PinDef::output("actors", DataType::Array(ElementType::String)),
+        ]
+    }
+}
+
+/// HasTag node - checks whether an actor has a tag.
+pub struct HasTagNode;
+
+impl NodeDefinition for HasTagNode {
+    fn kind_name(&self) -> &'static str {
+        "HasTag"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Has Tag"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Utility
+    }
+
+    fn description(&self) -> &'static str {
+        "Whether the target actor has the tag."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::input("target", DataType::String),
+            PinDef::input("tag", DataType::String),
+            PinDef::output("result", DataType::Bool),
+        ]
+    }
+}
+
+/// AddTag node - adds a tag to an actor.
+pub struct AddTagNode;
+
+impl NodeDefinition for AddTagNode {
+    fn kind_name(&self) -> &'static str {
+        "AddTag"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Add Tag"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Utility
+    }
+
+    fn description(&self) -> &'static str {
+        "Adds the tag to the target actor. Does nothing if the actor already has it."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_in("exec"),
+            PinDef::exec_out("then"),
+            PinDef::input("target", DataType::String),
+            PinDef::input("tag", DataType::String),
+        ]
+    }
+}
+
+/// RemoveTag node - removes a tag from an actor.
+pub struct RemoveTagNode;
+
+impl NodeDefinition for RemoveTagNode {
+    fn kind_name(&self) -> &'static str {
+        "RemoveTag"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Remove Tag"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Utility
+    }
+
+    fn description(&self) -> &'static str {
+        "Removes the tag from the target actor."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_in("exec"),
+            PinDef::exec_out("then"),
+            PinDef::input("target", DataType::String),
+            PinDef::input("tag", DataType::String),
+        ]
+    }
+}
@@ -0,0 +1,102 @@
+//! Timer nodes and the custom events they trigger.
+
+use super::{NodeCategory, NodeDefinition, PinDef, PropertyDef};
+use crate::model::{DataType, Value};
+
+/// CustomEvent node - an event of the graph that timers can trigger.
+pub struct CustomEventNode;
+
+impl NodeDefinition for CustomEventNode {
+    fn kind_name(&self) -> &'static str {
+        "CustomEvent"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Custom Event"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Event
+    }
+
+    fn description(&self) -> &'static str {
+        "An event with the given name. Fires when a timer started by Set Timer By Event fires."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![PinDef::exec_out("then")]
+    }
+
+    fn properties(&self) -> Vec<PropertyDef> {
+        vec![PropertyDef::new("event", Value::String(String::new()))]
+    }
+
+    fn is_entry(&self) -> bool {
+        true
+    }
+
+    fn allowed_graphs(&self) -> Vec<&'static str> {
+        vec!["EventGraph"]
+    }
+}
+
+/// SetTimerByEvent node - starts a timer that fires a custom event.
+pub struct SetTimerByEventNode;
+
+impl NodeDefinition for SetTimerByEventNode {
+    fn kind_name(&self) -> &'static str {
+        "SetTimerByEvent"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Set Timer By Event"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::FlowControl
+    }
+
+    fn description(&self) -> &'static str {
+        "Fires the custom event with the given name after the given time (in seconds), or every \
+         time the time passes if looping is set. Replaces the running timer of the event."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_in("exec"),
+            PinDef::exec_out("then"),
+            PinDef::input("event", DataType::String),
+            PinDef::input("time", DataType::F32),
+            PinDef::input("looping", DataType::Bool),
+        ]
+    }
+}
+
+/// ClearTimer node - stops the timer of a custom event.
+pub struct ClearTimerNode;
+
+impl NodeDefinition for ClearTimerNode {
+    fn kind_name(&self) -> &'static str {
+        "ClearTimer"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Clear Timer"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::FlowControl
+    }
+
+    fn description(&self) -> &'static str {
+        "Stops the timer of the custom event with the given name."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_in("exec"),
+            PinDef::exec_out("then"),
+            PinDef::input("event", DataType::String),
+        ]
+    }
+}
@@ -0,0 +1,102 @@
+//! Make Struct, Break Struct and Switch on Enum nodes.
+
+use super::{NodeCategory, NodeDefinition, PinDef, PropertyDef};
+use crate::model::Value;
+
+/// MakeStruct node - builds a struct value.
+///
+/// Field input pins depend on the struct definition, see
+/// [`Node::make_struct`](crate::model::Node::make_struct).
+pub struct MakeStructNode;
+
+impl NodeDefinition for MakeStructNode {
+    fn kind_name(&self) -> &'static str {
+        "MakeStruct"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Make Struct"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Variable
+    }
+
+    fn description(&self) -> &'static str {
+        "Builds a struct value from its fields. Unconnected fields get their defaults."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        Vec::new()
+    }
+
+    fn properties(&self) -> Vec<PropertyDef> {
+        vec![PropertyDef::new("type", Value::String(String::new()))]
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+}
+
+/// BreakStruct node - splits a struct value into its fields.
+pub struct BreakStructNode;
+
+impl NodeDefinition for BreakStructNode {
+    fn kind_name(&self) -> &'static str {
+        "BreakStruct"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Break Struct"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Variable
+    }
+
+    fn description(&self) -> &'static str {
+        "Reads every field of a struct value."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        Vec::new()
+    }
+
+    fn properties(&self) -> Vec<PropertyDef> {
+        vec![PropertyDef::new("type", Value::String(String::new()))]
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+}
+
+/// SwitchOnEnum node - one exec output per enum variant.
+pub struct SwitchOnEnumNode;
+
+impl NodeDefinition for SwitchOnEnumNode {
+    fn kind_name(&self) -> &'static str {
+        "SwitchOnEnum"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Switch on Enum"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::FlowControl
+    }
+
+    fn description(&self) -> &'static str {
+        "Continues with the output of the variant of the selection."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![PinDef::exec_in("exec")]
+    }
+
+    fn properties(&self) -> Vec<PropertyDef> {
+        vec![PropertyDef::new("type", Value::String(String::new()))]
+    }
+}
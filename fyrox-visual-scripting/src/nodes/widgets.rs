@@ -0,0 +1,83 @@
+//! Nodes of blueprints that script a user interface.
+
+use super::{NodeCategory, NodeDefinition, PinDef, PropertyDef};
+use crate::model::{DataType, Value};
+
+/// WidgetEvent node - fires when the named widget reports an event.
+///
+/// The `value` output depends on the event, see
+/// [`Node::widget_event`](crate::model::Node::widget_event).
+pub struct WidgetEventNode;
+
+impl NodeDefinition for WidgetEventNode {
+    fn kind_name(&self) -> &'static str {
+        "WidgetEvent"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Widget Event"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Event
+    }
+
+    fn description(&self) -> &'static str {
+        "Fires when the named widget is clicked, commits text, changes its value, etc."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![PinDef::exec_out("then")]
+    }
+
+    fn properties(&self) -> Vec<PropertyDef> {
+        vec![
+            PropertyDef::new("widget", Value::String(String::new())),
+            PropertyDef::new("event", Value::String("Clicked".to_string())),
+        ]
+    }
+
+    fn is_entry(&self) -> bool {
+        true
+    }
+
+    fn allowed_graphs(&self) -> Vec<&'static str> {
+        vec!["EventGraph"]
+    }
+}
+
+/// SetWidgetProperty node - changes the visibility, text, enabled state or brush of a widget.
+pub struct SetWidgetPropertyNode;
+
+impl NodeDefinition for SetWidgetPropertyNode {
+    fn kind_name(&self) -> &'static str {
+        "SetWidgetProperty"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Set Widget Property"
+    }
+
+    fn category(&self) -> NodeCategory {
+        NodeCategory::Utility
+    }
+
+    fn description(&self) -> &'static str {
+        "Changes a property of the widget with the given name."
+    }
+
+    fn pins(&self) -> Vec<PinDef> {
+        vec![
+            PinDef::exec_in("exec"),
+            PinDef::exec_out("then"),
+            PinDef::input("widget", DataType::String),
+        ]
+    }
+
+    fn properties(&self) -> Vec<PropertyDef> {
+        vec![PropertyDef::new(
+            "property",
+            Value::String("Visible".to_string()),
+        )]
+    }
+}
@@ -0,0 +1,177 @@
+//! Execution statistics of blueprint graphs.
+//!
+//! An [`Interpreter`](crate::Interpreter) with profiling enabled records how often every node ran
+//! and how long it took, along with totals per entry event and the time spent in Rhai. Profiles of
+//! several instances of the same blueprint can be merged, and a set of profiles can be written to
+//! (and read back from) CSV to compare builds.
+
+use crate::model::NodeId;
+use std::{collections::BTreeMap, fmt::Write, time::Duration};
+
+/// Number of runs and cumulative time of a node, an event or Rhai evaluation.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ProfileSample {
+    pub count: u64,
+    pub total: Duration,
+}
+
+impl ProfileSample {
+    pub fn add(&mut self, elapsed: Duration) {
+        self.count += 1;
+        self.total += elapsed;
+    }
+
+    pub fn merge(&mut self, other: &ProfileSample) {
+        self.count += other.count;
+        self.total += other.total;
+    }
+
+    pub fn average(&self) -> Duration {
+        if self.count == 0 {
+            Duration::ZERO
+        } else {
+            self.total.div_f64(self.count as f64)
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BlueprintProfile {
+    /// Per-node statistics. The time of a node is its self time: the time of the nodes it ran
+    /// (for example, interface functions it called on the same actor) is not included.
+    pub nodes: BTreeMap<NodeId, ProfileSample>,
+    /// Per-event statistics, keyed by `BeginPlay`, `Tick`, `ConstructionScript` or
+    /// `Interface::Function`.
+    pub events: BTreeMap<String, ProfileSample>,
+    /// Time spent evaluating Rhai snippets (also included in the time of their nodes).
+    pub rhai: ProfileSample,
+}
+
+impl BlueprintProfile {
+    pub const CSV_HEADER: &'static str = "blueprint,scope,id,count,total_us,average_us";
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty() && self.events.is_empty() && self.rhai.count == 0
+    }
+
+    pub fn merge(&mut self, other: &BlueprintProfile) {
+        for (node, sample) in other.nodes.iter() {
+            self.nodes.entry(*node).or_default().merge(sample);
+        }
+        for (event, sample) in other.events.iter() {
+            self.events.entry(event.clone()).or_default().merge(sample);
+        }
+        self.rhai.merge(&other.rhai);
+    }
+
+    /// Time of the node relative to the slowest node of the profile, in `[0; 1]`.
+    pub fn heat(&self, node: NodeId) -> f32 {
+        let max = self
+            .nodes
+            .values()
+            .map(|sample| sample.total)
+            .max()
+            .unwrap_or_default();
+        match self.nodes.get(&node) {
+            Some(sample) if !max.is_zero() => sample.total.as_secs_f32() / max.as_secs_f32(),
+            _ => 0.0,
+        }
+    }
+
+    /// Appends CSV rows of the profile (without the header) to `csv`.
+    pub fn write_csv(&self, blueprint: &str, csv: &mut String) {
+        let blueprint = csv_field(blueprint);
+        let mut row = |scope: &str, id: &str, sample: &ProfileSample| {
+            let _ = writeln!(
+                csv,
+                "{blueprint},{scope},{},{},{},{}",
+                csv_field(id),
+                sample.count,
+                sample.total.as_micros(),
+                sample.average().as_micros()
+            );
+        };
+        for (event, sample) in self.events.iter() {
+            row("event", event, sample);
+        }
+        for (node, sample) in self.nodes.iter() {
+            row("node", &node.0.to_string(), sample);
+        }
+        if self.rhai.count > 0 {
+            row("rhai", "", &self.rhai);
+        }
+    }
+}
+
+/// Writes profiles keyed by blueprint name as CSV, header included.
+pub fn profiles_to_csv<'a>(
+    profiles: impl IntoIterator<Item = (&'a str, &'a BlueprintProfile)>,
+) -> String {
+    let mut csv = String::new();
+    let _ = writeln!(csv, "{}", BlueprintProfile::CSV_HEADER);
+    for (blueprint, profile) in profiles {
+        profile.write_csv(blueprint, &mut csv);
+    }
+    csv
+}
+
+/// Reads profiles written by [`profiles_to_csv`]. Malformed rows are skipped.
+pub fn profiles_from_csv(csv: &str) -> BTreeMap<String, BlueprintProfile> {
+    let mut profiles = BTreeMap::<String, BlueprintProfile>::new();
+    for line in csv.lines().skip(1) {
+        let fields = split_csv_line(line);
+        let [blueprint, scope, id, count, total_us, _average_us] = fields.as_slice() else {
+            continue;
+        };
+        let (Ok(count), Ok(total_us)) = (count.parse::<u64>(), total_us.parse::<u64>()) else {
+            continue;
+        };
+        let sample = ProfileSample {
+            count,
+            total: Duration::from_micros(total_us),
+        };
+        let profile = profiles.entry(blueprint.clone()).or_default();
+        match scope.as_str() {
+            "event" => profile.events.entry(id.clone()).or_default().merge(&sample),
+            "node" => {
+                if let Ok(node) = id.parse() {
+                    profile
+                        .nodes
+                        .entry(NodeId(node))
+                        .or_default()
+                        .merge(&sample);
+                }
+            }
+            "rhai" => profile.rhai.merge(&sample),
+            _ => {}
+        }
+    }
+    profiles
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        let field = fields.last_mut().unwrap();
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            _ => field.push(c),
+        }
+    }
+    fields
+}
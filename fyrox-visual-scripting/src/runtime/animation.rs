@@ -0,0 +1,123 @@
+use crate::{
+    animation::{AbsmParameterKind, AnimationCommand},
+    compile::CompiledNode,
+    host::BlueprintHost,
+    interpret::{ExecutionEvent, Interpreter, InterpreterOutput},
+    model::{BuiltinNodeKind, NodeId, PinId, Value},
+    runtime::NodeRuntime,
+};
+
+fn string_input(
+    interpreter: &Interpreter,
+    host: &dyn BlueprintHost,
+    node_id: NodeId,
+    node: &CompiledNode,
+    name: &str,
+) -> String {
+    match interpreter.read_input_or_property(host, node_id, node, name) {
+        Some(Value::String(value)) => value,
+        _ => String::new(),
+    }
+}
+
+/// Asks the host to set a parameter of the target's state machine.
+pub struct SetAbsmParameterRuntime;
+
+impl NodeRuntime for SetAbsmParameterRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        host: &dyn BlueprintHost,
+        out: &mut InterpreterOutput,
+        node_id: NodeId,
+        node: &CompiledNode,
+    ) -> Option<PinId> {
+        let target = string_input(interpreter, host, node_id, node, "target");
+        let name = string_input(interpreter, host, node_id, node, "name");
+        let kind = node
+            .property_string("parameter")
+            .and_then(AbsmParameterKind::from_name);
+        let value = interpreter
+            .read_input_or_property(host, node_id, node, "value")
+            .or_else(|| kind.map(|kind| kind.value_type().default_value()));
+
+        if let (Some(kind), Some(value)) = (kind, value) {
+            if !name.is_empty() && value.data_type() == kind.value_type() {
+                out.events
+                    .push(ExecutionEvent::Animation(AnimationCommand::SetParameter {
+                        target,
+                        name,
+                        value,
+                    }));
+            }
+        }
+
+        interpreter.next_exec(node_id, "then")
+    }
+}
+
+/// Pure node: asks the host for the active state of a layer of the target's state machine.
+pub struct GetActiveStateRuntime;
+
+impl NodeRuntime for GetActiveStateRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        _host: &dyn BlueprintHost,
+        _out: &mut InterpreterOutput,
+        node_id: NodeId,
+        _node: &CompiledNode,
+    ) -> Option<PinId> {
+        interpreter.next_exec(node_id, "then")
+    }
+
+    fn evaluate(
+        &self,
+        interpreter: &Interpreter,
+        host: &dyn BlueprintHost,
+        node_id: NodeId,
+        node: &CompiledNode,
+        output: &str,
+    ) -> Option<Value> {
+        if output != "state" {
+            return None;
+        }
+
+        let target = string_input(interpreter, host, node_id, node, "target");
+        let layer = match interpreter.read_input_or_property(host, node_id, node, "layer") {
+            Some(Value::I32(layer)) => usize::try_from(layer).unwrap_or_default(),
+            _ => 0,
+        };
+        let state = host.active_state(&target, layer).unwrap_or_default();
+
+        Some(Value::String(state))
+    }
+}
+
+/// Asks the host to play or stop an animation of the target's animation player.
+pub struct AnimationPlaybackRuntime;
+
+impl NodeRuntime for AnimationPlaybackRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        host: &dyn BlueprintHost,
+        out: &mut InterpreterOutput,
+        node_id: NodeId,
+        node: &CompiledNode,
+    ) -> Option<PinId> {
+        let target = string_input(interpreter, host, node_id, node, "target");
+        let animation = string_input(interpreter, host, node_id, node, "animation");
+
+        if !animation.is_empty() {
+            let command = if node.kind == BuiltinNodeKind::StopAnimation {
+                AnimationCommand::Stop { target, animation }
+            } else {
+                AnimationCommand::Play { target, animation }
+            };
+            out.events.push(ExecutionEvent::Animation(command));
+        }
+
+        interpreter.next_exec(node_id, "then")
+    }
+}
@@ -0,0 +1,89 @@
+use crate::{
+    compile::CompiledNode,
+    host::BlueprintHost,
+    interpret::{AssertionResult, ExecutionEvent, Interpreter, InterpreterOutput},
+    model::{NodeId, PinId, Value},
+    runtime::NodeRuntime,
+};
+
+pub struct AssertRuntime;
+
+impl NodeRuntime for AssertRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        host: &dyn BlueprintHost,
+        out: &mut InterpreterOutput,
+        node_id: NodeId,
+        node: &CompiledNode,
+    ) -> Option<PinId> {
+        let condition = matches!(
+            interpreter.read_input_or_property(host, node_id, node, "condition"),
+            Some(Value::Bool(true))
+        );
+
+        out.events.push(ExecutionEvent::Assertion(AssertionResult {
+            node: node_id,
+            name: assertion_name(interpreter, host, node_id, node, "Assert"),
+            failure: (!condition).then(|| "condition is false".to_string()),
+        }));
+        interpreter.next_exec(node_id, "then")
+    }
+}
+
+pub struct ExpectEqualRuntime;
+
+impl NodeRuntime for ExpectEqualRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        host: &dyn BlueprintHost,
+        out: &mut InterpreterOutput,
+        node_id: NodeId,
+        node: &CompiledNode,
+    ) -> Option<PinId> {
+        let expected = interpreter.read_input_or_property(host, node_id, node, "expected");
+        let actual = interpreter.read_input_or_property(host, node_id, node, "actual");
+
+        // A missing input is a broken test, not a match, even if both inputs are missing.
+        let failure = match (&expected, &actual) {
+            (None, _) => Some("the expected value is missing".to_string()),
+            (_, None) => Some("the actual value is missing".to_string()),
+            _ => (expected != actual).then(|| {
+                format!(
+                    "expected {}, got {}",
+                    describe(expected.as_ref()),
+                    describe(actual.as_ref())
+                )
+            }),
+        };
+
+        out.events.push(ExecutionEvent::Assertion(AssertionResult {
+            node: node_id,
+            name: assertion_name(interpreter, host, node_id, node, "ExpectEqual"),
+            failure,
+        }));
+        interpreter.next_exec(node_id, "then")
+    }
+}
+
+fn assertion_name(
+    interpreter: &Interpreter,
+    host: &dyn BlueprintHost,
+    node_id: NodeId,
+    node: &CompiledNode,
+    kind: &str,
+) -> String {
+    match interpreter.read_input_or_property(host, node_id, node, "message") {
+        Some(Value::String(message)) if !message.is_empty() => message,
+        _ => format!("{kind} #{}", node_id.0),
+    }
+}
+
+fn describe(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::String(v)) => format!("\"{v}\""),
+        Some(Value::Unit) | None => "nothing".to_string(),
+        Some(v) => v.to_string(),
+    }
+}
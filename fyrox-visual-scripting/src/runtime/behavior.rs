@@ -0,0 +1,29 @@
+use crate::{
+    behavior::TaskStatus,
+    compile::CompiledNode,
+    host::BlueprintHost,
+    interpret::{Interpreter, InterpreterOutput},
+    model::{NodeId, PinId, Value},
+    runtime::NodeRuntime,
+};
+
+/// Reports the outcome of the behavior tree task and ends the run of its graph.
+pub struct FinishTaskRuntime;
+
+impl NodeRuntime for FinishTaskRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        host: &dyn BlueprintHost,
+        _out: &mut InterpreterOutput,
+        node_id: NodeId,
+        node: &CompiledNode,
+    ) -> Option<PinId> {
+        let status = match interpreter.read_input_or_property(host, node_id, node, "success") {
+            Some(Value::Bool(true)) => TaskStatus::Success,
+            _ => TaskStatus::Failure,
+        };
+        interpreter.finish_task(status);
+        None
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
     compile::CompiledNode,
+    host::BlueprintHost,
     interpret::{Interpreter, InterpreterOutput},
     model::{NodeId, PinId, Value},
     runtime::NodeRuntime,
@@ -11,12 +12,13 @@ impl NodeRuntime for BranchRuntime {
     fn execute(
         &self,
         interpreter: &mut Interpreter,
+        host: &dyn BlueprintHost,
         _out: &mut InterpreterOutput,
         node_id: NodeId,
         node: &CompiledNode,
     ) -> Option<PinId> {
         let condition = interpreter
-            .read_bool_input(node_id, "condition")
+            .read_bool_input(host, node_id, "condition")
             .or_else(|| {
                 node.properties.get("condition").and_then(|v| match v {
                     Value::Bool(b) => Some(*b),
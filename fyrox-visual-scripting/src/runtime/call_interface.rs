@@ -0,0 +1,64 @@
+use crate::{
+    compile::CompiledNode,
+    host::{is_self_target, BlueprintHost},
+    interpret::{ExecutionEvent, InterfaceCall, Interpreter, InterpreterOutput},
+    model::{DataType, NodeId, PinDirection, PinId},
+    runtime::NodeRuntime,
+};
+use std::collections::BTreeMap;
+
+pub struct CallInterfaceRuntime;
+
+impl NodeRuntime for CallInterfaceRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        host: &dyn BlueprintHost,
+        out: &mut InterpreterOutput,
+        node_id: NodeId,
+        node: &CompiledNode,
+    ) -> Option<PinId> {
+        let target = interpreter
+            .read_string_input(host, node_id, "target")
+            .or_else(|| node.property_string("target").map(str::to_string))
+            .unwrap_or_default();
+        let interface = node.property_string("interface").unwrap_or_default();
+        let function = node.property_string("function").unwrap_or_default();
+
+        // Every data input except the target is a parameter. Prefer linked input, otherwise the
+        // literal property with the same name.
+        let mut args = BTreeMap::new();
+        for (name, (_, direction, ty)) in node.pins.iter() {
+            if *direction != PinDirection::Input || *ty == DataType::Exec || name == "target" {
+                continue;
+            }
+            let value = interpreter
+                .read_value_input(host, node_id, name)
+                .or_else(|| {
+                    node.properties
+                        .get(name)
+                        .filter(|v| v.data_type() == *ty)
+                        .cloned()
+                });
+            if let Some(value) = value {
+                args.insert(name.clone(), value);
+            }
+        }
+
+        if is_self_target(&target) {
+            // Calls to self run immediately; nothing happens if the interface is not implemented.
+            let nested = interpreter.call_interface_with_host(host, interface, function, &args);
+            out.events.extend(nested.events);
+        } else {
+            out.events
+                .push(ExecutionEvent::InterfaceMessage(InterfaceCall {
+                    target,
+                    interface: interface.to_string(),
+                    function: function.to_string(),
+                    args,
+                }));
+        }
+
+        interpreter.next_exec(node_id, "then")
+    }
+}
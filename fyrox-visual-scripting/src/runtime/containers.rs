@@ -0,0 +1,324 @@
+use crate::{
+    compile::CompiledNode,
+    host::BlueprintHost,
+    interpret::{Interpreter, InterpreterOutput},
+    model::{NodeId, PinId, Value},
+    runtime::NodeRuntime,
+};
+
+/// Adds an element to an array or a set, or inserts (replaces) a map entry.
+pub struct ContainerAddRuntime;
+
+impl NodeRuntime for ContainerAddRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        host: &dyn BlueprintHost,
+        _out: &mut InterpreterOutput,
+        node_id: NodeId,
+        node: &CompiledNode,
+    ) -> Option<PinId> {
+        let key = interpreter.read_input_or_property(host, node_id, node, "key");
+        let value = interpreter.read_input_or_property(host, node_id, node, "value");
+
+        if let (Some(target), Some(value)) = (target_mut(interpreter, node_id), value) {
+            match target {
+                Value::Array(_, items) => items.push(value),
+                Value::Set(_, items) if !items.contains(&value) => items.push(value),
+                Value::Map(_, _, entries) => {
+                    if let Some(key) = key {
+                        match entries.iter_mut().find(|(k, _)| *k == key) {
+                            Some((_, existing)) => *existing = value,
+                            None => entries.push((key, value)),
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        interpreter.next_exec(node_id, "then")
+    }
+}
+
+/// Removes an array element by index, a set element or a map entry by key.
+pub struct ContainerRemoveRuntime;
+
+impl NodeRuntime for ContainerRemoveRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        host: &dyn BlueprintHost,
+        _out: &mut InterpreterOutput,
+        node_id: NodeId,
+        node: &CompiledNode,
+    ) -> Option<PinId> {
+        let key = interpreter.read_input_or_property(host, node_id, node, "key");
+
+        let mut removed = false;
+        if let (Some(target), Some(key)) = (target_mut(interpreter, node_id), key) {
+            match (target, key) {
+                (Value::Array(_, items), Value::I32(index)) => {
+                    if let Some(index) = checked_index(index, items.len()) {
+                        items.remove(index);
+                        removed = true;
+                    }
+                }
+                (Value::Set(_, items), key) => {
+                    let len = items.len();
+                    items.retain(|item| *item != key);
+                    removed = items.len() != len;
+                }
+                (Value::Map(_, _, entries), key) => {
+                    let len = entries.len();
+                    entries.retain(|(k, _)| *k != key);
+                    removed = entries.len() != len;
+                }
+                _ => {}
+            }
+        }
+
+        if let Some((pin, _, _)) = node.pin("removed") {
+            interpreter.set_pin_value(pin, Value::Bool(removed));
+        }
+        interpreter.next_exec(node_id, "then")
+    }
+}
+
+/// Removes every element of a container.
+pub struct ContainerClearRuntime;
+
+impl NodeRuntime for ContainerClearRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        _host: &dyn BlueprintHost,
+        _out: &mut InterpreterOutput,
+        node_id: NodeId,
+        _node: &CompiledNode,
+    ) -> Option<PinId> {
+        match target_mut(interpreter, node_id) {
+            Some(Value::Array(_, items)) | Some(Value::Set(_, items)) => items.clear(),
+            Some(Value::Map(_, _, entries)) => entries.clear(),
+            _ => {}
+        }
+        interpreter.next_exec(node_id, "then")
+    }
+}
+
+/// Reads an array element by index, a set element or a map value by key.
+pub struct ContainerGetRuntime;
+
+impl NodeRuntime for ContainerGetRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        _host: &dyn BlueprintHost,
+        _out: &mut InterpreterOutput,
+        node_id: NodeId,
+        _node: &CompiledNode,
+    ) -> Option<PinId> {
+        interpreter.next_exec(node_id, "then")
+    }
+
+    fn evaluate(
+        &self,
+        interpreter: &Interpreter,
+        host: &dyn BlueprintHost,
+        node_id: NodeId,
+        node: &CompiledNode,
+        output: &str,
+    ) -> Option<Value> {
+        let target = interpreter.read_value_input(host, node_id, "target")?;
+        let key = interpreter.read_input_or_property(host, node_id, node, "key")?;
+        let found = match (&target, &key) {
+            (Value::Array(_, items), Value::I32(index)) => {
+                checked_index(*index, items.len()).map(|index| items[index].clone())
+            }
+            (Value::Set(_, items), key) => items.iter().find(|item| *item == key).cloned(),
+            (Value::Map(_, _, entries), key) => entries
+                .iter()
+                .find_map(|(k, v)| (k == key).then(|| v.clone())),
+            _ => None,
+        };
+
+        match output {
+            "found" => Some(Value::Bool(found.is_some())),
+            "value" => {
+                found.or_else(|| target.data_type().element_type().map(|t| t.default_value()))
+            }
+            _ => None,
+        }
+    }
+}
+
+pub struct ContainerLengthRuntime;
+
+impl NodeRuntime for ContainerLengthRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        _host: &dyn BlueprintHost,
+        _out: &mut InterpreterOutput,
+        node_id: NodeId,
+        _node: &CompiledNode,
+    ) -> Option<PinId> {
+        interpreter.next_exec(node_id, "then")
+    }
+
+    fn evaluate(
+        &self,
+        interpreter: &Interpreter,
+        host: &dyn BlueprintHost,
+        node_id: NodeId,
+        _node: &CompiledNode,
+        output: &str,
+    ) -> Option<Value> {
+        if output != "length" {
+            return None;
+        }
+        let len = interpreter
+            .read_value_input(host, node_id, "target")?
+            .len()?;
+        Some(Value::I32(i32::try_from(len).unwrap_or(i32::MAX)))
+    }
+}
+
+/// Whether an array or a set has the element, or a map has the key.
+pub struct ContainerContainsRuntime;
+
+impl NodeRuntime for ContainerContainsRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        _host: &dyn BlueprintHost,
+        _out: &mut InterpreterOutput,
+        node_id: NodeId,
+        _node: &CompiledNode,
+    ) -> Option<PinId> {
+        interpreter.next_exec(node_id, "then")
+    }
+
+    fn evaluate(
+        &self,
+        interpreter: &Interpreter,
+        host: &dyn BlueprintHost,
+        node_id: NodeId,
+        node: &CompiledNode,
+        output: &str,
+    ) -> Option<Value> {
+        if output != "result" {
+            return None;
+        }
+        let target = interpreter.read_value_input(host, node_id, "target")?;
+        let item = interpreter.read_input_or_property(host, node_id, node, "item")?;
+        let contains = match target {
+            Value::Array(_, items) | Value::Set(_, items) => items.contains(&item),
+            Value::Map(_, _, entries) => entries.iter().any(|(k, _)| *k == item),
+            _ => false,
+        };
+        Some(Value::Bool(contains))
+    }
+}
+
+/// Finds the first element equal to the item: its index in an array, the element itself in a set
+/// or its key in a map.
+pub struct ContainerFindRuntime;
+
+impl NodeRuntime for ContainerFindRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        _host: &dyn BlueprintHost,
+        _out: &mut InterpreterOutput,
+        node_id: NodeId,
+        _node: &CompiledNode,
+    ) -> Option<PinId> {
+        interpreter.next_exec(node_id, "then")
+    }
+
+    fn evaluate(
+        &self,
+        interpreter: &Interpreter,
+        host: &dyn BlueprintHost,
+        node_id: NodeId,
+        node: &CompiledNode,
+        output: &str,
+    ) -> Option<Value> {
+        let target = interpreter.read_value_input(host, node_id, "target")?;
+        let item = interpreter.read_input_or_property(host, node_id, node, "item")?;
+        let key = match &target {
+            Value::Array(_, items) => items
+                .iter()
+                .position(|element| *element == item)
+                .map(|index| Value::I32(index as i32)),
+            Value::Set(_, items) => items.contains(&item).then_some(item),
+            Value::Map(_, _, entries) => entries
+                .iter()
+                .find_map(|(k, v)| (*v == item).then(|| k.clone())),
+            _ => None,
+        };
+
+        match output {
+            "found" => Some(Value::Bool(key.is_some())),
+            // Arrays report -1 if the element is missing.
+            "key" => key.or_else(|| match target {
+                Value::Array(..) => Some(Value::I32(-1)),
+                _ => target.data_type().key_type().map(|t| t.default_value()),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Runs `loop_body` for every element of a snapshot of the container, then `completed`.
+pub struct ForEachRuntime;
+
+impl NodeRuntime for ForEachRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        host: &dyn BlueprintHost,
+        out: &mut InterpreterOutput,
+        node_id: NodeId,
+        node: &CompiledNode,
+    ) -> Option<PinId> {
+        let entries = match interpreter.read_value_input(host, node_id, "target") {
+            Some(Value::Array(_, items)) => items
+                .into_iter()
+                .enumerate()
+                .map(|(index, item)| (Value::I32(index as i32), item))
+                .collect(),
+            Some(Value::Set(_, items)) => {
+                items.into_iter().map(|item| (item.clone(), item)).collect()
+            }
+            Some(Value::Map(_, _, entries)) => entries,
+            _ => Vec::new(),
+        };
+
+        let key_pin = node.pin("key").map(|(pin, _, _)| pin);
+        let element_pin = node.pin("element").map(|(pin, _, _)| pin);
+        for (key, element) in entries {
+            if let Some(pin) = key_pin {
+                interpreter.set_pin_value(pin, key);
+            }
+            if let Some(pin) = element_pin {
+                interpreter.set_pin_value(pin, element);
+            }
+            let body = interpreter.run_from_exec_out(host, node_id, "loop_body");
+            out.events.extend(body.events);
+        }
+
+        interpreter.next_exec(node_id, "completed")
+    }
+}
+
+/// The container variable connected to the `target` input.
+fn target_mut(interpreter: &mut Interpreter, node_id: NodeId) -> Option<&mut Value> {
+    let name = interpreter.input_variable(node_id, "target")?;
+    interpreter.variable_mut(&name)
+}
+
+fn checked_index(index: i32, len: usize) -> Option<usize> {
+    usize::try_from(index).ok().filter(|index| *index < len)
+}
@@ -0,0 +1,153 @@
+use crate::{
+    compile::CompiledNode,
+    host::BlueprintHost,
+    interpret::{Interpreter, InterpreterOutput},
+    model::{DataType, ElementType, NodeId, PinId, Value},
+    runtime::NodeRuntime,
+};
+
+fn string_input(
+    interpreter: &Interpreter,
+    host: &dyn BlueprintHost,
+    node_id: NodeId,
+    node: &CompiledNode,
+    name: &str,
+) -> String {
+    match interpreter.read_input_or_property(host, node_id, node, name) {
+        Some(Value::String(value)) => value,
+        _ => String::new(),
+    }
+}
+
+/// Converts a cell to the type of a struct field. Numbers convert to each other and everything
+/// converts to and from text.
+fn cell_value(cell: &Value, ty: DataType) -> Option<Value> {
+    match (cell, ty) {
+        (cell, ty) if cell.data_type() == ty => Some(cell.clone()),
+        (Value::I32(v), DataType::F32) => Some(Value::F32(*v as f32)),
+        (Value::F32(v), DataType::I32) => Some(Value::I32(*v as i32)),
+        (cell, DataType::String) => Some(Value::String(cell.to_string())),
+        (Value::String(text), DataType::Bool) => text.trim().parse().ok().map(Value::Bool),
+        (Value::String(text), DataType::I32) => text.trim().parse().ok().map(Value::I32),
+        (Value::String(text), DataType::F32) => text.trim().parse().ok().map(Value::F32),
+        _ => None,
+    }
+}
+
+/// Builds the value of the `row` output: a struct of the row type, or a map of cell texts.
+/// Fields without a matching column get their defaults.
+fn row_value(interpreter: &Interpreter, ty: DataType, cells: &[(String, Value)]) -> Value {
+    let DataType::Struct(id) = ty else {
+        let entries = cells
+            .iter()
+            .map(|(column, cell)| {
+                (
+                    Value::String(column.clone()),
+                    Value::String(cell.to_string()),
+                )
+            })
+            .collect();
+        return Value::Map(ElementType::String, ElementType::String, entries);
+    };
+    let Some(def) = interpreter.types().struct_def(id) else {
+        return interpreter.types().default_value(ty);
+    };
+    let fields = def
+        .fields
+        .iter()
+        .map(|field| {
+            let value = cells
+                .iter()
+                .find(|(column, _)| *column == field.name)
+                .and_then(|(_, cell)| cell_value(cell, field.data_type))
+                .unwrap_or_else(|| interpreter.types().default_value(field.data_type));
+            (field.name.clone(), value)
+        })
+        .collect();
+    Value::Struct(id, fields)
+}
+
+fn row_type(node: &CompiledNode) -> DataType {
+    node.pin("row")
+        .map(|(_, _, ty)| ty)
+        .unwrap_or(DataType::Map(ElementType::String, ElementType::String))
+}
+
+/// Pure node: asks the host for a row of a data table.
+pub struct GetDataTableRowRuntime;
+
+impl NodeRuntime for GetDataTableRowRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        _host: &dyn BlueprintHost,
+        _out: &mut InterpreterOutput,
+        node_id: NodeId,
+        _node: &CompiledNode,
+    ) -> Option<PinId> {
+        interpreter.next_exec(node_id, "then")
+    }
+
+    fn evaluate(
+        &self,
+        interpreter: &Interpreter,
+        host: &dyn BlueprintHost,
+        node_id: NodeId,
+        node: &CompiledNode,
+        output: &str,
+    ) -> Option<Value> {
+        let table = string_input(interpreter, host, node_id, node, "table");
+        let key = string_input(interpreter, host, node_id, node, "key");
+        let row = host.data_table_row(&table, &key);
+
+        match output {
+            "found" => Some(Value::Bool(row.is_some())),
+            "row" => Some(row_value(
+                interpreter,
+                row_type(node),
+                row.as_deref().unwrap_or_default(),
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Runs `loop_body` for every row of a snapshot of the table, then `completed`.
+pub struct ForEachRowRuntime;
+
+impl NodeRuntime for ForEachRowRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        host: &dyn BlueprintHost,
+        out: &mut InterpreterOutput,
+        node_id: NodeId,
+        node: &CompiledNode,
+    ) -> Option<PinId> {
+        let table = string_input(interpreter, host, node_id, node, "table");
+        let ty = row_type(node);
+        let rows = host
+            .data_table_keys(&table)
+            .into_iter()
+            .filter_map(|key| {
+                let cells = host.data_table_row(&table, &key)?;
+                Some((key, row_value(interpreter, ty, &cells)))
+            })
+            .collect::<Vec<_>>();
+
+        let key_pin = node.pin("key").map(|(pin, _, _)| pin);
+        let row_pin = node.pin("row").map(|(pin, _, _)| pin);
+        for (key, row) in rows {
+            if let Some(pin) = key_pin {
+                interpreter.set_pin_value(pin, Value::String(key));
+            }
+            if let Some(pin) = row_pin {
+                interpreter.set_pin_value(pin, row);
+            }
+            let body = interpreter.run_from_exec_out(host, node_id, "loop_body");
+            out.events.extend(body.events);
+        }
+
+        interpreter.next_exec(node_id, "completed")
+    }
+}
@@ -0,0 +1,50 @@
+use crate::{
+    compile::CompiledNode,
+    host::BlueprintHost,
+    interpret::{Interpreter, InterpreterOutput},
+    model::{NodeId, PinId, Value},
+    runtime::NodeRuntime,
+};
+
+/// Pure node: answers whether the target actor implements the configured interface.
+pub struct DoesImplementInterfaceRuntime;
+
+impl NodeRuntime for DoesImplementInterfaceRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        _host: &dyn BlueprintHost,
+        _out: &mut InterpreterOutput,
+        node_id: NodeId,
+        _node: &CompiledNode,
+    ) -> Option<PinId> {
+        interpreter.next_exec(node_id, "then")
+    }
+
+    fn evaluate(
+        &self,
+        interpreter: &Interpreter,
+        host: &dyn BlueprintHost,
+        node_id: NodeId,
+        node: &CompiledNode,
+        output: &str,
+    ) -> Option<Value> {
+        if output != "result" {
+            return None;
+        }
+
+        let target = interpreter
+            .read_string_input(host, node_id, "target")
+            .or_else(|| node.property_string("target").map(str::to_string))
+            .unwrap_or_default();
+        let interface = node.property_string("interface").unwrap_or_default();
+
+        let implements = if host.is_self(&target) {
+            interpreter.implements_interface(interface)
+        } else {
+            host.does_implement_interface(&target, interface)
+        };
+
+        Some(Value::Bool(implements))
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{
+    compile::CompiledNode,
+    host::BlueprintHost,
+    interpret::{ExecutionEvent, Interpreter, InterpreterOutput},
+    model::{ElementType, NodeId, PinId, Value},
+    runtime::NodeRuntime,
+};
+
+fn string_input(
+    interpreter: &Interpreter,
+    host: &dyn BlueprintHost,
+    node_id: NodeId,
+    node: &CompiledNode,
+    name: &str,
+) -> String {
+    match interpreter.read_input_or_property(host, node_id, node, name) {
+        Some(Value::String(value)) => value,
+        _ => String::new(),
+    }
+}
+
+/// Asks the host to load a scene.
+pub struct LoadSceneRuntime;
+
+impl NodeRuntime for LoadSceneRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        host: &dyn BlueprintHost,
+        out: &mut InterpreterOutput,
+        node_id: NodeId,
+        node: &CompiledNode,
+    ) -> Option<PinId> {
+        let path = string_input(interpreter, host, node_id, node, "path");
+        if !path.is_empty() {
+            out.events.push(ExecutionEvent::LoadScene(path));
+        }
+
+        interpreter.next_exec(node_id, "then")
+    }
+}
+
+/// Pure node: asks the host for the actors that run a blueprint.
+pub struct GetActorsWithBlueprintRuntime;
+
+impl NodeRuntime for GetActorsWithBlueprintRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        _host: &dyn BlueprintHost,
+        _out: &mut InterpreterOutput,
+        node_id: NodeId,
+        _node: &CompiledNode,
+    ) -> Option<PinId> {
+        interpreter.next_exec(node_id, "then")
+    }
+
+    fn evaluate(
+        &self,
+        interpreter: &Interpreter,
+        host: &dyn BlueprintHost,
+        node_id: NodeId,
+        node: &CompiledNode,
+        output: &str,
+    ) -> Option<Value> {
+        if output != "actors" {
+            return None;
+        }
+
+        let blueprint = string_input(interpreter, host, node_id, node, "blueprint");
+        let actors = host
+            .actors_with_blueprint(&blueprint)
+            .into_iter()
+            .map(Value::String)
+            .collect();
+
+        Some(Value::Array(ElementType::String, actors))
+    }
+}
+
+/// Pure node: reads a global variable from the host. Level blueprints have no globals in their
+/// host, they read their own variables.
+pub struct GetGlobalRuntime;
+
+impl NodeRuntime for GetGlobalRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        _host: &dyn BlueprintHost,
+        _out: &mut InterpreterOutput,
+        node_id: NodeId,
+        _node: &CompiledNode,
+    ) -> Option<PinId> {
+        interpreter.next_exec(node_id, "then")
+    }
+
+    fn evaluate(
+        &self,
+        interpreter: &Interpreter,
+        host: &dyn BlueprintHost,
+        _node_id: NodeId,
+        node: &CompiledNode,
+        output: &str,
+    ) -> Option<Value> {
+        let (_, _, data_type) = node.pin(output)?;
+        let name = node.property_string("name").unwrap_or_default();
+        let value = host
+            .global(name)
+            .or_else(|| interpreter.variables().get(name).cloned())
+            .filter(|value| value.data_type() == data_type);
+
+        Some(value.unwrap_or_else(|| interpreter.types().default_value(data_type)))
+    }
+}
+
+/// Reports a change of a global variable, the host applies it.
+pub struct SetGlobalRuntime;
+
+impl NodeRuntime for SetGlobalRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        host: &dyn BlueprintHost,
+        out: &mut InterpreterOutput,
+        node_id: NodeId,
+        node: &CompiledNode,
+    ) -> Option<PinId> {
+        let name = node.property_string("name").unwrap_or_default().to_string();
+        let value = interpreter.read_input_or_property(host, node_id, node, "value");
+        if let Some(value) = value.filter(|_| !name.is_empty()) {
+            out.events.push(ExecutionEvent::SetGlobal(name, value));
+        }
+
+        interpreter.next_exec(node_id, "then")
+    }
+}
@@ -1,21 +1,48 @@
 //! Runtime execution for built-in nodes.
 
+mod animation;
+mod assert;
+mod behavior;
 mod branch;
+mod call_interface;
+mod containers;
+mod data_tables;
+mod level;
+mod does_implement_interface;
 mod passthrough;
 mod print;
 mod rhai_script;
 mod set_variable;
+mod sound;
+mod tags;
+mod timers;
+mod user_types;
+mod widgets;
 
+pub use animation::*;
+pub use assert::*;
+pub use behavior::*;
 pub use branch::*;
+pub use call_interface::*;
+pub use containers::*;
+pub use data_tables::*;
+pub use level::*;
+pub use does_implement_interface::*;
 pub use passthrough::*;
 pub use print::*;
 pub use rhai_script::*;
 pub use set_variable::*;
+pub use sound::*;
+pub use tags::*;
+pub use timers::*;
+pub use user_types::*;
+pub use widgets::*;
 
 use crate::{
     compile::CompiledNode,
+    host::BlueprintHost,
     interpret::{Interpreter, InterpreterOutput},
-    model::{BuiltinNodeKind, NodeId, PinId},
+    model::{BuiltinNodeKind, NodeId, PinId, Value},
 };
 
 pub trait NodeRuntime: Send + Sync {
@@ -23,10 +50,24 @@ pub trait NodeRuntime: Send + Sync {
     fn execute(
         &self,
         interpreter: &mut Interpreter,
+        host: &dyn BlueprintHost,
         out: &mut InterpreterOutput,
         node_id: NodeId,
         node: &CompiledNode,
     ) -> Option<PinId>;
+
+    /// Compute the value of an output pin of a pure node. Called lazily when a connected input is
+    /// read.
+    fn evaluate(
+        &self,
+        _interpreter: &Interpreter,
+        _host: &dyn BlueprintHost,
+        _node_id: NodeId,
+        _node: &CompiledNode,
+        _output: &str,
+    ) -> Option<Value> {
+        None
+    }
 }
 
 static PASSTHROUGH: PassthroughRuntime = PassthroughRuntime;
@@ -34,6 +75,41 @@ static PRINT: PrintRuntime = PrintRuntime;
 static BRANCH: BranchRuntime = BranchRuntime;
 static SET_VARIABLE: SetVariableRuntime = SetVariableRuntime;
 static RHAI_SCRIPT: RhaiScriptRuntime = RhaiScriptRuntime;
+static CALL_INTERFACE: CallInterfaceRuntime = CallInterfaceRuntime;
+static DOES_IMPLEMENT_INTERFACE: DoesImplementInterfaceRuntime = DoesImplementInterfaceRuntime;
+static ASSERT: AssertRuntime = AssertRuntime;
+static EXPECT_EQUAL: ExpectEqualRuntime = ExpectEqualRuntime;
+static CONTAINER_ADD: ContainerAddRuntime = ContainerAddRuntime;
+static CONTAINER_REMOVE: ContainerRemoveRuntime = ContainerRemoveRuntime;
+static CONTAINER_GET: ContainerGetRuntime = ContainerGetRuntime;
+static CONTAINER_LENGTH: ContainerLengthRuntime = ContainerLengthRuntime;
+static CONTAINER_CONTAINS: ContainerContainsRuntime = ContainerContainsRuntime;
+static CONTAINER_FIND: ContainerFindRuntime = ContainerFindRuntime;
+static CONTAINER_CLEAR: ContainerClearRuntime = ContainerClearRuntime;
+static FOR_EACH: ForEachRuntime = ForEachRuntime;
+static MAKE_STRUCT: MakeStructRuntime = MakeStructRuntime;
+static BREAK_STRUCT: BreakStructRuntime = BreakStructRuntime;
+static SWITCH_ON_ENUM: SwitchOnEnumRuntime = SwitchOnEnumRuntime;
+static SET_WIDGET_PROPERTY: SetWidgetPropertyRuntime = SetWidgetPropertyRuntime;
+static SET_ABSM_PARAMETER: SetAbsmParameterRuntime = SetAbsmParameterRuntime;
+static GET_ACTIVE_STATE: GetActiveStateRuntime = GetActiveStateRuntime;
+static ANIMATION_PLAYBACK: AnimationPlaybackRuntime = AnimationPlaybackRuntime;
+static SOUND_PLAYBACK: SoundPlaybackRuntime = SoundPlaybackRuntime;
+static SPAWN_SOUND: SpawnSoundRuntime = SpawnSoundRuntime;
+static SET_SOUND_PROPERTY: SetSoundPropertyRuntime = SetSoundPropertyRuntime;
+static FADE_SOUND: FadeSoundRuntime = FadeSoundRuntime;
+static SET_SOUND_BUS: SetSoundBusRuntime = SetSoundBusRuntime;
+static LOAD_SCENE: LoadSceneRuntime = LoadSceneRuntime;
+static GET_ACTORS_WITH_BLUEPRINT: GetActorsWithBlueprintRuntime = GetActorsWithBlueprintRuntime;
+static GET_GLOBAL: GetGlobalRuntime = GetGlobalRuntime;
+static SET_GLOBAL: SetGlobalRuntime = SetGlobalRuntime;
+static GET_ACTORS_WITH_TAG: GetActorsWithTagRuntime = GetActorsWithTagRuntime;
+static HAS_TAG: HasTagRuntime = HasTagRuntime;
+static CHANGE_TAG: ChangeTagRuntime = ChangeTagRuntime;
+static TIMER: TimerRuntime = TimerRuntime;
+static GET_DATA_TABLE_ROW: GetDataTableRowRuntime = GetDataTableRowRuntime;
+static FOR_EACH_ROW: ForEachRowRuntime = ForEachRowRuntime;
+static FINISH_TASK: FinishTaskRuntime = FinishTaskRuntime;
 
 pub fn runtime_for(kind: BuiltinNodeKind) -> &'static dyn NodeRuntime {
     match kind {
@@ -41,11 +117,54 @@ pub fn runtime_for(kind: BuiltinNodeKind) -> &'static dyn NodeRuntime {
         BuiltinNodeKind::Branch => &BRANCH,
         BuiltinNodeKind::SetVariable => &SET_VARIABLE,
         BuiltinNodeKind::RhaiScript => &RHAI_SCRIPT,
+        BuiltinNodeKind::CallInterface => &CALL_INTERFACE,
+        BuiltinNodeKind::DoesImplementInterface => &DOES_IMPLEMENT_INTERFACE,
+        BuiltinNodeKind::Assert => &ASSERT,
+        BuiltinNodeKind::ExpectEqual => &EXPECT_EQUAL,
+        BuiltinNodeKind::ContainerAdd => &CONTAINER_ADD,
+        BuiltinNodeKind::ContainerRemove => &CONTAINER_REMOVE,
+        BuiltinNodeKind::ContainerGet => &CONTAINER_GET,
+        BuiltinNodeKind::ContainerLength => &CONTAINER_LENGTH,
+        BuiltinNodeKind::ContainerContains => &CONTAINER_CONTAINS,
+        BuiltinNodeKind::ContainerFind => &CONTAINER_FIND,
+        BuiltinNodeKind::ContainerClear => &CONTAINER_CLEAR,
+        BuiltinNodeKind::ForEach => &FOR_EACH,
+        BuiltinNodeKind::MakeStruct => &MAKE_STRUCT,
+        BuiltinNodeKind::BreakStruct => &BREAK_STRUCT,
+        BuiltinNodeKind::SwitchOnEnum => &SWITCH_ON_ENUM,
+        BuiltinNodeKind::SetWidgetProperty => &SET_WIDGET_PROPERTY,
+        BuiltinNodeKind::SetAbsmParameter => &SET_ABSM_PARAMETER,
+        BuiltinNodeKind::GetActiveState => &GET_ACTIVE_STATE,
+        BuiltinNodeKind::PlayAnimation | BuiltinNodeKind::StopAnimation => &ANIMATION_PLAYBACK,
+        BuiltinNodeKind::PlaySound | BuiltinNodeKind::StopSound => &SOUND_PLAYBACK,
+        BuiltinNodeKind::SpawnSound => &SPAWN_SOUND,
+        BuiltinNodeKind::SetSoundProperty => &SET_SOUND_PROPERTY,
+        BuiltinNodeKind::FadeInSound | BuiltinNodeKind::FadeOutSound => &FADE_SOUND,
+        BuiltinNodeKind::SetSoundBus => &SET_SOUND_BUS,
+        BuiltinNodeKind::LoadScene => &LOAD_SCENE,
+        BuiltinNodeKind::GetActorsWithBlueprint => &GET_ACTORS_WITH_BLUEPRINT,
+        BuiltinNodeKind::GetGlobal => &GET_GLOBAL,
+        BuiltinNodeKind::SetGlobal => &SET_GLOBAL,
+        BuiltinNodeKind::GetActorsWithTag => &GET_ACTORS_WITH_TAG,
+        BuiltinNodeKind::HasTag => &HAS_TAG,
+        BuiltinNodeKind::AddTag | BuiltinNodeKind::RemoveTag => &CHANGE_TAG,
+        BuiltinNodeKind::SetTimerByEvent | BuiltinNodeKind::ClearTimer => &TIMER,
+        BuiltinNodeKind::GetDataTableRow => &GET_DATA_TABLE_ROW,
+        BuiltinNodeKind::FinishTask => &FINISH_TASK,
+        BuiltinNodeKind::ForEachRow => &FOR_EACH_ROW,
 
         // These either do not execute directly (entry/pure nodes) or are MVP no-ops.
         BuiltinNodeKind::BeginPlay
         | BuiltinNodeKind::Tick
         | BuiltinNodeKind::ConstructionScript
+        | BuiltinNodeKind::InterfaceEvent
+        | BuiltinNodeKind::WidgetEvent
+        | BuiltinNodeKind::OnAnimationSignal
+        | BuiltinNodeKind::OnSoundFinished
+        | BuiltinNodeKind::OnSceneLoaded
+        | BuiltinNodeKind::OnSceneUnloaded
+        | BuiltinNodeKind::CustomEvent
+        | BuiltinNodeKind::BehaviorTask
         | BuiltinNodeKind::GetVariable
         | BuiltinNodeKind::Self_
         | BuiltinNodeKind::GetActorTransform
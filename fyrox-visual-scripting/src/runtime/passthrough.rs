@@ -1,5 +1,6 @@
 use crate::{
     compile::CompiledNode,
+    host::BlueprintHost,
     interpret::{Interpreter, InterpreterOutput},
     model::{NodeId, PinId},
     runtime::NodeRuntime,
@@ -12,6 +13,7 @@ impl NodeRuntime for PassthroughRuntime {
     fn execute(
         &self,
         interpreter: &mut Interpreter,
+        _host: &dyn BlueprintHost,
         _out: &mut InterpreterOutput,
         node_id: NodeId,
         _node: &CompiledNode,
@@ -1,5 +1,6 @@
 use crate::{
     compile::CompiledNode,
+    host::BlueprintHost,
     interpret::{ExecutionEvent, Interpreter, InterpreterOutput},
     model::{NodeId, PinId, Value},
     runtime::NodeRuntime,
@@ -11,13 +12,14 @@ impl NodeRuntime for PrintRuntime {
     fn execute(
         &self,
         interpreter: &mut Interpreter,
+        host: &dyn BlueprintHost,
         out: &mut InterpreterOutput,
         node_id: NodeId,
         node: &CompiledNode,
     ) -> Option<PinId> {
         // Prefer linked input, otherwise literal property "text".
         let text = interpreter
-            .read_string_input(node_id, "text")
+            .read_string_input(host, node_id, "text")
             .or_else(|| {
                 node.properties.get("text").and_then(|v| match v {
                     Value::String(s) => Some(s.clone()),
@@ -1,5 +1,6 @@
 use crate::{
     compile::CompiledNode,
+    host::BlueprintHost,
     interpret::{ExecutionEvent, Interpreter, InterpreterOutput},
     model::{NodeId, PinId, Value},
     runtime::NodeRuntime,
@@ -11,6 +12,7 @@ impl NodeRuntime for RhaiScriptRuntime {
     fn execute(
         &self,
         interpreter: &mut Interpreter,
+        host: &dyn BlueprintHost,
         out: &mut InterpreterOutput,
         node_id: NodeId,
         node: &CompiledNode,
@@ -18,7 +20,7 @@ impl NodeRuntime for RhaiScriptRuntime {
         // Keep execution exactly as before by delegating to the interpreter's existing helper.
         // This avoids duplicating Rhai bridge glue here.
         let code = interpreter
-            .read_string_input(node_id, "code")
+            .read_string_input(host, node_id, "code")
             .or_else(|| {
                 node.properties.get("code").and_then(|v| match v {
                     Value::String(s) => Some(s.clone()),
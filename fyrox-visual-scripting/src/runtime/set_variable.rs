@@ -1,5 +1,6 @@
 use crate::{
     compile::CompiledNode,
+    host::BlueprintHost,
     interpret::{Interpreter, InterpreterOutput},
     model::{DataType, NodeId, PinId, Value},
     runtime::NodeRuntime,
@@ -11,6 +12,7 @@ impl NodeRuntime for SetVariableRuntime {
     fn execute(
         &self,
         interpreter: &mut Interpreter,
+        host: &dyn BlueprintHost,
         _out: &mut InterpreterOutput,
         node_id: NodeId,
         node: &CompiledNode,
@@ -30,7 +32,7 @@ impl NodeRuntime for SetVariableRuntime {
 
         // Prefer linked input, otherwise literal property "value".
         let value = interpreter
-            .read_value_input(node_id, "value")
+            .read_value_input(host, node_id, "value")
             .or_else(|| {
                 node.properties.get("value").and_then(|v| {
                     if v.data_type() == expected_ty {
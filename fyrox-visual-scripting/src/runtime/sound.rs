@@ -0,0 +1,198 @@
+use crate::{
+    compile::CompiledNode,
+    host::BlueprintHost,
+    interpret::{ExecutionEvent, Interpreter, InterpreterOutput},
+    model::{BuiltinNodeKind, NodeId, PinId, Value},
+    runtime::NodeRuntime,
+    sound::{SoundCommand, SoundFade, SoundProperty},
+};
+
+fn string_input(
+    interpreter: &Interpreter,
+    host: &dyn BlueprintHost,
+    node_id: NodeId,
+    node: &CompiledNode,
+    name: &str,
+) -> String {
+    match interpreter.read_input_or_property(host, node_id, node, name) {
+        Some(Value::String(value)) => value,
+        _ => String::new(),
+    }
+}
+
+fn f32_input(
+    interpreter: &Interpreter,
+    host: &dyn BlueprintHost,
+    node_id: NodeId,
+    node: &CompiledNode,
+    name: &str,
+    default: f32,
+) -> f32 {
+    match interpreter.read_input_or_property(host, node_id, node, name) {
+        Some(Value::F32(value)) => value,
+        _ => default,
+    }
+}
+
+/// Asks the host to play or stop a sound node.
+pub struct SoundPlaybackRuntime;
+
+impl NodeRuntime for SoundPlaybackRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        host: &dyn BlueprintHost,
+        out: &mut InterpreterOutput,
+        node_id: NodeId,
+        node: &CompiledNode,
+    ) -> Option<PinId> {
+        let sound = string_input(interpreter, host, node_id, node, "sound");
+        let command = if node.kind == BuiltinNodeKind::StopSound {
+            SoundCommand::Stop { sound }
+        } else {
+            SoundCommand::Play { sound }
+        };
+        out.events.push(ExecutionEvent::Sound(command));
+
+        interpreter.next_exec(node_id, "then")
+    }
+}
+
+/// Asks the host to play a sound buffer once at a position.
+pub struct SpawnSoundRuntime;
+
+impl NodeRuntime for SpawnSoundRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        host: &dyn BlueprintHost,
+        out: &mut InterpreterOutput,
+        node_id: NodeId,
+        node: &CompiledNode,
+    ) -> Option<PinId> {
+        let buffer = string_input(interpreter, host, node_id, node, "buffer");
+        if !buffer.is_empty() {
+            let coordinate = |name| f32_input(interpreter, host, node_id, node, name, 0.0);
+            let position = [coordinate("x"), coordinate("y"), coordinate("z")];
+            let gain = f32_input(interpreter, host, node_id, node, "gain", 1.0);
+            out.events
+                .push(ExecutionEvent::Sound(SoundCommand::SpawnOneShot {
+                    buffer,
+                    position,
+                    gain,
+                }));
+        }
+
+        interpreter.next_exec(node_id, "then")
+    }
+}
+
+/// Asks the host to change the gain, pitch or looping of a sound node.
+pub struct SetSoundPropertyRuntime;
+
+impl NodeRuntime for SetSoundPropertyRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        host: &dyn BlueprintHost,
+        out: &mut InterpreterOutput,
+        node_id: NodeId,
+        node: &CompiledNode,
+    ) -> Option<PinId> {
+        let sound = string_input(interpreter, host, node_id, node, "sound");
+        let property = node
+            .property_string("property")
+            .and_then(SoundProperty::from_name);
+        let value = interpreter
+            .read_input_or_property(host, node_id, node, "value")
+            .or_else(|| property.map(|p| p.value_type().default_value()));
+
+        if let (Some(property), Some(value)) = (property, value) {
+            if value.data_type() == property.value_type() {
+                out.events
+                    .push(ExecutionEvent::Sound(SoundCommand::SetProperty {
+                        sound,
+                        property,
+                        value,
+                    }));
+            }
+        }
+
+        interpreter.next_exec(node_id, "then")
+    }
+}
+
+/// Starts a fade in or a fade out. Execution continues from `then` right away and from
+/// `completed` when the fade is over.
+pub struct FadeSoundRuntime;
+
+impl NodeRuntime for FadeSoundRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        host: &dyn BlueprintHost,
+        out: &mut InterpreterOutput,
+        node_id: NodeId,
+        node: &CompiledNode,
+    ) -> Option<PinId> {
+        let sound = string_input(interpreter, host, node_id, node, "sound");
+        let duration = f32_input(interpreter, host, node_id, node, "duration", 1.0).max(0.0);
+
+        let fade = if node.kind == BuiltinNodeKind::FadeOutSound {
+            SoundFade {
+                node: node_id,
+                from: host.sound_gain(&sound).unwrap_or(1.0),
+                to: 0.0,
+                sound,
+                duration,
+                elapsed: 0.0,
+                stop_at_end: true,
+            }
+        } else {
+            out.events
+                .push(ExecutionEvent::Sound(SoundCommand::SetProperty {
+                    sound: sound.clone(),
+                    property: SoundProperty::Gain,
+                    value: Value::F32(0.0),
+                }));
+            out.events.push(ExecutionEvent::Sound(SoundCommand::Play {
+                sound: sound.clone(),
+            }));
+            SoundFade {
+                node: node_id,
+                sound,
+                from: 0.0,
+                to: 1.0,
+                duration,
+                elapsed: 0.0,
+                stop_at_end: false,
+            }
+        };
+        interpreter.start_fade(fade);
+
+        interpreter.next_exec(node_id, "then")
+    }
+}
+
+/// Asks the host to route a sound node to an audio bus.
+pub struct SetSoundBusRuntime;
+
+impl NodeRuntime for SetSoundBusRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        host: &dyn BlueprintHost,
+        out: &mut InterpreterOutput,
+        node_id: NodeId,
+        node: &CompiledNode,
+    ) -> Option<PinId> {
+        let sound = string_input(interpreter, host, node_id, node, "sound");
+        let bus = string_input(interpreter, host, node_id, node, "bus");
+        if !bus.is_empty() {
+            out.events
+                .push(ExecutionEvent::Sound(SoundCommand::SetBus { sound, bus }));
+        }
+
+        interpreter.next_exec(node_id, "then")
+    }
+}
@@ -0,0 +1,120 @@
+use crate::{
+    compile::CompiledNode,
+    host::BlueprintHost,
+    interpret::{ExecutionEvent, Interpreter, InterpreterOutput},
+    model::{BuiltinNodeKind, ElementType, NodeId, PinId, Value},
+    runtime::NodeRuntime,
+    tags::TagCommand,
+};
+
+fn string_input(
+    interpreter: &Interpreter,
+    host: &dyn BlueprintHost,
+    node_id: NodeId,
+    node: &CompiledNode,
+    name: &str,
+) -> String {
+    match interpreter.read_input_or_property(host, node_id, node, name) {
+        Some(Value::String(value)) => value,
+        _ => String::new(),
+    }
+}
+
+/// Asks the host to add a tag to an actor or to remove it.
+pub struct ChangeTagRuntime;
+
+impl NodeRuntime for ChangeTagRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        host: &dyn BlueprintHost,
+        out: &mut InterpreterOutput,
+        node_id: NodeId,
+        node: &CompiledNode,
+    ) -> Option<PinId> {
+        let target = string_input(interpreter, host, node_id, node, "target");
+        let tag = string_input(interpreter, host, node_id, node, "tag");
+        if !tag.is_empty() {
+            let command = if node.kind == BuiltinNodeKind::RemoveTag {
+                TagCommand::Remove { target, tag }
+            } else {
+                TagCommand::Add { target, tag }
+            };
+            out.events.push(ExecutionEvent::Tag(command));
+        }
+
+        interpreter.next_exec(node_id, "then")
+    }
+}
+
+/// Pure node: asks the host for the actors with a tag.
+pub struct GetActorsWithTagRuntime;
+
+impl NodeRuntime for GetActorsWithTagRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        _host: &dyn BlueprintHost,
+        _out: &mut InterpreterOutput,
+        node_id: NodeId,
+        _node: &CompiledNode,
+    ) -> Option<PinId> {
+        interpreter.next_exec(node_id, "then")
+    }
+
+    fn evaluate(
+        &self,
+        interpreter: &Interpreter,
+        host: &dyn BlueprintHost,
+        node_id: NodeId,
+        node: &CompiledNode,
+        output: &str,
+    ) -> Option<Value> {
+        if output != "actors" {
+            return None;
+        }
+
+        let tag = string_input(interpreter, host, node_id, node, "tag");
+        let actors = host
+            .actors_with_tag(&tag)
+            .into_iter()
+            .map(Value::String)
+            .collect();
+
+        Some(Value::Array(ElementType::String, actors))
+    }
+}
+
+/// Pure node: asks the host whether an actor has a tag.
+pub struct HasTagRuntime;
+
+impl NodeRuntime for HasTagRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        _host: &dyn BlueprintHost,
+        _out: &mut InterpreterOutput,
+        node_id: NodeId,
+        _node: &CompiledNode,
+    ) -> Option<PinId> {
+        interpreter.next_exec(node_id, "then")
+    }
+
+    fn evaluate(
+        &self,
+        interpreter: &Interpreter,
+        host: &dyn BlueprintHost,
+        node_id: NodeId,
+        node: &CompiledNode,
+        output: &str,
+    ) -> Option<Value> {
+        if output != "result" {
+            return None;
+        }
+
+        let target = string_input(interpreter, host, node_id, node, "target");
+        let tag = string_input(interpreter, host, node_id, node, "tag");
+
+        Some(Value::Bool(host.has_tag(&target, &tag)))
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{
+    compile::CompiledNode,
+    host::BlueprintHost,
+    interpret::{ExecutionEvent, Interpreter, InterpreterOutput},
+    model::{BuiltinNodeKind, NodeId, PinId, Value},
+    runtime::NodeRuntime,
+    timers::TimerCommand,
+};
+
+/// Asks the host to start or clear the timer of a custom event.
+pub struct TimerRuntime;
+
+impl NodeRuntime for TimerRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        host: &dyn BlueprintHost,
+        out: &mut InterpreterOutput,
+        node_id: NodeId,
+        node: &CompiledNode,
+    ) -> Option<PinId> {
+        let input = |name| interpreter.read_input_or_property(host, node_id, node, name);
+        let event = match input("event") {
+            Some(Value::String(event)) => event,
+            _ => String::new(),
+        };
+        if !event.is_empty() {
+            let command = if node.kind == BuiltinNodeKind::ClearTimer {
+                TimerCommand::Clear { event }
+            } else {
+                let duration = match input("time") {
+                    Some(Value::F32(time)) => time,
+                    _ => 0.0,
+                };
+                let looping = matches!(input("looping"), Some(Value::Bool(true)));
+                TimerCommand::Set {
+                    event,
+                    duration,
+                    looping,
+                }
+            };
+            out.events.push(ExecutionEvent::Timer(command));
+        }
+
+        interpreter.next_exec(node_id, "then")
+    }
+}
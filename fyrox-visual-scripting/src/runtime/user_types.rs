@@ -0,0 +1,107 @@
+use crate::{
+    compile::CompiledNode,
+    host::BlueprintHost,
+    interpret::{Interpreter, InterpreterOutput},
+    model::{DataType, NodeId, PinDirection, PinId, Value},
+    runtime::NodeRuntime,
+};
+
+pub struct MakeStructRuntime;
+
+impl NodeRuntime for MakeStructRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        _host: &dyn BlueprintHost,
+        _out: &mut InterpreterOutput,
+        node_id: NodeId,
+        _node: &CompiledNode,
+    ) -> Option<PinId> {
+        interpreter.next_exec(node_id, "then")
+    }
+
+    fn evaluate(
+        &self,
+        interpreter: &Interpreter,
+        host: &dyn BlueprintHost,
+        node_id: NodeId,
+        node: &CompiledNode,
+        output: &str,
+    ) -> Option<Value> {
+        let (_, _, DataType::Struct(id)) = node.pin(output)? else {
+            return None;
+        };
+        let def = interpreter.types().struct_def(id)?;
+        // Unconnected fields get their defaults.
+        let fields = def
+            .fields
+            .iter()
+            .map(|field| {
+                let value = interpreter
+                    .read_value_input(host, node_id, &field.name)
+                    .unwrap_or_else(|| interpreter.types().default_value(field.data_type));
+                (field.name.clone(), value)
+            })
+            .collect();
+        Some(Value::Struct(id, fields))
+    }
+}
+
+pub struct BreakStructRuntime;
+
+impl NodeRuntime for BreakStructRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        _host: &dyn BlueprintHost,
+        _out: &mut InterpreterOutput,
+        node_id: NodeId,
+        _node: &CompiledNode,
+    ) -> Option<PinId> {
+        interpreter.next_exec(node_id, "then")
+    }
+
+    fn evaluate(
+        &self,
+        interpreter: &Interpreter,
+        host: &dyn BlueprintHost,
+        node_id: NodeId,
+        node: &CompiledNode,
+        output: &str,
+    ) -> Option<Value> {
+        let (_, PinDirection::Output, ty) = node.pin(output)? else {
+            return None;
+        };
+        match interpreter.read_value_input(host, node_id, "struct") {
+            Some(value) => value.field(output).cloned(),
+            // An unconnected struct reads as the default struct.
+            None => Some(interpreter.types().default_value(ty)),
+        }
+    }
+}
+
+/// Continues with the exec output named after the variant of the selection.
+pub struct SwitchOnEnumRuntime;
+
+impl NodeRuntime for SwitchOnEnumRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        host: &dyn BlueprintHost,
+        _out: &mut InterpreterOutput,
+        node_id: NodeId,
+        node: &CompiledNode,
+    ) -> Option<PinId> {
+        let variant = match interpreter.read_value_input(host, node_id, "selection") {
+            Some(Value::Enum(_, variant)) => variant,
+            _ => {
+                let (_, _, ty) = node.pin("selection")?;
+                match interpreter.types().default_value(ty) {
+                    Value::Enum(_, variant) => variant,
+                    _ => return None,
+                }
+            }
+        };
+        interpreter.next_exec(node_id, &variant)
+    }
+}
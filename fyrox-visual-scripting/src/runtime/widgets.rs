@@ -0,0 +1,46 @@
+use crate::{
+    compile::CompiledNode,
+    host::BlueprintHost,
+    interpret::{ExecutionEvent, Interpreter, InterpreterOutput},
+    model::{NodeId, PinId, Value},
+    runtime::NodeRuntime,
+    widgets::{WidgetCommand, WidgetProperty},
+};
+
+/// Asks the host to change a property of a widget. Prefers linked inputs, otherwise the literal
+/// properties of the node.
+pub struct SetWidgetPropertyRuntime;
+
+impl NodeRuntime for SetWidgetPropertyRuntime {
+    fn execute(
+        &self,
+        interpreter: &mut Interpreter,
+        host: &dyn BlueprintHost,
+        out: &mut InterpreterOutput,
+        node_id: NodeId,
+        node: &CompiledNode,
+    ) -> Option<PinId> {
+        let widget = match interpreter.read_input_or_property(host, node_id, node, "widget") {
+            Some(Value::String(widget)) => widget,
+            _ => String::new(),
+        };
+        let property = node
+            .property_string("property")
+            .and_then(WidgetProperty::from_name);
+        let value = interpreter
+            .read_input_or_property(host, node_id, node, "value")
+            .or_else(|| property.map(|p| p.value_type().default_value()));
+
+        if let (Some(property), Some(value)) = (property, value) {
+            if !widget.is_empty() && value.data_type() == property.value_type() {
+                out.events.push(ExecutionEvent::Widget(WidgetCommand {
+                    widget,
+                    property,
+                    value,
+                }));
+            }
+        }
+
+        interpreter.next_exec(node_id, "then")
+    }
+}
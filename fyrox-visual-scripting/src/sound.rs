@@ -0,0 +1,99 @@
+//! Sound playback for blueprints.
+//!
+//! Sound nodes are addressed by actor reference (see [`crate::host::is_self_target`]). Changes are
+//! reported as [`crate::ExecutionEvent::Sound`] commands; the host raises `OnSoundFinished` events
+//! with [`crate::Interpreter::dispatch_sound_finished`]. Fades are latent: the interpreter ramps
+//! the gain on every tick and continues from the `completed` output once the fade is over.
+
+use crate::model::{DataType, NodeId, Value};
+
+/// A sound property changed by `SetSoundProperty` nodes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SoundProperty {
+    Gain,
+    Pitch,
+    Looping,
+}
+
+impl SoundProperty {
+    pub const ALL: [SoundProperty; 3] = [
+        SoundProperty::Gain,
+        SoundProperty::Pitch,
+        SoundProperty::Looping,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SoundProperty::Gain => "Gain",
+            SoundProperty::Pitch => "Pitch",
+            SoundProperty::Looping => "Looping",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|property| property.name() == name)
+    }
+
+    pub fn value_type(self) -> DataType {
+        match self {
+            SoundProperty::Gain | SoundProperty::Pitch => DataType::F32,
+            SoundProperty::Looping => DataType::Bool,
+        }
+    }
+}
+
+/// A change requested by a sound node.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SoundCommand {
+    Play {
+        sound: String,
+    },
+    Stop {
+        sound: String,
+    },
+    SetProperty {
+        sound: String,
+        property: SoundProperty,
+        value: Value,
+    },
+    /// Routes the sound to the audio bus with the given name.
+    SetBus {
+        sound: String,
+        bus: String,
+    },
+    /// Plays the sound buffer at the given path once at a world position.
+    SpawnOneShot {
+        buffer: String,
+        position: [f32; 3],
+        gain: f32,
+    },
+}
+
+/// A running fade started by a `FadeInSound` or `FadeOutSound` node.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SoundFade {
+    pub node: NodeId,
+    pub sound: String,
+    pub from: f32,
+    pub to: f32,
+    pub duration: f32,
+    pub elapsed: f32,
+    /// Fade outs stop the sound at the end and restore its gain, so it can be played again.
+    pub stop_at_end: bool,
+}
+
+impl SoundFade {
+    pub fn gain(&self) -> f32 {
+        if self.duration <= 0.0 {
+            return self.to;
+        }
+        let t = (self.elapsed / self.duration).clamp(0.0, 1.0);
+        self.from + (self.to - self.from) * t
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+}
@@ -0,0 +1,14 @@
+//! Actor tags for blueprints.
+//!
+//! Tags are strings attached to scene nodes; the scene graph keeps an index of them, so
+//! `GetActorsWithTag` does not have to walk the scene and works when several actors share a name.
+//! Queries go through [`crate::BlueprintHost`], changes are reported as
+//! [`crate::ExecutionEvent::Tag`] commands.
+
+/// A change requested by `AddTag` or `RemoveTag`. `target` is an actor reference (see
+/// [`crate::host::is_self_target`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TagCommand {
+    Add { target: String, tag: String },
+    Remove { target: String, tag: String },
+}
@@ -0,0 +1,20 @@
+//! Timers for blueprints.
+//!
+//! `SetTimerByEvent` starts an engine timer that runs a `CustomEvent` node of the same graph when
+//! it fires; `ClearTimer` stops it. Timers are identified by the name of their event, so a graph
+//! has at most one timer per event. The nodes report [`crate::ExecutionEvent::Timer`] commands;
+//! the host owns the timers and calls [`crate::Interpreter::dispatch_custom_event`] when one fires.
+
+/// A change requested by a timer node.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimerCommand {
+    /// Starts a timer for the event, replacing the running timer of the event if there is one.
+    Set {
+        event: String,
+        duration: f32,
+        looping: bool,
+    },
+    Clear {
+        event: String,
+    },
+}
@@ -0,0 +1,245 @@
+//! User-defined struct and enum types.
+//!
+//! Types are declared in their own assets and copied into every blueprint that uses them, the same
+//! way interface signatures are. Values refer to their type by [`UserTypeId`], so a blueprint only
+//! needs the definitions to create default values, convert values from JSON or Rhai, and to migrate
+//! values after a definition changed.
+
+use crate::model::{DataType, ParamDef, UserTypeId, Value};
+use serde::{Deserialize, Serialize};
+
+/// Struct types may contain other struct types; deeper values are left empty. This also stops
+/// recursive definitions.
+const MAX_NESTING: usize = 8;
+
+/// A named set of typed fields.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructDef {
+    pub name: String,
+    #[serde(default)]
+    pub fields: Vec<ParamDef>,
+}
+
+impl StructDef {
+    pub fn id(&self) -> UserTypeId {
+        UserTypeId::of(&self.name)
+    }
+
+    pub fn data_type(&self) -> DataType {
+        DataType::Struct(self.id())
+    }
+
+    pub fn field(&self, name: &str) -> Option<&ParamDef> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+}
+
+/// A named list of variants.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnumDef {
+    pub name: String,
+    #[serde(default)]
+    pub variants: Vec<String>,
+}
+
+impl EnumDef {
+    pub fn id(&self) -> UserTypeId {
+        UserTypeId::of(&self.name)
+    }
+
+    pub fn data_type(&self) -> DataType {
+        DataType::Enum(self.id())
+    }
+
+    /// Variants as `Exec` parameters, the exec outputs of a `SwitchOnEnum` node.
+    pub fn params(&self) -> Vec<ParamDef> {
+        self.variants
+            .iter()
+            .map(|variant| ParamDef {
+                name: variant.clone(),
+                data_type: DataType::Exec,
+            })
+            .collect()
+    }
+}
+
+/// Definitions of the user-defined types known to a blueprint.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserTypes {
+    #[serde(default)]
+    pub structs: Vec<StructDef>,
+    #[serde(default)]
+    pub enums: Vec<EnumDef>,
+}
+
+impl UserTypes {
+    pub fn struct_def(&self, id: UserTypeId) -> Option<&StructDef> {
+        self.structs.iter().find(|s| s.id() == id)
+    }
+
+    pub fn enum_def(&self, id: UserTypeId) -> Option<&EnumDef> {
+        self.enums.iter().find(|e| e.id() == id)
+    }
+
+    /// Adds or replaces a struct definition. Returns `false` if it was already defined like this.
+    pub fn define_struct(&mut self, def: StructDef) -> bool {
+        match self.structs.iter_mut().find(|s| s.name == def.name) {
+            Some(existing) if *existing == def => false,
+            Some(existing) => {
+                *existing = def;
+                true
+            }
+            None => {
+                self.structs.push(def);
+                true
+            }
+        }
+    }
+
+    /// Adds or replaces an enum definition. Returns `false` if it was already defined like this.
+    pub fn define_enum(&mut self, def: EnumDef) -> bool {
+        match self.enums.iter_mut().find(|e| e.name == def.name) {
+            Some(existing) if *existing == def => false,
+            Some(existing) => {
+                *existing = def;
+                true
+            }
+            None => {
+                self.enums.push(def);
+                true
+            }
+        }
+    }
+
+    /// Whether the definitions of every struct or enum the type refers to are known.
+    pub fn is_defined(&self, ty: DataType) -> bool {
+        match ty {
+            DataType::Struct(id) => self.struct_def(id).is_some(),
+            DataType::Enum(id) => self.enum_def(id).is_some(),
+            _ => true,
+        }
+    }
+
+    /// Display name of a type, including the names of user-defined types.
+    pub fn type_name(&self, ty: DataType) -> String {
+        let element = |element: crate::model::ElementType| self.type_name(element.data_type());
+        match ty {
+            DataType::Exec => "Exec".to_string(),
+            DataType::Bool => "Bool".to_string(),
+            DataType::I32 => "Int".to_string(),
+            DataType::F32 => "Float".to_string(),
+            DataType::String => "String".to_string(),
+            DataType::Unit => "Unit".to_string(),
+            DataType::Array(e) => format!("Array<{}>", element(e)),
+            DataType::Set(e) => format!("Set<{}>", element(e)),
+            DataType::Map(k, v) => format!("Map<{}, {}>", element(k), element(v)),
+            DataType::Struct(id) => self
+                .struct_def(id)
+                .map(|s| s.name.clone())
+                .unwrap_or_else(|| "Unknown Struct".to_string()),
+            DataType::Enum(id) => self
+                .enum_def(id)
+                .map(|e| e.name.clone())
+                .unwrap_or_else(|| "Unknown Enum".to_string()),
+        }
+    }
+
+    /// Default value of a type: structs get the defaults of their fields and enums their first
+    /// variant.
+    pub fn default_value(&self, ty: DataType) -> Value {
+        self.default_value_nested(ty, 0)
+    }
+
+    fn default_value_nested(&self, ty: DataType, depth: usize) -> Value {
+        match ty {
+            DataType::Struct(id) if depth < MAX_NESTING => match self.struct_def(id) {
+                Some(def) => Value::Struct(
+                    id,
+                    def.fields
+                        .iter()
+                        .map(|f| {
+                            let value = self.default_value_nested(f.data_type, depth + 1);
+                            (f.name.clone(), value)
+                        })
+                        .collect(),
+                ),
+                None => ty.default_value(),
+            },
+            DataType::Enum(id) => match self.enum_def(id).and_then(|e| e.variants.first()) {
+                Some(variant) => Value::Enum(id, variant.clone()),
+                None => ty.default_value(),
+            },
+            _ => ty.default_value(),
+        }
+    }
+
+    /// Brings a value in line with the current definition of its type. Struct fields that kept
+    /// their name and type keep their value, other fields get defaults; enum values of removed
+    /// variants become the first variant.
+    pub fn migrate_value(&self, value: Value) -> Value {
+        match value {
+            Value::Struct(id, mut fields) => {
+                let Some(def) = self.struct_def(id) else {
+                    return Value::Struct(id, fields);
+                };
+                Value::Struct(
+                    id,
+                    def.fields
+                        .iter()
+                        .map(|f| {
+                            let old = fields
+                                .iter()
+                                .position(|(name, _)| *name == f.name)
+                                .map(|i| fields.swap_remove(i).1)
+                                .filter(|old| old.data_type() == f.data_type);
+                            let value = match old {
+                                Some(old) => self.migrate_value(old),
+                                None => self.default_value(f.data_type),
+                            };
+                            (f.name.clone(), value)
+                        })
+                        .collect(),
+                )
+            }
+            Value::Enum(id, variant) => match self.enum_def(id) {
+                Some(def) if !def.variants.contains(&variant) => {
+                    self.default_value(def.data_type())
+                }
+                _ => Value::Enum(id, variant),
+            },
+            value => value,
+        }
+    }
+
+    /// Reads a JSON literal as a value of the given type: structs are objects (missing fields get
+    /// defaults) and enums are variant names. Other types are read by [`Value::from_json`].
+    pub fn value_from_json(&self, json: &serde_json::Value, ty: DataType) -> Option<Value> {
+        match (ty, json) {
+            (DataType::Struct(id), serde_json::Value::Object(object)) => {
+                let def = self.struct_def(id)?;
+                if object.keys().any(|key| def.field(key).is_none()) {
+                    return None;
+                }
+                let fields = def
+                    .fields
+                    .iter()
+                    .map(|f| {
+                        let value = match object.get(&f.name) {
+                            Some(json) => self.value_from_json(json, f.data_type)?,
+                            None => self.default_value(f.data_type),
+                        };
+                        Some((f.name.clone(), value))
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                Some(Value::Struct(id, fields))
+            }
+            (DataType::Enum(id), serde_json::Value::String(variant)) => self
+                .enum_def(id)?
+                .variants
+                .contains(variant)
+                .then(|| Value::Enum(id, variant.clone())),
+            (DataType::Struct(_) | DataType::Enum(_), _) => None,
+            _ => Value::from_json(json, ty),
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! Events and properties of UI widgets, for blueprints that script a user interface instead of an
+//! actor.
+//!
+//! Widgets are addressed by name. The interpreter only knows the names: the host forwards widget
+//! messages with [`crate::Interpreter::dispatch_widget_event`] and applies the
+//! [`crate::ExecutionEvent::Widget`] commands produced by `SetWidgetProperty` nodes.
+
+use crate::model::{DataType, Value};
+
+/// Something a widget reported, handled by `WidgetEvent` nodes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WidgetEvent {
+    /// A button was clicked.
+    Clicked,
+    /// A text box committed its text.
+    TextCommitted,
+    /// A scroll bar (slider) or a numeric field changed its value.
+    ValueChanged,
+    /// A check box was checked or unchecked.
+    Toggled,
+    /// A dropdown list or a list view changed its selection; -1 means nothing is selected.
+    SelectionChanged,
+}
+
+impl WidgetEvent {
+    pub const ALL: [WidgetEvent; 5] = [
+        WidgetEvent::Clicked,
+        WidgetEvent::TextCommitted,
+        WidgetEvent::ValueChanged,
+        WidgetEvent::Toggled,
+        WidgetEvent::SelectionChanged,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            WidgetEvent::Clicked => "Clicked",
+            WidgetEvent::TextCommitted => "TextCommitted",
+            WidgetEvent::ValueChanged => "ValueChanged",
+            WidgetEvent::Toggled => "Toggled",
+            WidgetEvent::SelectionChanged => "SelectionChanged",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|event| event.name() == name)
+    }
+
+    /// Type of the `value` output of the event node, `None` if the event carries no value.
+    pub fn value_type(self) -> Option<DataType> {
+        match self {
+            WidgetEvent::Clicked => None,
+            WidgetEvent::TextCommitted => Some(DataType::String),
+            WidgetEvent::ValueChanged => Some(DataType::F32),
+            WidgetEvent::Toggled => Some(DataType::Bool),
+            WidgetEvent::SelectionChanged => Some(DataType::I32),
+        }
+    }
+}
+
+/// A widget property that `SetWidgetProperty` nodes change.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WidgetProperty {
+    Visible,
+    Text,
+    Enabled,
+    /// Background brush, a `#RRGGBB` or `#RRGGBBAA` color.
+    Brush,
+}
+
+impl WidgetProperty {
+    pub const ALL: [WidgetProperty; 4] = [
+        WidgetProperty::Visible,
+        WidgetProperty::Text,
+        WidgetProperty::Enabled,
+        WidgetProperty::Brush,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            WidgetProperty::Visible => "Visible",
+            WidgetProperty::Text => "Text",
+            WidgetProperty::Enabled => "Enabled",
+            WidgetProperty::Brush => "Brush",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|property| property.name() == name)
+    }
+
+    pub fn value_type(self) -> DataType {
+        match self {
+            WidgetProperty::Visible | WidgetProperty::Enabled => DataType::Bool,
+            WidgetProperty::Text | WidgetProperty::Brush => DataType::String,
+        }
+    }
+}
+
+/// A property change requested by a `SetWidgetProperty` node. The host finds the widget by name;
+/// commands for missing widgets are ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WidgetCommand {
+    pub widget: String,
+    pub property: WidgetProperty,
+    pub value: Value,
+}
+
+/// Parses a `#RRGGBB` or `#RRGGBBAA` color into RGBA components.
+pub fn parse_color(text: &str) -> Option<[u8; 4]> {
+    let hex = text.trim().strip_prefix('#')?;
+    if !matches!(hex.len(), 6 | 8) || !hex.is_ascii() {
+        return None;
+    }
+    let mut rgba = [255; 4];
+    for (i, component) in rgba.iter_mut().enumerate().take(hex.len() / 2) {
+        *component = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(rgba)
+}
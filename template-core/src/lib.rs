@@ -276,6 +276,9 @@ fn main() {{
 
     let mut executor = Executor::new(Some(EventLoop::new().unwrap()));
 
+    // Write a diagnostic bundle to the `crashes` folder if the game panics.
+    executor.set_crash_reporter(Some(Default::default()));
+
     // Dynamic linking with hot reloading.
     #[cfg(feature = "dylib")]
     {{
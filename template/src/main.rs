@@ -21,7 +21,12 @@
 //! Fyrox Project Template Generator command line interface.
 
 use clap::{Parser, Subcommand};
-use std::path::Path;
+use fyrox_resource::io::{PackedArchiveWriter, PackedCompression, PackedResourceIo};
+use std::{
+    fs::File,
+    io::BufWriter,
+    path::{Path, PathBuf},
+};
 
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
@@ -64,6 +69,37 @@ enum Commands {
         #[clap(long, default_value = "false")]
         local: bool,
     },
+    /// Packs asset folders into a single archive. An exported game loads its resources from the
+    /// archive, if it is placed next to the executable as `assets.pak`.
+    Pack {
+        /// Asset folders to pack, relative to the project root.
+        #[clap(default_value = "data")]
+        folders: Vec<PathBuf>,
+
+        #[clap(short, long, default_value = PackedResourceIo::DEFAULT_PATH)]
+        output: PathBuf,
+
+        /// Stores files without compression.
+        #[clap(long, default_value = "false")]
+        no_compression: bool,
+    },
+    /// Lists the files of a packed archive.
+    #[clap(arg_required_else_help = true)]
+    List { archive: PathBuf },
+}
+
+fn pack(
+    folders: &[PathBuf],
+    output: &Path,
+    compression: PackedCompression,
+) -> Result<usize, fyrox_resource::core::io::FileError> {
+    let mut writer = PackedArchiveWriter::new(BufWriter::new(File::create(output)?))?;
+    let mut count = 0;
+    for folder in folders {
+        count += writer.add_directory(Path::new("."), folder, &|_| true, compression)?;
+    }
+    writer.finish()?;
+    Ok(count)
 }
 
 fn main() {
@@ -102,5 +138,31 @@ fn main() {
 
             println!("Fyrox version was successfully set to '{version}'!");
         }
+        Commands::Pack {
+            folders,
+            output,
+            no_compression,
+        } => {
+            let compression = if no_compression {
+                PackedCompression::None
+            } else {
+                PackedCompression::Deflate
+            };
+            let count = pack(&folders, &output, compression).unwrap();
+
+            println!("{count} files were packed into {}!", output.display());
+        }
+        Commands::List { archive } => {
+            let io = PackedResourceIo::open(&archive).unwrap();
+            for (path, entry) in io.entries() {
+                println!(
+                    "{}\t{} bytes\t{} stored\t{:?}",
+                    path.display(),
+                    entry.size,
+                    entry.stored_size,
+                    entry.compression
+                );
+            }
+        }
     }
 }